@@ -399,13 +399,33 @@ parameter_types! {
 	pub const MaxRequests: u32 = 50;
 }
 
+parameter_types! {
+	/// Stake that is reserved when relayer is registered.
+	pub const RelayerStake: Balance = 1_000_000;
+	/// Minimal period of relayer registration.
+	pub const RelayerRegistrationLease: BlockNumber = bp_millau::DAYS;
+	/// Priority boost of bridge transactions, submitted by registered relayers.
+	pub const RegisteredRelayerPriorityBoost: sp_runtime::transaction_validity::TransactionPriority =
+		1_000_000;
+}
+
 impl pallet_bridge_relayers::Config for Runtime {
 	type Event = Event;
 	type Reward = Balance;
 	type PaymentProcedure = bp_relayers::MintReward<pallet_balances::Pallet<Runtime>, AccountId>;
+	type Currency = Balances;
+	type RequiredStake = RelayerStake;
+	type RequiredRegistrationLease = RelayerRegistrationLease;
+	type RegisteredRelayerPriorityBoost = RegisteredRelayerPriorityBoost;
+	type Slash = ();
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	/// Lane, where rewards are moved to during `pallet_bridge_relayers` storage migration.
+	pub const RelayerRewardsMigrationLane: bp_messages::LaneId = crate::rialto_messages::DEFAULT_XCM_LANE_TO_RIALTO;
+}
+
 #[cfg(feature = "runtime-benchmarks")]
 parameter_types! {
 	/// Number of headers to keep in benchmarks.
@@ -428,6 +448,7 @@ parameter_types! {
 
 pub type RialtoGrandpaInstance = ();
 impl pallet_bridge_grandpa::Config for Runtime {
+	type Event = Event;
 	type BridgedChain = bp_rialto::Rialto;
 	type MaxRequests = MaxRequests;
 	type HeadersToKeep = HeadersToKeep;
@@ -437,6 +458,7 @@ impl pallet_bridge_grandpa::Config for Runtime {
 
 pub type WestendGrandpaInstance = pallet_bridge_grandpa::Instance1;
 impl pallet_bridge_grandpa::Config<WestendGrandpaInstance> for Runtime {
+	type Event = Event;
 	type BridgedChain = bp_westend::Westend;
 	type MaxRequests = MaxRequests;
 	type HeadersToKeep = HeadersToKeep;
@@ -452,6 +474,11 @@ parameter_types! {
 		bp_rialto::MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX;
 	pub const MaxUnconfirmedMessagesAtInboundLane: bp_messages::MessageNonce =
 		bp_rialto::MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX;
+	pub const MaxFailedDispatchesAtInboundLane: u32 = 16;
+	pub const MaxFailedDispatchPayloadSize: u32 = 16 * 1024;
+	pub const FailedDispatchLifetime: BlockNumber = bp_millau::DAYS;
+	pub const ConfirmedMessageStatusLifetime: BlockNumber = bp_millau::DAYS;
+	pub const MaxMessageResponseSize: u32 = 128;
 	// `IdentityFee` is used by Millau => we may use weight directly
 	pub const GetDeliveryConfirmationTransactionFee: Balance =
 		bp_millau::MAX_SINGLE_MESSAGE_DELIVERY_CONFIRMATION_TX_WEIGHT as _;
@@ -470,6 +497,11 @@ impl pallet_bridge_messages::Config<WithRialtoMessagesInstance> for Runtime {
 	type MaxMessagesToPruneAtOnce = MaxMessagesToPruneAtOnce;
 	type MaxUnrewardedRelayerEntriesAtInboundLane = MaxUnrewardedRelayerEntriesAtInboundLane;
 	type MaxUnconfirmedMessagesAtInboundLane = MaxUnconfirmedMessagesAtInboundLane;
+	type MaxFailedDispatchesAtInboundLane = MaxFailedDispatchesAtInboundLane;
+	type MaxFailedDispatchPayloadSize = MaxFailedDispatchPayloadSize;
+	type FailedDispatchLifetime = FailedDispatchLifetime;
+	type ConfirmedMessageStatusLifetime = ConfirmedMessageStatusLifetime;
+	type MaxMessageResponseSize = MaxMessageResponseSize;
 
 	type MaximalOutboundPayloadSize = crate::rialto_messages::ToRialtoMaximalOutboundPayloadSize;
 	type OutboundPayload = crate::rialto_messages::ToRialtoMessagePayload;
@@ -489,6 +521,7 @@ impl pallet_bridge_messages::Config<WithRialtoMessagesInstance> for Runtime {
 		>;
 	type OnMessageAccepted = ();
 	type OnDeliveryConfirmed = ();
+	type OnMessageResponse = ();
 
 	type SourceHeaderChain = crate::rialto_messages::Rialto;
 	type MessageDispatch = crate::rialto_messages::FromRialtoMessageDispatch;
@@ -505,6 +538,11 @@ impl pallet_bridge_messages::Config<WithRialtoParachainMessagesInstance> for Run
 	type MaxMessagesToPruneAtOnce = MaxMessagesToPruneAtOnce;
 	type MaxUnrewardedRelayerEntriesAtInboundLane = MaxUnrewardedRelayerEntriesAtInboundLane;
 	type MaxUnconfirmedMessagesAtInboundLane = MaxUnconfirmedMessagesAtInboundLane;
+	type MaxFailedDispatchesAtInboundLane = MaxFailedDispatchesAtInboundLane;
+	type MaxFailedDispatchPayloadSize = MaxFailedDispatchPayloadSize;
+	type FailedDispatchLifetime = FailedDispatchLifetime;
+	type ConfirmedMessageStatusLifetime = ConfirmedMessageStatusLifetime;
+	type MaxMessageResponseSize = MaxMessageResponseSize;
 
 	type MaximalOutboundPayloadSize =
 		crate::rialto_parachain_messages::ToRialtoParachainMaximalOutboundPayloadSize;
@@ -525,6 +563,7 @@ impl pallet_bridge_messages::Config<WithRialtoParachainMessagesInstance> for Run
 		>;
 	type OnMessageAccepted = ();
 	type OnDeliveryConfirmed = ();
+	type OnMessageResponse = ();
 
 	type SourceHeaderChain = crate::rialto_parachain_messages::RialtoParachain;
 	type MessageDispatch = crate::rialto_parachain_messages::FromRialtoParachainMessageDispatch;
@@ -589,11 +628,11 @@ construct_runtime!(
 
 		// Rialto bridge modules.
 		BridgeRelayers: pallet_bridge_relayers::{Pallet, Call, Storage, Event<T>},
-		BridgeRialtoGrandpa: pallet_bridge_grandpa::{Pallet, Call, Storage},
+		BridgeRialtoGrandpa: pallet_bridge_grandpa::{Pallet, Call, Storage, Event<T>},
 		BridgeRialtoMessages: pallet_bridge_messages::{Pallet, Call, Storage, Event<T>, Config<T>},
 
 		// Westend bridge modules.
-		BridgeWestendGrandpa: pallet_bridge_grandpa::<Instance1>::{Pallet, Call, Config<T>, Storage},
+		BridgeWestendGrandpa: pallet_bridge_grandpa::<Instance1>::{Pallet, Call, Config<T>, Storage, Event<T>},
 		BridgeWestendParachains: pallet_bridge_parachains::<Instance1>::{Pallet, Call, Storage, Event<T>},
 
 		// RialtoParachain bridge modules.
@@ -650,6 +689,22 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	(
+		pallet_bridge_relayers::migration::v1::MigrateToV1<Runtime, RelayerRewardsMigrationLane>,
+		pallet_bridge_grandpa::migration::v1::MigrateToV1<Runtime, RialtoGrandpaInstance>,
+		pallet_bridge_grandpa::migration::v1::MigrateToV1<Runtime, WestendGrandpaInstance>,
+		pallet_bridge_messages::migration::v1::MigrateToV1<Runtime, WithRialtoMessagesInstance, ()>,
+		pallet_bridge_messages::migration::v2::MigrateToV2<Runtime, WithRialtoMessagesInstance>,
+		pallet_bridge_messages::migration::v1::MigrateToV1<
+			Runtime,
+			WithRialtoParachainMessagesInstance,
+			(),
+		>,
+		pallet_bridge_messages::migration::v2::MigrateToV2<
+			Runtime,
+			WithRialtoParachainMessagesInstance,
+		>,
+	),
 >;
 
 impl_runtime_apis! {
@@ -860,12 +915,44 @@ impl_runtime_apis! {
 		fn best_finalized() -> Option<HeaderId<bp_rialto::Hash, bp_rialto::BlockNumber>> {
 			BridgeRialtoGrandpa::best_finalized().map(|header| header.id())
 		}
+
+		fn finalized_header_at(
+			number: bp_rialto::BlockNumber,
+		) -> Option<HeaderId<bp_rialto::Hash, bp_rialto::BlockNumber>> {
+			BridgeRialtoGrandpa::finalized_header_at(number).map(|hash| HeaderId(number, hash))
+		}
+
+		fn imported_headers_range(
+			from: bp_rialto::BlockNumber,
+			to: bp_rialto::BlockNumber,
+		) -> Vec<HeaderId<bp_rialto::Hash, bp_rialto::BlockNumber>> {
+			BridgeRialtoGrandpa::imported_headers_range(from, to)
+				.into_iter()
+				.map(|(number, hash)| HeaderId(number, hash))
+				.collect()
+		}
 	}
 
 	impl bp_westend::WestendFinalityApi<Block> for Runtime {
 		fn best_finalized() -> Option<HeaderId<bp_westend::Hash, bp_westend::BlockNumber>> {
 			BridgeWestendGrandpa::best_finalized().map(|header| header.id())
 		}
+
+		fn finalized_header_at(
+			number: bp_westend::BlockNumber,
+		) -> Option<HeaderId<bp_westend::Hash, bp_westend::BlockNumber>> {
+			BridgeWestendGrandpa::finalized_header_at(number).map(|hash| HeaderId(number, hash))
+		}
+
+		fn imported_headers_range(
+			from: bp_westend::BlockNumber,
+			to: bp_westend::BlockNumber,
+		) -> Vec<HeaderId<bp_westend::Hash, bp_westend::BlockNumber>> {
+			BridgeWestendGrandpa::imported_headers_range(from, to)
+				.into_iter()
+				.map(|(number, hash)| HeaderId(number, hash))
+				.collect()
+		}
 	}
 
 	impl bp_westend::WestmintFinalityApi<Block> for Runtime {
@@ -880,6 +967,21 @@ impl_runtime_apis! {
 			let head = bp_westend::Header::decode(&mut &encoded_head.0[..]).ok()?;
 			Some(head.id())
 		}
+
+		fn finalized_header_at(
+			_number: bp_westend::BlockNumber,
+		) -> Option<HeaderId<bp_westend::Hash, bp_westend::BlockNumber>> {
+			// the parachains finality pallet only keeps the best parachain head
+			None
+		}
+
+		fn imported_headers_range(
+			_from: bp_westend::BlockNumber,
+			_to: bp_westend::BlockNumber,
+		) -> Vec<HeaderId<bp_westend::Hash, bp_westend::BlockNumber>> {
+			// the parachains finality pallet only keeps the best parachain head
+			Vec::new()
+		}
 	}
 
 	impl bp_rialto_parachain::RialtoParachainFinalityApi<Block> for Runtime {
@@ -893,6 +995,21 @@ impl_runtime_apis! {
 			let head = bp_rialto_parachain::Header::decode(&mut &encoded_head.0[..]).ok()?;
 			Some(head.id())
 		}
+
+		fn finalized_header_at(
+			_number: bp_rialto::BlockNumber,
+		) -> Option<HeaderId<bp_rialto::Hash, bp_rialto::BlockNumber>> {
+			// the parachains finality pallet only keeps the best parachain head
+			None
+		}
+
+		fn imported_headers_range(
+			_from: bp_rialto::BlockNumber,
+			_to: bp_rialto::BlockNumber,
+		) -> Vec<HeaderId<bp_rialto::Hash, bp_rialto::BlockNumber>> {
+			// the parachains finality pallet only keeps the best parachain head
+			Vec::new()
+		}
 	}
 
 	impl bp_rialto::ToRialtoOutboundLaneApi<Block, Balance, ToRialtoMessagePayload> for Runtime {
@@ -918,6 +1035,10 @@ impl_runtime_apis! {
 				WithRialtoMessagesInstance,
 			>(lane, begin, end)
 		}
+
+		fn lane_operating_mode(lane: bp_messages::LaneId) -> bp_messages::MessagesOperatingMode {
+			pallet_bridge_messages::Pallet::<Runtime, WithRialtoMessagesInstance>::lane_operating_mode(lane)
+		}
 	}
 
 	impl bp_rialto::FromRialtoInboundLaneApi<Block, bp_rialto::Balance> for Runtime {
@@ -955,6 +1076,10 @@ impl_runtime_apis! {
 				WithRialtoParachainMessagesInstance,
 			>(lane, begin, end)
 		}
+
+		fn lane_operating_mode(lane: bp_messages::LaneId) -> bp_messages::MessagesOperatingMode {
+			pallet_bridge_messages::Pallet::<Runtime, WithRialtoParachainMessagesInstance>::lane_operating_mode(lane)
+		}
 	}
 
 	impl bp_rialto_parachain::FromRialtoParachainInboundLaneApi<Block, bp_rialto_parachain::Balance> for Runtime {
@@ -19,9 +19,10 @@
 use crate::{Call, OriginCaller, Runtime};
 
 use bp_messages::{
-	source_chain::{SenderOrigin, TargetHeaderChain},
+	source_chain::{MessagesResponses, SenderOrigin, TargetHeaderChain},
 	target_chain::{ProvedMessages, SourceHeaderChain},
 	InboundLaneData, LaneId, Message, MessageNonce, Parameter as MessagesParameter,
+	VerificationError,
 };
 use bp_runtime::{Chain, ChainId, MILLAU_CHAIN_ID, RIALTO_CHAIN_ID};
 use bridge_runtime_common::messages::{
@@ -81,6 +82,8 @@ pub type FromRialtoMessageDispatch = messages::target::FromBridgedChainMessageDi
 	// 2 XCM instructions is for simple `Trap(42)` program, coming through bridge
 	// (it is prepended with `UniversalOrigin` instruction)
 	frame_support::traits::ConstU64<BASE_XCM_WEIGHT_TWICE>,
+	// failed dispatch may be retried by the account, derived from the message origin
+	crate::xcm_config::SovereignAccountOf,
 >;
 
 /// Maximal outbound payload size of Millau -> Rialto messages.
@@ -237,7 +240,7 @@ impl messages::BridgedChainWithMessages for Rialto {
 }
 
 impl TargetHeaderChain<ToRialtoMessagePayload, bp_millau::AccountId> for Rialto {
-	type Error = &'static str;
+	type Error = VerificationError;
 	// The proof is:
 	// - hash of the header this proof has been created with;
 	// - the storage proof or one or several keys;
@@ -246,11 +249,12 @@ impl TargetHeaderChain<ToRialtoMessagePayload, bp_millau::AccountId> for Rialto
 
 	fn verify_message(payload: &ToRialtoMessagePayload) -> Result<(), Self::Error> {
 		messages::source::verify_chain_message::<WithRialtoMessageBridge>(payload)
+			.map_err(Into::into)
 	}
 
 	fn verify_messages_delivery_proof(
 		proof: Self::MessagesDeliveryProof,
-	) -> Result<(LaneId, InboundLaneData<bp_millau::AccountId>), Self::Error> {
+	) -> Result<(LaneId, InboundLaneData<bp_millau::AccountId>, MessagesResponses), Self::Error> {
 		messages::source::verify_messages_delivery_proof::<
 			WithRialtoMessageBridge,
 			Runtime,
@@ -260,7 +264,7 @@ impl TargetHeaderChain<ToRialtoMessagePayload, bp_millau::AccountId> for Rialto
 }
 
 impl SourceHeaderChain<bp_rialto::Balance> for Rialto {
-	type Error = &'static str;
+	type Error = VerificationError;
 	// The proof is:
 	// - hash of the header this proof has been created with;
 	// - the storage proof or one or several keys;
@@ -327,6 +331,7 @@ mod tests {
 			bp_millau::ADDITIONAL_MESSAGE_BYTE_DELIVERY_WEIGHT,
 			bp_millau::MAX_SINGLE_MESSAGE_DELIVERY_CONFIRMATION_TX_WEIGHT,
 			bp_millau::PAY_INBOUND_DISPATCH_FEE_WEIGHT,
+			crate::MaxMessageResponseSize::get(),
 			DbWeight::get(),
 		);
 
@@ -353,6 +358,7 @@ mod tests {
 			max_incoming_inbound_lane_data_proof_size,
 			bp_millau::MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX,
 			bp_millau::MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX,
+			crate::MaxMessageResponseSize::get(),
 			DbWeight::get(),
 		);
 	}
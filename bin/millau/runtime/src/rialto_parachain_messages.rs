@@ -19,9 +19,10 @@
 use crate::Runtime;
 
 use bp_messages::{
-	source_chain::TargetHeaderChain,
+	source_chain::{MessagesResponses, TargetHeaderChain},
 	target_chain::{ProvedMessages, SourceHeaderChain},
 	InboundLaneData, LaneId, Message, MessageNonce, Parameter as MessagesParameter,
+	VerificationError,
 };
 use bp_polkadot_core::parachains::ParaId;
 use bp_runtime::{Chain, ChainId, MILLAU_CHAIN_ID, RIALTO_PARACHAIN_CHAIN_ID};
@@ -86,6 +87,8 @@ pub type FromRialtoParachainMessageDispatch = messages::target::FromBridgedChain
 	// 2 XCM instructions is for simple `Trap(42)` program, coming through bridge
 	// (it is prepended with `UniversalOrigin` instruction)
 	frame_support::traits::ConstU64<BASE_XCM_WEIGHT_TWICE>,
+	// failed dispatch may be retried by the account, derived from the message origin
+	crate::xcm_config::SovereignAccountOf,
 >;
 
 /// Maximal outbound payload size of Millau -> RialtoParachain messages.
@@ -230,7 +233,7 @@ impl messages::BridgedChainWithMessages for RialtoParachain {
 }
 
 impl TargetHeaderChain<ToRialtoParachainMessagePayload, bp_millau::AccountId> for RialtoParachain {
-	type Error = &'static str;
+	type Error = VerificationError;
 	// The proof is:
 	// - hash of the header this proof has been created with;
 	// - the storage proof or one or several keys;
@@ -239,11 +242,12 @@ impl TargetHeaderChain<ToRialtoParachainMessagePayload, bp_millau::AccountId> fo
 
 	fn verify_message(payload: &ToRialtoParachainMessagePayload) -> Result<(), Self::Error> {
 		messages::source::verify_chain_message::<WithRialtoParachainMessageBridge>(payload)
+			.map_err(Into::into)
 	}
 
 	fn verify_messages_delivery_proof(
 		proof: Self::MessagesDeliveryProof,
-	) -> Result<(LaneId, InboundLaneData<bp_millau::AccountId>), Self::Error> {
+	) -> Result<(LaneId, InboundLaneData<bp_millau::AccountId>, MessagesResponses), Self::Error> {
 		messages::source::verify_messages_delivery_proof_from_parachain::<
 			WithRialtoParachainMessageBridge,
 			bp_rialto_parachain::Header,
@@ -254,7 +258,7 @@ impl TargetHeaderChain<ToRialtoParachainMessagePayload, bp_millau::AccountId> fo
 }
 
 impl SourceHeaderChain<bp_rialto_parachain::Balance> for RialtoParachain {
-	type Error = &'static str;
+	type Error = VerificationError;
 	// The proof is:
 	// - hash of the header this proof has been created with;
 	// - the storage proof or one or several keys;
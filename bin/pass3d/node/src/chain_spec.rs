@@ -17,7 +17,7 @@
 use beefy_primitives::crypto::AuthorityId as BeefyId;
 use polkadot_primitives::v2::{AssignmentId, ValidatorId};
 use pass3d_runtime::{
-	AccountId, BabeConfig, BalancesConfig, BeefyConfig, BridgePass3dtMessagesConfig,
+	AccountId, AssetsConfig, BabeConfig, BalancesConfig, BeefyConfig, BridgePass3dtMessagesConfig,
 	ConfigurationConfig, GenesisConfig, GrandpaConfig, SessionConfig, SessionKeys, Signature,
 	SudoConfig, SystemConfig, WASM_BINARY,
 };
@@ -278,8 +278,14 @@ fn testnet_genesis(
 		paras: Default::default(),
 		bridge_pass3dt_messages: BridgePass3dtMessagesConfig {
 			owner: Some(get_account_id_from_seed::<sr25519::Public>(PASS3DT_MESSAGES_PALLET_OWNER)),
+			opened_lanes: pass3d_runtime::pass3dt_messages::genesis_opened_lanes(),
 			..Default::default()
 		},
+		assets: AssetsConfig {
+			assets: pass3d_runtime::xcm_config::genesis_assets(),
+			metadata: Vec::new(),
+			accounts: Vec::new(),
+		},
 		xcm_pallet: Default::default(),
 	}
 }
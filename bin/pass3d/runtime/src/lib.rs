@@ -29,6 +29,7 @@
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
 pub mod pass3dt_messages;
+pub mod weights;
 pub mod xcm_config;
 
 use crate::pass3dt_messages::{ToPass3dtMessagePayload, WithPass3dtMessageBridge};
@@ -75,6 +76,7 @@ pub use pallet_sudo::Call as SudoCall;
 pub use pallet_timestamp::Call as TimestampCall;
 pub use pallet_xcm::Call as XcmCall;
 
+use bridge_runtime_common::generate_bridge_reject_obsolete_headers_and_messages;
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
 pub use sp_runtime::{Perbill, Permill};
@@ -96,6 +98,9 @@ pub type AccountIndex = u32;
 /// Balance of an account.
 pub type Balance = bp_pass3d::Balance;
 
+/// Identifier of the asset, managed by the assets pallet.
+pub type AssetId = u32;
+
 /// Index of a transaction in the chain.
 pub type Index = bp_pass3d::Index;
 
@@ -349,6 +354,33 @@ impl pallet_balances::Config for Runtime {
 	type ReserveIdentifier = [u8; 8];
 }
 
+parameter_types! {
+	pub const AssetDeposit: Balance = 1_000_000;
+	pub const AssetAccountDeposit: Balance = 1_000;
+	pub const MetadataDepositBase: Balance = 1_000;
+	pub const MetadataDepositPerByte: Balance = 10;
+	pub const ApprovalDeposit: Balance = 1_000;
+	pub const AssetsStringLimit: u32 = 50;
+}
+
+impl pallet_assets::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = AssetId;
+	type Currency = Balances;
+	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+	type AssetDeposit = AssetDeposit;
+	type AssetAccountDeposit = AssetAccountDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ApprovalDeposit;
+	type StringLimit = AssetsStringLimit;
+	type Freezer = ();
+	type Extra = ();
+	// TODO: update me (https://github.com/paritytech/parity-bridges-common/issues/78)
+	type WeightInfo = ();
+}
+
 parameter_types! {
 	pub const TransactionBaseFee: Balance = 0;
 	pub const TransactionByteFee: Balance = 1;
@@ -379,6 +411,13 @@ impl pallet_sudo::Config for Runtime {
 	type Call = Call;
 }
 
+impl pallet_utility::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type PalletsOrigin = OriginCaller;
+	type WeightInfo = ();
+}
+
 impl pallet_session::Config for Runtime {
 	type Event = Event;
 	type ValidatorId = <Self as frame_system::Config>::AccountId;
@@ -396,20 +435,69 @@ impl pallet_authority_discovery::Config for Runtime {
 	type MaxAuthorities = MaxAuthorities;
 }
 
+parameter_types! {
+	/// Stake that is reserved when relayer is registered.
+	pub const RelayerStake: Balance = 1_000_000;
+	/// Minimal period of relayer registration.
+	pub const RelayerRegistrationLease: BlockNumber = bp_pass3d::DAYS;
+	/// Priority boost of bridge transactions, submitted by registered relayers.
+	pub const RegisteredRelayerPriorityBoost: sp_runtime::transaction_validity::TransactionPriority =
+		1_000_000;
+}
+
 impl pallet_bridge_relayers::Config for Runtime {
 	type Event = Event;
 	type Reward = Balance;
 	type PaymentProcedure = bp_relayers::MintReward<pallet_balances::Pallet<Runtime>, AccountId>;
-	type WeightInfo = ();
+	type Currency = Balances;
+	type RequiredStake = RelayerStake;
+	type RequiredRegistrationLease = RelayerRegistrationLease;
+	type RegisteredRelayerPriorityBoost = RegisteredRelayerPriorityBoost;
+	type Slash = ();
+	type WeightInfo = weights::pallet_bridge_relayers::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+	/// Lane, where rewards are moved to during `pallet_bridge_relayers` storage migration.
+	pub const RelayerRewardsMigrationLane: bp_messages::LaneId = crate::pass3dt_messages::DEFAULT_XCM_LANE_TO_PASS3DT;
+}
+
+parameter_types! {
+	/// Pool where relayers are refunded for useful bridge transactions without messages calls.
+	///
+	/// Relayers are refunded for transactions with messages calls at the lane of the call.
+	pub const FinalityRewardsPool: bp_messages::LaneId = bp_relayers::FINALITY_REWARDS_POOL;
+}
+
+/// Signed extension that refunds relayers for bridge transactions that have imported new data.
+pub type RefundBridgedPass3dtTransactions =
+	bridge_runtime_common::refund_relayer_extension::RefundRelayerForBridgeTransactions<
+		Runtime,
+		(BridgePass3dtGrandpa, BridgePass3dtMessages),
+		FinalityRewardsPool,
+	>;
+
 parameter_types! {
 	/// This is a pretty unscientific cap.
 	///
 	/// Note that once this is hit the pallet will essentially throttle incoming requests down to one
 	/// call per block.
 	pub const MaxRequests: u32 = 50;
+}
 
+#[cfg(feature = "runtime-benchmarks")]
+parameter_types! {
+	/// Number of headers to keep in benchmarks.
+	///
+	/// In benchmarks we always populate with full number of `HeadersToKeep` to make sure that
+	/// pruning is taken into account.
+	///
+	/// Note: This is lower than regular value, to speed up benchmarking setup.
+	pub const HeadersToKeep: u32 = 1024;
+}
+
+#[cfg(not(feature = "runtime-benchmarks"))]
+parameter_types! {
 	/// Number of headers to keep.
 	///
 	/// Assuming the worst case of every header being finalized, we will keep headers at least for a
@@ -419,10 +507,21 @@ parameter_types! {
 
 pub type Pass3dtGrandpaInstance = ();
 impl pallet_bridge_grandpa::Config for Runtime {
+	type Event = Event;
+	type BridgedChain = bp_pass3dt::Pass3dt;
+	type MaxRequests = MaxRequests;
+	type HeadersToKeep = HeadersToKeep;
+	type WeightInfo = weights::pallet_bridge_grandpa::WeightInfo<Runtime>;
+}
+
+pub type Pass3dtBeefyInstance = ();
+impl pallet_bridge_beefy::Config for Runtime {
+	type Event = Event;
 	type BridgedChain = bp_pass3dt::Pass3dt;
+	type BridgedMmrLeafExtra = ();
 	type MaxRequests = MaxRequests;
 	type HeadersToKeep = HeadersToKeep;
-	type WeightInfo = (); //pallet_bridge_grandpa::weights::Pass3dtWeight<Runtime>;
+	type WeightInfo = pallet_bridge_beefy::weights::BridgeWeight<Runtime>;
 }
 
 impl pallet_shift_session_manager::Config for Runtime {}
@@ -433,6 +532,11 @@ parameter_types! {
 		bp_pass3dt::MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX;
 	pub const MaxUnconfirmedMessagesAtInboundLane: bp_messages::MessageNonce =
 		bp_pass3dt::MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX;
+	pub const MaxFailedDispatchesAtInboundLane: u32 = 16;
+	pub const MaxFailedDispatchPayloadSize: u32 = 16 * 1024;
+	pub const FailedDispatchLifetime: BlockNumber = bp_pass3d::DAYS;
+	pub const ConfirmedMessageStatusLifetime: BlockNumber = bp_pass3d::DAYS;
+	pub const MaxMessageResponseSize: u32 = 128;
 	// `IdentityFee` is used by Pass3d => we may use weight directly
 	pub const GetDeliveryConfirmationTransactionFee: Balance =
 		bp_pass3d::MAX_SINGLE_MESSAGE_DELIVERY_CONFIRMATION_TX_WEIGHT as _;
@@ -440,22 +544,36 @@ parameter_types! {
 	pub const BridgedChainId: bp_runtime::ChainId = bp_runtime::PASS3DT_CHAIN_ID;
 }
 
+parameter_types! {
+	/// Lanes that are opened in the lane registry during `pallet_bridge_messages` storage migration.
+	pub MessagesMigrationLanes: Vec<(bp_messages::LaneId, bp_messages::LaneConfig<AccountId>)> =
+		pass3dt_messages::genesis_opened_lanes();
+}
+
 /// Instance of the messages pallet used to relay messages to/from Pass3dt chain.
 pub type WithPass3dtMessagesInstance = ();
 
 impl pallet_bridge_messages::Config<WithPass3dtMessagesInstance> for Runtime {
 	type Event = Event;
-	type WeightInfo = (); //pallet_bridge_messages::weights::Pass3dtWeight<Runtime>;
+	type WeightInfo = weights::pallet_bridge_messages::WeightInfo<Runtime>;
 	type Parameter = pass3dt_messages::Pass3dToPass3dtMessagesParameter;
 	type MaxMessagesToPruneAtOnce = MaxMessagesToPruneAtOnce;
 	type MaxUnrewardedRelayerEntriesAtInboundLane = MaxUnrewardedRelayerEntriesAtInboundLane;
 	type MaxUnconfirmedMessagesAtInboundLane = MaxUnconfirmedMessagesAtInboundLane;
+	type MaxFailedDispatchesAtInboundLane = MaxFailedDispatchesAtInboundLane;
+	type MaxFailedDispatchPayloadSize = MaxFailedDispatchPayloadSize;
+	type FailedDispatchLifetime = FailedDispatchLifetime;
+	type ConfirmedMessageStatusLifetime = ConfirmedMessageStatusLifetime;
+	type MaxMessageResponseSize = MaxMessageResponseSize;
 
 	type MaximalOutboundPayloadSize = crate::pass3dt_messages::ToPass3dtMaximalOutboundPayloadSize;
 	type OutboundPayload = crate::pass3dt_messages::ToPass3dtMessagePayload;
 	type OutboundMessageFee = Balance;
 
-	type InboundPayload = crate::pass3dt_messages::FromPass3dtMessagePayload;
+	type InboundPayload = pallet_bridge_token_transfer::RawMessagePayload<
+		crate::pass3dt_messages::FromPass3dtMessagePayload,
+		bp_pass3dt::Balance,
+	>;
 	type InboundMessageFee = bp_pass3dt::Balance;
 	type InboundRelayer = bp_pass3dt::AccountId;
 
@@ -468,13 +586,47 @@ impl pallet_bridge_messages::Config<WithPass3dtMessagesInstance> for Runtime {
 			GetDeliveryConfirmationTransactionFee,
 		>;
 	type OnMessageAccepted = ();
-	type OnDeliveryConfirmed = ();
+	type OnDeliveryConfirmed = BridgeTokenTransfer;
+	type OnMessageResponse = ();
 
 	type SourceHeaderChain = crate::pass3dt_messages::Pass3dt;
-	type MessageDispatch = crate::pass3dt_messages::FromPass3dtMessageDispatch;
+	type MessageDispatch = pallet_bridge_token_transfer::TokenTransferMessageDispatch<
+		Runtime,
+		crate::pass3dt_messages::FromPass3dtMessageDispatch,
+	>;
 	type BridgedChainId = BridgedChainId;
 }
 
+parameter_types! {
+	/// Identifier of the token transfer pallet, used to derive the bridge account.
+	pub const TokenTransferPalletId: frame_support::PalletId =
+		frame_support::PalletId(*b"py/brtkt");
+	/// Lane that is used to transfer tokens to/from Pass3dt chain.
+	pub const TokenTransferLane: bp_messages::LaneId = [0, 0, 0, 2];
+	/// Minimal amount of tokens that may be transferred over the bridge.
+	pub const MinimalTokenTransfer: Balance = 1_000;
+	/// Maximal amount of tokens that may be received from Pass3dt chain within limit period.
+	pub const InboundTokenTransfersLimit: Balance = 1_000_000_000_000;
+	/// Period of inbound token transfers limit.
+	pub const TokenTransfersLimitPeriod: BlockNumber = bp_pass3d::DAYS;
+	/// Maximal number of rejected token transfers that are refunded in a single block.
+	pub const MaxTokenTransferRefundsPerBlock: u32 = 16;
+}
+
+impl pallet_bridge_token_transfer::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type PalletId = TokenTransferPalletId;
+	type BridgedAccountId = bp_pass3dt::AccountId;
+	type MessagesBridge = pallet_bridge_messages::Pallet<Runtime, WithPass3dtMessagesInstance>;
+	type Lane = TokenTransferLane;
+	type MinimalTransfer = MinimalTokenTransfer;
+	type InboundTransfersLimit = InboundTokenTransfersLimit;
+	type LimitPeriod = TokenTransfersLimitPeriod;
+	type MaxRefundsPerBlock = MaxTokenTransferRefundsPerBlock;
+	type WeightInfo = pallet_bridge_token_transfer::weights::BridgeWeight<Runtime>;
+}
+
 construct_runtime!(
 	pub enum Runtime where
 		Block = Block,
@@ -483,6 +635,7 @@ construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Sudo: pallet_sudo::{Pallet, Call, Config<T>, Storage, Event<T>},
+		Utility: pallet_utility::{Pallet, Call, Event},
 
 		// Must be before session.
 		Babe: pallet_babe::{Pallet, Call, Storage, Config, ValidateUnsigned},
@@ -504,8 +657,10 @@ construct_runtime!(
 
 		// Pass3dt bridge modules.
 		BridgeRelayers: pallet_bridge_relayers::{Pallet, Call, Storage, Event<T>},
-		BridgePass3dtGrandpa: pallet_bridge_grandpa::{Pallet, Call, Storage},
+		BridgePass3dtGrandpa: pallet_bridge_grandpa::{Pallet, Call, Storage, Event<T>},
+		BridgePass3dtBeefy: pallet_bridge_beefy::{Pallet, Call, Storage, Event<T>},
 		BridgePass3dtMessages: pallet_bridge_messages::{Pallet, Call, Storage, Event<T>, Config<T>},
+		BridgeTokenTransfer: pallet_bridge_token_transfer::{Pallet, Call, Storage, Event<T>},
 
 		// Parachain modules.
 		// ParachainsOrigin: polkadot_runtime_parachains::origin::{Pallet, Origin},
@@ -526,11 +681,29 @@ construct_runtime!(
 		// Slots: polkadot_runtime_common::slots::{Pallet, Call, Storage, Event<T>},
 		// ParasSudoWrapper: polkadot_runtime_common::paras_sudo_wrapper::{Pallet, Call},
 
+		// Wrapped tokens of the Pass3dt chain.
+		Assets: pallet_assets::{Pallet, Call, Storage, Config<T>, Event<T>},
+
 		// Pallet for sending XCM.
 		XcmPallet: pallet_xcm::{Pallet, Call, Storage, Event<T>, Origin, Config} = 99,
 	}
 );
 
+generate_bridge_reject_obsolete_headers_and_messages! {
+	Call, AccountId,
+	// Relayers
+	relayers: BridgeRelayers,
+	// Grandpa
+	BridgePass3dtGrandpa,
+	//Messages
+	BridgePass3dtMessages,
+	// Batched bridge calls
+	bridge_runtime_common::BatchAllBridgeCalls<
+		Runtime,
+		(BridgePass3dtGrandpa, BridgePass3dtMessages),
+	>
+}
+
 /// The address format for describing accounts.
 pub type Address = sp_runtime::MultiAddress<AccountId, ()>;
 /// Block header type as expected by this runtime.
@@ -551,6 +724,8 @@ pub type SignedExtra = (
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
 	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	BridgeRejectObsoleteHeadersAndMessages,
+	RefundBridgedPass3dtTransactions,
 );
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<Call, SignedExtra>;
@@ -565,6 +740,17 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	(
+		pallet_bridge_relayers::migration::v1::MigrateToV1<Runtime, RelayerRewardsMigrationLane>,
+		pallet_bridge_grandpa::migration::v1::MigrateToV1<Runtime, Pass3dtGrandpaInstance>,
+		pallet_bridge_messages::migration::v1::MigrateToV1<
+			Runtime,
+			WithPass3dtMessagesInstance,
+			MessagesMigrationLanes,
+		>,
+		pallet_bridge_messages::migration::v2::MigrateToV2<Runtime, WithPass3dtMessagesInstance>,
+		xcm_config::CreateGenesisAssets,
+	),
 >;
 
 impl_runtime_apis! {
@@ -697,6 +883,22 @@ impl_runtime_apis! {
 		fn best_finalized() -> Option<HeaderId<bp_pass3dt::Hash, bp_pass3dt::BlockNumber>> {
 			BridgePass3dtGrandpa::best_finalized().map(|header| header.id())
 		}
+
+		fn finalized_header_at(
+			number: bp_pass3dt::BlockNumber,
+		) -> Option<HeaderId<bp_pass3dt::Hash, bp_pass3dt::BlockNumber>> {
+			BridgePass3dtGrandpa::finalized_header_at(number).map(|hash| HeaderId(number, hash))
+		}
+
+		fn imported_headers_range(
+			from: bp_pass3dt::BlockNumber,
+			to: bp_pass3dt::BlockNumber,
+		) -> Vec<HeaderId<bp_pass3dt::Hash, bp_pass3dt::BlockNumber>> {
+			BridgePass3dtGrandpa::imported_headers_range(from, to)
+				.into_iter()
+				.map(|(number, hash)| HeaderId(number, hash))
+				.collect()
+		}
 	}
 
 	impl sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block> for Runtime {
@@ -952,6 +1154,19 @@ impl_runtime_apis! {
 				WithPass3dtMessagesInstance,
 			>(lane, begin, end)
 		}
+
+		fn lane_operating_mode(lane: bp_messages::LaneId) -> bp_messages::MessagesOperatingMode {
+			pallet_bridge_messages::Pallet::<Runtime, WithPass3dtMessagesInstance>::lane_operating_mode(lane)
+		}
+	}
+
+	impl bp_pass3dt::ToPass3dtMessageStatusApi<Block, BlockNumber> for Runtime {
+		fn message_status(
+			lane: bp_messages::LaneId,
+			nonce: bp_messages::MessageNonce,
+		) -> Option<bp_messages::MessageStatus<BlockNumber>> {
+			pallet_bridge_messages::Pallet::<Runtime, WithPass3dtMessagesInstance>::message_status(lane, nonce)
+		}
 	}
 
 	impl bp_pass3dt::FromPass3dtInboundLaneApi<Block, bp_pass3dt::Balance> for Runtime {
@@ -965,11 +1180,446 @@ impl_runtime_apis! {
 			>(lane, messages)
 		}
 	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	impl frame_benchmarking::Benchmark<Block> for Runtime {
+		fn benchmark_metadata(extra: bool) -> (
+			Vec<frame_benchmarking::BenchmarkList>,
+			Vec<frame_support::traits::StorageInfo>,
+		) {
+			use frame_benchmarking::{list_benchmark, Benchmarking, BenchmarkList};
+			use frame_support::traits::StorageInfoTrait;
+
+			use pallet_bridge_messages::benchmarking::Pallet as MessagesBench;
+			use pallet_bridge_token_transfer::benchmarking::Pallet as TokenTransferBench;
+
+			let mut list = Vec::<BenchmarkList>::new();
+
+			list_benchmark!(list, extra, pallet_bridge_messages, MessagesBench::<Runtime, WithPass3dtMessagesInstance>);
+			list_benchmark!(list, extra, pallet_bridge_grandpa, BridgePass3dtGrandpa);
+			list_benchmark!(list, extra, pallet_bridge_beefy, BridgePass3dtBeefy);
+			list_benchmark!(list, extra, pallet_bridge_relayers, BridgeRelayers);
+			list_benchmark!(list, extra, pallet_bridge_token_transfer, TokenTransferBench::<Runtime>);
+
+			let storage_info = AllPalletsWithSystem::storage_info();
+
+			return (list, storage_info)
+		}
+
+		fn dispatch_benchmark(
+			config: frame_benchmarking::BenchmarkConfig,
+		) -> Result<Vec<frame_benchmarking::BenchmarkBatch>, sp_runtime::RuntimeString> {
+			use frame_benchmarking::{Benchmarking, BenchmarkBatch, TrackedStorageKey, add_benchmark};
+
+			let whitelist: Vec<TrackedStorageKey> = vec![
+				// Block Number
+				hex_literal::hex!("26aa394eea5630e07c48ae0c9558cef702a5c1b19ab7a04f536c519aca4983ac").to_vec().into(),
+				// Execution Phase
+				hex_literal::hex!("26aa394eea5630e07c48ae0c9558cef7ff553b5a9862a516939d82b3d3d8661a").to_vec().into(),
+				// Event Count
+				hex_literal::hex!("26aa394eea5630e07c48ae0c9558cef70a98fdbe9ce6c55837576c60c7af3850").to_vec().into(),
+				// System Events
+				hex_literal::hex!("26aa394eea5630e07c48ae0c9558cef780d41e5e16056765bc8461851072c9d7").to_vec().into(),
+				// Caller 0 Account
+				hex_literal::hex!("26aa394eea5630e07c48ae0c9558cef7b99d880ec681799c0cf30e8886371da946c154ffd9992e395af90b5b13cc6f295c77033fce8a9045824a6690bbf99c6db269502f0a8d1d2a008542d5690a0749").to_vec().into(),
+			];
+
+			let mut batches = Vec::<BenchmarkBatch>::new();
+			let params = (&config, &whitelist);
+
+			use bridge_runtime_common::messages_benchmarking::{prepare_message_delivery_proof, prepare_message_proof, prepare_outbound_message};
+			use bridge_runtime_common::messages;
+			use pallet_bridge_messages::benchmarking::{
+				Pallet as MessagesBench,
+				Config as MessagesConfig,
+				MessageDeliveryProofParams,
+				MessageParams,
+				MessageProofParams,
+			};
+			use pallet_bridge_token_transfer::benchmarking::{
+				Pallet as TokenTransferBench,
+				Config as TokenTransferConfig,
+			};
+
+			impl MessagesConfig<WithPass3dtMessagesInstance> for Runtime {
+				fn maximal_message_size() -> u32 {
+					messages::source::maximal_message_size::<WithPass3dtMessageBridge>()
+				}
+
+				fn bridged_relayer_id() -> Self::InboundRelayer {
+					[0u8; 32].into()
+				}
+
+				fn account_balance(account: &Self::AccountId) -> Self::OutboundMessageFee {
+					pallet_balances::Pallet::<Runtime>::free_balance(account)
+				}
+
+				fn endow_account(account: &Self::AccountId) {
+					pallet_balances::Pallet::<Runtime>::make_free_balance_be(
+						account,
+						Balance::MAX / 100,
+					);
+				}
+
+				fn prepare_outbound_message(
+					params: MessageParams<Self::AccountId>,
+				) -> (pass3dt_messages::ToPass3dtMessagePayload, Balance) {
+					(prepare_outbound_message::<WithPass3dtMessageBridge>(params), Self::message_fee())
+				}
+
+				fn prepare_message_proof(
+					params: MessageProofParams,
+				) -> (pass3dt_messages::FromPass3dtMessagesProof, Weight) {
+					prepare_message_proof::<Runtime, (), (), WithPass3dtMessageBridge, bp_pass3dt::Header, bp_pass3dt::Hasher>(
+						params,
+					)
+				}
+
+				fn prepare_message_delivery_proof(
+					params: MessageDeliveryProofParams<Self::AccountId>,
+				) -> pass3dt_messages::ToPass3dtMessagesDeliveryProof {
+					prepare_message_delivery_proof::<Runtime, (), WithPass3dtMessageBridge, bp_pass3dt::Header, bp_pass3dt::Hasher>(
+						params,
+					)
+				}
+
+				fn is_message_dispatched(_nonce: bp_messages::MessageNonce) -> bool {
+					true
+				}
+			}
+
+			impl TokenTransferConfig for Runtime {
+				fn message_fee() -> Balance {
+					<Runtime as MessagesConfig<WithPass3dtMessagesInstance>>::message_fee()
+				}
+			}
+
+			add_benchmark!(
+				params,
+				batches,
+				pallet_bridge_messages,
+				MessagesBench::<Runtime, WithPass3dtMessagesInstance>
+			);
+			add_benchmark!(params, batches, pallet_bridge_grandpa, BridgePass3dtGrandpa);
+			add_benchmark!(params, batches, pallet_bridge_beefy, BridgePass3dtBeefy);
+			add_benchmark!(params, batches, pallet_bridge_relayers, BridgeRelayers);
+			add_benchmark!(
+				params,
+				batches,
+				pallet_bridge_token_transfer,
+				TokenTransferBench::<Runtime>
+			);
+
+			Ok(batches)
+		}
+	}
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use bp_messages::{InboundLaneData, OutboundLaneData, UnrewardedRelayersState};
+	use bridge_runtime_common::messages::{
+		source::FromBridgedChainMessagesDeliveryProof, target::FromBridgedChainMessagesProof,
+	};
+	use frame_support::weights::{DispatchInfo, PostDispatchInfo};
+	use sp_runtime::{
+		traits::{Header as HeaderT, SignedExtension},
+		transaction_validity::InvalidTransaction,
+		DispatchError, DispatchResult,
+	};
+
+	const RELAYER: [u8; 32] = [1u8; 32];
+	const MESSAGES_LANE: bp_messages::LaneId = crate::pass3dt_messages::DEFAULT_XCM_LANE_TO_PASS3DT;
+
+	fn finality_call(number: bp_pass3dt::BlockNumber) -> Call {
+		let header: bp_pass3dt::Header = bp_test_utils::test_header(number);
+		Call::BridgePass3dtGrandpa(BridgeGrandpaCall::submit_finality_proof {
+			finality_target: Box::new(header.clone()),
+			justification: bp_test_utils::make_default_justification(&header),
+		})
+	}
+
+	fn import_finality(number: bp_pass3dt::BlockNumber) {
+		let header: bp_pass3dt::Header = bp_test_utils::test_header(number);
+		pallet_bridge_grandpa::BestFinalized::<Runtime, Pass3dtGrandpaInstance>::put((
+			number,
+			header.hash(),
+		));
+	}
+
+	fn message_delivery_call(nonces_end: bp_messages::MessageNonce) -> Call {
+		message_delivery_call_at_lane(MESSAGES_LANE, nonces_end)
+	}
+
+	fn message_delivery_call_at_lane(
+		lane: bp_messages::LaneId,
+		nonces_end: bp_messages::MessageNonce,
+	) -> Call {
+		Call::BridgePass3dtMessages(MessagesCall::receive_messages_proof {
+			relayer_id_at_bridged_chain: [0u8; 32].into(),
+			messages_count: 1,
+			dispatch_weight: 0,
+			proof: FromBridgedChainMessagesProof {
+				bridged_header_hash: Default::default(),
+				storage_proof: vec![],
+				lane,
+				nonces_start: nonces_end,
+				nonces_end,
+			},
+		})
+	}
+
+	fn message_confirmation_call(last_delivered_nonce: bp_messages::MessageNonce) -> Call {
+		Call::BridgePass3dtMessages(MessagesCall::receive_messages_delivery_proof {
+			proof: FromBridgedChainMessagesDeliveryProof {
+				bridged_header_hash: Default::default(),
+				storage_proof: vec![],
+				lane: MESSAGES_LANE,
+			},
+			relayers_state: UnrewardedRelayersState { last_delivered_nonce, ..Default::default() },
+		})
+	}
+
+	fn batch_call(calls: Vec<Call>) -> Call {
+		Call::Utility(pallet_utility::Call::batch_all { calls })
+	}
+
+	fn deliver_messages(nonce: bp_messages::MessageNonce) {
+		deliver_messages_at_lane(MESSAGES_LANE, nonce)
+	}
+
+	fn deliver_messages_at_lane(lane: bp_messages::LaneId, nonce: bp_messages::MessageNonce) {
+		pallet_bridge_messages::InboundLanes::<Runtime, WithPass3dtMessagesInstance>::insert(
+			lane,
+			InboundLaneData { relayers: Default::default(), last_confirmed_nonce: nonce },
+		);
+	}
+
+	fn confirm_messages(nonce: bp_messages::MessageNonce) {
+		pallet_bridge_messages::OutboundLanes::<Runtime, WithPass3dtMessagesInstance>::insert(
+			MESSAGES_LANE,
+			OutboundLaneData {
+				oldest_unpruned_nonce: 1,
+				latest_received_nonce: nonce,
+				latest_generated_nonce: nonce,
+			},
+		);
+	}
+
+	fn dispatch_info() -> DispatchInfo {
+		DispatchInfo { weight: 1_000_000, ..Default::default() }
+	}
+
+	fn run_refund_extension(call: Call, import: impl FnOnce(), result: DispatchResult) {
+		let pre = RefundBridgedPass3dtTransactions::default()
+			.pre_dispatch(&RELAYER.into(), &call, &dispatch_info(), 100)
+			.unwrap();
+		import();
+		RefundBridgedPass3dtTransactions::post_dispatch(
+			Some(pre),
+			&dispatch_info(),
+			&PostDispatchInfo::default(),
+			100,
+			&result,
+		)
+		.unwrap();
+	}
+
+	fn relayer_reward() -> Option<Balance> {
+		relayer_reward_at_lane(MESSAGES_LANE)
+	}
+
+	fn relayer_reward_at_lane(lane: bp_messages::LaneId) -> Option<Balance> {
+		BridgeRelayers::relayer_reward(lane, &RELAYER.into())
+	}
+
+	fn expected_reward() -> Balance {
+		TransactionPayment::compute_actual_fee(
+			100,
+			&dispatch_info(),
+			&PostDispatchInfo::default(),
+			0,
+		)
+	}
+
+	#[test]
+	fn relayer_is_refunded_for_useful_bridge_call() {
+		sp_io::TestExternalities::new(Default::default()).execute_with(|| {
+			run_refund_extension(message_delivery_call(10), || deliver_messages(10), Ok(()));
+			assert_eq!(relayer_reward(), Some(expected_reward()));
+		});
+	}
+
+	#[test]
+	fn relayer_is_refunded_for_useful_batch_of_bridge_calls() {
+		sp_io::TestExternalities::new(Default::default()).execute_with(|| {
+			run_refund_extension(
+				batch_call(vec![message_delivery_call(10), message_confirmation_call(10)]),
+				|| {
+					deliver_messages(10);
+					confirm_messages(10);
+				},
+				Ok(()),
+			);
+			assert_eq!(relayer_reward(), Some(expected_reward()));
+		});
+	}
+
+	#[test]
+	fn relayer_is_not_refunded_if_some_batch_call_has_not_imported_data() {
+		sp_io::TestExternalities::new(Default::default()).execute_with(|| {
+			run_refund_extension(
+				batch_call(vec![message_delivery_call(10), message_confirmation_call(10)]),
+				|| deliver_messages(10),
+				Ok(()),
+			);
+			assert_eq!(relayer_reward(), None);
+		});
+	}
+
+	#[test]
+	fn relayer_is_not_refunded_for_batch_with_non_bridge_calls() {
+		sp_io::TestExternalities::new(Default::default()).execute_with(|| {
+			run_refund_extension(
+				batch_call(vec![
+					message_delivery_call(10),
+					Call::System(SystemCall::remark { remark: vec![] }),
+				]),
+				|| deliver_messages(10),
+				Ok(()),
+			);
+			assert_eq!(relayer_reward(), None);
+		});
+	}
+
+	#[test]
+	fn relayer_is_not_refunded_for_obsolete_bridge_call() {
+		sp_io::TestExternalities::new(Default::default()).execute_with(|| {
+			deliver_messages(10);
+			run_refund_extension(message_delivery_call(10), || (), Ok(()));
+			assert_eq!(relayer_reward(), None);
+		});
+	}
+
+	#[test]
+	fn relayer_is_refunded_at_the_lane_of_messages_call() {
+		sp_io::TestExternalities::new(Default::default()).execute_with(|| {
+			run_refund_extension(
+				message_delivery_call_at_lane(TokenTransferLane::get(), 10),
+				|| deliver_messages_at_lane(TokenTransferLane::get(), 10),
+				Ok(()),
+			);
+			assert_eq!(relayer_reward_at_lane(TokenTransferLane::get()), Some(expected_reward()));
+			assert_eq!(relayer_reward(), None);
+		});
+	}
+
+	#[test]
+	fn relayer_is_refunded_for_finality_call_from_separate_pool() {
+		sp_io::TestExternalities::new(Default::default()).execute_with(|| {
+			import_finality(0);
+			run_refund_extension(message_delivery_call(10), || deliver_messages(10), Ok(()));
+			run_refund_extension(finality_call(10), || import_finality(10), Ok(()));
+
+			// messages relayer reward is not affected by the finality refund
+			assert_eq!(relayer_reward(), Some(expected_reward()));
+			assert_eq!(relayer_reward_at_lane(FinalityRewardsPool::get()), Some(expected_reward()));
+		});
+	}
+
+	#[test]
+	fn batch_with_duplicate_bridge_calls_is_rejected() {
+		sp_io::TestExternalities::new(Default::default()).execute_with(|| {
+			let call = batch_call(vec![message_delivery_call(10), message_delivery_call(10)]);
+			assert_eq!(
+				RefundBridgedPass3dtTransactions::default().validate(
+					&RELAYER.into(),
+					&call,
+					&dispatch_info(),
+					100
+				),
+				Err(InvalidTransaction::Call.into()),
+			);
+			assert_eq!(
+				RefundBridgedPass3dtTransactions::default()
+					.pre_dispatch(&RELAYER.into(), &call, &dispatch_info(), 100)
+					.map(drop),
+				Err(InvalidTransaction::Call.into()),
+			);
+		});
+	}
+
+	#[test]
+	fn batch_with_overlapping_bridge_calls_is_rejected() {
+		sp_io::TestExternalities::new(Default::default()).execute_with(|| {
+			let mut overlapping_call = message_delivery_call(10);
+			if let Call::BridgePass3dtMessages(MessagesCall::receive_messages_proof {
+				ref mut proof,
+				..
+			}) = overlapping_call
+			{
+				proof.nonces_start = 5;
+				proof.nonces_end = 15;
+			}
+			let call = batch_call(vec![message_delivery_call(10), overlapping_call]);
+			assert_eq!(
+				RefundBridgedPass3dtTransactions::default()
+					.pre_dispatch(&RELAYER.into(), &call, &dispatch_info(), 100)
+					.map(drop),
+				Err(InvalidTransaction::Call.into()),
+			);
+
+			// calls that are bringing messages of different lanes are not overlapping
+			let call = batch_call(vec![
+				message_delivery_call(10),
+				message_delivery_call_at_lane(TokenTransferLane::get(), 10),
+			]);
+			assert!(RefundBridgedPass3dtTransactions::default()
+				.pre_dispatch(&RELAYER.into(), &call, &dispatch_info(), 100)
+				.is_ok());
+		});
+	}
+
+	#[test]
+	fn batch_with_obsolete_bridge_call_is_rejected() {
+		sp_io::TestExternalities::new(Default::default()).execute_with(|| {
+			deliver_messages(10);
+			let validate = |call| {
+				BridgeRejectObsoleteHeadersAndMessages.validate(
+					&RELAYER.into(),
+					&call,
+					&dispatch_info(),
+					100,
+				)
+			};
+
+			assert!(validate(batch_call(vec![
+				message_delivery_call_at_lane(TokenTransferLane::get(), 10),
+				message_delivery_call(11),
+			]))
+			.is_ok());
+			assert_eq!(
+				validate(batch_call(vec![
+					message_delivery_call_at_lane(TokenTransferLane::get(), 10),
+					message_delivery_call(10),
+				])),
+				Err(InvalidTransaction::Stale.into()),
+			);
+		});
+	}
+
+	#[test]
+	fn relayer_is_not_refunded_for_failed_bridge_call() {
+		sp_io::TestExternalities::new(Default::default()).execute_with(|| {
+			run_refund_extension(
+				message_delivery_call(10),
+				|| deliver_messages(10),
+				Err(DispatchError::BadOrigin),
+			);
+			assert_eq!(relayer_reward(), None);
+		});
+	}
 
 	#[test]
 	fn call_size() {
@@ -19,13 +19,15 @@
 use crate::{Call, OriginCaller, Runtime};
 
 use bp_messages::{
-	source_chain::{SenderOrigin, TargetHeaderChain},
+	source_chain::{MessagesResponses, SenderOrigin, TargetHeaderChain},
 	target_chain::{ProvedMessages, SourceHeaderChain},
-	InboundLaneData, LaneId, Message, MessageNonce, Parameter as MessagesParameter,
+	InboundLaneData, LaneConfig, LaneId, Message, MessageNonce, OutboundLaneData,
+	Parameter as MessagesParameter, VerificationError,
 };
 use bp_runtime::{Chain, ChainId, PASS3DT_CHAIN_ID, PASS3D_CHAIN_ID};
 use bridge_runtime_common::messages::{
-	self, BasicConfirmationTransactionEstimation, MessageBridge, MessageTransaction,
+	self, source::SenderRateLimits, BasicConfirmationTransactionEstimation, MessageBridge,
+	MessageTransaction,
 };
 use codec::{Decode, Encode};
 use frame_support::{
@@ -35,13 +37,48 @@ use frame_support::{
 };
 use scale_info::TypeInfo;
 use sp_runtime::{traits::Saturating, FixedPointNumber, FixedU128};
-use sp_std::convert::TryFrom;
+use sp_std::{
+	convert::{TryFrom, TryInto},
+	vec,
+	vec::Vec,
+};
 
+/// Default lane that is used to send messages to Pass3dt.
+pub const DEFAULT_XCM_LANE_TO_PASS3DT: LaneId = [0, 0, 0, 0];
 /// Initial value of `Pass3dtToPass3dConversionRate` parameter.
 pub const INITIAL_PASS3DT_TO_PASS3D_CONVERSION_RATE: FixedU128 =
 	FixedU128::from_inner(FixedU128::DIV);
 /// Initial value of `Pass3dtFeeMultiplier` parameter.
 pub const INITIAL_PASS3DT_FEE_MULTIPLIER: FixedU128 = FixedU128::from_inner(FixedU128::DIV);
+/// Initial value of `Pass3dToPass3dtSenderRateLimits` parameter.
+pub const INITIAL_PASS3D_TO_PASS3DT_SENDER_RATE_LIMITS: SenderRateLimits<bp_pass3d::BlockNumber> =
+	SenderRateLimits { window: bp_pass3d::MINUTES, max_messages: 64, max_payload_size: 64 * 1024 };
+/// Returns lanes that are opened at genesis, with their configuration.
+///
+/// Other lanes may be opened later using the `open_lane` call of the messages pallet. Only the
+/// bridge account of the token transfer pallet may send messages over the token transfer lane.
+/// Messages over all other lanes may only be sent by the XCM pallet (see `is_message_accepted`).
+pub fn genesis_opened_lanes() -> Vec<(LaneId, LaneConfig<bp_pass3d::AccountId>)> {
+	let config = LaneConfig {
+		max_pending_messages: MessageNonce::MAX,
+		max_payload_size: messages::source::maximal_message_size::<WithPass3dtMessageBridge>(),
+		allowed_senders: None,
+	};
+	let token_transfer_config = LaneConfig {
+		allowed_senders: Some(
+			vec![pallet_bridge_token_transfer::Pallet::<Runtime>::bridge_account()]
+				.try_into()
+				.expect("single sender is below MAX_LANE_ALLOWED_SENDERS; qed"),
+		),
+		..config.clone()
+	};
+	[[0, 0, 0, 0], [0, 0, 0, 1]]
+		.into_iter()
+		.map(|lane| (lane, config.clone()))
+		.chain(sp_std::iter::once((crate::TokenTransferLane::get(), token_transfer_config)))
+		.collect()
+}
+
 /// Weight of 2 XCM instructions is for simple `Trap(42)` program, coming through bridge
 /// (it is prepended with `UniversalOrigin` instruction). It is used just for simplest manual
 /// tests, confirming that we don't break encoding somewhere between.
@@ -52,25 +89,45 @@ parameter_types! {
 	pub storage Pass3dtToPass3dConversionRate: FixedU128 = INITIAL_PASS3DT_TO_PASS3D_CONVERSION_RATE;
 	/// Fee multiplier value at Pass3dt chain.
 	pub storage Pass3dtFeeMultiplier: FixedU128 = INITIAL_PASS3DT_FEE_MULTIPLIER;
+	/// Per-sender limits of Pass3d -> Pass3dt messages.
+	pub storage Pass3dToPass3dtSenderRateLimits: SenderRateLimits<bp_pass3d::BlockNumber> =
+		INITIAL_PASS3D_TO_PASS3DT_SENDER_RATE_LIMITS;
 }
 
 /// Message payload for Pass3d -> Pass3dt messages.
 pub type ToPass3dtMessagePayload = messages::source::FromThisChainMessagePayload;
 
 /// Message verifier for Pass3d -> Pass3dt messages.
-pub type ToPass3dtMessageVerifier =
-	messages::source::FromThisChainMessageVerifier<WithPass3dtMessageBridge>;
+pub type ToPass3dtMessageVerifier = messages::source::SenderRateLimitedMessageVerifier<
+	WithPass3dtMessageBridge,
+	messages::source::FromThisChainMessageVerifier<WithPass3dtMessageBridge>,
+	Pass3dToPass3dtSenderRateLimits,
+	frame_system::Pallet<Runtime>,
+	ToPass3dtMessageInitiator,
+>;
+
+/// Initiator of Pass3d -> Pass3dt messages, used by the `ToPass3dtMessageVerifier`.
+///
+/// Transfer messages are sent by the bridge account of the token transfer pallet and XCM
+/// messages are sent by the XCM pallet, so their initiators are found in message payloads.
+pub type ToPass3dtMessageInitiator = (
+	pallet_bridge_token_transfer::TransferMessageInitiator<Runtime>,
+	messages::source::XcmMessageInitiator<crate::xcm_config::LocationToAccountId>,
+	messages::source::SignedMessageInitiator,
+);
 
 /// Message payload for Pass3dt -> Pass3d messages.
 pub type FromPass3dtMessagePayload = messages::target::FromBridgedChainMessagePayload<Call>;
 
-/// Call-dispatch based message dispatch for Pass3dt -> Pass3d messages.
-pub type FromPass3dtMessageDispatch = messages::target::FromBridgedChainMessageDispatch<
+/// Call-dispatch based message dispatch (with dispatch responses) for Pass3dt -> Pass3d messages.
+pub type FromPass3dtMessageDispatch = messages::target::FromBridgedChainMessageDispatchWithResponse<
 	WithPass3dtMessageBridge,
 	xcm_executor::XcmExecutor<crate::xcm_config::XcmConfig>,
 	crate::xcm_config::XcmWeigher,
 	//
 	frame_support::traits::ConstU64<BASE_XCM_WEIGHT_TWICE>,
+	// failed dispatch may be retried by the account, derived from the message origin
+	crate::xcm_config::SovereignAccountOf,
 >;
 
 /// Messages proof for Pass3dt -> Pass3d messages.
@@ -140,6 +197,14 @@ impl messages::ThisChainWithMessages for Pass3d {
 			{
 				log::trace!(target: "runtime::bridge", "Verifying message sent using XCM pallet to Pass3dt");
 			},
+			_ if *lane != crate::TokenTransferLane::get() => {
+				// messages from all lanes except the token transfer lane are executed by the XCM
+				// executor of the Pass3dt chain, with the origin from the message payload. If
+				// anyone could send them, they'd be able to forge e.g. reserve deposits from this
+				// chain, so only the XCM pallet (which always uses this chain location) may do that
+				log::trace!(target: "runtime::bridge", "Rejecting XCM message sent using messages pallet to Pass3dt");
+				return false
+			},
 			_ => {
 				// keep in mind that in this case all messages are free (in term of fees)
 				// => it's just to keep testing bridge on our test deployments until we'll have a
@@ -148,13 +213,36 @@ impl messages::ThisChainWithMessages for Pass3d {
 			},
 		}
 
-		*lane == [0, 0, 0, 0] || *lane == [0, 0, 0, 1]
+		// lanes are managed by the messages pallet lane registry (see `verify_registered_lane`)
+		true
 	}
 
 	fn maximal_pending_messages_at_outbound_lane() -> MessageNonce {
 		MessageNonce::MAX
 	}
 
+	fn verify_registered_lane(
+		send_origin: &Self::Origin,
+		lane: &LaneId,
+		lane_outbound_data: &OutboundLaneData,
+		payload: &[u8],
+	) -> Result<(), &'static str> {
+		let sender = match send_origin.caller {
+			OriginCaller::system(frame_system::RawOrigin::Signed(ref sender)) => Some(sender),
+			_ => None,
+		};
+		pallet_bridge_messages::Pallet::<Runtime, crate::WithPass3dtMessagesInstance>::verify_registered_lane_message(
+			sender,
+			lane,
+			lane_outbound_data,
+			u32::try_from(payload.len()).unwrap_or(u32::MAX),
+		)
+	}
+
+	fn verify_registered_lane_weight() -> Weight {
+		pallet_bridge_messages::Pallet::<Runtime, crate::WithPass3dtMessagesInstance>::verify_registered_lane_message_weight()
+	}
+
 	fn transaction_payment(transaction: MessageTransaction<Weight>) -> bp_pass3d::Balance {
 		// `transaction` may represent transaction from the future, when multiplier value will
 		// be larger, so let's use slightly increased value
@@ -234,7 +322,7 @@ impl messages::BridgedChainWithMessages for Pass3dt {
 }
 
 impl TargetHeaderChain<ToPass3dtMessagePayload, bp_pass3d::AccountId> for Pass3dt {
-	type Error = &'static str;
+	type Error = VerificationError;
 	// The proof is:
 	// - hash of the header this proof has been created with;
 	// - the storage proof of one or several keys;
@@ -243,11 +331,12 @@ impl TargetHeaderChain<ToPass3dtMessagePayload, bp_pass3d::AccountId> for Pass3d
 
 	fn verify_message(payload: &ToPass3dtMessagePayload) -> Result<(), Self::Error> {
 		messages::source::verify_chain_message::<WithPass3dtMessageBridge>(payload)
+			.map_err(Into::into)
 	}
 
 	fn verify_messages_delivery_proof(
 		proof: Self::MessagesDeliveryProof,
-	) -> Result<(LaneId, InboundLaneData<bp_pass3d::AccountId>), Self::Error> {
+	) -> Result<(LaneId, InboundLaneData<bp_pass3d::AccountId>, MessagesResponses), Self::Error> {
 		messages::source::verify_messages_delivery_proof::<
 			WithPass3dtMessageBridge,
 			Runtime,
@@ -257,7 +346,7 @@ impl TargetHeaderChain<ToPass3dtMessagePayload, bp_pass3d::AccountId> for Pass3d
 }
 
 impl SourceHeaderChain<bp_pass3dt::Balance> for Pass3dt {
-	type Error = &'static str;
+	type Error = VerificationError;
 	// The proof is:
 	// - hash of the header this proof has been created with;
 	// - the storage proof of one or several keys;
@@ -289,6 +378,8 @@ impl SenderOrigin<crate::AccountId> for crate::Origin {
 pub enum Pass3dToPass3dtMessagesParameter {
 	/// The conversion formula we use is: `Pass3dTokens = Pass3dtTokens * conversion_rate`.
 	Pass3dtToPass3dConversionRate(FixedU128),
+	/// Limits of messages that may be sent by a single account over a single lane.
+	SenderRateLimits(SenderRateLimits<bp_pass3d::BlockNumber>),
 }
 
 impl MessagesParameter for Pass3dToPass3dtMessagesParameter {
@@ -296,6 +387,8 @@ impl MessagesParameter for Pass3dToPass3dtMessagesParameter {
 		match *self {
 			Pass3dToPass3dtMessagesParameter::Pass3dtToPass3dConversionRate(ref conversion_rate) =>
 				Pass3dtToPass3dConversionRate::set(conversion_rate),
+			Pass3dToPass3dtMessagesParameter::SenderRateLimits(ref limits) =>
+				Pass3dToPass3dtSenderRateLimits::set(limits),
 		}
 	}
 }
@@ -308,21 +401,25 @@ mod tests {
 	use bridge_runtime_common::{
 		assert_complete_bridge_types,
 		integrity::{
-			assert_complete_bridge_constants, AssertBridgeMessagesPalletConstants,
+			assert_bridge_messages_pallet_weights, assert_complete_bridge_constants,
+			AssertBridgeMessagesPalletConstants, AssertBridgeMessagesPalletWeights,
 			AssertBridgePalletNames, AssertChainConstants, AssertCompleteBridgeConstants,
 		},
 	};
 
 	#[test]
 	fn ensure_pass3d_message_lane_weights_are_correct() {
-		type Weights = pallet_bridge_messages::weights::Pass3dtWeight<Runtime>;
-
-		pallet_bridge_messages::ensure_weights_are_correct::<Weights>(
-			bp_pass3d::DEFAULT_MESSAGE_DELIVERY_TX_WEIGHT,
-			bp_pass3d::ADDITIONAL_MESSAGE_BYTE_DELIVERY_WEIGHT,
-			bp_pass3d::MAX_SINGLE_MESSAGE_DELIVERY_CONFIRMATION_TX_WEIGHT,
-			bp_pass3d::PAY_INBOUND_DISPATCH_FEE_WEIGHT,
-			DbWeight::get(),
+		type Weights = crate::weights::pallet_bridge_messages::WeightInfo<Runtime>;
+
+		assert_bridge_messages_pallet_weights::<Runtime, WithPass3dtMessagesInstance>(
+			AssertBridgeMessagesPalletWeights {
+				default_message_delivery_tx_weight: bp_pass3d::DEFAULT_MESSAGE_DELIVERY_TX_WEIGHT,
+				additional_message_byte_delivery_weight:
+					bp_pass3d::ADDITIONAL_MESSAGE_BYTE_DELIVERY_WEIGHT,
+				max_single_message_delivery_confirmation_tx_weight:
+					bp_pass3d::MAX_SINGLE_MESSAGE_DELIVERY_CONFIRMATION_TX_WEIGHT,
+				pay_inbound_dispatch_fee_weight: bp_pass3d::PAY_INBOUND_DISPATCH_FEE_WEIGHT,
+			},
 		);
 
 		let max_incoming_message_proof_size = bp_pass3dt::EXTRA_STORAGE_PROOF_SIZE.saturating_add(
@@ -348,6 +445,7 @@ mod tests {
 			max_incoming_inbound_lane_data_proof_size,
 			bp_pass3d::MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX,
 			bp_pass3d::MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX,
+			crate::MaxMessageResponseSize::get(),
 			DbWeight::get(),
 		);
 	}
@@ -361,6 +459,10 @@ mod tests {
 			bridge: WithPass3dtMessageBridge,
 			this_chain: bp_pass3d::Pass3d,
 			bridged_chain: bp_pass3dt::Pass3dt,
+			inbound_payload: pallet_bridge_token_transfer::RawMessagePayload<
+				FromPass3dtMessagePayload,
+				bp_pass3dt::Balance,
+			>,
 		);
 
 		assert_complete_bridge_constants::<
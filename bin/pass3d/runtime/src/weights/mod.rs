@@ -0,0 +1,33 @@
+// Copyright 2019-2022 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights of bridge pallets at the Pass3d runtime.
+//!
+//! The benchmarks have not been run for this runtime yet, so weights are copied from the
+//! reference weights of bridge pallets. See the `scripts/update-weights.sh` for how to regenerate
+//! them.
+
+pub mod pallet_bridge_grandpa;
+pub mod pallet_bridge_messages;
+pub mod pallet_bridge_relayers;
+
+impl<T: frame_system::Config> ::pallet_bridge_messages::WeightInfoExt
+	for pallet_bridge_messages::WeightInfo<T>
+{
+	fn expected_extra_storage_proof_size() -> u32 {
+		bp_pass3dt::EXTRA_STORAGE_PROOF_SIZE
+	}
+}
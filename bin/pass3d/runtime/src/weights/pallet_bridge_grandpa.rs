@@ -0,0 +1,42 @@
+// Copyright 2019-2022 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for `pallet_bridge_grandpa` at the Pass3d runtime.
+//!
+//! THE BENCHMARKS HAVE NOT BEEN RUN FOR THE PASS3D RUNTIME YET. Values below are copied from
+//! the `pallet_bridge_grandpa::weights::BridgeWeight`, which is generated using the Millau
+//! runtime. This file must be regenerated by the `scripts/update-weights.sh` (using the
+//! `.maintain/runtime-weight-template.hbs` template) before the runtime is deployed.
+
+#![allow(clippy::all)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weights for `pallet_bridge_grandpa` at the Pass3d runtime.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_bridge_grandpa::WeightInfo for WeightInfo<T> {
+	fn submit_finality_proof(p: u32, v: u32) -> Weight {
+		(105_417_000 as Weight)
+			.saturating_add((40_923_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add((1_691_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+}
@@ -222,6 +222,7 @@ fn testnet_genesis(
 		},
 		bridge_pass3d_messages: BridgePass3dMessagesConfig {
 			owner: Some(get_account_id_from_seed::<sr25519::Public>(PASS3D_MESSAGES_PALLET_OWNER)),
+			opened_lanes: millau_runtime::pass3d_messages::genesis_opened_lanes(),
 			..Default::default()
 		},
 		bridge_pass3d_parachain_messages: BridgePass3dParachainMessagesConfig {
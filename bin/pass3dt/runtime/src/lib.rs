@@ -29,6 +29,7 @@
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
 pub mod pass3d_messages;
+pub mod weights;
 pub mod xcm_config;
 
 use crate::{
@@ -103,6 +104,9 @@ pub type AccountIndex = u32;
 /// Balance of an account.
 pub type Balance = bp_pass3dt::Balance;
 
+/// Identifier of the asset, managed by the assets pallet.
+pub type AssetId = u32;
+
 /// Index of a transaction in the chain.
 pub type Index = bp_pass3dt::Index;
 
@@ -336,6 +340,33 @@ impl pallet_balances::Config for Runtime {
 	type ReserveIdentifier = [u8; 8];
 }
 
+parameter_types! {
+	pub const AssetDeposit: Balance = 1_000_000;
+	pub const AssetAccountDeposit: Balance = 1_000;
+	pub const MetadataDepositBase: Balance = 1_000;
+	pub const MetadataDepositPerByte: Balance = 10;
+	pub const ApprovalDeposit: Balance = 1_000;
+	pub const AssetsStringLimit: u32 = 50;
+}
+
+impl pallet_assets::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = AssetId;
+	type Currency = Balances;
+	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+	type AssetDeposit = AssetDeposit;
+	type AssetAccountDeposit = AssetAccountDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ApprovalDeposit;
+	type StringLimit = AssetsStringLimit;
+	type Freezer = ();
+	type Extra = ();
+	// TODO: update me (https://github.com/paritytech/parity-bridges-common/issues/78)
+	type WeightInfo = ();
+}
+
 parameter_types! {
 	pub const TransactionBaseFee: Balance = 0;
 	pub const TransactionByteFee: Balance = 1;
@@ -366,6 +397,13 @@ impl pallet_sudo::Config for Runtime {
 	type Call = Call;
 }
 
+impl pallet_utility::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type PalletsOrigin = OriginCaller;
+	type WeightInfo = ();
+}
+
 parameter_types! {
 	/// Authorities are changing every 5 minutes.
 	pub const Period: BlockNumber = bp_pass3dt::SESSION_LENGTH;
@@ -393,11 +431,31 @@ parameter_types! {
 	pub const MaxRequests: u32 = 50;
 }
 
+parameter_types! {
+	/// Stake that is reserved when relayer is registered.
+	pub const RelayerStake: Balance = 1_000_000;
+	/// Minimal period of relayer registration.
+	pub const RelayerRegistrationLease: BlockNumber = bp_pass3dt::DAYS;
+	/// Priority boost of bridge transactions, submitted by registered relayers.
+	pub const RegisteredRelayerPriorityBoost: sp_runtime::transaction_validity::TransactionPriority =
+		1_000_000;
+}
+
 impl pallet_bridge_relayers::Config for Runtime {
 	type Event = Event;
 	type Reward = Balance;
 	type PaymentProcedure = bp_relayers::MintReward<pallet_balances::Pallet<Runtime>, AccountId>;
-	type WeightInfo = ();
+	type Currency = Balances;
+	type RequiredStake = RelayerStake;
+	type RequiredRegistrationLease = RelayerRegistrationLease;
+	type RegisteredRelayerPriorityBoost = RegisteredRelayerPriorityBoost;
+	type Slash = ();
+	type WeightInfo = weights::pallet_bridge_relayers::WeightInfo<Runtime>;
+}
+
+parameter_types! {
+	/// Lane, where rewards are moved to during `pallet_bridge_relayers` storage migration.
+	pub const RelayerRewardsMigrationLane: bp_messages::LaneId = crate::pass3d_messages::DEFAULT_XCM_LANE_TO_PASS3D;
 }
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -422,20 +480,32 @@ parameter_types! {
 
 pub type Pass3dGrandpaInstance = ();
 impl pallet_bridge_grandpa::Config for Runtime {
+	type Event = Event;
 	type BridgedChain = bp_pass3d::Pass3d;
 	type MaxRequests = MaxRequests;
 	type HeadersToKeep = HeadersToKeep;
 
-	type WeightInfo = (); //pallet_bridge_grandpa::weights::Pass3dtWeight<Runtime>;
+	type WeightInfo = weights::pallet_bridge_grandpa::WeightInfo<Runtime>;
 }
 
-pub type WestendGrandpaInstance = pallet_bridge_grandpa::Instance1;
-impl pallet_bridge_grandpa::Config<WestendGrandpaInstance> for Runtime {
-	type BridgedChain = bp_westend::Westend;
+// pub type WestendGrandpaInstance = pallet_bridge_grandpa::Instance1;
+// impl pallet_bridge_grandpa::Config<WestendGrandpaInstance> for Runtime {
+// 	type Event = Event;
+// 	type BridgedChain = bp_westend::Westend;
+// 	type MaxRequests = MaxRequests;
+// 	type HeadersToKeep = HeadersToKeep;
+//
+// 	type WeightInfo = (); //pallet_bridge_grandpa::weights::Pass3dtWeight<Runtime>;
+// }
+
+pub type Pass3dBeefyInstance = ();
+impl pallet_bridge_beefy::Config for Runtime {
+	type Event = Event;
+	type BridgedChain = bp_pass3d::Pass3d;
+	type BridgedMmrLeafExtra = ();
 	type MaxRequests = MaxRequests;
 	type HeadersToKeep = HeadersToKeep;
-
-	type WeightInfo = (); //pallet_bridge_grandpa::weights::Pass3dtWeight<Runtime>;
+	type WeightInfo = pallet_bridge_beefy::weights::BridgeWeight<Runtime>;
 }
 
 impl pallet_shift_session_manager::Config for Runtime {}
@@ -446,6 +516,11 @@ parameter_types! {
 		bp_pass3d::MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX;
 	pub const MaxUnconfirmedMessagesAtInboundLane: bp_messages::MessageNonce =
 		bp_pass3d::MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX;
+	pub const MaxFailedDispatchesAtInboundLane: u32 = 16;
+	pub const MaxFailedDispatchPayloadSize: u32 = 16 * 1024;
+	pub const FailedDispatchLifetime: BlockNumber = bp_pass3dt::DAYS;
+	pub const ConfirmedMessageStatusLifetime: BlockNumber = bp_pass3dt::DAYS;
+	pub const MaxMessageResponseSize: u32 = 128;
 	// `IdentityFee` is used by Pass3dt => we may use weight directly
 	pub const GetDeliveryConfirmationTransactionFee: Balance =
 		bp_pass3dt::MAX_SINGLE_MESSAGE_DELIVERY_CONFIRMATION_TX_WEIGHT as _;
@@ -454,22 +529,36 @@ parameter_types! {
 	// pub const Pass3dParachainChainId: bp_runtime::ChainId = bp_runtime::PASS3D_PARACHAIN_CHAIN_ID;
 }
 
+parameter_types! {
+	/// Lanes that are opened in the lane registry during `pallet_bridge_messages` storage migration.
+	pub MessagesMigrationLanes: Vec<(bp_messages::LaneId, bp_messages::LaneConfig<AccountId>)> =
+		pass3d_messages::genesis_opened_lanes();
+}
+
 /// Instance of the messages pallet used to relay messages to/from Pass3d chain.
 pub type WithPass3dMessagesInstance = ();
 
 impl pallet_bridge_messages::Config<WithPass3dMessagesInstance> for Runtime {
 	type Event = Event;
-	type WeightInfo = (); //pallet_bridge_messages::weights::Pass3dtWeight<Runtime>;
+	type WeightInfo = weights::pallet_bridge_messages::WeightInfo<Runtime>;
 	type Parameter = pass3d_messages::Pass3dtToPass3dMessagesParameter;
 	type MaxMessagesToPruneAtOnce = MaxMessagesToPruneAtOnce;
 	type MaxUnrewardedRelayerEntriesAtInboundLane = MaxUnrewardedRelayerEntriesAtInboundLane;
 	type MaxUnconfirmedMessagesAtInboundLane = MaxUnconfirmedMessagesAtInboundLane;
+	type MaxFailedDispatchesAtInboundLane = MaxFailedDispatchesAtInboundLane;
+	type MaxFailedDispatchPayloadSize = MaxFailedDispatchPayloadSize;
+	type FailedDispatchLifetime = FailedDispatchLifetime;
+	type ConfirmedMessageStatusLifetime = ConfirmedMessageStatusLifetime;
+	type MaxMessageResponseSize = MaxMessageResponseSize;
 
 	type MaximalOutboundPayloadSize = crate::pass3d_messages::ToPass3dMaximalOutboundPayloadSize;
 	type OutboundPayload = crate::pass3d_messages::ToPass3dMessagePayload;
 	type OutboundMessageFee = Balance;
 
-	type InboundPayload = crate::pass3d_messages::FromPass3dMessagePayload;
+	type InboundPayload = pallet_bridge_token_transfer::RawMessagePayload<
+		crate::pass3d_messages::FromPass3dMessagePayload,
+		bp_pass3d::Balance,
+	>;
 	type InboundMessageFee = bp_pass3d::Balance;
 	type InboundRelayer = bp_pass3d::AccountId;
 
@@ -482,13 +571,47 @@ impl pallet_bridge_messages::Config<WithPass3dMessagesInstance> for Runtime {
 			GetDeliveryConfirmationTransactionFee,
 		>;
 	type OnMessageAccepted = ();
-	type OnDeliveryConfirmed = ();
+	type OnDeliveryConfirmed = BridgeTokenTransfer;
+	type OnMessageResponse = ();
 
 	type SourceHeaderChain = crate::pass3d_messages::Pass3d;
-	type MessageDispatch = crate::pass3d_messages::FromPass3dMessageDispatch;
+	type MessageDispatch = pallet_bridge_token_transfer::TokenTransferMessageDispatch<
+		Runtime,
+		crate::pass3d_messages::FromPass3dMessageDispatch,
+	>;
 	type BridgedChainId = Pass3dChainId;
 }
 
+parameter_types! {
+	/// Identifier of the token transfer pallet, used to derive the bridge account.
+	pub const TokenTransferPalletId: frame_support::PalletId =
+		frame_support::PalletId(*b"py/brtkt");
+	/// Lane that is used to transfer tokens to/from Pass3d chain.
+	pub const TokenTransferLane: bp_messages::LaneId = [0, 0, 0, 2];
+	/// Minimal amount of tokens that may be transferred over the bridge.
+	pub const MinimalTokenTransfer: Balance = 1_000;
+	/// Maximal amount of tokens that may be received from Pass3d chain within limit period.
+	pub const InboundTokenTransfersLimit: Balance = 1_000_000_000_000;
+	/// Period of inbound token transfers limit.
+	pub const TokenTransfersLimitPeriod: BlockNumber = bp_pass3dt::DAYS;
+	/// Maximal number of rejected token transfers that are refunded in a single block.
+	pub const MaxTokenTransferRefundsPerBlock: u32 = 16;
+}
+
+impl pallet_bridge_token_transfer::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type PalletId = TokenTransferPalletId;
+	type BridgedAccountId = bp_pass3d::AccountId;
+	type MessagesBridge = pallet_bridge_messages::Pallet<Runtime, WithPass3dMessagesInstance>;
+	type Lane = TokenTransferLane;
+	type MinimalTransfer = MinimalTokenTransfer;
+	type InboundTransfersLimit = InboundTokenTransfersLimit;
+	type LimitPeriod = TokenTransfersLimitPeriod;
+	type MaxRefundsPerBlock = MaxTokenTransferRefundsPerBlock;
+	type WeightInfo = pallet_bridge_token_transfer::weights::BridgeWeight<Runtime>;
+}
+
 // /// Instance of the messages pallet used to relay messages to/from Pass3dParachain chain.
 // pub type WithPass3dParachainMessagesInstance = pallet_bridge_messages::Instance1;
 //
@@ -519,6 +642,7 @@ impl pallet_bridge_messages::Config<WithPass3dMessagesInstance> for Runtime {
 // 		>;
 // 	type OnMessageAccepted = ();
 // 	type OnDeliveryConfirmed = ();
+// 	type OnMessageResponse = ();
 //
 // 	type SourceHeaderChain = crate::pass3d_parachain_messages::Pass3dParachain;
 // 	type MessageDispatch = crate::pass3d_parachain_messages::FromPass3dParachainMessageDispatch;
@@ -562,6 +686,7 @@ construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Sudo: pallet_sudo::{Pallet, Call, Config<T>, Storage, Event<T>},
+		Utility: pallet_utility::{Pallet, Call, Event},
 
 		// Must be before session.
 		Aura: pallet_aura::{Pallet, Config<T>},
@@ -583,17 +708,22 @@ construct_runtime!(
 
 		// Pass3d bridge modules.
 		BridgeRelayers: pallet_bridge_relayers::{Pallet, Call, Storage, Event<T>},
-		BridgePass3dGrandpa: pallet_bridge_grandpa::{Pallet, Call, Storage},
+		BridgePass3dGrandpa: pallet_bridge_grandpa::{Pallet, Call, Storage, Event<T>},
+		BridgePass3dBeefy: pallet_bridge_beefy::{Pallet, Call, Storage, Event<T>},
 		BridgePass3dMessages: pallet_bridge_messages::{Pallet, Call, Storage, Event<T>, Config<T>},
+		BridgeTokenTransfer: pallet_bridge_token_transfer::{Pallet, Call, Storage, Event<T>},
 
 		// Westend bridge modules.
-		// BridgeWestendGrandpa: pallet_bridge_grandpa::<Instance1>::{Pallet, Call, Config<T>, Storage},
+		// BridgeWestendGrandpa: pallet_bridge_grandpa::<Instance1>::{Pallet, Call, Config<T>, Storage, Event<T>},
 		// BridgeWestendParachains: pallet_bridge_parachains::<Instance1>::{Pallet, Call, Storage, Event<T>},
 
 		// Pass3dParachain bridge modules.
 		// BridgePass3dParachains: pallet_bridge_parachains::{Pallet, Call, Storage, Event<T>},
 		// BridgePass3dParachainMessages: pallet_bridge_messages::<Instance1>::{Pallet, Call, Storage, Event<T>, Config<T>},
 
+		// Wrapped tokens of the Pass3d chain.
+		Assets: pallet_assets::{Pallet, Call, Storage, Config<T>, Event<T>},
+
 		// Pallet for sending XCM.
 		XcmPallet: pallet_xcm::{Pallet, Call, Storage, Event<T>, Origin, Config} = 99,
 	}
@@ -601,10 +731,17 @@ construct_runtime!(
 
 generate_bridge_reject_obsolete_headers_and_messages! {
 	Call, AccountId,
+	// Relayers
+	relayers: BridgeRelayers,
 	// Grandpa
 	BridgePass3dGrandpa,
 	//Messages
-	BridgePass3dMessages
+	BridgePass3dMessages,
+	// Batched bridge calls
+	bridge_runtime_common::BatchAllBridgeCalls<
+		Runtime,
+		(BridgePass3dGrandpa, BridgePass3dMessages),
+	>
 }
 
 /// The address format for describing accounts.
@@ -642,6 +779,17 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	(
+		pallet_bridge_relayers::migration::v1::MigrateToV1<Runtime, RelayerRewardsMigrationLane>,
+		pallet_bridge_grandpa::migration::v1::MigrateToV1<Runtime, Pass3dGrandpaInstance>,
+		pallet_bridge_messages::migration::v1::MigrateToV1<
+			Runtime,
+			WithPass3dMessagesInstance,
+			MessagesMigrationLanes,
+		>,
+		pallet_bridge_messages::migration::v2::MigrateToV2<Runtime, WithPass3dMessagesInstance>,
+		xcm_config::CreateGenesisAssets,
+	),
 >;
 
 impl_runtime_apis! {
@@ -852,6 +1000,22 @@ impl_runtime_apis! {
 		fn best_finalized() -> Option<HeaderId<bp_pass3d::Hash, bp_pass3d::BlockNumber>> {
 			BridgePass3dGrandpa::best_finalized().map(|header| header.id())
 		}
+
+		fn finalized_header_at(
+			number: bp_pass3d::BlockNumber,
+		) -> Option<HeaderId<bp_pass3d::Hash, bp_pass3d::BlockNumber>> {
+			BridgePass3dGrandpa::finalized_header_at(number).map(|hash| HeaderId(number, hash))
+		}
+
+		fn imported_headers_range(
+			from: bp_pass3d::BlockNumber,
+			to: bp_pass3d::BlockNumber,
+		) -> Vec<HeaderId<bp_pass3d::Hash, bp_pass3d::BlockNumber>> {
+			BridgePass3dGrandpa::imported_headers_range(from, to)
+				.into_iter()
+				.map(|(number, hash)| HeaderId(number, hash))
+				.collect()
+		}
 	}
 
 	impl bp_pass3d::ToPass3dOutboundLaneApi<Block, Balance, ToPass3dMessagePayload> for Runtime {
@@ -877,6 +1041,19 @@ impl_runtime_apis! {
 				WithPass3dMessagesInstance,
 			>(lane, begin, end)
 		}
+
+		fn lane_operating_mode(lane: bp_messages::LaneId) -> bp_messages::MessagesOperatingMode {
+			pallet_bridge_messages::Pallet::<Runtime, WithPass3dMessagesInstance>::lane_operating_mode(lane)
+		}
+	}
+
+	impl bp_pass3d::ToPass3dMessageStatusApi<Block, BlockNumber> for Runtime {
+		fn message_status(
+			lane: bp_messages::LaneId,
+			nonce: bp_messages::MessageNonce,
+		) -> Option<bp_messages::MessageStatus<BlockNumber>> {
+			pallet_bridge_messages::Pallet::<Runtime, WithPass3dMessagesInstance>::message_status(lane, nonce)
+		}
 	}
 
 	impl bp_pass3d::FromPass3dInboundLaneApi<Block, bp_pass3d::Balance> for Runtime {
@@ -901,14 +1078,15 @@ impl_runtime_apis! {
 			use frame_support::traits::StorageInfoTrait;
 
 			use pallet_bridge_messages::benchmarking::Pallet as MessagesBench;
-			use pallet_bridge_parachains::benchmarking::Pallet as ParachainsBench;
+			use pallet_bridge_token_transfer::benchmarking::Pallet as TokenTransferBench;
 
 			let mut list = Vec::<BenchmarkList>::new();
 
 			list_benchmark!(list, extra, pallet_bridge_messages, MessagesBench::<Runtime, WithPass3dMessagesInstance>);
 			list_benchmark!(list, extra, pallet_bridge_grandpa, BridgePass3dGrandpa);
-			list_benchmark!(list, extra, pallet_bridge_parachains, ParachainsBench::<Runtime, WithPass3dMessagesInstance>);
+			list_benchmark!(list, extra, pallet_bridge_beefy, BridgePass3dBeefy);
 			list_benchmark!(list, extra, pallet_bridge_relayers, BridgeRelayers);
+			list_benchmark!(list, extra, pallet_bridge_token_transfer, TokenTransferBench::<Runtime>);
 
 			let storage_info = AllPalletsWithSystem::storage_info();
 
@@ -945,9 +1123,9 @@ impl_runtime_apis! {
 				MessageParams,
 				MessageProofParams,
 			};
-			use pallet_bridge_parachains::benchmarking::{
-				Pallet as ParachainsBench,
-				Config as ParachainsConfig,
+			use pallet_bridge_token_transfer::benchmarking::{
+				Pallet as TokenTransferBench,
+				Config as TokenTransferConfig,
 			};
 			use pass3d_messages::WithPass3dMessageBridge;
 
@@ -998,22 +1176,9 @@ impl_runtime_apis! {
 				}
 			}
 
-			impl ParachainsConfig<WithPass3dParachainsInstance> for Runtime {
-				fn prepare_parachain_heads_proof(
-					parachains: &[bp_polkadot_core::parachains::ParaId],
-					parachain_head_size: u32,
-					proof_size: bp_runtime::StorageProofSize,
-				) -> (
-					pallet_bridge_parachains::RelayBlockNumber,
-					pallet_bridge_parachains::RelayBlockHash,
-					bp_polkadot_core::parachains::ParaHeadsProof,
-					Vec<(bp_polkadot_core::parachains::ParaId, bp_polkadot_core::parachains::ParaHash)>,
-				) {
-					bridge_runtime_common::parachains_benchmarking::prepare_parachain_heads_proof::<Runtime, WithPass3dParachainsInstance>(
-						parachains,
-						parachain_head_size,
-						proof_size,
-					)
+			impl TokenTransferConfig for Runtime {
+				fn message_fee() -> Balance {
+					<Runtime as MessagesConfig<WithPass3dMessagesInstance>>::message_fee()
 				}
 			}
 
@@ -1024,13 +1189,14 @@ impl_runtime_apis! {
 				MessagesBench::<Runtime, WithPass3dMessagesInstance>
 			);
 			add_benchmark!(params, batches, pallet_bridge_grandpa, BridgePass3dGrandpa);
+			add_benchmark!(params, batches, pallet_bridge_beefy, BridgePass3dBeefy);
+			add_benchmark!(params, batches, pallet_bridge_relayers, BridgeRelayers);
 			add_benchmark!(
 				params,
 				batches,
-				pallet_bridge_parachains,
-				ParachainsBench::<Runtime, WithPass3dParachainsInstance>
+				pallet_bridge_token_transfer,
+				TokenTransferBench::<Runtime>
 			);
-			add_benchmark!(params, batches, pallet_bridge_relayers, BridgeRelayers);
 
 			Ok(batches)
 		}
@@ -1040,6 +1206,65 @@ impl_runtime_apis! {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use bp_messages::InboundLaneData;
+	use bridge_runtime_common::messages::target::FromBridgedChainMessagesProof;
+	use frame_support::weights::DispatchInfo;
+	use sp_runtime::{traits::SignedExtension, transaction_validity::InvalidTransaction};
+
+	const RELAYER: [u8; 32] = [1u8; 32];
+
+	fn message_delivery_call_at_lane(
+		lane: bp_messages::LaneId,
+		nonces_end: bp_messages::MessageNonce,
+	) -> Call {
+		Call::BridgePass3dMessages(MessagesCall::receive_messages_proof {
+			relayer_id_at_bridged_chain: [0u8; 32].into(),
+			messages_count: 1,
+			dispatch_weight: 0,
+			proof: FromBridgedChainMessagesProof {
+				bridged_header_hash: Default::default(),
+				storage_proof: vec![],
+				lane,
+				nonces_start: nonces_end,
+				nonces_end,
+			},
+		})
+	}
+
+	fn batch_call(calls: Vec<Call>) -> Call {
+		Call::Utility(pallet_utility::Call::batch_all { calls })
+	}
+
+	#[test]
+	fn batch_with_obsolete_bridge_call_is_rejected() {
+		sp_io::TestExternalities::new(Default::default()).execute_with(|| {
+			pallet_bridge_messages::InboundLanes::<Runtime, WithPass3dMessagesInstance>::insert(
+				TokenTransferLane::get(),
+				InboundLaneData { relayers: Default::default(), last_confirmed_nonce: 10 },
+			);
+			let validate = |call| {
+				BridgeRejectObsoleteHeadersAndMessages.validate(
+					&RELAYER.into(),
+					&call,
+					&DispatchInfo::default(),
+					100,
+				)
+			};
+
+			assert!(validate(batch_call(vec![
+				message_delivery_call_at_lane(RelayerRewardsMigrationLane::get(), 10),
+				message_delivery_call_at_lane(TokenTransferLane::get(), 11),
+			]))
+			.is_ok());
+			assert_eq!(
+				validate(batch_call(vec![
+					message_delivery_call_at_lane(RelayerRewardsMigrationLane::get(), 10),
+					message_delivery_call_at_lane(TokenTransferLane::get(), 10),
+				])),
+				Err(InvalidTransaction::Stale.into()),
+			);
+		});
+	}
 
 	#[test]
 	fn call_size() {
@@ -19,13 +19,15 @@
 use crate::{Call, OriginCaller, Runtime};
 
 use bp_messages::{
-	source_chain::{SenderOrigin, TargetHeaderChain},
+	source_chain::{MessagesResponses, SenderOrigin, TargetHeaderChain},
 	target_chain::{ProvedMessages, SourceHeaderChain},
-	InboundLaneData, LaneId, Message, MessageNonce, Parameter as MessagesParameter,
+	InboundLaneData, LaneConfig, LaneId, Message, MessageNonce, OutboundLaneData,
+	Parameter as MessagesParameter, VerificationError,
 };
 use bp_runtime::{Chain, ChainId, PASS3DT_CHAIN_ID, PASS3D_CHAIN_ID};
 use bridge_runtime_common::messages::{
-	self, BasicConfirmationTransactionEstimation, MessageBridge, MessageTransaction,
+	self, source::SenderRateLimits, BasicConfirmationTransactionEstimation, MessageBridge,
+	MessageTransaction,
 };
 use codec::{Decode, Encode};
 use frame_support::{
@@ -35,7 +37,11 @@ use frame_support::{
 };
 use scale_info::TypeInfo;
 use sp_runtime::{traits::Saturating, FixedPointNumber, FixedU128};
-use sp_std::convert::TryFrom;
+use sp_std::{
+	convert::{TryFrom, TryInto},
+	vec,
+	vec::Vec,
+};
 
 /// Default lane that is used to send messages to Pass3d.
 pub const DEFAULT_XCM_LANE_TO_PASS3D: LaneId = [0, 0, 0, 0];
@@ -44,6 +50,35 @@ pub const INITIAL_PASS3D_TO_PASS3DT_CONVERSION_RATE: FixedU128 =
 	FixedU128::from_inner(FixedU128::DIV);
 /// Initial value of `Pass3dFeeMultiplier` parameter.
 pub const INITIAL_PASS3D_FEE_MULTIPLIER: FixedU128 = FixedU128::from_inner(FixedU128::DIV);
+/// Initial value of `Pass3dtToPass3dSenderRateLimits` parameter.
+pub const INITIAL_PASS3DT_TO_PASS3D_SENDER_RATE_LIMITS: SenderRateLimits<bp_pass3dt::BlockNumber> =
+	SenderRateLimits { window: bp_pass3dt::MINUTES, max_messages: 64, max_payload_size: 64 * 1024 };
+/// Returns lanes that are opened at genesis, with their configuration.
+///
+/// Other lanes may be opened later using the `open_lane` call of the messages pallet. Only the
+/// bridge account of the token transfer pallet may send messages over the token transfer lane.
+/// Messages over all other lanes may only be sent by the XCM pallet (see `is_message_accepted`).
+pub fn genesis_opened_lanes() -> Vec<(LaneId, LaneConfig<bp_pass3dt::AccountId>)> {
+	let config = LaneConfig {
+		max_pending_messages: MessageNonce::MAX,
+		max_payload_size: messages::source::maximal_message_size::<WithPass3dMessageBridge>(),
+		allowed_senders: None,
+	};
+	let token_transfer_config = LaneConfig {
+		allowed_senders: Some(
+			vec![pallet_bridge_token_transfer::Pallet::<Runtime>::bridge_account()]
+				.try_into()
+				.expect("single sender is below MAX_LANE_ALLOWED_SENDERS; qed"),
+		),
+		..config.clone()
+	};
+	[DEFAULT_XCM_LANE_TO_PASS3D, [0, 0, 0, 1]]
+		.into_iter()
+		.map(|lane| (lane, config.clone()))
+		.chain(sp_std::iter::once((crate::TokenTransferLane::get(), token_transfer_config)))
+		.collect()
+}
+
 /// Weight of 2 XCM instructions is for simple `Trap(42)` program, coming through bridge
 /// (it is prepended with `UniversalOrigin` instruction). It is used just for simplest manual
 /// tests, confirming that we don't break encoding somewhere between.
@@ -54,14 +89,32 @@ parameter_types! {
 	pub storage Pass3dToPass3dtConversionRate: FixedU128 = INITIAL_PASS3D_TO_PASS3DT_CONVERSION_RATE;
 	/// Fee multiplier value at Pass3d chain.
 	pub storage Pass3dFeeMultiplier: FixedU128 = INITIAL_PASS3D_FEE_MULTIPLIER;
+	/// Per-sender limits of Pass3dt -> Pass3d messages.
+	pub storage Pass3dtToPass3dSenderRateLimits: SenderRateLimits<bp_pass3dt::BlockNumber> =
+		INITIAL_PASS3DT_TO_PASS3D_SENDER_RATE_LIMITS;
 }
 
 /// Message payload for Pass3dt -> Pass3d messages.
 pub type ToPass3dMessagePayload = messages::source::FromThisChainMessagePayload;
 
 /// Message verifier for Pass3dt -> Pass3d messages.
-pub type ToPass3dMessageVerifier =
-	messages::source::FromThisChainMessageVerifier<WithPass3dMessageBridge>;
+pub type ToPass3dMessageVerifier = messages::source::SenderRateLimitedMessageVerifier<
+	WithPass3dMessageBridge,
+	messages::source::FromThisChainMessageVerifier<WithPass3dMessageBridge>,
+	Pass3dtToPass3dSenderRateLimits,
+	frame_system::Pallet<Runtime>,
+	ToPass3dMessageInitiator,
+>;
+
+/// Initiator of Pass3dt -> Pass3d messages, used by the `ToPass3dMessageVerifier`.
+///
+/// Transfer messages are sent by the bridge account of the token transfer pallet and XCM
+/// messages are sent by the XCM pallet, so their initiators are found in message payloads.
+pub type ToPass3dMessageInitiator = (
+	pallet_bridge_token_transfer::TransferMessageInitiator<Runtime>,
+	messages::source::XcmMessageInitiator<crate::xcm_config::LocationToAccountId>,
+	messages::source::SignedMessageInitiator,
+);
 
 /// Message payload for Pass3d -> Pass3dt messages.
 pub type FromPass3dMessagePayload = messages::target::FromBridgedChainMessagePayload<Call>;
@@ -73,14 +126,16 @@ pub type FromPass3dMessagesProof = messages::target::FromBridgedChainMessagesPro
 pub type ToPass3dMessagesDeliveryProof =
 	messages::source::FromBridgedChainMessagesDeliveryProof<bp_pass3d::Hash>;
 
-/// Call-dispatch based message dispatch for Pass3d -> Pass3dt messages.
-pub type FromPass3dMessageDispatch = messages::target::FromBridgedChainMessageDispatch<
+/// Call-dispatch based message dispatch (with dispatch responses) for Pass3d -> Pass3dt messages.
+pub type FromPass3dMessageDispatch = messages::target::FromBridgedChainMessageDispatchWithResponse<
 	WithPass3dMessageBridge,
 	xcm_executor::XcmExecutor<crate::xcm_config::XcmConfig>,
 	crate::xcm_config::XcmWeigher,
 	// 2 XCM instructions is for simple `Trap(42)` program, coming through bridge
 	// (it is prepended with `UniversalOrigin` instruction)
 	frame_support::traits::ConstU64<BASE_XCM_WEIGHT_TWICE>,
+	// failed dispatch may be retried by the account, derived from the message origin
+	crate::xcm_config::SovereignAccountOf,
 >;
 
 /// Maximal outbound payload size of Pass3dt -> Pass3d messages.
@@ -143,6 +198,14 @@ impl messages::ThisChainWithMessages for Pass3dt {
 			{
 				log::trace!(target: "runtime::bridge", "Verifying message sent using XCM pallet to Pass3d");
 			},
+			_ if *lane != crate::TokenTransferLane::get() => {
+				// messages from all lanes except the token transfer lane are executed by the XCM
+				// executor of the Pass3d chain, with the origin from the message payload. If
+				// anyone could send them, they'd be able to forge e.g. reserve deposits from this
+				// chain, so only the XCM pallet (which always uses this chain location) may do that
+				log::trace!(target: "runtime::bridge", "Rejecting XCM message sent using messages pallet to Pass3d");
+				return false
+			},
 			_ => {
 				// keep in mind that in this case all messages are free (in term of fees)
 				// => it's just to keep testing bridge on our test deployments until we'll have a
@@ -151,13 +214,36 @@ impl messages::ThisChainWithMessages for Pass3dt {
 			},
 		}
 
-		*lane == DEFAULT_XCM_LANE_TO_PASS3D || *lane == [0, 0, 0, 1]
+		// lanes are managed by the messages pallet lane registry (see `verify_registered_lane`)
+		true
 	}
 
 	fn maximal_pending_messages_at_outbound_lane() -> MessageNonce {
 		MessageNonce::MAX
 	}
 
+	fn verify_registered_lane(
+		send_origin: &Self::Origin,
+		lane: &LaneId,
+		lane_outbound_data: &OutboundLaneData,
+		payload: &[u8],
+	) -> Result<(), &'static str> {
+		let sender = match send_origin.caller {
+			OriginCaller::system(frame_system::RawOrigin::Signed(ref sender)) => Some(sender),
+			_ => None,
+		};
+		pallet_bridge_messages::Pallet::<Runtime, crate::WithPass3dMessagesInstance>::verify_registered_lane_message(
+			sender,
+			lane,
+			lane_outbound_data,
+			u32::try_from(payload.len()).unwrap_or(u32::MAX),
+		)
+	}
+
+	fn verify_registered_lane_weight() -> Weight {
+		pallet_bridge_messages::Pallet::<Runtime, crate::WithPass3dMessagesInstance>::verify_registered_lane_message_weight()
+	}
+
 	fn transaction_payment(transaction: MessageTransaction<Weight>) -> bp_pass3dt::Balance {
 		// `transaction` may represent transaction from the future, when multiplier value will
 		// be larger, so let's use slightly increased value
@@ -237,7 +323,7 @@ impl messages::BridgedChainWithMessages for Pass3d {
 }
 
 impl TargetHeaderChain<ToPass3dMessagePayload, bp_pass3dt::AccountId> for Pass3d {
-	type Error = &'static str;
+	type Error = VerificationError;
 	// The proof is:
 	// - hash of the header this proof has been created with;
 	// - the storage proof or one or several keys;
@@ -246,11 +332,12 @@ impl TargetHeaderChain<ToPass3dMessagePayload, bp_pass3dt::AccountId> for Pass3d
 
 	fn verify_message(payload: &ToPass3dMessagePayload) -> Result<(), Self::Error> {
 		messages::source::verify_chain_message::<WithPass3dMessageBridge>(payload)
+			.map_err(Into::into)
 	}
 
 	fn verify_messages_delivery_proof(
 		proof: Self::MessagesDeliveryProof,
-	) -> Result<(LaneId, InboundLaneData<bp_pass3dt::AccountId>), Self::Error> {
+	) -> Result<(LaneId, InboundLaneData<bp_pass3dt::AccountId>, MessagesResponses), Self::Error> {
 		messages::source::verify_messages_delivery_proof::<
 			WithPass3dMessageBridge,
 			Runtime,
@@ -260,7 +347,7 @@ impl TargetHeaderChain<ToPass3dMessagePayload, bp_pass3dt::AccountId> for Pass3d
 }
 
 impl SourceHeaderChain<bp_pass3d::Balance> for Pass3d {
-	type Error = &'static str;
+	type Error = VerificationError;
 	// The proof is:
 	// - hash of the header this proof has been created with;
 	// - the storage proof or one or several keys;
@@ -292,6 +379,8 @@ impl SenderOrigin<crate::AccountId> for crate::Origin {
 pub enum Pass3dtToPass3dMessagesParameter {
 	/// The conversion formula we use is: `Pass3dtTokens = Pass3dTokens * conversion_rate`.
 	Pass3dToPass3dtConversionRate(FixedU128),
+	/// Limits of messages that may be sent by a single account over a single lane.
+	SenderRateLimits(SenderRateLimits<bp_pass3dt::BlockNumber>),
 }
 
 impl MessagesParameter for Pass3dtToPass3dMessagesParameter {
@@ -299,6 +388,8 @@ impl MessagesParameter for Pass3dtToPass3dMessagesParameter {
 		match *self {
 			Pass3dtToPass3dMessagesParameter::Pass3dToPass3dtConversionRate(ref conversion_rate) =>
 				Pass3dToPass3dtConversionRate::set(conversion_rate),
+			Pass3dtToPass3dMessagesParameter::SenderRateLimits(ref limits) =>
+				Pass3dtToPass3dSenderRateLimits::set(limits),
 		}
 	}
 }
@@ -312,7 +403,8 @@ mod tests {
 	use bridge_runtime_common::{
 		assert_complete_bridge_types,
 		integrity::{
-			assert_complete_bridge_constants, AssertBridgeMessagesPalletConstants,
+			assert_bridge_messages_pallet_weights, assert_complete_bridge_constants,
+			AssertBridgeMessagesPalletConstants, AssertBridgeMessagesPalletWeights,
 			AssertBridgePalletNames, AssertChainConstants, AssertCompleteBridgeConstants,
 		},
 		messages,
@@ -320,14 +412,17 @@ mod tests {
 
 	#[test]
 	fn ensure_pass3dt_message_lane_weights_are_correct() {
-		type Weights = pallet_bridge_messages::weights::Pass3dtWeight<Runtime>;
-
-		pallet_bridge_messages::ensure_weights_are_correct::<Weights>(
-			bp_pass3dt::DEFAULT_MESSAGE_DELIVERY_TX_WEIGHT,
-			bp_pass3dt::ADDITIONAL_MESSAGE_BYTE_DELIVERY_WEIGHT,
-			bp_pass3dt::MAX_SINGLE_MESSAGE_DELIVERY_CONFIRMATION_TX_WEIGHT,
-			bp_pass3dt::PAY_INBOUND_DISPATCH_FEE_WEIGHT,
-			DbWeight::get(),
+		type Weights = crate::weights::pallet_bridge_messages::WeightInfo<Runtime>;
+
+		assert_bridge_messages_pallet_weights::<Runtime, WithPass3dMessagesInstance>(
+			AssertBridgeMessagesPalletWeights {
+				default_message_delivery_tx_weight: bp_pass3dt::DEFAULT_MESSAGE_DELIVERY_TX_WEIGHT,
+				additional_message_byte_delivery_weight:
+					bp_pass3dt::ADDITIONAL_MESSAGE_BYTE_DELIVERY_WEIGHT,
+				max_single_message_delivery_confirmation_tx_weight:
+					bp_pass3dt::MAX_SINGLE_MESSAGE_DELIVERY_CONFIRMATION_TX_WEIGHT,
+				pay_inbound_dispatch_fee_weight: bp_pass3dt::PAY_INBOUND_DISPATCH_FEE_WEIGHT,
+			},
 		);
 
 		let max_incoming_message_proof_size = bp_pass3d::EXTRA_STORAGE_PROOF_SIZE.saturating_add(
@@ -353,6 +448,7 @@ mod tests {
 			max_incoming_inbound_lane_data_proof_size,
 			bp_pass3dt::MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX,
 			bp_pass3dt::MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX,
+			crate::MaxMessageResponseSize::get(),
 			DbWeight::get(),
 		);
 	}
@@ -366,6 +462,10 @@ mod tests {
 			bridge: WithPass3dMessageBridge,
 			this_chain: bp_pass3dt::Pass3dt,
 			bridged_chain: bp_pass3d::Pass3d,
+			inbound_payload: pallet_bridge_token_transfer::RawMessagePayload<
+				FromPass3dMessagePayload,
+				bp_pass3d::Balance,
+			>,
 		);
 
 		assert_complete_bridge_constants::<
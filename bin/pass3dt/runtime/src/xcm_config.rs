@@ -18,26 +18,31 @@
 
 use super::{
 	pass3d_messages::{WithPass3dMessageBridge, DEFAULT_XCM_LANE_TO_PASS3D},
-	AccountId, AllPalletsWithSystem, Balances, Call, Event, Origin, Runtime,
-	WithPass3dMessagesInstance,XcmPallet,
+	AccountId, AllPalletsWithSystem, Assets, Balance, Balances, Call, Event, Origin, Runtime,
+	WithPass3dMessagesInstance, XcmPallet,
 };
 use bp_messages::LaneId;
 use bp_pass3dt::WeightToFee;
 use bridge_runtime_common::{
 	messages::source::{XcmBridge, XcmBridgeAdapter},
+	messages_compression::PayloadCompression,
 	CustomNetworkId,
 };
+use codec::Encode;
 use frame_support::{
 	parameter_types,
-	traits::{Everything, Nothing},
-	weights::Weight,
+	traits::{Everything, Get, Nothing, OnRuntimeUpgrade},
+	weights::{constants::WEIGHT_PER_SECOND, Weight},
 };
+use sp_std::{borrow::Borrow, vec, vec::Vec};
 use xcm::latest::prelude::*;
 use xcm_builder::{
-	AccountId32Aliases, AllowKnownQueryResponses, AllowTopLevelPaidExecutionFrom,
-	CurrencyAdapter as XcmCurrencyAdapter, IsConcrete, SignedAccountId32AsNative,
-	SignedToAccountId32, SovereignSignedViaLocation, TakeWeightCredit, UsingComponents,
+	AccountId32Aliases, AllowKnownQueryResponses, AllowTopLevelPaidExecutionFrom, Case,
+	ConvertedConcreteAssetId, CurrencyAdapter as XcmCurrencyAdapter, FixedRateOfFungible,
+	FungiblesAdapter, IsConcrete, SignedAccountId32AsNative, SignedToAccountId32,
+	SovereignSignedViaLocation, TakeWeightCredit, UsingComponents,
 };
+use xcm_executor::traits::{Convert, JustTry};
 
 parameter_types! {
 	/// The location of the `MLAU` token, from the context of this chain. Since this token is native to this
@@ -57,6 +62,116 @@ parameter_types! {
 	pub CheckAccount: AccountId = XcmPallet::check_account();
 }
 
+/// Identifier of the wrapped Pass3d token in the assets pallet.
+pub const WRAPPED_PASS3D_TOKEN_ASSET_ID: crate::AssetId = 0;
+/// Minimal balance of the wrapped Pass3d token.
+pub const WRAPPED_PASS3D_TOKEN_MIN_BALANCE: Balance = 1;
+
+parameter_types! {
+	/// The location of the Pass3d chain, from the context of this chain.
+	pub Pass3dLocation: MultiLocation =
+		MultiLocation::new(1, X1(GlobalConsensus(Pass3dNetwork::get())));
+	/// The location of the Pass3d native token, from the context of this chain. The Pass3d chain
+	/// is the reserve for this token.
+	pub Pass3dTokenLocation: MultiLocation = Pass3dLocation::get();
+	/// Pass3d tokens, that are reserved at the Pass3d chain.
+	pub Pass3dTokenFromPass3d: (MultiAssetFilter, MultiLocation) = (
+		Wild(AllOf { id: Concrete(Pass3dTokenLocation::get()), fun: WildFungible }),
+		Pass3dLocation::get(),
+	);
+	/// Price of the XCM execution, that is bought with wrapped Pass3d tokens. Initially we treat
+	/// both tokens as equal.
+	pub Pass3dTokenPerSecond: (xcm::latest::AssetId, u128) =
+		(Concrete(Pass3dTokenLocation::get()), WEIGHT_PER_SECOND as u128);
+	/// Sovereign account of the Pass3d chain. It holds native tokens of this chain, that have been
+	/// transferred to the Pass3d chain.
+	pub Pass3dSovereignAccount: AccountId =
+		sp_io::hashing::blake2_256(&(b"multiloc", Pass3dLocation::get()).encode()).into();
+}
+
+/// Returns assets that are created at genesis, with their owner, sufficiency flag and minimal
+/// balance.
+pub fn genesis_assets() -> Vec<(crate::AssetId, AccountId, bool, Balance)> {
+	vec![(
+		WRAPPED_PASS3D_TOKEN_ASSET_ID,
+		Pass3dSovereignAccount::get(),
+		true,
+		WRAPPED_PASS3D_TOKEN_MIN_BALANCE,
+	)]
+}
+
+/// Creates assets from the `genesis_assets` at chains that have been started before these assets
+/// have been added to the genesis. Assets that already exist are left untouched.
+pub struct CreateGenesisAssets;
+
+impl OnRuntimeUpgrade for CreateGenesisAssets {
+	fn on_runtime_upgrade() -> Weight {
+		let assets = genesis_assets();
+		let checked_assets = assets.len() as Weight;
+		let mut created_assets: Weight = 0;
+		for (id, owner, is_sufficient, min_balance) in assets {
+			if Assets::maybe_total_supply(id).is_some() {
+				continue
+			}
+
+			match Assets::force_create(Origin::root(), id, owner.into(), is_sufficient, min_balance)
+			{
+				Ok(()) => {
+					log::info!(target: "runtime::bridge", "Created asset {}", id);
+					created_assets += 1;
+				},
+				Err(e) =>
+					log::error!(target: "runtime::bridge", "Failed to create asset {}: {:?}", id, e),
+			}
+		}
+
+		<Runtime as frame_system::Config>::DbWeight::get()
+			.reads_writes(checked_assets, created_assets)
+	}
+}
+
+/// Converts location of the Pass3d chain into its sovereign account.
+pub struct Pass3dAsSovereignAccount;
+
+impl Convert<MultiLocation, AccountId> for Pass3dAsSovereignAccount {
+	fn convert_ref(location: impl Borrow<MultiLocation>) -> Result<AccountId, ()> {
+		if *location.borrow() == Pass3dLocation::get() {
+			Ok(Pass3dSovereignAccount::get())
+		} else {
+			Err(())
+		}
+	}
+
+	fn reverse_ref(account: impl Borrow<AccountId>) -> Result<MultiLocation, ()> {
+		if *account.borrow() == Pass3dSovereignAccount::get() {
+			Ok(Pass3dLocation::get())
+		} else {
+			Err(())
+		}
+	}
+}
+
+/// Converts location of the Pass3d native token into identifier of the wrapped token asset.
+pub struct Pass3dTokenAsAssetId;
+
+impl Convert<MultiLocation, crate::AssetId> for Pass3dTokenAsAssetId {
+	fn convert_ref(location: impl Borrow<MultiLocation>) -> Result<crate::AssetId, ()> {
+		if *location.borrow() == Pass3dTokenLocation::get() {
+			Ok(WRAPPED_PASS3D_TOKEN_ASSET_ID)
+		} else {
+			Err(())
+		}
+	}
+
+	fn reverse_ref(asset_id: impl Borrow<crate::AssetId>) -> Result<MultiLocation, ()> {
+		if *asset_id.borrow() == WRAPPED_PASS3D_TOKEN_ASSET_ID {
+			Ok(Pass3dTokenLocation::get())
+		} else {
+			Err(())
+		}
+	}
+}
+
 /// The canonical means of converting a `MultiLocation` into an `AccountId`, used when we want to
 /// determine the sovereign account controlled by a location.
 pub type SovereignAccountOf = (
@@ -64,6 +179,15 @@ pub type SovereignAccountOf = (
 	AccountId32Aliases<ThisNetwork, AccountId>,
 );
 
+/// The means of converting a `MultiLocation` into an `AccountId`, used by our asset transactors.
+/// In addition to `SovereignAccountOf`, it converts the Pass3d chain location into its sovereign
+/// account.
+pub type LocationToAccountId = (
+	SovereignAccountOf,
+	// Location of the Pass3d chain is converted into its sovereign account.
+	Pass3dAsSovereignAccount,
+);
+
 /// Our asset transactor. This is what allows us to interest with the runtime facilities from the
 /// point of view of XCM-only concepts like `MultiLocation` and `MultiAsset`.
 ///
@@ -74,13 +198,33 @@ pub type LocalAssetTransactor = XcmCurrencyAdapter<
 	// Use this currency when it is a fungible asset matching the given location or name:
 	IsConcrete<TokenLocation>,
 	// We can convert the MultiLocations with our converter above:
-	SovereignAccountOf,
+	LocationToAccountId,
 	// Our chain's account ID type (we can't get away without mentioning it explicitly):
 	AccountId,
 	// We track our teleports in/out to keep total issuance correct.
 	CheckAccount,
 >;
 
+/// Asset transactor of wrapped Pass3d tokens. They're minted when Pass3d tokens are deposited at
+/// the Pass3d chain (which is the reserve) and burned when they're withdrawn.
+pub type Pass3dTokenTransactor = FungiblesAdapter<
+	// Use this fungibles implementation:
+	Assets,
+	// Use this fungibles implementation when it is a fungible asset matching the Pass3d token:
+	ConvertedConcreteAssetId<crate::AssetId, Balance, Pass3dTokenAsAssetId, JustTry>,
+	// We can convert the MultiLocations with our converter above:
+	LocationToAccountId,
+	// Our chain's account ID type (we can't get away without mentioning it explicitly):
+	AccountId,
+	// We don't support teleports of wrapped tokens.
+	Nothing,
+	// The account to use for tracking teleports.
+	CheckAccount,
+>;
+
+/// All asset transactors of this chain.
+pub type AssetTransactors = (LocalAssetTransactor, Pass3dTokenTransactor);
+
 /// The means that we convert the XCM message origin location into a local dispatch origin.
 type LocalOriginConverter = (
 	// A `Signed` origin of the sovereign account that the original location controls.
@@ -128,15 +272,20 @@ pub struct XcmConfig;
 impl xcm_executor::Config for XcmConfig {
 	type Call = Call;
 	type XcmSender = XcmRouter;
-	type AssetTransactor = LocalAssetTransactor;
+	type AssetTransactor = AssetTransactors;
 	type OriginConverter = LocalOriginConverter;
-	type IsReserve = ();
+	// The Pass3d chain is the reserve for its native token.
+	type IsReserve = Case<Pass3dTokenFromPass3d>;
 	type IsTeleporter = ();
 	type UniversalLocation = UniversalLocation;
 	type Barrier = Barrier;
 	type Weigher = XcmWeigher;
-	// The weight trader piggybacks on the existing transaction-fee conversion logic.
-	type Trader = UsingComponents<WeightToFee, TokenLocation, AccountId, Balances, ()>;
+	type Trader = (
+		// The weight trader piggybacks on the existing transaction-fee conversion logic.
+		UsingComponents<WeightToFee, TokenLocation, AccountId, Balances, ()>,
+		// Execution may also be bought with wrapped Pass3d tokens.
+		FixedRateOfFungible<Pass3dTokenPerSecond, ()>,
+	);
 	type ResponseHandler = XcmPallet;
 	type AssetTrap = XcmPallet;
 	type AssetLocker = ();
@@ -210,9 +359,19 @@ impl XcmBridge for ToPass3dBridge {
 		dest.relative_to(&here)
 	}
 
+	fn build_origin() -> MultiLocation {
+		let dest: InteriorMultiLocation = Pass3dNetwork::get().into();
+		let here = UniversalLocation::get();
+		here.relative_to(&dest)
+	}
+
 	fn xcm_lane() -> LaneId {
 		DEFAULT_XCM_LANE_TO_PASS3D
 	}
+
+	fn payload_compression() -> Option<PayloadCompression> {
+		Some(PayloadCompression::Lz4)
+	}
 }
 
 #[cfg(test)]
@@ -223,13 +382,30 @@ mod tests {
 		MessageKey,
 	};
 	use bp_runtime::messages::MessageDispatchResult;
-	use bridge_runtime_common::messages::target::FromBridgedChainMessageDispatch;
-	use codec::Encode;
+	use bridge_runtime_common::messages::target::{
+		FromBridgedChainMessageDispatch, FromBridgedChainMessageDispatchWithResponse,
+		FromBridgedChainMessageResponse,
+	};
+	use codec::{Decode, Encode};
+	use frame_support::{assert_noop, assert_ok, traits::Currency};
+	use xcm::{VersionedMultiAssets, VersionedMultiLocation};
 
 	fn new_test_ext() -> sp_io::TestExternalities {
-		sp_io::TestExternalities::new(
-			frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap(),
-		)
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		pallet_bridge_messages::GenesisConfig::<Runtime, crate::WithPass3dMessagesInstance> {
+			opened_lanes: crate::pass3d_messages::genesis_opened_lanes(),
+			..Default::default()
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+		pallet_assets::GenesisConfig::<Runtime> {
+			assets: genesis_assets(),
+			metadata: vec![],
+			accounts: vec![],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+		sp_io::TestExternalities::new(t)
 	}
 
 	#[test]
@@ -253,6 +429,77 @@ mod tests {
 		})
 	}
 
+	#[test]
+	fn large_xcm_messages_are_compressed_by_bridge_router() {
+		use bridge_runtime_common::{
+			messages::target::FromBridgedChainMessagePayload,
+			messages_compression::is_compressed_payload,
+		};
+
+		new_test_ext().execute_with(|| {
+			let xcm: Xcm<()> = vec![Instruction::Trap(42); 64].into();
+			let dest = (Parent, X1(GlobalConsensus(Pass3dNetwork::get())));
+			assert_ok!(send_xcm::<XcmRouter>(dest.into(), xcm));
+
+			let message = pallet_bridge_messages::Pallet::<
+				Runtime,
+				WithPass3dMessagesInstance,
+			>::outbound_message_data(DEFAULT_XCM_LANE_TO_PASS3D, 1)
+			.unwrap();
+			let sent_payload = Vec::<u8>::decode(&mut &message.payload[..]).unwrap();
+			assert!(is_compressed_payload(&sent_payload));
+
+			let payload =
+				FromBridgedChainMessagePayload::<Call>::decode(&mut &message.payload[..]).unwrap();
+			assert_eq!(payload.xcm.1, vec![Instruction::Trap(42); 64].into());
+			assert!(payload.decompressed_size.unwrap() > sent_payload.len() as u32);
+		})
+	}
+
+	#[test]
+	fn xcm_message_initiator_is_the_account_that_has_sent_xcm() {
+		use bp_messages::source_chain::MessageInitiator;
+		use bridge_runtime_common::{
+			messages::source::XcmMessageInitiator,
+			messages_compression::{compress_payload, PayloadCompression},
+		};
+
+		type Initiator = XcmMessageInitiator<LocationToAccountId>;
+
+		let sender = AccountId::from([1u8; 32]);
+		let xcm: Xcm<()> = vec![
+			Instruction::DescendOrigin(X1(AccountId32 { network: None, id: [1u8; 32] })),
+			Instruction::Trap(42),
+		]
+		.into();
+		let payload = (ToPass3dBridge::build_origin(), xcm).encode();
+		let xcm_origin: Origin =
+			pallet_xcm::Origin::Xcm(MultiLocation::from(UniversalLocation::get())).into();
+
+		// initiator is found in both raw and compressed payloads
+		let lane = DEFAULT_XCM_LANE_TO_PASS3D;
+		assert_eq!(
+			Initiator::message_initiator(&xcm_origin, &lane, &payload),
+			Some(sender.clone())
+		);
+		assert_eq!(
+			Initiator::message_initiator(
+				&xcm_origin,
+				&lane,
+				&compress_payload(PayloadCompression::Lz4, &payload),
+			),
+			Some(sender.clone()),
+		);
+
+		// messages that are not sent by the XCM pallet have no XCM initiator
+		assert_eq!(Initiator::message_initiator(&Origin::signed(sender), &lane, &payload), None);
+
+		// messages that are sent by this chain itself have no initiator
+		let xcm: Xcm<()> = vec![Instruction::Trap(42)].into();
+		let payload = (ToPass3dBridge::build_origin(), xcm).encode();
+		assert_eq!(Initiator::message_initiator(&xcm_origin, &lane, &payload), None);
+	}
+
 	#[test]
 	fn xcm_messages_from_pass3d_are_dispatched() {
 		type XcmExecutor = xcm_executor::XcmExecutor<XcmConfig>;
@@ -270,7 +517,11 @@ mod tests {
 
 			let mut incoming_message = DispatchMessage {
 				key: MessageKey { lane_id: [0, 0, 0, 0], nonce: 1 },
-				data: DispatchMessageData { payload: Ok((location, xcm).into()), fee: 0 },
+				data: DispatchMessageData {
+					payload: Ok((location, xcm).into()),
+					fee: 0,
+					is_cancelled: false,
+				},
 			};
 
 			let dispatch_weight = MessageDispatcher::dispatch_weight(&mut incoming_message);
@@ -284,8 +535,298 @@ mod tests {
 					dispatch_result: true,
 					unspent_weight: 0,
 					dispatch_fee_paid_during_dispatch: false,
+					response: None,
 				}
 			);
 		})
 	}
+
+	#[test]
+	fn compressed_xcm_messages_from_pass3d_are_dispatched() {
+		use bridge_runtime_common::{
+			messages::target::FromBridgedChainMessagePayload,
+			messages_compression::{compress_payload, decompression_weight, PayloadCompression},
+		};
+
+		type XcmExecutor = xcm_executor::XcmExecutor<XcmConfig>;
+		type MessageDispatcher = FromBridgedChainMessageDispatch<
+			WithPass3dMessageBridge,
+			XcmExecutor,
+			XcmWeigher,
+			frame_support::traits::ConstU64<BASE_XCM_WEIGHT>,
+		>;
+
+		new_test_ext().execute_with(|| {
+			let location: MultiLocation =
+				(Parent, X1(GlobalConsensus(Pass3dNetwork::get()))).into();
+			let xcm: Xcm<Call> = vec![Instruction::Trap(42)].into();
+			let raw_payload = (location, xcm).encode();
+			let compressed_payload = compress_payload(PayloadCompression::Lz4, &raw_payload);
+			let payload = FromBridgedChainMessagePayload::<Call>::decode(
+				&mut &compressed_payload.encode()[..],
+			)
+			.unwrap();
+			assert_eq!(payload.decompressed_size, Some(raw_payload.len() as u32));
+
+			let mut incoming_message = DispatchMessage {
+				key: MessageKey { lane_id: [0, 0, 0, 0], nonce: 1 },
+				data: DispatchMessageData { payload: Ok(payload), fee: 0, is_cancelled: false },
+			};
+
+			let dispatch_weight = MessageDispatcher::dispatch_weight(&mut incoming_message);
+			assert_eq!(
+				dispatch_weight,
+				1_000_000_000 + decompression_weight(raw_payload.len() as u32),
+			);
+			assert_eq!(incoming_message.data.payload.as_ref().unwrap().weight, Some(1_000_000_000));
+		})
+	}
+
+	#[test]
+	fn xcm_messages_from_pass3d_are_dispatched_with_response() {
+		type XcmExecutor = xcm_executor::XcmExecutor<XcmConfig>;
+		type MessageDispatcher = FromBridgedChainMessageDispatchWithResponse<
+			WithPass3dMessageBridge,
+			XcmExecutor,
+			XcmWeigher,
+			frame_support::traits::ConstU64<BASE_XCM_WEIGHT>,
+		>;
+
+		new_test_ext().execute_with(|| {
+			let location: MultiLocation =
+				(Parent, X1(GlobalConsensus(Pass3dNetwork::get()))).into();
+			let xcm: Xcm<Call> = vec![Instruction::Trap(42)].into();
+
+			let mut incoming_message = DispatchMessage {
+				key: MessageKey { lane_id: [0, 0, 0, 0], nonce: 1 },
+				data: DispatchMessageData {
+					payload: Ok((location, xcm).into()),
+					fee: 0,
+					is_cancelled: false,
+				},
+			};
+
+			let dispatch_weight = MessageDispatcher::dispatch_weight(&mut incoming_message);
+			assert_eq!(dispatch_weight, 1_000_000_000);
+
+			let dispatch_result =
+				MessageDispatcher::dispatch(&AccountId::from([0u8; 32]), incoming_message);
+			let response = FromBridgedChainMessageResponse::decode(
+				&mut &dispatch_result.response.expect("response is always returned")[..],
+			)
+			.unwrap();
+			assert!(matches!(response, FromBridgedChainMessageResponse::Executed(_)));
+		})
+	}
+
+	#[test]
+	fn undecodable_messages_from_pass3d_are_dispatched_with_response() {
+		type XcmExecutor = xcm_executor::XcmExecutor<XcmConfig>;
+		type MessageDispatcher = FromBridgedChainMessageDispatchWithResponse<
+			WithPass3dMessageBridge,
+			XcmExecutor,
+			XcmWeigher,
+			frame_support::traits::ConstU64<BASE_XCM_WEIGHT>,
+		>;
+
+		new_test_ext().execute_with(|| {
+			let incoming_message = DispatchMessage {
+				key: MessageKey { lane_id: [0, 0, 0, 0], nonce: 1 },
+				data: DispatchMessageData {
+					payload: Err(codec::Error::from("test")),
+					fee: 0,
+					is_cancelled: false,
+				},
+			};
+
+			let dispatch_result =
+				MessageDispatcher::dispatch(&AccountId::from([0u8; 32]), incoming_message);
+			assert_eq!(
+				dispatch_result.response,
+				Some(FromBridgedChainMessageResponse::UndecodablePayload.encode()),
+			);
+		})
+	}
+
+	#[test]
+	fn reserve_transfer_assets_exports_message_to_pass3d() {
+		new_test_ext().execute_with(|| {
+			let sender = AccountId::from([1u8; 32]);
+			let amount: Balance = 1_000_000_000_000;
+			let _ = Balances::deposit_creating(&sender, 2 * amount);
+
+			let beneficiary: MultiLocation =
+				X1(AccountId32 { network: Pass3dNetwork::get(), id: [2u8; 32] }).into();
+			assert_ok!(XcmPallet::reserve_transfer_assets(
+				Origin::signed(sender.clone()),
+				Box::new(VersionedMultiLocation::from(Pass3dLocation::get())),
+				Box::new(VersionedMultiLocation::from(beneficiary)),
+				Box::new(VersionedMultiAssets::from(MultiAssets::from((Here, amount)))),
+				0,
+			));
+
+			// transferred tokens are reserved at the sovereign account of Pass3d chain and the
+			// sender has also paid for the message delivery
+			assert_eq!(Balances::free_balance(&Pass3dSovereignAccount::get()), amount);
+			assert!(Balances::free_balance(&sender) < amount);
+
+			// message is sent over the bridge and the Pass3d chain sees that it comes from us
+			let message = pallet_bridge_messages::OutboundMessages::<
+				Runtime,
+				WithPass3dMessagesInstance,
+			>::get(MessageKey { lane_id: ToPass3dBridge::xcm_lane(), nonce: 1 })
+			.expect("message is sent");
+			let payload = Vec::<u8>::decode(&mut &message.payload[..]).unwrap();
+			let (origin, xcm) = <(MultiLocation, Xcm<()>)>::decode(&mut &payload[..]).unwrap();
+			let this_chain_location =
+				MultiLocation::new(1, X1(GlobalConsensus(ThisNetwork::get())));
+			assert_eq!(origin, this_chain_location);
+			assert_eq!(
+				xcm.0.first(),
+				Some(&ReserveAssetDeposited(MultiAssets::from((this_chain_location, amount)))),
+			);
+		})
+	}
+
+	#[test]
+	fn reserve_asset_deposited_from_pass3d_mints_wrapped_tokens() {
+		type XcmExecutor = xcm_executor::XcmExecutor<XcmConfig>;
+		type MessageDispatcher = FromBridgedChainMessageDispatch<
+			WithPass3dMessageBridge,
+			XcmExecutor,
+			XcmWeigher,
+			frame_support::traits::ConstU64<0>,
+		>;
+
+		new_test_ext().execute_with(|| {
+			let beneficiary = AccountId::from([2u8; 32]);
+			let amount: Balance = 1_000_000_000_000;
+			let xcm: Xcm<Call> = vec![
+				ReserveAssetDeposited(MultiAssets::from((Pass3dTokenLocation::get(), amount))),
+				ClearOrigin,
+				BuyExecution {
+					fees: (Pass3dTokenLocation::get(), amount).into(),
+					weight_limit: Unlimited,
+				},
+				DepositAsset {
+					assets: Wild(AllCounted(1)),
+					beneficiary: X1(AccountId32 { network: ThisNetwork::get(), id: [2u8; 32] })
+						.into(),
+				},
+			]
+			.into();
+
+			let mut incoming_message = DispatchMessage {
+				key: MessageKey { lane_id: ToPass3dBridge::xcm_lane(), nonce: 1 },
+				data: DispatchMessageData {
+					payload: Ok((Pass3dLocation::get(), xcm).into()),
+					fee: 0,
+					is_cancelled: false,
+				},
+			};
+			MessageDispatcher::dispatch_weight(&mut incoming_message);
+			MessageDispatcher::dispatch(&AccountId::from([0u8; 32]), incoming_message);
+
+			// execution of all 4 instructions is paid with wrapped tokens
+			assert_eq!(
+				Assets::balance(WRAPPED_PASS3D_TOKEN_ASSET_ID, &beneficiary),
+				amount - 4 * BASE_XCM_WEIGHT as Balance,
+			);
+		})
+	}
+
+	#[test]
+	fn reserve_asset_deposited_from_other_location_is_rejected() {
+		type XcmExecutor = xcm_executor::XcmExecutor<XcmConfig>;
+		type MessageDispatcher = FromBridgedChainMessageDispatch<
+			WithPass3dMessageBridge,
+			XcmExecutor,
+			XcmWeigher,
+			frame_support::traits::ConstU64<0>,
+		>;
+
+		new_test_ext().execute_with(|| {
+			let beneficiary = AccountId::from([2u8; 32]);
+			let amount: Balance = 1_000_000_000_000;
+			let xcm: Xcm<Call> = vec![
+				ReserveAssetDeposited(MultiAssets::from((Pass3dTokenLocation::get(), amount))),
+				ClearOrigin,
+				BuyExecution {
+					fees: (Pass3dTokenLocation::get(), amount).into(),
+					weight_limit: Unlimited,
+				},
+				DepositAsset {
+					assets: Wild(AllCounted(1)),
+					beneficiary: X1(AccountId32 { network: ThisNetwork::get(), id: [2u8; 32] })
+						.into(),
+				},
+			]
+			.into();
+
+			// the message is sent by some account of the Pass3d chain, not by the chain itself
+			let origin = MultiLocation::new(
+				1,
+				X2(
+					GlobalConsensus(Pass3dNetwork::get()),
+					AccountId32 { network: Pass3dNetwork::get(), id: [3u8; 32] },
+				),
+			);
+			let mut incoming_message = DispatchMessage {
+				key: MessageKey { lane_id: ToPass3dBridge::xcm_lane(), nonce: 1 },
+				data: DispatchMessageData {
+					payload: Ok((origin, xcm).into()),
+					fee: 0,
+					is_cancelled: false,
+				},
+			};
+			MessageDispatcher::dispatch_weight(&mut incoming_message);
+			MessageDispatcher::dispatch(&AccountId::from([0u8; 32]), incoming_message);
+
+			assert_eq!(Assets::balance(WRAPPED_PASS3D_TOKEN_ASSET_ID, &beneficiary), 0);
+		})
+	}
+
+	#[test]
+	fn forged_xcm_message_from_regular_account_is_rejected() {
+		new_test_ext().execute_with(|| {
+			let sender = AccountId::from([1u8; 32]);
+			let amount: Balance = 1_000_000_000_000;
+			let _ = Balances::deposit_creating(&sender, 2 * amount);
+
+			// the message claims that it comes from this chain, so the Pass3d chain would
+			// mint wrapped tokens if we'd send it
+			let this_chain_location =
+				MultiLocation::new(1, X1(GlobalConsensus(ThisNetwork::get())));
+			let xcm: Xcm<()> =
+				vec![ReserveAssetDeposited(MultiAssets::from((this_chain_location, amount)))]
+					.into();
+			assert_noop!(
+				crate::BridgePass3dMessages::send_message(
+					Origin::signed(sender),
+					ToPass3dBridge::xcm_lane(),
+					(this_chain_location, xcm).encode(),
+					amount,
+				),
+				pallet_bridge_messages::Error::<Runtime, WithPass3dMessagesInstance>::MessageRejectedByLaneVerifier,
+			);
+		})
+	}
+
+	#[test]
+	fn genesis_assets_are_created_by_migration() {
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		pallet_assets::GenesisConfig::<Runtime>::default()
+			.assimilate_storage(&mut t)
+			.unwrap();
+		sp_io::TestExternalities::new(t).execute_with(|| {
+			assert_eq!(Assets::maybe_total_supply(WRAPPED_PASS3D_TOKEN_ASSET_ID), None);
+
+			CreateGenesisAssets::on_runtime_upgrade();
+			assert_eq!(Assets::maybe_total_supply(WRAPPED_PASS3D_TOKEN_ASSET_ID), Some(0));
+
+			// second run leaves existing assets untouched
+			CreateGenesisAssets::on_runtime_upgrade();
+			assert_eq!(Assets::maybe_total_supply(WRAPPED_PASS3D_TOKEN_ASSET_ID), Some(0));
+		})
+	}
 }
@@ -124,6 +124,12 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	(
+		pallet_bridge_relayers::migration::v1::MigrateToV1<Runtime, RelayerRewardsMigrationLane>,
+		pallet_bridge_grandpa::migration::v1::MigrateToV1<Runtime, MillauGrandpaInstance>,
+		pallet_bridge_messages::migration::v1::MigrateToV1<Runtime, WithMillauMessagesInstance, ()>,
+		pallet_bridge_messages::migration::v2::MigrateToV2<Runtime, WithMillauMessagesInstance>,
+	),
 >;
 
 impl_opaque_keys! {
@@ -512,13 +518,33 @@ impl pallet_aura::Config for Runtime {
 	type MaxAuthorities = MaxAuthorities;
 }
 
+parameter_types! {
+	/// Stake that is reserved when relayer is registered.
+	pub const RelayerStake: Balance = 1_000_000;
+	/// Minimal period of relayer registration.
+	pub const RelayerRegistrationLease: BlockNumber = DAYS;
+	/// Priority boost of bridge transactions, submitted by registered relayers.
+	pub const RegisteredRelayerPriorityBoost: sp_runtime::transaction_validity::TransactionPriority =
+		1_000_000;
+}
+
 impl pallet_bridge_relayers::Config for Runtime {
 	type Event = Event;
 	type Reward = Balance;
 	type PaymentProcedure = bp_relayers::MintReward<pallet_balances::Pallet<Runtime>, AccountId>;
+	type Currency = Balances;
+	type RequiredStake = RelayerStake;
+	type RequiredRegistrationLease = RelayerRegistrationLease;
+	type RegisteredRelayerPriorityBoost = RegisteredRelayerPriorityBoost;
+	type Slash = ();
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	/// Lane, where rewards are moved to during `pallet_bridge_relayers` storage migration.
+	pub const RelayerRewardsMigrationLane: bp_messages::LaneId = DEFAULT_XCM_LANE_TO_MILLAU;
+}
+
 parameter_types! {
 	/// This is a pretty unscientific cap.
 	///
@@ -535,6 +561,7 @@ parameter_types! {
 
 pub type MillauGrandpaInstance = ();
 impl pallet_bridge_grandpa::Config for Runtime {
+	type Event = Event;
 	type BridgedChain = bp_millau::Millau;
 	type MaxRequests = MaxRequests;
 	type HeadersToKeep = HeadersToKeep;
@@ -547,6 +574,11 @@ parameter_types! {
 		bp_millau::MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX;
 	pub const MaxUnconfirmedMessagesAtInboundLane: bp_messages::MessageNonce =
 		bp_millau::MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX;
+	pub const MaxFailedDispatchesAtInboundLane: u32 = 16;
+	pub const MaxFailedDispatchPayloadSize: u32 = 16 * 1024;
+	pub const FailedDispatchLifetime: BlockNumber = DAYS;
+	pub const ConfirmedMessageStatusLifetime: BlockNumber = DAYS;
+	pub const MaxMessageResponseSize: u32 = 128;
 	// `IdentityFee` is used by Rialto => we may use weight directly
 	pub const GetDeliveryConfirmationTransactionFee: Balance =
 		bp_rialto_parachain::MAX_SINGLE_MESSAGE_DELIVERY_CONFIRMATION_TX_WEIGHT as _;
@@ -564,6 +596,11 @@ impl pallet_bridge_messages::Config<WithMillauMessagesInstance> for Runtime {
 	type MaxMessagesToPruneAtOnce = MaxMessagesToPruneAtOnce;
 	type MaxUnrewardedRelayerEntriesAtInboundLane = MaxUnrewardedRelayerEntriesAtInboundLane;
 	type MaxUnconfirmedMessagesAtInboundLane = MaxUnconfirmedMessagesAtInboundLane;
+	type MaxFailedDispatchesAtInboundLane = MaxFailedDispatchesAtInboundLane;
+	type MaxFailedDispatchPayloadSize = MaxFailedDispatchPayloadSize;
+	type FailedDispatchLifetime = FailedDispatchLifetime;
+	type ConfirmedMessageStatusLifetime = ConfirmedMessageStatusLifetime;
+	type MaxMessageResponseSize = MaxMessageResponseSize;
 
 	type MaximalOutboundPayloadSize = crate::millau_messages::ToMillauMaximalOutboundPayloadSize;
 	type OutboundPayload = crate::millau_messages::ToMillauMessagePayload;
@@ -583,6 +620,7 @@ impl pallet_bridge_messages::Config<WithMillauMessagesInstance> for Runtime {
 		>;
 	type OnMessageAccepted = ();
 	type OnDeliveryConfirmed = ();
+	type OnMessageResponse = ();
 
 	type SourceHeaderChain = crate::millau_messages::Millau;
 	type MessageDispatch = crate::millau_messages::FromMillauMessageDispatch;
@@ -618,7 +656,7 @@ construct_runtime!(
 
 		// Millau bridge modules.
 		BridgeRelayers: pallet_bridge_relayers::{Pallet, Call, Storage, Event<T>},
-		BridgeMillauGrandpa: pallet_bridge_grandpa::{Pallet, Call, Storage},
+		BridgeMillauGrandpa: pallet_bridge_grandpa::{Pallet, Call, Storage, Event<T>},
 		BridgeMillauMessages: pallet_bridge_messages::{Pallet, Call, Storage, Event<T>, Config<T>},
 	}
 );
@@ -736,6 +774,22 @@ impl_runtime_apis! {
 		fn best_finalized() -> Option<HeaderId<bp_millau::Hash, bp_millau::BlockNumber>> {
 			BridgeMillauGrandpa::best_finalized().map(|header| header.id())
 		}
+
+		fn finalized_header_at(
+			number: bp_millau::BlockNumber,
+		) -> Option<HeaderId<bp_millau::Hash, bp_millau::BlockNumber>> {
+			BridgeMillauGrandpa::finalized_header_at(number).map(|hash| HeaderId(number, hash))
+		}
+
+		fn imported_headers_range(
+			from: bp_millau::BlockNumber,
+			to: bp_millau::BlockNumber,
+		) -> Vec<HeaderId<bp_millau::Hash, bp_millau::BlockNumber>> {
+			BridgeMillauGrandpa::imported_headers_range(from, to)
+				.into_iter()
+				.map(|(number, hash)| HeaderId(number, hash))
+				.collect()
+		}
 	}
 
 	impl bp_millau::ToMillauOutboundLaneApi<Block, Balance, ToMillauMessagePayload> for Runtime {
@@ -761,6 +815,10 @@ impl_runtime_apis! {
 				WithMillauMessagesInstance,
 			>(lane, begin, end)
 		}
+
+		fn lane_operating_mode(lane: bp_messages::LaneId) -> bp_messages::MessagesOperatingMode {
+			pallet_bridge_messages::Pallet::<Runtime, WithMillauMessagesInstance>::lane_operating_mode(lane)
+		}
 	}
 
 	impl bp_millau::FromMillauInboundLaneApi<Block, bp_millau::Balance> for Runtime {
@@ -895,7 +953,11 @@ mod tests {
 
 			let mut incoming_message = DispatchMessage {
 				key: MessageKey { lane_id: [0, 0, 0, 0], nonce: 1 },
-				data: DispatchMessageData { payload: Ok((location, xcm).into()), fee: 0 },
+				data: DispatchMessageData {
+					payload: Ok((location, xcm).into()),
+					fee: 0,
+					is_cancelled: false,
+				},
 			};
 
 			let dispatch_weight = MessageDispatcher::dispatch_weight(&mut incoming_message);
@@ -909,6 +971,7 @@ mod tests {
 					dispatch_result: true,
 					unspent_weight: 0,
 					dispatch_fee_paid_during_dispatch: false,
+					response: None,
 				}
 			);
 		})
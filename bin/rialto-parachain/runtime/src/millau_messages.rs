@@ -22,9 +22,10 @@
 use crate::{OriginCaller, Runtime};
 
 use bp_messages::{
-	source_chain::{SenderOrigin, TargetHeaderChain},
+	source_chain::{MessagesResponses, SenderOrigin, TargetHeaderChain},
 	target_chain::{ProvedMessages, SourceHeaderChain},
 	InboundLaneData, LaneId, Message, MessageNonce, Parameter as MessagesParameter,
+	VerificationError,
 };
 use bp_runtime::{Chain, ChainId, MILLAU_CHAIN_ID, RIALTO_PARACHAIN_CHAIN_ID};
 use bridge_runtime_common::messages::{
@@ -77,6 +78,8 @@ pub type FromMillauMessageDispatch = messages::target::FromBridgedChainMessageDi
 	// 2 XCM instructions is for simple `Trap(42)` program, coming through bridge
 	// (it is prepended with `UniversalOrigin` instruction)
 	frame_support::traits::ConstU64<BASE_XCM_WEIGHT_TWICE>,
+	// failed dispatch may be retried by the account, derived from the message origin
+	crate::LocationToAccountId,
 >;
 
 /// Messages proof for Millau -> RialtoParachain messages.
@@ -244,7 +247,7 @@ impl messages::BridgedChainWithMessages for Millau {
 }
 
 impl TargetHeaderChain<ToMillauMessagePayload, bp_rialto_parachain::AccountId> for Millau {
-	type Error = &'static str;
+	type Error = VerificationError;
 	// The proof is:
 	// - hash of the header this proof has been created with;
 	// - the storage proof of one or several keys;
@@ -253,11 +256,15 @@ impl TargetHeaderChain<ToMillauMessagePayload, bp_rialto_parachain::AccountId> f
 
 	fn verify_message(payload: &ToMillauMessagePayload) -> Result<(), Self::Error> {
 		messages::source::verify_chain_message::<WithMillauMessageBridge>(payload)
+			.map_err(Into::into)
 	}
 
 	fn verify_messages_delivery_proof(
 		proof: Self::MessagesDeliveryProof,
-	) -> Result<(LaneId, InboundLaneData<bp_rialto_parachain::AccountId>), Self::Error> {
+	) -> Result<
+		(LaneId, InboundLaneData<bp_rialto_parachain::AccountId>, MessagesResponses),
+		Self::Error,
+	> {
 		messages::source::verify_messages_delivery_proof::<
 			WithMillauMessageBridge,
 			Runtime,
@@ -267,7 +274,7 @@ impl TargetHeaderChain<ToMillauMessagePayload, bp_rialto_parachain::AccountId> f
 }
 
 impl SourceHeaderChain<bp_millau::Balance> for Millau {
-	type Error = &'static str;
+	type Error = VerificationError;
 	// The proof is:
 	// - hash of the header this proof has been created with;
 	// - the storage proof of one or several keys;
@@ -398,13 +398,33 @@ impl pallet_authority_discovery::Config for Runtime {
 	type MaxAuthorities = MaxAuthorities;
 }
 
+parameter_types! {
+	/// Stake that is reserved when relayer is registered.
+	pub const RelayerStake: Balance = 1_000_000;
+	/// Minimal period of relayer registration.
+	pub const RelayerRegistrationLease: BlockNumber = bp_rialto::DAYS;
+	/// Priority boost of bridge transactions, submitted by registered relayers.
+	pub const RegisteredRelayerPriorityBoost: sp_runtime::transaction_validity::TransactionPriority =
+		1_000_000;
+}
+
 impl pallet_bridge_relayers::Config for Runtime {
 	type Event = Event;
 	type Reward = Balance;
 	type PaymentProcedure = bp_relayers::MintReward<pallet_balances::Pallet<Runtime>, AccountId>;
+	type Currency = Balances;
+	type RequiredStake = RelayerStake;
+	type RequiredRegistrationLease = RelayerRegistrationLease;
+	type RegisteredRelayerPriorityBoost = RegisteredRelayerPriorityBoost;
+	type Slash = ();
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	/// Lane, where rewards are moved to during `pallet_bridge_relayers` storage migration.
+	pub const RelayerRewardsMigrationLane: bp_messages::LaneId = crate::millau_messages::DEFAULT_XCM_LANE_TO_MILLAU;
+}
+
 parameter_types! {
 	/// This is a pretty unscientific cap.
 	///
@@ -421,6 +441,7 @@ parameter_types! {
 
 pub type MillauGrandpaInstance = ();
 impl pallet_bridge_grandpa::Config for Runtime {
+	type Event = Event;
 	type BridgedChain = bp_millau::Millau;
 	type MaxRequests = MaxRequests;
 	type HeadersToKeep = HeadersToKeep;
@@ -435,6 +456,11 @@ parameter_types! {
 		bp_millau::MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX;
 	pub const MaxUnconfirmedMessagesAtInboundLane: bp_messages::MessageNonce =
 		bp_millau::MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX;
+	pub const MaxFailedDispatchesAtInboundLane: u32 = 16;
+	pub const MaxFailedDispatchPayloadSize: u32 = 16 * 1024;
+	pub const FailedDispatchLifetime: BlockNumber = bp_rialto::DAYS;
+	pub const ConfirmedMessageStatusLifetime: BlockNumber = bp_rialto::DAYS;
+	pub const MaxMessageResponseSize: u32 = 128;
 	// `IdentityFee` is used by Rialto => we may use weight directly
 	pub const GetDeliveryConfirmationTransactionFee: Balance =
 		bp_rialto::MAX_SINGLE_MESSAGE_DELIVERY_CONFIRMATION_TX_WEIGHT as _;
@@ -452,6 +478,11 @@ impl pallet_bridge_messages::Config<WithMillauMessagesInstance> for Runtime {
 	type MaxMessagesToPruneAtOnce = MaxMessagesToPruneAtOnce;
 	type MaxUnrewardedRelayerEntriesAtInboundLane = MaxUnrewardedRelayerEntriesAtInboundLane;
 	type MaxUnconfirmedMessagesAtInboundLane = MaxUnconfirmedMessagesAtInboundLane;
+	type MaxFailedDispatchesAtInboundLane = MaxFailedDispatchesAtInboundLane;
+	type MaxFailedDispatchPayloadSize = MaxFailedDispatchPayloadSize;
+	type FailedDispatchLifetime = FailedDispatchLifetime;
+	type ConfirmedMessageStatusLifetime = ConfirmedMessageStatusLifetime;
+	type MaxMessageResponseSize = MaxMessageResponseSize;
 
 	type MaximalOutboundPayloadSize = crate::millau_messages::ToMillauMaximalOutboundPayloadSize;
 	type OutboundPayload = crate::millau_messages::ToMillauMessagePayload;
@@ -471,6 +502,7 @@ impl pallet_bridge_messages::Config<WithMillauMessagesInstance> for Runtime {
 		>;
 	type OnMessageAccepted = ();
 	type OnDeliveryConfirmed = ();
+	type OnMessageResponse = ();
 
 	type SourceHeaderChain = crate::millau_messages::Millau;
 	type MessageDispatch = crate::millau_messages::FromMillauMessageDispatch;
@@ -506,7 +538,7 @@ construct_runtime!(
 
 		// Millau bridge modules.
 		BridgeRelayers: pallet_bridge_relayers::{Pallet, Call, Storage, Event<T>},
-		BridgeMillauGrandpa: pallet_bridge_grandpa::{Pallet, Call, Storage},
+		BridgeMillauGrandpa: pallet_bridge_grandpa::{Pallet, Call, Storage, Event<T>},
 		BridgeMillauMessages: pallet_bridge_messages::{Pallet, Call, Storage, Event<T>, Config<T>},
 
 		// Parachain modules.
@@ -567,6 +599,12 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	(
+		pallet_bridge_relayers::migration::v1::MigrateToV1<Runtime, RelayerRewardsMigrationLane>,
+		pallet_bridge_grandpa::migration::v1::MigrateToV1<Runtime, MillauGrandpaInstance>,
+		pallet_bridge_messages::migration::v1::MigrateToV1<Runtime, WithMillauMessagesInstance, ()>,
+		pallet_bridge_messages::migration::v2::MigrateToV2<Runtime, WithMillauMessagesInstance>,
+	),
 >;
 
 impl_runtime_apis! {
@@ -699,6 +737,22 @@ impl_runtime_apis! {
 		fn best_finalized() -> Option<HeaderId<bp_millau::Hash, bp_millau::BlockNumber>> {
 			BridgeMillauGrandpa::best_finalized().map(|header| header.id())
 		}
+
+		fn finalized_header_at(
+			number: bp_millau::BlockNumber,
+		) -> Option<HeaderId<bp_millau::Hash, bp_millau::BlockNumber>> {
+			BridgeMillauGrandpa::finalized_header_at(number).map(|hash| HeaderId(number, hash))
+		}
+
+		fn imported_headers_range(
+			from: bp_millau::BlockNumber,
+			to: bp_millau::BlockNumber,
+		) -> Vec<HeaderId<bp_millau::Hash, bp_millau::BlockNumber>> {
+			BridgeMillauGrandpa::imported_headers_range(from, to)
+				.into_iter()
+				.map(|(number, hash)| HeaderId(number, hash))
+				.collect()
+		}
 	}
 
 	impl sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block> for Runtime {
@@ -954,6 +1008,10 @@ impl_runtime_apis! {
 				WithMillauMessagesInstance,
 			>(lane, begin, end)
 		}
+
+		fn lane_operating_mode(lane: bp_messages::LaneId) -> bp_messages::MessagesOperatingMode {
+			pallet_bridge_messages::Pallet::<Runtime, WithMillauMessagesInstance>::lane_operating_mode(lane)
+		}
 	}
 
 	impl bp_millau::FromMillauInboundLaneApi<Block, bp_millau::Balance> for Runtime {
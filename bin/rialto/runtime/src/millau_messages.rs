@@ -19,9 +19,10 @@
 use crate::{Call, OriginCaller, Runtime};
 
 use bp_messages::{
-	source_chain::{SenderOrigin, TargetHeaderChain},
+	source_chain::{MessagesResponses, SenderOrigin, TargetHeaderChain},
 	target_chain::{ProvedMessages, SourceHeaderChain},
 	InboundLaneData, LaneId, Message, MessageNonce, Parameter as MessagesParameter,
+	VerificationError,
 };
 use bp_runtime::{Chain, ChainId, MILLAU_CHAIN_ID, RIALTO_CHAIN_ID};
 use bridge_runtime_common::messages::{
@@ -37,6 +38,8 @@ use scale_info::TypeInfo;
 use sp_runtime::{traits::Saturating, FixedPointNumber, FixedU128};
 use sp_std::convert::TryFrom;
 
+/// Default lane that is used to send messages to Millau.
+pub const DEFAULT_XCM_LANE_TO_MILLAU: LaneId = [0, 0, 0, 0];
 /// Initial value of `MillauToRialtoConversionRate` parameter.
 pub const INITIAL_MILLAU_TO_RIALTO_CONVERSION_RATE: FixedU128 =
 	FixedU128::from_inner(FixedU128::DIV);
@@ -71,6 +74,8 @@ pub type FromMillauMessageDispatch = messages::target::FromBridgedChainMessageDi
 	crate::xcm_config::XcmWeigher,
 	//
 	frame_support::traits::ConstU64<BASE_XCM_WEIGHT_TWICE>,
+	// failed dispatch may be retried by the account, derived from the message origin
+	crate::xcm_config::SovereignAccountOf,
 >;
 
 /// Messages proof for Millau -> Rialto messages.
@@ -234,7 +239,7 @@ impl messages::BridgedChainWithMessages for Millau {
 }
 
 impl TargetHeaderChain<ToMillauMessagePayload, bp_rialto::AccountId> for Millau {
-	type Error = &'static str;
+	type Error = VerificationError;
 	// The proof is:
 	// - hash of the header this proof has been created with;
 	// - the storage proof of one or several keys;
@@ -243,11 +248,12 @@ impl TargetHeaderChain<ToMillauMessagePayload, bp_rialto::AccountId> for Millau
 
 	fn verify_message(payload: &ToMillauMessagePayload) -> Result<(), Self::Error> {
 		messages::source::verify_chain_message::<WithMillauMessageBridge>(payload)
+			.map_err(Into::into)
 	}
 
 	fn verify_messages_delivery_proof(
 		proof: Self::MessagesDeliveryProof,
-	) -> Result<(LaneId, InboundLaneData<bp_rialto::AccountId>), Self::Error> {
+	) -> Result<(LaneId, InboundLaneData<bp_rialto::AccountId>, MessagesResponses), Self::Error> {
 		messages::source::verify_messages_delivery_proof::<
 			WithMillauMessageBridge,
 			Runtime,
@@ -257,7 +263,7 @@ impl TargetHeaderChain<ToMillauMessagePayload, bp_rialto::AccountId> for Millau
 }
 
 impl SourceHeaderChain<bp_millau::Balance> for Millau {
-	type Error = &'static str;
+	type Error = VerificationError;
 	// The proof is:
 	// - hash of the header this proof has been created with;
 	// - the storage proof of one or several keys;
@@ -322,6 +328,7 @@ mod tests {
 			bp_rialto::ADDITIONAL_MESSAGE_BYTE_DELIVERY_WEIGHT,
 			bp_rialto::MAX_SINGLE_MESSAGE_DELIVERY_CONFIRMATION_TX_WEIGHT,
 			bp_rialto::PAY_INBOUND_DISPATCH_FEE_WEIGHT,
+			crate::MaxMessageResponseSize::get(),
 			DbWeight::get(),
 		);
 
@@ -348,6 +355,7 @@ mod tests {
 			max_incoming_inbound_lane_data_proof_size,
 			bp_rialto::MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX,
 			bp_rialto::MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX,
+			crate::MaxMessageResponseSize::get(),
 			DbWeight::get(),
 		);
 	}
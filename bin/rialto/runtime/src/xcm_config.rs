@@ -262,7 +262,11 @@ mod tests {
 
 			let mut incoming_message = DispatchMessage {
 				key: MessageKey { lane_id: [0, 0, 0, 0], nonce: 1 },
-				data: DispatchMessageData { payload: Ok((location, xcm).into()), fee: 0 },
+				data: DispatchMessageData {
+					payload: Ok((location, xcm).into()),
+					fee: 0,
+					is_cancelled: false,
+				},
 			};
 
 			let dispatch_weight = MessageDispatcher::dispatch_weight(&mut incoming_message);
@@ -276,6 +280,7 @@ mod tests {
 					dispatch_result: true,
 					unspent_weight: 0,
 					dispatch_fee_paid_during_dispatch: false,
+					response: None,
 				}
 			);
 		})
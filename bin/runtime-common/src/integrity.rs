@@ -21,11 +21,12 @@
 
 use crate::messages::MessageBridge;
 
-use bp_messages::MessageNonce;
-use bp_runtime::{Chain, ChainId};
+use bp_messages::{MessageNonce, UnrewardedRelayersState};
+use bp_runtime::{Chain, ChainId, PreComputedSize};
 use codec::Encode;
-use frame_support::{storage::generator::StorageValue, traits::Get};
+use frame_support::{storage::generator::StorageValue, traits::Get, weights::Weight};
 use frame_system::limits;
+use pallet_bridge_messages::{WeightInfoExt, EXPECTED_DEFAULT_MESSAGE_LENGTH};
 
 /// Macro that ensures that the runtime configuration and chain primitives crate are sharing
 /// the same types (index, block number, hash, hasher, account id and header).
@@ -100,12 +101,30 @@ macro_rules! assert_bridge_grandpa_pallet_types(
 
 /// Macro that ensures that the bridge messages pallet is configured properly to bridge using given
 /// configuration.
+///
+/// The `inbound_payload` may be specified if the runtime is using non-standard inbound payload
+/// type. By default, it is `FromBridgedChainMessagePayload`.
 #[macro_export]
 macro_rules! assert_bridge_messages_pallet_types(
 	(
 		runtime: $r:path,
 		with_bridged_chain_messages_instance: $i:path,
 		bridge: $bridge:path
+	) => {
+		$crate::assert_bridge_messages_pallet_types!(
+			runtime: $r,
+			with_bridged_chain_messages_instance: $i,
+			bridge: $bridge,
+			inbound_payload: $crate::messages::target::FromBridgedChainMessagePayload<
+				$crate::messages::CallOf<$crate::messages::ThisChain<$bridge>>
+			>
+		);
+	};
+	(
+		runtime: $r:path,
+		with_bridged_chain_messages_instance: $i:path,
+		bridge: $bridge:path,
+		inbound_payload: $payload:ty
 	) => {
 		{
 			// if one of asserts fail, then either bridge isn't configured properly (or alternatively - non-standard
@@ -113,8 +132,7 @@ macro_rules! assert_bridge_messages_pallet_types(
 			// and relays will stop functioning)
 			use $crate::messages::{
 				source::FromThisChainMessagePayload,
-				target::FromBridgedChainMessagePayload,
-				AccountIdOf, BalanceOf, BridgedChain, CallOf, ThisChain, WeightOf,
+				AccountIdOf, BalanceOf, BridgedChain, ThisChain, WeightOf,
 			};
 			use pallet_bridge_messages::Config as MessagesConfig;
 			use static_assertions::assert_type_eq_all;
@@ -122,7 +140,7 @@ macro_rules! assert_bridge_messages_pallet_types(
 			assert_type_eq_all!(<$r as MessagesConfig<$i>>::OutboundPayload, FromThisChainMessagePayload);
 			assert_type_eq_all!(<$r as MessagesConfig<$i>>::OutboundMessageFee, BalanceOf<ThisChain<$bridge>>);
 
-			assert_type_eq_all!(<$r as MessagesConfig<$i>>::InboundPayload, FromBridgedChainMessagePayload<CallOf<ThisChain<$bridge>>>);
+			assert_type_eq_all!(<$r as MessagesConfig<$i>>::InboundPayload, $payload);
 			assert_type_eq_all!(<$r as MessagesConfig<$i>>::InboundMessageFee, BalanceOf<BridgedChain<$bridge>>);
 			assert_type_eq_all!(<$r as MessagesConfig<$i>>::InboundRelayer, AccountIdOf<BridgedChain<$bridge>>);
 
@@ -158,7 +176,30 @@ macro_rules! assert_complete_bridge_types(
 			with_bridged_chain_messages_instance: $mi,
 			bridge: $bridge
 		);
-	}
+	};
+	(
+		runtime: $r:path,
+		with_bridged_chain_grandpa_instance: $gi:path,
+		with_bridged_chain_messages_instance: $mi:path,
+		bridge: $bridge:path,
+		this_chain: $this:path,
+		bridged_chain: $bridged:path,
+		inbound_payload: $payload:ty,
+	) => {
+		$crate::assert_chain_types!(runtime: $r, this_chain: $this);
+		$crate::assert_bridge_types!(bridge: $bridge, this_chain: $this, bridged_chain: $bridged);
+		$crate::assert_bridge_grandpa_pallet_types!(
+			runtime: $r,
+			with_bridged_chain_grandpa_instance: $gi,
+			bridged_chain: $bridged
+		);
+		$crate::assert_bridge_messages_pallet_types!(
+			runtime: $r,
+			with_bridged_chain_messages_instance: $mi,
+			bridge: $bridge,
+			inbound_payload: $payload
+		);
+	};
 );
 
 /// Parameters for asserting chain-related constants.
@@ -258,6 +299,90 @@ where
 	assert_eq!(R::BridgedChainId::get(), params.bridged_chain_id);
 }
 
+/// Parameters for asserting messages pallet weights.
+#[derive(Debug)]
+pub struct AssertBridgeMessagesPalletWeights {
+	/// Hardcoded weight of single regular message delivery transaction at this chain.
+	pub default_message_delivery_tx_weight: Weight,
+	/// Hardcoded increase of delivery transaction weight at this chain with every additional
+	/// message byte.
+	pub additional_message_byte_delivery_weight: Weight,
+	/// Hardcoded maximal weight of single message delivery confirmation transaction at this chain.
+	pub max_single_message_delivery_confirmation_tx_weight: Weight,
+	/// Hardcoded weight of pay-dispatch-fee operation for inbound messages at this chain.
+	pub pay_inbound_dispatch_fee_weight: Weight,
+}
+
+/// Test that the hardcoded weight constants of this chain are derived from the messages pallet
+/// weights.
+///
+/// Every constant must cover the actual weight, computed using the runtime `WeightInfo`. But it
+/// also must not be more than twice larger than the actual weight - otherwise the constants are
+/// outdated and must be updated after weights regeneration.
+pub fn assert_bridge_messages_pallet_weights<R, MI>(params: AssertBridgeMessagesPalletWeights)
+where
+	R: pallet_bridge_messages::Config<MI>,
+	MI: 'static,
+{
+	type WeightsOf<R, MI> = <R as pallet_bridge_messages::Config<MI>>::WeightInfo;
+
+	let db_weight = R::DbWeight::get();
+	pallet_bridge_messages::ensure_weights_are_correct::<WeightsOf<R, MI>>(
+		params.default_message_delivery_tx_weight,
+		params.additional_message_byte_delivery_weight,
+		params.max_single_message_delivery_confirmation_tx_weight,
+		params.pay_inbound_dispatch_fee_weight,
+		R::MaxMessageResponseSize::get(),
+		db_weight,
+	);
+
+	let assert_not_too_large = |name: &str, actual: Weight, hardcoded: Weight| {
+		assert!(
+			hardcoded <= actual.saturating_mul(2),
+			"Hardcoded {} ({}) is too large comparing to the actual weight: {}",
+			name,
+			hardcoded,
+			actual,
+		);
+	};
+	assert_not_too_large(
+		"default message delivery transaction weight",
+		WeightsOf::<R, MI>::receive_messages_proof_weight(
+			&PreComputedSize(
+				(EXPECTED_DEFAULT_MESSAGE_LENGTH +
+					WeightsOf::<R, MI>::expected_extra_storage_proof_size()) as usize,
+			),
+			1,
+			0,
+		),
+		params.default_message_delivery_tx_weight,
+	);
+	assert_not_too_large(
+		"additional message byte delivery weight",
+		WeightsOf::<R, MI>::storage_proof_size_overhead(1),
+		params.additional_message_byte_delivery_weight,
+	);
+	assert_not_too_large(
+		"single message delivery confirmation transaction weight",
+		WeightsOf::<R, MI>::receive_messages_delivery_proof_weight(
+			&PreComputedSize(WeightsOf::<R, MI>::expected_extra_storage_proof_size() as usize),
+			&UnrewardedRelayersState {
+				unrewarded_relayer_entries: 1,
+				total_messages: 1,
+				..Default::default()
+			},
+			R::MaxMessageResponseSize::get(),
+			db_weight,
+		),
+		params.max_single_message_delivery_confirmation_tx_weight,
+	);
+	assert_not_too_large(
+		"pay-dispatch-fee weight",
+		WeightsOf::<R, MI>::pay_inbound_dispatch_fee_overhead(),
+		params.pay_inbound_dispatch_fee_weight,
+	);
+}
+
 /// Parameters for asserting bridge pallet names.
 #[derive(Debug)]
 pub struct AssertBridgePalletNames<'a> {
@@ -18,15 +18,25 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use bp_messages::LaneId;
 use bp_runtime::FilterCall;
-use sp_runtime::transaction_validity::TransactionValidity;
+use frame_support::{dispatch::CallableCallFor, traits::IsSubType};
+use pallet_utility::{Call as UtilityCall, Pallet as UtilityPallet};
+use refund_relayer_extension::CallOf;
+use sp_runtime::{
+	transaction_validity::{TransactionPriority, TransactionValidity, ValidTransaction},
+	DispatchError,
+};
+use sp_std::marker::PhantomData;
 use xcm::v3::NetworkId;
 
 pub mod messages;
 pub mod messages_api;
 pub mod messages_benchmarking;
+pub mod messages_compression;
 pub mod messages_extension;
 pub mod parachains_benchmarking;
+pub mod refund_relayer_extension;
 
 #[cfg(feature = "integrity-test")]
 pub mod integrity;
@@ -38,6 +48,104 @@ pub mod integrity;
 pub trait BridgeRuntimeFilterCall<Call> {
 	/// Checks if a runtime call is valid.
 	fn validate(call: &Call) -> TransactionValidity;
+
+	/// Returns true if the runtime call is a bridge call, checked by this filter.
+	fn is_bridge_call(_call: &Call) -> bool {
+		false
+	}
+
+	/// Returns true if data, brought by the bridge call, is imported.
+	///
+	/// Must be called after the call is dispatched.
+	fn is_call_data_imported(_call: &Call) -> bool {
+		false
+	}
+
+	/// Returns true if the bridge call has failed with given error, because the submitter has
+	/// provided invalid proof.
+	fn is_invalid_proof_error(_error: &DispatchError) -> bool {
+		false
+	}
+
+	/// Returns true if both bridge calls are bringing (partially) the same data.
+	fn are_overlapping_calls(_call: &Call, _other_call: &Call) -> bool {
+		false
+	}
+
+	/// Returns lane of the messages call.
+	///
+	/// Returns `None` if the call is not a messages call, checked by this filter.
+	fn messages_lane(_call: &Call) -> Option<LaneId> {
+		None
+	}
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(8)]
+impl<Call> BridgeRuntimeFilterCall<Call> for Tuple {
+	fn validate(call: &Call) -> TransactionValidity {
+		let mut valid = ValidTransaction::default();
+		for_tuples!(
+			#(
+				valid = valid.combine_with(Tuple::validate(call)?);
+			)*
+		);
+		Ok(valid)
+	}
+
+	fn is_bridge_call(call: &Call) -> bool {
+		for_tuples!(
+			#(
+				if Tuple::is_bridge_call(call) {
+					return true
+				}
+			)*
+		);
+		false
+	}
+
+	fn is_call_data_imported(call: &Call) -> bool {
+		for_tuples!(
+			#(
+				if Tuple::is_call_data_imported(call) {
+					return true
+				}
+			)*
+		);
+		false
+	}
+
+	fn is_invalid_proof_error(error: &DispatchError) -> bool {
+		for_tuples!(
+			#(
+				if Tuple::is_invalid_proof_error(error) {
+					return true
+				}
+			)*
+		);
+		false
+	}
+
+	fn are_overlapping_calls(call: &Call, other_call: &Call) -> bool {
+		for_tuples!(
+			#(
+				if Tuple::are_overlapping_calls(call, other_call) {
+					return true
+				}
+			)*
+		);
+		false
+	}
+
+	fn messages_lane(call: &Call) -> Option<LaneId> {
+		for_tuples!(
+			#(
+				if let Some(lane) = Tuple::messages_lane(call) {
+					return Some(lane)
+				}
+			)*
+		);
+		None
+	}
 }
 
 impl<Call, T, I> BridgeRuntimeFilterCall<Call> for pallet_bridge_grandpa::Pallet<T, I>
@@ -47,6 +155,24 @@ where
 	fn validate(call: &Call) -> TransactionValidity {
 		<pallet_bridge_grandpa::Pallet<T, I> as FilterCall<Call>>::validate(call)
 	}
+
+	fn is_bridge_call(call: &Call) -> bool {
+		<pallet_bridge_grandpa::Pallet<T, I> as FilterCall<Call>>::is_bridge_call(call)
+	}
+
+	fn is_call_data_imported(call: &Call) -> bool {
+		<pallet_bridge_grandpa::Pallet<T, I> as FilterCall<Call>>::is_call_data_imported(call)
+	}
+
+	fn is_invalid_proof_error(error: &DispatchError) -> bool {
+		<pallet_bridge_grandpa::Pallet<T, I> as FilterCall<Call>>::is_invalid_proof_error(error)
+	}
+
+	fn are_overlapping_calls(call: &Call, other_call: &Call) -> bool {
+		<pallet_bridge_grandpa::Pallet<T, I> as FilterCall<Call>>::are_overlapping_calls(
+			call, other_call,
+		)
+	}
 }
 
 impl<Call, T, I> BridgeRuntimeFilterCall<Call> for pallet_bridge_parachains::Pallet<T, I>
@@ -56,6 +182,89 @@ where
 	fn validate(call: &Call) -> TransactionValidity {
 		<pallet_bridge_parachains::Pallet<T, I> as FilterCall<Call>>::validate(call)
 	}
+
+	fn is_bridge_call(call: &Call) -> bool {
+		<pallet_bridge_parachains::Pallet<T, I> as FilterCall<Call>>::is_bridge_call(call)
+	}
+
+	fn is_call_data_imported(call: &Call) -> bool {
+		<pallet_bridge_parachains::Pallet<T, I> as FilterCall<Call>>::is_call_data_imported(call)
+	}
+
+	fn is_invalid_proof_error(error: &DispatchError) -> bool {
+		<pallet_bridge_parachains::Pallet<T, I> as FilterCall<Call>>::is_invalid_proof_error(error)
+	}
+
+	fn are_overlapping_calls(call: &Call, other_call: &Call) -> bool {
+		<pallet_bridge_parachains::Pallet<T, I> as FilterCall<Call>>::are_overlapping_calls(
+			call, other_call,
+		)
+	}
+}
+
+/// Bridge calls, batched using the `utility::batch_all` call.
+///
+/// Every call of the batch is checked by the `BridgeCalls` filter (usually, a tuple of bridge
+/// pallets), so the whole batch is rejected if any of its bridge calls is obsolete.
+pub struct BatchAllBridgeCalls<Runtime, BridgeCalls>(PhantomData<(Runtime, BridgeCalls)>);
+
+impl<Runtime, BridgeCalls> BridgeRuntimeFilterCall<CallOf<Runtime>>
+	for BatchAllBridgeCalls<Runtime, BridgeCalls>
+where
+	Runtime: pallet_utility::Config<Call = CallOf<Runtime>>,
+	BridgeCalls: BridgeRuntimeFilterCall<CallOf<Runtime>>,
+	CallOf<Runtime>: IsSubType<CallableCallFor<UtilityPallet<Runtime>, Runtime>>,
+{
+	fn validate(call: &CallOf<Runtime>) -> TransactionValidity {
+		let mut valid = ValidTransaction::default();
+		if let Some(UtilityCall::<Runtime>::batch_all { ref calls }) = call.is_sub_type() {
+			for call in calls {
+				valid = valid.combine_with(BridgeCalls::validate(call)?);
+			}
+		}
+		Ok(valid)
+	}
+
+	fn is_bridge_call(call: &CallOf<Runtime>) -> bool {
+		match call.is_sub_type() {
+			Some(UtilityCall::<Runtime>::batch_all { ref calls }) =>
+				!calls.is_empty() && calls.iter().all(BridgeCalls::is_bridge_call),
+			_ => false,
+		}
+	}
+
+	fn is_invalid_proof_error(error: &DispatchError) -> bool {
+		BridgeCalls::is_invalid_proof_error(error)
+	}
+}
+
+/// Registry of relayers, used by the `BridgeRejectObsoleteHeadersAndMessages` signed extension.
+///
+/// Bridge transactions of registered relayers are getting priority boost. In exchange, registered
+/// relayers are slashed if their bridge transaction has failed because of invalid proof.
+pub trait RelayersRegistry<AccountId> {
+	/// Returns true if given relayer has an active registration.
+	fn is_registration_active(relayer: &AccountId) -> bool;
+	/// Returns priority boost of bridge transactions, submitted by given relayer.
+	fn priority_boost(relayer: &AccountId) -> TransactionPriority;
+	/// Slash and deregister given relayer.
+	fn slash_and_deregister(relayer: &AccountId);
+}
+
+impl<T: pallet_bridge_relayers::Config> RelayersRegistry<T::AccountId>
+	for pallet_bridge_relayers::Pallet<T>
+{
+	fn is_registration_active(relayer: &T::AccountId) -> bool {
+		pallet_bridge_relayers::Pallet::<T>::is_registration_active(relayer)
+	}
+
+	fn priority_boost(relayer: &T::AccountId) -> TransactionPriority {
+		pallet_bridge_relayers::Pallet::<T>::priority_boost(relayer)
+	}
+
+	fn slash_and_deregister(relayer: &T::AccountId) {
+		pallet_bridge_relayers::Pallet::<T>::slash_and_deregister(relayer)
+	}
 }
 
 /// Declares a runtime-specific `BridgeRejectObsoleteHeadersAndMessages` signed extension.
@@ -73,8 +282,113 @@ where
 /// The goal of this extension is to avoid "mining" transactions that provide outdated bridged
 /// headers and messages. Without that extension, even honest relayers may lose their funds if
 /// there are multiple relays running and submitting the same information.
+///
+/// If the `relayers: RelayersRegistry` argument is provided, bridge transactions of registered
+/// relayers are getting priority boost. Obsolete bridge transactions of registered relayers are
+/// rejected, exactly like transactions of other relayers. Registered relayers are slashed only
+/// if their bridge transaction has failed because of provably invalid proof.
+///
+/// ```nocompile
+/// generate_bridge_reject_obsolete_headers_and_messages!{
+///     Call, AccountId, relayers: BridgeRelayers,
+///     BridgeRialtoGrandpa, BridgeRialtoMessages
+/// }
+/// ```
 #[macro_export]
 macro_rules! generate_bridge_reject_obsolete_headers_and_messages {
+	($call:ty, $account_id:ty, relayers: $relayers:ty, $($filter_call:ty),*) => {
+		#[derive(Clone, codec::Decode, codec::Encode, Eq, PartialEq, frame_support::RuntimeDebug, scale_info::TypeInfo)]
+		pub struct BridgeRejectObsoleteHeadersAndMessages;
+		impl BridgeRejectObsoleteHeadersAndMessages {
+			/// Returns true if the call is a bridge call.
+			fn is_bridge_call(call: &$call) -> bool {
+				$(
+					if <$filter_call as $crate::BridgeRuntimeFilterCall<$call>>::is_bridge_call(call) {
+						return true;
+					}
+				)*
+				false
+			}
+
+			/// Returns true if the bridge call has failed because of invalid proof.
+			fn is_invalid_proof_error(error: &sp_runtime::DispatchError) -> bool {
+				$(
+					if <$filter_call as $crate::BridgeRuntimeFilterCall<$call>>::is_invalid_proof_error(error) {
+						return true;
+					}
+				)*
+				false
+			}
+		}
+		impl sp_runtime::traits::SignedExtension for BridgeRejectObsoleteHeadersAndMessages {
+			const IDENTIFIER: &'static str = "BridgeRejectObsoleteHeadersAndMessages";
+			type AccountId = $account_id;
+			type Call = $call;
+			type AdditionalSigned = ();
+			/// Registered relayer that has submitted the bridge transaction.
+			type Pre = Option<$account_id>;
+
+			fn additional_signed(&self) -> sp_std::result::Result<
+				(),
+				sp_runtime::transaction_validity::TransactionValidityError,
+			> {
+				Ok(())
+			}
+
+			fn validate(
+				&self,
+				who: &Self::AccountId,
+				call: &Self::Call,
+				_info: &sp_runtime::traits::DispatchInfoOf<Self::Call>,
+				_len: usize,
+			) -> sp_runtime::transaction_validity::TransactionValidity {
+				let mut valid = sp_runtime::transaction_validity::ValidTransaction::default();
+				$(
+					valid = valid
+						.combine_with(<$filter_call as $crate::BridgeRuntimeFilterCall<$call>>::validate(call)?);
+				)*
+				if Self::is_bridge_call(call) {
+					valid.priority = valid.priority.saturating_add(
+						<$relayers as $crate::RelayersRegistry<$account_id>>::priority_boost(who),
+					);
+				}
+				Ok(valid)
+			}
+
+			fn pre_dispatch(
+				self,
+				who: &Self::AccountId,
+				call: &Self::Call,
+				info: &sp_runtime::traits::DispatchInfoOf<Self::Call>,
+				len: usize,
+			) -> Result<Self::Pre, sp_runtime::transaction_validity::TransactionValidityError> {
+				self.validate(who, call, info, len)?;
+
+				let is_registered_relayer = Self::is_bridge_call(call) &&
+					<$relayers as $crate::RelayersRegistry<$account_id>>::is_registration_active(who);
+				Ok(if is_registered_relayer { Some(who.clone()) } else { None })
+			}
+
+			fn post_dispatch(
+				pre: Option<Self::Pre>,
+				_info: &sp_runtime::traits::DispatchInfoOf<Self::Call>,
+				_post_info: &sp_runtime::traits::PostDispatchInfoOf<Self::Call>,
+				_len: usize,
+				result: &sp_runtime::DispatchResult,
+			) -> Result<(), sp_runtime::transaction_validity::TransactionValidityError> {
+				// obsolete transactions are rejected before dispatch and other failures may be
+				// caused by races between relayers, so relayers are only slashed for invalid proofs
+				if let (Some(Some(relayer)), Err(error)) = (pre, result) {
+					if Self::is_invalid_proof_error(error) {
+						<$relayers as $crate::RelayersRegistry<$account_id>>::slash_and_deregister(
+							&relayer,
+						);
+					}
+				}
+				Ok(())
+			}
+		}
+	};
 	($call:ty, $account_id:ty, $($filter_call:ty),*) => {
 		#[derive(Clone, codec::Decode, codec::Encode, Eq, PartialEq, frame_support::RuntimeDebug, scale_info::TypeInfo)]
 		pub struct BridgeRejectObsoleteHeadersAndMessages;
@@ -150,12 +464,16 @@ impl CustomNetworkId {
 
 #[cfg(test)]
 mod tests {
-	use crate::BridgeRuntimeFilterCall;
+	use crate::{BridgeRuntimeFilterCall, RelayersRegistry};
 	use frame_support::{assert_err, assert_ok};
 	use sp_runtime::{
 		traits::SignedExtension,
-		transaction_validity::{InvalidTransaction, TransactionValidity, ValidTransaction},
+		transaction_validity::{
+			InvalidTransaction, TransactionPriority, TransactionValidity, ValidTransaction,
+		},
+		DispatchError,
 	};
+	use std::cell::RefCell;
 
 	pub struct MockCall {
 		data: u32,
@@ -178,6 +496,9 @@ mod tests {
 	struct FirstFilterCall;
 	impl BridgeRuntimeFilterCall<MockCall> for FirstFilterCall {
 		fn validate(call: &MockCall) -> TransactionValidity {
+			if call.data == 0 {
+				return InvalidTransaction::Stale.into()
+			}
 			if call.data <= 1 {
 				return InvalidTransaction::Custom(1).into()
 			}
@@ -195,6 +516,47 @@ mod tests {
 
 			Ok(ValidTransaction { priority: 2, ..Default::default() })
 		}
+
+		fn is_bridge_call(call: &MockCall) -> bool {
+			call.data < 100
+		}
+
+		fn is_invalid_proof_error(error: &DispatchError) -> bool {
+			*error == INVALID_PROOF_ERROR
+		}
+	}
+
+	const INVALID_PROOF_ERROR: DispatchError = DispatchError::Other("invalid proof");
+
+	const REGISTERED_RELAYER: u64 = 1;
+	const UNREGISTERED_RELAYER: u64 = 2;
+	const REGISTERED_RELAYER_PRIORITY_BOOST: TransactionPriority = 100;
+
+	thread_local! {
+		static SLASHED_RELAYERS: RefCell<Vec<u64>> = RefCell::new(Vec::new());
+	}
+
+	struct MockRelayers;
+	impl RelayersRegistry<u64> for MockRelayers {
+		fn is_registration_active(relayer: &u64) -> bool {
+			*relayer == REGISTERED_RELAYER
+		}
+
+		fn priority_boost(relayer: &u64) -> TransactionPriority {
+			if Self::is_registration_active(relayer) {
+				REGISTERED_RELAYER_PRIORITY_BOOST
+			} else {
+				0
+			}
+		}
+
+		fn slash_and_deregister(relayer: &u64) {
+			SLASHED_RELAYERS.with(|slashed| slashed.borrow_mut().push(*relayer));
+		}
+	}
+
+	fn slashed_relayers() -> Vec<u64> {
+		SLASHED_RELAYERS.with(|slashed| slashed.borrow().clone())
 	}
 
 	#[test]
@@ -221,4 +583,123 @@ mod tests {
 			ValidTransaction { priority: 3, ..Default::default() }
 		)
 	}
+	generate_bridge_reject_obsolete_headers_and_messages!(
+		MockCall,
+		u64,
+		relayers: MockRelayers,
+		FirstFilterCall,
+		SecondFilterCall
+	);
+
+	fn pre_dispatch_and_post_dispatch(
+		relayer: u64,
+		data: u32,
+		result: sp_runtime::DispatchResult,
+	) -> Result<(), sp_runtime::transaction_validity::TransactionValidityError> {
+		let pre = BridgeRejectObsoleteHeadersAndMessages.pre_dispatch(
+			&relayer,
+			&MockCall { data },
+			&(),
+			0,
+		)?;
+		BridgeRejectObsoleteHeadersAndMessages::post_dispatch(Some(pre), &(), &(), 0, &result)
+	}
+
+	#[test]
+	fn registered_relayer_gets_priority_boost() {
+		assert_ok!(
+			BridgeRejectObsoleteHeadersAndMessages.validate(
+				&REGISTERED_RELAYER,
+				&MockCall { data: 3 },
+				&(),
+				0
+			),
+			ValidTransaction {
+				priority: 3 + REGISTERED_RELAYER_PRIORITY_BOOST,
+				..Default::default()
+			}
+		);
+		assert_ok!(
+			BridgeRejectObsoleteHeadersAndMessages.validate(
+				&UNREGISTERED_RELAYER,
+				&MockCall { data: 3 },
+				&(),
+				0
+			),
+			ValidTransaction { priority: 3, ..Default::default() }
+		);
+	}
+
+	#[test]
+	fn registered_relayer_gets_no_priority_boost_for_non_bridge_calls() {
+		assert_ok!(
+			BridgeRejectObsoleteHeadersAndMessages.validate(
+				&REGISTERED_RELAYER,
+				&MockCall { data: 100 },
+				&(),
+				0
+			),
+			ValidTransaction { priority: 3, ..Default::default() }
+		);
+	}
+
+	#[test]
+	fn obsolete_transaction_of_unregistered_relayer_is_rejected() {
+		assert_err!(
+			pre_dispatch_and_post_dispatch(UNREGISTERED_RELAYER, 0, Ok(())),
+			InvalidTransaction::Stale
+		);
+		assert_eq!(slashed_relayers(), Vec::<u64>::new());
+	}
+
+	#[test]
+	fn obsolete_transaction_of_registered_relayer_is_rejected() {
+		assert_err!(
+			pre_dispatch_and_post_dispatch(REGISTERED_RELAYER, 0, Ok(())),
+			InvalidTransaction::Stale
+		);
+		assert_eq!(slashed_relayers(), Vec::<u64>::new());
+	}
+
+	#[test]
+	fn successful_transaction_of_registered_relayer_is_not_slashed() {
+		assert_ok!(pre_dispatch_and_post_dispatch(REGISTERED_RELAYER, 3, Ok(())));
+		assert_eq!(slashed_relayers(), Vec::<u64>::new());
+	}
+
+	#[test]
+	fn registered_relayer_is_not_slashed_for_failed_transaction() {
+		assert_ok!(pre_dispatch_and_post_dispatch(
+			REGISTERED_RELAYER,
+			3,
+			Err(DispatchError::BadOrigin)
+		));
+		assert_eq!(slashed_relayers(), Vec::<u64>::new());
+	}
+
+	#[test]
+	fn registered_relayer_is_slashed_for_invalid_proof() {
+		assert_ok!(pre_dispatch_and_post_dispatch(REGISTERED_RELAYER, 3, Err(INVALID_PROOF_ERROR)));
+		assert_eq!(slashed_relayers(), vec![REGISTERED_RELAYER]);
+	}
+
+	#[test]
+	fn unregistered_relayer_is_not_slashed_for_invalid_proof() {
+		assert_ok!(pre_dispatch_and_post_dispatch(
+			UNREGISTERED_RELAYER,
+			3,
+			Err(INVALID_PROOF_ERROR)
+		));
+		assert_eq!(slashed_relayers(), Vec::<u64>::new());
+	}
+
+	#[test]
+	fn unregistered_relayer_is_not_slashed_for_failed_transaction() {
+		assert_ok!(pre_dispatch_and_post_dispatch(
+			UNREGISTERED_RELAYER,
+			3,
+			Err(DispatchError::BadOrigin)
+		));
+		assert_eq!(slashed_relayers(), Vec::<u64>::new());
+	}
 }
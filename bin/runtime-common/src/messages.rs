@@ -21,18 +21,27 @@
 //! of to elements - message lane id and message nonce.
 
 use bp_messages::{
-	source_chain::LaneMessageVerifier,
+	source_chain::{LaneMessageVerifier, MessageInitiator, MessagesResponses},
 	target_chain::{DispatchMessage, MessageDispatch, ProvedLaneMessages, ProvedMessages},
 	InboundLaneData, LaneId, Message, MessageData, MessageKey, MessageNonce, OutboundLaneData,
+	VerificationError,
 };
 use bp_polkadot_core::parachains::{ParaHash, ParaHasher, ParaId};
 use bp_runtime::{messages::MessageDispatchResult, ChainId, Size, StorageProofChecker};
-use codec::{Decode, DecodeLimit, Encode, MaxEncodedLen};
-use frame_support::{traits::Get, weights::Weight, RuntimeDebug};
+use codec::{Decode, DecodeLimit, Encode, FullCodec, MaxEncodedLen};
+use frame_support::{
+	storage::types::{OptionQuery, StorageDoubleMap},
+	traits::{Get, StorageInstance},
+	weights::{RuntimeDbWeight, Weight},
+	Blake2_128Concat, RuntimeDebug,
+};
 use hash_db::Hasher;
 use scale_info::TypeInfo;
 use sp_runtime::{
-	traits::{AtLeast32BitUnsigned, CheckedAdd, CheckedDiv, CheckedMul, Header as HeaderT},
+	traits::{
+		AtLeast32BitUnsigned, CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Header as HeaderT,
+		UniqueSaturatedInto, Zero,
+	},
 	FixedPointNumber, FixedPointOperand, FixedU128,
 };
 use sp_std::{cmp::PartialOrd, convert::TryFrom, fmt::Debug, marker::PhantomData, vec::Vec};
@@ -157,6 +166,28 @@ pub trait ThisChainWithMessages: ChainWithMessages {
 	/// Any messages over this limit, will be rejected.
 	fn maximal_pending_messages_at_outbound_lane() -> MessageNonce;
 
+	/// Verify message against the lane registry of the messages pallet.
+	///
+	/// By default, the lane registry is not used and all messages are accepted. Chains that
+	/// are managing their lanes using the registry should override this method (see
+	/// `pallet_bridge_messages::Pallet::verify_registered_lane_message`).
+	fn verify_registered_lane(
+		_origin: &Self::Origin,
+		_lane: &LaneId,
+		_lane_outbound_data: &OutboundLaneData,
+		_payload: &[u8],
+	) -> Result<(), &'static str> {
+		Ok(())
+	}
+
+	/// Return maximal weight of storage accesses, performed by the `verify_registered_lane` call.
+	///
+	/// Chains that are overriding `verify_registered_lane` should also override this method (see
+	/// `pallet_bridge_messages::Pallet::verify_registered_lane_message_weight`).
+	fn verify_registered_lane_weight() -> Weight {
+		0
+	}
+
 	/// Estimate size and weight of single message delivery confirmation transaction at This chain.
 	fn estimate_delivery_confirmation_transaction() -> MessageTransaction<WeightOf<Self>> {
 		Self::ConfirmationTransactionEstimation::estimate_delivery_confirmation_transaction()
@@ -238,6 +269,22 @@ pub fn transaction_payment<Balance: AtLeast32BitUnsigned + FixedPointOperand>(
 	base_fee.saturating_add(len_fee).saturating_add(adjusted_weight_fee)
 }
 
+/// Convert error of the bridged headers pallet `parse_finalized_storage_proof` call into the
+/// proof verification error.
+///
+/// Only the storage root mismatch proves that the proof is invalid. Other errors (e.g. unknown
+/// bridged header or halted pallet) are caused by the state of this chain.
+fn storage_proof_verification_error(
+	error: sp_runtime::DispatchError,
+	storage_root_mismatch: sp_runtime::DispatchError,
+) -> VerificationError {
+	if error == storage_root_mismatch {
+		VerificationError::StorageRootMismatch
+	} else {
+		VerificationError::Other(error.into())
+	}
+}
+
 /// Sub-module that is declaring types required for processing This -> Bridged chain messages.
 pub mod source {
 	use super::*;
@@ -257,7 +304,7 @@ pub mod source {
 	/// Messages delivery proof from bridged chain:
 	///
 	/// - hash of finalized header;
-	/// - storage proof of inbound lane state;
+	/// - storage proof of inbound lane state and dispatch responses of all unrewarded messages;
 	/// - lane id.
 	#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
 	pub struct FromBridgedChainMessagesDeliveryProof<BridgedHeaderHash> {
@@ -280,9 +327,10 @@ pub mod source {
 		}
 	}
 
-	/// 'Parsed' message delivery proof - inbound lane id and its state.
+	/// 'Parsed' message delivery proof - inbound lane id, its state and dispatch responses of
+	/// unrewarded messages.
 	pub type ParsedMessagesDeliveryProofFromBridgedChain<B> =
-		(LaneId, InboundLaneData<AccountIdOf<ThisChain<B>>>);
+		(LaneId, InboundLaneData<AccountIdOf<ThisChain<B>>>, MessagesResponses);
 
 	/// Message verifier that is doing all basic checks.
 	///
@@ -348,6 +396,9 @@ pub mod source {
 				return Err(TOO_MANY_PENDING_MESSAGES)
 			}
 
+			// reject message if it violates the lane registry limits
+			ThisChain::<B>::verify_registered_lane(submitter, lane, lane_outbound_data, payload)?;
+
 			let minimal_fee_in_this_tokens = estimate_message_dispatch_and_delivery_fee::<B>(
 				payload,
 				B::RELAYER_FEE_PERCENT,
@@ -361,6 +412,316 @@ pub mod source {
 
 			Ok(())
 		}
+
+		fn verify_message_weight(_db_weight: RuntimeDbWeight) -> Weight {
+			ThisChain::<B>::verify_registered_lane_weight()
+		}
+	}
+
+	/// The error message returned from LaneMessageVerifier when the sender has exceeded its
+	/// messages quota at the lane.
+	pub const SENDER_RATE_LIMIT_EXCEEDED: &str =
+		"The sender has exceeded its messages quota at the lane.";
+
+	/// The error message returned from LaneMessageVerifier when the sender has exceeded its
+	/// payload size quota at the lane.
+	pub const SENDER_PAYLOAD_SIZE_LIMIT_EXCEEDED: &str =
+		"The sender has exceeded its payload size quota at the lane.";
+
+	/// Limits of messages that may be sent by a single account over a single lane.
+	#[derive(Clone, Copy, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct SenderRateLimits<BlockNumber> {
+		/// Length of the rate limiting window (in blocks). If it is zero, limits are not enforced.
+		pub window: BlockNumber,
+		/// Maximal number of messages that the account may send within single window.
+		pub max_messages: u32,
+		/// Maximal total size of payloads of messages that the account may send within single
+		/// window.
+		pub max_payload_size: u32,
+	}
+
+	/// Messages that have been sent by a single account over a single lane within the window.
+	#[derive(Clone, Copy, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct SenderMessagesUsage<BlockNumber> {
+		/// First block of the window.
+		pub window_start: BlockNumber,
+		/// Number of messages that have been sent within the window.
+		pub messages: u32,
+		/// Total size of payloads of messages that have been sent within the window.
+		pub payload_size: u32,
+	}
+
+	/// Prefix of the `SenderMessagesUsages` storage map.
+	pub struct SenderMessagesUsagesPrefix;
+
+	impl StorageInstance for SenderMessagesUsagesPrefix {
+		fn pallet_prefix() -> &'static str {
+			"BridgeSenderRateLimits"
+		}
+
+		const STORAGE_PREFIX: &'static str = "SenderMessagesUsages";
+	}
+
+	/// Number of window buckets, used by the `SenderMessagesUsages` map.
+	///
+	/// At any time, one bucket holds usages of the current window, one holds usages of the
+	/// previous window and the remaining bucket holds stale usages that are pruned.
+	pub const SENDER_MESSAGES_USAGES_BUCKETS: u8 = 3;
+
+	/// Maximal number of stale usages that are pruned when the message is sent.
+	///
+	/// Every sent message adds at most one usage to the map, so pruning two usages per message
+	/// guarantees that stale usages are removed faster than new usages are added.
+	pub const MAX_PRUNED_SENDER_MESSAGES_USAGES: u32 = 2;
+
+	/// Map of `(BridgedChainId, LaneId, WindowBucket), AccountId` => messages, sent by the account
+	/// over the lane within the window, that is mapped to the bucket.
+	pub type SenderMessagesUsages<AccountId, BlockNumber> = StorageDoubleMap<
+		SenderMessagesUsagesPrefix,
+		Blake2_128Concat,
+		(ChainId, LaneId, u8),
+		Blake2_128Concat,
+		AccountId,
+		SenderMessagesUsage<BlockNumber>,
+		OptionQuery,
+	>;
+
+	/// Message initiator that is the account, linked to the signed message origin.
+	#[derive(RuntimeDebug)]
+	pub struct SignedMessageInitiator;
+
+	impl<Origin, AccountId> MessageInitiator<Origin, FromThisChainMessagePayload, AccountId>
+		for SignedMessageInitiator
+	where
+		// matches requirements from the `frame_system::Config::Origin`
+		Origin: Clone + Into<Result<frame_system::RawOrigin<AccountId>, Origin>>,
+	{
+		fn message_initiator(
+			submitter: &Origin,
+			_lane: &LaneId,
+			_payload: &FromThisChainMessagePayload,
+		) -> Option<AccountId> {
+			match submitter.clone().into() {
+				Ok(frame_system::RawOrigin::Signed(sender)) => Some(sender),
+				_ => None,
+			}
+		}
+	}
+
+	/// Message initiator of the XCM message, that is sent by the XCM pallet.
+	///
+	/// The XCM pallet prepends the program with the `DescendOrigin` instruction, if the program
+	/// is sent by some location within this chain (e.g. by the signed account). This location is
+	/// converted into account using the `LocationConverter`. Messages, sent by this chain itself,
+	/// have no initiator.
+	///
+	/// The payload may be compressed by the `XcmBridgeAdapter`, so it is decompressed here. The
+	/// size of decompressed payload is limited by the `MAXIMAL_DECOMPRESSED_PAYLOAD_SIZE`.
+	#[derive(RuntimeDebug)]
+	pub struct XcmMessageInitiator<LocationConverter>(PhantomData<LocationConverter>);
+
+	impl<Origin, AccountId, LocationConverter>
+		MessageInitiator<Origin, FromThisChainMessagePayload, AccountId>
+		for XcmMessageInitiator<LocationConverter>
+	where
+		Origin: Clone + Into<Result<pallet_xcm::Origin, Origin>>,
+		LocationConverter: xcm_executor::traits::Convert<MultiLocation, AccountId>,
+	{
+		fn message_initiator(
+			submitter: &Origin,
+			_lane: &LaneId,
+			payload: &FromThisChainMessagePayload,
+		) -> Option<AccountId> {
+			if !matches!(submitter.clone().into(), Ok(pallet_xcm::Origin::Xcm(_))) {
+				return None
+			}
+
+			let payload = crate::messages_compression::decompress_payload(
+				payload,
+				crate::messages_compression::MAXIMAL_DECOMPRESSED_PAYLOAD_SIZE,
+			)
+			.ok()?;
+
+			// the payload is the `(MultiLocation, Xcm<()>)` tuple and we only need the first
+			// instruction of the program
+			let mut input = &payload[..];
+			let _origin = MultiLocation::decode(&mut input).ok()?;
+			let _instructions_count = codec::Compact::<u32>::decode(&mut input).ok()?;
+			match Instruction::<()>::decode_with_depth_limit(
+				sp_api::MAX_EXTRINSIC_DEPTH,
+				&mut input,
+			)
+			.ok()?
+			{
+				Instruction::DescendOrigin(interior) =>
+					LocationConverter::convert(MultiLocation { parents: 0, interior }).ok(),
+				_ => None,
+			}
+		}
+	}
+
+	/// Message verifier that enforces per-sender limits on top of some other verifier.
+	///
+	/// Messages are counted per `(lane, initiator)` pair within the window of
+	/// `Limits::get().window` blocks, where the initiator is the account that has actually
+	/// initiated the message (see `MessageInitiator`). E.g. it may be the signed message origin,
+	/// the location that has sent the XCM program or the sender of the tokens transfer. The
+	/// message is rejected if the initiator has already sent `max_messages` messages or
+	/// `max_payload_size` bytes of payload over the lane within the sliding window, ending at the
+	/// current block. This way single (noisy) sender can't starve the shared lane. Messages
+	/// without known initiator are not limited.
+	///
+	/// The sliding window usage is estimated from usages of the current and previous fixed
+	/// windows: the usage of the previous window is counted proportionally to the part of the
+	/// previous window that is still covered by the sliding window. So the sender can't send
+	/// the doubled quota around the window boundary.
+	///
+	/// The `Limits` are expected to be stored in the runtime storage (see `parameter_types!`
+	/// with the `storage` keyword) and updated by the governance using the pallet parameters.
+	///
+	/// Counters are updated by the verifier itself, so it must only be used in the transactional
+	/// context (the `send_message` call of the messages pallet is fine). Usages of expired
+	/// windows are pruned when messages are sent over the lane.
+	#[derive(RuntimeDebug)]
+	pub struct SenderRateLimitedMessageVerifier<
+		B,
+		Verifier,
+		Limits,
+		BlockNumberProvider,
+		Initiator = SignedMessageInitiator,
+	>(PhantomData<(B, Verifier, Limits, BlockNumberProvider, Initiator)>);
+
+	impl<B, Verifier, Limits, BlockNumberProvider, Initiator>
+		LaneMessageVerifier<
+			OriginOf<ThisChain<B>>,
+			FromThisChainMessagePayload,
+			BalanceOf<ThisChain<B>>,
+		> for SenderRateLimitedMessageVerifier<B, Verifier, Limits, BlockNumberProvider, Initiator>
+	where
+		B: MessageBridge,
+		AccountIdOf<ThisChain<B>>: FullCodec,
+		Verifier: LaneMessageVerifier<
+			OriginOf<ThisChain<B>>,
+			FromThisChainMessagePayload,
+			BalanceOf<ThisChain<B>>,
+		>,
+		Limits: Get<SenderRateLimits<BlockNumberProvider::BlockNumber>>,
+		BlockNumberProvider: sp_runtime::traits::BlockNumberProvider,
+		BlockNumberProvider::BlockNumber: FullCodec,
+		Initiator: MessageInitiator<
+			OriginOf<ThisChain<B>>,
+			FromThisChainMessagePayload,
+			AccountIdOf<ThisChain<B>>,
+		>,
+	{
+		type Error = &'static str;
+
+		fn verify_message(
+			submitter: &OriginOf<ThisChain<B>>,
+			delivery_and_dispatch_fee: &BalanceOf<ThisChain<B>>,
+			lane: &LaneId,
+			lane_outbound_data: &OutboundLaneData,
+			payload: &FromThisChainMessagePayload,
+		) -> Result<(), Self::Error> {
+			Verifier::verify_message(
+				submitter,
+				delivery_and_dispatch_fee,
+				lane,
+				lane_outbound_data,
+				payload,
+			)
+			.map_err(Into::into)?;
+
+			// only messages with known initiator are limited
+			let sender = match Initiator::message_initiator(submitter, lane, payload) {
+				Some(sender) => sender,
+				None => return Ok(()),
+			};
+
+			let limits = Limits::get();
+			if limits.window.is_zero() {
+				return Ok(())
+			}
+
+			// select buckets of the current, previous and stale windows
+			let now = BlockNumberProvider::current_block_number();
+			let window_start = now.clone() - now.clone() % limits.window.clone();
+			let window_index: u64 = (now.clone() / limits.window.clone()).unique_saturated_into();
+			let bucket = |offset: u64| {
+				let buckets = u64::from(SENDER_MESSAGES_USAGES_BUCKETS);
+				((window_index + offset) % buckets) as u8
+			};
+			let current_key = (B::BRIDGED_CHAIN_ID, *lane, bucket(0));
+			let previous_key = (B::BRIDGED_CHAIN_ID, *lane, bucket(2));
+			let stale_key = (B::BRIDGED_CHAIN_ID, *lane, bucket(1));
+
+			// prune usages of some stale windows
+			SenderMessagesUsages::<AccountIdOf<ThisChain<B>>, BlockNumberProvider::BlockNumber>::drain_prefix(
+				&stale_key,
+			)
+			.take(MAX_PRUNED_SENDER_MESSAGES_USAGES as usize)
+			.for_each(drop);
+
+			// compute sender usage within the current window
+			let mut usage =
+				SenderMessagesUsages::<AccountIdOf<ThisChain<B>>, _>::get(&current_key, &sender)
+					.filter(|usage| usage.window_start == window_start)
+					.unwrap_or(SenderMessagesUsage {
+						window_start: window_start.clone(),
+						messages: 0,
+						payload_size: 0,
+					});
+			usage.messages = usage.messages.saturating_add(1);
+			usage.payload_size = usage
+				.payload_size
+				.saturating_add(u32::try_from(payload.len()).unwrap_or(u32::MAX));
+
+			// previous window usage is counted proportionally to the part of previous window,
+			// covered by the sliding window
+			let window: u64 = limits.window.clone().unique_saturated_into();
+			let elapsed: u64 = (now - window_start.clone()).unique_saturated_into();
+			let previous_window_part = |value: u32| -> u32 {
+				let value = u64::from(value).saturating_mul(window.saturating_sub(elapsed));
+				u32::try_from(value / window).unwrap_or(u32::MAX)
+			};
+			let previous_usage = window_start
+				.checked_sub(&limits.window)
+				.and_then(|previous_window_start| {
+					SenderMessagesUsages::<AccountIdOf<ThisChain<B>>, _>::get(
+						&previous_key,
+						&sender,
+					)
+					.filter(|usage| usage.window_start == previous_window_start)
+				})
+				.map(|usage| {
+					(previous_window_part(usage.messages), previous_window_part(usage.payload_size))
+				})
+				.unwrap_or((0, 0));
+
+			// reject message if the sender has exceeded its quota within the sliding window
+			if usage.messages.saturating_add(previous_usage.0) > limits.max_messages {
+				return Err(SENDER_RATE_LIMIT_EXCEEDED)
+			}
+			if usage.payload_size.saturating_add(previous_usage.1) > limits.max_payload_size {
+				return Err(SENDER_PAYLOAD_SIZE_LIMIT_EXCEEDED)
+			}
+
+			SenderMessagesUsages::<AccountIdOf<ThisChain<B>>, _>::insert(
+				&current_key,
+				&sender,
+				usage,
+			);
+
+			Ok(())
+		}
+
+		fn verify_message_weight(db_weight: RuntimeDbWeight) -> Weight {
+			// we're reading limits, usages of current and previous windows and pruning stale
+			// usages (including the read that finds the end of the prefix) + writing current usage
+			let pruned_usages = Weight::from(MAX_PRUNED_SENDER_MESSAGES_USAGES);
+			Verifier::verify_message_weight(db_weight)
+				.saturating_add(db_weight.reads_writes(3 + pruned_usages + 1, 1 + pruned_usages))
+		}
 	}
 
 	/// Return maximal message size of This -> Bridged chain message.
@@ -446,7 +807,7 @@ pub mod source {
 	/// parachains, please use the `verify_messages_delivery_proof_from_parachain`.
 	pub fn verify_messages_delivery_proof<B: MessageBridge, ThisRuntime, GrandpaInstance: 'static>(
 		proof: FromBridgedChainMessagesDeliveryProof<HashOf<BridgedChain<B>>>,
-	) -> Result<ParsedMessagesDeliveryProofFromBridgedChain<B>, &'static str>
+	) -> Result<ParsedMessagesDeliveryProofFromBridgedChain<B>, VerificationError>
 	where
 		ThisRuntime: pallet_bridge_grandpa::Config<GrandpaInstance>,
 		HashOf<BridgedChain<B>>: Into<
@@ -467,7 +828,55 @@ pub mod source {
 				>,
 			>(lane, storage),
 		)
-		.map_err(<&'static str>::from)?
+		.map_err(|e| {
+			storage_proof_verification_error(
+				e,
+				pallet_bridge_grandpa::Error::<ThisRuntime, GrandpaInstance>::StorageRootMismatch
+					.into(),
+			)
+		})?
+	}
+
+	/// Verify proof of This -> Bridged chain messages delivery.
+	///
+	/// This function is used when Bridged chain headers are finalized by BEEFY and imported
+	/// into the `pallet-bridge-beefy`.
+	pub fn verify_messages_delivery_proof_from_beefy_chain<
+		B: MessageBridge,
+		ThisRuntime,
+		BeefyInstance: 'static,
+	>(
+		proof: FromBridgedChainMessagesDeliveryProof<HashOf<BridgedChain<B>>>,
+	) -> Result<ParsedMessagesDeliveryProofFromBridgedChain<B>, VerificationError>
+	where
+		ThisRuntime: pallet_bridge_beefy::Config<BeefyInstance>,
+		HashOf<BridgedChain<B>>: Into<
+			bp_runtime::HashOf<
+				<ThisRuntime as pallet_bridge_beefy::Config<BeefyInstance>>::BridgedChain,
+			>,
+		>,
+	{
+		let FromBridgedChainMessagesDeliveryProof { bridged_header_hash, storage_proof, lane } =
+			proof;
+		pallet_bridge_beefy::Pallet::<ThisRuntime, BeefyInstance>::parse_finalized_storage_proof(
+			bridged_header_hash.into(),
+			StorageProof::new(storage_proof),
+			|storage| {
+				do_verify_messages_delivery_proof::<
+					B,
+					bp_runtime::HasherOf<
+						<ThisRuntime as pallet_bridge_beefy::Config<BeefyInstance>>::BridgedChain,
+					>,
+				>(lane, storage)
+			},
+		)
+		.map_err(|e| {
+			storage_proof_verification_error(
+				e,
+				pallet_bridge_beefy::Error::<ThisRuntime, BeefyInstance>::StorageRootMismatch
+					.into(),
+			)
+		})?
 	}
 
 	/// Verify proof of This -> Bridged chain messages delivery.
@@ -485,7 +894,7 @@ pub mod source {
 	>(
 		bridged_parachain: ParaId,
 		proof: FromBridgedChainMessagesDeliveryProof<HashOf<BridgedChain<B>>>,
-	) -> Result<ParsedMessagesDeliveryProofFromBridgedChain<B>, &'static str>
+	) -> Result<ParsedMessagesDeliveryProofFromBridgedChain<B>, VerificationError>
 	where
 		B: MessageBridge,
 		B::BridgedChain: ChainWithMessages<Hash = ParaHash>,
@@ -501,28 +910,61 @@ pub mod source {
 			|para_head| BridgedHeader::decode(&mut &para_head.0[..]).ok().map(|h| *h.state_root()),
 			|storage| do_verify_messages_delivery_proof::<B, ParaHasher>(lane, storage),
 		)
-		.map_err(<&'static str>::from)?
+		.map_err(|e| {
+			storage_proof_verification_error(
+				e,
+				pallet_bridge_parachains::Error::<ThisRuntime, ParachainsInstance>::StorageRootMismatch
+					.into(),
+			)
+		})?
 	}
 
 	/// The essense of This -> Bridged chain messages delivery proof verification.
 	fn do_verify_messages_delivery_proof<B: MessageBridge, H: Hasher>(
 		lane: LaneId,
 		storage: bp_runtime::StorageProofChecker<H>,
-	) -> Result<ParsedMessagesDeliveryProofFromBridgedChain<B>, &'static str> {
-		// Messages delivery proof is just proof of single storage key read => any error
-		// is fatal.
+	) -> Result<ParsedMessagesDeliveryProofFromBridgedChain<B>, VerificationError> {
+		// Messages delivery proof is a proof of inbound lane state and dispatch responses of all
+		// unrewarded messages => any error is fatal.
 		let storage_inbound_lane_data_key = bp_messages::storage_keys::inbound_lane_data_key(
 			B::BRIDGED_MESSAGES_PALLET_NAME,
 			&lane,
 		);
 		let raw_inbound_lane_data = storage
 			.read_value(storage_inbound_lane_data_key.0.as_ref())
-			.map_err(|_| "Failed to read inbound lane state from storage proof")?
-			.ok_or("Inbound lane state is missing from the messages proof")?;
-		let inbound_lane_data = InboundLaneData::decode(&mut &raw_inbound_lane_data[..])
-			.map_err(|_| "Failed to decode inbound lane state from the proof")?;
+			.map_err(|_| VerificationError::InvalidStorageProof)?
+			.ok_or(VerificationError::InvalidStorageProof)?;
+		let inbound_lane_data: InboundLaneData<AccountIdOf<ThisChain<B>>> =
+			InboundLaneData::decode(&mut &raw_inbound_lane_data[..])
+				.map_err(|_| VerificationError::InvalidProvedData)?;
+
+		// the proof must also prove (non-)existence of dispatch responses of all unrewarded
+		// messages. Otherwise the relayer could drop responses that it doesn't like.
+		//
+		// The loop is bounded by the `MaxUnconfirmedMessagesAtInboundLane` at the bridged chain.
+		let mut responses = MessagesResponses::new();
+		let unrewarded_messages =
+			match (inbound_lane_data.relayers.front(), inbound_lane_data.relayers.back()) {
+				(Some(front), Some(back)) => front.messages.begin..=back.messages.end,
+				_ => 1..=0,
+			};
+		for nonce in unrewarded_messages {
+			let storage_response_key = bp_messages::storage_keys::inbound_message_response_key(
+				B::BRIDGED_MESSAGES_PALLET_NAME,
+				&lane,
+				nonce,
+			);
+			let raw_response = storage
+				.read_value(storage_response_key.0.as_ref())
+				.map_err(|_| VerificationError::InvalidStorageProof)?;
+			if let Some(raw_response) = raw_response {
+				let response = Vec::<u8>::decode(&mut &raw_response[..])
+					.map_err(|_| VerificationError::InvalidProvedData)?;
+				responses.insert(nonce, response);
+			}
+		}
 
-		Ok((lane, inbound_lane_data))
+		Ok((lane, inbound_lane_data, responses))
 	}
 
 	/// XCM bridge.
@@ -543,8 +985,22 @@ pub mod source {
 		fn verify_destination(dest: &MultiLocation) -> bool;
 		/// Build route from this chain to the XCM destination.
 		fn build_destination() -> MultiLocation;
+		/// Build location of this chain, as it is seen from the XCM destination. This location is
+		/// used as an origin of XCM program, when it is executed at the bridged chain.
+		///
+		/// By default, it is the same as the route from this chain to the XCM destination.
+		fn build_origin() -> MultiLocation {
+			Self::build_destination()
+		}
 		/// Return message lane used to deliver XCM messages.
 		fn xcm_lane() -> LaneId;
+		/// Return compression algorithm that is used to compress outbound XCM messages.
+		///
+		/// The payload is only compressed if it becomes smaller after compression. By default,
+		/// payloads are not compressed.
+		fn payload_compression() -> Option<crate::messages_compression::PayloadCompression> {
+			None
+		}
 	}
 
 	/// XCM bridge adapter for `bridge-messages` pallet.
@@ -567,8 +1023,20 @@ pub mod source {
 				return Err(SendError::NotApplicable)
 			}
 
-			let route = T::build_destination();
-			let msg = (route, msg.take().ok_or(SendError::MissingArgument)?).encode();
+			let origin = T::build_origin();
+			let msg = (origin, msg.take().ok_or(SendError::MissingArgument)?).encode();
+			let msg = match T::payload_compression() {
+				Some(compression) => {
+					let compressed_msg =
+						crate::messages_compression::compress_payload(compression, &msg);
+					if compressed_msg.len() < msg.len() {
+						compressed_msg
+					} else {
+						msg
+					}
+				},
+				None => msg,
+			};
 
 			let fee = estimate_message_dispatch_and_delivery_fee::<T::MessageBridge>(
 				&msg,
@@ -636,24 +1104,39 @@ pub mod target {
 	use super::*;
 
 	/// Decoded Bridged -> This message payload.
+	///
+	/// The payload may be compressed by the message sender (see `messages_compression` module).
+	/// In this case it is decompressed during decoding and the decompression cost is added to the
+	/// message dispatch weight.
 	#[derive(RuntimeDebug, PartialEq, Eq)]
 	pub struct FromBridgedChainMessagePayload<Call> {
 		/// Data that is actually sent over the wire.
 		pub xcm: (xcm::v3::MultiLocation, xcm::v3::Xcm<Call>),
 		/// Weight of the message, computed by the weigher. Unknown initially.
 		pub weight: Option<Weight>,
+		/// Size of the decompressed payload if the payload has been compressed.
+		pub decompressed_size: Option<u32>,
 	}
 
 	impl<Call: Decode> Decode for FromBridgedChainMessagePayload<Call> {
 		fn decode<I: codec::Input>(input: &mut I) -> Result<Self, codec::Error> {
-			let _: codec::Compact<u32> = Decode::decode(input)?;
+			let encoded_payload: Vec<u8> = Decode::decode(input)?;
+			let payload = crate::messages_compression::decompress_payload(
+				&encoded_payload,
+				crate::messages_compression::MAXIMAL_DECOMPRESSED_PAYLOAD_SIZE,
+			)?;
+			let decompressed_size =
+				crate::messages_compression::is_compressed_payload(&encoded_payload)
+					.then(|| payload.len() as u32);
+
 			type XcmPairType<Call> = (xcm::v3::MultiLocation, xcm::v3::Xcm<Call>);
 			Ok(FromBridgedChainMessagePayload {
 				xcm: XcmPairType::<Call>::decode_with_depth_limit(
 					sp_api::MAX_EXTRINSIC_DEPTH,
-					input,
+					&mut &payload[..],
 				)?,
 				weight: None,
+				decompressed_size,
 			})
 		}
 	}
@@ -662,7 +1145,7 @@ pub mod target {
 		for FromBridgedChainMessagePayload<Call>
 	{
 		fn from(xcm: (xcm::v3::MultiLocation, xcm::v3::Xcm<Call>)) -> Self {
-			FromBridgedChainMessagePayload { xcm, weight: None }
+			FromBridgedChainMessagePayload { xcm, weight: None, decompressed_size: None }
 		}
 	}
 
@@ -698,18 +1181,30 @@ pub mod target {
 	}
 
 	/// Dispatching Bridged -> This chain messages.
+	///
+	/// If the XCM program has not been executed at all, the message dispatch is considered
+	/// failed and it may be retried by the account, which is derived from the message origin
+	/// using the `RetryAccountOf` converter. Partially executed programs are never retried.
 	#[derive(RuntimeDebug, Clone, Copy)]
-	pub struct FromBridgedChainMessageDispatch<B, XcmExecutor, XcmWeigher, WeightCredit> {
-		_marker: PhantomData<(B, XcmExecutor, XcmWeigher, WeightCredit)>,
+	pub struct FromBridgedChainMessageDispatch<
+		B,
+		XcmExecutor,
+		XcmWeigher,
+		WeightCredit,
+		RetryAccountOf = (),
+	> {
+		_marker: PhantomData<(B, XcmExecutor, XcmWeigher, WeightCredit, RetryAccountOf)>,
 	}
 
-	impl<B: MessageBridge, XcmExecutor, XcmWeigher, WeightCredit>
+	impl<B: MessageBridge, XcmExecutor, XcmWeigher, WeightCredit, RetryAccountOf>
 		MessageDispatch<AccountIdOf<ThisChain<B>>, BalanceOf<BridgedChain<B>>>
-		for FromBridgedChainMessageDispatch<B, XcmExecutor, XcmWeigher, WeightCredit>
+		for FromBridgedChainMessageDispatch<B, XcmExecutor, XcmWeigher, WeightCredit, RetryAccountOf>
 	where
 		XcmExecutor: xcm::v3::ExecuteXcm<CallOf<ThisChain<B>>>,
 		XcmWeigher: xcm_executor::traits::WeightBounds<CallOf<ThisChain<B>>>,
 		WeightCredit: Get<Weight>,
+		RetryAccountOf:
+			xcm_executor::traits::Convert<xcm::v3::MultiLocation, AccountIdOf<ThisChain<B>>>,
 	{
 		type DispatchPayload = FromBridgedChainMessagePayload<CallOf<ThisChain<B>>>;
 
@@ -736,20 +1231,62 @@ pub mod target {
 					});
 
 					payload.weight = Some(weight);
-					weight
+
+					// decompression has already happened, but it isn't covered by the delivery
+					// transaction weight => let's add it to the dispatch weight
+					weight.saturating_add(
+						payload
+							.decompressed_size
+							.map(crate::messages_compression::decompression_weight)
+							.unwrap_or(0),
+					)
 				},
 				_ => 0,
 			}
 		}
 
+		fn dispatch_retry_account(
+			message: &DispatchMessage<Self::DispatchPayload, BalanceOf<BridgedChain<B>>>,
+		) -> Option<AccountIdOf<ThisChain<B>>> {
+			let payload = message.data.payload.as_ref().ok()?;
+			RetryAccountOf::convert_ref(&payload.xcm.0).ok()
+		}
+
 		fn dispatch(
 			_relayer_account: &AccountIdOf<ThisChain<B>>,
 			message: DispatchMessage<Self::DispatchPayload, BalanceOf<BridgedChain<B>>>,
 		) -> MessageDispatchResult {
+			// the outcome is already logged, so we only need to know whether it may be retried
+			let xcm_outcome = Self::execute_message(message);
+			MessageDispatchResult {
+				dispatch_result: is_xcm_executed(&xcm_outcome),
+				unspent_weight: 0,
+				dispatch_fee_paid_during_dispatch: false,
+				response: None,
+			}
+		}
+	}
+
+	impl<B: MessageBridge, XcmExecutor, XcmWeigher, WeightCredit, RetryAccountOf>
+		FromBridgedChainMessageDispatch<B, XcmExecutor, XcmWeigher, WeightCredit, RetryAccountOf>
+	where
+		XcmExecutor: xcm::v3::ExecuteXcm<CallOf<ThisChain<B>>>,
+		WeightCredit: Get<Weight>,
+	{
+		/// Execute XCM program from the message payload and return the execution outcome.
+		fn execute_message(
+			message: DispatchMessage<
+				FromBridgedChainMessagePayload<CallOf<ThisChain<B>>>,
+				BalanceOf<BridgedChain<B>>,
+			>,
+		) -> Result<Outcome, codec::Error> {
 			let message_id = (message.key.lane_id, message.key.nonce);
 			let do_dispatch = move || -> sp_std::result::Result<Outcome, codec::Error> {
-				let FromBridgedChainMessagePayload { xcm: (location, xcm), weight: weight_limit } =
-					message.data.payload?;
+				let FromBridgedChainMessagePayload {
+					xcm: (location, xcm),
+					weight: weight_limit,
+					..
+				} = message.data.payload?;
 				log::trace!(
 					target: "runtime::bridge-dispatch",
 					"Going to execute message {:?} (weight limit: {:?}): {:?} {:?}",
@@ -775,10 +1312,106 @@ pub mod target {
 
 			let xcm_outcome = do_dispatch();
 			log::trace!(target: "runtime::bridge-dispatch", "Incoming message {:?} dispatched with result: {:?}", message_id, xcm_outcome);
+			xcm_outcome
+		}
+	}
+
+	/// Returns true if the XCM program has been (maybe partially) executed.
+	///
+	/// Programs that have been partially executed can't be retried, because that would execute
+	/// some instructions twice.
+	fn is_xcm_executed(xcm_outcome: &Result<Outcome, codec::Error>) -> bool {
+		!matches!(xcm_outcome, Err(_) | Ok(Outcome::Error(_)))
+	}
+
+	/// Response of the Bridged -> This chain message dispatcher, that is relayed back to the
+	/// Bridged chain.
+	#[derive(Decode, Encode, Clone, Eq, PartialEq, RuntimeDebug)]
+	pub enum FromBridgedChainMessageResponse {
+		/// The message payload has failed to decode, so nothing has been executed.
+		UndecodablePayload,
+		/// XCM program from the message has been executed with given outcome.
+		Executed(Outcome),
+	}
+
+	/// Dispatching Bridged -> This chain messages, with dispatch response.
+	///
+	/// This works exactly like `FromBridgedChainMessageDispatch`, but also returns encoded
+	/// `FromBridgedChainMessageResponse` that is relayed back to the Bridged chain.
+	#[derive(RuntimeDebug, Clone, Copy)]
+	pub struct FromBridgedChainMessageDispatchWithResponse<
+		B,
+		XcmExecutor,
+		XcmWeigher,
+		WeightCredit,
+		RetryAccountOf = (),
+	> {
+		_marker: PhantomData<(B, XcmExecutor, XcmWeigher, WeightCredit, RetryAccountOf)>,
+	}
+
+	impl<B: MessageBridge, XcmExecutor, XcmWeigher, WeightCredit, RetryAccountOf>
+		MessageDispatch<AccountIdOf<ThisChain<B>>, BalanceOf<BridgedChain<B>>>
+		for FromBridgedChainMessageDispatchWithResponse<
+			B,
+			XcmExecutor,
+			XcmWeigher,
+			WeightCredit,
+			RetryAccountOf,
+		>
+	where
+		XcmExecutor: xcm::v3::ExecuteXcm<CallOf<ThisChain<B>>>,
+		XcmWeigher: xcm_executor::traits::WeightBounds<CallOf<ThisChain<B>>>,
+		WeightCredit: Get<Weight>,
+		RetryAccountOf:
+			xcm_executor::traits::Convert<xcm::v3::MultiLocation, AccountIdOf<ThisChain<B>>>,
+	{
+		type DispatchPayload = FromBridgedChainMessagePayload<CallOf<ThisChain<B>>>;
+
+		fn dispatch_weight(
+			message: &mut DispatchMessage<Self::DispatchPayload, BalanceOf<BridgedChain<B>>>,
+		) -> frame_support::weights::Weight {
+			FromBridgedChainMessageDispatch::<
+				B,
+				XcmExecutor,
+				XcmWeigher,
+				WeightCredit,
+				RetryAccountOf,
+			>::dispatch_weight(message)
+		}
+
+		fn dispatch_retry_account(
+			message: &DispatchMessage<Self::DispatchPayload, BalanceOf<BridgedChain<B>>>,
+		) -> Option<AccountIdOf<ThisChain<B>>> {
+			FromBridgedChainMessageDispatch::<
+				B,
+				XcmExecutor,
+				XcmWeigher,
+				WeightCredit,
+				RetryAccountOf,
+			>::dispatch_retry_account(message)
+		}
+
+		fn dispatch(
+			_relayer_account: &AccountIdOf<ThisChain<B>>,
+			message: DispatchMessage<Self::DispatchPayload, BalanceOf<BridgedChain<B>>>,
+		) -> MessageDispatchResult {
+			let xcm_outcome = FromBridgedChainMessageDispatch::<
+				B,
+				XcmExecutor,
+				XcmWeigher,
+				WeightCredit,
+				RetryAccountOf,
+			>::execute_message(message);
+			let dispatch_result = is_xcm_executed(&xcm_outcome);
+			let response = match xcm_outcome {
+				Ok(xcm_outcome) => FromBridgedChainMessageResponse::Executed(xcm_outcome),
+				Err(_) => FromBridgedChainMessageResponse::UndecodablePayload,
+			};
 			MessageDispatchResult {
-				dispatch_result: true,
+				dispatch_result,
 				unspent_weight: 0,
 				dispatch_fee_paid_during_dispatch: false,
+				response: Some(response.encode()),
 			}
 		}
 	}
@@ -804,7 +1437,7 @@ pub mod target {
 	pub fn verify_messages_proof<B: MessageBridge, ThisRuntime, GrandpaInstance: 'static>(
 		proof: FromBridgedChainMessagesProof<HashOf<BridgedChain<B>>>,
 		messages_count: u32,
-	) -> Result<ProvedMessages<Message<BalanceOf<BridgedChain<B>>>>, &'static str>
+	) -> Result<ProvedMessages<Message<BalanceOf<BridgedChain<B>>>>, VerificationError>
 	where
 		ThisRuntime: pallet_bridge_grandpa::Config<GrandpaInstance>,
 		HashOf<BridgedChain<B>>: Into<
@@ -826,7 +1459,62 @@ pub mod target {
 					storage,
 					_dummy: Default::default(),
 				})
-				.map_err(|err| MessageProofError::Custom(err.into()))
+				.map_err(|err| {
+					MessageProofError::Custom(storage_proof_verification_error(
+						err,
+						pallet_bridge_grandpa::Error::<ThisRuntime, GrandpaInstance>::StorageRootMismatch
+							.into(),
+					))
+				})
+			},
+		)
+		.map_err(Into::into)
+	}
+
+	/// Verify proof of Bridged -> This chain messages.
+	///
+	/// This function is used when Bridged chain headers are finalized by BEEFY and imported
+	/// into the `pallet-bridge-beefy`.
+	///
+	/// The `messages_count` argument verification (sane limits) is supposed to be made
+	/// outside of this function. This function only verifies that the proof declares exactly
+	/// `messages_count` messages.
+	pub fn verify_messages_proof_from_beefy_chain<
+		B: MessageBridge,
+		ThisRuntime,
+		BeefyInstance: 'static,
+	>(
+		proof: FromBridgedChainMessagesProof<HashOf<BridgedChain<B>>>,
+		messages_count: u32,
+	) -> Result<ProvedMessages<Message<BalanceOf<BridgedChain<B>>>>, VerificationError>
+	where
+		ThisRuntime: pallet_bridge_beefy::Config<BeefyInstance>,
+		HashOf<BridgedChain<B>>: Into<
+			bp_runtime::HashOf<
+				<ThisRuntime as pallet_bridge_beefy::Config<BeefyInstance>>::BridgedChain,
+			>,
+		>,
+	{
+		verify_messages_proof_with_parser::<B, _, _>(
+			proof,
+			messages_count,
+			|bridged_header_hash, bridged_storage_proof| {
+				pallet_bridge_beefy::Pallet::<ThisRuntime, BeefyInstance>::parse_finalized_storage_proof(
+					bridged_header_hash.into(),
+					StorageProof::new(bridged_storage_proof),
+					|storage_adapter| storage_adapter,
+				)
+				.map(|storage| StorageProofCheckerAdapter::<_, B> {
+					storage,
+					_dummy: Default::default(),
+				})
+				.map_err(|err| {
+					MessageProofError::Custom(storage_proof_verification_error(
+						err,
+						pallet_bridge_beefy::Error::<ThisRuntime, BeefyInstance>::StorageRootMismatch
+							.into(),
+					))
+				})
 			},
 		)
 		.map_err(Into::into)
@@ -852,7 +1540,7 @@ pub mod target {
 		bridged_parachain: ParaId,
 		proof: FromBridgedChainMessagesProof<HashOf<BridgedChain<B>>>,
 		messages_count: u32,
-	) -> Result<ProvedMessages<Message<BalanceOf<BridgedChain<B>>>>, &'static str>
+	) -> Result<ProvedMessages<Message<BalanceOf<BridgedChain<B>>>>, VerificationError>
 	where
 		B: MessageBridge,
 		B::BridgedChain: ChainWithMessages<Hash = ParaHash>,
@@ -874,7 +1562,13 @@ pub mod target {
 					storage,
 					_dummy: Default::default(),
 				})
-				.map_err(|err| MessageProofError::Custom(err.into()))
+				.map_err(|err| {
+					MessageProofError::Custom(storage_proof_verification_error(
+						err,
+						pallet_bridge_parachains::Error::<ThisRuntime, ParachainsInstance>::StorageRootMismatch
+							.into(),
+					))
+				})
 			},
 		)
 		.map_err(Into::into)
@@ -887,20 +1581,17 @@ pub mod target {
 		MissingRequiredMessage,
 		FailedToDecodeMessage,
 		FailedToDecodeOutboundLaneState,
-		Custom(&'static str),
+		Custom(VerificationError),
 	}
 
-	impl From<MessageProofError> for &'static str {
-		fn from(err: MessageProofError) -> &'static str {
+	impl From<MessageProofError> for VerificationError {
+		fn from(err: MessageProofError) -> VerificationError {
 			match err {
-				MessageProofError::Empty => "Messages proof is empty",
-				MessageProofError::MessagesCountMismatch =>
-					"Declared messages count doesn't match actual value",
-				MessageProofError::MissingRequiredMessage => "Message is missing from the proof",
-				MessageProofError::FailedToDecodeMessage =>
-					"Failed to decode message from the proof",
-				MessageProofError::FailedToDecodeOutboundLaneState =>
-					"Failed to decode outbound lane data from the proof",
+				MessageProofError::Empty |
+				MessageProofError::MessagesCountMismatch |
+				MessageProofError::FailedToDecodeMessage |
+				MessageProofError::FailedToDecodeOutboundLaneState => VerificationError::InvalidProvedData,
+				MessageProofError::MissingRequiredMessage => VerificationError::InvalidStorageProof,
 				MessageProofError::Custom(err) => err,
 			}
 		}
@@ -1324,6 +2015,10 @@ mod tests {
 			MAXIMAL_PENDING_MESSAGES_AT_TEST_LANE
 		}
 
+		fn verify_registered_lane_weight() -> Weight {
+			VERIFY_REGISTERED_LANE_WEIGHT
+		}
+
 		fn transaction_payment(transaction: MessageTransaction<WeightOf<Self>>) -> BalanceOf<Self> {
 			ThisChainBalance(
 				transaction.dispatch_weight as u32 * THIS_CHAIN_WEIGHT_TO_BALANCE_RATE as u32,
@@ -1425,6 +2120,7 @@ mod tests {
 
 	const TEST_LANE_ID: &LaneId = b"test";
 	const MAXIMAL_PENDING_MESSAGES_AT_TEST_LANE: MessageNonce = 32;
+	const VERIFY_REGISTERED_LANE_WEIGHT: Weight = 777;
 
 	fn regular_outbound_message_payload() -> source::FromThisChainMessagePayload {
 		vec![42]
@@ -1468,6 +2164,16 @@ mod tests {
 		.is_ok(),);
 	}
 
+	#[test]
+	fn message_verifier_weight_includes_registered_lane_verification() {
+		assert_eq!(
+			source::FromThisChainMessageVerifier::<OnThisChainBridge>::verify_message_weight(
+				RuntimeDbWeight { read: 1, write: 1 },
+			),
+			VERIFY_REGISTERED_LANE_WEIGHT,
+		);
+	}
+
 	#[test]
 	fn message_is_rejected_when_sent_using_disabled_lane() {
 		assert_eq!(
@@ -1500,6 +2206,246 @@ mod tests {
 		);
 	}
 
+	struct TestBlockNumberProvider;
+
+	impl sp_runtime::traits::BlockNumberProvider for TestBlockNumberProvider {
+		type BlockNumber = u32;
+
+		fn current_block_number() -> u32 {
+			frame_support::storage::unhashed::get_or_default(b":test-block-number:")
+		}
+	}
+
+	fn set_block_number(block_number: u32) {
+		frame_support::storage::unhashed::put(b":test-block-number:", &block_number);
+	}
+
+	frame_support::parameter_types! {
+		const TestSenderRateLimits: source::SenderRateLimits<u32> = source::SenderRateLimits {
+			window: 10,
+			max_messages: 2,
+			max_payload_size: 8,
+		};
+	}
+
+	type RateLimitedVerifier = source::SenderRateLimitedMessageVerifier<
+		OnThisChainBridge,
+		source::FromThisChainMessageVerifier<OnThisChainBridge>,
+		TestSenderRateLimits,
+		TestBlockNumberProvider,
+	>;
+
+	fn send_rate_limited_message(
+		origin: frame_system::RawOrigin<ThisChainAccountId>,
+		payload_size: usize,
+	) -> Result<(), &'static str> {
+		RateLimitedVerifier::verify_message(
+			&ThisChainOrigin(Ok(origin)),
+			&ThisChainBalance(1_000_000),
+			TEST_LANE_ID,
+			&test_lane_outbound_data(),
+			&vec![42; payload_size],
+		)
+	}
+
+	#[test]
+	fn sender_rate_limits_are_enforced_within_window() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			let sender = || frame_system::RawOrigin::Signed(ThisChainAccountId(1));
+			set_block_number(11);
+
+			// sender may send up to `max_messages` messages within the window
+			assert_eq!(send_rate_limited_message(sender(), 1), Ok(()));
+			assert_eq!(send_rate_limited_message(sender(), 1), Ok(()));
+			assert_eq!(
+				send_rate_limited_message(sender(), 1),
+				Err(source::SENDER_RATE_LIMIT_EXCEEDED)
+			);
+
+			// other senders are not affected
+			assert_eq!(
+				send_rate_limited_message(
+					frame_system::RawOrigin::Signed(ThisChainAccountId(2)),
+					1
+				),
+				Ok(())
+			);
+			assert_eq!(send_rate_limited_message(frame_system::RawOrigin::Root, 1), Ok(()));
+
+			// quota is restored gradually when the next window starts
+			set_block_number(19);
+			assert_eq!(
+				send_rate_limited_message(sender(), 1),
+				Err(source::SENDER_RATE_LIMIT_EXCEEDED)
+			);
+			set_block_number(20);
+			assert_eq!(
+				send_rate_limited_message(sender(), 1),
+				Err(source::SENDER_RATE_LIMIT_EXCEEDED)
+			);
+			set_block_number(25);
+			assert_eq!(send_rate_limited_message(sender(), 1), Ok(()));
+			assert_eq!(
+				send_rate_limited_message(sender(), 1),
+				Err(source::SENDER_RATE_LIMIT_EXCEEDED)
+			);
+		});
+	}
+
+	#[test]
+	fn sender_is_unable_to_send_doubled_quota_around_window_boundary() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			let sender = || frame_system::RawOrigin::Signed(ThisChainAccountId(1));
+
+			set_block_number(19);
+			assert_eq!(send_rate_limited_message(sender(), 1), Ok(()));
+			assert_eq!(send_rate_limited_message(sender(), 1), Ok(()));
+
+			set_block_number(20);
+			assert_eq!(
+				send_rate_limited_message(sender(), 1),
+				Err(source::SENDER_RATE_LIMIT_EXCEEDED)
+			);
+
+			set_block_number(29);
+			assert_eq!(send_rate_limited_message(sender(), 1), Ok(()));
+		});
+	}
+
+	#[test]
+	fn stale_sender_messages_usages_are_pruned() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			let sender = |id| frame_system::RawOrigin::Signed(ThisChainAccountId(id));
+			let stale_usages = || {
+				source::SenderMessagesUsages::<ThisChainAccountId, u32>::iter_prefix((
+					OnThisChainBridge::BRIDGED_CHAIN_ID,
+					*TEST_LANE_ID,
+					0,
+				))
+				.count()
+			};
+
+			set_block_number(1);
+			assert_eq!(send_rate_limited_message(sender(1), 1), Ok(()));
+			assert_eq!(send_rate_limited_message(sender(2), 1), Ok(()));
+			assert_eq!(send_rate_limited_message(sender(3), 1), Ok(()));
+			assert_eq!(stale_usages(), 3);
+
+			// usages of the window #0 are not pruned while it is the previous window
+			set_block_number(11);
+			assert_eq!(send_rate_limited_message(sender(4), 1), Ok(()));
+			assert_eq!(stale_usages(), 3);
+
+			// and are pruned when the window becomes stale
+			set_block_number(21);
+			assert_eq!(send_rate_limited_message(sender(4), 1), Ok(()));
+			assert_eq!(stale_usages(), 1);
+			assert_eq!(send_rate_limited_message(sender(4), 1), Ok(()));
+			assert_eq!(stale_usages(), 0);
+		});
+	}
+
+	#[test]
+	fn sender_payload_size_limit_is_enforced_within_window() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			let sender = || frame_system::RawOrigin::Signed(ThisChainAccountId(1));
+
+			assert_eq!(send_rate_limited_message(sender(), 6), Ok(()));
+			assert_eq!(
+				send_rate_limited_message(sender(), 3),
+				Err(source::SENDER_PAYLOAD_SIZE_LIMIT_EXCEEDED)
+			);
+			assert_eq!(send_rate_limited_message(sender(), 2), Ok(()));
+		});
+	}
+
+	#[test]
+	fn rejected_messages_are_not_counted_by_rate_limited_verifier() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			let sender = || frame_system::RawOrigin::Signed(ThisChainAccountId(1));
+
+			// message is rejected by the wrapped verifier
+			assert_eq!(
+				RateLimitedVerifier::verify_message(
+					&ThisChainOrigin(Ok(sender())),
+					&ThisChainBalance(1),
+					TEST_LANE_ID,
+					&test_lane_outbound_data(),
+					&regular_outbound_message_payload(),
+				),
+				Err(source::TOO_LOW_FEE)
+			);
+
+			assert_eq!(send_rate_limited_message(sender(), 1), Ok(()));
+			assert_eq!(send_rate_limited_message(sender(), 1), Ok(()));
+		});
+	}
+
+	/// Initiator, that is encoded in the first byte of the payload. Zero byte means that the
+	/// initiator is unknown.
+	struct PayloadMessageInitiator;
+
+	impl MessageInitiator<ThisChainOrigin, source::FromThisChainMessagePayload, ThisChainAccountId>
+		for PayloadMessageInitiator
+	{
+		fn message_initiator(
+			_submitter: &ThisChainOrigin,
+			_lane: &LaneId,
+			payload: &source::FromThisChainMessagePayload,
+		) -> Option<ThisChainAccountId> {
+			payload
+				.first()
+				.filter(|initiator| **initiator != 0)
+				.map(|initiator| ThisChainAccountId(*initiator as _))
+		}
+	}
+
+	#[test]
+	fn messages_are_rate_limited_by_message_initiator() {
+		type InitiatorRateLimitedVerifier = source::SenderRateLimitedMessageVerifier<
+			OnThisChainBridge,
+			source::FromThisChainMessageVerifier<OnThisChainBridge>,
+			TestSenderRateLimits,
+			TestBlockNumberProvider,
+			(PayloadMessageInitiator, source::SignedMessageInitiator),
+		>;
+
+		let send_message = |origin, payload: Vec<u8>| {
+			InitiatorRateLimitedVerifier::verify_message(
+				&ThisChainOrigin(Ok(origin)),
+				&ThisChainBalance(1_000_000),
+				TEST_LANE_ID,
+				&test_lane_outbound_data(),
+				&payload,
+			)
+		};
+
+		sp_io::TestExternalities::default().execute_with(|| {
+			let submitter = || frame_system::RawOrigin::Signed(ThisChainAccountId(1));
+
+			// messages of different initiators, sent by the same submitter, are limited separately
+			assert_eq!(send_message(submitter(), vec![10]), Ok(()));
+			assert_eq!(send_message(submitter(), vec![10]), Ok(()));
+			assert_eq!(send_message(submitter(), vec![20]), Ok(()));
+			assert_eq!(
+				send_message(submitter(), vec![10]),
+				Err(source::SENDER_RATE_LIMIT_EXCEEDED)
+			);
+
+			// messages of the same initiator are limited, even if they're sent by other submitters
+			assert_eq!(
+				send_message(frame_system::RawOrigin::Root, vec![10]),
+				Err(source::SENDER_RATE_LIMIT_EXCEEDED)
+			);
+
+			// messages without initiator in the payload are limited by the signed origin
+			assert_eq!(send_message(submitter(), vec![0]), Ok(()));
+			assert_eq!(send_message(submitter(), vec![0]), Ok(()));
+			assert_eq!(send_message(submitter(), vec![0]), Err(source::SENDER_RATE_LIMIT_EXCEEDED));
+			assert_eq!(send_message(frame_system::RawOrigin::Root, vec![0]), Ok(()));
+		});
+	}
+
 	#[test]
 	fn verify_chain_message_rejects_message_with_too_small_declared_weight() {
 		assert!(source::verify_chain_message::<OnThisChainBridge>(&vec![
@@ -1563,10 +2509,10 @@ mod tests {
 				Some(vec![])
 			} else if self.messages.contains(&message_key.nonce) {
 				Some(
-					MessageData::<BridgedChainBalance> {
-						payload: message_key.nonce.encode(),
-						fee: BridgedChainBalance(0),
-					}
+					MessageData::<BridgedChainBalance>::new(
+						message_key.nonce.encode(),
+						BridgedChainBalance(0),
+					)
 					.encode(),
 				)
 			} else {
@@ -1620,9 +2566,9 @@ mod tests {
 			target::verify_messages_proof_with_parser::<OnThisChainBridge, _, TestMessageProofParser>(
 				messages_proof(10),
 				10,
-				|_, _| Err(target::MessageProofError::Custom("test")),
+				|_, _| Err(target::MessageProofError::Custom("test".into())),
 			),
-			Err(target::MessageProofError::Custom("test")),
+			Err(target::MessageProofError::Custom("test".into())),
 		);
 	}
 
@@ -1752,7 +2698,7 @@ mod tests {
 					}),
 					messages: vec![Message {
 						key: MessageKey { lane_id: Default::default(), nonce: 1 },
-						data: MessageData { payload: 1u64.encode(), fee: BridgedChainBalance(0) },
+						data: MessageData::new(1u64.encode(), BridgedChainBalance(0)),
 					}],
 				},
 			)]
@@ -165,10 +165,8 @@ where
 		// insert messages
 		for nonce in params.message_nonces.clone() {
 			let message_key = MessageKey { lane_id: params.lane, nonce };
-			let message_data = MessageData {
-				fee: BalanceOf::<BridgedChain<B>>::from(0),
-				payload: message_payload.clone(),
-			};
+			let message_data =
+				MessageData::new(message_payload.clone(), BalanceOf::<BridgedChain<B>>::from(0));
 			let storage_key = storage_keys::message_key(
 				B::BRIDGED_MESSAGES_PALLET_NAME,
 				&message_key.lane_id,
@@ -0,0 +1,198 @@
+// Copyright 2019-2022 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Optional compression of bridge message payloads.
+//!
+//! Message payloads are sent as raw SCALE-encoded bytes by default. Since the delivery cost
+//! depends on the payload size, the message sender may choose to compress the payload. The
+//! compressed payload is:
+//!
+//! - the `COMPRESSED_PAYLOAD_MARKER` byte;
+//! - the SCALE-encoded `PayloadCompression` (version tag);
+//! - the compact-encoded size of the decompressed payload;
+//! - compressed payload bytes.
+//!
+//! The marker occupies the place of `parents` field of the XCM `MultiLocation`, which is never
+//! set to this value in practice. So raw (uncompressed) payloads are still accepted.
+
+use codec::{Compact, Decode, Encode};
+use frame_support::{weights::Weight, RuntimeDebug};
+use sp_std::{borrow::Cow, vec::Vec};
+
+/// First byte of the compressed message payload.
+pub const COMPRESSED_PAYLOAD_MARKER: u8 = 0xFF;
+
+/// Maximal size of the decompressed message payload. Payloads that are larger than this value
+/// after decompression are rejected.
+pub const MAXIMAL_DECOMPRESSED_PAYLOAD_SIZE: u32 = 256 * 1024;
+
+/// Weight of decompressing single byte of the message payload.
+///
+/// LZ4 decompression is faster than 1 GB/s on reference hardware. We're using 2ns per byte to
+/// leave some margin.
+pub const DECOMPRESSION_WEIGHT_PER_BYTE: Weight = 2_000;
+
+/// Compression algorithm, used to compress the message payload.
+#[derive(Clone, Copy, Decode, Encode, Eq, PartialEq, RuntimeDebug)]
+pub enum PayloadCompression {
+	/// LZ4 block format.
+	#[codec(index = 1)]
+	Lz4,
+}
+
+/// Error that may happen during payload decompression.
+#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+pub enum DecompressionError {
+	/// Payload is compressed using unknown algorithm.
+	UnknownCompression,
+	/// Decompressed payload is larger than allowed.
+	PayloadIsTooLarge,
+	/// Compressed payload is corrupted.
+	CorruptedPayload,
+}
+
+impl From<DecompressionError> for &'static str {
+	fn from(err: DecompressionError) -> &'static str {
+		match err {
+			DecompressionError::UnknownCompression =>
+				"Message payload is compressed using unknown algorithm",
+			DecompressionError::PayloadIsTooLarge => "Decompressed message payload is too large",
+			DecompressionError::CorruptedPayload => "Compressed message payload is corrupted",
+		}
+	}
+}
+
+impl From<DecompressionError> for codec::Error {
+	fn from(err: DecompressionError) -> codec::Error {
+		codec::Error::from(<&'static str>::from(err))
+	}
+}
+
+/// Compress message payload.
+pub fn compress_payload(compression: PayloadCompression, payload: &[u8]) -> Vec<u8> {
+	let compressed_payload = match compression {
+		PayloadCompression::Lz4 => lz4_flex::block::compress(payload),
+	};
+
+	let mut result = Vec::with_capacity(compressed_payload.len() + 8);
+	COMPRESSED_PAYLOAD_MARKER.encode_to(&mut result);
+	compression.encode_to(&mut result);
+	Compact(u32::try_from(payload.len()).unwrap_or(u32::MAX)).encode_to(&mut result);
+	result.extend_from_slice(&compressed_payload);
+	result
+}
+
+/// Returns true if message payload is compressed.
+pub fn is_compressed_payload(payload: &[u8]) -> bool {
+	payload.first() == Some(&COMPRESSED_PAYLOAD_MARKER)
+}
+
+/// Decompress message payload if it is compressed. Raw payloads are returned as is.
+pub fn decompress_payload(
+	payload: &[u8],
+	maximal_decompressed_size: u32,
+) -> Result<Cow<[u8]>, DecompressionError> {
+	if !is_compressed_payload(payload) {
+		return Ok(Cow::Borrowed(payload))
+	}
+
+	let mut input = &payload[1..];
+	let compression = PayloadCompression::decode(&mut input)
+		.map_err(|_| DecompressionError::UnknownCompression)?;
+	let decompressed_size: Compact<u32> =
+		Decode::decode(&mut input).map_err(|_| DecompressionError::CorruptedPayload)?;
+	if decompressed_size.0 > maximal_decompressed_size {
+		return Err(DecompressionError::PayloadIsTooLarge)
+	}
+
+	let decompressed_payload = match compression {
+		PayloadCompression::Lz4 => lz4_flex::block::decompress(input, decompressed_size.0 as usize)
+			.map_err(|_| DecompressionError::CorruptedPayload)?,
+	};
+	if decompressed_payload.len() != decompressed_size.0 as usize {
+		return Err(DecompressionError::CorruptedPayload)
+	}
+
+	Ok(Cow::Owned(decompressed_payload))
+}
+
+/// Returns weight of decompressing the payload of given (decompressed) size.
+pub fn decompression_weight(decompressed_size: u32) -> Weight {
+	DECOMPRESSION_WEIGHT_PER_BYTE.saturating_mul(decompressed_size.into())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn large_payload() -> Vec<u8> {
+		(0..4096u32).map(|i| (i % 16) as u8).collect()
+	}
+
+	#[test]
+	fn compressed_payload_is_decompressed() {
+		let payload = large_payload();
+		let compressed_payload = compress_payload(PayloadCompression::Lz4, &payload);
+		assert!(compressed_payload.len() < payload.len());
+		assert!(is_compressed_payload(&compressed_payload));
+		assert_eq!(
+			decompress_payload(&compressed_payload, MAXIMAL_DECOMPRESSED_PAYLOAD_SIZE)
+				.map(|payload| payload.into_owned()),
+			Ok(payload),
+		);
+	}
+
+	#[test]
+	fn raw_payload_is_returned_as_is() {
+		let payload = vec![0, 1, 2, 3];
+		assert!(!is_compressed_payload(&payload));
+		assert_eq!(
+			decompress_payload(&payload, MAXIMAL_DECOMPRESSED_PAYLOAD_SIZE),
+			Ok(Cow::Borrowed(&payload[..])),
+		);
+	}
+
+	#[test]
+	fn too_large_payload_is_not_decompressed() {
+		let payload = large_payload();
+		let compressed_payload = compress_payload(PayloadCompression::Lz4, &payload);
+		assert_eq!(
+			decompress_payload(&compressed_payload, payload.len() as u32 - 1),
+			Err(DecompressionError::PayloadIsTooLarge),
+		);
+	}
+
+	#[test]
+	fn payload_with_unknown_compression_is_rejected() {
+		let mut compressed_payload = compress_payload(PayloadCompression::Lz4, &large_payload());
+		compressed_payload[1] = 42;
+		assert_eq!(
+			decompress_payload(&compressed_payload, MAXIMAL_DECOMPRESSED_PAYLOAD_SIZE),
+			Err(DecompressionError::UnknownCompression),
+		);
+	}
+
+	#[test]
+	fn corrupted_payload_is_rejected() {
+		let payload = large_payload();
+		let mut compressed_payload = compress_payload(PayloadCompression::Lz4, &payload);
+		compressed_payload.truncate(compressed_payload.len() - 1);
+		assert_eq!(
+			decompress_payload(&compressed_payload, MAXIMAL_DECOMPRESSED_PAYLOAD_SIZE),
+			Err(DecompressionError::CorruptedPayload),
+		);
+	}
+}
@@ -20,9 +20,27 @@ use crate::{
 	},
 	BridgeRuntimeFilterCall,
 };
-use frame_support::{dispatch::CallableCallFor, traits::IsSubType};
+use bp_messages::{LaneId, MessageNonce};
+use frame_support::{
+	dispatch::CallableCallFor,
+	traits::{Get, IsSubType},
+};
 use pallet_bridge_messages::{Config, Pallet};
-use sp_runtime::transaction_validity::TransactionValidity;
+use sp_runtime::{
+	transaction_validity::{TransactionPriority, TransactionValidity, ValidTransaction},
+	DispatchError,
+};
+
+/// Priority boost for every new message, brought by the messages delivery transaction.
+///
+/// The more new messages the delivery transaction brings, the larger its priority is. So if there
+/// are several competing delivery transactions, the most useful one wins.
+pub const PRIORITY_BOOST_PER_MESSAGE: TransactionPriority = 1_000;
+
+/// Returns priority boost of the delivery transaction that brings given number of new messages.
+pub fn delivery_priority_boost(new_messages: MessageNonce) -> TransactionPriority {
+	new_messages.saturating_mul(PRIORITY_BOOST_PER_MESSAGE)
+}
 
 /// Validate messages in order to avoid "mining" messages delivery and delivery confirmation
 /// transactions, that are delivering outdated messages/confirmations. Without this validation,
@@ -49,6 +67,7 @@ impl<
 		match call.is_sub_type() {
 			Some(pallet_bridge_messages::Call::<T, I>::receive_messages_proof {
 				ref proof,
+				ref messages_count,
 				..
 			}) => {
 				let inbound_lane_data =
@@ -65,6 +84,22 @@ impl<
 
 					return sp_runtime::transaction_validity::InvalidTransaction::Stale.into()
 				}
+
+				// the transaction can't deliver more than `messages_count` messages and more
+				// messages than the inbound lane may accept before they're confirmed
+				let last_delivered_nonce = inbound_lane_data.last_delivered_nonce();
+				let unconfirmed_messages =
+					last_delivered_nonce.saturating_sub(inbound_lane_data.last_confirmed_nonce);
+				let new_messages = (proof.nonces_end - last_delivered_nonce)
+					.min(MessageNonce::from(*messages_count))
+					.min(
+						T::MaxUnconfirmedMessagesAtInboundLane::get()
+							.saturating_sub(unconfirmed_messages),
+					);
+				return Ok(ValidTransaction {
+					priority: delivery_priority_boost(new_messages),
+					..Default::default()
+				})
 			},
 			Some(pallet_bridge_messages::Call::<T, I>::receive_messages_delivery_proof {
 				ref proof,
@@ -91,22 +126,114 @@ impl<
 			_ => {},
 		}
 
-		Ok(sp_runtime::transaction_validity::ValidTransaction::default())
+		Ok(ValidTransaction::default())
+	}
+
+	fn is_bridge_call(call: &Call) -> bool {
+		matches!(
+			call.is_sub_type(),
+			Some(pallet_bridge_messages::Call::<T, I>::receive_messages_proof { .. }) |
+				Some(
+					pallet_bridge_messages::Call::<T, I>::receive_messages_delivery_proof { .. }
+				)
+		)
+	}
+
+	fn are_overlapping_calls(call: &Call, other_call: &Call) -> bool {
+		match (call.is_sub_type(), other_call.is_sub_type()) {
+			(
+				Some(pallet_bridge_messages::Call::<T, I>::receive_messages_proof {
+					ref proof,
+					..
+				}),
+				Some(pallet_bridge_messages::Call::<T, I>::receive_messages_proof {
+					proof: ref other_proof,
+					..
+				}),
+			) =>
+				proof.lane == other_proof.lane &&
+					proof.nonces_start <= other_proof.nonces_end &&
+					other_proof.nonces_start <= proof.nonces_end,
+			(
+				Some(pallet_bridge_messages::Call::<T, I>::receive_messages_delivery_proof {
+					ref proof,
+					..
+				}),
+				Some(pallet_bridge_messages::Call::<T, I>::receive_messages_delivery_proof {
+					proof: ref other_proof,
+					..
+				}),
+			) => proof.lane == other_proof.lane,
+			_ => false,
+		}
+	}
+
+	fn messages_lane(call: &Call) -> Option<LaneId> {
+		match call.is_sub_type() {
+			Some(pallet_bridge_messages::Call::<T, I>::receive_messages_proof {
+				ref proof,
+				..
+			}) => Some(proof.lane),
+			Some(pallet_bridge_messages::Call::<T, I>::receive_messages_delivery_proof {
+				ref proof,
+				..
+			}) => Some(proof.lane),
+			_ => None,
+		}
+	}
+
+	fn is_invalid_proof_error(error: &DispatchError) -> bool {
+		// `InvalidMessagesProof` and `InvalidMessagesDeliveryProof` errors are not here, because
+		// they're returned when the proof can't be verified at the moment (e.g. the bridged
+		// header is unknown or the finality pallet is halted)
+		*error == pallet_bridge_messages::Error::<T, I>::StorageRootMismatch.into() ||
+			*error == pallet_bridge_messages::Error::<T, I>::InvalidStorageProof.into() ||
+			*error == pallet_bridge_messages::Error::<T, I>::InvalidProvedData.into()
+	}
+
+	fn is_call_data_imported(call: &Call) -> bool {
+		match call.is_sub_type() {
+			Some(pallet_bridge_messages::Call::<T, I>::receive_messages_proof {
+				ref proof,
+				..
+			}) => {
+				let inbound_lane_data =
+					pallet_bridge_messages::InboundLanes::<T, I>::get(proof.lane);
+				inbound_lane_data.last_delivered_nonce() >= proof.nonces_end
+			},
+			Some(pallet_bridge_messages::Call::<T, I>::receive_messages_delivery_proof {
+				ref proof,
+				ref relayers_state,
+				..
+			}) => {
+				let outbound_lane_data =
+					pallet_bridge_messages::OutboundLanes::<T, I>::get(proof.lane);
+				outbound_lane_data.latest_received_nonce >= relayers_state.last_delivered_nonce
+			},
+			_ => false,
+		}
 	}
 }
 
 #[cfg(test)]
 mod tests {
 	use bp_messages::UnrewardedRelayersState;
+	use frame_support::traits::Get;
 	use millau_runtime::{
 		bridge_runtime_common::{
 			messages::{
 				source::FromBridgedChainMessagesDeliveryProof,
 				target::FromBridgedChainMessagesProof,
 			},
+			messages_extension::delivery_priority_boost,
 			BridgeRuntimeFilterCall,
 		},
-		Call, Runtime, WithRialtoMessagesInstance,
+		Call, MaxUnconfirmedMessagesAtInboundLane, Origin, RialtoGrandpaInstance, Runtime,
+		WithRialtoMessagesInstance,
+	};
+	use sp_runtime::{
+		traits::{Dispatchable, Header as HeaderT},
+		DispatchError,
 	};
 
 	fn deliver_message_10() {
@@ -116,25 +243,44 @@ mod tests {
 		);
 	}
 
+	fn message_delivery_call(
+		nonces_start: bp_messages::MessageNonce,
+		nonces_end: bp_messages::MessageNonce,
+	) -> Call {
+		message_delivery_call_with_count(
+			nonces_start,
+			nonces_end,
+			(nonces_end - nonces_start + 1) as u32,
+		)
+	}
+
+	fn message_delivery_call_with_count(
+		nonces_start: bp_messages::MessageNonce,
+		nonces_end: bp_messages::MessageNonce,
+		messages_count: u32,
+	) -> Call {
+		Call::BridgeRialtoMessages(
+			pallet_bridge_messages::Call::<Runtime, ()>::receive_messages_proof {
+				relayer_id_at_bridged_chain: [0u8; 32].into(),
+				messages_count,
+				dispatch_weight: 0,
+				proof: FromBridgedChainMessagesProof {
+					bridged_header_hash: Default::default(),
+					storage_proof: vec![],
+					lane: [0, 0, 0, 0],
+					nonces_start,
+					nonces_end,
+				},
+			},
+		)
+	}
+
 	fn validate_message_delivery(
 		nonces_start: bp_messages::MessageNonce,
 		nonces_end: bp_messages::MessageNonce,
 	) -> bool {
 		pallet_bridge_messages::Pallet::<Runtime, WithRialtoMessagesInstance>::validate(
-			&Call::BridgeRialtoMessages(
-				pallet_bridge_messages::Call::<Runtime, ()>::receive_messages_proof {
-					relayer_id_at_bridged_chain: [0u8; 32].into(),
-					messages_count: (nonces_end - nonces_start + 1) as u32,
-					dispatch_weight: 0,
-					proof: FromBridgedChainMessagesProof {
-						bridged_header_hash: Default::default(),
-						storage_proof: vec![],
-						lane: [0, 0, 0, 0],
-						nonces_start,
-						nonces_end,
-					},
-				},
-			),
+			&message_delivery_call(nonces_start, nonces_end),
 		)
 		.is_ok()
 	}
@@ -169,6 +315,72 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn extension_boosts_priority_of_new_messages_delivery() {
+		sp_io::TestExternalities::new(Default::default()).execute_with(|| {
+			// when current best delivered is message#10 and we're trying to deliver messages
+			// #8..=#15, only 5 messages are new
+			deliver_message_10();
+			assert_eq!(
+				pallet_bridge_messages::Pallet::<Runtime, WithRialtoMessagesInstance>::validate(
+					&message_delivery_call(8, 15),
+				)
+				.map(|valid| valid.priority),
+				Ok(delivery_priority_boost(5)),
+			);
+			assert!(delivery_priority_boost(5) > delivery_priority_boost(1));
+		});
+	}
+
+	#[test]
+	fn extension_clamps_priority_boost_by_messages_count() {
+		sp_io::TestExternalities::new(Default::default()).execute_with(|| {
+			// when the transaction claims that it brings messages #11..=#15, but it only has
+			// 2 messages, only 2 messages are new
+			deliver_message_10();
+			assert_eq!(
+				pallet_bridge_messages::Pallet::<Runtime, WithRialtoMessagesInstance>::validate(
+					&message_delivery_call_with_count(11, 15, 2),
+				)
+				.map(|valid| valid.priority),
+				Ok(delivery_priority_boost(2)),
+			);
+		});
+	}
+
+	#[test]
+	fn extension_clamps_priority_boost_by_max_unconfirmed_messages() {
+		sp_io::TestExternalities::new(Default::default()).execute_with(|| {
+			// when there are `max - 2` unconfirmed messages at the lane, only 2 messages may be
+			// delivered
+			let max_unconfirmed_messages = MaxUnconfirmedMessagesAtInboundLane::get();
+			let mut delivered_messages = bp_messages::DeliveredMessages::new(11, true);
+			for _ in 1..max_unconfirmed_messages - 2 {
+				delivered_messages.note_dispatched_message(true);
+			}
+			pallet_bridge_messages::InboundLanes::<Runtime, WithRialtoMessagesInstance>::insert(
+				[0, 0, 0, 0],
+				bp_messages::InboundLaneData {
+					relayers: vec![bp_messages::UnrewardedRelayer {
+						relayer: [0u8; 32].into(),
+						messages: delivered_messages,
+					}]
+					.into(),
+					last_confirmed_nonce: 10,
+				},
+			);
+
+			let last_delivered_nonce = 10 + max_unconfirmed_messages - 2;
+			assert_eq!(
+				pallet_bridge_messages::Pallet::<Runtime, WithRialtoMessagesInstance>::validate(
+					&message_delivery_call(last_delivered_nonce + 1, last_delivered_nonce + 5),
+				)
+				.map(|valid| valid.priority),
+				Ok(delivery_priority_boost(2)),
+			);
+		});
+	}
+
 	fn confirm_message_10() {
 		pallet_bridge_messages::OutboundLanes::<Runtime, WithRialtoMessagesInstance>::insert(
 			[0, 0, 0, 0],
@@ -229,4 +441,134 @@ mod tests {
 			assert!(validate_message_confirmation(15));
 		});
 	}
+	#[test]
+	fn extension_detects_bridge_calls() {
+		assert!(
+			pallet_bridge_messages::Pallet::<Runtime, WithRialtoMessagesInstance>::is_bridge_call(
+				&Call::BridgeRialtoMessages(pallet_bridge_messages::Call::<
+					Runtime,
+					WithRialtoMessagesInstance,
+				>::receive_messages_delivery_proof {
+					proof: FromBridgedChainMessagesDeliveryProof {
+						bridged_header_hash: Default::default(),
+						storage_proof: Vec::new(),
+						lane: [0, 0, 0, 0],
+					},
+					relayers_state: Default::default(),
+				}),
+			)
+		);
+		assert!(
+			!pallet_bridge_messages::Pallet::<Runtime, WithRialtoMessagesInstance>::is_bridge_call(
+				&Call::System(frame_system::Call::<Runtime>::remark { remark: vec![] }),
+			)
+		);
+	}
+
+	type RialtoHash = pallet_bridge_grandpa::BridgedBlockHash<Runtime, RialtoGrandpaInstance>;
+	type MessagesPallet = pallet_bridge_messages::Pallet<Runtime, WithRialtoMessagesInstance>;
+	type MessagesError = pallet_bridge_messages::Error<Runtime, WithRialtoMessagesInstance>;
+
+	fn import_rialto_header() -> RialtoHash {
+		let header = pallet_bridge_grandpa::BridgedHeader::<Runtime, RialtoGrandpaInstance>::new(
+			1,
+			Default::default(),
+			Default::default(),
+			Default::default(),
+			Default::default(),
+		);
+		let hash = header.hash();
+		pallet_bridge_grandpa::ImportedHeaders::<Runtime, RialtoGrandpaInstance>::insert(
+			hash, header,
+		);
+		hash
+	}
+
+	fn dispatch_message_delivery(bridged_header_hash: RialtoHash) -> DispatchError {
+		Call::BridgeRialtoMessages(pallet_bridge_messages::Call::<
+			Runtime,
+			WithRialtoMessagesInstance,
+		>::receive_messages_proof {
+			relayer_id_at_bridged_chain: [0u8; 32].into(),
+			messages_count: 1,
+			dispatch_weight: 0,
+			proof: FromBridgedChainMessagesProof {
+				bridged_header_hash,
+				storage_proof: vec![],
+				lane: [0, 0, 0, 0],
+				nonces_start: 11,
+				nonces_end: 11,
+			},
+		})
+		.dispatch(Origin::signed([1u8; 32].into()))
+		.unwrap_err()
+		.error
+	}
+
+	fn dispatch_message_confirmation(bridged_header_hash: RialtoHash) -> DispatchError {
+		Call::BridgeRialtoMessages(pallet_bridge_messages::Call::<
+			Runtime,
+			WithRialtoMessagesInstance,
+		>::receive_messages_delivery_proof {
+			proof: FromBridgedChainMessagesDeliveryProof {
+				bridged_header_hash,
+				storage_proof: vec![],
+				lane: [0, 0, 0, 0],
+			},
+			relayers_state: Default::default(),
+		})
+		.dispatch(Origin::signed([1u8; 32].into()))
+		.unwrap_err()
+		.error
+	}
+
+	fn is_invalid_proof_error(error: &DispatchError) -> bool {
+		<MessagesPallet as BridgeRuntimeFilterCall<Call>>::is_invalid_proof_error(error)
+	}
+
+	#[test]
+	fn proofs_of_unknown_headers_are_not_invalid_proofs() {
+		sp_io::TestExternalities::new(Default::default()).execute_with(|| {
+			let error = dispatch_message_delivery([42u8; 32].into());
+			assert_eq!(error, MessagesError::InvalidMessagesProof.into());
+			assert!(!is_invalid_proof_error(&error));
+
+			let error = dispatch_message_confirmation([42u8; 32].into());
+			assert_eq!(error, MessagesError::InvalidMessagesDeliveryProof.into());
+			assert!(!is_invalid_proof_error(&error));
+		});
+	}
+
+	#[test]
+	fn proofs_verified_by_halted_bridge_are_not_invalid_proofs() {
+		sp_io::TestExternalities::new(Default::default()).execute_with(|| {
+			let hash = import_rialto_header();
+			pallet_bridge_grandpa::PalletOperatingMode::<Runtime, RialtoGrandpaInstance>::put(
+				bp_runtime::BasicOperatingMode::Halted,
+			);
+
+			let error = dispatch_message_delivery(hash);
+			assert_eq!(error, MessagesError::InvalidMessagesProof.into());
+			assert!(!is_invalid_proof_error(&error));
+
+			let error = dispatch_message_confirmation(hash);
+			assert_eq!(error, MessagesError::InvalidMessagesDeliveryProof.into());
+			assert!(!is_invalid_proof_error(&error));
+		});
+	}
+
+	#[test]
+	fn proofs_with_storage_root_mismatch_are_invalid_proofs() {
+		sp_io::TestExternalities::new(Default::default()).execute_with(|| {
+			let hash = import_rialto_header();
+
+			let error = dispatch_message_delivery(hash);
+			assert_eq!(error, MessagesError::StorageRootMismatch.into());
+			assert!(is_invalid_proof_error(&error));
+
+			let error = dispatch_message_confirmation(hash);
+			assert_eq!(error, MessagesError::StorageRootMismatch.into());
+			assert!(is_invalid_proof_error(&error));
+		});
+	}
 }
@@ -0,0 +1,211 @@
+// Copyright 2019-2022 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Signed extension that refunds relayer if it has delivered some new data to the bridge.
+//!
+//! The bridge transaction is either a single bridge call (finality, parachain heads or messages
+//! call), or the `utility::batch_all` of bridge calls. The whole transaction is considered useful
+//! only if every call in the batch has imported new data. Batches with duplicate or overlapping
+//! bridge calls are rejected. The fee, paid for the useful transaction, is registered as the
+//! relayer reward in the `pallet_bridge_relayers`, so the relayer may claim it later. Transactions
+//! with messages calls are refunded at the messages lane and all other transactions are refunded
+//! at the separate finality rewards pool.
+
+use crate::BridgeRuntimeFilterCall;
+
+use bp_messages::LaneId;
+use codec::{Decode, Encode};
+use frame_support::{
+	dispatch::CallableCallFor,
+	traits::{Get, IsSubType},
+	weights::{DispatchInfo, PostDispatchInfo},
+	CloneNoBound, DefaultNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+};
+use pallet_transaction_payment::Pallet as TransactionPaymentPallet;
+use pallet_utility::{Call as UtilityCall, Pallet as UtilityPallet};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{DispatchInfoOf, Dispatchable, PostDispatchInfoOf, SignedExtension, Zero},
+	transaction_validity::{
+		InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+	},
+	DispatchResult,
+};
+use sp_std::{marker::PhantomData, vec, vec::Vec};
+
+/// Runtime call type.
+pub type CallOf<Runtime> = <Runtime as frame_system::Config>::Call;
+
+/// Signed extension that refunds relayer for new bridge data, delivered by the transaction.
+///
+/// The `BridgeCalls` is the set of filters (usually, a tuple of bridge pallets), that are used
+/// to detect bridge calls and to check whether call data has been imported. Rewards are
+/// registered at the lane of the (first) messages call of the transaction. If the transaction
+/// has no messages calls (e.g. it only brings finality proofs), rewards are registered at the
+/// `FinalityRewardsPool`, which must not be used by any messages lane (see
+/// `bp_relayers::FINALITY_REWARDS_POOL`).
+#[derive(
+	DefaultNoBound,
+	CloneNoBound,
+	Decode,
+	Encode,
+	EqNoBound,
+	PartialEqNoBound,
+	RuntimeDebugNoBound,
+	TypeInfo,
+)]
+#[scale_info(skip_type_params(Runtime, BridgeCalls, FinalityRewardsPool))]
+pub struct RefundRelayerForBridgeTransactions<Runtime, BridgeCalls, FinalityRewardsPool>(
+	PhantomData<(Runtime, BridgeCalls, FinalityRewardsPool)>,
+);
+
+impl<Runtime, BridgeCalls, FinalityRewardsPool>
+	RefundRelayerForBridgeTransactions<Runtime, BridgeCalls, FinalityRewardsPool>
+where
+	Runtime: pallet_utility::Config<Call = CallOf<Runtime>>,
+	BridgeCalls: BridgeRuntimeFilterCall<CallOf<Runtime>>,
+	CallOf<Runtime>: IsSubType<CallableCallFor<UtilityPallet<Runtime>, Runtime>>,
+{
+	/// Returns all bridge calls of the transaction.
+	///
+	/// Returns `None` if the transaction is not a bridge transaction, or if it is a batch that
+	/// has at least one non-bridge call.
+	pub fn bridge_calls(call: &CallOf<Runtime>) -> Option<Vec<&CallOf<Runtime>>> {
+		match call.is_sub_type() {
+			Some(UtilityCall::<Runtime>::batch_all { ref calls }) => {
+				if calls.is_empty() || !calls.iter().all(BridgeCalls::is_bridge_call) {
+					return None
+				}
+
+				Some(calls.iter().collect())
+			},
+			_ if BridgeCalls::is_bridge_call(call) => Some(vec![call]),
+			_ => None,
+		}
+	}
+
+	/// Returns true if some bridge calls of the transaction are duplicate or overlapping.
+	pub fn has_overlapping_calls(bridge_calls: &[&CallOf<Runtime>]) -> bool {
+		bridge_calls.iter().enumerate().any(|(index, call)| {
+			bridge_calls[index + 1..].iter().any(|other_call| {
+				call == other_call || BridgeCalls::are_overlapping_calls(call, other_call)
+			})
+		})
+	}
+}
+
+impl<Runtime, BridgeCalls, FinalityRewardsPool> SignedExtension
+	for RefundRelayerForBridgeTransactions<Runtime, BridgeCalls, FinalityRewardsPool>
+where
+	Runtime: 'static
+		+ Send
+		+ Sync
+		+ pallet_utility::Config<Call = CallOf<Runtime>>
+		+ pallet_transaction_payment::Config
+		+ pallet_bridge_relayers::Config,
+	BridgeCalls: 'static + Send + Sync + BridgeRuntimeFilterCall<CallOf<Runtime>>,
+	FinalityRewardsPool: 'static + Send + Sync + Get<LaneId>,
+	CallOf<Runtime>: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>
+		+ IsSubType<CallableCallFor<UtilityPallet<Runtime>, Runtime>>,
+	pallet_transaction_payment::BalanceOf<Runtime>: Into<Runtime::Reward>,
+{
+	const IDENTIFIER: &'static str = "RefundRelayerForBridgeTransactions";
+	type AccountId = Runtime::AccountId;
+	type Call = CallOf<Runtime>;
+	type AdditionalSigned = ();
+	/// Relayer that has submitted the bridge transaction, lane (or pool) where it is rewarded and
+	/// bridge calls of the transaction.
+	type Pre = Option<(Runtime::AccountId, LaneId, Vec<CallOf<Runtime>>)>;
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		_who: &Self::AccountId,
+		call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> TransactionValidity {
+		match Self::bridge_calls(call) {
+			Some(bridge_calls) if Self::has_overlapping_calls(&bridge_calls) =>
+				InvalidTransaction::Call.into(),
+			_ => Ok(ValidTransaction::default()),
+		}
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		// we only refund transactions that are bringing new data when they're dispatched
+		let bridge_calls = match Self::bridge_calls(call) {
+			Some(bridge_calls) => bridge_calls,
+			None => return Ok(None),
+		};
+		// relayer may not be refunded multiple times for the same data
+		if Self::has_overlapping_calls(&bridge_calls) {
+			return Err(InvalidTransaction::Call.into())
+		}
+		if bridge_calls.iter().any(|call| BridgeCalls::validate(call).is_err()) {
+			return Ok(None)
+		}
+
+		// finality-only transactions are refunded from the separate pool, so they never affect
+		// rewards of messages relayers
+		let lane = bridge_calls
+			.iter()
+			.find_map(|call| BridgeCalls::messages_lane(call))
+			.unwrap_or_else(FinalityRewardsPool::get);
+		Ok(Some((who.clone(), lane, bridge_calls.into_iter().cloned().collect())))
+	}
+
+	fn post_dispatch(
+		pre: Option<Self::Pre>,
+		info: &DispatchInfoOf<Self::Call>,
+		post_info: &PostDispatchInfoOf<Self::Call>,
+		len: usize,
+		result: &DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		let (relayer, lane, bridge_calls) = match pre.flatten() {
+			Some(pre) => pre,
+			None => return Ok(()),
+		};
+
+		// the transaction is useful only if all its calls have imported new data
+		if result.is_err() || !bridge_calls.iter().all(BridgeCalls::is_call_data_imported) {
+			return Ok(())
+		}
+
+		let fee = TransactionPaymentPallet::<Runtime>::compute_actual_fee(
+			len as u32,
+			info,
+			post_info,
+			Zero::zero(),
+		);
+		pallet_bridge_relayers::Pallet::<Runtime>::register_relayer_reward(
+			lane,
+			&relayer,
+			fee.into(),
+		);
+
+		Ok(())
+	}
+}
@@ -1,29 +1,23 @@
 #!/usr/bin/env bash
 
-# A script for relaying Millau headers to the Rialto chain.
+# A script for relaying Pass3dt headers to the Pass3d chain.
 #
-# Will not work unless both the Rialto and Millau are running (see `run-rialto-node.sh`
-# and `run-millau-node.sh).
+# Will not work unless both the Pass3d and Pass3dt are running (see `run-pass3d-node.sh`
+# and `run-pass3dt-node.sh).
 
-MILLAU_PORT="${MILLAU_PORT:-9945}"
-RIALTO_PORT="${RIALTO_PORT:-9944}"
+PASS3DT_PORT="${PASS3DT_PORT:-9945}"
+PASS3D_PORT="${PASS3D_PORT:-9944}"
+RELAY_CONFIG="${RELAY_CONFIG:-$(dirname "$0")/relay-pass3dt-pass3d.toml}"
 
 RUST_LOG=bridge=debug \
-./target/release/substrate-relay init-bridge millau-to-rialto \
-	--source-host localhost \
-	--source-port $MILLAU_PORT \
-	--target-host localhost \
-	--target-port $RIALTO_PORT \
-	--target-signer //Alice \
-	--source-version-mode Bundle \
-	--target-version-mode Bundle
+./target/release/substrate-relay init-bridge pass3dt-to-pass3d \
+	--config $RELAY_CONFIG \
+	--source-port $PASS3DT_PORT \
+	--target-port $PASS3D_PORT
 
 sleep 5
 RUST_LOG=bridge=debug \
-./target/release/substrate-relay relay-headers millau-to-rialto \
-	--source-host localhost \
-	--source-port $MILLAU_PORT \
-	--target-host localhost \
-	--target-port $RIALTO_PORT \
-	--target-signer //Alice \
-	--prometheus-host=0.0.0.0
+./target/release/substrate-relay relay-headers pass3dt-to-pass3d \
+	--config $RELAY_CONFIG \
+	--source-port $PASS3DT_PORT \
+	--target-port $PASS3D_PORT
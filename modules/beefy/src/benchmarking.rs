@@ -0,0 +1,175 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarks for the BEEFY Pallet.
+//!
+//! The main dispatchable for the BEEFY pallet is `submit_commitment`, so these benchmarks are
+//! based around that. There are two main factors which affect commitment verification:
+//!
+//! 1. The number of signatures in the commitment (which is equal to the validator set size);
+//! 2. The number of items in the MMR proof of the commitment block leaf.
+//!
+//! The benchmark always imports commitment, signed by the next validator set, because the
+//! validator set hand-off is the most expensive path of `submit_commitment`. Validator keys
+//! are generated in the keystore, so the benchmark requires the keystore extension, which is
+//! registered by the benchmarking CLI.
+
+use crate::*;
+
+use beefy_primitives::{mmr::MmrLeafVersion, Commitment, Payload, KEY_TYPE};
+use bp_runtime::BasicOperatingMode;
+use frame_benchmarking::{benchmarks_instance_pallet, whitelisted_caller};
+use frame_support::traits::Get;
+use frame_system::RawOrigin;
+use sp_core::ecdsa;
+use sp_runtime::traits::{Hash, Zero};
+use sp_std::vec::Vec;
+
+// The maximum number of validators in the bridged chain validator set.
+const MAX_VALIDATOR_SET_SIZE: u32 = 1024;
+
+// `1..MAX_VALIDATOR_SET_SIZE` is too large and signatures generation makes benchmark setup
+// really slow. Since we're building linear function here, let's just select some limited
+// subrange for benchmarking.
+const VALIDATOR_SET_SIZE_RANGE_BEGIN: u32 = MAX_VALIDATOR_SET_SIZE / 20;
+const VALIDATOR_SET_SIZE_RANGE_END: u32 =
+	VALIDATOR_SET_SIZE_RANGE_BEGIN + VALIDATOR_SET_SIZE_RANGE_BEGIN;
+
+// The maximum number of items in the MMR proof. The proof of the single leaf has at most
+// `log2(leaf_count)` items, so it is enough to cover MMR of `2^32` leaves.
+const MAX_MMR_PROOF_SIZE: u32 = 32;
+
+/// Returns number of the header to be imported.
+///
+/// Since we bootstrap the pallet with `HeadersToKeep` already imported headers,
+/// this function computes the next expected header number to import.
+fn header_number<T: Config<I>, I: 'static, N: From<u32>>() -> N {
+	(T::HeadersToKeep::get() + 1).into()
+}
+
+/// Generate `count` validator keys in the keystore.
+fn generate_validators(count: u32) -> Vec<ecdsa::Public> {
+	(0..count).map(|_| sp_io::crypto::ecdsa_generate(KEY_TYPE, None)).collect()
+}
+
+/// Return BEEFY identifiers of given validators.
+fn validator_ids(validators: &[ecdsa::Public]) -> Vec<BeefyId> {
+	validators.iter().cloned().map(Into::into).collect()
+}
+
+/// Prepare everything that is required to call `submit_commitment` with `validators`
+/// signatures and `proof_items` MMR proof items.
+fn prepare_benchmark_data<T: Config<I>, I: 'static>(
+	validators: u32,
+	proof_items: u32,
+) -> (
+	BridgedBeefySignedCommitment<T, I>,
+	Vec<BeefyId>,
+	BridgedBeefyMmrLeaf<T, I>,
+	BridgedMmrProof,
+	BridgedHeader<T, I>,
+)
+where
+	T::BridgedMmrLeafExtra: Default,
+{
+	let current_validators = generate_validators(validators);
+	let next_validators = generate_validators(validators);
+	let set_info = |id, validators: &[ecdsa::Public]| BeefyAuthoritySetInfo {
+		id,
+		len: validators.len() as u32,
+		root: validator_set_root(&validator_ids(validators)),
+	};
+
+	bootstrap_bridge::<T, I>(InitializationData {
+		header: Box::new(bp_test_utils::test_header(Zero::zero())),
+		current_authority_set: set_info(0, &current_validators),
+		next_authority_set: set_info(1, &next_validators),
+		operating_mode: BasicOperatingMode::Normal,
+	});
+
+	let header: BridgedHeader<T, I> = bp_test_utils::test_header(header_number::<T, I, _>());
+	let mmr_leaf = BridgedBeefyMmrLeaf::<T, I> {
+		version: MmrLeafVersion::new(0, 0),
+		parent_number_and_hash: (*header.number(), header.hash()),
+		beefy_next_authority_set: set_info(2, &current_validators),
+		leaf_extra: Default::default(),
+	};
+
+	// the leaf is the first leaf of the perfect MMR with `2^proof_items` leaves, so every
+	// proof item is the right sibling of the node on the path from the leaf to the MMR root
+	let proof_items = (0..proof_items).map(BridgedMmrHash::from_low_u64_be).collect::<Vec<_>>();
+	let mmr_root = proof_items
+		.iter()
+		.fold(BridgedMmrHashing::hash(&mmr_leaf.encode()), |node, sibling| {
+			BridgedMmrHashing::hash(&[node.as_bytes(), sibling.as_bytes()].concat())
+		});
+	let mmr_proof = BridgedMmrProof {
+		leaf_indices: vec![0],
+		leaf_count: 1u64 << proof_items.len(),
+		items: proof_items,
+	};
+
+	let commitment = Commitment {
+		payload: Payload::new(MMR_ROOT_ID, mmr_root.encode()),
+		block_number: *header.number() + One::one(),
+		validator_set_id: 1,
+	};
+	let commitment_hash = sp_io::hashing::keccak_256(&commitment.encode());
+	let signatures = next_validators
+		.iter()
+		.map(|validator| {
+			sp_io::crypto::ecdsa_sign_prehashed(KEY_TYPE, validator, &commitment_hash)
+				.map(Into::into)
+		})
+		.collect();
+
+	(
+		SignedCommitment { commitment, signatures },
+		validator_ids(&next_validators),
+		mmr_leaf,
+		mmr_proof,
+		header,
+	)
+}
+
+benchmarks_instance_pallet! {
+	where_clause { where T::BridgedMmrLeafExtra: Default }
+
+	// This is the "gold standard" benchmark for this extrinsic, and it's what should be used to
+	// annotate the weight in the pallet.
+	submit_commitment {
+		let s in VALIDATOR_SET_SIZE_RANGE_BEGIN..VALIDATOR_SET_SIZE_RANGE_END;
+		let p in 0..MAX_MMR_PROOF_SIZE;
+		let caller: T::AccountId = whitelisted_caller();
+		let (commitment, validator_set, mmr_leaf, mmr_proof, header) =
+			prepare_benchmark_data::<T, I>(s, p);
+	}: submit_commitment(
+		RawOrigin::Signed(caller),
+		commitment,
+		validator_set,
+		Box::new(mmr_leaf),
+		mmr_proof,
+		Box::new(header)
+	)
+	verify {
+		let header: BridgedHeader<T, I> = bp_test_utils::test_header(header_number::<T, I, _>());
+		let expected_hash = header.hash();
+
+		assert_eq!(<BestFinalized<T, I>>::get().unwrap().1, expected_hash);
+		assert!(<ImportedHeaders<T, I>>::contains_key(expected_hash));
+		assert_eq!(<CurrentAuthoritySet<T, I>>::get().id, 1);
+	}
+}
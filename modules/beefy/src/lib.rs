@@ -0,0 +1,1036 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Substrate BEEFY Pallet
+//!
+//! This pallet is an on-chain BEEFY light client for Substrate based chains, that are running
+//! `pallet-beefy`, `pallet-mmr` and `pallet-beefy-mmr`.
+//!
+//! The pallet accepts signed BEEFY commitments. Every commitment is accompanied by the list of
+//! validators of the set that has signed it, the MMR leaf of the commitment block and the MMR
+//! proof of that leaf. Once signatures and the MMR proof are verified, the parent header of the
+//! commitment block (whose hash is stored in the leaf) is stored in the pallet, thereby creating
+//! a sparse header chain. This sparse header chain can be used as a source of truth for other
+//! higher-level applications, e.g. to verify messages proofs.
+//!
+//! The pallet is responsible for tracking BEEFY validator set hand-offs. It knows the current
+//! and the next validator sets (in form of `BeefyNextAuthoritySet`, which only contains set id,
+//! length and the merkle root of validators addresses). Once commitment, signed by the next
+//! validator set, is imported, the next set becomes the current one and the next set is read
+//! from the imported MMR leaf.
+//!
+//! The pallet only supports bridged chains that are using `Keccak256` as MMR hasher, which is
+//! the same hasher that is used to compute BEEFY commitment hashes.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// Runtime-generated enums
+#![allow(clippy::large_enum_variant)]
+
+use beefy_primitives::{
+	crypto::{AuthorityId as BeefyId, Signature as BeefySignature},
+	known_payload_ids::MMR_ROOT_ID,
+	mmr::{BeefyNextAuthoritySet, MmrLeaf},
+	SignedCommitment, ValidatorSetId,
+};
+use bp_runtime::{
+	BasicOperatingMode, BlockNumberOf, Chain, HashOf, HasherOf, HeaderOf, OwnedBridgeModule,
+};
+use codec::{Decode, Encode};
+use frame_support::{ensure, RuntimeDebug};
+use frame_system::ensure_signed;
+use scale_info::TypeInfo;
+use sp_core::H256;
+use sp_runtime::traits::{Convert, Header as HeaderT, One};
+use sp_std::{boxed::Box, convert::TryInto, vec, vec::Vec};
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+
+/// Module, containing weights for this pallet.
+pub mod weights;
+
+// Re-export in crate namespace for `construct_runtime!`
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+/// The target that will be used when publishing logs related to this pallet.
+pub const LOG_TARGET: &str = "runtime::bridge-beefy";
+
+/// Block number of the bridged chain.
+pub type BridgedBlockNumber<T, I> = BlockNumberOf<<T as Config<I>>::BridgedChain>;
+/// Block hash of the bridged chain.
+pub type BridgedBlockHash<T, I> = HashOf<<T as Config<I>>::BridgedChain>;
+/// Hasher of the bridged chain.
+pub type BridgedBlockHasher<T, I> = HasherOf<<T as Config<I>>::BridgedChain>;
+/// Header of the bridged chain.
+pub type BridgedHeader<T, I> = HeaderOf<<T as Config<I>>::BridgedChain>;
+
+/// Hasher, used by the bridged chain MMR.
+pub type BridgedMmrHashing = sp_runtime::traits::Keccak256;
+/// Hash of the bridged chain MMR nodes.
+pub type BridgedMmrHash = H256;
+/// MMR proof of the bridged chain leaf.
+pub type BridgedMmrProof = sp_mmr_primitives::BatchProof<BridgedMmrHash>;
+/// Short information about BEEFY validator set of the bridged chain.
+pub type BeefyAuthoritySetInfo = BeefyNextAuthoritySet<BridgedMmrHash>;
+/// BEEFY commitment, signed by the bridged chain validators.
+pub type BridgedBeefySignedCommitment<T, I> =
+	SignedCommitment<BridgedBlockNumber<T, I>, BeefySignature>;
+/// MMR leaf of the bridged chain, produced by the `pallet-beefy-mmr`.
+pub type BridgedBeefyMmrLeaf<T, I> = MmrLeaf<
+	BridgedBlockNumber<T, I>,
+	BridgedBlockHash<T, I>,
+	BridgedMmrHash,
+	<T as Config<I>>::BridgedMmrLeafExtra,
+>;
+
+/// Data required for initializing the BEEFY bridge pallet.
+#[derive(Encode, Decode, RuntimeDebug, PartialEq, Eq, Clone, TypeInfo)]
+pub struct InitializationData<H: HeaderT> {
+	/// The header from which we should start syncing.
+	pub header: Box<H>,
+	/// Validator set, that is signing commitments at the `header`.
+	pub current_authority_set: BeefyAuthoritySetInfo,
+	/// Validator set, that will replace current set at the next session.
+	pub next_authority_set: BeefyAuthoritySetInfo,
+	/// Pallet operating mode.
+	pub operating_mode: BasicOperatingMode,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self, I>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The chain we are bridging to here.
+		type BridgedChain: Chain;
+
+		/// Type of the `leaf_extra` field of bridged chain MMR leaves.
+		///
+		/// It must match the `LeafExtra` type of the `pallet-beefy-mmr` configuration at the
+		/// bridged chain.
+		type BridgedMmrLeafExtra: Parameter;
+
+		/// The upper bound on the number of requests allowed by the pallet.
+		///
+		/// A request refers to an action which writes a header to storage.
+		///
+		/// Once this bound is reached the pallet will not allow any dispatchables to be called
+		/// until the request count has decreased.
+		#[pallet::constant]
+		type MaxRequests: Get<u32>;
+
+		/// Maximal number of finalized headers to keep in the storage.
+		///
+		/// The setting is there to prevent growing the on-chain state indefinitely. Note
+		/// the setting does not relate to block numbers - we will simply keep as much items
+		/// in the storage, so it doesn't guarantee any fixed timeframe for finality headers.
+		///
+		/// Incautious change of this constant may lead to orphan entries in the runtime storage.
+		#[pallet::constant]
+		type HeadersToKeep: Get<u32>;
+
+		/// Weights of this pallet calls.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
+
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		fn on_initialize(_n: T::BlockNumber) -> frame_support::weights::Weight {
+			<RequestCount<T, I>>::mutate(|count| *count = count.saturating_sub(1));
+
+			(0_u64)
+				.saturating_add(T::DbWeight::get().reads(1))
+				.saturating_add(T::DbWeight::get().writes(1))
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> OwnedBridgeModule<T> for Pallet<T, I> {
+		const LOG_TARGET: &'static str = LOG_TARGET;
+		type OwnerStorage = PalletOwner<T, I>;
+		type OperatingMode = BasicOperatingMode;
+		type OperatingModeStorage = PalletOperatingMode<T, I>;
+	}
+
+	#[pallet::call]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Verify signed BEEFY commitment and import parent header of the commitment block.
+		///
+		/// The commitment must be signed either by the current, or by the next validator set
+		/// known to the pallet. The `validator_set` must contain all validators of that set, in
+		/// the same order that has been used to compute the set merkle root. The `mmr_leaf`
+		/// must be the leaf of the commitment block and the `mmr_proof` must prove that the leaf
+		/// is included in the MMR, whose root is stored in the commitment payload. The `header`
+		/// must be the header, referenced by the `mmr_leaf`.
+		///
+		/// If the commitment is signed by the next validator set, then validator sets are
+		/// rotated and the transaction is free.
+		#[pallet::weight(T::WeightInfo::submit_commitment(
+			commitment.signatures.len().try_into().unwrap_or(u32::MAX),
+			mmr_proof.items.len().try_into().unwrap_or(u32::MAX),
+		))]
+		pub fn submit_commitment(
+			origin: OriginFor<T>,
+			commitment: BridgedBeefySignedCommitment<T, I>,
+			validator_set: Vec<BeefyId>,
+			mmr_leaf: Box<BridgedBeefyMmrLeaf<T, I>>,
+			mmr_proof: BridgedMmrProof,
+			header: Box<BridgedHeader<T, I>>,
+		) -> DispatchResultWithPostInfo {
+			Self::ensure_not_halted().map_err(Error::<T, I>::BridgeModule)?;
+			let _ = ensure_signed(origin)?;
+
+			ensure!(Self::request_count() < T::MaxRequests::get(), <Error<T, I>>::TooManyRequests);
+
+			let (best_finalized_number, _) =
+				<BestFinalized<T, I>>::get().ok_or(<Error<T, I>>::NotInitialized)?;
+
+			// the header must be the one that is referenced by the leaf, and the leaf must be
+			// the leaf of the commitment block
+			let (header_number, header_hash) = (*header.number(), header.hash());
+			ensure!(
+				mmr_leaf.parent_number_and_hash == (header_number, header_hash),
+				<Error<T, I>>::InvalidHeader,
+			);
+			ensure!(
+				header_number + One::one() == commitment.commitment.block_number,
+				<Error<T, I>>::InvalidMmrLeaf,
+			);
+			ensure!(header_number > best_finalized_number, <Error<T, I>>::OldCommitment);
+
+			// select validator set that has signed the commitment
+			let current_authority_set = <CurrentAuthoritySet<T, I>>::get();
+			let next_authority_set = <NextAuthoritySet<T, I>>::get();
+			let commitment_set_id = commitment.commitment.validator_set_id;
+			let is_handoff = if commitment_set_id == current_authority_set.id {
+				false
+			} else if commitment_set_id == next_authority_set.id {
+				true
+			} else {
+				log::trace!(
+					target: LOG_TARGET,
+					"Commitment is signed by unknown validator set {}. Known sets: {} and {}",
+					commitment_set_id,
+					current_authority_set.id,
+					next_authority_set.id,
+				);
+				return Err(<Error<T, I>>::InvalidValidatorSetId.into())
+			};
+			let signer_set_info =
+				if is_handoff { &next_authority_set } else { &current_authority_set };
+
+			verify_commitment::<T, I>(&commitment, signer_set_info, &validator_set)?;
+			verify_mmr_leaf::<T, I>(&commitment, &mmr_leaf, mmr_proof)?;
+
+			if is_handoff {
+				ensure!(
+					mmr_leaf.beefy_next_authority_set.id == next_authority_set.id + 1,
+					<Error<T, I>>::InvalidMmrLeaf,
+				);
+
+				log::info!(
+					target: LOG_TARGET,
+					"Enacted new BEEFY validator set {} at {:?}",
+					next_authority_set.id,
+					header_number,
+				);
+
+				<CurrentAuthoritySet<T, I>>::put(next_authority_set.clone());
+				<NextAuthoritySet<T, I>>::put(mmr_leaf.beefy_next_authority_set.clone());
+				Self::deposit_event(Event::AuthoritySetEnacted { set_id: next_authority_set.id });
+			}
+
+			<RequestCount<T, I>>::mutate(|count| *count += 1);
+			insert_header::<T, I>(*header, header_hash);
+			log::info!(
+				target: LOG_TARGET,
+				"Successfully imported BEEFY-finalized header {:?}!",
+				header_hash,
+			);
+			Self::deposit_event(Event::HeaderImported { header_number, header_hash });
+
+			// mandatory commitments are free, since they're required to keep the bridge running
+			let pays_fee = if is_handoff { Pays::No } else { Pays::Yes };
+
+			Ok(pays_fee.into())
+		}
+
+		/// Bootstrap the bridge pallet with an initial header and validator sets from which to
+		/// sync.
+		///
+		/// This function is only allowed to be called from a trusted origin and writes to storage
+		/// with practically no checks in terms of the validity of the data. It is important that
+		/// you ensure that valid data is being passed in.
+		#[pallet::weight((T::DbWeight::get().reads_writes(2, 7), DispatchClass::Operational))]
+		pub fn initialize(
+			origin: OriginFor<T>,
+			init_data: super::InitializationData<BridgedHeader<T, I>>,
+		) -> DispatchResultWithPostInfo {
+			Self::ensure_owner_or_root(origin)?;
+
+			let init_allowed = !<BestFinalized<T, I>>::exists();
+			ensure!(init_allowed, <Error<T, I>>::AlreadyInitialized);
+			initialize_bridge::<T, I>(init_data.clone());
+
+			log::info!(
+				target: LOG_TARGET,
+				"Pallet has been initialized with the following parameters: {:?}",
+				init_data
+			);
+
+			Ok(().into())
+		}
+
+		/// Change `PalletOwner`.
+		///
+		/// May only be called either by root, or by `PalletOwner`.
+		#[pallet::weight((T::DbWeight::get().reads_writes(1, 1), DispatchClass::Operational))]
+		pub fn set_owner(origin: OriginFor<T>, new_owner: Option<T::AccountId>) -> DispatchResult {
+			<Self as OwnedBridgeModule<_>>::set_owner(origin, new_owner)
+		}
+
+		/// Halt or resume all pallet operations.
+		///
+		/// May only be called either by root, or by `PalletOwner`.
+		#[pallet::weight((T::DbWeight::get().reads_writes(1, 1), DispatchClass::Operational))]
+		pub fn set_operating_mode(
+			origin: OriginFor<T>,
+			operating_mode: BasicOperatingMode,
+		) -> DispatchResult {
+			<Self as OwnedBridgeModule<_>>::set_operating_mode(origin, operating_mode)
+		}
+	}
+
+	/// The current number of requests which have written to storage.
+	///
+	/// If the `RequestCount` hits `MaxRequests`, no more calls will be allowed to the pallet until
+	/// the request capacity is increased.
+	///
+	/// The `RequestCount` is decreased by one at the beginning of every block. This is to ensure
+	/// that the pallet can always make progress.
+	#[pallet::storage]
+	#[pallet::getter(fn request_count)]
+	pub(super) type RequestCount<T: Config<I>, I: 'static = ()> = StorageValue<_, u32, ValueQuery>;
+
+	/// Number and hash of the best finalized header.
+	#[pallet::storage]
+	pub type BestFinalized<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, (BridgedBlockNumber<T, I>, BridgedBlockHash<T, I>), OptionQuery>;
+
+	/// A ring buffer of imported hashes. Ordered by the insertion time.
+	#[pallet::storage]
+	pub(super) type ImportedHashes<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Identity, u32, BridgedBlockHash<T, I>>;
+
+	/// Current ring buffer position.
+	#[pallet::storage]
+	pub(super) type ImportedHashesPointer<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, u32, ValueQuery>;
+
+	/// Headers which have been imported into the pallet.
+	#[pallet::storage]
+	pub type ImportedHeaders<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Identity, BridgedBlockHash<T, I>, BridgedHeader<T, I>>;
+
+	/// The current BEEFY validator set of the bridged chain.
+	#[pallet::storage]
+	pub type CurrentAuthoritySet<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BeefyAuthoritySetInfo, ValueQuery>;
+
+	/// The next BEEFY validator set of the bridged chain.
+	#[pallet::storage]
+	pub type NextAuthoritySet<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BeefyAuthoritySetInfo, ValueQuery>;
+
+	/// Optional pallet owner.
+	///
+	/// Pallet owner has a right to halt all pallet operations and then resume it. If it is
+	/// `None`, then there are no direct ways to halt/resume pallet operations, but other
+	/// runtime methods may still be used to do that (i.e. democracy::referendum to update halt
+	/// flag directly or call the `halt_operations`).
+	#[pallet::storage]
+	pub type PalletOwner<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, T::AccountId, OptionQuery>;
+
+	/// The current operating mode of the pallet.
+	///
+	/// Depending on the mode either all, or no transactions will be allowed.
+	#[pallet::storage]
+	pub type PalletOperatingMode<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BasicOperatingMode, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config<I>, I: 'static = ()> {
+		/// BEEFY-finalized header has been imported.
+		HeaderImported {
+			/// Number of the imported header.
+			header_number: BridgedBlockNumber<T, I>,
+			/// Hash of the imported header.
+			header_hash: BridgedBlockHash<T, I>,
+		},
+		/// Next validator set has become the current validator set.
+		AuthoritySetEnacted {
+			/// Identifier of the new current validator set.
+			set_id: ValidatorSetId,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T, I = ()> {
+		/// The pallet has not been initialized yet.
+		NotInitialized,
+		/// The pallet has already been initialized.
+		AlreadyInitialized,
+		/// There are too many requests for the current window to handle.
+		TooManyRequests,
+		/// The commitment is not newer than the best finalized header known to the pallet.
+		OldCommitment,
+		/// The commitment is signed by unknown validator set.
+		InvalidValidatorSetId,
+		/// The submitted validator set doesn't match the validator set known to the pallet.
+		InvalidValidatorSet,
+		/// The number of signatures in the commitment doesn't match the number of validators.
+		InvalidSignaturesCount,
+		/// The commitment doesn't have enough valid signatures.
+		NotEnoughCorrectSignatures,
+		/// The commitment payload doesn't have the MMR root.
+		MmrRootMissingFromCommitment,
+		/// The MMR leaf doesn't belong to the commitment block or is invalid.
+		InvalidMmrLeaf,
+		/// The MMR proof is invalid.
+		InvalidMmrProof,
+		/// The header doesn't match the header, referenced by the MMR leaf.
+		InvalidHeader,
+		/// The header is unknown to the pallet.
+		UnknownHeader,
+		/// The storage proof doesn't contains storage root. So it is invalid for given header.
+		StorageRootMismatch,
+		/// Error generated by the `OwnedBridgeModule` trait.
+		BridgeModule(bp_runtime::OwnedBridgeModuleError),
+	}
+
+	/// Verify that the commitment is signed by the supermajority of given validator set.
+	pub(crate) fn verify_commitment<T: Config<I>, I: 'static>(
+		commitment: &BridgedBeefySignedCommitment<T, I>,
+		authority_set_info: &BeefyAuthoritySetInfo,
+		validator_set: &[BeefyId],
+	) -> Result<(), Error<T, I>> {
+		ensure!(
+			validator_set.len() == authority_set_info.len as usize &&
+				validator_set_root(validator_set) == authority_set_info.root,
+			<Error<T, I>>::InvalidValidatorSet,
+		);
+		ensure!(
+			commitment.signatures.len() == validator_set.len(),
+			<Error<T, I>>::InvalidSignaturesCount,
+		);
+
+		let required_signatures = signatures_required(validator_set.len());
+		let commitment_hash = sp_io::hashing::keccak_256(&commitment.commitment.encode());
+		let mut correct_signatures = 0;
+		for (signature, authority) in commitment.signatures.iter().zip(validator_set) {
+			let is_correct = signature
+				.as_ref()
+				.map(|signature| verify_signature(authority, signature, &commitment_hash))
+				.unwrap_or(false);
+			if is_correct {
+				correct_signatures += 1;
+				// there's no need to verify remaining signatures
+				if correct_signatures >= required_signatures {
+					return Ok(())
+				}
+			}
+		}
+
+		log::trace!(
+			target: LOG_TARGET,
+			"Commitment has {} correct signatures. Required: {}",
+			correct_signatures,
+			required_signatures,
+		);
+
+		Err(<Error<T, I>>::NotEnoughCorrectSignatures)
+	}
+
+	/// Verify that the MMR leaf is included in the MMR, whose root is signed by the commitment.
+	pub(crate) fn verify_mmr_leaf<T: Config<I>, I: 'static>(
+		commitment: &BridgedBeefySignedCommitment<T, I>,
+		mmr_leaf: &BridgedBeefyMmrLeaf<T, I>,
+		mmr_proof: BridgedMmrProof,
+	) -> Result<(), Error<T, I>> {
+		let mmr_root: BridgedMmrHash = commitment
+			.commitment
+			.payload
+			.get_decoded(&MMR_ROOT_ID)
+			.ok_or(<Error<T, I>>::MmrRootMissingFromCommitment)?;
+		pallet_mmr::verify_leaves_proof::<BridgedMmrHashing, _>(
+			mmr_root,
+			vec![pallet_mmr::primitives::DataOrHash::Data(mmr_leaf.clone())],
+			mmr_proof,
+		)
+		.map_err(|e| {
+			log::trace!(target: LOG_TARGET, "MMR proof of leaf {:?} is invalid: {:?}", mmr_leaf, e);
+			<Error<T, I>>::InvalidMmrProof
+		})
+	}
+
+	/// Import a previously verified header to the storage.
+	///
+	/// Note this function solely takes care of updating the storage and pruning old entries,
+	/// but does not verify the validity of such import.
+	pub(crate) fn insert_header<T: Config<I>, I: 'static>(
+		header: BridgedHeader<T, I>,
+		hash: BridgedBlockHash<T, I>,
+	) {
+		let index = <ImportedHashesPointer<T, I>>::get();
+		let pruning = <ImportedHashes<T, I>>::try_get(index);
+		<BestFinalized<T, I>>::put((*header.number(), hash));
+		<ImportedHeaders<T, I>>::insert(hash, header);
+		<ImportedHashes<T, I>>::insert(index, hash);
+
+		// Update ring buffer pointer and remove old header.
+		<ImportedHashesPointer<T, I>>::put((index + 1) % T::HeadersToKeep::get());
+		if let Ok(hash) = pruning {
+			log::debug!(target: LOG_TARGET, "Pruning old header: {:?}.", hash);
+			<ImportedHeaders<T, I>>::remove(hash);
+		}
+	}
+
+	/// Since this writes to storage with no real checks this should only be used in functions that
+	/// were called by a trusted origin.
+	pub(crate) fn initialize_bridge<T: Config<I>, I: 'static>(
+		init_params: super::InitializationData<BridgedHeader<T, I>>,
+	) {
+		let super::InitializationData {
+			header,
+			current_authority_set,
+			next_authority_set,
+			operating_mode,
+		} = init_params;
+
+		let initial_hash = header.hash();
+		<ImportedHashesPointer<T, I>>::put(0);
+		insert_header::<T, I>(*header, initial_hash);
+
+		<CurrentAuthoritySet<T, I>>::put(current_authority_set);
+		<NextAuthoritySet<T, I>>::put(next_authority_set);
+
+		<PalletOperatingMode<T, I>>::put(operating_mode);
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	pub(crate) fn bootstrap_bridge<T: Config<I>, I: 'static>(
+		init_params: super::InitializationData<BridgedHeader<T, I>>,
+	) {
+		let start_number = *init_params.header.number();
+		let end_number = start_number + T::HeadersToKeep::get().into();
+		initialize_bridge::<T, I>(init_params);
+
+		let mut number = start_number;
+		while number < end_number {
+			number = number + One::one();
+			let header = bp_test_utils::test_header::<BridgedHeader<T, I>>(number);
+			let hash = header.hash();
+			insert_header::<T, I>(header, hash);
+		}
+	}
+}
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Get the best finalized header the pallet knows of.
+	pub fn best_finalized() -> Option<BridgedHeader<T, I>> {
+		let (_, hash) = <BestFinalized<T, I>>::get()?;
+		<ImportedHeaders<T, I>>::get(hash)
+	}
+
+	/// Check if a particular header is known to the bridge pallet.
+	pub fn is_known_header(hash: BridgedBlockHash<T, I>) -> bool {
+		<ImportedHeaders<T, I>>::contains_key(hash)
+	}
+
+	/// Verify that the passed storage proof is valid, given it is crafted using
+	/// known finalized header. If the proof is valid, then the `parse` callback
+	/// is called and the function returns its result.
+	pub fn parse_finalized_storage_proof<R>(
+		hash: BridgedBlockHash<T, I>,
+		storage_proof: sp_trie::StorageProof,
+		parse: impl FnOnce(bp_runtime::StorageProofChecker<BridgedBlockHasher<T, I>>) -> R,
+	) -> Result<R, sp_runtime::DispatchError> {
+		Self::ensure_not_halted().map_err(Error::<T, I>::BridgeModule)?;
+		let header = <ImportedHeaders<T, I>>::get(hash).ok_or(Error::<T, I>::UnknownHeader)?;
+		let storage_proof_checker =
+			bp_runtime::StorageProofChecker::new(*header.state_root(), storage_proof)
+				.map_err(|_| Error::<T, I>::StorageRootMismatch)?;
+
+		Ok(parse(storage_proof_checker))
+	}
+}
+
+/// Keccak256 hasher, used to compute merkle root of BEEFY validators addresses.
+struct BeefyKeccak256;
+
+impl beefy_merkle_tree::Hasher for BeefyKeccak256 {
+	fn hash(data: &[u8]) -> beefy_merkle_tree::Hash {
+		sp_io::hashing::keccak_256(data)
+	}
+}
+
+/// Compute merkle root of Ethereum addresses of given BEEFY validators.
+///
+/// This is the same root that `pallet-beefy-mmr` is storing in the `BeefyNextAuthoritySet`.
+pub fn validator_set_root(validators: &[BeefyId]) -> BridgedMmrHash {
+	let addresses = validators
+		.iter()
+		.cloned()
+		.map(pallet_beefy_mmr::BeefyEcdsaToEthereum::convert)
+		.collect::<Vec<_>>();
+	beefy_merkle_tree::merkle_root::<BeefyKeccak256, _, _>(addresses).into()
+}
+
+/// Return number of correct signatures, required to accept commitment of the set with given
+/// number of validators.
+fn signatures_required(validators_len: usize) -> usize {
+	validators_len - validators_len.saturating_sub(1) / 3
+}
+
+/// Returns true if the signature of the `msg_hash` is produced by the `authority`.
+fn verify_signature(authority: &BeefyId, signature: &BeefySignature, msg_hash: &[u8; 32]) -> bool {
+	let raw_signature: &[u8] = signature.as_ref();
+	let raw_signature: [u8; 65] = match raw_signature.try_into() {
+		Ok(raw_signature) => raw_signature,
+		Err(_) => return false,
+	};
+
+	sp_io::crypto::secp256k1_ecdsa_recover_compressed(&raw_signature, msg_hash)
+		.map(|recovered_authority| &recovered_authority[..] == AsRef::<[u8]>::as_ref(authority))
+		.unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{
+		authority_set_info, make_commitment, run_test, sign_commitment, test_header, validator_ids,
+		validator_pairs, Event as TestEvent, Origin, TestCommitment, TestRuntime,
+	};
+	use bp_test_utils::generate_owned_bridge_module_tests;
+	use frame_support::{assert_noop, assert_ok, dispatch::DispatchResultWithPostInfo};
+	use frame_system::{EventRecord, Phase};
+	use sp_runtime::DispatchError;
+
+	fn initialize_bridge() {
+		let current_set = validator_pairs(0, 4);
+		let next_set = validator_pairs(1, 4);
+		assert_ok!(Pallet::<TestRuntime>::initialize(
+			Origin::root(),
+			InitializationData {
+				header: Box::new(test_header(0)),
+				current_authority_set: authority_set_info(0, &current_set),
+				next_authority_set: authority_set_info(1, &next_set),
+				operating_mode: BasicOperatingMode::Normal,
+			},
+		));
+	}
+
+	fn submit_commitment(commitment: TestCommitment) -> DispatchResultWithPostInfo {
+		Pallet::<TestRuntime>::submit_commitment(
+			Origin::signed(1),
+			commitment.commitment,
+			commitment.validator_set,
+			Box::new(commitment.mmr_leaf),
+			commitment.mmr_proof,
+			Box::new(commitment.header),
+		)
+	}
+
+	fn current_set_commitment(block_number: u64) -> TestCommitment {
+		make_commitment(
+			block_number,
+			0,
+			&validator_pairs(0, 4),
+			authority_set_info(1, &validator_pairs(1, 4)),
+		)
+	}
+
+	fn next_block() {
+		use frame_support::traits::OnInitialize;
+
+		let current_number = frame_system::Pallet::<TestRuntime>::block_number();
+		frame_system::Pallet::<TestRuntime>::set_block_number(current_number + 1);
+		let _ = Pallet::<TestRuntime>::on_initialize(current_number);
+	}
+
+	#[test]
+	fn init_root_or_owner_origin_can_initialize_pallet() {
+		run_test(|| {
+			let init_data = InitializationData {
+				header: Box::new(test_header(0)),
+				current_authority_set: authority_set_info(0, &validator_pairs(0, 4)),
+				next_authority_set: authority_set_info(1, &validator_pairs(1, 4)),
+				operating_mode: BasicOperatingMode::Normal,
+			};
+
+			assert_noop!(
+				Pallet::<TestRuntime>::initialize(Origin::signed(1), init_data.clone()),
+				DispatchError::BadOrigin,
+			);
+
+			PalletOwner::<TestRuntime>::put(2);
+			assert_ok!(Pallet::<TestRuntime>::initialize(Origin::signed(2), init_data.clone()));
+			assert_eq!(Pallet::<TestRuntime>::best_finalized(), Some(test_header(0)));
+			assert_eq!(CurrentAuthoritySet::<TestRuntime>::get(), init_data.current_authority_set);
+			assert_eq!(NextAuthoritySet::<TestRuntime>::get(), init_data.next_authority_set);
+
+			assert_noop!(
+				Pallet::<TestRuntime>::initialize(Origin::root(), init_data),
+				<Error<TestRuntime>>::AlreadyInitialized,
+			);
+		})
+	}
+
+	#[test]
+	fn pallet_rejects_commitment_if_not_initialized_yet() {
+		run_test(|| {
+			assert_noop!(
+				submit_commitment(current_set_commitment(2)),
+				<Error<TestRuntime>>::NotInitialized,
+			);
+		})
+	}
+
+	#[test]
+	fn pallet_rejects_commitment_if_halted() {
+		run_test(|| {
+			initialize_bridge();
+			PalletOperatingMode::<TestRuntime>::put(BasicOperatingMode::Halted);
+
+			assert_noop!(
+				submit_commitment(current_set_commitment(2)),
+				Error::<TestRuntime>::BridgeModule(bp_runtime::OwnedBridgeModuleError::Halted),
+			);
+		})
+	}
+
+	#[test]
+	fn succesfully_imports_commitment_signed_by_current_set() {
+		run_test(|| {
+			initialize_bridge();
+			frame_system::Pallet::<TestRuntime>::set_block_number(1);
+			frame_system::Pallet::<TestRuntime>::reset_events();
+
+			let commitment = current_set_commitment(2);
+			let header = commitment.header.clone();
+			let result = submit_commitment(commitment);
+			assert_ok!(result);
+			assert_eq!(result.unwrap().pays_fee, Pays::Yes);
+
+			assert_eq!(Pallet::<TestRuntime>::best_finalized(), Some(header.clone()));
+			assert!(Pallet::<TestRuntime>::is_known_header(header.hash()));
+			assert_eq!(CurrentAuthoritySet::<TestRuntime>::get().id, 0);
+			assert_eq!(NextAuthoritySet::<TestRuntime>::get().id, 1);
+			assert_eq!(
+				frame_system::Pallet::<TestRuntime>::events(),
+				vec![EventRecord {
+					phase: Phase::Initialization,
+					event: TestEvent::Beefy(Event::HeaderImported {
+						header_number: 1,
+						header_hash: header.hash(),
+					}),
+					topics: vec![],
+				}],
+			);
+		})
+	}
+
+	#[test]
+	fn commitment_signed_by_next_set_enacts_new_set() {
+		run_test(|| {
+			initialize_bridge();
+
+			let next_next_set_info = authority_set_info(2, &validator_pairs(2, 3));
+			let commitment =
+				make_commitment(3, 1, &validator_pairs(1, 4), next_next_set_info.clone());
+			let result = submit_commitment(commitment);
+			assert_ok!(result);
+			assert_eq!(result.unwrap().pays_fee, Pays::No);
+
+			assert_eq!(
+				CurrentAuthoritySet::<TestRuntime>::get(),
+				authority_set_info(1, &validator_pairs(1, 4)),
+			);
+			assert_eq!(NextAuthoritySet::<TestRuntime>::get(), next_next_set_info);
+
+			// commitments of the old set are rejected after hand-off
+			next_block();
+			assert_noop!(
+				submit_commitment(current_set_commitment(4)),
+				<Error<TestRuntime>>::InvalidValidatorSetId,
+			);
+
+			// commitments of the new set are accepted
+			assert_ok!(submit_commitment(make_commitment(
+				4,
+				1,
+				&validator_pairs(1, 4),
+				next_next_set_info,
+			)));
+		})
+	}
+
+	#[test]
+	fn hand_off_is_rejected_if_leaf_has_invalid_next_set() {
+		run_test(|| {
+			initialize_bridge();
+
+			assert_noop!(
+				submit_commitment(make_commitment(
+					3,
+					1,
+					&validator_pairs(1, 4),
+					authority_set_info(1, &validator_pairs(1, 4)),
+				)),
+				<Error<TestRuntime>>::InvalidMmrLeaf,
+			);
+		})
+	}
+
+	#[test]
+	fn rejects_old_commitment() {
+		run_test(|| {
+			initialize_bridge();
+			assert_ok!(submit_commitment(current_set_commitment(3)));
+
+			assert_noop!(
+				submit_commitment(current_set_commitment(3)),
+				<Error<TestRuntime>>::OldCommitment,
+			);
+			assert_noop!(
+				submit_commitment(current_set_commitment(2)),
+				<Error<TestRuntime>>::OldCommitment,
+			);
+		})
+	}
+
+	#[test]
+	fn rejects_commitment_of_unknown_set() {
+		run_test(|| {
+			initialize_bridge();
+
+			assert_noop!(
+				submit_commitment(make_commitment(
+					2,
+					2,
+					&validator_pairs(2, 4),
+					authority_set_info(3, &validator_pairs(3, 4)),
+				)),
+				<Error<TestRuntime>>::InvalidValidatorSetId,
+			);
+		})
+	}
+
+	#[test]
+	fn rejects_commitment_with_invalid_validator_set() {
+		run_test(|| {
+			initialize_bridge();
+
+			// validators of other set
+			let mut commitment = current_set_commitment(2);
+			commitment.validator_set = validator_ids(&validator_pairs(1, 4));
+			assert_noop!(submit_commitment(commitment), <Error<TestRuntime>>::InvalidValidatorSet);
+
+			// missing validator
+			let mut commitment = current_set_commitment(2);
+			commitment.validator_set.pop();
+			assert_noop!(submit_commitment(commitment), <Error<TestRuntime>>::InvalidValidatorSet);
+
+			// missing signature
+			let mut commitment = current_set_commitment(2);
+			commitment.commitment.signatures.pop();
+			assert_noop!(
+				submit_commitment(commitment),
+				<Error<TestRuntime>>::InvalidSignaturesCount,
+			);
+		})
+	}
+
+	#[test]
+	fn rejects_commitment_without_supermajority_of_correct_signatures() {
+		run_test(|| {
+			initialize_bridge();
+
+			// 3 of 4 signatures are required
+			let mut commitment = current_set_commitment(2);
+			commitment.commitment.signatures[0] = None;
+			commitment.commitment.signatures[1] = None;
+			assert_noop!(
+				submit_commitment(commitment),
+				<Error<TestRuntime>>::NotEnoughCorrectSignatures,
+			);
+
+			// signatures of other validators are ignored
+			let mut commitment = current_set_commitment(2);
+			let other_commitment =
+				make_commitment(2, 0, &validator_pairs(1, 4), authority_set_info(1, &[]));
+			commitment.commitment.signatures[0] = other_commitment.commitment.signatures[0].clone();
+			commitment.commitment.signatures[1] = other_commitment.commitment.signatures[1].clone();
+			assert_noop!(
+				submit_commitment(commitment),
+				<Error<TestRuntime>>::NotEnoughCorrectSignatures,
+			);
+
+			// but if there are enough correct signatures, the commitment is accepted
+			let mut commitment = current_set_commitment(2);
+			commitment.commitment.signatures[0] = None;
+			assert_ok!(submit_commitment(commitment));
+		})
+	}
+
+	#[test]
+	fn rejects_commitment_with_invalid_mmr_proof() {
+		run_test(|| {
+			initialize_bridge();
+
+			// leaf is not in the MMR
+			let mut commitment = current_set_commitment(2);
+			commitment.mmr_leaf.beefy_next_authority_set.len += 1;
+			assert_noop!(submit_commitment(commitment), <Error<TestRuntime>>::InvalidMmrProof);
+
+			// MMR root is missing
+			let mut commitment = current_set_commitment(2);
+			commitment.commitment.commitment.payload =
+				beefy_primitives::Payload::new(*b"xx", vec![]);
+			sign_commitment(&mut commitment.commitment, &validator_pairs(0, 4));
+			assert_noop!(
+				submit_commitment(commitment),
+				<Error<TestRuntime>>::MmrRootMissingFromCommitment,
+			);
+		})
+	}
+
+	#[test]
+	fn rejects_commitment_with_mismatching_header_or_leaf() {
+		run_test(|| {
+			initialize_bridge();
+
+			// header is not referenced by the leaf
+			let mut commitment = current_set_commitment(2);
+			commitment.header = test_header(5);
+			assert_noop!(submit_commitment(commitment), <Error<TestRuntime>>::InvalidHeader);
+
+			// leaf is not the leaf of the commitment block
+			let commitment = current_set_commitment(2);
+			let mut other_commitment = current_set_commitment(3);
+			other_commitment.mmr_leaf = commitment.mmr_leaf;
+			other_commitment.header = commitment.header;
+			assert_noop!(submit_commitment(other_commitment), <Error<TestRuntime>>::InvalidMmrLeaf);
+		})
+	}
+
+	#[test]
+	fn rate_limiter_disallows_imports_once_limit_is_hit_in_single_block() {
+		run_test(|| {
+			initialize_bridge();
+
+			assert_ok!(submit_commitment(current_set_commitment(2)));
+			assert_ok!(submit_commitment(current_set_commitment(3)));
+			assert_noop!(
+				submit_commitment(current_set_commitment(4)),
+				<Error<TestRuntime>>::TooManyRequests,
+			);
+
+			next_block();
+			assert_ok!(submit_commitment(current_set_commitment(4)));
+		})
+	}
+
+	#[test]
+	fn old_headers_are_pruned() {
+		run_test(|| {
+			initialize_bridge();
+
+			for block_number in 2..8 {
+				next_block();
+				assert_ok!(submit_commitment(current_set_commitment(block_number)));
+			}
+
+			// we keep 5 headers, so initial header and the header #1 must be pruned
+			assert!(!Pallet::<TestRuntime>::is_known_header(test_header(0).hash()));
+			assert!(!Pallet::<TestRuntime>::is_known_header(test_header(1).hash()));
+			for number in 2..7 {
+				assert!(Pallet::<TestRuntime>::is_known_header(test_header(number).hash()));
+			}
+		})
+	}
+
+	#[test]
+	fn parse_finalized_storage_proof_rejects_proof_on_unknown_header() {
+		run_test(|| {
+			assert_noop!(
+				Pallet::<TestRuntime>::parse_finalized_storage_proof(
+					Default::default(),
+					sp_trie::StorageProof::new(vec![]),
+					|_| (),
+				),
+				Error::<TestRuntime>::UnknownHeader,
+			);
+		});
+	}
+
+	#[test]
+	fn parse_finalized_storage_accepts_valid_proof() {
+		run_test(|| {
+			let (state_root, storage_proof) = bp_runtime::craft_valid_storage_proof();
+
+			let mut header = test_header(2);
+			header.set_state_root(state_root);
+
+			let hash = header.hash();
+			<BestFinalized<TestRuntime>>::put((2, hash));
+			<ImportedHeaders<TestRuntime>>::insert(hash, header);
+
+			assert_ok!(
+				Pallet::<TestRuntime>::parse_finalized_storage_proof(hash, storage_proof, |_| (),),
+				(),
+			);
+
+			PalletOperatingMode::<TestRuntime>::put(BasicOperatingMode::Halted);
+			assert_noop!(
+				Pallet::<TestRuntime>::parse_finalized_storage_proof(
+					hash,
+					sp_trie::StorageProof::new(vec![]),
+					|_| (),
+				),
+				Error::<TestRuntime>::BridgeModule(bp_runtime::OwnedBridgeModuleError::Halted),
+			);
+		});
+	}
+
+	generate_owned_bridge_module_tests!(BasicOperatingMode::Normal, BasicOperatingMode::Halted);
+}
@@ -0,0 +1,224 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+// From construct_runtime macro
+#![allow(clippy::from_over_into)]
+
+use crate::{
+	validator_set_root, BeefyAuthoritySetInfo, BridgedBeefyMmrLeaf, BridgedBeefySignedCommitment,
+	BridgedMmrHashing, BridgedMmrProof,
+};
+
+use beefy_primitives::{
+	crypto::AuthorityId as BeefyId, known_payload_ids::MMR_ROOT_ID, mmr::MmrLeafVersion,
+	Commitment, Payload, SignedCommitment, ValidatorSetId,
+};
+use bp_runtime::Chain;
+use codec::Encode;
+use frame_support::{construct_runtime, parameter_types, weights::Weight};
+use sp_core::{ecdsa, sr25519::Signature, Pair};
+use sp_runtime::{
+	testing::{Header, H256},
+	traits::{BlakeTwo256, Hash, Header as HeaderT, IdentityLookup},
+	Perbill,
+};
+
+pub type AccountId = u64;
+pub type TestHeader = crate::BridgedHeader<TestRuntime, ()>;
+pub type TestNumber = crate::BridgedBlockNumber<TestRuntime, ()>;
+
+type Block = frame_system::mocking::MockBlock<TestRuntime>;
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<TestRuntime>;
+
+use crate as beefy;
+
+construct_runtime! {
+	pub enum TestRuntime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Beefy: beefy::{Pallet, Call, Event<T>},
+	}
+}
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+
+impl frame_system::Config for TestRuntime {
+	type Origin = Origin;
+	type Index = u64;
+	type Call = Call;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type BaseCallFilter = frame_support::traits::Everything;
+	type SystemWeightInfo = ();
+	type DbWeight = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+parameter_types! {
+	pub const MaxRequests: u32 = 2;
+	pub const HeadersToKeep: u32 = 5;
+}
+
+impl beefy::Config for TestRuntime {
+	type Event = Event;
+	type BridgedChain = TestBridgedChain;
+	type BridgedMmrLeafExtra = ();
+	type MaxRequests = MaxRequests;
+	type HeadersToKeep = HeadersToKeep;
+	type WeightInfo = ();
+}
+
+#[derive(Debug)]
+pub struct TestBridgedChain;
+
+impl Chain for TestBridgedChain {
+	type BlockNumber = <TestRuntime as frame_system::Config>::BlockNumber;
+	type Hash = <TestRuntime as frame_system::Config>::Hash;
+	type Hasher = <TestRuntime as frame_system::Config>::Hashing;
+	type Header = <TestRuntime as frame_system::Config>::Header;
+
+	type AccountId = AccountId;
+	type Balance = u64;
+	type Index = u64;
+	type Signature = Signature;
+
+	fn max_extrinsic_size() -> u32 {
+		unreachable!()
+	}
+	fn max_extrinsic_weight() -> Weight {
+		unreachable!()
+	}
+}
+
+/// Everything that is required to call `submit_commitment`.
+pub struct TestCommitment {
+	pub commitment: BridgedBeefySignedCommitment<TestRuntime, ()>,
+	pub validator_set: Vec<BeefyId>,
+	pub mmr_leaf: BridgedBeefyMmrLeaf<TestRuntime, ()>,
+	pub mmr_proof: BridgedMmrProof,
+	pub header: TestHeader,
+}
+
+pub fn run_test<T>(test: impl FnOnce() -> T) -> T {
+	sp_io::TestExternalities::new(Default::default()).execute_with(test)
+}
+
+pub fn test_header(num: TestNumber) -> TestHeader {
+	// We wrap the call to avoid explicit type annotations in our tests
+	bp_test_utils::test_header(num)
+}
+
+/// Return key pairs of validators of given set.
+pub fn validator_pairs(set_id: ValidatorSetId, count: u8) -> Vec<ecdsa::Pair> {
+	(0..count)
+		.map(|index| {
+			let mut seed = [1u8; 32];
+			seed[0] = set_id as u8;
+			seed[1] = index;
+			ecdsa::Pair::from_seed(&seed)
+		})
+		.collect()
+}
+
+/// Return BEEFY identifiers of given validators.
+pub fn validator_ids(validators: &[ecdsa::Pair]) -> Vec<BeefyId> {
+	validators.iter().map(|pair| pair.public().into()).collect()
+}
+
+/// Return short information about validator set, that is stored in the MMR leaves.
+pub fn authority_set_info(
+	set_id: ValidatorSetId,
+	validators: &[ecdsa::Pair],
+) -> BeefyAuthoritySetInfo {
+	BeefyAuthoritySetInfo {
+		id: set_id,
+		len: validators.len() as u32,
+		root: validator_set_root(&validator_ids(validators)),
+	}
+}
+
+/// (Re)sign commitment by all given validators.
+pub fn sign_commitment(
+	commitment: &mut BridgedBeefySignedCommitment<TestRuntime, ()>,
+	validators: &[ecdsa::Pair],
+) {
+	let commitment_hash = sp_io::hashing::keccak_256(&commitment.commitment.encode());
+	commitment.signatures = validators
+		.iter()
+		.map(|pair| Some(pair.sign_prehashed(&commitment_hash).into()))
+		.collect();
+}
+
+/// Make commitment of the block `block_number`, signed by all given validators.
+///
+/// MMR of the commitment has the single leaf of the commitment block, so the MMR root
+/// is the hash of this leaf and the MMR proof has no items.
+pub fn make_commitment(
+	block_number: TestNumber,
+	set_id: ValidatorSetId,
+	validators: &[ecdsa::Pair],
+	next_authority_set: BeefyAuthoritySetInfo,
+) -> TestCommitment {
+	let header = test_header(block_number - 1);
+	let mmr_leaf = BridgedBeefyMmrLeaf::<TestRuntime, ()> {
+		version: MmrLeafVersion::new(0, 0),
+		parent_number_and_hash: (*header.number(), header.hash()),
+		beefy_next_authority_set: next_authority_set,
+		leaf_extra: (),
+	};
+	let mmr_root = BridgedMmrHashing::hash(&mmr_leaf.encode());
+
+	let mut commitment = SignedCommitment {
+		commitment: Commitment {
+			payload: Payload::new(MMR_ROOT_ID, mmr_root.encode()),
+			block_number,
+			validator_set_id: set_id,
+		},
+		signatures: vec![],
+	};
+	sign_commitment(&mut commitment, validators);
+
+	TestCommitment {
+		commitment,
+		validator_set: validator_ids(validators),
+		mmr_leaf,
+		mmr_proof: BridgedMmrProof { leaf_indices: vec![0], leaf_count: 1, items: vec![] },
+		header,
+	}
+}
@@ -0,0 +1,62 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for `pallet_bridge_beefy`.
+//!
+//! Weights are estimated from the cost of the `secp256k1_ecdsa_recover_compressed` host function
+//! and the number of MMR proof items. They must be regenerated using the `submit_commitment`
+//! benchmark from the `benchmarking` module.
+
+#![allow(clippy::all)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for `pallet_bridge_beefy`.
+pub trait WeightInfo {
+	fn submit_commitment(s: u32, p: u32) -> Weight;
+}
+
+/// Weights for `pallet_bridge_beefy` that are estimated for one of the Bridge testnets.
+///
+/// Those weights are test only and must never be used in production.
+pub struct BridgeWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for BridgeWeight<T> {
+	fn submit_commitment(s: u32, p: u32) -> Weight {
+		(120_000_000 as Weight)
+			.saturating_add((55_000_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add((2_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn submit_commitment(s: u32, p: u32) -> Weight {
+		(120_000_000 as Weight)
+			.saturating_add((55_000_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add((2_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+}
@@ -20,6 +20,7 @@ use frame_support::{dispatch::CallableCallFor, traits::IsSubType};
 use sp_runtime::{
 	traits::Header,
 	transaction_validity::{InvalidTransaction, TransactionValidity, ValidTransaction},
+	DispatchError,
 };
 
 /// Validate Grandpa headers in order to avoid "mining" transactions that provide outdated
@@ -35,6 +36,13 @@ impl<
 		let bundled_block_number = match call.is_sub_type() {
 			Some(crate::Call::<T, I>::submit_finality_proof { ref finality_target, .. }) =>
 				*finality_target.number(),
+			// headers of the batch are ordered, so if the first header is obsolete, the whole
+			// batch will be rejected
+			Some(crate::Call::<T, I>::submit_finality_proofs { ref finality_proofs }) =>
+				match finality_proofs.first() {
+					Some((finality_target, _)) => *finality_target.number(),
+					None => return InvalidTransaction::Call.into(),
+				},
 			_ => return Ok(ValidTransaction::default()),
 		};
 
@@ -57,6 +65,62 @@ impl<
 
 		Ok(ValidTransaction::default())
 	}
+
+	fn is_bridge_call(call: &<T as frame_system::Config>::Call) -> bool {
+		matches!(
+			call.is_sub_type(),
+			Some(crate::Call::<T, I>::submit_finality_proof { .. }) |
+				Some(crate::Call::<T, I>::submit_finality_proofs { .. })
+		)
+	}
+
+	fn are_overlapping_calls(
+		call: &<T as frame_system::Config>::Call,
+		other_call: &<T as frame_system::Config>::Call,
+	) -> bool {
+		let headers_range = |call: &<T as frame_system::Config>::Call| match call.is_sub_type() {
+			Some(crate::Call::<T, I>::submit_finality_proof { ref finality_target, .. }) =>
+				Some((*finality_target.number(), *finality_target.number())),
+			Some(crate::Call::<T, I>::submit_finality_proofs { ref finality_proofs }) =>
+				match (finality_proofs.first(), finality_proofs.last()) {
+					(Some((first, _)), Some((last, _))) => Some((*first.number(), *last.number())),
+					_ => None,
+				},
+			_ => None,
+		};
+
+		match (headers_range(call), headers_range(other_call)) {
+			(Some((first, last)), Some((other_first, other_last))) =>
+				first <= other_last && other_first <= last,
+			_ => false,
+		}
+	}
+
+	fn is_invalid_proof_error(_error: &DispatchError) -> bool {
+		// the justification may fail verification because the authority set is changed by a
+		// concurrent transaction (or hasn't been changed yet), so the submitter may be honest
+		false
+	}
+
+	fn is_call_data_imported(call: &<T as frame_system::Config>::Call) -> bool {
+		let bundled_block_hash = match call.is_sub_type() {
+			Some(crate::Call::<T, I>::submit_finality_proof { ref finality_target, .. }) =>
+				finality_target.hash(),
+			// headers of the batch are ordered, so if the last header is imported, the whole
+			// batch has been imported
+			Some(crate::Call::<T, I>::submit_finality_proofs { ref finality_proofs }) =>
+				match finality_proofs.last() {
+					Some((finality_target, _)) => finality_target.hash(),
+					None => return false,
+				},
+			_ => return false,
+		};
+
+		matches!(
+			crate::BestFinalized::<T, I>::get(),
+			Some((_, best_finalized_hash)) if best_finalized_hash == bundled_block_hash
+		)
+	}
 }
 
 #[cfg(test)]
@@ -78,6 +142,18 @@ mod tests {
 		.is_ok()
 	}
 
+	fn validate_batch_submit(nums: &[TestNumber]) -> bool {
+		crate::Pallet::<TestRuntime>::validate(&Call::Grandpa(
+			crate::Call::<TestRuntime, ()>::submit_finality_proofs {
+				finality_proofs: nums
+					.iter()
+					.map(|num| (test_header(*num), make_default_justification(&test_header(*num))))
+					.collect(),
+			},
+		))
+		.is_ok()
+	}
+
 	fn sync_to_header_10() {
 		let header10_hash = sp_core::H256::default();
 		BestFinalized::<TestRuntime, ()>::put((10, header10_hash));
@@ -112,4 +188,25 @@ mod tests {
 			assert!(validate_block_submit(15));
 		});
 	}
+
+	#[test]
+	fn extension_rejects_batch_with_obsolete_first_header() {
+		run_test(|| {
+			// when current best finalized is #10 and the batch starts with header#10 => tx is
+			// rejected
+			sync_to_header_10();
+			assert!(!validate_batch_submit(&[10, 15]));
+			assert!(!validate_batch_submit(&[]));
+		});
+	}
+
+	#[test]
+	fn extension_accepts_batch_of_new_headers() {
+		run_test(|| {
+			// when current best finalized is #10 and the batch starts with header#11 => tx is
+			// accepted
+			sync_to_header_10();
+			assert!(validate_batch_submit(&[11, 15]));
+		});
+	}
 }
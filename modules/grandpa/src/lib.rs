@@ -24,13 +24,23 @@
 //!
 //! The pallet is responsible for tracking GRANDPA validator set hand-offs. We only import headers
 //! with justifications signed by the current validator set we know of. The header is inspected for
-//! a `ScheduledChanges` digest item, which is then used to update to next validator set.
+//! a `ScheduledChanges` digest item, which is then used to update to next validator set. If the
+//! change is scheduled with a delay, it is stored in the pallet and enacted once the header at
+//! (or after) the enactment block is imported.
+//!
+//! Forced authority set changes (`ForcedChange` digest items) are not supported. GRANDPA only
+//! issues them to recover from a stall: the new set starts finalizing headers on top of the
+//! `median_last_finalized` header, which hasn't been finalized by the set known to the pallet, so
+//! the light client can't verify the hand-off. Headers that signal forced changes are rejected.
+//! Once the bridged chain GRANDPA has been recovered by forced change, the pallet owner may use the
+//! `reset_authority_set` call to replace both current authority set and best finalized header.
 //!
 //! Since this pallet only tracks finalized headers it does not deal with forks. Forks can only
 //! occur if the GRANDPA validator set on the bridged chain is either colluding or there is a severe
-//! bug causing resulting in an equivocation. Such events are outside the scope of this pallet.
-//! Shall the fork occur on the bridged chain governance intervention will be required to
-//! re-initialize the bridge and track the right fork.
+//! bug causing resulting in an equivocation. Anyone may submit two conflicting justifications,
+//! signed by the same authority set, using the `submit_equivocation_proof` call. Once the proof
+//! is verified, the pallet is halted and stops serving storage proofs. Governance intervention
+//! will then be required to re-initialize the bridge and track the right fork.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 // Runtime-generated enums
@@ -42,13 +52,14 @@ use finality_grandpa::voter_set::VoterSet;
 use frame_support::{ensure, fail};
 use frame_system::ensure_signed;
 use sp_finality_grandpa::{ConsensusLog, GRANDPA_ENGINE_ID};
-use sp_runtime::traits::{Header as HeaderT, Zero};
-use sp_std::{boxed::Box, convert::TryInto};
+use sp_runtime::traits::{Header as HeaderT, One, Saturating, Zero};
+use sp_std::{boxed::Box, collections::btree_set::BTreeSet, convert::TryInto, vec::Vec};
 
 mod extension;
 #[cfg(test)]
 mod mock;
 
+pub mod migration;
 /// Module, containing weights for this pallet.
 pub mod weights;
 
@@ -75,11 +86,15 @@ pub type BridgedHeader<T, I> = HeaderOf<<T as Config<I>>::BridgedChain>;
 pub mod pallet {
 	use super::*;
 	use bp_runtime::BasicOperatingMode;
-	use frame_support::pallet_prelude::*;
+	use frame_support::{pallet_prelude::*, weights::PostDispatchInfo};
 	use frame_system::pallet_prelude::*;
+	use sp_runtime::DispatchErrorWithPostInfo;
 
 	#[pallet::config]
 	pub trait Config<I: 'static = ()>: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self, I>> + IsType<<Self as frame_system::Config>::Event>;
+
 		/// The chain we are bridging to here.
 		type BridgedChain: Chain;
 
@@ -106,8 +121,12 @@ pub mod pallet {
 		type WeightInfo: WeightInfo;
 	}
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
 
 	#[pallet::hooks]
@@ -149,58 +168,157 @@ pub mod pallet {
 			Self::ensure_not_halted().map_err(Error::<T, I>::BridgeModule)?;
 			let _ = ensure_signed(origin)?;
 
-			ensure!(Self::request_count() < T::MaxRequests::get(), <Error<T, I>>::TooManyRequests);
+			let is_authorities_set_updated =
+				import_finality_proof::<T, I>(*finality_target, justification)?;
 
-			let (hash, number) = (finality_target.hash(), finality_target.number());
-			log::trace!(
-				target: LOG_TARGET,
-				"Going to try and finalize header {:?}",
-				finality_target
-			);
+			// mandatory header is a header that changes authorities set (or schedules delayed
+			// change). The pallet can't go further without importing this header. So every bridge
+			// MUST import mandatory headers.
+			//
+			// We don't want to charge extra costs for mandatory operations. So relayer is not
+			// paying fee for mandatory headers import transactions.
+			let is_mandatory_header = is_authorities_set_updated;
+			let pays_fee = if is_mandatory_header { Pays::No } else { Pays::Yes };
 
-			let best_finalized = BestFinalized::<T, I>::get();
-			let best_finalized =
-				best_finalized.and_then(|(_, hash)| ImportedHeaders::<T, I>::get(hash));
-			let best_finalized = match best_finalized {
-				Some(best_finalized) => best_finalized,
-				None => {
-					log::error!(
-						target: LOG_TARGET,
-						"Cannot finalize header {:?} because pallet is not yet initialized",
-						finality_target,
-					);
-					fail!(<Error<T, I>>::NotInitialized);
-				},
-			};
+			Ok(pays_fee.into())
+		}
+
+		/// Verify and import multiple finalized headers at once.
+		///
+		/// Every item of `finality_proofs` is a target header and its justification, which are
+		/// verified and imported the same way as in `submit_finality_proof`. Headers must be
+		/// ordered by their numbers. Authority set changes, signalled by some header, are enacted
+		/// before the next header of the batch is verified, so the batch may span multiple
+		/// authority sets. Every imported header counts towards the `MaxRequests` limit.
+		///
+		/// Headers are imported atomically - if any item is invalid, the whole batch is rejected
+		/// and the weight of remaining items is refunded. The submitter doesn't pay fee if all
+		/// headers of the batch are mandatory.
+		#[pallet::weight(finality_proofs.iter().fold(0, |weight: Weight, (_, justification)| {
+			weight.saturating_add(finality_proof_weight::<T, I>(justification))
+		}))]
+		pub fn submit_finality_proofs(
+			origin: OriginFor<T>,
+			finality_proofs: Vec<(BridgedHeader<T, I>, GrandpaJustification<BridgedHeader<T, I>>)>,
+		) -> DispatchResultWithPostInfo {
+			Self::ensure_not_halted().map_err(Error::<T, I>::BridgeModule)?;
+			let _ = ensure_signed(origin)?;
+			ensure!(!finality_proofs.is_empty(), <Error<T, I>>::NoFinalityProofs);
+
+			let mut actual_weight: Weight = 0;
+			let mut are_all_headers_mandatory = true;
+			for (finality_target, justification) in finality_proofs {
+				actual_weight =
+					actual_weight.saturating_add(finality_proof_weight::<T, I>(&justification));
+				let is_mandatory_header =
+					import_finality_proof::<T, I>(finality_target, justification).map_err(
+						|error| DispatchErrorWithPostInfo {
+							post_info: PostDispatchInfo {
+								actual_weight: Some(actual_weight),
+								pays_fee: Pays::Yes,
+							},
+							error,
+						},
+					)?;
+				are_all_headers_mandatory &= is_mandatory_header;
+			}
+
+			let pays_fee = if are_all_headers_mandatory { Pays::No } else { Pays::Yes };
+
+			Ok(PostDispatchInfo { actual_weight: Some(actual_weight), pays_fee })
+		}
 
-			// We do a quick check here to ensure that our header chain is making progress and isn't
-			// "travelling back in time" (which could be indicative of something bad, e.g a
-			// hard-fork).
-			ensure!(best_finalized.number() < number, <Error<T, I>>::OldHeader);
+		/// Submit proof of GRANDPA equivocation on the bridged chain.
+		///
+		/// The proof is two justifications for different headers with the same number, which are
+		/// both signed by the authority set that is known to the pallet. If both justifications
+		/// are valid, the pallet is halted, so it stops importing new headers and serving storage
+		/// proofs. Authorities that have signed both headers are recorded in the storage.
+		///
+		/// The submitter doesn't pay fee for valid equivocation proofs.
+		#[pallet::weight(T::WeightInfo::submit_finality_proof(
+			first_justification.commit.precommits.len().try_into().unwrap_or(u32::MAX),
+			first_justification.votes_ancestries.len().try_into().unwrap_or(u32::MAX),
+		).saturating_add(T::WeightInfo::submit_finality_proof(
+			second_justification.commit.precommits.len().try_into().unwrap_or(u32::MAX),
+			second_justification.votes_ancestries.len().try_into().unwrap_or(u32::MAX),
+		)))]
+		pub fn submit_equivocation_proof(
+			origin: OriginFor<T>,
+			first_justification: GrandpaJustification<BridgedHeader<T, I>>,
+			second_justification: GrandpaJustification<BridgedHeader<T, I>>,
+		) -> DispatchResultWithPostInfo {
+			let _ = ensure_signed(origin)?;
+			ensure!(<BestFinalized<T, I>>::exists(), <Error<T, I>>::NotInitialized);
+
+			let (first_hash, number) =
+				(first_justification.commit.target_hash, first_justification.commit.target_number);
+			let second_hash = second_justification.commit.target_hash;
+			ensure!(
+				number == second_justification.commit.target_number && first_hash != second_hash,
+				<Error<T, I>>::InvalidEquivocationProof
+			);
 
-			let authority_set = <CurrentAuthoritySet<T, I>>::get();
+			let authority_set = expected_authority_set::<T, I>(number);
 			let set_id = authority_set.set_id;
-			verify_justification::<T, I>(&justification, hash, *number, authority_set)?;
+			ensure!(
+				!<Equivocations<T, I>>::contains_key(set_id),
+				<Error<T, I>>::EquivocationAlreadyReported
+			);
+			verify_justification::<T, I>(
+				&first_justification,
+				first_hash,
+				number,
+				authority_set.clone(),
+			)?;
+			verify_justification::<T, I>(
+				&second_justification,
+				second_hash,
+				number,
+				authority_set,
+			)?;
+
+			let first_signers = first_justification
+				.commit
+				.precommits
+				.iter()
+				.map(|signed| &signed.id)
+				.collect::<BTreeSet<_>>();
+			let equivocators = second_justification
+				.commit
+				.precommits
+				.iter()
+				.map(|signed| &signed.id)
+				.filter(|id| first_signers.contains(id))
+				.cloned()
+				.collect::<BTreeSet<_>>()
+				.into_iter()
+				.collect::<Vec<_>>();
 
-			let is_authorities_change_enacted =
-				try_enact_authority_change::<T, I>(&finality_target, set_id)?;
-			<RequestCount<T, I>>::mutate(|count| *count += 1);
-			insert_header::<T, I>(*finality_target, hash);
-			log::info!(
+			log::error!(
 				target: LOG_TARGET,
-				"Successfully imported finalized header with hash {:?}!",
-				hash
+				"Authority set {} has finalized conflicting headers {:?} and {:?} at {:?}. \
+				Equivocators: {:?}. Halting the pallet",
+				set_id,
+				first_hash,
+				second_hash,
+				number,
+				equivocators,
 			);
 
-			// mandatory header is a header that changes authorities set. The pallet can't go
-			// further without importing this header. So every bridge MUST import mandatory headers.
-			//
-			// We don't want to charge extra costs for mandatory operations. So relayer is not
-			// paying fee for mandatory headers import transactions.
-			let is_mandatory_header = is_authorities_change_enacted;
-			let pays_fee = if is_mandatory_header { Pays::No } else { Pays::Yes };
+			<Equivocations<T, I>>::insert(
+				set_id,
+				bp_header_chain::EquivocationEvidence {
+					header_number: number,
+					first_header_hash: first_hash,
+					second_header_hash: second_hash,
+					equivocators: equivocators.clone(),
+				},
+			);
+			<PalletOperatingMode<T, I>>::put(BasicOperatingMode::Halted);
+			Self::deposit_event(Event::EquivocationDetected { set_id, equivocators });
 
-			Ok(pays_fee.into())
+			Ok(Pays::No.into())
 		}
 
 		/// Bootstrap the bridge pallet with an initial header and authority set from which to sync.
@@ -232,6 +350,58 @@ pub mod pallet {
 			Ok(().into())
 		}
 
+		/// Replace current authority set and best finalized header.
+		///
+		/// This call may be used to recover the bridge after GRANDPA on the bridged chain has been
+		/// stalled and then recovered with a forced authority set change. Previously imported
+		/// headers are kept in the storage, but the new header must be newer than the current best
+		/// finalized header and the new authority set id must be larger than the current one.
+		///
+		/// The pallet doesn't verify that the header is actually finalized by the given authority
+		/// set, so it is important that you ensure that valid data is being passed in.
+		///
+		/// May only be called either by root, or by `PalletOwner`.
+		#[pallet::weight((T::DbWeight::get().reads_writes(3, 6), DispatchClass::Operational))]
+		pub fn reset_authority_set(
+			origin: OriginFor<T>,
+			header: Box<BridgedHeader<T, I>>,
+			authority_set: bp_header_chain::AuthoritySet,
+		) -> DispatchResultWithPostInfo {
+			Self::ensure_owner_or_root(origin)?;
+
+			let (best_finalized_number, _) =
+				<BestFinalized<T, I>>::get().ok_or(<Error<T, I>>::NotInitialized)?;
+			let (hash, number) = (header.hash(), *header.number());
+			ensure!(best_finalized_number < number, <Error<T, I>>::OldHeader);
+
+			let current_set_id = <CurrentAuthoritySet<T, I>>::get().set_id;
+			ensure!(current_set_id < authority_set.set_id, <Error<T, I>>::InvalidAuthoritySetId);
+			ensure!(
+				VoterSet::new(authority_set.authorities.iter().cloned()).is_some(),
+				<Error<T, I>>::InvalidAuthoritySet
+			);
+
+			let set_id = authority_set.set_id;
+			insert_header::<T, I>(*header, hash);
+			<CurrentAuthoritySet<T, I>>::put(authority_set);
+			<PendingAuthoritySetChange<T, I>>::kill();
+
+			log::info!(
+				target: LOG_TARGET,
+				"Authority set has been reset to {} at header {:?}/{:?}",
+				set_id,
+				number,
+				hash,
+			);
+			Self::deposit_event(Event::AuthoritySetReset {
+				header_number: number,
+				header_hash: hash,
+				set_id,
+			});
+
+			Ok(().into())
+		}
+
 		/// Change `PalletOwner`.
 		///
 		/// May only be called either by root, or by `PalletOwner`.
@@ -288,11 +458,38 @@ pub mod pallet {
 	pub type ImportedHeaders<T: Config<I>, I: 'static = ()> =
 		StorageMap<_, Identity, BridgedBlockHash<T, I>, BridgedHeader<T, I>>;
 
+	/// Hashes of headers which have been imported into the pallet, indexed by header number.
+	///
+	/// Entries are pruned together with entries of the `ImportedHeaders` map.
+	#[pallet::storage]
+	pub type ImportedHashesByNumber<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, BridgedBlockNumber<T, I>, BridgedBlockHash<T, I>>;
+
 	/// The current GRANDPA Authority set.
 	#[pallet::storage]
 	pub(super) type CurrentAuthoritySet<T: Config<I>, I: 'static = ()> =
 		StorageValue<_, bp_header_chain::AuthoritySet, ValueQuery>;
 
+	/// Evidence of GRANDPA equivocations, reported by the `submit_equivocation_proof` call.
+	///
+	/// We only keep single evidence for every authority set.
+	#[pallet::storage]
+	pub type Equivocations<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		sp_finality_grandpa::SetId,
+		bp_header_chain::EquivocationEvidence<BridgedBlockNumber<T, I>, BridgedBlockHash<T, I>>,
+	>;
+
+	/// Authority set change that has been scheduled with a non-zero delay and is waiting
+	/// for the enactment header to be imported.
+	#[pallet::storage]
+	pub(super) type PendingAuthoritySetChange<T: Config<I>, I: 'static = ()> = StorageValue<
+		_,
+		bp_header_chain::ScheduledAuthoritySetChange<BridgedBlockNumber<T, I>>,
+		OptionQuery,
+	>;
+
 	/// Optional pallet owner.
 	///
 	/// Pallet owner has a right to halt all pallet operations and then resume it. If it is
@@ -342,6 +539,29 @@ pub mod pallet {
 		}
 	}
 
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config<I>, I: 'static = ()> {
+		/// Current authority set and best finalized header have been replaced by the pallet
+		/// owner or root.
+		AuthoritySetReset {
+			/// Number of the new best finalized header.
+			header_number: BridgedBlockNumber<T, I>,
+			/// Hash of the new best finalized header.
+			header_hash: BridgedBlockHash<T, I>,
+			/// Identifier of the new authority set.
+			set_id: sp_finality_grandpa::SetId,
+		},
+		/// Authorities of the bridged chain have finalized conflicting headers. The pallet has
+		/// been halted.
+		EquivocationDetected {
+			/// Identifier of the authority set that has finalized conflicting headers.
+			set_id: sp_finality_grandpa::SetId,
+			/// Authorities that have signed precommits for both conflicting headers.
+			equivocators: Vec<sp_finality_grandpa::AuthorityId>,
+		},
+	}
+
 	#[pallet::error]
 	pub enum Error<T, I = ()> {
 		/// The given justification is invalid for the given header.
@@ -352,66 +572,206 @@ pub mod pallet {
 		TooManyRequests,
 		/// The header being imported is older than the best finalized header known to the pallet.
 		OldHeader,
+		/// The batch of finality proofs is empty.
+		NoFinalityProofs,
 		/// The header is unknown to the pallet.
 		UnknownHeader,
-		/// The scheduled authority set change found in the header is unsupported by the pallet.
-		///
-		/// This is the case for non-standard (e.g forced) authority set changes.
-		UnsupportedScheduledChange,
 		/// The pallet is not yet initialized.
 		NotInitialized,
 		/// The pallet has already been initialized.
 		AlreadyInitialized,
 		/// The storage proof doesn't contains storage root. So it is invalid for given header.
 		StorageRootMismatch,
+		/// The header schedules authority set change while another change is still pending.
+		ScheduledChangeAlreadyPending,
+		/// The header signals forced authority set change, which can't be verified by the pallet.
+		UnsupportedForcedChange,
+		/// The new authority set id must be larger than the current authority set id.
+		InvalidAuthoritySetId,
+		/// The equivocation proof must contain justifications for different headers with the same
+		/// number.
+		InvalidEquivocationProof,
+		/// Equivocation of the same authority set has already been reported.
+		EquivocationAlreadyReported,
 		/// Error generated by the `OwnedBridgeModule` trait.
 		BridgeModule(bp_runtime::OwnedBridgeModuleError),
 	}
 
+	/// Verify finality proof of the header and import the header to the storage.
+	///
+	/// Returns true if the header is mandatory, i.e. it has enacted or scheduled authority set
+	/// change.
+	pub(crate) fn import_finality_proof<T: Config<I>, I: 'static>(
+		finality_target: BridgedHeader<T, I>,
+		justification: GrandpaJustification<BridgedHeader<T, I>>,
+	) -> Result<bool, sp_runtime::DispatchError> {
+		ensure!(
+			Pallet::<T, I>::request_count() < T::MaxRequests::get(),
+			<Error<T, I>>::TooManyRequests
+		);
+
+		let (hash, number) = (finality_target.hash(), *finality_target.number());
+		log::trace!(
+			target: LOG_TARGET,
+			"Going to try and finalize header {:?}",
+			finality_target
+		);
+
+		let best_finalized = BestFinalized::<T, I>::get();
+		let best_finalized =
+			best_finalized.and_then(|(_, hash)| ImportedHeaders::<T, I>::get(hash));
+		let best_finalized = match best_finalized {
+			Some(best_finalized) => best_finalized,
+			None => {
+				log::error!(
+					target: LOG_TARGET,
+					"Cannot finalize header {:?} because pallet is not yet initialized",
+					finality_target,
+				);
+				fail!(<Error<T, I>>::NotInitialized);
+			},
+		};
+
+		// We do a quick check here to ensure that our header chain is making progress and isn't
+		// "travelling back in time" (which could be indicative of something bad, e.g a
+		// hard-fork).
+		ensure!(*best_finalized.number() < number, <Error<T, I>>::OldHeader);
+
+		let authority_set = expected_authority_set::<T, I>(number);
+		verify_justification::<T, I>(&justification, hash, number, authority_set)?;
+
+		let is_authorities_set_updated = try_enact_authority_change::<T, I>(&finality_target)?;
+		<RequestCount<T, I>>::mutate(|count| *count += 1);
+		insert_header::<T, I>(finality_target, hash);
+		log::info!(
+			target: LOG_TARGET,
+			"Successfully imported finalized header with hash {:?}!",
+			hash
+		);
+
+		Ok(is_authorities_set_updated)
+	}
+
+	/// Returns authority set that is expected to sign justification of the header with given
+	/// number.
+	///
+	/// Normally this is the current authority set. But if there's a pending change that should
+	/// have been enacted by some header in-between the best finalized header and the given
+	/// header, then GRANDPA on the bridged chain has already switched to the next set, so it is
+	/// the next set that is finalizing the header.
+	pub(crate) fn expected_authority_set<T: Config<I>, I: 'static>(
+		number: BridgedBlockNumber<T, I>,
+	) -> bp_header_chain::AuthoritySet {
+		let current_authority_set = <CurrentAuthoritySet<T, I>>::get();
+		match <PendingAuthoritySetChange<T, I>>::get() {
+			Some(change) if change.enact_at < number => bp_header_chain::AuthoritySet::new(
+				change.authorities,
+				current_authority_set.set_id + 1,
+			),
+			_ => current_authority_set,
+		}
+	}
+
 	/// Check the given header for a GRANDPA scheduled authority set change. If a change
-	/// is found it will be enacted immediately.
+	/// is found and its delay is zero, it will be enacted immediately. Otherwise, it is
+	/// saved in the storage and enacted later, when header at (or after) the enactment
+	/// block is imported.
+	///
+	/// The header may also enact previously scheduled change.
 	///
-	/// This function does not support forced changes, or scheduled changes with delays
-	/// since these types of changes are indicative of abnormal behavior from GRANDPA.
+	/// Headers that signal forced changes are rejected. Scheduling new change while other
+	/// change is pending is not supported either.
 	///
-	/// Returned value will indicate if a change was enacted or not.
+	/// Returned value will indicate if a change was enacted or scheduled.
 	pub(crate) fn try_enact_authority_change<T: Config<I>, I: 'static>(
 		header: &BridgedHeader<T, I>,
-		current_set_id: sp_finality_grandpa::SetId,
 	) -> Result<bool, sp_runtime::DispatchError> {
-		let mut change_enacted = false;
-
-		// We don't support forced changes - at that point governance intervention is required.
+		// Forced change means that GRANDPA on the bridged chain has been stalled. The next set
+		// will finalize headers on top of the `median_last_finalized` header, which may not be
+		// finalized by the current set. We can't verify that, so the bridge must be recovered
+		// by the pallet owner (see `reset_authority_set`).
 		ensure!(
 			super::find_forced_change(header).is_none(),
-			<Error<T, I>>::UnsupportedScheduledChange
+			<Error<T, I>>::UnsupportedForcedChange
 		);
 
+		let number = *header.number();
+		let mut authority_set_updated = false;
+
+		// Enact pending change if its enactment block is finalized. If the header is a descendant
+		// of the enactment block, then it is signed by the next set and `expected_authority_set`
+		// has already accounted for that.
+		if let Some(pending_change) = <PendingAuthoritySetChange<T, I>>::get() {
+			if pending_change.enact_at <= number {
+				<PendingAuthoritySetChange<T, I>>::kill();
+				enact_authority_set::<T, I>(pending_change.authorities);
+				authority_set_updated = true;
+			}
+		}
+
 		if let Some(change) = super::find_scheduled_change(header) {
-			// GRANDPA only includes a `delay` for forced changes, so this isn't valid.
-			ensure!(change.delay == Zero::zero(), <Error<T, I>>::UnsupportedScheduledChange);
+			// GRANDPA on the bridged chain doesn't allow scheduling changes while another change
+			// is pending, so we don't support it either.
+			ensure!(
+				!<PendingAuthoritySetChange<T, I>>::exists(),
+				<Error<T, I>>::ScheduledChangeAlreadyPending
+			);
 
-			// TODO [#788]: Stop manually increasing the `set_id` here.
-			let next_authorities = bp_header_chain::AuthoritySet {
-				authorities: change.next_authorities,
-				set_id: current_set_id + 1,
-			};
+			schedule_authority_set_change::<T, I>(number, change);
+			authority_set_updated = true;
+		}
 
-			// Since our header schedules a change and we know the delay is 0, it must also enact
-			// the change.
-			<CurrentAuthoritySet<T, I>>::put(&next_authorities);
-			change_enacted = true;
+		Ok(authority_set_updated)
+	}
 
-			log::info!(
-				target: LOG_TARGET,
-				"Transitioned from authority set {} to {}! New authorities are: {:?}",
-				current_set_id,
-				current_set_id + 1,
-				next_authorities,
-			);
+	/// Enact the authority set change immediately if its delay is zero. Otherwise, save it
+	/// in the storage.
+	fn schedule_authority_set_change<T: Config<I>, I: 'static>(
+		number: BridgedBlockNumber<T, I>,
+		change: sp_finality_grandpa::ScheduledChange<BridgedBlockNumber<T, I>>,
+	) {
+		if change.delay.is_zero() {
+			// Since our header schedules a change and we know the delay is 0, it must also
+			// enact the change.
+			enact_authority_set::<T, I>(change.next_authorities);
+			return
+		}
+
+		let enact_at = number.saturating_add(change.delay);
+		log::info!(
+			target: LOG_TARGET,
+			"Header {:?} has scheduled authority set change at {:?}. Next authorities are: {:?}",
+			number,
+			enact_at,
+			change.next_authorities,
+		);
+
+		<PendingAuthoritySetChange<T, I>>::put(bp_header_chain::ScheduledAuthoritySetChange {
+			authorities: change.next_authorities,
+			enact_at,
+		});
+	}
+
+	/// Replace current authority set with the next one.
+	fn enact_authority_set<T: Config<I>, I: 'static>(
+		next_authorities: sp_finality_grandpa::AuthorityList,
+	) {
+		let current_set_id = <CurrentAuthoritySet<T, I>>::get().set_id;
+		// TODO [#788]: Stop manually increasing the `set_id` here.
+		let next_authorities = bp_header_chain::AuthoritySet {
+			authorities: next_authorities,
+			set_id: current_set_id + 1,
 		};
 
-		Ok(change_enacted)
+		<CurrentAuthoritySet<T, I>>::put(&next_authorities);
+
+		log::info!(
+			target: LOG_TARGET,
+			"Transitioned from authority set {} to {}! New authorities are: {:?}",
+			current_set_id,
+			current_set_id + 1,
+			next_authorities,
+		);
 	}
 
 	/// Verify a GRANDPA justification (finality proof) for a given header.
@@ -459,15 +819,24 @@ pub mod pallet {
 	) {
 		let index = <ImportedHashesPointer<T, I>>::get();
 		let pruning = <ImportedHashes<T, I>>::try_get(index);
-		<BestFinalized<T, I>>::put((*header.number(), hash));
+		let number = *header.number();
+		<BestFinalized<T, I>>::put((number, hash));
 		<ImportedHeaders<T, I>>::insert(hash, header);
+		<ImportedHashesByNumber<T, I>>::insert(number, hash);
 		<ImportedHashes<T, I>>::insert(index, hash);
 
 		// Update ring buffer pointer and remove old header.
 		<ImportedHashesPointer<T, I>>::put((index + 1) % T::HeadersToKeep::get());
 		if let Ok(hash) = pruning {
 			log::debug!(target: LOG_TARGET, "Pruning old header: {:?}.", hash);
-			<ImportedHeaders<T, I>>::remove(hash);
+			if let Some(pruned_header) = <ImportedHeaders<T, I>>::take(hash) {
+				// the number may already be occupied by other header (e.g. after
+				// re-initialization), so we only remove our own entry
+				let pruned_number = *pruned_header.number();
+				if <ImportedHashesByNumber<T, I>>::get(pruned_number) == Some(hash) {
+					<ImportedHashesByNumber<T, I>>::remove(pruned_number);
+				}
+			}
 		}
 	}
 
@@ -486,6 +855,7 @@ pub mod pallet {
 
 		let authority_set = bp_header_chain::AuthoritySet::new(authority_list, set_id);
 		<CurrentAuthoritySet<T, I>>::put(authority_set);
+		<PendingAuthoritySetChange<T, I>>::kill();
 
 		<PalletOperatingMode<T, I>>::put(operating_mode);
 	}
@@ -529,14 +899,62 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		<ImportedHeaders<T, I>>::contains_key(hash)
 	}
 
+	/// Get hash of the imported header with given number.
+	///
+	/// Returns `None` if header with given number has not been imported or has already been
+	/// pruned. Note that the pallet only imports some of finalized headers, so this doesn't
+	/// mean that the header with given number is not finalized.
+	pub fn finalized_header_at(number: BridgedBlockNumber<T, I>) -> Option<BridgedBlockHash<T, I>> {
+		<ImportedHashesByNumber<T, I>>::get(number)
+	}
+
+	/// Get numbers and hashes of all imported headers in the given inclusive range.
+	///
+	/// The resulting vector is ordered by header number.
+	pub fn imported_headers_range(
+		from: BridgedBlockNumber<T, I>,
+		to: BridgedBlockNumber<T, I>,
+	) -> Vec<(BridgedBlockNumber<T, I>, BridgedBlockHash<T, I>)> {
+		if from > to {
+			return Vec::new()
+		}
+
+		// if the range is larger than the number of headers we keep, it is cheaper to read all
+		// entries of the index
+		if to - from >= T::HeadersToKeep::get().into() {
+			let mut headers = <ImportedHashesByNumber<T, I>>::iter()
+				.filter(|(number, _)| *number >= from && *number <= to)
+				.collect::<Vec<_>>();
+			headers.sort_by_key(|(number, _)| *number);
+			return headers
+		}
+
+		let mut headers = Vec::new();
+		let mut number = from;
+		loop {
+			if let Some(hash) = <ImportedHashesByNumber<T, I>>::get(number) {
+				headers.push((number, hash));
+			}
+			if number == to {
+				break
+			}
+			number += One::one();
+		}
+		headers
+	}
+
 	/// Verify that the passed storage proof is valid, given it is crafted using
 	/// known finalized header. If the proof is valid, then the `parse` callback
 	/// is called and the function returns its result.
+	///
+	/// Proofs are not accepted while the pallet is halted, because the bridged chain finality
+	/// may be compromised (e.g. after equivocation has been reported).
 	pub fn parse_finalized_storage_proof<R>(
 		hash: BridgedBlockHash<T, I>,
 		storage_proof: sp_trie::StorageProof,
 		parse: impl FnOnce(bp_runtime::StorageProofChecker<BridgedBlockHasher<T, I>>) -> R,
 	) -> Result<R, sp_runtime::DispatchError> {
+		Self::ensure_not_halted().map_err(Error::<T, I>::BridgeModule)?;
 		let header = <ImportedHeaders<T, I>>::get(hash).ok_or(Error::<T, I>::UnknownHeader)?;
 		let storage_proof_checker =
 			bp_runtime::StorageProofChecker::new(*header.state_root(), storage_proof)
@@ -582,6 +1000,16 @@ pub(crate) fn find_forced_change<H: HeaderT>(
 	header.digest().convert_first(|l| l.try_to(id).and_then(filter_log))
 }
 
+/// Returns weight of the single header import, given its justification.
+pub(crate) fn finality_proof_weight<T: Config<I>, I: 'static>(
+	justification: &GrandpaJustification<BridgedHeader<T, I>>,
+) -> frame_support::weights::Weight {
+	T::WeightInfo::submit_finality_proof(
+		justification.commit.precommits.len().try_into().unwrap_or(u32::MAX),
+		justification.votes_ancestries.len().try_into().unwrap_or(u32::MAX),
+	)
+}
+
 /// (Re)initialize bridge with given header for using it in `pallet-bridge-messages` benchmarks.
 #[cfg(feature = "runtime-benchmarks")]
 pub fn initialize_for_benchmarks<T: Config<I>, I: 'static>(header: BridgedHeader<T, I>) {
@@ -597,7 +1025,9 @@ pub fn initialize_for_benchmarks<T: Config<I>, I: 'static>(header: BridgedHeader
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::mock::{run_test, test_header, Origin, TestHeader, TestNumber, TestRuntime};
+	use crate::mock::{
+		run_test, test_header, Event as TestEvent, Origin, TestHeader, TestNumber, TestRuntime,
+	};
 	use bp_runtime::BasicOperatingMode;
 	use bp_test_utils::{
 		authority_list, generate_owned_bridge_module_tests, make_default_justification,
@@ -608,6 +1038,7 @@ mod tests {
 		assert_err, assert_noop, assert_ok, storage::generator::StorageValue,
 		weights::PostDispatchInfo,
 	};
+	use frame_system::{EventRecord, Phase};
 	use sp_runtime::{Digest, DigestItem, DispatchError};
 
 	fn initialize_substrate_bridge() {
@@ -643,6 +1074,11 @@ mod tests {
 		)
 	}
 
+	fn get_ready_for_events() {
+		frame_system::Pallet::<TestRuntime>::set_block_number(1);
+		frame_system::Pallet::<TestRuntime>::reset_events();
+	}
+
 	fn next_block() {
 		use frame_support::traits::OnInitialize;
 
@@ -661,6 +1097,18 @@ mod tests {
 		Digest { logs: vec![DigestItem::Consensus(GRANDPA_ENGINE_ID, consensus_log.encode())] }
 	}
 
+	fn make_next_set_justification(
+		header: &TestHeader,
+		set_id: sp_finality_grandpa::SetId,
+	) -> GrandpaJustification<TestHeader> {
+		make_justification_for_header(JustificationGeneratorParams {
+			header: header.clone(),
+			set_id,
+			authorities: vec![(ALICE, 1), (BOB, 1)],
+			..Default::default()
+		})
+	}
+
 	fn forced_change_log(delay: u64) -> Digest {
 		let consensus_log = ConsensusLog::<TestNumber>::ForcedChange(
 			delay,
@@ -886,51 +1334,678 @@ mod tests {
 	}
 
 	#[test]
-	fn importing_header_rejects_header_with_scheduled_change_delay() {
+	fn importing_header_schedules_delayed_authority_set_change() {
 		run_test(|| {
 			initialize_substrate_bridge();
 
 			// Need to update the header digest to indicate that our header signals an authority set
-			// change. However, the change doesn't happen until the next block.
+			// change. However, the change doesn't happen until the header #4.
 			let mut header = test_header(2);
-			header.digest = change_log(1);
+			header.digest = change_log(2);
 
 			// Create a valid justification for the header
 			let justification = make_default_justification(&header);
 
-			// Should not be allowed to import this header
-			assert_err!(
+			// The header must be imported for free, because it is mandatory
+			assert_ok!(
 				Pallet::<TestRuntime>::submit_finality_proof(
 					Origin::signed(1),
 					Box::new(header),
 					justification
 				),
-				<Error<TestRuntime>>::UnsupportedScheduledChange
+				PostDispatchInfo {
+					actual_weight: None,
+					pays_fee: frame_support::weights::Pays::No,
+				},
+			);
+
+			// Make sure that the authority set is not changed, but the change is pending
+			assert_eq!(
+				<CurrentAuthoritySet<TestRuntime>>::get(),
+				bp_header_chain::AuthoritySet::new(authority_list(), 1),
+			);
+			assert_eq!(
+				<PendingAuthoritySetChange<TestRuntime>>::get(),
+				Some(bp_header_chain::ScheduledAuthoritySetChange {
+					authorities: vec![(ALICE.into(), 1), (BOB.into(), 1)],
+					enact_at: 4,
+				}),
 			);
 		})
 	}
 
 	#[test]
-	fn importing_header_rejects_header_with_forced_changes() {
+	fn importing_header_enacts_pending_authority_set_change() {
 		run_test(|| {
 			initialize_substrate_bridge();
 
-			// Need to update the header digest to indicate that it signals a forced authority set
-			// change.
 			let mut header = test_header(2);
-			header.digest = forced_change_log(0);
+			header.digest = change_log(2);
+			let justification = make_default_justification(&header);
+			assert_ok!(Pallet::<TestRuntime>::submit_finality_proof(
+				Origin::signed(1),
+				Box::new(header),
+				justification
+			));
 
-			// Create a valid justification for the header
+			// header before the enactment block is still finalized by the current set
+			assert_ok!(
+				submit_finality_proof(3),
+				PostDispatchInfo {
+					actual_weight: None,
+					pays_fee: frame_support::weights::Pays::Yes,
+				},
+			);
+			assert!(<PendingAuthoritySetChange<TestRuntime>>::exists());
+
+			// the enactment block is also finalized by the current set, but it enacts the change
+			next_block();
+			assert_ok!(
+				submit_finality_proof(4),
+				PostDispatchInfo {
+					actual_weight: None,
+					pays_fee: frame_support::weights::Pays::No,
+				},
+			);
+			assert!(!<PendingAuthoritySetChange<TestRuntime>>::exists());
+			assert_eq!(
+				<CurrentAuthoritySet<TestRuntime>>::get(),
+				bp_header_chain::AuthoritySet::new(vec![(ALICE.into(), 1), (BOB.into(), 1)], 2),
+			);
+
+			// and following headers are finalized by the next set
+			next_block();
+			let header = test_header(5);
+			let justification = make_next_set_justification(&header, 2);
+			assert_ok!(Pallet::<TestRuntime>::submit_finality_proof(
+				Origin::signed(1),
+				Box::new(header),
+				justification
+			));
+		})
+	}
+
+	#[test]
+	fn importing_descendant_of_enactment_header_enacts_pending_authority_set_change() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			let mut header = test_header(2);
+			header.digest = change_log(2);
+			let justification = make_default_justification(&header);
+			assert_ok!(Pallet::<TestRuntime>::submit_finality_proof(
+				Origin::signed(1),
+				Box::new(header),
+				justification
+			));
+
+			// header #5 is a descendant of the enactment header #4, so it can't be finalized by
+			// the current set
+			assert_err!(submit_finality_proof(5), <Error<TestRuntime>>::InvalidJustification);
+
+			// but it is finalized by the next set
+			let header = test_header(5);
+			let justification = make_next_set_justification(&header, 2);
+			assert_ok!(
+				Pallet::<TestRuntime>::submit_finality_proof(
+					Origin::signed(1),
+					Box::new(header),
+					justification
+				),
+				PostDispatchInfo {
+					actual_weight: None,
+					pays_fee: frame_support::weights::Pays::No,
+				},
+			);
+			assert!(!<PendingAuthoritySetChange<TestRuntime>>::exists());
+			assert_eq!(
+				<CurrentAuthoritySet<TestRuntime>>::get(),
+				bp_header_chain::AuthoritySet::new(vec![(ALICE.into(), 1), (BOB.into(), 1)], 2),
+			);
+		})
+	}
+
+	#[test]
+	fn importing_header_rejects_change_scheduled_while_other_change_is_pending() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			let mut header = test_header(2);
+			header.digest = change_log(5);
+			let justification = make_default_justification(&header);
+			assert_ok!(Pallet::<TestRuntime>::submit_finality_proof(
+				Origin::signed(1),
+				Box::new(header),
+				justification
+			));
+
+			// the change at #2 is scheduled to be enacted at #7, so header #3 can't schedule
+			// another change (neither delayed, nor immediate)
+			for delay in [0, 1, 10] {
+				let mut header = test_header(3);
+				header.digest = change_log(delay);
+				let justification = make_default_justification(&header);
+				assert_noop!(
+					Pallet::<TestRuntime>::submit_finality_proof(
+						Origin::signed(1),
+						Box::new(header),
+						justification
+					),
+					<Error<TestRuntime>>::ScheduledChangeAlreadyPending
+				);
+			}
+		})
+	}
+
+	#[test]
+	fn importing_enactment_header_may_schedule_next_authority_set_change() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			let mut header = test_header(2);
+			header.digest = change_log(2);
+			let justification = make_default_justification(&header);
+			assert_ok!(Pallet::<TestRuntime>::submit_finality_proof(
+				Origin::signed(1),
+				Box::new(header),
+				justification
+			));
+
+			// header #4 enacts change scheduled at #2 and schedules the next change at #7
+			let mut header = test_header(4);
+			header.digest = change_log(3);
+			let justification = make_default_justification(&header);
+			assert_ok!(Pallet::<TestRuntime>::submit_finality_proof(
+				Origin::signed(1),
+				Box::new(header),
+				justification
+			));
+			assert_eq!(<CurrentAuthoritySet<TestRuntime>>::get().set_id, 2);
+			assert_eq!(
+				<PendingAuthoritySetChange<TestRuntime>>::get().map(|change| change.enact_at),
+				Some(7),
+			);
+
+			// header #7 is finalized by the set#2 and enacts set#3
+			next_block();
+			let header = test_header(7);
+			let justification = make_next_set_justification(&header, 2);
+			assert_ok!(Pallet::<TestRuntime>::submit_finality_proof(
+				Origin::signed(1),
+				Box::new(header),
+				justification
+			));
+			assert_eq!(<CurrentAuthoritySet<TestRuntime>>::get().set_id, 3);
+			assert!(!<PendingAuthoritySetChange<TestRuntime>>::exists());
+		})
+	}
+
+	#[test]
+	fn initialization_removes_pending_authority_set_change() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			let mut header = test_header(2);
+			header.digest = change_log(2);
+			let justification = make_default_justification(&header);
+			assert_ok!(Pallet::<TestRuntime>::submit_finality_proof(
+				Origin::signed(1),
+				Box::new(header),
+				justification
+			));
+			assert!(<PendingAuthoritySetChange<TestRuntime>>::exists());
+
+			BestFinalized::<TestRuntime>::kill();
+			initialize_substrate_bridge();
+			assert!(!<PendingAuthoritySetChange<TestRuntime>>::exists());
+		})
+	}
+
+	#[test]
+	fn importing_header_rejects_forced_change() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			// header #2 schedules regular change
+			let mut header = test_header(2);
+			header.digest = change_log(10);
 			let justification = make_default_justification(&header);
+			assert_ok!(Pallet::<TestRuntime>::submit_finality_proof(
+				Origin::signed(1),
+				Box::new(header),
+				justification
+			));
 
-			// Should not be allowed to import this header
+			// header #3 signals forced change, which can't be verified by the pallet
+			let mut header = test_header(3);
+			header.digest = forced_change_log(0);
+			let justification = make_default_justification(&header);
 			assert_err!(
 				Pallet::<TestRuntime>::submit_finality_proof(
 					Origin::signed(1),
 					Box::new(header),
 					justification
 				),
-				<Error<TestRuntime>>::UnsupportedScheduledChange
+				<Error<TestRuntime>>::UnsupportedForcedChange
+			);
+
+			// neither current set, nor pending change are affected
+			assert_eq!(<CurrentAuthoritySet<TestRuntime>>::get().set_id, 1);
+			assert_eq!(
+				<PendingAuthoritySetChange<TestRuntime>>::get().map(|change| change.enact_at),
+				Some(12),
+			);
+			assert_eq!(<BestFinalized<TestRuntime>>::get().map(|(number, _)| number), Some(2));
+		})
+	}
+
+	#[test]
+	fn batch_imports_headers_and_enacts_authority_set_changes_in_between() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			// header#2 enacts authority set#2 and header#3 is signed by this new set
+			let mut header2 = test_header(2);
+			header2.digest = change_log(0);
+			let justification2 = make_default_justification(&header2);
+			let header3 = test_header(3);
+			let justification3 = make_next_set_justification(&header3, 2);
+			let expected_weight = finality_proof_weight::<TestRuntime, ()>(&justification2) +
+				finality_proof_weight::<TestRuntime, ()>(&justification3);
+
+			assert_ok!(
+				Pallet::<TestRuntime>::submit_finality_proofs(
+					Origin::signed(1),
+					vec![(header2.clone(), justification2), (header3.clone(), justification3)],
+				),
+				PostDispatchInfo {
+					actual_weight: Some(expected_weight),
+					pays_fee: frame_support::weights::Pays::Yes,
+				},
+			);
+
+			assert_eq!(<BestFinalized<TestRuntime>>::get(), Some((3, header3.hash())));
+			assert!(<ImportedHeaders<TestRuntime>>::contains_key(header2.hash()));
+			assert!(<ImportedHeaders<TestRuntime>>::contains_key(header3.hash()));
+			assert_eq!(<CurrentAuthoritySet<TestRuntime>>::get().set_id, 2);
+			assert_eq!(Pallet::<TestRuntime>::request_count(), 2);
+		})
+	}
+
+	#[test]
+	fn batch_of_mandatory_headers_is_free() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			let mut header2 = test_header(2);
+			header2.digest = change_log(0);
+			let justification2 = make_default_justification(&header2);
+			let mut header3 = test_header(3);
+			header3.digest = change_log(0);
+			let justification3 = make_next_set_justification(&header3, 2);
+
+			let result = Pallet::<TestRuntime>::submit_finality_proofs(
+				Origin::signed(1),
+				vec![(header2, justification2), (header3, justification3)],
+			);
+			assert_ok!(result);
+			assert_eq!(result.unwrap().pays_fee, frame_support::weights::Pays::No);
+			assert_eq!(<CurrentAuthoritySet<TestRuntime>>::get().set_id, 3);
+		})
+	}
+
+	#[test]
+	fn batch_is_rejected_atomically_and_unused_weight_is_refunded() {
+		use frame_support::dispatch::Dispatchable;
+
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			let header1 = test_header(1);
+			let justification1 = make_default_justification(&header1);
+			let header2 = test_header(2);
+			let mut justification2 = make_default_justification(&header2);
+			justification2.round = 42;
+			let header3 = test_header(3);
+			let justification3 = make_default_justification(&header3);
+			let expected_weight = finality_proof_weight::<TestRuntime, ()>(&justification1) +
+				finality_proof_weight::<TestRuntime, ()>(&justification2);
+
+			let call = crate::mock::Call::Grandpa(Call::<TestRuntime>::submit_finality_proofs {
+				finality_proofs: vec![
+					(header1, justification1),
+					(header2, justification2),
+					(header3, justification3),
+				],
+			});
+			assert_eq!(
+				call.dispatch(Origin::signed(1)),
+				Err(sp_runtime::DispatchErrorWithPostInfo {
+					post_info: PostDispatchInfo {
+						actual_weight: Some(expected_weight),
+						pays_fee: frame_support::weights::Pays::Yes,
+					},
+					error: <Error<TestRuntime>>::InvalidJustification.into(),
+				}),
+			);
+
+			// nothing has been imported
+			assert_eq!(<BestFinalized<TestRuntime>>::get(), Some((0, test_header(0).hash())));
+			assert!(!<ImportedHeaders<TestRuntime>>::contains_key(test_header(1).hash()));
+			assert_eq!(Pallet::<TestRuntime>::request_count(), 0);
+		})
+	}
+
+	#[test]
+	fn batch_is_rejected_if_empty() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			assert_noop!(
+				Pallet::<TestRuntime>::submit_finality_proofs(Origin::signed(1), vec![]),
+				<Error<TestRuntime>>::NoFinalityProofs,
+			);
+		})
+	}
+
+	#[test]
+	fn batch_headers_count_towards_request_count() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			let finality_proofs = (1..4)
+				.map(|number| {
+					let header = test_header(number);
+					let justification = make_default_justification(&header);
+					(header, justification)
+				})
+				.collect::<Vec<_>>();
+			assert_err!(
+				Pallet::<TestRuntime>::submit_finality_proofs(Origin::signed(1), finality_proofs)
+					.map_err(|e| e.error),
+				<Error<TestRuntime>>::TooManyRequests,
+			);
+		})
+	}
+
+	#[test]
+	fn reset_authority_set_works() {
+		run_test(|| {
+			initialize_substrate_bridge();
+			assert_ok!(submit_finality_proof(1));
+			let imported_header = test_header(1);
+
+			let mut header = test_header(2);
+			header.digest = change_log(5);
+			let justification = make_default_justification(&header);
+			assert_ok!(Pallet::<TestRuntime>::submit_finality_proof(
+				Origin::signed(1),
+				Box::new(header),
+				justification
+			));
+			get_ready_for_events();
+
+			let header = test_header(10);
+			let next_authority_set =
+				bp_header_chain::AuthoritySet::new(vec![(ALICE.into(), 1), (BOB.into(), 1)], 5);
+			assert_ok!(Pallet::<TestRuntime>::reset_authority_set(
+				Origin::root(),
+				Box::new(header.clone()),
+				next_authority_set.clone(),
+			));
+
+			assert_eq!(<BestFinalized<TestRuntime>>::get(), Some((10, header.hash())));
+			assert_eq!(<CurrentAuthoritySet<TestRuntime>>::get(), next_authority_set);
+			assert!(!<PendingAuthoritySetChange<TestRuntime>>::exists());
+			assert!(Pallet::<TestRuntime>::is_known_header(imported_header.hash()));
+			assert_eq!(
+				frame_system::Pallet::<TestRuntime>::events(),
+				vec![EventRecord {
+					phase: Phase::Initialization,
+					event: TestEvent::Grandpa(Event::AuthoritySetReset {
+						header_number: 10,
+						header_hash: header.hash(),
+						set_id: 5,
+					}),
+					topics: vec![],
+				}],
+			);
+
+			// and the next header is finalized by the new set
+			next_block();
+			let header = test_header(11);
+			let justification = make_next_set_justification(&header, 5);
+			assert_ok!(Pallet::<TestRuntime>::submit_finality_proof(
+				Origin::signed(1),
+				Box::new(header),
+				justification
+			));
+		})
+	}
+
+	#[test]
+	fn reset_authority_set_requires_root_or_owner() {
+		run_test(|| {
+			initialize_substrate_bridge();
+			PalletOwner::<TestRuntime>::put(2);
+
+			let next_authority_set = bp_header_chain::AuthoritySet::new(authority_list(), 2);
+			assert_noop!(
+				Pallet::<TestRuntime>::reset_authority_set(
+					Origin::signed(1),
+					Box::new(test_header(10)),
+					next_authority_set.clone(),
+				),
+				DispatchError::BadOrigin,
+			);
+			assert_ok!(Pallet::<TestRuntime>::reset_authority_set(
+				Origin::signed(2),
+				Box::new(test_header(10)),
+				next_authority_set,
+			));
+		})
+	}
+
+	#[test]
+	fn reset_authority_set_fails_if_not_initialized() {
+		run_test(|| {
+			assert_noop!(
+				Pallet::<TestRuntime>::reset_authority_set(
+					Origin::root(),
+					Box::new(test_header(10)),
+					bp_header_chain::AuthoritySet::new(authority_list(), 2),
+				),
+				<Error<TestRuntime>>::NotInitialized,
+			);
+		})
+	}
+
+	#[test]
+	fn reset_authority_set_rejects_old_header() {
+		run_test(|| {
+			initialize_substrate_bridge();
+			assert_ok!(submit_finality_proof(5));
+
+			assert_noop!(
+				Pallet::<TestRuntime>::reset_authority_set(
+					Origin::root(),
+					Box::new(test_header(5)),
+					bp_header_chain::AuthoritySet::new(authority_list(), 2),
+				),
+				<Error<TestRuntime>>::OldHeader,
+			);
+		})
+	}
+
+	#[test]
+	fn reset_authority_set_rejects_invalid_authority_set() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			// set id must be increased
+			assert_noop!(
+				Pallet::<TestRuntime>::reset_authority_set(
+					Origin::root(),
+					Box::new(test_header(10)),
+					bp_header_chain::AuthoritySet::new(authority_list(), 1),
+				),
+				<Error<TestRuntime>>::InvalidAuthoritySetId,
+			);
+
+			// authorities must form a valid voter set
+			assert_noop!(
+				Pallet::<TestRuntime>::reset_authority_set(
+					Origin::root(),
+					Box::new(test_header(10)),
+					bp_header_chain::AuthoritySet::new(vec![], 2),
+				),
+				<Error<TestRuntime>>::InvalidAuthoritySet,
+			);
+		})
+	}
+
+	fn conflicting_header(number: TestNumber) -> TestHeader {
+		let mut header = test_header(number);
+		header.digest = Digest { logs: vec![DigestItem::Other(vec![42])] };
+		header
+	}
+
+	fn submit_equivocation_proof(
+		first_header: &TestHeader,
+		second_header: &TestHeader,
+	) -> frame_support::dispatch::DispatchResultWithPostInfo {
+		Pallet::<TestRuntime>::submit_equivocation_proof(
+			Origin::signed(1),
+			make_default_justification(first_header),
+			make_default_justification(second_header),
+		)
+	}
+
+	#[test]
+	fn equivocation_proof_halts_pallet() {
+		run_test(|| {
+			initialize_substrate_bridge();
+			get_ready_for_events();
+
+			let (first_header, second_header) = (test_header(2), conflicting_header(2));
+			assert_ok!(
+				submit_equivocation_proof(&first_header, &second_header),
+				PostDispatchInfo {
+					actual_weight: None,
+					pays_fee: frame_support::weights::Pays::No,
+				},
+			);
+
+			let equivocators: Vec<sp_finality_grandpa::AuthorityId> = authority_list()
+				.into_iter()
+				.map(|(id, _)| id)
+				.collect::<BTreeSet<_>>()
+				.into_iter()
+				.collect();
+			assert_eq!(PalletOperatingMode::<TestRuntime>::get(), BasicOperatingMode::Halted);
+			assert_eq!(
+				Equivocations::<TestRuntime>::get(1),
+				Some(bp_header_chain::EquivocationEvidence {
+					header_number: 2,
+					first_header_hash: first_header.hash(),
+					second_header_hash: second_header.hash(),
+					equivocators: equivocators.clone(),
+				}),
+			);
+			assert_eq!(
+				frame_system::Pallet::<TestRuntime>::events(),
+				vec![EventRecord {
+					phase: Phase::Initialization,
+					event: TestEvent::Grandpa(Event::EquivocationDetected {
+						set_id: 1,
+						equivocators
+					}),
+					topics: vec![],
+				}],
+			);
+
+			// the pallet doesn't import new headers and doesn't serve storage proofs anymore
+			assert_noop!(
+				submit_finality_proof(1),
+				Error::<TestRuntime>::BridgeModule(bp_runtime::OwnedBridgeModuleError::Halted)
+			);
+			assert_noop!(
+				Pallet::<TestRuntime>::parse_finalized_storage_proof(
+					test_header(0).hash(),
+					sp_trie::StorageProof::new(vec![]),
+					|_| (),
+				),
+				Error::<TestRuntime>::BridgeModule(bp_runtime::OwnedBridgeModuleError::Halted)
+			);
+		})
+	}
+
+	#[test]
+	fn equivocation_proof_is_rejected_if_headers_are_not_conflicting() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			// same header
+			assert_noop!(
+				submit_equivocation_proof(&test_header(2), &test_header(2)),
+				<Error<TestRuntime>>::InvalidEquivocationProof
+			);
+
+			// headers with different numbers
+			assert_noop!(
+				submit_equivocation_proof(&test_header(2), &conflicting_header(3)),
+				<Error<TestRuntime>>::InvalidEquivocationProof
+			);
+		})
+	}
+
+	#[test]
+	fn equivocation_proof_is_rejected_if_justification_is_invalid() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			let mut invalid_justification = make_default_justification(&conflicting_header(2));
+			invalid_justification.round = 42;
+			assert_noop!(
+				Pallet::<TestRuntime>::submit_equivocation_proof(
+					Origin::signed(1),
+					make_default_justification(&test_header(2)),
+					invalid_justification,
+				),
+				<Error<TestRuntime>>::InvalidJustification
+			);
+
+			// justifications signed by other authority set are also rejected
+			assert_noop!(
+				Pallet::<TestRuntime>::submit_equivocation_proof(
+					Origin::signed(1),
+					make_default_justification(&test_header(2)),
+					make_next_set_justification(&conflicting_header(2), 2),
+				),
+				<Error<TestRuntime>>::InvalidJustification
+			);
+		})
+	}
+
+	#[test]
+	fn equivocation_proof_is_rejected_if_not_initialized() {
+		run_test(|| {
+			assert_noop!(
+				submit_equivocation_proof(&test_header(2), &conflicting_header(2)),
+				<Error<TestRuntime>>::NotInitialized
+			);
+		})
+	}
+
+	#[test]
+	fn equivocation_of_the_same_set_is_reported_once() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			assert_ok!(submit_equivocation_proof(&test_header(2), &conflicting_header(2)));
+			assert_noop!(
+				submit_equivocation_proof(&test_header(3), &conflicting_header(3)),
+				<Error<TestRuntime>>::EquivocationAlreadyReported
 			);
 		})
 	}
@@ -1075,6 +2150,68 @@ mod tests {
 		})
 	}
 
+	#[test]
+	fn imported_headers_are_indexed_by_number() {
+		run_test(|| {
+			initialize_substrate_bridge();
+			assert_ok!(submit_finality_proof(1));
+			assert_ok!(submit_finality_proof(3));
+
+			assert_eq!(Pallet::<TestRuntime>::finalized_header_at(0), Some(test_header(0).hash()));
+			assert_eq!(Pallet::<TestRuntime>::finalized_header_at(1), Some(test_header(1).hash()));
+			assert_eq!(Pallet::<TestRuntime>::finalized_header_at(2), None);
+			assert_eq!(Pallet::<TestRuntime>::finalized_header_at(3), Some(test_header(3).hash()));
+			assert_eq!(Pallet::<TestRuntime>::finalized_header_at(4), None);
+		})
+	}
+
+	#[test]
+	fn number_index_is_pruned_together_with_headers() {
+		run_test(|| {
+			initialize_substrate_bridge();
+			for number in 1..=6 {
+				assert_ok!(submit_finality_proof(number));
+				next_block();
+			}
+
+			// we keep 5 headers, so headers #0 and #1 are pruned
+			assert_eq!(Pallet::<TestRuntime>::finalized_header_at(0), None);
+			assert_eq!(Pallet::<TestRuntime>::finalized_header_at(1), None);
+			for number in 2..=6 {
+				assert_eq!(
+					Pallet::<TestRuntime>::finalized_header_at(number),
+					Some(test_header(number).hash()),
+				);
+			}
+			assert_eq!(ImportedHashesByNumber::<TestRuntime>::iter().count(), 5);
+		})
+	}
+
+	#[test]
+	fn imported_headers_range_works() {
+		run_test(|| {
+			initialize_substrate_bridge();
+			assert_ok!(submit_finality_proof(2));
+			assert_ok!(submit_finality_proof(4));
+
+			let header_id = |number| (number, test_header(number).hash());
+
+			// short range is read number-by-number
+			assert_eq!(Pallet::<TestRuntime>::imported_headers_range(1, 3), vec![header_id(2)]);
+			assert_eq!(
+				Pallet::<TestRuntime>::imported_headers_range(0, 4),
+				vec![header_id(0), header_id(2), header_id(4)],
+			);
+			// long range is read from the whole index
+			assert_eq!(
+				Pallet::<TestRuntime>::imported_headers_range(1, 100),
+				vec![header_id(2), header_id(4)],
+			);
+			// invalid range is empty
+			assert_eq!(Pallet::<TestRuntime>::imported_headers_range(4, 2), vec![]);
+		})
+	}
+
 	#[test]
 	fn storage_keys_computed_properly() {
 		assert_eq!(
@@ -1086,6 +2223,11 @@ mod tests {
 			BestFinalized::<TestRuntime>::storage_value_final_key().to_vec(),
 			bp_header_chain::storage_keys::best_finalized_key("Grandpa").0,
 		);
+
+		assert_eq!(
+			CurrentAuthoritySet::<TestRuntime>::storage_value_final_key().to_vec(),
+			bp_header_chain::storage_keys::current_authority_set_key("Grandpa").0,
+		);
 	}
 
 	generate_owned_bridge_module_tests!(BasicOperatingMode::Normal, BasicOperatingMode::Halted);
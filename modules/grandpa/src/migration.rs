@@ -0,0 +1,127 @@
+// Copyright 2019-2022 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Storage migrations of the GRANDPA pallet.
+
+use crate::{Config, ImportedHashes, ImportedHashesByNumber, ImportedHeaders, Pallet, LOG_TARGET};
+
+use frame_support::{
+	traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	weights::Weight,
+};
+use sp_runtime::traits::Header as HeaderT;
+use sp_std::marker::PhantomData;
+
+/// Migration from the storage version 0 to the version 1.
+pub mod v1 {
+	use super::*;
+
+	/// Fills the `ImportedHashesByNumber` index with all imported headers that are not yet pruned.
+	///
+	/// Version 0 of the pallet hasn't had the index, so without this migration headers that have
+	/// been imported before the upgrade would be missing from the `finalized_header_at` and
+	/// `imported_headers_range` results. At most `HeadersToKeep` headers are indexed.
+	pub struct MigrateToV1<T, I>(PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for MigrateToV1<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T, I>::on_chain_storage_version();
+			if on_chain_version != 0 {
+				log::info!(
+					target: LOG_TARGET,
+					"Skipping imported headers index migration: storage version is {:?}",
+					on_chain_version,
+				);
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut checked_headers: Weight = 0;
+			let mut indexed_headers: Weight = 0;
+			for (_, hash) in ImportedHashes::<T, I>::iter() {
+				checked_headers += 1;
+				let number = match ImportedHeaders::<T, I>::get(hash) {
+					Some(header) => *header.number(),
+					None => continue,
+				};
+
+				// the number may already be occupied by other header (e.g. after
+				// re-initialization), so we never overwrite existing entries
+				if ImportedHashesByNumber::<T, I>::contains_key(number) {
+					continue
+				}
+
+				ImportedHashesByNumber::<T, I>::insert(number, hash);
+				indexed_headers += 1;
+			}
+			StorageVersion::new(1).put::<Pallet<T, I>>();
+
+			log::info!(
+				target: LOG_TARGET,
+				"Indexed {} imported headers by number",
+				indexed_headers,
+			);
+
+			T::DbWeight::get().reads_writes(3 * checked_headers + 1, indexed_headers + 1)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		insert_header,
+		mock::{run_test, test_header, TestRuntime},
+	};
+
+	fn import_headers_without_index() {
+		for number in 1..=3 {
+			let header = test_header(number);
+			insert_header::<TestRuntime, ()>(header.clone(), header.hash());
+			ImportedHashesByNumber::<TestRuntime>::remove(number);
+		}
+	}
+
+	#[test]
+	fn imported_headers_are_indexed() {
+		run_test(|| {
+			StorageVersion::new(0).put::<Pallet<TestRuntime>>();
+			import_headers_without_index();
+
+			v1::MigrateToV1::<TestRuntime, ()>::on_runtime_upgrade();
+
+			assert_eq!(Pallet::<TestRuntime>::on_chain_storage_version(), 1);
+			for number in 1..=3 {
+				assert_eq!(
+					ImportedHashesByNumber::<TestRuntime>::get(number),
+					Some(test_header(number).hash()),
+				);
+			}
+		});
+	}
+
+	#[test]
+	fn migration_is_not_applied_twice() {
+		run_test(|| {
+			StorageVersion::new(1).put::<Pallet<TestRuntime>>();
+			import_headers_without_index();
+
+			v1::MigrateToV1::<TestRuntime, ()>::on_runtime_upgrade();
+
+			assert_eq!(ImportedHashesByNumber::<TestRuntime>::iter().count(), 0);
+		});
+	}
+}
@@ -17,20 +17,25 @@
 //! Messages pallet benchmarking.
 
 use crate::{
-	inbound_lane::InboundLaneStorage, inbound_lane_storage, outbound_lane,
+	enqueue_failed_dispatch, inbound_lane::InboundLaneStorage, inbound_lane_storage, outbound_lane,
 	outbound_lane::ReceivalConfirmationResult, weights_ext::EXPECTED_DEFAULT_MESSAGE_LENGTH, Call,
-	OutboundLanes, OutboundMessages,
+	LaneOperatingModes, LaneRegistry, OutboundLanes, OutboundMessageSenders,
+	OutboundMessageStatuses, OutboundMessages,
 };
 
 use bp_messages::{
-	source_chain::TargetHeaderChain, target_chain::SourceHeaderChain, DeliveredMessages,
-	InboundLaneData, LaneId, MessageData, MessageKey, MessageNonce, OutboundLaneData,
-	UnrewardedRelayer, UnrewardedRelayersState,
+	source_chain::TargetHeaderChain,
+	target_chain::{DispatchMessage, DispatchMessageData, MessageDispatch, SourceHeaderChain},
+	DeliveredMessages, InboundLaneData, LaneConfig, LaneId, MessageData, MessageKey, MessageNonce,
+	MessageStatus, MessagesOperatingMode, OutboundLaneData, UnrewardedRelayer,
+	UnrewardedRelayersState, MAX_LANE_ALLOWED_SENDERS,
 };
 use bp_runtime::{messages::DispatchFeePayment, StorageProofSize};
+use codec::Decode;
 use frame_benchmarking::{account, benchmarks_instance_pallet};
 use frame_support::{traits::Get, weights::Weight};
 use frame_system::RawOrigin;
+use sp_runtime::traits::Zero;
 use sp_std::{collections::vec_deque::VecDeque, convert::TryInto, ops::RangeInclusive, prelude::*};
 
 const SEED: u32 = 0;
@@ -108,6 +113,15 @@ pub trait Config<I: 'static>: crate::Config<I> {
 	) -> <Self::TargetHeaderChain as TargetHeaderChain<Self::OutboundPayload, Self::AccountId>>::MessagesDeliveryProof;
 	/// Returns true if message has been dispatched (either successfully or not).
 	fn is_message_dispatched(nonce: MessageNonce) -> bool;
+	/// Prepare encoded payload of the inbound message, which dispatch has failed and which is
+	/// retried in the `retry_dispatch` benchmark.
+	///
+	/// By default, the payload of `size` zero bytes is used. Normally its dispatch fails again,
+	/// so the message stays in the retry queue. Runtime should override it with the payload that
+	/// is dispatched successfully, because then the message is also removed from the queue.
+	fn prepare_failed_dispatch_payload(size: u32) -> Vec<u8> {
+		vec![0; size as usize]
+	}
 }
 
 benchmarks_instance_pallet! {
@@ -589,16 +603,119 @@ benchmarks_instance_pallet! {
 	verify {
 		assert_eq!(OutboundLanes::<T, I>::get(T::bench_lane_id()).latest_received_nonce, 2);
 	}
+
+	// Benchmark `set_lane_operating_mode` extrinsic.
+	//
+	// Result of this benchmark is directly used by weight formula of the call.
+	set_lane_operating_mode {
+		let lane_id = T::bench_lane_id();
+		let operating_mode = MessagesOperatingMode::RejectingOutboundMessages;
+	}: set_lane_operating_mode(RawOrigin::Root, lane_id, operating_mode)
+	verify {
+		assert_eq!(LaneOperatingModes::<T, I>::get(lane_id), operating_mode);
+	}
+
+	// Benchmark `open_lane` extrinsic with following conditions:
+	// * the lane is opened with the list of `s` allowed senders.
+	//
+	// Result of this benchmark is directly used by weight formula of the call.
+	open_lane {
+		let s in 0 .. MAX_LANE_ALLOWED_SENDERS;
+
+		// the benchmark lane may already be opened at genesis, so we need another lane here
+		let lane_id: LaneId = *b"open";
+		let allowed_senders = (0..s)
+			.map(|i| account("sender", i, SEED))
+			.collect::<Vec<_>>()
+			.try_into()
+			.expect("number of senders is limited by MAX_LANE_ALLOWED_SENDERS; qed");
+		let config = LaneConfig {
+			max_pending_messages: MessageNonce::MAX,
+			max_payload_size: T::maximal_message_size(),
+			allowed_senders: Some(allowed_senders),
+		};
+	}: open_lane(RawOrigin::Root, lane_id, config)
+	verify {
+		assert!(LaneRegistry::<T, I>::contains_key(lane_id));
+	}
+
+	// Benchmark `cancel_expired_message` extrinsic with following conditions:
+	// * the message has been sent with time-to-live, which has already expired;
+	// * the message has maximal size, so it takes some time to read and decode it.
+	//
+	// Result of this benchmark is directly used by weight formula of the call.
+	cancel_expired_message {
+		let lane_id = T::bench_lane_id();
+		let sender: T::AccountId = account("sender", 0, SEED);
+		let nonce = send_expiring_message_with_payload::<T, I>(
+			sender.clone(),
+			vec![42u8; T::maximal_message_size() as _],
+		);
+		frame_system::Pallet::<T>::set_block_number(1u32.into());
+	}: cancel_expired_message(RawOrigin::Signed(sender), lane_id, nonce)
+	verify {
+		assert!(OutboundMessages::<T, I>::get(MessageKey { lane_id, nonce }).unwrap().is_cancelled);
+	}
+
+	// Benchmark `retry_dispatch` extrinsic with following conditions:
+	// * the message payload has maximal size that may be kept in the retry queue;
+	// * the dispatch of the message is retried for the first time.
+	//
+	// Result of this benchmark is used as a base weight for `retry_dispatch` call. The dispatch
+	// weight of the message, declared by the caller, is added to this weight.
+	retry_dispatch {
+		let lane_id = T::bench_lane_id();
+		let nonce = 1;
+		let retry_account: T::AccountId = account("sender", 0, SEED);
+		let message_key = MessageKey { lane_id, nonce };
+		let payload = T::prepare_failed_dispatch_payload(T::MaxFailedDispatchPayloadSize::get());
+		let mut message = DispatchMessage {
+			key: message_key.clone(),
+			data: DispatchMessageData::<T::InboundPayload, T::InboundMessageFee> {
+				payload: T::InboundPayload::decode(&mut &payload[..]),
+				fee: Zero::zero(),
+				is_cancelled: false,
+			},
+		};
+		let dispatch_weight = T::MessageDispatch::dispatch_weight(&mut message);
+		enqueue_failed_dispatch::<T, I>(
+			message_key.clone(),
+			retry_account.clone(),
+			payload.try_into().expect("payload has maximal allowed size; qed"),
+		);
+	}: retry_dispatch(RawOrigin::Signed(retry_account), lane_id, nonce, dispatch_weight)
 }
 
 fn send_regular_message<T: Config<I>, I: 'static>() {
 	let mut outbound_lane = outbound_lane::<T, I>(T::bench_lane_id());
-	outbound_lane.send_message(MessageData { payload: vec![], fee: T::message_fee() });
+	outbound_lane.send_message(MessageData::new(vec![], T::message_fee()));
 }
 
 fn send_regular_message_with_payload<T: Config<I>, I: 'static>(payload: Vec<u8>) {
 	let mut outbound_lane = outbound_lane::<T, I>(T::bench_lane_id());
-	outbound_lane.send_message(MessageData { payload, fee: T::message_fee() });
+	outbound_lane.send_message(MessageData::new(payload, T::message_fee()));
+}
+
+fn send_expiring_message_with_payload<T: Config<I>, I: 'static>(
+	sender: T::AccountId,
+	payload: Vec<u8>,
+) -> MessageNonce {
+	// the message expires at the genesis block
+	let lane_id = T::bench_lane_id();
+	let mut outbound_lane = outbound_lane::<T, I>(lane_id);
+	let nonce = outbound_lane.send_message(MessageData {
+		payload,
+		fee: T::message_fee(),
+		expires_at: Some(0),
+		is_cancelled: false,
+	});
+	let message_key = MessageKey { lane_id, nonce };
+	OutboundMessageSenders::<T, I>::insert(&message_key, sender);
+	OutboundMessageStatuses::<T, I>::insert(
+		&message_key,
+		MessageStatus::Pending { sent_at: Zero::zero() },
+	);
+	nonce
 }
 
 fn confirm_message_delivery<T: Config<I>, I: 'static>(nonce: MessageNonce) {
@@ -46,6 +46,8 @@ pub trait InboundLaneStorage {
 	fn data(&self) -> InboundLaneData<Self::Relayer>;
 	/// Update lane data in the storage.
 	fn set_data(&mut self, data: InboundLaneData<Self::Relayer>);
+	/// Remove dispatch response of the message from the storage, if it is there.
+	fn remove_message_response(&mut self, nonce: MessageNonce);
 }
 
 /// Inbound lane data wrapper that implements `MaxEncodedLen`.
@@ -124,6 +126,9 @@ pub enum ReceivalResult {
 	TooManyUnrewardedRelayers,
 	/// There are too many unconfirmed messages at the lane.
 	TooManyUnconfirmedMessages,
+	/// Message has been cancelled at the source chain. It has been received, but not
+	/// dispatched.
+	Cancelled,
 }
 
 /// Inbound messages lane.
@@ -154,6 +159,11 @@ impl<S: InboundLaneStorage> InboundLane<S> {
 		}
 
 		let new_confirmed_nonce = outbound_lane_data.latest_received_nonce;
+		// the bridged chain knows about delivery of these messages => it has already received
+		// their dispatch responses
+		for nonce in data.last_confirmed_nonce + 1..=new_confirmed_nonce {
+			self.storage.remove_message_response(nonce);
+		}
 		data.last_confirmed_nonce = new_confirmed_nonce;
 		// Firstly, remove all of the records where higher nonce <= new confirmed nonce
 		while data
@@ -183,12 +193,17 @@ impl<S: InboundLaneStorage> InboundLane<S> {
 	}
 
 	/// Receive new message.
+	///
+	/// If `may_retry_dispatch` is true, the message is kept in the retry queue when its dispatch
+	/// fails. Then it is reported as dispatched to the bridged chain, because it still may be
+	/// dispatched later.
 	pub fn receive_message<P: MessageDispatch<AccountId, S::MessageFee>, AccountId>(
 		&mut self,
 		relayer_at_bridged_chain: &S::Relayer,
 		relayer_at_this_chain: &AccountId,
 		nonce: MessageNonce,
 		message_data: DispatchMessageData<P::DispatchPayload, S::MessageFee>,
+		may_retry_dispatch: bool,
 	) -> ReceivalResult {
 		let mut data = self.storage.data();
 		let is_correct_message = nonce == data.last_delivered_nonce() + 1;
@@ -207,19 +222,31 @@ impl<S: InboundLaneStorage> InboundLane<S> {
 			return ReceivalResult::TooManyUnconfirmedMessages
 		}
 
-		// then, dispatch message
-		let dispatch_result = P::dispatch(
-			relayer_at_this_chain,
-			DispatchMessage {
-				key: MessageKey { lane_id: self.storage.id(), nonce },
-				data: message_data,
-			},
-		);
+		// then, dispatch message (cancelled messages are never dispatched, but we still need to
+		// mark their nonces as delivered)
+		let is_cancelled = message_data.is_cancelled;
+		let dispatch_result = if is_cancelled {
+			None
+		} else {
+			Some(P::dispatch(
+				relayer_at_this_chain,
+				DispatchMessage {
+					key: MessageKey { lane_id: self.storage.id(), nonce },
+					data: message_data,
+				},
+			))
+		};
+		// the bridged chain may refund fee of the cancelled message if it is not dispatched, so
+		// messages that may still be dispatched using `retry_dispatch` are marked as dispatched
+		let is_dispatched = dispatch_result
+			.as_ref()
+			.map(|result| result.dispatch_result || may_retry_dispatch)
+			.unwrap_or(false);
 
 		// now let's update inbound lane storage
 		let push_new = match data.relayers.back_mut() {
 			Some(entry) if entry.relayer == *relayer_at_bridged_chain => {
-				entry.messages.note_dispatched_message(dispatch_result.dispatch_result);
+				entry.messages.note_dispatched_message(is_dispatched);
 				false
 			},
 			_ => true,
@@ -227,12 +254,15 @@ impl<S: InboundLaneStorage> InboundLane<S> {
 		if push_new {
 			data.relayers.push_back(UnrewardedRelayer {
 				relayer: (*relayer_at_bridged_chain).clone(),
-				messages: DeliveredMessages::new(nonce, dispatch_result.dispatch_result),
+				messages: DeliveredMessages::new(nonce, is_dispatched),
 			});
 		}
 		self.storage.set_data(data);
 
-		ReceivalResult::Dispatched(dispatch_result)
+		match dispatch_result {
+			Some(dispatch_result) => ReceivalResult::Dispatched(dispatch_result),
+			None => ReceivalResult::Cancelled,
+		}
 	}
 }
 
@@ -258,7 +288,8 @@ mod tests {
 				&TEST_RELAYER_A,
 				&TEST_RELAYER_A,
 				nonce,
-				message_data(REGULAR_PAYLOAD).into()
+				message_data(REGULAR_PAYLOAD).into(),
+				false,
 			),
 			ReceivalResult::Dispatched(dispatch_result(0))
 		);
@@ -386,7 +417,8 @@ mod tests {
 					&TEST_RELAYER_A,
 					&TEST_RELAYER_A,
 					10,
-					message_data(REGULAR_PAYLOAD).into()
+					message_data(REGULAR_PAYLOAD).into(),
+					false,
 				),
 				ReceivalResult::InvalidNonce
 			);
@@ -406,7 +438,8 @@ mod tests {
 						&(TEST_RELAYER_A + current_nonce),
 						&(TEST_RELAYER_A + current_nonce),
 						current_nonce,
-						message_data(REGULAR_PAYLOAD).into()
+						message_data(REGULAR_PAYLOAD).into(),
+						false,
 					),
 					ReceivalResult::Dispatched(dispatch_result(0))
 				);
@@ -417,7 +450,8 @@ mod tests {
 					&(TEST_RELAYER_A + max_nonce + 1),
 					&(TEST_RELAYER_A + max_nonce + 1),
 					max_nonce + 1,
-					message_data(REGULAR_PAYLOAD).into()
+					message_data(REGULAR_PAYLOAD).into(),
+					false,
 				),
 				ReceivalResult::TooManyUnrewardedRelayers,
 			);
@@ -427,7 +461,8 @@ mod tests {
 					&(TEST_RELAYER_A + max_nonce),
 					&(TEST_RELAYER_A + max_nonce),
 					max_nonce + 1,
-					message_data(REGULAR_PAYLOAD).into()
+					message_data(REGULAR_PAYLOAD).into(),
+					false,
 				),
 				ReceivalResult::TooManyUnrewardedRelayers,
 			);
@@ -445,7 +480,8 @@ mod tests {
 						&TEST_RELAYER_A,
 						&TEST_RELAYER_A,
 						current_nonce,
-						message_data(REGULAR_PAYLOAD).into()
+						message_data(REGULAR_PAYLOAD).into(),
+						false,
 					),
 					ReceivalResult::Dispatched(dispatch_result(0))
 				);
@@ -456,7 +492,8 @@ mod tests {
 					&TEST_RELAYER_B,
 					&TEST_RELAYER_B,
 					max_nonce + 1,
-					message_data(REGULAR_PAYLOAD).into()
+					message_data(REGULAR_PAYLOAD).into(),
+					false,
 				),
 				ReceivalResult::TooManyUnconfirmedMessages,
 			);
@@ -466,7 +503,8 @@ mod tests {
 					&TEST_RELAYER_A,
 					&TEST_RELAYER_A,
 					max_nonce + 1,
-					message_data(REGULAR_PAYLOAD).into()
+					message_data(REGULAR_PAYLOAD).into(),
+					false,
 				),
 				ReceivalResult::TooManyUnconfirmedMessages,
 			);
@@ -482,7 +520,8 @@ mod tests {
 					&TEST_RELAYER_A,
 					&TEST_RELAYER_A,
 					1,
-					message_data(REGULAR_PAYLOAD).into()
+					message_data(REGULAR_PAYLOAD).into(),
+					false,
 				),
 				ReceivalResult::Dispatched(dispatch_result(0))
 			);
@@ -491,7 +530,8 @@ mod tests {
 					&TEST_RELAYER_B,
 					&TEST_RELAYER_B,
 					2,
-					message_data(REGULAR_PAYLOAD).into()
+					message_data(REGULAR_PAYLOAD).into(),
+					false,
 				),
 				ReceivalResult::Dispatched(dispatch_result(0))
 			);
@@ -500,7 +540,8 @@ mod tests {
 					&TEST_RELAYER_A,
 					&TEST_RELAYER_A,
 					3,
-					message_data(REGULAR_PAYLOAD).into()
+					message_data(REGULAR_PAYLOAD).into(),
+					false,
 				),
 				ReceivalResult::Dispatched(dispatch_result(0))
 			);
@@ -524,7 +565,8 @@ mod tests {
 					&TEST_RELAYER_A,
 					&TEST_RELAYER_A,
 					1,
-					message_data(REGULAR_PAYLOAD).into()
+					message_data(REGULAR_PAYLOAD).into(),
+					false,
 				),
 				ReceivalResult::Dispatched(dispatch_result(0))
 			);
@@ -533,13 +575,42 @@ mod tests {
 					&TEST_RELAYER_B,
 					&TEST_RELAYER_B,
 					1,
-					message_data(REGULAR_PAYLOAD).into()
+					message_data(REGULAR_PAYLOAD).into(),
+					false,
 				),
 				ReceivalResult::InvalidNonce,
 			);
 		});
 	}
 
+	#[test]
+	fn cancelled_message_is_received_but_not_dispatched() {
+		run_test(|| {
+			let mut lane = inbound_lane::<TestRuntime, _>(TEST_LANE_ID);
+			let mut message_data: DispatchMessageData<_, _> = message_data(REGULAR_PAYLOAD).into();
+			message_data.is_cancelled = true;
+			assert_eq!(
+				lane.receive_message::<TestMessageDispatch, _>(
+					&TEST_RELAYER_A,
+					&TEST_RELAYER_A,
+					1,
+					message_data,
+					true,
+				),
+				ReceivalResult::Cancelled,
+			);
+			assert_eq!(lane.storage.data().last_delivered_nonce(), 1);
+			assert_eq!(
+				lane.storage
+					.data()
+					.relayers
+					.back()
+					.map(|entry| entry.messages.dispatch_results[0]),
+				Some(false),
+			);
+		});
+	}
+
 	#[test]
 	fn correct_message_is_processed_instantly() {
 		run_test(|| {
@@ -560,7 +631,8 @@ mod tests {
 					&TEST_RELAYER_A,
 					&TEST_RELAYER_A,
 					1,
-					message_data(payload).into()
+					message_data(payload).into(),
+					false,
 				),
 				ReceivalResult::Dispatched(dispatch_result(1))
 			);
@@ -52,25 +52,30 @@ use crate::{
 
 use bp_messages::{
 	source_chain::{
-		LaneMessageVerifier, MessageDeliveryAndDispatchPayment, OnDeliveryConfirmed,
-		OnMessageAccepted, RelayersRewards, SendMessageArtifacts, TargetHeaderChain,
+		LaneMessageVerifier, MessageDeliveryAndDispatchPayment, MessagesResponses,
+		OnDeliveryConfirmed, OnMessageAccepted, OnMessageResponse, RelayersRewards,
+		SendMessageArtifacts, TargetHeaderChain,
 	},
 	target_chain::{
-		DispatchMessage, MessageDispatch, ProvedLaneMessages, ProvedMessages, SourceHeaderChain,
+		DispatchMessage, DispatchMessageData, FailedMessageDispatch, MessageDispatch,
+		SourceHeaderChain,
 	},
-	total_unrewarded_messages, DeliveredMessages, InboundLaneData, InboundMessageDetails, LaneId,
-	MessageData, MessageKey, MessageNonce, MessagePayload, MessagesOperatingMode, OutboundLaneData,
-	OutboundMessageDetails, Parameter as MessagesParameter, UnrewardedRelayer,
-	UnrewardedRelayersState,
+	total_unrewarded_messages, DeliveredMessages, InboundLaneData, InboundMessageDetails,
+	LaneConfig, LaneId, LaneState, MessageData, MessageKey, MessageNonce, MessagePayload,
+	MessageStatus, MessagesOperatingMode, OutboundLaneData, OutboundMessageDetails,
+	Parameter as MessagesParameter, RegisteredLane, UnrewardedRelayer, UnrewardedRelayersState,
+	VerificationError,
 };
-use bp_runtime::{BasicOperatingMode, ChainId, OwnedBridgeModule, Size};
+use bp_runtime::{BasicOperatingMode, ChainId, OperatingMode, OwnedBridgeModule, Size};
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
 	ensure, fail,
 	traits::Get,
-	weights::{Pays, PostDispatchInfo},
+	weights::{Pays, PostDispatchInfo, Weight},
+	BoundedVec,
 };
 use num_traits::{SaturatingAdd, Zero};
+use sp_runtime::traits::{Saturating, UniqueSaturatedInto};
 use sp_std::{
 	cell::RefCell, cmp::PartialOrd, collections::vec_deque::VecDeque, marker::PhantomData,
 	ops::RangeInclusive, prelude::*,
@@ -80,6 +85,7 @@ mod inbound_lane;
 mod outbound_lane;
 mod weights_ext;
 
+pub mod migration;
 pub mod weights;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -93,6 +99,19 @@ pub use pallet::*;
 /// The target that will be used when publishing logs related to this pallet.
 pub const LOG_TARGET: &str = "runtime::bridge-messages";
 
+/// The error message returned from the lane registry when the lane is not opened.
+pub const LANE_IS_NOT_OPENED: &str = "The lane is not opened in the lane registry.";
+/// The error message returned from the lane registry when too many pending messages at the lane.
+pub const TOO_MANY_PENDING_MESSAGES_AT_LANE: &str =
+	"Too many pending messages at the registered lane.";
+/// The error message returned from the lane registry when the message is too large for the lane.
+pub const MESSAGE_IS_TOO_LARGE_FOR_LANE: &str =
+	"The message is too large to be sent over the registered lane.";
+/// The error message returned from the lane registry when the sender is not allowed to use the
+/// lane.
+pub const SENDER_IS_NOT_ALLOWED_AT_LANE: &str =
+	"The sender is not allowed to send messages over the registered lane.";
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -144,6 +163,29 @@ pub mod pallet {
 		/// Transaction that is declaring more messages than this value, will be rejected. Even if
 		/// these messages are from different lanes.
 		type MaxUnconfirmedMessagesAtInboundLane: Get<MessageNonce>;
+		/// Maximal number of failed message dispatches that are kept for retry at every inbound
+		/// lane. When there are too many failed dispatches, the oldest one is removed. Zero
+		/// disables the retry queue.
+		#[pallet::constant]
+		type MaxFailedDispatchesAtInboundLane: Get<u32>;
+		/// Maximal size of the inbound message payload that may be kept for retry. Failed
+		/// messages with larger payloads are dropped.
+		#[pallet::constant]
+		type MaxFailedDispatchPayloadSize: Get<u32>;
+		/// Number of blocks during which the failed message dispatch may be retried.
+		#[pallet::constant]
+		type FailedDispatchLifetime: Get<Self::BlockNumber>;
+		/// Number of blocks during which the status of outbound message is kept after the bridged
+		/// chain has learned about its delivery confirmation.
+		#[pallet::constant]
+		type ConfirmedMessageStatusLifetime: Get<Self::BlockNumber>;
+		/// Maximal size of the inbound message dispatch response that is relayed back to the
+		/// bridged chain. Larger responses are dropped. Zero disables responses.
+		///
+		/// It is also the maximal size of the outbound message dispatch response, that is handled
+		/// by this chain. Larger responses are ignored.
+		#[pallet::constant]
+		type MaxMessageResponseSize: Get<u32>;
 
 		/// Maximal size of the outbound payload.
 		#[pallet::constant]
@@ -188,6 +230,8 @@ pub mod pallet {
 		type OnMessageAccepted: OnMessageAccepted;
 		/// Handler for delivered messages.
 		type OnDeliveryConfirmed: OnDeliveryConfirmed;
+		/// Handler for dispatch responses of delivered messages.
+		type OnMessageResponse: OnMessageResponse;
 
 		// Types that are used by inbound_lane (on target chain).
 
@@ -212,8 +256,15 @@ pub mod pallet {
 			<T as frame_system::Config>::AccountId,
 		>>::MessagesDeliveryProof;
 
+	/// The current storage version.
+	///
+	/// Relayers are reading `bp_messages::MESSAGES_WIRE_FORMAT_VERSION` from the storage version,
+	/// so it must be bumped whenever the wire format is changed.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
 
 	impl<T: Config<I>, I: 'static> OwnedBridgeModule<T> for Pallet<T, I> {
@@ -261,20 +312,148 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Halt or resume all/some operations at given lane.
+		///
+		/// The pallet-wide operating mode is still checked before the lane operating mode, so
+		/// this call may not be used to resume lane operations when the whole pallet is halted.
+		///
+		/// May only be called either by root, or by `PalletOwner`.
+		#[pallet::weight((T::WeightInfo::set_lane_operating_mode(), DispatchClass::Operational))]
+		pub fn set_lane_operating_mode(
+			origin: OriginFor<T>,
+			lane_id: LaneId,
+			operating_mode: MessagesOperatingMode,
+		) -> DispatchResult {
+			Self::ensure_owner_or_root(origin)?;
+			LaneOperatingModes::<T, I>::insert(lane_id, operating_mode);
+			log::info!(
+				target: LOG_TARGET,
+				"Setting lane {:?} operating mode to {:?}.",
+				lane_id,
+				operating_mode,
+			);
+			Self::deposit_event(Event::LaneOperatingModeUpdated { lane_id, operating_mode });
+			Ok(())
+		}
+
+		/// Open new lane in the lane registry.
+		///
+		/// May only be called either by root, or by `PalletOwner`.
+		#[pallet::weight((
+			T::WeightInfo::open_lane(
+				config.allowed_senders.as_ref().map(|senders| senders.len() as u32).unwrap_or(0),
+			),
+			DispatchClass::Operational,
+		))]
+		pub fn open_lane(
+			origin: OriginFor<T>,
+			lane_id: LaneId,
+			config: LaneConfig<T::AccountId>,
+		) -> DispatchResult {
+			Self::ensure_owner_or_root(origin)?;
+			ensure!(
+				!LaneRegistry::<T, I>::contains_key(lane_id),
+				Error::<T, I>::LaneIsAlreadyRegistered
+			);
+			LaneRegistry::<T, I>::insert(
+				lane_id,
+				RegisteredLane { config, state: LaneState::Opened },
+			);
+			log::info!(target: LOG_TARGET, "Opened lane {:?}.", lane_id);
+			Self::deposit_event(Event::LaneOpened { lane_id });
+			Ok(())
+		}
+
+		/// Update configuration of the opened lane.
+		///
+		/// May only be called either by root, or by `PalletOwner`.
+		#[pallet::weight((T::DbWeight::get().reads_writes(2, 2), DispatchClass::Operational))]
+		pub fn update_lane_config(
+			origin: OriginFor<T>,
+			lane_id: LaneId,
+			config: LaneConfig<T::AccountId>,
+		) -> DispatchResult {
+			Self::ensure_owner_or_root(origin)?;
+			LaneRegistry::<T, I>::try_mutate(lane_id, |registered_lane| match registered_lane {
+				Some(registered_lane) if registered_lane.state == LaneState::Opened => {
+					registered_lane.config = config;
+					Ok(())
+				},
+				_ => Err(Error::<T, I>::LaneIsNotOpened),
+			})?;
+			log::info!(target: LOG_TARGET, "Updated configuration of lane {:?}.", lane_id);
+			Self::deposit_event(Event::LaneConfigUpdated { lane_id });
+			Ok(())
+		}
+
+		/// Start closing the opened lane.
+		///
+		/// The closing lane stops accepting new messages. Messages that are already queued are
+		/// still delivered and once all of them are confirmed, the lane is removed from the
+		/// registry. If there are no queued messages, the lane is removed immediately.
+		///
+		/// May only be called either by root, or by `PalletOwner`.
+		#[pallet::weight((T::DbWeight::get().reads_writes(3, 3), DispatchClass::Operational))]
+		pub fn close_lane(origin: OriginFor<T>, lane_id: LaneId) -> DispatchResult {
+			Self::ensure_owner_or_root(origin)?;
+			LaneRegistry::<T, I>::try_mutate(lane_id, |registered_lane| match registered_lane {
+				Some(registered_lane) if registered_lane.state == LaneState::Opened => {
+					registered_lane.state = LaneState::Closing;
+					Ok(())
+				},
+				_ => Err(Error::<T, I>::LaneIsNotOpened),
+			})?;
+			log::info!(target: LOG_TARGET, "Closing lane {:?}.", lane_id);
+			Self::deposit_event(Event::LaneClosing { lane_id });
+
+			close_lane_if_drained::<T, I>(&lane_id);
+			Ok(())
+		}
+
 		/// Send message over lane.
-		#[pallet::weight(T::WeightInfo::send_message_weight(payload, T::DbWeight::get()))]
+		#[pallet::weight(send_message_weight::<T, I>(payload))]
 		pub fn send_message(
 			origin: OriginFor<T>,
 			lane_id: LaneId,
 			payload: T::OutboundPayload,
 			delivery_and_dispatch_fee: T::OutboundMessageFee,
 		) -> DispatchResultWithPostInfo {
-			crate::send_message::<T, I>(origin, lane_id, payload, delivery_and_dispatch_fee).map(
-				|sent_message| PostDispatchInfo {
+			crate::send_message::<T, I>(origin, lane_id, payload, delivery_and_dispatch_fee, None)
+				.map(|sent_message| PostDispatchInfo {
 					actual_weight: Some(sent_message.weight),
 					pays_fee: Pays::Yes,
-				},
+				})
+		}
+
+		/// Send message over lane, with given time-to-live.
+		///
+		/// If the message is not delivered to the bridged chain within `ttl` blocks, the sender
+		/// may cancel it and get the delivery and dispatch fee back using the
+		/// `cancel_expired_message` call.
+		#[pallet::weight(
+			send_message_weight::<T, I>(payload).saturating_add(T::DbWeight::get().writes(1))
+		)]
+		pub fn send_message_with_ttl(
+			origin: OriginFor<T>,
+			lane_id: LaneId,
+			payload: T::OutboundPayload,
+			delivery_and_dispatch_fee: T::OutboundMessageFee,
+			ttl: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin.clone())?;
+			crate::send_message::<T, I>(
+				origin,
+				lane_id,
+				payload,
+				delivery_and_dispatch_fee,
+				Some((sender, ttl)),
 			)
+			.map(|sent_message| PostDispatchInfo {
+				actual_weight: Some(
+					sent_message.weight.saturating_add(T::DbWeight::get().writes(1)),
+				),
+				pays_fee: Pays::Yes,
+			})
 		}
 
 		/// Pay additional fee for the message.
@@ -286,6 +465,7 @@ pub mod pallet {
 			additional_fee: T::OutboundMessageFee,
 		) -> DispatchResultWithPostInfo {
 			Self::ensure_not_halted().map_err(Error::<T, I>::BridgeModule)?;
+			ensure_lane_not_halted::<T, I>(&lane_id)?;
 			// if someone tries to pay for already-delivered message, we're rejecting this intention
 			// (otherwise this additional fee will be locked forever in relayers fund)
 			//
@@ -300,6 +480,12 @@ pub mod pallet {
 				nonce <= lane.data().latest_generated_nonce,
 				Error::<T, I>::MessageIsNotYetSent
 			);
+			// there's no point in paying for the cancelled message - it'll never be dispatched
+			let message_key = MessageKey { lane_id, nonce };
+			ensure!(
+				!OutboundMessages::<T, I>::get(&message_key).map_or(false, |m| m.is_cancelled),
+				Error::<T, I>::MessageIsCancelled
+			);
 
 			// withdraw additional fee from submitter
 			T::MessageDeliveryAndDispatchPayment::pay_delivery_and_dispatch_fee(
@@ -320,7 +506,6 @@ pub mod pallet {
 			})?;
 
 			// and finally update fee in the storage
-			let message_key = MessageKey { lane_id, nonce };
 			let message_size = OutboundMessages::<T, I>::mutate(message_key, |message_data| {
 				// saturating_add is fine here - overflow here means that someone controls all
 				// chain funds, which shouldn't ever happen + `pay_delivery_and_dispatch_fee`
@@ -341,6 +526,152 @@ pub mod pallet {
 			Ok(PostDispatchInfo { actual_weight: Some(actual_weight), pays_fee: Pays::Yes })
 		}
 
+		/// Cancel expired message.
+		///
+		/// Only messages that have been sent using `send_message_with_ttl` may be cancelled and
+		/// only by their sender. The cancelled message is replaced with an empty stub, which is
+		/// still delivered to the bridged chain (we can't skip nonces there), but is never
+		/// dispatched.
+		///
+		/// The relayer may have already generated the proof of the original message before it
+		/// has been cancelled. If this proof is submitted after cancellation, the original
+		/// message is dispatched at the bridged chain. So the delivery and dispatch fee is only
+		/// refunded to the sender when the delivery confirmation shows that the message has not
+		/// been dispatched at the bridged chain. Otherwise, the cancellation has no effect and
+		/// the relayer is rewarded as usual.
+		#[pallet::weight(T::WeightInfo::cancel_expired_message())]
+		pub fn cancel_expired_message(
+			origin: OriginFor<T>,
+			lane_id: LaneId,
+			nonce: MessageNonce,
+		) -> DispatchResult {
+			Self::ensure_not_halted().map_err(Error::<T, I>::BridgeModule)?;
+			ensure_lane_not_halted::<T, I>(&lane_id)?;
+			let sender = ensure_signed(origin)?;
+
+			let lane = outbound_lane::<T, I>(lane_id);
+			ensure!(
+				nonce > lane.data().latest_received_nonce,
+				Error::<T, I>::MessageIsAlreadyDelivered
+			);
+			ensure!(
+				nonce <= lane.data().latest_generated_nonce,
+				Error::<T, I>::MessageIsNotYetSent
+			);
+
+			let message_key = MessageKey { lane_id, nonce };
+			let message_data = OutboundMessages::<T, I>::get(&message_key)
+				.expect("the message is sent and not yet delivered; so it is in the storage; qed");
+			ensure!(!message_data.is_cancelled, Error::<T, I>::MessageIsCancelled);
+			let expires_at = message_data.expires_at.ok_or(Error::<T, I>::MessageHasNoTtl)?;
+			ensure!(
+				OutboundMessageSenders::<T, I>::get(&message_key).as_ref() == Some(&sender),
+				Error::<T, I>::NotMessageSender
+			);
+			let current_block: u64 =
+				frame_system::Pallet::<T>::block_number().unique_saturated_into();
+			ensure!(current_block > expires_at, Error::<T, I>::MessageIsNotExpired);
+
+			// the fee and the sender are kept until the delivery is confirmed (see
+			// `refund_cancelled_messages`)
+			OutboundMessages::<T, I>::insert(
+				&message_key,
+				MessageData {
+					payload: Vec::new(),
+					fee: message_data.fee,
+					expires_at: Some(expires_at),
+					is_cancelled: true,
+				},
+			);
+			OutboundMessageStatuses::<T, I>::mutate(&message_key, |status| {
+				if let Some(MessageStatus::Pending { sent_at }) = *status {
+					*status = Some(MessageStatus::Cancelled {
+						sent_at,
+						cancelled_at: frame_system::Pallet::<T>::block_number(),
+					});
+				}
+			});
+
+			log::trace!(
+				target: LOG_TARGET,
+				"Message {:?}/{:?} has been cancelled by its sender {:?}",
+				lane_id,
+				nonce,
+				sender,
+			);
+			Self::deposit_event(Event::MessageCancelled { lane_id, nonce });
+			Ok(())
+		}
+
+		/// Retry dispatch of the inbound message, which dispatch has previously failed.
+		///
+		/// May only be called by the retry account of the failed dispatch (normally it is the
+		/// account, derived from the message sender). The message is removed from the retry
+		/// queue when the dispatch succeeds. Otherwise it may be retried again until it expires.
+		#[pallet::weight(T::WeightInfo::retry_dispatch().saturating_add(*dispatch_weight))]
+		pub fn retry_dispatch(
+			origin: OriginFor<T>,
+			lane_id: LaneId,
+			nonce: MessageNonce,
+			dispatch_weight: Weight,
+		) -> DispatchResultWithPostInfo {
+			Self::ensure_not_halted().map_err(Error::<T, I>::BridgeModule)?;
+			ensure_lane_not_halted::<T, I>(&lane_id)?;
+			let retry_account = ensure_signed(origin)?;
+
+			let message_key = MessageKey { lane_id, nonce };
+			let failed_dispatch = FailedDispatches::<T, I>::get(&message_key)
+				.ok_or(Error::<T, I>::FailedDispatchNotFound)?;
+			let expires_at =
+				failed_dispatch.failed_at.saturating_add(T::FailedDispatchLifetime::get());
+			ensure!(
+				frame_system::Pallet::<T>::block_number() <= expires_at,
+				Error::<T, I>::FailedDispatchNotFound
+			);
+			ensure!(
+				failed_dispatch.retry_account == retry_account,
+				Error::<T, I>::NotDispatchRetryAccount
+			);
+
+			let mut message = DispatchMessage {
+				key: message_key.clone(),
+				data: DispatchMessageData::<T::InboundPayload, T::InboundMessageFee> {
+					payload: T::InboundPayload::decode(&mut &failed_dispatch.payload[..]),
+					fee: Zero::zero(),
+					is_cancelled: false,
+				},
+			};
+			let message_dispatch_weight = T::MessageDispatch::dispatch_weight(&mut message);
+			ensure!(
+				message_dispatch_weight <= dispatch_weight,
+				Error::<T, I>::InsufficientDispatchWeight
+			);
+
+			let dispatch_result = T::MessageDispatch::dispatch(&retry_account, message);
+			if dispatch_result.dispatch_result {
+				remove_failed_dispatch::<T, I>(message_key);
+			}
+
+			log::trace!(
+				target: LOG_TARGET,
+				"Retried dispatch of message {:?}/{}: {:?}",
+				lane_id,
+				nonce,
+				dispatch_result,
+			);
+			Self::deposit_event(Event::MessageDispatchRetried {
+				lane_id,
+				nonce,
+				dispatch_result: dispatch_result.dispatch_result,
+			});
+
+			let unspent_weight =
+				sp_std::cmp::min(dispatch_result.unspent_weight, message_dispatch_weight);
+			let actual_weight = T::WeightInfo::retry_dispatch()
+				.saturating_add(message_dispatch_weight - unspent_weight);
+			Ok(PostDispatchInfo { actual_weight: Some(actual_weight), pays_fee: Pays::Yes })
+		}
+
 		/// Receive messages proof from bridged chain.
 		///
 		/// The weight of the call assumes that the transaction always brings outbound lane
@@ -381,16 +712,25 @@ pub mod pallet {
 			let mut actual_weight = declared_weight;
 
 			// verify messages proof && convert proof into messages
-			let messages = verify_and_decode_messages_proof::<
-				T::SourceHeaderChain,
-				T::InboundMessageFee,
-				T::InboundPayload,
-			>(proof, messages_count)
-			.map_err(|err| {
-				log::trace!(target: LOG_TARGET, "Rejecting invalid messages proof: {:?}", err,);
+			//
+			// `MaxUnconfirmedMessagesAtInboundLane` check guarantees that the `message_count` is
+			// sane and Vec<Message> may be allocated. (tx with too many messages will either be
+			// rejected from the pool, or will fail earlier)
+			let messages = T::SourceHeaderChain::verify_messages_proof(proof, messages_count)
+				.map_err(|err| {
+					log::trace!(target: LOG_TARGET, "Rejecting invalid messages proof: {:?}", err,);
 
-				Error::<T, I>::InvalidMessagesProof
-			})?;
+					proof_verification_error::<T, I>(
+						err.into(),
+						Error::<T, I>::InvalidMessagesProof,
+					)
+				})?;
+
+			// we don't want to accept messages for halted lanes, even if other lanes in the proof
+			// are operating normally
+			for lane_id in messages.keys() {
+				ensure_lane_not_halted::<T, I>(lane_id)?;
+			}
 
 			// dispatch messages and (optionally) update lane(s) state(s)
 			let mut total_messages = 0;
@@ -411,13 +751,24 @@ pub mod pallet {
 					}
 				}
 
-				for mut message in lane_data.messages {
+				for message in lane_data.messages {
 					debug_assert_eq!(message.key.lane_id, lane_id);
 
+					let retry_payload = failed_dispatch_payload::<T, I>(&message.data.payload);
+					let mut message: DispatchMessage<T::InboundPayload, T::InboundMessageFee> =
+						message.into();
+					let message_key = message.key.clone();
+
 					// ensure that relayer has declared enough weight for dispatching next message
 					// on this lane. We can't dispatch lane messages out-of-order, so if declared
 					// weight is not enough, let's move to next lane
-					let dispatch_weight = T::MessageDispatch::dispatch_weight(&mut message);
+					let (dispatch_weight, retry_data) =
+						message_dispatch_weight_and_retry_data::<T, I>(retry_payload, &mut message);
+					let retry_overhead = if retry_data.is_some() {
+						T::WeightInfo::single_message_retry_overhead(T::DbWeight::get())
+					} else {
+						0
+					};
 					if dispatch_weight > dispatch_weight_left {
 						log::trace!(
 							target: LOG_TARGET,
@@ -435,6 +786,7 @@ pub mod pallet {
 						&relayer_id_at_this_chain,
 						message.key.nonce,
 						message.data,
+						retry_data.is_some(),
 					);
 
 					// note that we're returning unspent weight to relayer even if message has been
@@ -446,10 +798,35 @@ pub mod pallet {
 					let (unspent_weight, refund_pay_dispatch_fee) = match receival_result {
 						ReceivalResult::Dispatched(dispatch_result) => {
 							valid_messages += 1;
-							(
+							// the retry queue overhead is a part of the declared dispatch weight,
+							// so it is refunded if the message is not kept for retry
+							let mut unspent_weight = sp_std::cmp::min(
 								dispatch_result.unspent_weight,
-								!dispatch_result.dispatch_fee_paid_during_dispatch,
-							)
+								dispatch_weight - retry_overhead,
+							);
+							match (dispatch_result.dispatch_result, retry_data) {
+								(false, Some((retry_account, retry_payload))) =>
+									enqueue_failed_dispatch::<T, I>(
+										message_key.clone(),
+										retry_account,
+										retry_payload,
+									),
+								_ => unspent_weight = unspent_weight.saturating_add(retry_overhead),
+							}
+							if let Some(response) = dispatch_result.response {
+								if save_message_response::<T, I>(message_key, response) {
+									// same here - use unspent dispatch weight to cover both
+									// response write and its removal once the delivery is
+									// confirmed
+									unspent_weight =
+										unspent_weight.saturating_sub(T::DbWeight::get().writes(2));
+								}
+							}
+							(unspent_weight, !dispatch_result.dispatch_fee_paid_during_dispatch)
+						},
+						ReceivalResult::Cancelled => {
+							valid_messages += 1;
+							(dispatch_weight, true)
 						},
 						ReceivalResult::InvalidNonce |
 						ReceivalResult::TooManyUnrewardedRelayers |
@@ -487,6 +864,7 @@ pub mod pallet {
 		#[pallet::weight(T::WeightInfo::receive_messages_delivery_proof_weight(
 			proof,
 			relayers_state,
+			T::MaxMessageResponseSize::get(),
 			T::DbWeight::get(),
 		))]
 		pub fn receive_messages_delivery_proof(
@@ -510,21 +888,26 @@ pub mod pallet {
 			let declared_weight = T::WeightInfo::receive_messages_delivery_proof_weight(
 				&proof,
 				&relayers_state,
+				T::MaxMessageResponseSize::get(),
 				T::DbWeight::get(),
 			);
 			let mut actual_weight = declared_weight;
 
 			let confirmation_relayer = ensure_signed(origin)?;
-			let (lane_id, lane_data) = T::TargetHeaderChain::verify_messages_delivery_proof(proof)
-				.map_err(|err| {
+			let (lane_id, lane_data, responses) =
+				T::TargetHeaderChain::verify_messages_delivery_proof(proof).map_err(|err| {
 					log::trace!(
 						target: LOG_TARGET,
 						"Rejecting invalid messages delivery proof: {:?}",
 						err,
 					);
 
-					Error::<T, I>::InvalidMessagesDeliveryProof
+					proof_verification_error::<T, I>(
+						err.into(),
+						Error::<T, I>::InvalidMessagesDeliveryProof,
+					)
 				})?;
+			ensure_lane_not_halted::<T, I>(&lane_id)?;
 
 			// verify that the relayer has declared correct `lane_data::relayers` state
 			// (we only care about total number of entries and messages, because this affects call
@@ -578,11 +961,22 @@ pub mod pallet {
 			};
 
 			if let Some(confirmed_messages) = confirmed_messages {
-				// handle messages delivery confirmation
-				let preliminary_callback_overhead =
-					relayers_state.total_messages.saturating_mul(single_message_callback_overhead);
+				// handle messages delivery confirmation and dispatch responses
+				let preliminary_callback_overhead = relayers_state.total_messages.saturating_mul(
+					single_message_callback_overhead.saturating_add(
+						T::WeightInfo::single_message_response_overhead(
+							T::MaxMessageResponseSize::get(),
+							T::DbWeight::get(),
+						),
+					),
+				);
 				let actual_callback_weight =
-					T::OnDeliveryConfirmed::on_messages_delivered(&lane_id, &confirmed_messages);
+					T::OnDeliveryConfirmed::on_messages_delivered(&lane_id, &confirmed_messages)
+						.saturating_add(handle_messages_responses::<T, I>(
+							&lane_id,
+							&confirmed_messages,
+							responses,
+						));
 				match preliminary_callback_overhead.checked_sub(actual_callback_weight) {
 					Some(difference) if difference == 0 => (),
 					Some(difference) => {
@@ -611,6 +1005,20 @@ pub mod pallet {
 					},
 				}
 
+				// remember dispatch results of delivered messages
+				note_delivered_messages::<T, I>(&lane_id, &confirmed_messages);
+
+				// refund fees of cancelled messages that haven't been dispatched at the bridged
+				// chain. It must happen before relayers are rewarded, because refunded fees are
+				// not paid to relayers
+				let refunded_messages =
+					refund_cancelled_messages::<T, I>(&lane_id, &confirmed_messages);
+				let unused_refund_weight =
+					relayers_state.total_messages.saturating_sub(refunded_messages).saturating_mul(
+						T::WeightInfo::single_message_refund_overhead(T::DbWeight::get()),
+					);
+				actual_weight = actual_weight.saturating_sub(unused_refund_weight);
+
 				// emit 'delivered' event
 				let received_range = confirmed_messages.begin..=confirmed_messages.end;
 				Self::deposit_event(Event::MessagesDelivered {
@@ -625,8 +1033,22 @@ pub mod pallet {
 					&confirmation_relayer,
 					&received_range,
 				);
+
+				// the closing lane is removed from the registry once all its messages are
+				// confirmed. It is a single storage read for lanes that are not closing, so we
+				// don't include it in the call weight
+				close_lane_if_drained::<T, I>(&lane_id);
 			}
 
+			// update statuses of messages, which delivery confirmation is known to the bridged
+			// chain. Every message is counted in the `total_messages` of the proof that confirms
+			// its delivery, so this budget is enough to update and prune all statuses eventually
+			note_confirmed_at_bridged_chain::<T, I>(
+				&lane_id,
+				lane_data.last_confirmed_nonce,
+				relayers_state.total_messages,
+			);
+
 			log::trace!(
 				target: LOG_TARGET,
 				"Received messages delivery proof up to (and including) {} at lane {:?}",
@@ -647,6 +1069,26 @@ pub mod pallet {
 		MessageAccepted { lane_id: LaneId, nonce: MessageNonce },
 		/// Messages in the inclusive range have been delivered to the bridged chain.
 		MessagesDelivered { lane_id: LaneId, messages: DeliveredMessages },
+		/// Lane operating mode has been updated.
+		LaneOperatingModeUpdated { lane_id: LaneId, operating_mode: MessagesOperatingMode },
+		/// Lane has been opened in the lane registry.
+		LaneOpened { lane_id: LaneId },
+		/// Configuration of the opened lane has been updated.
+		LaneConfigUpdated { lane_id: LaneId },
+		/// Lane has been switched to the closing state and doesn't accept new messages anymore.
+		LaneClosing { lane_id: LaneId },
+		/// All messages of the closing lane have been confirmed and it has been removed from the
+		/// lane registry.
+		LaneClosed { lane_id: LaneId },
+		/// Expired message has been cancelled. Its fee is refunded to the sender once the
+		/// delivery is confirmed, unless the message has been dispatched at the bridged chain.
+		MessageCancelled { lane_id: LaneId, nonce: MessageNonce },
+		/// Fee of the cancelled message has been refunded to the sender.
+		MessageFeeRefunded { lane_id: LaneId, nonce: MessageNonce },
+		/// Dispatch of the previously failed inbound message has been retried.
+		MessageDispatchRetried { lane_id: LaneId, nonce: MessageNonce, dispatch_result: bool },
+		/// Response of the message dispatcher at the bridged chain has been received.
+		MessageResponseReceived { lane_id: LaneId, nonce: MessageNonce, response: Vec<u8> },
 	}
 
 	#[pallet::error]
@@ -663,10 +1105,18 @@ pub mod pallet {
 		FailedToWithdrawMessageFee,
 		/// The transaction brings too many messages.
 		TooManyMessagesInTheProof,
-		/// Invalid messages has been submitted.
+		/// Messages proof can't be verified at the moment (e.g. because the bridged header
+		/// is unknown).
 		InvalidMessagesProof,
-		/// Invalid messages delivery proof has been submitted.
+		/// Messages delivery proof can't be verified at the moment (e.g. because the bridged
+		/// header is unknown).
 		InvalidMessagesDeliveryProof,
+		/// The storage proof doesn't have the state root of the bridged header.
+		StorageRootMismatch,
+		/// The storage proof can't be read or misses some values that it must prove.
+		InvalidStorageProof,
+		/// Data in the proof can't be decoded or doesn't match the call arguments.
+		InvalidProvedData,
 		/// The bridged chain has invalid `UnrewardedRelayers` in its storage (fatal for the lane).
 		InvalidUnrewardedRelayers,
 		/// The relayer has declared invalid unrewarded relayers state in the
@@ -679,6 +1129,30 @@ pub mod pallet {
 		/// The number of actually confirmed messages is going to be larger than the number of
 		/// messages in the proof. This may mean that this or bridged chain storage is corrupted.
 		TryingToConfirmMoreMessagesThanExpected,
+		/// Lane is not in Normal operating mode.
+		LaneNotOperatingNormally,
+		/// Lane is halted.
+		LaneHalted,
+		/// The lane is already registered in the lane registry.
+		LaneIsAlreadyRegistered,
+		/// The lane is not opened in the lane registry.
+		LaneIsNotOpened,
+		/// The message someone is trying to work with is cancelled.
+		MessageIsCancelled,
+		/// The message someone is trying to cancel has been sent without time-to-live.
+		MessageHasNoTtl,
+		/// The message someone is trying to cancel is not yet expired.
+		MessageIsNotExpired,
+		/// Only the message sender may cancel the message.
+		NotMessageSender,
+		/// There's no failed dispatch of given inbound message in the retry queue (or it has
+		/// expired).
+		FailedDispatchNotFound,
+		/// Only the retry account of the failed message dispatch may retry it.
+		NotDispatchRetryAccount,
+		/// The weight, declared by the retry transaction submitter, is not enough to dispatch
+		/// the message.
+		InsufficientDispatchWeight,
 		/// Error generated by the `OwnedBridgeModule` trait.
 		BridgeModule(bp_runtime::OwnedBridgeModuleError),
 	}
@@ -701,6 +1175,25 @@ pub mod pallet {
 	pub type PalletOperatingMode<T: Config<I>, I: 'static = ()> =
 		StorageValue<_, MessagesOperatingMode, ValueQuery>;
 
+	/// Map of lane id => lane operating mode.
+	///
+	/// Lane operating mode is checked in addition to the pallet operating mode, so it may only be
+	/// used to restrict operations at given lane. Lanes are operating normally by default.
+	#[pallet::storage]
+	#[pallet::getter(fn lane_operating_mode)]
+	pub type LaneOperatingModes<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, LaneId, MessagesOperatingMode, ValueQuery>;
+
+	/// Map of lane id => registered lane.
+	///
+	/// The registry is only consulted by the runtime `LaneMessageVerifier` (see
+	/// `Pallet::verify_registered_lane_message`), so runtimes that are not using it may accept
+	/// messages at lanes that are missing from the registry.
+	#[pallet::storage]
+	#[pallet::getter(fn registered_lane)]
+	pub type LaneRegistry<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, LaneId, RegisteredLane<T::AccountId>>;
+
 	/// Map of lane id => inbound lane data.
 	#[pallet::storage]
 	pub type InboundLanes<T: Config<I>, I: 'static = ()> =
@@ -716,12 +1209,82 @@ pub mod pallet {
 	pub type OutboundMessages<T: Config<I>, I: 'static = ()> =
 		StorageMap<_, Blake2_128Concat, MessageKey, StoredMessageData<T, I>>;
 
+	/// Inbound messages which dispatch has failed and that may be retried.
+	#[pallet::storage]
+	pub type FailedDispatches<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		MessageKey,
+		FailedMessageDispatch<
+			T::AccountId,
+			T::BlockNumber,
+			BoundedVec<u8, T::MaxFailedDispatchPayloadSize>,
+		>,
+	>;
+
+	/// Map of lane id => nonces of failed inbound message dispatches (oldest first), along
+	/// with the number of the block where the dispatch has failed.
+	#[pallet::storage]
+	pub type FailedDispatchesQueue<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		LaneId,
+		BoundedVec<(MessageNonce, T::BlockNumber), T::MaxFailedDispatchesAtInboundLane>,
+		ValueQuery,
+	>;
+
+	/// Dispatch responses of inbound messages, which delivery is not yet confirmed at the
+	/// bridged chain.
+	///
+	/// Responses are relayed back to the bridged chain in the messages delivery proof and are
+	/// removed once the bridged chain confirms that it knows about the message delivery.
+	#[pallet::storage]
+	pub type InboundMessageResponses<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, MessageKey, BoundedVec<u8, T::MaxMessageResponseSize>>;
+
+	/// Statuses of outbound messages.
+	///
+	/// Once the bridged chain knows that the message delivery has been confirmed (see
+	/// `LatestConfirmedNonceAtBridgedChain`), the message status is changed to `Confirmed`. It
+	/// is removed from this map after `ConfirmedMessageStatusLifetime` blocks.
+	#[pallet::storage]
+	pub type OutboundMessageStatuses<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, MessageKey, MessageStatus<T::BlockNumber>>;
+
+	/// Map of lane id => latest nonce of outbound message, which `Confirmed` status has been
+	/// removed from the `OutboundMessageStatuses` map.
+	///
+	/// If there's no entry for the lane, statuses of all messages up to the
+	/// `LatestConfirmedNonceAtBridgedChain` have already been removed.
+	#[pallet::storage]
+	pub type LatestPrunedMessageStatusNonce<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, LaneId, MessageNonce, OptionQuery>;
+
+	/// Map of lane id => latest nonce of outbound message, which delivery confirmation is known
+	/// to the bridged chain.
+	///
+	/// The value is updated from the inbound lane state of the bridged chain, received in the
+	/// messages delivery proof.
+	#[pallet::storage]
+	pub type LatestConfirmedNonceAtBridgedChain<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, LaneId, MessageNonce, ValueQuery>;
+
+	/// Senders of queued outbound messages that have been sent with time-to-live.
+	///
+	/// Only the sender of the message may cancel it when it expires, so we only need this for
+	/// messages with time-to-live.
+	#[pallet::storage]
+	pub type OutboundMessageSenders<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, MessageKey, T::AccountId>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
 		/// Initial pallet operating mode.
 		pub operating_mode: MessagesOperatingMode,
 		/// Initial pallet owner.
 		pub owner: Option<T::AccountId>,
+		/// Lanes that are opened at genesis.
+		pub opened_lanes: Vec<(LaneId, LaneConfig<T::AccountId>)>,
 		/// Dummy marker.
 		pub phantom: sp_std::marker::PhantomData<I>,
 	}
@@ -732,6 +1295,7 @@ pub mod pallet {
 			Self {
 				operating_mode: Default::default(),
 				owner: Default::default(),
+				opened_lanes: Default::default(),
 				phantom: Default::default(),
 			}
 		}
@@ -744,10 +1308,29 @@ pub mod pallet {
 			if let Some(ref owner) = self.owner {
 				PalletOwner::<T, I>::put(owner);
 			}
+			for (lane_id, config) in &self.opened_lanes {
+				LaneRegistry::<T, I>::insert(
+					lane_id,
+					RegisteredLane { config: config.clone(), state: LaneState::Opened },
+				);
+			}
 		}
 	}
 
 	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Get status of the outbound message with given nonce.
+		///
+		/// Returns `None` if the message hasn't been sent yet, if its status is not tracked
+		/// (i.e. it has been sent before status tracking has been introduced) or if the message
+		/// has been confirmed more than `ConfirmedMessageStatusLifetime` blocks ago and its
+		/// status has already been pruned.
+		pub fn message_status(
+			lane: LaneId,
+			nonce: MessageNonce,
+		) -> Option<MessageStatus<T::BlockNumber>> {
+			OutboundMessageStatuses::<T, I>::get(MessageKey { lane_id: lane, nonce })
+		}
+
 		/// Get stored data of the outbound message with given nonce.
 		pub fn outbound_message_data(
 			lane: LaneId,
@@ -762,14 +1345,62 @@ pub mod pallet {
 			payload: MessagePayload,
 			outbound_details: OutboundMessageDetails<T::InboundMessageFee>,
 		) -> InboundMessageDetails {
+			let retry_payload = failed_dispatch_payload::<T, I>(&payload);
 			let mut dispatch_message = DispatchMessage {
 				key: MessageKey { lane_id: lane, nonce: outbound_details.nonce },
-				data: MessageData { payload, fee: outbound_details.delivery_and_dispatch_fee }
-					.into(),
+				data: MessageData::new(payload, outbound_details.delivery_and_dispatch_fee).into(),
+			};
+			let (dispatch_weight, _) = message_dispatch_weight_and_retry_data::<T, I>(
+				retry_payload,
+				&mut dispatch_message,
+			);
+			InboundMessageDetails { dispatch_weight }
+		}
+
+		/// Returns weight of the `verify_registered_lane_message` call.
+		///
+		/// The runtime `LaneMessageVerifier` that calls `verify_registered_lane_message` must
+		/// include this weight in its `verify_message_weight`.
+		pub fn verify_registered_lane_message_weight() -> Weight {
+			// we're reading the lane configuration with at most `MAX_LANE_ALLOWED_SENDERS` senders
+			T::DbWeight::get().reads(1)
+		}
+
+		/// Verify outbound message against the lane registry.
+		///
+		/// The `sender` is `None` if the message is sent by non-signed origin. This method is
+		/// supposed to be called by the runtime `LaneMessageVerifier`.
+		pub fn verify_registered_lane_message(
+			sender: Option<&T::AccountId>,
+			lane: &LaneId,
+			lane_outbound_data: &OutboundLaneData,
+			payload_size: u32,
+		) -> Result<(), &'static str> {
+			let config = match LaneRegistry::<T, I>::get(lane) {
+				Some(registered_lane) if registered_lane.state == LaneState::Opened =>
+					registered_lane.config,
+				_ => return Err(LANE_IS_NOT_OPENED),
 			};
-			InboundMessageDetails {
-				dispatch_weight: T::MessageDispatch::dispatch_weight(&mut dispatch_message),
+
+			let pending_messages = lane_outbound_data
+				.latest_generated_nonce
+				.saturating_sub(lane_outbound_data.latest_received_nonce);
+			if pending_messages >= config.max_pending_messages {
+				return Err(TOO_MANY_PENDING_MESSAGES_AT_LANE)
+			}
+
+			if payload_size > config.max_payload_size {
+				return Err(MESSAGE_IS_TOO_LARGE_FOR_LANE)
+			}
+
+			if let Some(ref allowed_senders) = config.allowed_senders {
+				match sender {
+					Some(sender) if allowed_senders.contains(sender) => (),
+					_ => return Err(SENDER_IS_NOT_ALLOWED_AT_LANE),
+				}
 			}
+
+			Ok(())
 		}
 	}
 }
@@ -793,21 +1424,30 @@ where
 		message: T::OutboundPayload,
 		delivery_and_dispatch_fee: T::OutboundMessageFee,
 	) -> Result<SendMessageArtifacts, Self::Error> {
-		crate::send_message::<T, I>(sender, lane, message, delivery_and_dispatch_fee)
+		crate::send_message::<T, I>(sender, lane, message, delivery_and_dispatch_fee, None)
 	}
 }
 
+/// Returns pre-dispatch weight of the send-message transaction.
+fn send_message_weight<T: Config<I>, I: 'static>(payload: &T::OutboundPayload) -> Weight {
+	let db_weight = T::DbWeight::get();
+	T::WeightInfo::send_message_weight(payload, db_weight)
+		.saturating_add(T::LaneMessageVerifier::verify_message_weight(db_weight))
+}
+
 /// Function that actually sends message.
 fn send_message<T: Config<I>, I: 'static>(
 	submitter: T::Origin,
 	lane_id: LaneId,
 	payload: T::OutboundPayload,
 	delivery_and_dispatch_fee: T::OutboundMessageFee,
+	sender_and_ttl: Option<(T::AccountId, T::BlockNumber)>,
 ) -> sp_std::result::Result<
 	SendMessageArtifacts,
 	sp_runtime::DispatchErrorWithPostInfo<PostDispatchInfo>,
 > {
 	ensure_normal_operating_mode::<T, I>()?;
+	ensure_lane_normal_operating_mode::<T, I>(&lane_id)?;
 
 	// the most lightweigh check is the message size check
 	ensure!(
@@ -816,7 +1456,7 @@ fn send_message<T: Config<I>, I: 'static>(
 	);
 
 	// initially, actual (post-dispatch) weight is equal to pre-dispatch weight
-	let mut actual_weight = T::WeightInfo::send_message_weight(&payload, T::DbWeight::get());
+	let mut actual_weight = send_message_weight::<T, I>(&payload);
 
 	// let's first check if message can be delivered to target chain
 	T::TargetHeaderChain::verify_message(&payload).map_err(|err| {
@@ -870,8 +1510,23 @@ fn send_message<T: Config<I>, I: 'static>(
 	// finally, save message in outbound storage and emit event
 	let encoded_payload = payload.encode();
 	let encoded_payload_len = encoded_payload.len();
-	let nonce =
-		lane.send_message(MessageData { payload: encoded_payload, fee: delivery_and_dispatch_fee });
+	let expires_at = sender_and_ttl.as_ref().map(|(_, ttl)| {
+		let current_block: u64 = frame_system::Pallet::<T>::block_number().unique_saturated_into();
+		current_block.saturating_add((*ttl).unique_saturated_into())
+	});
+	let nonce = lane.send_message(MessageData {
+		payload: encoded_payload,
+		fee: delivery_and_dispatch_fee,
+		expires_at,
+		is_cancelled: false,
+	});
+	if let Some((sender, _)) = sender_and_ttl {
+		OutboundMessageSenders::<T, I>::insert(MessageKey { lane_id, nonce }, sender);
+	}
+	OutboundMessageStatuses::<T, I>::insert(
+		MessageKey { lane_id, nonce },
+		MessageStatus::Pending { sent_at: frame_system::Pallet::<T>::block_number() },
+	);
 	// Guaranteed to be called outside only when the message is accepted.
 	// We assume that the maximum weight call back used is `single_message_callback_overhead`, so do
 	// not perform complex db operation in callback. If you want to, put these magic logic in
@@ -968,6 +1623,62 @@ fn ensure_normal_operating_mode<T: Config<I>, I: 'static>() -> Result<(), Error<
 	Err(Error::<T, I>::NotOperatingNormally)
 }
 
+/// Ensure that the lane is in normal operational mode.
+fn ensure_lane_normal_operating_mode<T: Config<I>, I: 'static>(
+	lane_id: &LaneId,
+) -> Result<(), Error<T, I>> {
+	if LaneOperatingModes::<T, I>::get(lane_id) ==
+		MessagesOperatingMode::Basic(BasicOperatingMode::Normal)
+	{
+		return Ok(())
+	}
+
+	Err(Error::<T, I>::LaneNotOperatingNormally)
+}
+
+/// Remove the closing lane from the lane registry if all its messages have been confirmed.
+fn close_lane_if_drained<T: Config<I>, I: 'static>(lane_id: &LaneId) {
+	let is_closing = LaneRegistry::<T, I>::get(lane_id)
+		.map(|registered_lane| registered_lane.state == LaneState::Closing)
+		.unwrap_or(false);
+	if !is_closing {
+		return
+	}
+
+	let lane_data = OutboundLanes::<T, I>::get(lane_id);
+	if lane_data.latest_received_nonce < lane_data.latest_generated_nonce {
+		return
+	}
+
+	LaneRegistry::<T, I>::remove(lane_id);
+	log::info!(target: LOG_TARGET, "Closed lane {:?}.", lane_id);
+	Pallet::<T, I>::deposit_event(Event::LaneClosed { lane_id: *lane_id });
+}
+
+/// Ensure that the lane is not halted.
+fn ensure_lane_not_halted<T: Config<I>, I: 'static>(lane_id: &LaneId) -> Result<(), Error<T, I>> {
+	if LaneOperatingModes::<T, I>::get(lane_id).is_halted() {
+		return Err(Error::<T, I>::LaneHalted)
+	}
+
+	Ok(())
+}
+
+/// Convert messages (delivery) proof verification error into the pallet error.
+///
+/// Errors that don't prove that the proof is invalid are converted into the `other` error.
+fn proof_verification_error<T: Config<I>, I: 'static>(
+	error: VerificationError,
+	other: Error<T, I>,
+) -> Error<T, I> {
+	match error {
+		VerificationError::StorageRootMismatch => Error::<T, I>::StorageRootMismatch,
+		VerificationError::InvalidStorageProof => Error::<T, I>::InvalidStorageProof,
+		VerificationError::InvalidProvedData => Error::<T, I>::InvalidProvedData,
+		VerificationError::Other(_) => other,
+	}
+}
+
 /// Creates new inbound lane object, backed by runtime storage.
 fn inbound_lane<T: Config<I>, I: 'static>(
 	lane_id: LaneId,
@@ -1038,6 +1749,12 @@ impl<T: Config<I>, I: 'static> InboundLaneStorage for RuntimeInboundLaneStorage<
 		) = Some(data.clone());
 		InboundLanes::<T, I>::insert(self.lane_id, StoredInboundLaneData::<T, I>(data))
 	}
+
+	fn remove_message_response(&mut self, nonce: MessageNonce) {
+		if T::MaxMessageResponseSize::get() != 0 {
+			InboundMessageResponses::<T, I>::remove(MessageKey { lane_id: self.lane_id, nonce });
+		}
+	}
 }
 
 /// Runtime outbound lane storage.
@@ -1076,54 +1793,358 @@ impl<T: Config<I>, I: 'static> OutboundLaneStorage for RuntimeOutboundLaneStorag
 	}
 
 	fn remove_message(&mut self, nonce: &MessageNonce) {
-		OutboundMessages::<T, I>::remove(MessageKey { lane_id: self.lane_id, nonce: *nonce });
+		let message_key = MessageKey { lane_id: self.lane_id, nonce: *nonce };
+		OutboundMessages::<T, I>::remove(&message_key);
+		OutboundMessageSenders::<T, I>::remove(message_key);
 	}
 }
 
-/// Verify messages proof and return proved messages with decoded payload.
-fn verify_and_decode_messages_proof<Chain: SourceHeaderChain<Fee>, Fee, DispatchPayload: Decode>(
-	proof: Chain::MessagesProof,
-	messages_count: u32,
-) -> Result<ProvedMessages<DispatchMessage<DispatchPayload, Fee>>, Chain::Error> {
-	// `receive_messages_proof` weight formula and `MaxUnconfirmedMessagesAtInboundLane` check
-	// guarantees that the `message_count` is sane and Vec<Message> may be allocated.
-	// (tx with too many messages will either be rejected from the pool, or will fail earlier)
-	Chain::verify_messages_proof(proof, messages_count).map(|messages_by_lane| {
-		messages_by_lane
-			.into_iter()
-			.map(|(lane, lane_data)| {
-				(
-					lane,
-					ProvedLaneMessages {
-						lane_state: lane_data.lane_state,
-						messages: lane_data.messages.into_iter().map(Into::into).collect(),
-					},
-				)
-			})
-			.collect()
-	})
+/// Update statuses of messages, which delivery has been confirmed.
+fn note_delivered_messages<T: Config<I>, I: 'static>(
+	lane_id: &LaneId,
+	delivered_messages: &DeliveredMessages,
+) {
+	let now = frame_system::Pallet::<T>::block_number();
+	for nonce in delivered_messages.begin..=delivered_messages.end {
+		OutboundMessageStatuses::<T, I>::mutate(
+			MessageKey { lane_id: *lane_id, nonce },
+			|status| {
+				if let Some(
+					MessageStatus::Pending { sent_at } | MessageStatus::Cancelled { sent_at, .. },
+				) = *status
+				{
+					*status = Some(MessageStatus::Delivered {
+						sent_at,
+						delivered_at: now,
+						dispatch_result: delivered_messages.message_dispatch_result(nonce),
+					});
+				}
+			},
+		);
+	}
 }
 
-#[cfg(test)]
-mod tests {
-	use super::*;
-	use crate::mock::{
-		message, message_payload, run_test, unrewarded_relayer, Balance, Event as TestEvent,
-		Origin, TestMessageDeliveryAndDispatchPayment, TestMessagesDeliveryProof,
-		TestMessagesParameter, TestMessagesProof, TestOnDeliveryConfirmed1,
-		TestOnDeliveryConfirmed2, TestOnMessageAccepted, TestRuntime, TokenConversionRate,
-		MAX_OUTBOUND_PAYLOAD_SIZE, PAYLOAD_REJECTED_BY_TARGET_CHAIN, REGULAR_PAYLOAD, TEST_LANE_ID,
-		TEST_RELAYER_A, TEST_RELAYER_B,
-	};
-	use bp_messages::{UnrewardedRelayer, UnrewardedRelayersState};
-	use bp_test_utils::generate_owned_bridge_module_tests;
-	use frame_support::{
-		assert_noop, assert_ok,
-		storage::generator::{StorageMap, StorageValue},
-		weights::Weight,
-	};
-	use frame_system::{EventRecord, Pallet as System, Phase};
-	use sp_runtime::DispatchError;
+/// Refund fees of cancelled messages, which delivery has been confirmed.
+///
+/// The fee is only refunded if the message has not been dispatched at the bridged chain.
+/// Otherwise, the original message has been delivered using the proof that has been generated
+/// before cancellation, so the fee is kept and paid to the relayer. The original message, which
+/// dispatch has failed, but may be retried at the bridged chain, is also reported as dispatched,
+/// so its fee is not refunded either. Returns number of messages with refunded fees.
+fn refund_cancelled_messages<T: Config<I>, I: 'static>(
+	lane_id: &LaneId,
+	delivered_messages: &DeliveredMessages,
+) -> MessageNonce {
+	let mut refunded_messages = 0;
+	for nonce in delivered_messages.begin..=delivered_messages.end {
+		let message_key = MessageKey { lane_id: *lane_id, nonce };
+		let mut message_data = match OutboundMessages::<T, I>::get(&message_key) {
+			Some(message_data) if message_data.is_cancelled => message_data,
+			_ => continue,
+		};
+		if delivered_messages.message_dispatch_result(nonce) {
+			log::trace!(
+				target: LOG_TARGET,
+				"Cancelled message {:?}/{} has been dispatched at the bridged chain. Not refunding",
+				lane_id,
+				nonce,
+			);
+			continue
+		}
+		let sender = match OutboundMessageSenders::<T, I>::get(&message_key) {
+			Some(sender) => sender,
+			None => continue,
+		};
+
+		if let Err(err) = T::MessageDeliveryAndDispatchPayment::refund_delivery_and_dispatch_fee(
+			&sender,
+			&message_data.fee,
+		) {
+			log::error!(
+				target: LOG_TARGET,
+				"Failed to refund fee {:?} of the cancelled message {:?}/{} to {:?}: {:?}",
+				message_data.fee,
+				lane_id,
+				nonce,
+				sender,
+				err,
+			);
+			continue
+		}
+
+		// refunded fee must not be paid to the relayer
+		message_data.fee = Zero::zero();
+		OutboundMessages::<T, I>::insert(&message_key, message_data);
+		refunded_messages += 1;
+		Pallet::<T, I>::deposit_event(Event::MessageFeeRefunded { lane_id: *lane_id, nonce });
+	}
+	refunded_messages
+}
+
+/// Update statuses of messages, which delivery confirmation is known to the bridged chain, and
+/// prune statuses of messages that have been confirmed more than `ConfirmedMessageStatusLifetime`
+/// blocks ago.
+///
+/// At most `max_statuses_to_update` statuses are updated and at most `max_statuses_to_update`
+/// statuses are pruned, so that the weight of this function is bounded. Remaining statuses are
+/// updated (pruned) by following calls and until then, their status is still reported as
+/// `Delivered` (`Confirmed`).
+fn note_confirmed_at_bridged_chain<T: Config<I>, I: 'static>(
+	lane_id: &LaneId,
+	latest_confirmed_nonce: MessageNonce,
+	max_statuses_to_update: MessageNonce,
+) {
+	let now = frame_system::Pallet::<T>::block_number();
+	let prev_latest_confirmed_nonce = LatestConfirmedNonceAtBridgedChain::<T, I>::get(lane_id);
+
+	// statuses are confirmed in the nonces order, so we only need to check the oldest ones
+	let mut latest_pruned_nonce =
+		LatestPrunedMessageStatusNonce::<T, I>::get(lane_id).unwrap_or(prev_latest_confirmed_nonce);
+	let prev_latest_pruned_nonce = latest_pruned_nonce;
+	while latest_pruned_nonce < prev_latest_confirmed_nonce &&
+		latest_pruned_nonce - prev_latest_pruned_nonce < max_statuses_to_update
+	{
+		let message_key = MessageKey { lane_id: *lane_id, nonce: latest_pruned_nonce + 1 };
+		if let Some(MessageStatus::Confirmed { confirmed_at, .. }) =
+			OutboundMessageStatuses::<T, I>::get(&message_key)
+		{
+			if confirmed_at.saturating_add(T::ConfirmedMessageStatusLifetime::get()) >= now {
+				break
+			}
+		}
+
+		OutboundMessageStatuses::<T, I>::remove(message_key);
+		latest_pruned_nonce += 1;
+	}
+
+	// the bridged chain may only know about confirmations of messages that we have already
+	// received delivery proofs for, so every status is updated at most once here
+	let latest_confirmed_nonce = sp_std::cmp::min(
+		sp_std::cmp::min(
+			latest_confirmed_nonce,
+			OutboundLanes::<T, I>::get(lane_id).latest_received_nonce,
+		),
+		prev_latest_confirmed_nonce.saturating_add(max_statuses_to_update),
+	);
+	if latest_confirmed_nonce > prev_latest_confirmed_nonce {
+		for nonce in prev_latest_confirmed_nonce + 1..=latest_confirmed_nonce {
+			OutboundMessageStatuses::<T, I>::mutate(
+				MessageKey { lane_id: *lane_id, nonce },
+				|status| {
+					if let Some(MessageStatus::Delivered {
+						sent_at,
+						delivered_at,
+						dispatch_result,
+					}) = *status
+					{
+						*status = Some(MessageStatus::Confirmed {
+							sent_at,
+							delivered_at,
+							confirmed_at: now,
+							dispatch_result,
+						});
+					}
+				},
+			);
+		}
+		LatestConfirmedNonceAtBridgedChain::<T, I>::insert(lane_id, latest_confirmed_nonce);
+	} else if latest_pruned_nonce == prev_latest_pruned_nonce {
+		return
+	}
+
+	LatestPrunedMessageStatusNonce::<T, I>::insert(lane_id, latest_pruned_nonce);
+}
+
+/// Remember dispatch response of the inbound message, so that it may be relayed back to the
+/// bridged chain.
+///
+/// Returns false if responses are disabled or the response is too large and has been dropped.
+fn save_message_response<T: Config<I>, I: 'static>(
+	message_key: MessageKey,
+	response: Vec<u8>,
+) -> bool {
+	if T::MaxMessageResponseSize::get() == 0 {
+		return false
+	}
+
+	match BoundedVec::<u8, T::MaxMessageResponseSize>::try_from(response) {
+		Ok(response) => {
+			InboundMessageResponses::<T, I>::insert(message_key, response);
+			true
+		},
+		Err(response) => {
+			log::trace!(
+				target: LOG_TARGET,
+				"Dropped too large ({} bytes) dispatch response of message {:?}/{}",
+				response.len(),
+				message_key.lane_id,
+				message_key.nonce,
+			);
+			false
+		},
+	}
+}
+
+/// Handle dispatch responses of messages, which delivery has just been confirmed.
+///
+/// Responses of messages that have been confirmed before are ignored, so every response is
+/// handled at most once. Responses that are larger than `MaxMessageResponseSize` are also
+/// ignored. Returns total weight, consumed by the `OnMessageResponse` callback and events.
+fn handle_messages_responses<T: Config<I>, I: 'static>(
+	lane_id: &LaneId,
+	confirmed_messages: &DeliveredMessages,
+	responses: MessagesResponses,
+) -> Weight {
+	let max_response_size = T::MaxMessageResponseSize::get();
+	let mut total_weight: Weight = 0;
+	for (nonce, response) in responses {
+		if !confirmed_messages.contains_message(nonce) {
+			continue
+		}
+		if response.len() > max_response_size as usize {
+			log::trace!(
+				target: LOG_TARGET,
+				"Ignoring too large dispatch response of message {:?}/{}: {} vs {}",
+				lane_id,
+				nonce,
+				response.len(),
+				max_response_size,
+			);
+			continue
+		}
+
+		total_weight = total_weight
+			.saturating_add(T::OnMessageResponse::on_message_response(lane_id, nonce, &response))
+			.saturating_add(T::WeightInfo::message_response_event_overhead(
+				response.len() as u32,
+				T::DbWeight::get(),
+			));
+		Pallet::<T, I>::deposit_event(Event::MessageResponseReceived {
+			lane_id: *lane_id,
+			nonce,
+			response,
+		});
+	}
+	total_weight
+}
+
+/// Returns copy of the encoded inbound message payload, if it may be kept in the retry queue.
+fn failed_dispatch_payload<T: Config<I>, I: 'static>(
+	encoded_payload: &MessagePayload,
+) -> Option<BoundedVec<u8, T::MaxFailedDispatchPayloadSize>> {
+	// we only keep a copy of the encoded payload if the message may be retried
+	if T::MaxFailedDispatchesAtInboundLane::get() == 0 ||
+		encoded_payload.len() > T::MaxFailedDispatchPayloadSize::get() as usize
+	{
+		return None
+	}
+
+	BoundedVec::try_from(encoded_payload.clone()).ok()
+}
+
+/// Returns weight that the relayer must declare to dispatch the inbound message, the account that
+/// may retry dispatch of the message and the copy of the encoded message payload. The latter two
+/// are kept in the retry queue if the message dispatch fails.
+///
+/// If the message may be kept in the retry queue, the weight includes the weight of the queue
+/// update. The retry data is `None` if the message can't be retried.
+///
+/// The `MessageDispatch::dispatch_retry_account` is called after the
+/// `MessageDispatch::dispatch_weight`, so the dispatcher may reuse the data that it has
+/// decoded when computing the weight.
+fn message_dispatch_weight_and_retry_data<T: Config<I>, I: 'static>(
+	retry_payload: Option<BoundedVec<u8, T::MaxFailedDispatchPayloadSize>>,
+	message: &mut DispatchMessage<T::InboundPayload, T::InboundMessageFee>,
+) -> (Weight, Option<(T::AccountId, BoundedVec<u8, T::MaxFailedDispatchPayloadSize>)>) {
+	// cancelled messages are not dispatched at all
+	if message.data.is_cancelled {
+		return (0, None)
+	}
+
+	let dispatch_weight = T::MessageDispatch::dispatch_weight(message);
+	let retry_data = retry_payload.and_then(|retry_payload| {
+		let retry_account = T::MessageDispatch::dispatch_retry_account(message)?;
+		Some((retry_account, retry_payload))
+	});
+	let retry_overhead = if retry_data.is_some() {
+		T::WeightInfo::single_message_retry_overhead(T::DbWeight::get())
+	} else {
+		0
+	};
+	(dispatch_weight.saturating_add(retry_overhead), retry_data)
+}
+
+/// Remember failed message dispatch, so that it may be retried later.
+///
+/// The oldest entry of the lane queue is removed if it's expired or if the queue is full. We
+/// never remove more than one entry here, so the weight of this function is constant. It is
+/// covered by the `WeightInfoExt::single_message_retry_overhead`.
+fn enqueue_failed_dispatch<T: Config<I>, I: 'static>(
+	message_key: MessageKey,
+	retry_account: T::AccountId,
+	payload: BoundedVec<u8, T::MaxFailedDispatchPayloadSize>,
+) {
+	let now = frame_system::Pallet::<T>::block_number();
+	FailedDispatchesQueue::<T, I>::mutate(message_key.lane_id, |queue| {
+		if let Some((oldest_nonce, oldest_failed_at)) = queue.first().cloned() {
+			let is_expired =
+				oldest_failed_at.saturating_add(T::FailedDispatchLifetime::get()) < now;
+			let is_queue_full = queue.len() >= T::MaxFailedDispatchesAtInboundLane::get() as usize;
+			if is_expired || is_queue_full {
+				queue.remove(0);
+				FailedDispatches::<T, I>::remove(MessageKey {
+					lane_id: message_key.lane_id,
+					nonce: oldest_nonce,
+				});
+				log::trace!(
+					target: LOG_TARGET,
+					"Removed failed dispatch of message {:?}/{} from the retry queue",
+					message_key.lane_id,
+					oldest_nonce,
+				);
+			}
+		}
+
+		if queue.try_push((message_key.nonce, now)).is_ok() {
+			FailedDispatches::<T, I>::insert(
+				message_key,
+				FailedMessageDispatch { retry_account, failed_at: now, payload },
+			);
+		}
+	});
+}
+
+/// Remove failed message dispatch from the retry queue.
+fn remove_failed_dispatch<T: Config<I>, I: 'static>(message_key: MessageKey) {
+	FailedDispatches::<T, I>::remove(&message_key);
+	FailedDispatchesQueue::<T, I>::mutate(message_key.lane_id, |queue| {
+		queue.retain(|(nonce, _)| *nonce != message_key.nonce)
+	});
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{
+		message, message_payload, run_test, unrewarded_relayer, Balance,
+		ConfirmedMessageStatusLifetime, Event as TestEvent, Origin,
+		TestMessageDeliveryAndDispatchPayment, TestMessageDispatch, TestMessagesDeliveryProof,
+		TestMessagesParameter, TestMessagesProof, TestOnDeliveryConfirmed1,
+		TestOnDeliveryConfirmed2, TestOnMessageAccepted, TestOnMessageResponse, TestRuntime,
+		TestTargetHeaderChain, TokenConversionRate, MAX_OUTBOUND_PAYLOAD_SIZE,
+		PAYLOAD_REJECTED_BY_TARGET_CHAIN, REGULAR_PAYLOAD, TEST_DISPATCH_RETRY_ACCOUNT,
+		TEST_LANE_ID, TEST_RELAYER_A, TEST_RELAYER_B,
+	};
+	use bp_messages::{UnrewardedRelayer, UnrewardedRelayersState};
+	use bp_test_utils::generate_owned_bridge_module_tests;
+	use frame_support::{
+		assert_noop, assert_ok,
+		storage::generator::{StorageMap, StorageValue},
+		traits::{GetStorageVersion, StorageVersion},
+		weights::Weight,
+	};
+	use frame_system::{EventRecord, Pallet as System, Phase};
+	use sp_runtime::DispatchError;
 
 	fn get_ready_for_events() {
 		System::<TestRuntime>::set_block_number(1);
@@ -1436,96 +2457,160 @@ mod tests {
 	}
 
 	#[test]
-	fn send_message_works() {
+	fn lane_operating_mode_may_be_updated_by_owner_or_root() {
 		run_test(|| {
-			send_regular_message();
-		});
-	}
+			let halted = MessagesOperatingMode::Basic(BasicOperatingMode::Halted);
 
-	#[test]
-	fn send_message_rejects_too_large_message() {
-		run_test(|| {
-			let mut message_payload = message_payload(1, 0);
-			// the payload isn't simply extra, so it'll definitely overflow
-			// `MAX_OUTBOUND_PAYLOAD_SIZE` if we add `MAX_OUTBOUND_PAYLOAD_SIZE` bytes to extra
-			message_payload
-				.extra
-				.extend_from_slice(&[0u8; MAX_OUTBOUND_PAYLOAD_SIZE as usize]);
 			assert_noop!(
-				Pallet::<TestRuntime>::send_message(
-					Origin::signed(1),
+				Pallet::<TestRuntime>::set_lane_operating_mode(
+					Origin::signed(2),
 					TEST_LANE_ID,
-					message_payload.clone(),
-					Balance::MAX,
+					halted,
 				),
-				Error::<TestRuntime, ()>::MessageIsTooLarge,
+				DispatchError::BadOrigin,
 			);
 
-			// let's check that we're able to send `MAX_OUTBOUND_PAYLOAD_SIZE` messages
-			while message_payload.size() > MAX_OUTBOUND_PAYLOAD_SIZE {
-				message_payload.extra.pop();
-			}
-			assert_eq!(message_payload.size(), MAX_OUTBOUND_PAYLOAD_SIZE);
-			assert_ok!(Pallet::<TestRuntime>::send_message(
-				Origin::signed(1),
+			PalletOwner::<TestRuntime>::put(2);
+			get_ready_for_events();
+
+			assert_ok!(Pallet::<TestRuntime>::set_lane_operating_mode(
+				Origin::signed(2),
 				TEST_LANE_ID,
-				message_payload,
-				Balance::MAX,
-			),);
-		})
+				halted,
+			));
+			assert_eq!(Pallet::<TestRuntime>::lane_operating_mode(TEST_LANE_ID), halted);
+
+			assert_ok!(Pallet::<TestRuntime>::set_lane_operating_mode(
+				Origin::root(),
+				TEST_LANE_ID,
+				MessagesOperatingMode::RejectingOutboundMessages,
+			));
+			assert_eq!(
+				Pallet::<TestRuntime>::lane_operating_mode(TEST_LANE_ID),
+				MessagesOperatingMode::RejectingOutboundMessages,
+			);
+
+			assert_eq!(
+				System::<TestRuntime>::events(),
+				vec![
+					EventRecord {
+						phase: Phase::Initialization,
+						event: TestEvent::Messages(Event::LaneOperatingModeUpdated {
+							lane_id: TEST_LANE_ID,
+							operating_mode: halted,
+						}),
+						topics: vec![],
+					},
+					EventRecord {
+						phase: Phase::Initialization,
+						event: TestEvent::Messages(Event::LaneOperatingModeUpdated {
+							lane_id: TEST_LANE_ID,
+							operating_mode: MessagesOperatingMode::RejectingOutboundMessages,
+						}),
+						topics: vec![],
+					},
+				],
+			);
+		});
 	}
 
 	#[test]
-	fn chain_verifier_rejects_invalid_message_in_send_message() {
+	fn pallet_rejects_lane_transactions_if_lane_is_halted() {
 		run_test(|| {
-			// messages with this payload are rejected by target chain verifier
+			// send message first to be able to check that delivery_proof fails later
+			send_regular_message();
+
+			LaneOperatingModes::<TestRuntime, ()>::insert(
+				TEST_LANE_ID,
+				MessagesOperatingMode::Basic(BasicOperatingMode::Halted),
+			);
+
 			assert_noop!(
 				Pallet::<TestRuntime>::send_message(
 					Origin::signed(1),
 					TEST_LANE_ID,
-					PAYLOAD_REJECTED_BY_TARGET_CHAIN,
-					PAYLOAD_REJECTED_BY_TARGET_CHAIN.declared_weight
+					REGULAR_PAYLOAD,
+					REGULAR_PAYLOAD.declared_weight,
 				),
-				Error::<TestRuntime, ()>::MessageRejectedByChainVerifier,
+				Error::<TestRuntime, ()>::LaneNotOperatingNormally,
 			);
-		});
-	}
 
-	#[test]
-	fn lane_verifier_rejects_invalid_message_in_send_message() {
-		run_test(|| {
-			// messages with zero fee are rejected by lane verifier
 			assert_noop!(
-				Pallet::<TestRuntime>::send_message(
+				Pallet::<TestRuntime>::increase_message_fee(Origin::signed(1), TEST_LANE_ID, 1, 1,),
+				Error::<TestRuntime, ()>::LaneHalted,
+			);
+
+			assert_noop!(
+				Pallet::<TestRuntime>::receive_messages_proof(
 					Origin::signed(1),
-					TEST_LANE_ID,
-					REGULAR_PAYLOAD,
-					0
+					TEST_RELAYER_A,
+					Ok(vec![message(1, REGULAR_PAYLOAD)]).into(),
+					1,
+					REGULAR_PAYLOAD.declared_weight,
 				),
-				Error::<TestRuntime, ()>::MessageRejectedByLaneVerifier,
+				Error::<TestRuntime, ()>::LaneHalted,
+			);
+
+			assert_noop!(
+				Pallet::<TestRuntime>::receive_messages_delivery_proof(
+					Origin::signed(1),
+					TestMessagesDeliveryProof(Ok((
+						TEST_LANE_ID,
+						InboundLaneData {
+							last_confirmed_nonce: 1,
+							relayers: vec![unrewarded_relayer(1, 1, TEST_RELAYER_A)]
+								.into_iter()
+								.collect(),
+						},
+					))),
+					UnrewardedRelayersState {
+						unrewarded_relayer_entries: 1,
+						messages_in_oldest_entry: 1,
+						total_messages: 1,
+						last_delivered_nonce: 1,
+					},
+				),
+				Error::<TestRuntime, ()>::LaneHalted,
 			);
+
+			// other lanes are still operating normally
+			assert_ok!(Pallet::<TestRuntime>::send_message(
+				Origin::signed(1),
+				[0, 0, 0, 2],
+				REGULAR_PAYLOAD,
+				REGULAR_PAYLOAD.declared_weight,
+			));
 		});
 	}
 
 	#[test]
-	fn message_send_fails_if_submitter_cant_pay_message_fee() {
+	fn lane_rejects_new_messages_in_rejecting_outbound_messages_operating_mode() {
 		run_test(|| {
-			TestMessageDeliveryAndDispatchPayment::reject_payments();
+			// send message first to be able to check that delivery_proof works later
+			send_regular_message();
+
+			LaneOperatingModes::<TestRuntime, ()>::insert(
+				TEST_LANE_ID,
+				MessagesOperatingMode::RejectingOutboundMessages,
+			);
+
 			assert_noop!(
 				Pallet::<TestRuntime>::send_message(
 					Origin::signed(1),
 					TEST_LANE_ID,
 					REGULAR_PAYLOAD,
-					REGULAR_PAYLOAD.declared_weight
+					REGULAR_PAYLOAD.declared_weight,
 				),
-				Error::<TestRuntime, ()>::FailedToWithdrawMessageFee,
+				Error::<TestRuntime, ()>::LaneNotOperatingNormally,
 			);
-		});
-	}
 
-	#[test]
-	fn receive_messages_proof_works() {
-		run_test(|| {
+			assert_ok!(Pallet::<TestRuntime>::increase_message_fee(
+				Origin::signed(1),
+				TEST_LANE_ID,
+				1,
+				1,
+			));
+
 			assert_ok!(Pallet::<TestRuntime>::receive_messages_proof(
 				Origin::signed(1),
 				TEST_RELAYER_A,
@@ -1534,20 +2619,677 @@ mod tests {
 				REGULAR_PAYLOAD.declared_weight,
 			));
 
-			assert_eq!(InboundLanes::<TestRuntime>::get(TEST_LANE_ID).0.last_delivered_nonce(), 1);
+			assert_ok!(Pallet::<TestRuntime>::receive_messages_delivery_proof(
+				Origin::signed(1),
+				TestMessagesDeliveryProof(Ok((
+					TEST_LANE_ID,
+					InboundLaneData {
+						last_confirmed_nonce: 1,
+						relayers: vec![unrewarded_relayer(1, 1, TEST_RELAYER_A)]
+							.into_iter()
+							.collect(),
+					},
+				))),
+				UnrewardedRelayersState {
+					unrewarded_relayer_entries: 1,
+					messages_in_oldest_entry: 1,
+					total_messages: 1,
+					last_delivered_nonce: 1,
+				},
+			));
 		});
 	}
 
+	fn test_lane_config() -> LaneConfig<u64> {
+		LaneConfig { max_pending_messages: 2, max_payload_size: 16, allowed_senders: None }
+	}
+
 	#[test]
-	fn receive_messages_proof_updates_confirmed_message_nonce() {
+	fn lane_may_be_opened_updated_and_closed_by_owner_or_root() {
 		run_test(|| {
-			// say we have received 10 messages && last confirmed message is 8
-			InboundLanes::<TestRuntime, ()>::insert(
-				TEST_LANE_ID,
-				InboundLaneData {
-					last_confirmed_nonce: 8,
-					relayers: vec![
-						unrewarded_relayer(9, 9, TEST_RELAYER_A),
+			assert_noop!(
+				Pallet::<TestRuntime>::open_lane(
+					Origin::signed(2),
+					TEST_LANE_ID,
+					test_lane_config()
+				),
+				DispatchError::BadOrigin,
+			);
+
+			PalletOwner::<TestRuntime>::put(2);
+			get_ready_for_events();
+
+			assert_ok!(Pallet::<TestRuntime>::open_lane(
+				Origin::signed(2),
+				TEST_LANE_ID,
+				test_lane_config()
+			));
+			assert_noop!(
+				Pallet::<TestRuntime>::open_lane(Origin::root(), TEST_LANE_ID, test_lane_config()),
+				Error::<TestRuntime, ()>::LaneIsAlreadyRegistered,
+			);
+
+			let updated_config = LaneConfig {
+				allowed_senders: Some(vec![1].try_into().unwrap()),
+				..test_lane_config()
+			};
+			assert_ok!(Pallet::<TestRuntime>::update_lane_config(
+				Origin::root(),
+				TEST_LANE_ID,
+				updated_config.clone(),
+			));
+			assert_eq!(
+				Pallet::<TestRuntime>::registered_lane(TEST_LANE_ID),
+				Some(RegisteredLane { config: updated_config, state: LaneState::Opened }),
+			);
+
+			// there are no queued messages => lane is closed immediately
+			assert_ok!(Pallet::<TestRuntime>::close_lane(Origin::signed(2), TEST_LANE_ID));
+			assert_eq!(Pallet::<TestRuntime>::registered_lane(TEST_LANE_ID), None);
+			assert_noop!(
+				Pallet::<TestRuntime>::close_lane(Origin::root(), TEST_LANE_ID),
+				Error::<TestRuntime, ()>::LaneIsNotOpened,
+			);
+			assert_noop!(
+				Pallet::<TestRuntime>::update_lane_config(
+					Origin::root(),
+					TEST_LANE_ID,
+					test_lane_config()
+				),
+				Error::<TestRuntime, ()>::LaneIsNotOpened,
+			);
+
+			assert_eq!(
+				System::<TestRuntime>::events()
+					.into_iter()
+					.map(|event_record| event_record.event)
+					.collect::<Vec<_>>(),
+				vec![
+					TestEvent::Messages(Event::LaneOpened { lane_id: TEST_LANE_ID }),
+					TestEvent::Messages(Event::LaneConfigUpdated { lane_id: TEST_LANE_ID }),
+					TestEvent::Messages(Event::LaneClosing { lane_id: TEST_LANE_ID }),
+					TestEvent::Messages(Event::LaneClosed { lane_id: TEST_LANE_ID }),
+				],
+			);
+		});
+	}
+
+	#[test]
+	fn closing_lane_is_removed_from_registry_when_drained() {
+		run_test(|| {
+			assert_ok!(Pallet::<TestRuntime>::open_lane(
+				Origin::root(),
+				TEST_LANE_ID,
+				test_lane_config()
+			));
+			send_regular_message();
+
+			assert_ok!(Pallet::<TestRuntime>::close_lane(Origin::root(), TEST_LANE_ID));
+			assert_eq!(
+				Pallet::<TestRuntime>::registered_lane(TEST_LANE_ID).map(|lane| lane.state),
+				Some(LaneState::Closing),
+			);
+			assert_eq!(
+				Pallet::<TestRuntime>::verify_registered_lane_message(
+					Some(&1),
+					&TEST_LANE_ID,
+					&OutboundLanes::<TestRuntime>::get(TEST_LANE_ID),
+					0,
+				),
+				Err(LANE_IS_NOT_OPENED),
+			);
+
+			assert_ok!(Pallet::<TestRuntime>::receive_messages_delivery_proof(
+				Origin::signed(1),
+				TestMessagesDeliveryProof(Ok((
+					TEST_LANE_ID,
+					InboundLaneData {
+						last_confirmed_nonce: 1,
+						relayers: vec![unrewarded_relayer(1, 1, TEST_RELAYER_A)]
+							.into_iter()
+							.collect(),
+					},
+				))),
+				UnrewardedRelayersState {
+					unrewarded_relayer_entries: 1,
+					messages_in_oldest_entry: 1,
+					total_messages: 1,
+					last_delivered_nonce: 1,
+				},
+			));
+			assert_eq!(Pallet::<TestRuntime>::registered_lane(TEST_LANE_ID), None);
+			assert!(System::<TestRuntime>::events()
+				.iter()
+				.any(|event_record| event_record.event ==
+					TestEvent::Messages(Event::LaneClosed { lane_id: TEST_LANE_ID })));
+		});
+	}
+
+	#[test]
+	fn verify_registered_lane_message_works() {
+		run_test(|| {
+			let verify = |sender: Option<&u64>, lane: &LaneId, pending: MessageNonce, size: u32| {
+				Pallet::<TestRuntime>::verify_registered_lane_message(
+					sender,
+					lane,
+					&OutboundLaneData { latest_generated_nonce: pending, ..Default::default() },
+					size,
+				)
+			};
+
+			assert_eq!(verify(Some(&1), &TEST_LANE_ID, 0, 0), Err(LANE_IS_NOT_OPENED));
+
+			assert_ok!(Pallet::<TestRuntime>::open_lane(
+				Origin::root(),
+				TEST_LANE_ID,
+				LaneConfig {
+					allowed_senders: Some(vec![1].try_into().unwrap()),
+					..test_lane_config()
+				},
+			));
+
+			assert_eq!(verify(Some(&1), &TEST_LANE_ID, 1, 16), Ok(()));
+			assert_eq!(
+				verify(Some(&1), &TEST_LANE_ID, 2, 16),
+				Err(TOO_MANY_PENDING_MESSAGES_AT_LANE)
+			);
+			assert_eq!(verify(Some(&1), &TEST_LANE_ID, 1, 17), Err(MESSAGE_IS_TOO_LARGE_FOR_LANE));
+			assert_eq!(verify(Some(&2), &TEST_LANE_ID, 1, 16), Err(SENDER_IS_NOT_ALLOWED_AT_LANE));
+			assert_eq!(verify(None, &TEST_LANE_ID, 1, 16), Err(SENDER_IS_NOT_ALLOWED_AT_LANE));
+		});
+	}
+
+	#[test]
+	fn send_message_works() {
+		run_test(|| {
+			send_regular_message();
+		});
+	}
+
+	#[test]
+	fn send_message_rejects_too_large_message() {
+		run_test(|| {
+			let mut message_payload = message_payload(1, 0);
+			// the payload isn't simply extra, so it'll definitely overflow
+			// `MAX_OUTBOUND_PAYLOAD_SIZE` if we add `MAX_OUTBOUND_PAYLOAD_SIZE` bytes to extra
+			message_payload
+				.extra
+				.extend_from_slice(&[0u8; MAX_OUTBOUND_PAYLOAD_SIZE as usize]);
+			assert_noop!(
+				Pallet::<TestRuntime>::send_message(
+					Origin::signed(1),
+					TEST_LANE_ID,
+					message_payload.clone(),
+					Balance::MAX,
+				),
+				Error::<TestRuntime, ()>::MessageIsTooLarge,
+			);
+
+			// let's check that we're able to send `MAX_OUTBOUND_PAYLOAD_SIZE` messages
+			while message_payload.size() > MAX_OUTBOUND_PAYLOAD_SIZE {
+				message_payload.extra.pop();
+			}
+			assert_eq!(message_payload.size(), MAX_OUTBOUND_PAYLOAD_SIZE);
+			assert_ok!(Pallet::<TestRuntime>::send_message(
+				Origin::signed(1),
+				TEST_LANE_ID,
+				message_payload,
+				Balance::MAX,
+			),);
+		})
+	}
+
+	#[test]
+	fn chain_verifier_rejects_invalid_message_in_send_message() {
+		run_test(|| {
+			// messages with this payload are rejected by target chain verifier
+			assert_noop!(
+				Pallet::<TestRuntime>::send_message(
+					Origin::signed(1),
+					TEST_LANE_ID,
+					PAYLOAD_REJECTED_BY_TARGET_CHAIN,
+					PAYLOAD_REJECTED_BY_TARGET_CHAIN.declared_weight
+				),
+				Error::<TestRuntime, ()>::MessageRejectedByChainVerifier,
+			);
+		});
+	}
+
+	#[test]
+	fn lane_verifier_rejects_invalid_message_in_send_message() {
+		run_test(|| {
+			// messages with zero fee are rejected by lane verifier
+			assert_noop!(
+				Pallet::<TestRuntime>::send_message(
+					Origin::signed(1),
+					TEST_LANE_ID,
+					REGULAR_PAYLOAD,
+					0
+				),
+				Error::<TestRuntime, ()>::MessageRejectedByLaneVerifier,
+			);
+		});
+	}
+
+	#[test]
+	fn message_send_fails_if_submitter_cant_pay_message_fee() {
+		run_test(|| {
+			TestMessageDeliveryAndDispatchPayment::reject_payments();
+			assert_noop!(
+				Pallet::<TestRuntime>::send_message(
+					Origin::signed(1),
+					TEST_LANE_ID,
+					REGULAR_PAYLOAD,
+					REGULAR_PAYLOAD.declared_weight
+				),
+				Error::<TestRuntime, ()>::FailedToWithdrawMessageFee,
+			);
+		});
+	}
+
+	#[test]
+	fn receive_messages_proof_works() {
+		run_test(|| {
+			assert_ok!(Pallet::<TestRuntime>::receive_messages_proof(
+				Origin::signed(1),
+				TEST_RELAYER_A,
+				Ok(vec![message(1, REGULAR_PAYLOAD)]).into(),
+				1,
+				REGULAR_PAYLOAD.declared_weight,
+			));
+
+			assert_eq!(InboundLanes::<TestRuntime>::get(TEST_LANE_ID).0.last_delivered_nonce(), 1);
+		});
+	}
+
+	fn receive_failing_message(nonce: MessageNonce) {
+		let mut payload = REGULAR_PAYLOAD;
+		payload.dispatch_result.dispatch_result = false;
+		assert_ok!(Pallet::<TestRuntime>::receive_messages_proof(
+			Origin::signed(1),
+			TEST_RELAYER_A,
+			Ok(vec![message(nonce, payload)]).into(),
+			1,
+			REGULAR_PAYLOAD.declared_weight +
+				<TestRuntime as Config>::WeightInfo::single_message_retry_overhead(
+					crate::mock::DbWeight::get(),
+				),
+		));
+	}
+
+	fn failed_dispatches_queue() -> Vec<MessageNonce> {
+		FailedDispatchesQueue::<TestRuntime>::get(TEST_LANE_ID)
+			.into_iter()
+			.map(|(nonce, _)| nonce)
+			.collect()
+	}
+
+	#[test]
+	fn retry_dispatch_works() {
+		run_test(|| {
+			get_ready_for_events();
+			receive_failing_message(1);
+
+			let message_key = MessageKey { lane_id: TEST_LANE_ID, nonce: 1 };
+			let failed_dispatch = FailedDispatches::<TestRuntime>::get(&message_key).unwrap();
+			assert_eq!(failed_dispatch.retry_account, TEST_DISPATCH_RETRY_ACCOUNT);
+			assert_eq!(failed_dispatch.failed_at, 1);
+			assert_eq!(failed_dispatches_queue(), vec![1]);
+
+			// only retry account may retry dispatch
+			assert_noop!(
+				Pallet::<TestRuntime>::retry_dispatch(
+					Origin::signed(1),
+					TEST_LANE_ID,
+					1,
+					REGULAR_PAYLOAD.declared_weight,
+				),
+				Error::<TestRuntime, ()>::NotDispatchRetryAccount,
+			);
+			// the declared weight must be enough to dispatch the message
+			assert_noop!(
+				Pallet::<TestRuntime>::retry_dispatch(
+					Origin::signed(TEST_DISPATCH_RETRY_ACCOUNT),
+					TEST_LANE_ID,
+					1,
+					REGULAR_PAYLOAD.declared_weight - 1,
+				),
+				Error::<TestRuntime, ()>::InsufficientDispatchWeight,
+			);
+
+			// when dispatch fails again, the message stays in the queue
+			System::<TestRuntime>::reset_events();
+			assert_ok!(Pallet::<TestRuntime>::retry_dispatch(
+				Origin::signed(TEST_DISPATCH_RETRY_ACCOUNT),
+				TEST_LANE_ID,
+				1,
+				REGULAR_PAYLOAD.declared_weight,
+			));
+			assert_eq!(failed_dispatches_queue(), vec![1]);
+			assert_eq!(
+				System::<TestRuntime>::events(),
+				vec![EventRecord {
+					phase: Phase::Initialization,
+					event: TestEvent::Messages(Event::MessageDispatchRetried {
+						lane_id: TEST_LANE_ID,
+						nonce: 1,
+						dispatch_result: false,
+					}),
+					topics: vec![],
+				}],
+			);
+
+			// when dispatch succeeds, the message is removed from the queue
+			TestMessageDispatch::make_all_dispatches_succeed();
+			assert_ok!(Pallet::<TestRuntime>::retry_dispatch(
+				Origin::signed(TEST_DISPATCH_RETRY_ACCOUNT),
+				TEST_LANE_ID,
+				1,
+				REGULAR_PAYLOAD.declared_weight,
+			));
+			assert_eq!(FailedDispatches::<TestRuntime>::get(&message_key), None);
+			assert_eq!(failed_dispatches_queue(), Vec::<MessageNonce>::new());
+			assert_noop!(
+				Pallet::<TestRuntime>::retry_dispatch(
+					Origin::signed(TEST_DISPATCH_RETRY_ACCOUNT),
+					TEST_LANE_ID,
+					1,
+					REGULAR_PAYLOAD.declared_weight,
+				),
+				Error::<TestRuntime, ()>::FailedDispatchNotFound,
+			);
+		});
+	}
+
+	#[test]
+	fn successfully_dispatched_messages_are_not_kept_for_retry() {
+		run_test(|| {
+			assert_ok!(Pallet::<TestRuntime>::receive_messages_proof(
+				Origin::signed(1),
+				TEST_RELAYER_A,
+				Ok(vec![message(1, REGULAR_PAYLOAD)]).into(),
+				1,
+				REGULAR_PAYLOAD.declared_weight,
+			));
+
+			assert_eq!(failed_dispatches_queue(), Vec::<MessageNonce>::new());
+		});
+	}
+
+	#[test]
+	fn failed_dispatches_queue_is_bounded_and_old_entries_are_removed() {
+		run_test(|| {
+			get_ready_for_events();
+			receive_failing_message(1);
+			receive_failing_message(2);
+			receive_failing_message(3);
+
+			// the oldest entry is removed when the queue is full
+			assert_eq!(failed_dispatches_queue(), vec![2, 3]);
+			assert_eq!(
+				FailedDispatches::<TestRuntime>::get(&MessageKey {
+					lane_id: TEST_LANE_ID,
+					nonce: 1
+				}),
+				None,
+			);
+
+			// expired dispatches can't be retried
+			System::<TestRuntime>::set_block_number(12);
+			assert_noop!(
+				Pallet::<TestRuntime>::retry_dispatch(
+					Origin::signed(TEST_DISPATCH_RETRY_ACCOUNT),
+					TEST_LANE_ID,
+					2,
+					REGULAR_PAYLOAD.declared_weight,
+				),
+				Error::<TestRuntime, ()>::FailedDispatchNotFound,
+			);
+
+			// and are removed (one at a time) when next dispatch fails
+			receive_failing_message(4);
+			assert_eq!(failed_dispatches_queue(), vec![3, 4]);
+			assert_eq!(
+				FailedDispatches::<TestRuntime>::get(&MessageKey {
+					lane_id: TEST_LANE_ID,
+					nonce: 2
+				}),
+				None,
+			);
+		});
+	}
+
+	#[test]
+	fn retry_queue_overhead_is_refunded_if_message_is_dispatched() {
+		run_test(|| {
+			let retry_overhead = <TestRuntime as Config>::WeightInfo::single_message_retry_overhead(
+				crate::mock::DbWeight::get(),
+			);
+			let mut payload = REGULAR_PAYLOAD;
+			payload.dispatch_result.dispatch_result = false;
+			let proof: TestMessagesProof = Ok(vec![message(1, payload)]).into();
+			let declared_weight = REGULAR_PAYLOAD.declared_weight + retry_overhead;
+			let pre_dispatch_weight =
+				<TestRuntime as Config>::WeightInfo::receive_messages_proof_weight(
+					&proof,
+					1,
+					declared_weight,
+				);
+
+			TestMessageDispatch::make_all_dispatches_succeed();
+			let post_dispatch_weight = Pallet::<TestRuntime>::receive_messages_proof(
+				Origin::signed(1),
+				TEST_RELAYER_A,
+				proof,
+				1,
+				declared_weight,
+			)
+			.expect("delivery has failed")
+			.actual_weight
+			.expect("receive_messages_proof always returns Some");
+
+			// when the message is dispatched, the retry queue overhead is refunded
+			assert_eq!(failed_dispatches_queue(), Vec::<MessageNonce>::new());
+			assert_eq!(post_dispatch_weight, pre_dispatch_weight - retry_overhead);
+		});
+	}
+
+	fn receive_message_with_response(nonce: MessageNonce, response: Vec<u8>) {
+		let mut payload = REGULAR_PAYLOAD;
+		payload.dispatch_result.response = Some(response);
+		assert_ok!(Pallet::<TestRuntime>::receive_messages_proof(
+			Origin::signed(1),
+			TEST_RELAYER_A,
+			Ok(vec![message(nonce, payload)]).into(),
+			1,
+			REGULAR_PAYLOAD.declared_weight,
+		));
+	}
+
+	fn message_response(nonce: MessageNonce) -> Option<Vec<u8>> {
+		InboundMessageResponses::<TestRuntime>::get(MessageKey { lane_id: TEST_LANE_ID, nonce })
+			.map(|response| response.into_inner())
+	}
+
+	#[test]
+	fn message_responses_are_stored_until_delivery_is_confirmed() {
+		run_test(|| {
+			receive_message_with_response(1, vec![42]);
+			assert_eq!(message_response(1), Some(vec![42]));
+
+			// message proof includes outbound lane state that confirms delivery of message 1
+			let mut message_proof: TestMessagesProof = Ok(vec![message(2, REGULAR_PAYLOAD)]).into();
+			message_proof.result.as_mut().unwrap()[0].1.lane_state =
+				Some(OutboundLaneData { latest_received_nonce: 1, ..Default::default() });
+			assert_ok!(Pallet::<TestRuntime>::receive_messages_proof(
+				Origin::signed(1),
+				TEST_RELAYER_A,
+				message_proof,
+				1,
+				REGULAR_PAYLOAD.declared_weight,
+			));
+
+			assert_eq!(message_response(1), None);
+			assert_eq!(message_response(2), None);
+		});
+	}
+
+	#[test]
+	fn too_large_message_responses_are_not_stored() {
+		run_test(|| {
+			let max_response_size = crate::mock::MaxMessageResponseSize::get() as usize;
+			receive_message_with_response(1, vec![42; max_response_size + 1]);
+			assert_eq!(message_response(1), None);
+
+			receive_message_with_response(2, vec![42; max_response_size]);
+			assert_eq!(message_response(2), Some(vec![42; max_response_size]));
+		});
+	}
+
+	#[test]
+	fn too_large_outbound_message_responses_are_ignored() {
+		run_test(|| {
+			get_ready_for_events();
+			send_regular_message();
+
+			let max_response_size = crate::mock::MaxMessageResponseSize::get() as usize;
+			TestTargetHeaderChain::set_proved_responses(
+				vec![(1, vec![42; max_response_size + 1])].into_iter().collect(),
+			);
+			assert_ok!(Pallet::<TestRuntime>::receive_messages_delivery_proof(
+				Origin::signed(1),
+				TestMessagesDeliveryProof(Ok((
+					TEST_LANE_ID,
+					InboundLaneData {
+						last_confirmed_nonce: 0,
+						relayers: vec![unrewarded_relayer(1, 1, TEST_RELAYER_A)]
+							.into_iter()
+							.collect(),
+					},
+				))),
+				UnrewardedRelayersState {
+					unrewarded_relayer_entries: 1,
+					total_messages: 1,
+					last_delivered_nonce: 1,
+					..Default::default()
+				},
+			));
+			TestOnMessageResponse::ensure_not_called(&TEST_LANE_ID, 1);
+			assert!(!System::<TestRuntime>::events().iter().any(|event| matches!(
+				event.event,
+				TestEvent::Messages(Event::MessageResponseReceived { .. })
+			)));
+		});
+	}
+
+	#[test]
+	fn message_responses_are_handled_once_when_delivery_is_confirmed() {
+		run_test(|| {
+			get_ready_for_events();
+			send_regular_message();
+			send_regular_message();
+
+			// confirm delivery of message 1 only
+			TestTargetHeaderChain::set_proved_responses(
+				vec![(1, vec![1]), (2, vec![2])].into_iter().collect(),
+			);
+			assert_ok!(Pallet::<TestRuntime>::receive_messages_delivery_proof(
+				Origin::signed(1),
+				TestMessagesDeliveryProof(Ok((
+					TEST_LANE_ID,
+					InboundLaneData {
+						last_confirmed_nonce: 0,
+						relayers: vec![unrewarded_relayer(1, 1, TEST_RELAYER_A)]
+							.into_iter()
+							.collect(),
+					},
+				))),
+				UnrewardedRelayersState {
+					unrewarded_relayer_entries: 1,
+					total_messages: 1,
+					last_delivered_nonce: 1,
+					..Default::default()
+				},
+			));
+			TestOnMessageResponse::ensure_called(&TEST_LANE_ID, 1, &[1]);
+			TestOnMessageResponse::ensure_not_called(&TEST_LANE_ID, 2);
+			assert!(System::<TestRuntime>::events().contains(&EventRecord {
+				phase: Phase::Initialization,
+				event: TestEvent::Messages(Event::MessageResponseReceived {
+					lane_id: TEST_LANE_ID,
+					nonce: 1,
+					response: vec![1],
+				}),
+				topics: vec![],
+			}));
+
+			// response to the already confirmed message is ignored
+			TestTargetHeaderChain::set_proved_responses(
+				vec![(1, vec![3]), (2, vec![2])].into_iter().collect(),
+			);
+			assert_ok!(Pallet::<TestRuntime>::receive_messages_delivery_proof(
+				Origin::signed(1),
+				TestMessagesDeliveryProof(Ok((
+					TEST_LANE_ID,
+					InboundLaneData {
+						last_confirmed_nonce: 0,
+						relayers: vec![
+							unrewarded_relayer(1, 1, TEST_RELAYER_A),
+							unrewarded_relayer(2, 2, TEST_RELAYER_B),
+						]
+						.into_iter()
+						.collect(),
+					},
+				))),
+				UnrewardedRelayersState {
+					unrewarded_relayer_entries: 2,
+					total_messages: 2,
+					last_delivered_nonce: 2,
+					..Default::default()
+				},
+			));
+			TestOnMessageResponse::ensure_called(&TEST_LANE_ID, 1, &[1]);
+			TestOnMessageResponse::ensure_called(&TEST_LANE_ID, 2, &[2]);
+		});
+	}
+
+	#[test]
+	fn receive_messages_proof_does_not_dispatch_cancelled_messages() {
+		run_test(|| {
+			let mut cancelled_message = message(1, REGULAR_PAYLOAD);
+			cancelled_message.data.is_cancelled = true;
+
+			// cancelled message isn't dispatched, so relayer doesn't need to declare any weight
+			assert_ok!(Pallet::<TestRuntime>::receive_messages_proof(
+				Origin::signed(1),
+				TEST_RELAYER_A,
+				Ok(vec![cancelled_message]).into(),
+				1,
+				0,
+			));
+
+			let inbound_lane_data = InboundLanes::<TestRuntime>::get(TEST_LANE_ID).0;
+			assert_eq!(inbound_lane_data.last_delivered_nonce(), 1);
+			assert_eq!(
+				inbound_lane_data
+					.relayers
+					.back()
+					.map(|entry| entry.messages.dispatch_results[0]),
+				Some(false),
+			);
+		});
+	}
+
+	#[test]
+	fn receive_messages_proof_updates_confirmed_message_nonce() {
+		run_test(|| {
+			// say we have received 10 messages && last confirmed message is 8
+			InboundLanes::<TestRuntime, ()>::insert(
+				TEST_LANE_ID,
+				InboundLaneData {
+					last_confirmed_nonce: 8,
+					relayers: vec![
+						unrewarded_relayer(9, 9, TEST_RELAYER_A),
 						unrewarded_relayer(10, 10, TEST_RELAYER_B),
 					]
 					.into_iter()
@@ -1603,349 +3345,846 @@ mod tests {
 	}
 
 	#[test]
-	fn receive_messages_proof_does_not_accept_message_if_dispatch_weight_is_not_enough() {
+	fn receive_messages_proof_does_not_accept_message_if_dispatch_weight_is_not_enough() {
+		run_test(|| {
+			assert_ok!(Pallet::<TestRuntime>::receive_messages_proof(
+				Origin::signed(1),
+				TEST_RELAYER_A,
+				Ok(vec![message(1, REGULAR_PAYLOAD)]).into(),
+				1,
+				REGULAR_PAYLOAD.declared_weight - 1,
+			));
+			assert_eq!(InboundLanes::<TestRuntime>::get(TEST_LANE_ID).last_delivered_nonce(), 0);
+		});
+	}
+
+	#[test]
+	fn receive_messages_proof_rejects_invalid_proof() {
+		run_test(|| {
+			assert_noop!(
+				Pallet::<TestRuntime, ()>::receive_messages_proof(
+					Origin::signed(1),
+					TEST_RELAYER_A,
+					Err(()).into(),
+					1,
+					0,
+				),
+				Error::<TestRuntime, ()>::InvalidMessagesProof,
+			);
+		});
+	}
+
+	#[test]
+	fn receive_messages_proof_rejects_proof_with_too_many_messages() {
+		run_test(|| {
+			assert_noop!(
+				Pallet::<TestRuntime, ()>::receive_messages_proof(
+					Origin::signed(1),
+					TEST_RELAYER_A,
+					Ok(vec![message(1, REGULAR_PAYLOAD)]).into(),
+					u32::MAX,
+					0,
+				),
+				Error::<TestRuntime, ()>::TooManyMessagesInTheProof,
+			);
+		});
+	}
+
+	#[test]
+	fn receive_messages_delivery_proof_works() {
+		run_test(|| {
+			send_regular_message();
+			receive_messages_delivery_proof();
+
+			assert_eq!(
+				OutboundLanes::<TestRuntime, ()>::get(TEST_LANE_ID).latest_received_nonce,
+				1,
+			);
+		});
+	}
+
+	#[test]
+	fn receive_messages_delivery_proof_rewards_relayers() {
+		run_test(|| {
+			assert_ok!(Pallet::<TestRuntime>::send_message(
+				Origin::signed(1),
+				TEST_LANE_ID,
+				REGULAR_PAYLOAD,
+				1000,
+			));
+			assert_ok!(Pallet::<TestRuntime>::send_message(
+				Origin::signed(1),
+				TEST_LANE_ID,
+				REGULAR_PAYLOAD,
+				2000,
+			));
+
+			// this reports delivery of message 1 => reward is paid to TEST_RELAYER_A
+			assert_ok!(Pallet::<TestRuntime>::receive_messages_delivery_proof(
+				Origin::signed(1),
+				TestMessagesDeliveryProof(Ok((
+					TEST_LANE_ID,
+					InboundLaneData {
+						relayers: vec![unrewarded_relayer(1, 1, TEST_RELAYER_A)]
+							.into_iter()
+							.collect(),
+						..Default::default()
+					}
+				))),
+				UnrewardedRelayersState {
+					unrewarded_relayer_entries: 1,
+					total_messages: 1,
+					last_delivered_nonce: 1,
+					..Default::default()
+				},
+			));
+			assert!(TestMessageDeliveryAndDispatchPayment::is_reward_paid(TEST_RELAYER_A, 1000));
+			assert!(!TestMessageDeliveryAndDispatchPayment::is_reward_paid(TEST_RELAYER_B, 2000));
+
+			// this reports delivery of both message 1 and message 2 => reward is paid only to
+			// TEST_RELAYER_B
+			assert_ok!(Pallet::<TestRuntime>::receive_messages_delivery_proof(
+				Origin::signed(1),
+				TestMessagesDeliveryProof(Ok((
+					TEST_LANE_ID,
+					InboundLaneData {
+						relayers: vec![
+							unrewarded_relayer(1, 1, TEST_RELAYER_A),
+							unrewarded_relayer(2, 2, TEST_RELAYER_B)
+						]
+						.into_iter()
+						.collect(),
+						..Default::default()
+					}
+				))),
+				UnrewardedRelayersState {
+					unrewarded_relayer_entries: 2,
+					total_messages: 2,
+					last_delivered_nonce: 2,
+					..Default::default()
+				},
+			));
+			assert!(!TestMessageDeliveryAndDispatchPayment::is_reward_paid(TEST_RELAYER_A, 1000));
+			assert!(TestMessageDeliveryAndDispatchPayment::is_reward_paid(TEST_RELAYER_B, 2000));
+		});
+	}
+
+	#[test]
+	fn receive_messages_delivery_proof_rejects_invalid_proof() {
+		run_test(|| {
+			assert_noop!(
+				Pallet::<TestRuntime>::receive_messages_delivery_proof(
+					Origin::signed(1),
+					TestMessagesDeliveryProof(Err(())),
+					Default::default(),
+				),
+				Error::<TestRuntime, ()>::InvalidMessagesDeliveryProof,
+			);
+		});
+	}
+
+	#[test]
+	fn receive_messages_delivery_proof_rejects_proof_if_declared_relayers_state_is_invalid() {
+		run_test(|| {
+			// when number of relayers entries is invalid
+			assert_noop!(
+				Pallet::<TestRuntime>::receive_messages_delivery_proof(
+					Origin::signed(1),
+					TestMessagesDeliveryProof(Ok((
+						TEST_LANE_ID,
+						InboundLaneData {
+							relayers: vec![
+								unrewarded_relayer(1, 1, TEST_RELAYER_A),
+								unrewarded_relayer(2, 2, TEST_RELAYER_B)
+							]
+							.into_iter()
+							.collect(),
+							..Default::default()
+						}
+					))),
+					UnrewardedRelayersState {
+						unrewarded_relayer_entries: 1,
+						total_messages: 2,
+						last_delivered_nonce: 2,
+						..Default::default()
+					},
+				),
+				Error::<TestRuntime, ()>::InvalidUnrewardedRelayersState,
+			);
+
+			// when number of messages is invalid
+			assert_noop!(
+				Pallet::<TestRuntime>::receive_messages_delivery_proof(
+					Origin::signed(1),
+					TestMessagesDeliveryProof(Ok((
+						TEST_LANE_ID,
+						InboundLaneData {
+							relayers: vec![
+								unrewarded_relayer(1, 1, TEST_RELAYER_A),
+								unrewarded_relayer(2, 2, TEST_RELAYER_B)
+							]
+							.into_iter()
+							.collect(),
+							..Default::default()
+						}
+					))),
+					UnrewardedRelayersState {
+						unrewarded_relayer_entries: 2,
+						total_messages: 1,
+						last_delivered_nonce: 2,
+						..Default::default()
+					},
+				),
+				Error::<TestRuntime, ()>::InvalidUnrewardedRelayersState,
+			);
+
+			// when last delivered nonce is invalid
+			assert_noop!(
+				Pallet::<TestRuntime>::receive_messages_delivery_proof(
+					Origin::signed(1),
+					TestMessagesDeliveryProof(Ok((
+						TEST_LANE_ID,
+						InboundLaneData {
+							relayers: vec![
+								unrewarded_relayer(1, 1, TEST_RELAYER_A),
+								unrewarded_relayer(2, 2, TEST_RELAYER_B)
+							]
+							.into_iter()
+							.collect(),
+							..Default::default()
+						}
+					))),
+					UnrewardedRelayersState {
+						unrewarded_relayer_entries: 2,
+						total_messages: 2,
+						last_delivered_nonce: 8,
+						..Default::default()
+					},
+				),
+				Error::<TestRuntime, ()>::InvalidUnrewardedRelayersState,
+			);
+		});
+	}
+
+	#[test]
+	fn receive_messages_accepts_single_message_with_invalid_payload() {
+		run_test(|| {
+			let mut invalid_message = message(1, REGULAR_PAYLOAD);
+			invalid_message.data.payload = Vec::new();
+
+			assert_ok!(Pallet::<TestRuntime, ()>::receive_messages_proof(
+				Origin::signed(1),
+				TEST_RELAYER_A,
+				Ok(vec![invalid_message]).into(),
+				1,
+				0, // weight may be zero in this case (all messages are improperly encoded)
+			),);
+
+			assert_eq!(InboundLanes::<TestRuntime>::get(TEST_LANE_ID).last_delivered_nonce(), 1,);
+		});
+	}
+
+	#[test]
+	fn receive_messages_accepts_batch_with_message_with_invalid_payload() {
+		run_test(|| {
+			let mut invalid_message = message(2, REGULAR_PAYLOAD);
+			invalid_message.data.payload = Vec::new();
+
+			assert_ok!(Pallet::<TestRuntime, ()>::receive_messages_proof(
+				Origin::signed(1),
+				TEST_RELAYER_A,
+				Ok(
+					vec![message(1, REGULAR_PAYLOAD), invalid_message, message(3, REGULAR_PAYLOAD),]
+				)
+				.into(),
+				3,
+				REGULAR_PAYLOAD.declared_weight + REGULAR_PAYLOAD.declared_weight,
+			),);
+
+			assert_eq!(InboundLanes::<TestRuntime>::get(TEST_LANE_ID).last_delivered_nonce(), 3,);
+		});
+	}
+
+	#[test]
+	fn actual_dispatch_weight_does_not_overlow() {
 		run_test(|| {
-			assert_ok!(Pallet::<TestRuntime>::receive_messages_proof(
+			let message1 = message(1, message_payload(0, Weight::MAX / 2));
+			let message2 = message(2, message_payload(0, Weight::MAX / 2));
+			let message3 = message(3, message_payload(0, Weight::MAX / 2));
+
+			assert_ok!(Pallet::<TestRuntime, ()>::receive_messages_proof(
 				Origin::signed(1),
 				TEST_RELAYER_A,
-				Ok(vec![message(1, REGULAR_PAYLOAD)]).into(),
-				1,
-				REGULAR_PAYLOAD.declared_weight - 1,
+				// this may cause overflow if source chain storage is invalid
+				Ok(vec![message1, message2, message3]).into(),
+				3,
+				Weight::MAX,
 			));
-			assert_eq!(InboundLanes::<TestRuntime>::get(TEST_LANE_ID).last_delivered_nonce(), 0);
+			assert_eq!(InboundLanes::<TestRuntime>::get(TEST_LANE_ID).last_delivered_nonce(), 2);
 		});
 	}
 
 	#[test]
-	fn receive_messages_proof_rejects_invalid_proof() {
+	fn increase_message_fee_fails_if_message_is_already_delivered() {
 		run_test(|| {
+			send_regular_message();
+			receive_messages_delivery_proof();
+
 			assert_noop!(
-				Pallet::<TestRuntime, ()>::receive_messages_proof(
+				Pallet::<TestRuntime, ()>::increase_message_fee(
 					Origin::signed(1),
-					TEST_RELAYER_A,
-					Err(()).into(),
+					TEST_LANE_ID,
 					1,
-					0,
+					100,
 				),
-				Error::<TestRuntime, ()>::InvalidMessagesProof,
+				Error::<TestRuntime, ()>::MessageIsAlreadyDelivered,
 			);
 		});
 	}
 
 	#[test]
-	fn receive_messages_proof_rejects_proof_with_too_many_messages() {
+	fn increase_message_fee_fails_if_message_is_not_yet_sent() {
 		run_test(|| {
 			assert_noop!(
-				Pallet::<TestRuntime, ()>::receive_messages_proof(
+				Pallet::<TestRuntime, ()>::increase_message_fee(
 					Origin::signed(1),
-					TEST_RELAYER_A,
-					Ok(vec![message(1, REGULAR_PAYLOAD)]).into(),
-					u32::MAX,
-					0,
+					TEST_LANE_ID,
+					1,
+					100,
 				),
-				Error::<TestRuntime, ()>::TooManyMessagesInTheProof,
+				Error::<TestRuntime, ()>::MessageIsNotYetSent,
 			);
 		});
 	}
 
 	#[test]
-	fn receive_messages_delivery_proof_works() {
+	fn increase_message_fee_fails_if_submitter_cant_pay_additional_fee() {
 		run_test(|| {
 			send_regular_message();
-			receive_messages_delivery_proof();
 
-			assert_eq!(
-				OutboundLanes::<TestRuntime, ()>::get(TEST_LANE_ID).latest_received_nonce,
-				1,
+			TestMessageDeliveryAndDispatchPayment::reject_payments();
+
+			assert_noop!(
+				Pallet::<TestRuntime, ()>::increase_message_fee(
+					Origin::signed(1),
+					TEST_LANE_ID,
+					1,
+					100,
+				),
+				Error::<TestRuntime, ()>::FailedToWithdrawMessageFee,
 			);
 		});
 	}
 
 	#[test]
-	fn receive_messages_delivery_proof_rewards_relayers() {
+	fn increase_message_fee_succeeds() {
 		run_test(|| {
-			assert_ok!(Pallet::<TestRuntime>::send_message(
+			send_regular_message();
+
+			assert_ok!(Pallet::<TestRuntime, ()>::increase_message_fee(
 				Origin::signed(1),
 				TEST_LANE_ID,
-				REGULAR_PAYLOAD,
-				1000,
-			));
-			assert_ok!(Pallet::<TestRuntime>::send_message(
+				1,
+				100,
+			),);
+			assert!(TestMessageDeliveryAndDispatchPayment::is_fee_paid(1, 100));
+		});
+	}
+
+	#[test]
+	fn message_status_works() {
+		run_test(|| {
+			send_regular_message();
+			send_regular_message();
+			assert_eq!(
+				Pallet::<TestRuntime>::message_status(TEST_LANE_ID, 1),
+				Some(MessageStatus::Pending { sent_at: 1 }),
+			);
+			assert_eq!(Pallet::<TestRuntime>::message_status(TEST_LANE_ID, 3), None);
+
+			// delivery of both messages is confirmed
+			System::<TestRuntime>::set_block_number(5);
+			let mut delivered_messages = DeliveredMessages::new(1, true);
+			delivered_messages.note_dispatched_message(false);
+			assert_ok!(Pallet::<TestRuntime>::receive_messages_delivery_proof(
 				Origin::signed(1),
-				TEST_LANE_ID,
-				REGULAR_PAYLOAD,
-				2000,
+				TestMessagesDeliveryProof(Ok((
+					TEST_LANE_ID,
+					InboundLaneData {
+						last_confirmed_nonce: 0,
+						relayers: vec![UnrewardedRelayer {
+							relayer: 0,
+							messages: delivered_messages,
+						}]
+						.into_iter()
+						.collect(),
+					},
+				))),
+				UnrewardedRelayersState {
+					unrewarded_relayer_entries: 1,
+					total_messages: 2,
+					last_delivered_nonce: 2,
+					..Default::default()
+				},
 			));
+			assert_eq!(
+				Pallet::<TestRuntime>::message_status(TEST_LANE_ID, 1),
+				Some(MessageStatus::Delivered {
+					sent_at: 1,
+					delivered_at: 5,
+					dispatch_result: true
+				}),
+			);
+			assert_eq!(
+				Pallet::<TestRuntime>::message_status(TEST_LANE_ID, 2),
+				Some(MessageStatus::Delivered {
+					sent_at: 1,
+					delivered_at: 5,
+					dispatch_result: false
+				}),
+			);
 
-			// this reports delivery of message 1 => reward is paid to TEST_RELAYER_A
+			// the bridged chain now knows that the delivery of the first message is confirmed
 			assert_ok!(Pallet::<TestRuntime>::receive_messages_delivery_proof(
 				Origin::signed(1),
 				TestMessagesDeliveryProof(Ok((
 					TEST_LANE_ID,
 					InboundLaneData {
-						relayers: vec![unrewarded_relayer(1, 1, TEST_RELAYER_A)]
-							.into_iter()
-							.collect(),
-						..Default::default()
-					}
+						last_confirmed_nonce: 1,
+						relayers: vec![UnrewardedRelayer {
+							relayer: 0,
+							messages: DeliveredMessages::new(2, false),
+						}]
+						.into_iter()
+						.collect(),
+					},
 				))),
 				UnrewardedRelayersState {
 					unrewarded_relayer_entries: 1,
 					total_messages: 1,
-					last_delivered_nonce: 1,
+					last_delivered_nonce: 2,
 					..Default::default()
 				},
 			));
-			assert!(TestMessageDeliveryAndDispatchPayment::is_reward_paid(TEST_RELAYER_A, 1000));
-			assert!(!TestMessageDeliveryAndDispatchPayment::is_reward_paid(TEST_RELAYER_B, 2000));
+			assert_eq!(
+				Pallet::<TestRuntime>::message_status(TEST_LANE_ID, 1),
+				Some(MessageStatus::Confirmed {
+					sent_at: 1,
+					delivered_at: 5,
+					confirmed_at: 5,
+					dispatch_result: true
+				}),
+			);
+			assert_eq!(
+				Pallet::<TestRuntime>::message_status(TEST_LANE_ID, 2),
+				Some(MessageStatus::Delivered {
+					sent_at: 1,
+					delivered_at: 5,
+					dispatch_result: false
+				}),
+			);
+		});
+	}
 
-			// this reports delivery of both message 1 and message 2 => reward is paid only to
-			// TEST_RELAYER_B
+	fn receive_confirmation_at_bridged_chain(last_confirmed_nonce: MessageNonce) {
+		assert_ok!(Pallet::<TestRuntime>::receive_messages_delivery_proof(
+			Origin::signed(1),
+			TestMessagesDeliveryProof(Ok((
+				TEST_LANE_ID,
+				InboundLaneData {
+					last_confirmed_nonce,
+					relayers: vec![UnrewardedRelayer {
+						relayer: 0,
+						messages: DeliveredMessages::new(2, true),
+					}]
+					.into_iter()
+					.collect(),
+				},
+			))),
+			UnrewardedRelayersState {
+				unrewarded_relayer_entries: 1,
+				total_messages: 1,
+				last_delivered_nonce: 2,
+				..Default::default()
+			},
+		));
+	}
+
+	#[test]
+	fn confirmed_message_status_is_available_until_pruned() {
+		run_test(|| {
+			send_regular_message();
+			send_regular_message();
+
+			// delivery of both messages is confirmed
+			System::<TestRuntime>::set_block_number(5);
+			let mut delivered_messages = DeliveredMessages::new(1, true);
+			delivered_messages.note_dispatched_message(true);
+			assert_ok!(Pallet::<TestRuntime>::receive_messages_delivery_proof(
+				Origin::signed(1),
+				TestMessagesDeliveryProof(Ok((
+					TEST_LANE_ID,
+					InboundLaneData {
+						last_confirmed_nonce: 0,
+						relayers: vec![UnrewardedRelayer {
+							relayer: 0,
+							messages: delivered_messages,
+						}]
+						.into_iter()
+						.collect(),
+					},
+				))),
+				UnrewardedRelayersState {
+					unrewarded_relayer_entries: 1,
+					total_messages: 2,
+					last_delivered_nonce: 2,
+					..Default::default()
+				},
+			));
+
+			// the bridged chain knows that delivery of both messages is confirmed, but we only
+			// have budget to update single status at every call
+			System::<TestRuntime>::set_block_number(6);
+			receive_confirmation_at_bridged_chain(2);
+			System::<TestRuntime>::set_block_number(7);
+			receive_confirmation_at_bridged_chain(2);
+			let confirmed_status = |confirmed_at| {
+				Some(MessageStatus::Confirmed {
+					sent_at: 1,
+					delivered_at: 5,
+					confirmed_at,
+					dispatch_result: true,
+				})
+			};
+			assert_eq!(Pallet::<TestRuntime>::message_status(TEST_LANE_ID, 1), confirmed_status(6));
+			assert_eq!(Pallet::<TestRuntime>::message_status(TEST_LANE_ID, 2), confirmed_status(7));
+
+			// statuses are kept during `ConfirmedMessageStatusLifetime` blocks
+			System::<TestRuntime>::set_block_number(6 + ConfirmedMessageStatusLifetime::get());
+			receive_confirmation_at_bridged_chain(2);
+			assert_eq!(Pallet::<TestRuntime>::message_status(TEST_LANE_ID, 1), confirmed_status(6));
+			assert_eq!(Pallet::<TestRuntime>::message_status(TEST_LANE_ID, 2), confirmed_status(7));
+
+			// and then they are pruned
+			System::<TestRuntime>::set_block_number(7 + ConfirmedMessageStatusLifetime::get());
+			receive_confirmation_at_bridged_chain(2);
+			assert_eq!(Pallet::<TestRuntime>::message_status(TEST_LANE_ID, 1), None);
+			assert_eq!(Pallet::<TestRuntime>::message_status(TEST_LANE_ID, 2), confirmed_status(7));
+
+			System::<TestRuntime>::set_block_number(8 + ConfirmedMessageStatusLifetime::get());
+			receive_confirmation_at_bridged_chain(2);
+			assert_eq!(Pallet::<TestRuntime>::message_status(TEST_LANE_ID, 2), None);
+			assert_eq!(LatestPrunedMessageStatusNonce::<TestRuntime>::get(TEST_LANE_ID), Some(2));
+		});
+	}
+
+	fn send_message_with_ttl(ttl: u64) -> MessageNonce {
+		let message_nonce =
+			outbound_lane::<TestRuntime, ()>(TEST_LANE_ID).data().latest_generated_nonce + 1;
+		assert_ok!(Pallet::<TestRuntime>::send_message_with_ttl(
+			Origin::signed(1),
+			TEST_LANE_ID,
+			REGULAR_PAYLOAD,
+			REGULAR_PAYLOAD.declared_weight,
+			ttl,
+		));
+		message_nonce
+	}
+
+	#[test]
+	fn cancel_expired_message_works() {
+		run_test(|| {
+			get_ready_for_events();
+			let nonce = send_message_with_ttl(10);
+			let message_key = MessageKey { lane_id: TEST_LANE_ID, nonce };
+			assert_eq!(
+				OutboundMessages::<TestRuntime>::get(&message_key).unwrap().expires_at,
+				Some(11)
+			);
+			assert_eq!(OutboundMessageSenders::<TestRuntime>::get(&message_key), Some(1));
+
+			System::<TestRuntime>::set_block_number(12);
+			System::<TestRuntime>::reset_events();
+			assert_ok!(Pallet::<TestRuntime>::cancel_expired_message(
+				Origin::signed(1),
+				TEST_LANE_ID,
+				nonce,
+			));
+
+			// the fee is not refunded until the delivery is confirmed
+			assert!(!TestMessageDeliveryAndDispatchPayment::is_fee_refunded(
+				1,
+				REGULAR_PAYLOAD.declared_weight
+			));
+			assert_eq!(
+				OutboundMessages::<TestRuntime>::get(&message_key).map(|m| m.0),
+				Some(MessageData {
+					payload: vec![],
+					fee: REGULAR_PAYLOAD.declared_weight,
+					expires_at: Some(11),
+					is_cancelled: true
+				}),
+			);
+			assert_eq!(OutboundMessageSenders::<TestRuntime>::get(message_key), Some(1));
+			assert_eq!(
+				Pallet::<TestRuntime>::message_status(TEST_LANE_ID, nonce),
+				Some(MessageStatus::Cancelled { sent_at: 1, cancelled_at: 12 }),
+			);
+			assert_eq!(
+				System::<TestRuntime>::events(),
+				vec![EventRecord {
+					phase: Phase::Initialization,
+					event: TestEvent::Messages(Event::MessageCancelled {
+						lane_id: TEST_LANE_ID,
+						nonce
+					}),
+					topics: vec![],
+				}],
+			);
+
+			// fee of the cancelled message can't be increased
+			assert_noop!(
+				Pallet::<TestRuntime, ()>::increase_message_fee(
+					Origin::signed(1),
+					TEST_LANE_ID,
+					nonce,
+					100,
+				),
+				Error::<TestRuntime, ()>::MessageIsCancelled,
+			);
+		});
+	}
+
+	#[test]
+	fn cancelled_message_fee_is_refunded_only_if_message_is_not_dispatched() {
+		run_test(|| {
+			get_ready_for_events();
+			let stub_nonce = send_message_with_ttl(10);
+			let dispatched_nonce = send_message_with_ttl(10);
+			System::<TestRuntime>::set_block_number(12);
+			for nonce in [stub_nonce, dispatched_nonce] {
+				assert_ok!(Pallet::<TestRuntime>::cancel_expired_message(
+					Origin::signed(1),
+					TEST_LANE_ID,
+					nonce,
+				));
+			}
+			System::<TestRuntime>::reset_events();
+
+			// the stub of the first message has been delivered, but the second message has been
+			// delivered using the proof that has been generated before cancellation
+			let mut delivered_messages = DeliveredMessages::new(stub_nonce, false);
+			delivered_messages.note_dispatched_message(true);
 			assert_ok!(Pallet::<TestRuntime>::receive_messages_delivery_proof(
 				Origin::signed(1),
 				TestMessagesDeliveryProof(Ok((
 					TEST_LANE_ID,
 					InboundLaneData {
-						relayers: vec![
-							unrewarded_relayer(1, 1, TEST_RELAYER_A),
-							unrewarded_relayer(2, 2, TEST_RELAYER_B)
-						]
+						last_confirmed_nonce: 0,
+						relayers: vec![UnrewardedRelayer {
+							relayer: TEST_RELAYER_A,
+							messages: delivered_messages,
+						}]
 						.into_iter()
 						.collect(),
-						..Default::default()
-					}
+					},
 				))),
 				UnrewardedRelayersState {
-					unrewarded_relayer_entries: 2,
+					unrewarded_relayer_entries: 1,
 					total_messages: 2,
-					last_delivered_nonce: 2,
+					last_delivered_nonce: dispatched_nonce,
 					..Default::default()
 				},
 			));
-			assert!(!TestMessageDeliveryAndDispatchPayment::is_reward_paid(TEST_RELAYER_A, 1000));
-			assert!(TestMessageDeliveryAndDispatchPayment::is_reward_paid(TEST_RELAYER_B, 2000));
-		});
-	}
-
-	#[test]
-	fn receive_messages_delivery_proof_rejects_invalid_proof() {
-		run_test(|| {
-			assert_noop!(
-				Pallet::<TestRuntime>::receive_messages_delivery_proof(
-					Origin::signed(1),
-					TestMessagesDeliveryProof(Err(())),
-					Default::default(),
-				),
-				Error::<TestRuntime, ()>::InvalidMessagesDeliveryProof,
-			);
-		});
-	}
 
-	#[test]
-	fn receive_messages_delivery_proof_rejects_proof_if_declared_relayers_state_is_invalid() {
-		run_test(|| {
-			// when number of relayers entries is invalid
-			assert_noop!(
-				Pallet::<TestRuntime>::receive_messages_delivery_proof(
-					Origin::signed(1),
-					TestMessagesDeliveryProof(Ok((
-						TEST_LANE_ID,
-						InboundLaneData {
-							relayers: vec![
-								unrewarded_relayer(1, 1, TEST_RELAYER_A),
-								unrewarded_relayer(2, 2, TEST_RELAYER_B)
-							]
-							.into_iter()
-							.collect(),
-							..Default::default()
-						}
-					))),
-					UnrewardedRelayersState {
-						unrewarded_relayer_entries: 1,
-						total_messages: 2,
-						last_delivered_nonce: 2,
-						..Default::default()
-					},
-				),
-				Error::<TestRuntime, ()>::InvalidUnrewardedRelayersState,
+			// fee of the first message is refunded and the relayer is only rewarded for the
+			// second message
+			assert!(TestMessageDeliveryAndDispatchPayment::is_fee_refunded(
+				1,
+				REGULAR_PAYLOAD.declared_weight
+			));
+			assert!(TestMessageDeliveryAndDispatchPayment::is_reward_paid(
+				TEST_RELAYER_A,
+				REGULAR_PAYLOAD.declared_weight
+			));
+			assert_eq!(
+				OutboundMessages::<TestRuntime>::get(&MessageKey {
+					lane_id: TEST_LANE_ID,
+					nonce: stub_nonce
+				})
+				.map(|m| m.fee),
+				Some(0),
 			);
-
-			// when number of messages is invalid
-			assert_noop!(
-				Pallet::<TestRuntime>::receive_messages_delivery_proof(
-					Origin::signed(1),
-					TestMessagesDeliveryProof(Ok((
-						TEST_LANE_ID,
-						InboundLaneData {
-							relayers: vec![
-								unrewarded_relayer(1, 1, TEST_RELAYER_A),
-								unrewarded_relayer(2, 2, TEST_RELAYER_B)
-							]
-							.into_iter()
-							.collect(),
-							..Default::default()
-						}
-					))),
-					UnrewardedRelayersState {
-						unrewarded_relayer_entries: 2,
-						total_messages: 1,
-						last_delivered_nonce: 2,
-						..Default::default()
-					},
-				),
-				Error::<TestRuntime, ()>::InvalidUnrewardedRelayersState,
+			assert_eq!(
+				OutboundMessages::<TestRuntime>::get(&MessageKey {
+					lane_id: TEST_LANE_ID,
+					nonce: dispatched_nonce
+				})
+				.map(|m| m.fee),
+				Some(REGULAR_PAYLOAD.declared_weight),
 			);
-
-			// when last delivered nonce is invalid
-			assert_noop!(
-				Pallet::<TestRuntime>::receive_messages_delivery_proof(
-					Origin::signed(1),
-					TestMessagesDeliveryProof(Ok((
-						TEST_LANE_ID,
-						InboundLaneData {
-							relayers: vec![
-								unrewarded_relayer(1, 1, TEST_RELAYER_A),
-								unrewarded_relayer(2, 2, TEST_RELAYER_B)
-							]
-							.into_iter()
-							.collect(),
-							..Default::default()
-						}
-					))),
-					UnrewardedRelayersState {
-						unrewarded_relayer_entries: 2,
-						total_messages: 2,
-						last_delivered_nonce: 8,
-						..Default::default()
-					},
-				),
-				Error::<TestRuntime, ()>::InvalidUnrewardedRelayersState,
+			assert!(System::<TestRuntime>::events().contains(&EventRecord {
+				phase: Phase::Initialization,
+				event: TestEvent::Messages(Event::MessageFeeRefunded {
+					lane_id: TEST_LANE_ID,
+					nonce: stub_nonce,
+				}),
+				topics: vec![],
+			}));
+			assert!(!System::<TestRuntime>::events().contains(&EventRecord {
+				phase: Phase::Initialization,
+				event: TestEvent::Messages(Event::MessageFeeRefunded {
+					lane_id: TEST_LANE_ID,
+					nonce: dispatched_nonce,
+				}),
+				topics: vec![],
+			}));
+			assert_eq!(
+				Pallet::<TestRuntime>::message_status(TEST_LANE_ID, stub_nonce),
+				Some(MessageStatus::Delivered {
+					sent_at: 1,
+					delivered_at: 12,
+					dispatch_result: false
+				}),
 			);
 		});
 	}
 
 	#[test]
-	fn receive_messages_accepts_single_message_with_invalid_payload() {
+	fn cancelled_message_fee_is_not_refunded_if_its_dispatch_may_be_retried() {
 		run_test(|| {
-			let mut invalid_message = message(1, REGULAR_PAYLOAD);
-			invalid_message.data.payload = Vec::new();
+			get_ready_for_events();
+			let stub_nonce = send_message_with_ttl(10);
+			let failed_nonce = send_message_with_ttl(10);
+			System::<TestRuntime>::set_block_number(12);
+			for nonce in [stub_nonce, failed_nonce] {
+				assert_ok!(Pallet::<TestRuntime>::cancel_expired_message(
+					Origin::signed(1),
+					TEST_LANE_ID,
+					nonce,
+				));
+			}
 
-			assert_ok!(Pallet::<TestRuntime, ()>::receive_messages_proof(
+			// the stub of the first message is delivered to the bridged chain (we're using the
+			// same runtime here), but the second message is delivered using the proof that has
+			// been generated before cancellation and its dispatch fails
+			let mut stub_message = message(stub_nonce, REGULAR_PAYLOAD);
+			stub_message.data.is_cancelled = true;
+			let mut failed_payload = REGULAR_PAYLOAD;
+			failed_payload.dispatch_result.dispatch_result = false;
+			assert_ok!(Pallet::<TestRuntime>::receive_messages_proof(
 				Origin::signed(1),
 				TEST_RELAYER_A,
-				Ok(vec![invalid_message]).into(),
-				1,
-				0, // weight may be zero in this case (all messages are improperly encoded)
-			),);
-
-			assert_eq!(InboundLanes::<TestRuntime>::get(TEST_LANE_ID).last_delivered_nonce(), 1,);
-		});
-	}
+				Ok(vec![stub_message, message(failed_nonce, failed_payload)]).into(),
+				2,
+				REGULAR_PAYLOAD.declared_weight +
+					<TestRuntime as Config>::WeightInfo::single_message_retry_overhead(
+						crate::mock::DbWeight::get(),
+					),
+			));
+			assert_eq!(failed_dispatches_queue(), vec![failed_nonce]);
 
-	#[test]
-	fn receive_messages_accepts_batch_with_message_with_invalid_payload() {
-		run_test(|| {
-			let mut invalid_message = message(2, REGULAR_PAYLOAD);
-			invalid_message.data.payload = Vec::new();
+			// the failed message may be dispatched later, so it is reported as dispatched
+			let inbound_lane_data = InboundLanes::<TestRuntime>::get(TEST_LANE_ID).0;
+			let delivered_messages = inbound_lane_data.relayers.back().unwrap().messages.clone();
+			assert!(!delivered_messages.message_dispatch_result(stub_nonce));
+			assert!(delivered_messages.message_dispatch_result(failed_nonce));
 
-			assert_ok!(Pallet::<TestRuntime, ()>::receive_messages_proof(
+			System::<TestRuntime>::reset_events();
+			assert_ok!(Pallet::<TestRuntime>::receive_messages_delivery_proof(
 				Origin::signed(1),
-				TEST_RELAYER_A,
-				Ok(
-					vec![message(1, REGULAR_PAYLOAD), invalid_message, message(3, REGULAR_PAYLOAD),]
-				)
-				.into(),
-				3,
-				REGULAR_PAYLOAD.declared_weight + REGULAR_PAYLOAD.declared_weight,
-			),);
-
-			assert_eq!(InboundLanes::<TestRuntime>::get(TEST_LANE_ID).last_delivered_nonce(), 3,);
-		});
-	}
-
-	#[test]
-	fn actual_dispatch_weight_does_not_overlow() {
-		run_test(|| {
-			let message1 = message(1, message_payload(0, Weight::MAX / 2));
-			let message2 = message(2, message_payload(0, Weight::MAX / 2));
-			let message3 = message(3, message_payload(0, Weight::MAX / 2));
+				TestMessagesDeliveryProof(Ok((TEST_LANE_ID, inbound_lane_data))),
+				UnrewardedRelayersState {
+					unrewarded_relayer_entries: 1,
+					total_messages: 2,
+					last_delivered_nonce: failed_nonce,
+					..Default::default()
+				},
+			));
 
-			assert_ok!(Pallet::<TestRuntime, ()>::receive_messages_proof(
-				Origin::signed(1),
+			// only fee of the cancelled message stub is refunded
+			let refunded_nonces = System::<TestRuntime>::events()
+				.into_iter()
+				.filter_map(|record| match record.event {
+					TestEvent::Messages(Event::MessageFeeRefunded { nonce, .. }) => Some(nonce),
+					_ => None,
+				})
+				.collect::<Vec<_>>();
+			assert_eq!(refunded_nonces, vec![stub_nonce]);
+			assert_eq!(
+				OutboundMessages::<TestRuntime>::get(&MessageKey {
+					lane_id: TEST_LANE_ID,
+					nonce: failed_nonce
+				})
+				.map(|m| m.fee),
+				Some(REGULAR_PAYLOAD.declared_weight),
+			);
+			assert!(TestMessageDeliveryAndDispatchPayment::is_reward_paid(
 				TEST_RELAYER_A,
-				// this may cause overflow if source chain storage is invalid
-				Ok(vec![message1, message2, message3]).into(),
-				3,
-				Weight::MAX,
+				REGULAR_PAYLOAD.declared_weight
 			));
-			assert_eq!(InboundLanes::<TestRuntime>::get(TEST_LANE_ID).last_delivered_nonce(), 2);
 		});
 	}
 
 	#[test]
-	fn increase_message_fee_fails_if_message_is_already_delivered() {
+	fn cancel_expired_message_rejects_invalid_requests() {
 		run_test(|| {
+			get_ready_for_events();
 			send_regular_message();
-			receive_messages_delivery_proof();
+			let nonce = send_message_with_ttl(10);
 
+			// message without ttl can't be cancelled
 			assert_noop!(
-				Pallet::<TestRuntime, ()>::increase_message_fee(
+				Pallet::<TestRuntime>::cancel_expired_message(
 					Origin::signed(1),
 					TEST_LANE_ID,
-					1,
-					100,
+					nonce - 1,
 				),
-				Error::<TestRuntime, ()>::MessageIsAlreadyDelivered,
+				Error::<TestRuntime, ()>::MessageHasNoTtl,
 			);
-		});
-	}
-
-	#[test]
-	fn increase_message_fee_fails_if_message_is_not_yet_sent() {
-		run_test(|| {
+			// message that is not yet sent can't be cancelled
 			assert_noop!(
-				Pallet::<TestRuntime, ()>::increase_message_fee(
+				Pallet::<TestRuntime>::cancel_expired_message(
 					Origin::signed(1),
 					TEST_LANE_ID,
-					1,
-					100,
+					nonce + 1,
 				),
 				Error::<TestRuntime, ()>::MessageIsNotYetSent,
 			);
-		});
-	}
-
-	#[test]
-	fn increase_message_fee_fails_if_submitter_cant_pay_additional_fee() {
-		run_test(|| {
-			send_regular_message();
-
-			TestMessageDeliveryAndDispatchPayment::reject_payments();
-
+			// message that is not yet expired can't be cancelled
+			System::<TestRuntime>::set_block_number(11);
 			assert_noop!(
-				Pallet::<TestRuntime, ()>::increase_message_fee(
+				Pallet::<TestRuntime>::cancel_expired_message(
 					Origin::signed(1),
 					TEST_LANE_ID,
-					1,
-					100,
+					nonce,
 				),
-				Error::<TestRuntime, ()>::FailedToWithdrawMessageFee,
+				Error::<TestRuntime, ()>::MessageIsNotExpired,
 			);
-		});
-	}
-
-	#[test]
-	fn increase_message_fee_succeeds() {
-		run_test(|| {
-			send_regular_message();
-
-			assert_ok!(Pallet::<TestRuntime, ()>::increase_message_fee(
+			// only sender may cancel the message
+			System::<TestRuntime>::set_block_number(12);
+			assert_noop!(
+				Pallet::<TestRuntime>::cancel_expired_message(
+					Origin::signed(2),
+					TEST_LANE_ID,
+					nonce,
+				),
+				Error::<TestRuntime, ()>::NotMessageSender,
+			);
+			// message can't be cancelled twice
+			assert_ok!(Pallet::<TestRuntime>::cancel_expired_message(
 				Origin::signed(1),
 				TEST_LANE_ID,
-				1,
-				100,
-			),);
-			assert!(TestMessageDeliveryAndDispatchPayment::is_fee_paid(1, 100));
+				nonce,
+			));
+			assert_noop!(
+				Pallet::<TestRuntime>::cancel_expired_message(
+					Origin::signed(1),
+					TEST_LANE_ID,
+					nonce,
+				),
+				Error::<TestRuntime, ()>::MessageIsCancelled,
+			);
 		});
 	}
 
@@ -2098,6 +4337,7 @@ mod tests {
 			<TestRuntime as Config>::WeightInfo::receive_messages_delivery_proof_weight(
 				&proof,
 				&relayers_state,
+				crate::mock::MaxMessageResponseSize::get(),
 				crate::mock::DbWeight::get(),
 			);
 		let post_dispatch_weight = Pallet::<TestRuntime>::receive_messages_delivery_proof(
@@ -2108,7 +4348,18 @@ mod tests {
 		.expect("confirmation has failed")
 		.actual_weight
 		.expect("receive_messages_delivery_proof always returns Some");
-		(pre_dispatch_weight, post_dispatch_weight)
+		// none of messages is cancelled and none has dispatch response, so the refund and
+		// response weights are never used
+		let refund_weight = 3 *
+			<TestRuntime as Config>::WeightInfo::single_message_refund_overhead(
+				crate::mock::DbWeight::get(),
+			);
+		let response_weight = 3 *
+			<TestRuntime as Config>::WeightInfo::single_message_response_overhead(
+				crate::mock::MaxMessageResponseSize::get(),
+				crate::mock::DbWeight::get(),
+			);
+		(pre_dispatch_weight - refund_weight - response_weight, post_dispatch_weight)
 	}
 
 	#[test]
@@ -2325,6 +4576,19 @@ mod tests {
 			InboundLanes::<TestRuntime>::storage_map_final_key(TEST_LANE_ID),
 			bp_messages::storage_keys::inbound_lane_data_key("Messages", &TEST_LANE_ID).0,
 		);
+
+		assert_eq!(
+			LaneOperatingModes::<TestRuntime>::storage_map_final_key(TEST_LANE_ID),
+			bp_messages::storage_keys::lane_operating_mode_key("Messages", &TEST_LANE_ID).0,
+		);
+	}
+
+	#[test]
+	fn storage_version_is_not_behind_messages_wire_format_version() {
+		assert!(
+			Pallet::<TestRuntime>::current_storage_version() >=
+				StorageVersion::new(bp_messages::MESSAGES_WIRE_FORMAT_VERSION)
+		);
 	}
 
 	#[test]
@@ -0,0 +1,210 @@
+// Copyright 2019-2022 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Storage migrations of the messages pallet.
+
+use crate::{Config, LaneRegistry, OutboundMessages, Pallet, StoredMessageData, LOG_TARGET};
+
+use bp_messages::{LaneConfig, LaneId, LaneState, MessageData, MessagePayload, RegisteredLane};
+use codec::{Decode, Encode};
+use frame_support::{
+	traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	weights::Weight,
+};
+use sp_std::{marker::PhantomData, vec::Vec};
+
+/// Storage items of the version 1 of the pallet.
+mod v1_storage {
+	use super::*;
+
+	/// Message data as it has been stored before messages time-to-live has been introduced.
+	#[derive(Encode, Decode)]
+	pub struct MessageData<Fee> {
+		/// Message payload.
+		pub payload: MessagePayload,
+		/// Message delivery and dispatch fee, paid by the submitter.
+		pub fee: Fee,
+	}
+}
+
+/// Migration from the storage version 0 to the version 1.
+pub mod v1 {
+	use super::*;
+
+	/// Opens given lanes in the lane registry.
+	///
+	/// Version 0 of the pallet hasn't had the lane registry, so messages were accepted at
+	/// every lane. The `Lanes` are the lanes that are used by the chain at the moment of
+	/// upgrade. Lanes that are already registered are left untouched.
+	pub struct MigrateToV1<T, I, Lanes>(PhantomData<(T, I, Lanes)>);
+
+	impl<T, I, Lanes> OnRuntimeUpgrade for MigrateToV1<T, I, Lanes>
+	where
+		T: Config<I>,
+		I: 'static,
+		Lanes: Get<Vec<(LaneId, LaneConfig<T::AccountId>)>>,
+	{
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T, I>::on_chain_storage_version();
+			if on_chain_version != 0 {
+				log::info!(
+					target: LOG_TARGET,
+					"Skipping lane registry migration: storage version is {:?}",
+					on_chain_version,
+				);
+				return T::DbWeight::get().reads(1)
+			}
+
+			let lanes = Lanes::get();
+			let checked_lanes = lanes.len() as Weight;
+			let mut registered_lanes: Weight = 0;
+			for (lane_id, config) in lanes {
+				if LaneRegistry::<T, I>::contains_key(lane_id) {
+					continue
+				}
+
+				LaneRegistry::<T, I>::insert(
+					lane_id,
+					RegisteredLane { config, state: LaneState::Opened },
+				);
+				registered_lanes += 1;
+			}
+			StorageVersion::new(1).put::<Pallet<T, I>>();
+
+			log::info!(
+				target: LOG_TARGET,
+				"Registered {} lanes in the lane registry",
+				registered_lanes,
+			);
+
+			T::DbWeight::get().reads_writes(checked_lanes + 1, registered_lanes + 1)
+		}
+	}
+}
+
+/// Migration from the storage version 1 to the version 2.
+pub mod v2 {
+	use super::*;
+
+	/// Adds time-to-live and cancellation fields to all queued outbound messages.
+	///
+	/// Messages that have been sent before the upgrade have no time-to-live and are not
+	/// cancelled.
+	pub struct MigrateToV2<T, I>(PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for MigrateToV2<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T, I>::on_chain_storage_version();
+			if on_chain_version != 1 {
+				log::info!(
+					target: LOG_TARGET,
+					"Skipping outbound messages migration: storage version is {:?}",
+					on_chain_version,
+				);
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut migrated_messages: Weight = 0;
+			OutboundMessages::<T, I>::translate::<v1_storage::MessageData<T::OutboundMessageFee>, _>(
+				|_, old_message| {
+					migrated_messages += 1;
+					Some(StoredMessageData(MessageData::new(old_message.payload, old_message.fee)))
+				},
+			);
+			StorageVersion::new(2).put::<Pallet<T, I>>();
+
+			log::info!(target: LOG_TARGET, "Migrated {} outbound messages", migrated_messages);
+
+			T::DbWeight::get().reads_writes(migrated_messages + 1, migrated_messages + 1)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{run_test, TestRuntime, TEST_LANE_ID};
+	use bp_messages::{MessageKey, MessageNonce};
+	use frame_support::parameter_types;
+
+	const OTHER_LANE_ID: LaneId = [0, 0, 0, 2];
+
+	fn lane_config(max_pending_messages: MessageNonce) -> LaneConfig<u64> {
+		LaneConfig { max_pending_messages, max_payload_size: 1024, allowed_senders: None }
+	}
+
+	parameter_types! {
+		pub MigrationLanes: Vec<(LaneId, LaneConfig<u64>)> = vec![
+			(TEST_LANE_ID, lane_config(1)),
+			(OTHER_LANE_ID, lane_config(1)),
+		];
+	}
+
+	#[test]
+	fn lanes_are_registered() {
+		run_test(|| {
+			StorageVersion::new(0).put::<Pallet<TestRuntime>>();
+			LaneRegistry::<TestRuntime>::insert(
+				TEST_LANE_ID,
+				RegisteredLane { config: lane_config(10), state: LaneState::Closing },
+			);
+
+			v1::MigrateToV1::<TestRuntime, (), MigrationLanes>::on_runtime_upgrade();
+
+			assert_eq!(Pallet::<TestRuntime>::on_chain_storage_version(), 1);
+			assert_eq!(
+				LaneRegistry::<TestRuntime>::get(TEST_LANE_ID),
+				Some(RegisteredLane { config: lane_config(10), state: LaneState::Closing }),
+			);
+			assert_eq!(
+				LaneRegistry::<TestRuntime>::get(OTHER_LANE_ID),
+				Some(RegisteredLane { config: lane_config(1), state: LaneState::Opened }),
+			);
+		});
+	}
+
+	#[test]
+	fn migration_is_not_applied_twice() {
+		run_test(|| {
+			StorageVersion::new(1).put::<Pallet<TestRuntime>>();
+
+			v1::MigrateToV1::<TestRuntime, (), MigrationLanes>::on_runtime_upgrade();
+
+			assert_eq!(LaneRegistry::<TestRuntime>::get(TEST_LANE_ID), None);
+			assert_eq!(LaneRegistry::<TestRuntime>::get(OTHER_LANE_ID), None);
+		});
+	}
+
+	#[test]
+	fn outbound_messages_are_migrated() {
+		run_test(|| {
+			StorageVersion::new(1).put::<Pallet<TestRuntime>>();
+			let message_key = MessageKey { lane_id: TEST_LANE_ID, nonce: 1 };
+			frame_support::storage::unhashed::put(
+				&OutboundMessages::<TestRuntime>::hashed_key_for(&message_key),
+				&v1_storage::MessageData { payload: vec![42], fee: 100u64 },
+			);
+
+			v2::MigrateToV2::<TestRuntime, ()>::on_runtime_upgrade();
+
+			assert_eq!(Pallet::<TestRuntime>::on_chain_storage_version(), 2);
+			assert_eq!(
+				OutboundMessages::<TestRuntime>::get(&message_key).map(|m| m.0),
+				Some(MessageData::new(vec![42], 100)),
+			);
+		});
+	}
+}
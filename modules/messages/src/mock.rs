@@ -22,8 +22,8 @@ use crate::{calc_relayers_rewards, Config};
 use bitvec::prelude::*;
 use bp_messages::{
 	source_chain::{
-		LaneMessageVerifier, MessageDeliveryAndDispatchPayment, OnDeliveryConfirmed,
-		OnMessageAccepted, SenderOrigin, TargetHeaderChain,
+		LaneMessageVerifier, MessageDeliveryAndDispatchPayment, MessagesResponses,
+		OnDeliveryConfirmed, OnMessageAccepted, OnMessageResponse, SenderOrigin, TargetHeaderChain,
 	},
 	target_chain::{
 		DispatchMessage, MessageDispatch, ProvedLaneMessages, ProvedMessages, SourceHeaderChain,
@@ -140,6 +140,10 @@ parameter_types! {
 	pub const MaxMessagesToPruneAtOnce: u64 = 10;
 	pub const MaxUnrewardedRelayerEntriesAtInboundLane: u64 = 16;
 	pub const MaxUnconfirmedMessagesAtInboundLane: u64 = 32;
+	pub const MaxFailedDispatchesAtInboundLane: u32 = 2;
+	pub const FailedDispatchLifetime: u64 = 10;
+	pub const ConfirmedMessageStatusLifetime: u64 = 10;
+	pub const MaxMessageResponseSize: u32 = 16;
 	pub storage TokenConversionRate: FixedU128 = 1.into();
 	pub const TestBridgedChainId: bp_runtime::ChainId = *b"test";
 }
@@ -165,6 +169,11 @@ impl Config for TestRuntime {
 	type MaxMessagesToPruneAtOnce = MaxMessagesToPruneAtOnce;
 	type MaxUnrewardedRelayerEntriesAtInboundLane = MaxUnrewardedRelayerEntriesAtInboundLane;
 	type MaxUnconfirmedMessagesAtInboundLane = MaxUnconfirmedMessagesAtInboundLane;
+	type MaxFailedDispatchesAtInboundLane = MaxFailedDispatchesAtInboundLane;
+	type MaxFailedDispatchPayloadSize = frame_support::traits::ConstU32<MAX_OUTBOUND_PAYLOAD_SIZE>;
+	type FailedDispatchLifetime = FailedDispatchLifetime;
+	type ConfirmedMessageStatusLifetime = ConfirmedMessageStatusLifetime;
+	type MaxMessageResponseSize = MaxMessageResponseSize;
 
 	type MaximalOutboundPayloadSize = frame_support::traits::ConstU32<MAX_OUTBOUND_PAYLOAD_SIZE>;
 	type OutboundPayload = TestPayload;
@@ -179,6 +188,7 @@ impl Config for TestRuntime {
 	type MessageDeliveryAndDispatchPayment = TestMessageDeliveryAndDispatchPayment;
 	type OnMessageAccepted = TestOnMessageAccepted;
 	type OnDeliveryConfirmed = (TestOnDeliveryConfirmed1, TestOnDeliveryConfirmed2);
+	type OnMessageResponse = TestOnMessageResponse;
 
 	type SourceHeaderChain = TestSourceHeaderChain;
 	type MessageDispatch = TestMessageDispatch;
@@ -216,6 +226,9 @@ pub const TEST_RELAYER_B: AccountId = 101;
 /// Account id of additional test relayer - C.
 pub const TEST_RELAYER_C: AccountId = 102;
 
+/// Account that is allowed to retry failed dispatches of test messages.
+pub const TEST_DISPATCH_RETRY_ACCOUNT: AccountId = 200;
+
 /// Error that is returned by all test implementations.
 pub const TEST_ERROR: &str = "Test error";
 
@@ -274,6 +287,13 @@ impl Size for TestMessagesDeliveryProof {
 #[derive(Debug, Default)]
 pub struct TestTargetHeaderChain;
 
+impl TestTargetHeaderChain {
+	/// Set dispatch responses that are returned along with all following delivery proofs.
+	pub fn set_proved_responses(responses: MessagesResponses) {
+		frame_support::storage::unhashed::put(b":proved-responses:", &responses);
+	}
+}
+
 impl TargetHeaderChain<TestPayload, TestRelayer> for TestTargetHeaderChain {
 	type Error = &'static str;
 
@@ -289,8 +309,13 @@ impl TargetHeaderChain<TestPayload, TestRelayer> for TestTargetHeaderChain {
 
 	fn verify_messages_delivery_proof(
 		proof: Self::MessagesDeliveryProof,
-	) -> Result<(LaneId, InboundLaneData<TestRelayer>), Self::Error> {
-		proof.0.map_err(|_| TEST_ERROR)
+	) -> Result<(LaneId, InboundLaneData<TestRelayer>, MessagesResponses), Self::Error> {
+		let responses =
+			frame_support::storage::unhashed::get(b":proved-responses:").unwrap_or_default();
+		proof
+			.0
+			.map(|(lane, lane_data)| (lane, lane_data, responses))
+			.map_err(|_| TEST_ERROR)
 	}
 }
 
@@ -332,6 +357,11 @@ impl TestMessageDeliveryAndDispatchPayment {
 		frame_support::storage::unhashed::get(b":message-fee:") == Some((raw_origin.unwrap(), fee))
 	}
 
+	/// Returns true if given fee has been refunded to given sender.
+	pub fn is_fee_refunded(sender: AccountId, fee: TestMessageFee) -> bool {
+		frame_support::storage::unhashed::get(b":refunded-fee:") == Some((sender, fee))
+	}
+
 	/// Returns true if given relayer has been rewarded with given balance. The reward-paid flag is
 	/// cleared after the call.
 	pub fn is_reward_paid(relayer: AccountId, fee: TestMessageFee) -> bool {
@@ -358,6 +388,18 @@ impl MessageDeliveryAndDispatchPayment<Origin, AccountId, TestMessageFee>
 		Ok(())
 	}
 
+	fn refund_delivery_and_dispatch_fee(
+		sender: &AccountId,
+		fee: &TestMessageFee,
+	) -> Result<(), Self::Error> {
+		if frame_support::storage::unhashed::get(b":reject-message-fee:") == Some(true) {
+			return Err(TEST_ERROR)
+		}
+
+		frame_support::storage::unhashed::put(b":refunded-fee:", &(sender, fee));
+		Ok(())
+	}
+
 	fn pay_relayers_rewards(
 		lane_id: LaneId,
 		message_relayers: VecDeque<UnrewardedRelayer<AccountId>>,
@@ -455,6 +497,32 @@ impl OnDeliveryConfirmed for TestOnDeliveryConfirmed2 {
 	}
 }
 
+/// On-message-response callback.
+#[derive(Debug)]
+pub struct TestOnMessageResponse;
+
+impl TestOnMessageResponse {
+	/// Verify that the callback has been called with given response.
+	pub fn ensure_called(lane: &LaneId, nonce: MessageNonce, response: &[u8]) {
+		let key = (b"TestOnMessageResponse", lane, nonce).encode();
+		assert_eq!(frame_support::storage::unhashed::get(&key), Some(response.to_vec()));
+	}
+
+	/// Verify that the callback has not been called for given message.
+	pub fn ensure_not_called(lane: &LaneId, nonce: MessageNonce) {
+		let key = (b"TestOnMessageResponse", lane, nonce).encode();
+		assert_eq!(frame_support::storage::unhashed::get::<Vec<u8>>(&key), None);
+	}
+}
+
+impl OnMessageResponse for TestOnMessageResponse {
+	fn on_message_response(lane: &LaneId, nonce: MessageNonce, response: &[u8]) -> Weight {
+		let key = (b"TestOnMessageResponse", lane, nonce).encode();
+		frame_support::storage::unhashed::put(&key, &response.to_vec());
+		0
+	}
+}
+
 /// Source header chain that is used in tests.
 #[derive(Debug)]
 pub struct TestSourceHeaderChain;
@@ -476,6 +544,13 @@ impl SourceHeaderChain<TestMessageFee> for TestSourceHeaderChain {
 #[derive(Debug)]
 pub struct TestMessageDispatch;
 
+impl TestMessageDispatch {
+	/// Make all following dispatches succeed, regardless of the dispatch result in the payload.
+	pub fn make_all_dispatches_succeed() {
+		frame_support::storage::unhashed::put(b":all-dispatches-succeed:", &true);
+	}
+}
+
 impl MessageDispatch<AccountId, TestMessageFee> for TestMessageDispatch {
 	type DispatchPayload = TestPayload;
 
@@ -486,11 +561,27 @@ impl MessageDispatch<AccountId, TestMessageFee> for TestMessageDispatch {
 		}
 	}
 
+	fn dispatch_retry_account(
+		message: &DispatchMessage<TestPayload, TestMessageFee>,
+	) -> Option<AccountId> {
+		// only messages that are expected to fail may be retried, so that other tests don't need
+		// to care about the retry queue overhead
+		match message.data.payload.as_ref() {
+			Ok(payload) if !payload.dispatch_result.dispatch_result =>
+				Some(TEST_DISPATCH_RETRY_ACCOUNT),
+			_ => None,
+		}
+	}
+
 	fn dispatch(
 		_relayer_account: &AccountId,
 		message: DispatchMessage<TestPayload, TestMessageFee>,
 	) -> MessageDispatchResult {
 		match message.data.payload.as_ref() {
+			Ok(_)
+				if frame_support::storage::unhashed::get(b":all-dispatches-succeed:") ==
+					Some(true) =>
+				dispatch_result(0),
 			Ok(payload) => payload.dispatch_result.clone(),
 			Err(_) => dispatch_result(0),
 		}
@@ -509,7 +600,7 @@ pub const fn message_payload(id: u64, declared_weight: Weight) -> TestPayload {
 
 /// Return message data with valid fee for given payload.
 pub fn message_data(payload: TestPayload) -> MessageData<TestMessageFee> {
-	MessageData { payload: payload.encode(), fee: 1 }
+	MessageData::new(payload.encode(), 1)
 }
 
 /// Returns message dispatch result with given unspent weight.
@@ -518,6 +609,7 @@ pub const fn dispatch_result(unspent_weight: Weight) -> MessageDispatchResult {
 		dispatch_result: true,
 		unspent_weight,
 		dispatch_fee_paid_during_dispatch: true,
+		response: None,
 	}
 }
 
@@ -97,6 +97,8 @@ impl<T: Config<I>, I: 'static> MaxEncodedLen for StoredMessageData<T, I> {
 	fn max_encoded_len() -> usize {
 		T::OutboundMessageFee::max_encoded_len()
 			.saturating_add(T::MaximalOutboundPayloadSize::get() as usize)
+			.saturating_add(Option::<u64>::max_encoded_len())
+			.saturating_add(bool::max_encoded_len())
 	}
 }
 
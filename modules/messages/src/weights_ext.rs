@@ -40,6 +40,7 @@ pub fn ensure_weights_are_correct<W: WeightInfoExt>(
 	expected_additional_byte_delivery_weight: Weight,
 	expected_messages_delivery_confirmation_tx_weight: Weight,
 	expected_pay_inbound_dispatch_fee_weight: Weight,
+	max_message_response_size: u32,
 	db_weight: RuntimeDbWeight,
 ) {
 	// verify `send_message` weight components
@@ -93,6 +94,7 @@ pub fn ensure_weights_are_correct<W: WeightInfoExt>(
 			total_messages: 1,
 			..Default::default()
 		},
+		max_message_response_size,
 		db_weight,
 	);
 	assert!(
@@ -154,6 +156,7 @@ pub fn ensure_able_to_receive_confirmation<W: WeightInfoExt>(
 	max_inbound_lane_data_proof_size_from_peer_chain: u32,
 	max_unrewarded_relayer_entries_at_peer_inbound_lane: MessageNonce,
 	max_unconfirmed_messages_at_inbound_lane: MessageNonce,
+	max_message_response_size: u32,
 	db_weight: RuntimeDbWeight,
 ) {
 	// verify that we're able to receive confirmation of maximal-size
@@ -176,6 +179,7 @@ pub fn ensure_able_to_receive_confirmation<W: WeightInfoExt>(
 			total_messages: max_unconfirmed_messages_at_inbound_lane,
 			..Default::default()
 		},
+		max_message_response_size,
 		db_weight,
 	);
 	assert!(
@@ -203,10 +207,13 @@ pub trait WeightInfoExt: WeightInfo {
 		let transaction_overhead = Self::send_message_overhead();
 		let message_size_overhead = Self::send_message_size_overhead(message.size());
 		let call_back_overhead = Self::single_message_callback_overhead(db_weight);
+		// the message status is inserted when the message is sent
+		let status_overhead = db_weight.writes(1);
 
 		transaction_overhead
 			.saturating_add(message_size_overhead)
 			.saturating_add(call_back_overhead)
+			.saturating_add(status_overhead)
 	}
 
 	/// Weight of message delivery extrinsic.
@@ -240,9 +247,13 @@ pub trait WeightInfoExt: WeightInfo {
 	}
 
 	/// Weight of confirmation delivery extrinsic.
+	///
+	/// The `max_response_size` is the maximal size of the message dispatch response, that is
+	/// handled by this chain.
 	fn receive_messages_delivery_proof_weight(
 		proof: &impl Size,
 		relayers_state: &UnrewardedRelayersState,
+		max_response_size: u32,
 		db_weight: RuntimeDbWeight,
 	) -> Weight {
 		// basic components of extrinsic weight
@@ -266,11 +277,33 @@ pub trait WeightInfoExt: WeightInfo {
 			.total_messages
 			.saturating_mul(Self::single_message_callback_overhead(db_weight));
 
+		// and cost of handling dispatch response of every confirmed message (in case all of them
+		// have maximal-size responses)
+		let response_overhead = relayers_state
+			.total_messages
+			.saturating_mul(Self::single_message_response_overhead(max_response_size, db_weight));
+
+		// and cost of refunding fee of every confirmed message (in case all of them are
+		// cancelled)
+		let refund_overhead = relayers_state
+			.total_messages
+			.saturating_mul(Self::single_message_refund_overhead(db_weight));
+
+		// and cost of updating status of every confirmed message, updating statuses of messages,
+		// which delivery confirmation is known to the bridged chain, and pruning old statuses
+		let status_overhead = relayers_state
+			.total_messages
+			.saturating_mul(Self::single_message_status_overhead(db_weight))
+			.saturating_add(db_weight.reads_writes(2, 2));
+
 		transaction_overhead
 			.saturating_add(messages_overhead)
 			.saturating_add(relayers_overhead)
 			.saturating_add(proof_size_overhead)
 			.saturating_add(callback_overhead)
+			.saturating_add(response_overhead)
+			.saturating_add(refund_overhead)
+			.saturating_add(status_overhead)
 	}
 
 	// Functions that are used by extrinsics weights formulas.
@@ -384,6 +417,51 @@ pub trait WeightInfoExt: WeightInfo {
 	fn single_message_callback_overhead(db_weight: RuntimeDbWeight) -> Weight {
 		db_weight.reads_writes(1, 1)
 	}
+
+	/// Returns pre-dispatch weight of handling the single message dispatch response of given size.
+	///
+	/// It is the `OnMessageResponse` callback, which must fit into the single callback overhead,
+	/// and the `MessageResponseReceived` event, which includes the response itself.
+	fn single_message_response_overhead(response_size: u32, db_weight: RuntimeDbWeight) -> Weight {
+		Self::single_message_callback_overhead(db_weight)
+			.saturating_add(Self::message_response_event_overhead(response_size, db_weight))
+	}
+
+	/// Returns weight of depositing the `MessageResponseReceived` event with response of given
+	/// size.
+	fn message_response_event_overhead(response_size: u32, db_weight: RuntimeDbWeight) -> Weight {
+		db_weight
+			.writes(1)
+			.saturating_add(Self::storage_proof_size_overhead(response_size))
+	}
+
+	/// Returns weight of refunding fee of the single cancelled message, which delivery has been
+	/// confirmed.
+	///
+	/// It is: read of the message and its sender, update of the message and two account writes
+	/// of the refund itself.
+	fn single_message_refund_overhead(db_weight: RuntimeDbWeight) -> Weight {
+		db_weight.reads_writes(2, 3)
+	}
+
+	/// Returns weight of tracking status of the single outbound message, which delivery has
+	/// been confirmed.
+	///
+	/// It is: read and update of the message status when its delivery is confirmed, read and
+	/// update of the status when the delivery confirmation is known to the bridged chain and
+	/// read and removal of the status when it is pruned.
+	fn single_message_status_overhead(db_weight: RuntimeDbWeight) -> Weight {
+		db_weight.reads_writes(3, 3)
+	}
+
+	/// Returns weight of keeping the single inbound message, which dispatch has failed, in the
+	/// retry queue.
+	///
+	/// It is: read and update of the lane retry queue, removal of the oldest queued message and
+	/// insertion of the new message.
+	fn single_message_retry_overhead(db_weight: RuntimeDbWeight) -> Weight {
+		db_weight.reads_writes(1, 3)
+	}
 }
 
 impl WeightInfoExt for () {
@@ -17,7 +17,10 @@
 use crate::{Config, Pallet, RelayBlockHash, RelayBlockHasher, RelayBlockNumber};
 use bp_runtime::FilterCall;
 use frame_support::{dispatch::CallableCallFor, traits::IsSubType};
-use sp_runtime::transaction_validity::{InvalidTransaction, TransactionValidity, ValidTransaction};
+use sp_runtime::{
+	transaction_validity::{InvalidTransaction, TransactionValidity, ValidTransaction},
+	DispatchError,
+};
 
 /// Validate parachain heads in order to avoid "mining" transactions that provide
 /// outdated bridged parachain heads. Without this validation, even honest relayers
@@ -71,6 +74,44 @@ where
 			InvalidTransaction::Stale.into()
 		}
 	}
+
+	fn is_bridge_call(call: &Call) -> bool {
+		matches!(call.is_sub_type(), Some(crate::Call::<T, I>::submit_parachain_heads { .. }))
+	}
+
+	fn are_overlapping_calls(call: &Call, other_call: &Call) -> bool {
+		match (call.is_sub_type(), other_call.is_sub_type()) {
+			(
+				Some(crate::Call::<T, I>::submit_parachain_heads { ref parachains, .. }),
+				Some(crate::Call::<T, I>::submit_parachain_heads {
+					parachains: ref other_parachains,
+					..
+				}),
+			) => parachains.iter().any(|(parachain, _)| {
+				other_parachains.iter().any(|(other_parachain, _)| other_parachain == parachain)
+			}),
+			_ => false,
+		}
+	}
+
+	fn is_invalid_proof_error(error: &DispatchError) -> bool {
+		*error == crate::Error::<T, I>::InvalidStorageProof.into()
+	}
+
+	fn is_call_data_imported(call: &Call) -> bool {
+		let parachains = match call.is_sub_type() {
+			Some(crate::Call::<T, I>::submit_parachain_heads { ref parachains, .. }) =>
+				parachains,
+			_ => return false,
+		};
+
+		!parachains.is_empty() &&
+			parachains.iter().all(|(parachain, parachain_head_hash)| {
+				crate::ParasInfo::<T, I>::get(parachain)
+					.map(|para_info| para_info.best_head_hash.head_hash == *parachain_head_hash)
+					.unwrap_or(false)
+			})
+	}
 }
 
 #[cfg(test)]
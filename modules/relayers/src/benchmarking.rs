@@ -20,21 +20,90 @@
 
 use crate::*;
 
-use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::Get;
 use frame_system::RawOrigin;
+use sp_arithmetic::traits::{One, Saturating, Zero};
 
 /// Reward amount that is (hopefully) is larger than existential deposit across all chains.
 const REWARD_AMOUNT: u32 = u32::MAX;
 
+/// Maximal number of lanes, rewards from which are claimed in `claim_rewards` benchmark.
+const MAX_CLAIMED_LANES: u32 = 16;
+
 benchmarks! {
 	// Benchmark `claim_rewards` call.
 	claim_rewards {
+		let l in 1..MAX_CLAIMED_LANES;
+
 		let relayer: T::AccountId = whitelisted_caller();
-		RelayerRewards::<T>::insert(&relayer, T::Reward::from(REWARD_AMOUNT));
-	}: _(RawOrigin::Signed(relayer))
+		let lanes = (0..l).map(|i| i.to_le_bytes()).collect::<Vec<LaneId>>();
+		for lane_id in &lanes {
+			RelayerRewards::<T>::insert(lane_id, &relayer, T::Reward::from(REWARD_AMOUNT));
+		}
+	}: _(RawOrigin::Signed(relayer), lanes)
 	verify {
 		// we can't check anything here, because `PaymentProcedure` is responsible for
 		// payment logic, so we assume that if call has succeeded, the procedure has
 		// also completed successfully
 	}
+
+	// Benchmark `set_beneficiary` call.
+	set_beneficiary {
+		let relayer: T::AccountId = whitelisted_caller();
+		let beneficiary: T::AccountId = account("beneficiary", 0, 0);
+	}: _(RawOrigin::Signed(relayer.clone()), Some(beneficiary.clone()))
+	verify {
+		assert_eq!(RelayerBeneficiaries::<T>::get(&relayer), Some(beneficiary));
+	}
+
+	// Benchmark `register` call.
+	register {
+		let relayer: T::AccountId = whitelisted_caller();
+		let valid_till = frame_system::Pallet::<T>::block_number()
+			.saturating_add(T::RequiredRegistrationLease::get());
+		fund_relayer::<T>(&relayer);
+	}: _(RawOrigin::Signed(relayer.clone()), valid_till)
+	verify {
+		assert!(Pallet::<T>::is_registration_active(&relayer));
+	}
+
+	// Benchmark `deregister` call.
+	deregister {
+		let relayer: T::AccountId = whitelisted_caller();
+		let valid_till = frame_system::Pallet::<T>::block_number()
+			.saturating_add(T::RequiredRegistrationLease::get());
+		fund_relayer::<T>(&relayer);
+		Pallet::<T>::register(RawOrigin::Signed(relayer.clone()).into(), valid_till).unwrap();
+
+		frame_system::Pallet::<T>::set_block_number(valid_till.saturating_add(One::one()));
+	}: _(RawOrigin::Signed(relayer.clone()))
+	verify {
+		assert!(!Pallet::<T>::is_registration_active(&relayer));
+	}
+
+	// Benchmark `slash_and_deregister` method of the pallet. The method is called by the signed
+	// extension when bridge transaction of the registered relayer has failed because of invalid
+	// proof.
+	slash_and_deregister {
+		let relayer: T::AccountId = whitelisted_caller();
+		let valid_till = frame_system::Pallet::<T>::block_number()
+			.saturating_add(T::RequiredRegistrationLease::get());
+		fund_relayer::<T>(&relayer);
+		Pallet::<T>::register(RawOrigin::Signed(relayer.clone()).into(), valid_till).unwrap();
+	}: {
+		Pallet::<T>::slash_and_deregister(&relayer)
+	}
+	verify {
+		assert!(!Pallet::<T>::is_registration_active(&relayer));
+	}
+}
+
+/// Give relayer enough funds to register.
+fn fund_relayer<T: Config>(relayer: &T::AccountId) {
+	let stake = T::RequiredStake::get();
+	let balance = T::Currency::minimum_balance().saturating_add(stake.saturating_add(stake));
+	if !balance.is_zero() {
+		T::Currency::make_free_balance_be(relayer, balance);
+	}
 }
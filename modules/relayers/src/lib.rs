@@ -14,29 +14,53 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
 
-//! Runtime module that is used to store relayer rewards and (in the future) to
-//! coordinate relations between relayers.
+//! Runtime module that is used to store relayer rewards and to coordinate relations
+//! between relayers.
+//!
+//! Rewards are stored separately for every messages lane. Relayer may claim rewards from
+//! multiple lanes at once. Rewards are paid either to the relayer account, or to the
+//! beneficiary account, configured by the relayer.
+//!
+//! Relayers may register themselves by reserving some stake for the given period (lease).
+//! Bridge transactions of registered relayers are getting priority boost. In exchange,
+//! registered relayers are slashed if their bridge transaction has failed because of provably
+//! invalid proof. Failures that may be caused by races between relayers (e.g. proof of the
+//! bridged header that is not yet imported) are not slashed.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![warn(missing_docs)]
 
-use bp_relayers::PaymentProcedure;
-use sp_arithmetic::traits::AtLeast32BitUnsigned;
-use sp_std::marker::PhantomData;
-use weights::WeightInfo;
+use bp_messages::LaneId;
+use bp_relayers::{PaymentProcedure, Registration};
+use frame_support::traits::{OnUnbalanced, ReservableCurrency};
+use sp_arithmetic::traits::{AtLeast32BitUnsigned, CheckedSub, Saturating, Zero};
+use sp_std::{marker::PhantomData, vec::Vec};
 
 pub use pallet::*;
 pub use payment_adapter::MessageDeliveryAndDispatchPaymentAdapter;
+pub use weights::WeightInfo;
 
 mod benchmarking;
 mod mock;
 mod payment_adapter;
 
+pub mod migration;
 pub mod weights;
 
 /// The target that will be used when publishing logs related to this pallet.
 pub const LOG_TARGET: &str = "runtime::bridge-relayers";
 
+/// Balance type of the relayers pallet.
+pub type BalanceOf<T> = <<T as Config>::Currency as frame_support::traits::Currency<
+	<T as frame_system::Config>::AccountId,
+>>::Balance;
+/// Negative imbalance type of the relayers pallet.
+pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as frame_support::traits::Currency<
+	<T as frame_system::Config>::AccountId,
+>>::NegativeImbalance;
+/// Relayer registration type of the relayers pallet.
+pub type RegistrationOf<T> = Registration<<T as frame_system::Config>::BlockNumber, BalanceOf<T>>;
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -51,36 +75,295 @@ pub mod pallet {
 		type Reward: AtLeast32BitUnsigned + Copy + Parameter + MaxEncodedLen;
 		/// Pay rewards adapter.
 		type PaymentProcedure: PaymentProcedure<Self::AccountId, Self::Reward>;
+		/// Currency that is used to reserve relayer stakes.
+		type Currency: ReservableCurrency<Self::AccountId>;
+		/// Stake that is reserved when relayer is registered.
+		#[pallet::constant]
+		type RequiredStake: Get<BalanceOf<Self>>;
+		/// Minimal period (in blocks) of relayer registration.
+		#[pallet::constant]
+		type RequiredRegistrationLease: Get<Self::BlockNumber>;
+		/// Priority boost of bridge transactions, submitted by registered relayers.
+		#[pallet::constant]
+		type RegisteredRelayerPriorityBoost: Get<TransactionPriority>;
+		/// Handler of slashed relayer stakes.
+		type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
 		/// Pallet call weights.
 		type WeightInfo: WeightInfo;
 	}
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		/// Claim accumulated rewards.
-		#[pallet::weight(T::WeightInfo::claim_rewards())]
-		pub fn claim_rewards(origin: OriginFor<T>) -> DispatchResult {
+		/// Claim rewards, accumulated at given lanes.
+		///
+		/// Rewards are paid to the relayer beneficiary (if it is configured) or to the relayer
+		/// account.
+		#[pallet::weight(T::WeightInfo::claim_rewards(lanes.len() as u32))]
+		pub fn claim_rewards(origin: OriginFor<T>, lanes: Vec<LaneId>) -> DispatchResult {
+			let relayer = ensure_signed(origin)?;
+			let beneficiary =
+				RelayerBeneficiaries::<T>::get(&relayer).unwrap_or_else(|| relayer.clone());
+			Self::do_claim_rewards(relayer, lanes, beneficiary)
+		}
+
+		/// Claim rewards, accumulated at given lanes and pay them to the given beneficiary.
+		///
+		/// The configured relayer beneficiary (if any) is ignored.
+		#[pallet::weight(T::WeightInfo::claim_rewards(lanes.len() as u32))]
+		pub fn claim_rewards_to(
+			origin: OriginFor<T>,
+			lanes: Vec<LaneId>,
+			beneficiary: T::AccountId,
+		) -> DispatchResult {
 			let relayer = ensure_signed(origin)?;
+			Self::do_claim_rewards(relayer, lanes, beneficiary)
+		}
+
+		/// Set or reset the account that receives relayer rewards.
+		#[pallet::weight(T::WeightInfo::set_beneficiary())]
+		pub fn set_beneficiary(
+			origin: OriginFor<T>,
+			beneficiary: Option<T::AccountId>,
+		) -> DispatchResult {
+			let relayer = ensure_signed(origin)?;
+
+			RelayerBeneficiaries::<T>::set(&relayer, beneficiary.clone());
+			Self::deposit_event(Event::<T>::BeneficiaryUpdated { relayer, beneficiary });
+
+			Ok(())
+		}
+
+		/// Register relayer or update its registration.
+		///
+		/// Registration is active until the `valid_till` block (inclusive). The `valid_till`
+		/// must be at least `RequiredRegistrationLease` blocks ahead of the current block. The
+		/// `RequiredStake` is reserved from the relayer account.
+		#[pallet::weight(T::WeightInfo::register())]
+		pub fn register(origin: OriginFor<T>, valid_till: T::BlockNumber) -> DispatchResult {
+			let relayer = ensure_signed(origin)?;
+
+			let lease = valid_till.saturating_sub(frame_system::Pallet::<T>::block_number());
+			ensure!(
+				lease >= T::RequiredRegistrationLease::get(),
+				Error::<T>::InvalidRegistrationLease
+			);
+
+			RegisteredRelayers::<T>::try_mutate(&relayer, |maybe_registration| -> DispatchResult {
+				let mut registration = maybe_registration
+					.unwrap_or_else(|| Registration { valid_till, stake: Zero::zero() });
+
+				// the registration may be extended, but not reduced
+				ensure!(
+					valid_till >= registration.valid_till,
+					Error::<T>::CannotReduceRegistrationLease,
+				);
+				registration.valid_till = valid_till;
 
-			RelayerRewards::<T>::try_mutate_exists(&relayer, |maybe_reward| -> DispatchResult {
-				let reward = maybe_reward.take().ok_or(Error::<T>::NoRewardForRelayer)?;
-				T::PaymentProcedure::pay_reward(&relayer, reward).map_err(|e| {
+				// the required stake may have been changed since previous registration, so we
+				// either reserve the difference or unreserve the excess
+				let required_stake = T::RequiredStake::get();
+				if let Some(to_reserve) = required_stake.checked_sub(&registration.stake) {
+					T::Currency::reserve(&relayer, to_reserve).map_err(|e| {
+						log::trace!(
+							target: LOG_TARGET,
+							"Failed to reserve {:?} on relayer {:?} account: {:?}",
+							to_reserve,
+							relayer,
+							e,
+						);
+						Error::<T>::FailedToReserve
+					})?;
+				} else if let Some(to_unreserve) = registration.stake.checked_sub(&required_stake) {
+					Self::do_unreserve(&relayer, to_unreserve)?;
+				}
+				registration.stake = required_stake;
+
+				log::trace!(target: LOG_TARGET, "Successfully registered relayer: {:?}", relayer);
+				Self::deposit_event(Event::<T>::RegistrationUpdated {
+					relayer: relayer.clone(),
+					registration,
+				});
+
+				*maybe_registration = Some(registration);
+
+				Ok(())
+			})
+		}
+
+		/// Deregister relayer and unreserve its stake.
+		///
+		/// The registration may only be removed after the `valid_till` block.
+		#[pallet::weight(T::WeightInfo::deregister())]
+		pub fn deregister(origin: OriginFor<T>) -> DispatchResult {
+			let relayer = ensure_signed(origin)?;
+
+			RegisteredRelayers::<T>::try_mutate(&relayer, |maybe_registration| -> DispatchResult {
+				let registration = maybe_registration.take().ok_or(Error::<T>::NotRegistered)?;
+
+				// the registration can't be removed while it is active
+				ensure!(
+					registration.valid_till < frame_system::Pallet::<T>::block_number(),
+					Error::<T>::RegistrationIsStillActive,
+				);
+
+				if !registration.stake.is_zero() {
+					Self::do_unreserve(&relayer, registration.stake)?;
+				}
+
+				log::trace!(target: LOG_TARGET, "Successfully deregistered relayer: {:?}", relayer);
+				Self::deposit_event(Event::<T>::Deregistered { relayer: relayer.clone() });
+
+				Ok(())
+			})
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Returns reward of the relayer, accumulated at given lane.
+		pub fn relayer_reward(lane_id: LaneId, relayer: &T::AccountId) -> Option<T::Reward> {
+			RelayerRewards::<T>::get(lane_id, relayer)
+		}
+
+		/// Remember that the reward shall be paid to the relayer.
+		pub fn register_relayer_reward(lane_id: LaneId, relayer: &T::AccountId, reward: T::Reward) {
+			if reward.is_zero() {
+				return
+			}
+
+			RelayerRewards::<T>::mutate(lane_id, relayer, |old_reward: &mut Option<T::Reward>| {
+				let new_reward = old_reward.unwrap_or_else(Zero::zero).saturating_add(reward);
+				*old_reward = Some(new_reward);
+
+				log::trace!(
+					target: LOG_TARGET,
+					"Relayer {:?} can now claim reward at lane {:?}: {:?}",
+					relayer,
+					lane_id,
+					new_reward,
+				);
+			});
+		}
+
+		/// Pay rewards, accumulated at given lanes, to the beneficiary.
+		fn do_claim_rewards(
+			relayer: T::AccountId,
+			lanes: Vec<LaneId>,
+			beneficiary: T::AccountId,
+		) -> DispatchResult {
+			let mut has_claimed_reward = false;
+			for lane_id in lanes {
+				let reward = match RelayerRewards::<T>::take(lane_id, &relayer) {
+					Some(reward) => reward,
+					None => continue,
+				};
+
+				T::PaymentProcedure::pay_reward(&beneficiary, reward).map_err(|e| {
 					log::trace!(
 						target: LOG_TARGET,
-						"Failed to pay rewards to {:?}: {:?}",
+						"Failed to pay {:?} rewards at lane {:?} to {:?}: {:?}",
 						relayer,
+						lane_id,
+						beneficiary,
 						e,
 					);
 					Error::<T>::FailedToPayReward
 				})?;
 
-				Self::deposit_event(Event::<T>::RewardPaid { relayer: relayer.clone(), reward });
-				Ok(())
-			})
+				has_claimed_reward = true;
+				Self::deposit_event(Event::<T>::RewardPaid {
+					relayer: relayer.clone(),
+					lane_id,
+					beneficiary: beneficiary.clone(),
+					reward,
+				});
+			}
+
+			ensure!(has_claimed_reward, Error::<T>::NoRewardForRelayer);
+
+			Ok(())
+		}
+
+		/// Returns true if given relayer registration is active at the current block.
+		///
+		/// Registration is inactive if it has expired or if its stake is lower than the
+		/// `RequiredStake`.
+		pub fn is_registration_active(relayer: &T::AccountId) -> bool {
+			match Self::registered_relayer(relayer) {
+				Some(registration) => registration
+					.is_active(frame_system::Pallet::<T>::block_number(), T::RequiredStake::get()),
+				None => false,
+			}
+		}
+
+		/// Returns priority boost of bridge transactions, submitted by given relayer.
+		pub fn priority_boost(relayer: &T::AccountId) -> TransactionPriority {
+			if Self::is_registration_active(relayer) {
+				T::RegisteredRelayerPriorityBoost::get()
+			} else {
+				0
+			}
+		}
+
+		/// Slash and deregister the relayer.
+		///
+		/// The whole relayer stake is slashed and passed to the `Slash` handler.
+		pub fn slash_and_deregister(relayer: &T::AccountId) {
+			let registration = match RegisteredRelayers::<T>::take(relayer) {
+				Some(registration) => registration,
+				None => {
+					log::trace!(
+						target: LOG_TARGET,
+						"Cannot slash unregistered relayer {:?}",
+						relayer,
+					);
+
+					return
+				},
+			};
+
+			let (imbalance, not_slashed) = T::Currency::slash_reserved(relayer, registration.stake);
+			if !not_slashed.is_zero() {
+				log::trace!(
+					target: LOG_TARGET,
+					"Failed to slash {:?} of {:?} stake of relayer {:?}",
+					not_slashed,
+					registration.stake,
+					relayer,
+				);
+			}
+			T::Slash::on_unbalanced(imbalance);
+
+			log::trace!(target: LOG_TARGET, "Relayer {:?} has been slashed", relayer);
+			Self::deposit_event(Event::<T>::SlashedAndDeregistered {
+				relayer: relayer.clone(),
+				registration,
+			});
+		}
+
+		/// Unreserve given amount on the relayer account.
+		fn do_unreserve(relayer: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+			let failed_to_unreserve = T::Currency::unreserve(relayer, amount);
+			if !failed_to_unreserve.is_zero() {
+				log::trace!(
+					target: LOG_TARGET,
+					"Failed to unreserve {:?}/{:?} on relayer {:?} account",
+					failed_to_unreserve,
+					amount,
+					relayer,
+				);
+
+				return Err(Error::<T>::FailedToUnreserve.into())
+			}
+
+			Ok(())
 		}
 	}
 
@@ -91,9 +374,39 @@ pub mod pallet {
 		RewardPaid {
 			/// Relayer account that has been rewarded.
 			relayer: T::AccountId,
+			/// Lane, where the reward has been accumulated.
+			lane_id: LaneId,
+			/// Account that has received the reward.
+			beneficiary: T::AccountId,
 			/// Reward amount.
 			reward: T::Reward,
 		},
+		/// Relayer beneficiary has been updated.
+		BeneficiaryUpdated {
+			/// Relayer account.
+			relayer: T::AccountId,
+			/// New relayer beneficiary. If `None`, rewards are paid to the relayer account.
+			beneficiary: Option<T::AccountId>,
+		},
+		/// Relayer registration has been added or updated.
+		RegistrationUpdated {
+			/// Relayer account that has been registered.
+			relayer: T::AccountId,
+			/// Relayer registration.
+			registration: RegistrationOf<T>,
+		},
+		/// Relayer has been deregistered.
+		Deregistered {
+			/// Relayer account that has been deregistered.
+			relayer: T::AccountId,
+		},
+		/// Relayer has been slashed and deregistered.
+		SlashedAndDeregistered {
+			/// Relayer account that has been slashed and deregistered.
+			relayer: T::AccountId,
+			/// Registration that was removed.
+			registration: RegistrationOf<T>,
+		},
 	}
 
 	#[pallet::error]
@@ -102,12 +415,43 @@ pub mod pallet {
 		NoRewardForRelayer,
 		/// Reward payment procedure has failed.
 		FailedToPayReward,
+		/// The relayer has tried to register for past block or registration lease
+		/// is too short.
+		InvalidRegistrationLease,
+		/// New `valid_till` is less than the previous one.
+		CannotReduceRegistrationLease,
+		/// Failed to reserve enough funds on relayer account.
+		FailedToReserve,
+		/// Failed to `unreserve` enough funds on relayer account.
+		FailedToUnreserve,
+		/// Cannot deregister the relayer, because it is not registered.
+		NotRegistered,
+		/// Failed to deregister the relayer, because its registration is still active.
+		RegistrationIsStillActive,
 	}
 
-	/// Map of the relayer => accumulated reward.
+	/// Map of the (lane, relayer) => reward, accumulated at this lane.
 	#[pallet::storage]
-	pub type RelayerRewards<T: Config> =
-		StorageMap<_, Blake2_128Concat, T::AccountId, T::Reward, OptionQuery>;
+	pub type RelayerRewards<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		LaneId,
+		Blake2_128Concat,
+		T::AccountId,
+		T::Reward,
+		OptionQuery,
+	>;
+
+	/// Map of the relayer => account that receives relayer rewards.
+	#[pallet::storage]
+	pub type RelayerBeneficiaries<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId, OptionQuery>;
+
+	/// Map of the relayer => its registration.
+	#[pallet::storage]
+	#[pallet::getter(fn registered_relayer)]
+	pub type RegisteredRelayers<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, RegistrationOf<T>, OptionQuery>;
 }
 
 #[cfg(test)]
@@ -115,21 +459,46 @@ mod tests {
 	use super::*;
 	use mock::{Event as TestEvent, *};
 
-	use crate::Event::RewardPaid;
-	use frame_support::{assert_noop, assert_ok, traits::fungible::Inspect};
+	use crate::Event::{
+		BeneficiaryUpdated, Deregistered, RegistrationUpdated, RewardPaid, SlashedAndDeregistered,
+	};
+	use frame_support::{
+		assert_noop, assert_ok,
+		traits::fungible::{Inspect, Mutate},
+	};
 	use frame_system::{EventRecord, Pallet as System, Phase};
 	use sp_runtime::DispatchError;
 
+	const TEST_LANE_ID: LaneId = [0, 0, 0, 0];
+	const OTHER_LANE_ID: LaneId = [0, 0, 0, 1];
+
 	fn get_ready_for_events() {
 		System::<TestRuntime>::set_block_number(1);
 		System::<TestRuntime>::reset_events();
 	}
 
+	fn reward_paid_event(
+		lane_id: LaneId,
+		beneficiary: AccountId,
+		reward: Balance,
+	) -> EventRecord<TestEvent, sp_core::H256> {
+		EventRecord {
+			phase: Phase::Initialization,
+			event: TestEvent::Relayers(RewardPaid {
+				relayer: REGULAR_RELAYER,
+				lane_id,
+				beneficiary,
+				reward,
+			}),
+			topics: vec![],
+		}
+	}
+
 	#[test]
 	fn root_cant_claim_anything() {
 		run_test(|| {
 			assert_noop!(
-				Pallet::<TestRuntime>::claim_rewards(Origin::root()),
+				Pallet::<TestRuntime>::claim_rewards(Origin::root(), vec![TEST_LANE_ID]),
 				DispatchError::BadOrigin,
 			);
 		});
@@ -139,7 +508,24 @@ mod tests {
 	fn relayer_cant_claim_if_no_reward_exists() {
 		run_test(|| {
 			assert_noop!(
-				Pallet::<TestRuntime>::claim_rewards(Origin::signed(REGULAR_RELAYER)),
+				Pallet::<TestRuntime>::claim_rewards(
+					Origin::signed(REGULAR_RELAYER),
+					vec![TEST_LANE_ID]
+				),
+				Error::<TestRuntime>::NoRewardForRelayer,
+			);
+		});
+	}
+
+	#[test]
+	fn relayer_cant_claim_reward_from_other_lane() {
+		run_test(|| {
+			RelayerRewards::<TestRuntime>::insert(OTHER_LANE_ID, REGULAR_RELAYER, 100);
+			assert_noop!(
+				Pallet::<TestRuntime>::claim_rewards(
+					Origin::signed(REGULAR_RELAYER),
+					vec![TEST_LANE_ID]
+				),
 				Error::<TestRuntime>::NoRewardForRelayer,
 			);
 		});
@@ -148,9 +534,12 @@ mod tests {
 	#[test]
 	fn relayer_cant_claim_if_payment_procedure_fails() {
 		run_test(|| {
-			RelayerRewards::<TestRuntime>::insert(FAILING_RELAYER, 100);
+			RelayerRewards::<TestRuntime>::insert(TEST_LANE_ID, FAILING_RELAYER, 100);
 			assert_noop!(
-				Pallet::<TestRuntime>::claim_rewards(Origin::signed(FAILING_RELAYER)),
+				Pallet::<TestRuntime>::claim_rewards(
+					Origin::signed(FAILING_RELAYER),
+					vec![TEST_LANE_ID]
+				),
 				Error::<TestRuntime>::FailedToPayReward,
 			);
 		});
@@ -161,21 +550,154 @@ mod tests {
 		run_test(|| {
 			get_ready_for_events();
 
-			RelayerRewards::<TestRuntime>::insert(REGULAR_RELAYER, 100);
-			assert_ok!(Pallet::<TestRuntime>::claim_rewards(Origin::signed(REGULAR_RELAYER)));
-			assert_eq!(RelayerRewards::<TestRuntime>::get(REGULAR_RELAYER), None);
+			RelayerRewards::<TestRuntime>::insert(TEST_LANE_ID, REGULAR_RELAYER, 100);
+			assert_ok!(Pallet::<TestRuntime>::claim_rewards(
+				Origin::signed(REGULAR_RELAYER),
+				vec![TEST_LANE_ID]
+			));
+			assert_eq!(RelayerRewards::<TestRuntime>::get(TEST_LANE_ID, REGULAR_RELAYER), None);
 
 			//Check if the `RewardPaid` event was emitted.
 			assert_eq!(
 				System::<TestRuntime>::events(),
-				vec![EventRecord {
-					phase: Phase::Initialization,
-					event: TestEvent::Relayers(RewardPaid {
-						relayer: REGULAR_RELAYER,
-						reward: 100
-					}),
-					topics: vec![],
-				}],
+				vec![reward_paid_event(TEST_LANE_ID, REGULAR_RELAYER, 100)],
+			);
+		});
+	}
+
+	#[test]
+	fn relayer_can_claim_rewards_from_multiple_lanes() {
+		run_test(|| {
+			get_ready_for_events();
+
+			RelayerRewards::<TestRuntime>::insert(TEST_LANE_ID, REGULAR_RELAYER, 100);
+			RelayerRewards::<TestRuntime>::insert(OTHER_LANE_ID, REGULAR_RELAYER, 200);
+			assert_ok!(Pallet::<TestRuntime>::claim_rewards(
+				Origin::signed(REGULAR_RELAYER),
+				vec![TEST_LANE_ID, [0, 0, 0, 2], OTHER_LANE_ID]
+			));
+			assert_eq!(RelayerRewards::<TestRuntime>::get(TEST_LANE_ID, REGULAR_RELAYER), None);
+			assert_eq!(RelayerRewards::<TestRuntime>::get(OTHER_LANE_ID, REGULAR_RELAYER), None);
+
+			assert_eq!(
+				System::<TestRuntime>::events(),
+				vec![
+					reward_paid_event(TEST_LANE_ID, REGULAR_RELAYER, 100),
+					reward_paid_event(OTHER_LANE_ID, REGULAR_RELAYER, 200),
+				],
+			);
+		});
+	}
+
+	#[test]
+	fn relayer_can_claim_reward_to_beneficiary() {
+		run_test(|| {
+			get_ready_for_events();
+
+			RelayerRewards::<TestRuntime>::insert(TEST_LANE_ID, REGULAR_RELAYER, 100);
+			assert_ok!(Pallet::<TestRuntime>::claim_rewards_to(
+				Origin::signed(REGULAR_RELAYER),
+				vec![TEST_LANE_ID],
+				REGISTER_RELAYER,
+			));
+			assert_eq!(RelayerRewards::<TestRuntime>::get(TEST_LANE_ID, REGULAR_RELAYER), None);
+
+			assert_eq!(
+				System::<TestRuntime>::events(),
+				vec![reward_paid_event(TEST_LANE_ID, REGISTER_RELAYER, 100)],
+			);
+		});
+	}
+
+	#[test]
+	fn relayer_cant_claim_reward_if_payment_to_beneficiary_fails() {
+		run_test(|| {
+			RelayerRewards::<TestRuntime>::insert(TEST_LANE_ID, REGULAR_RELAYER, 100);
+			assert_noop!(
+				Pallet::<TestRuntime>::claim_rewards_to(
+					Origin::signed(REGULAR_RELAYER),
+					vec![TEST_LANE_ID],
+					FAILING_RELAYER,
+				),
+				Error::<TestRuntime>::FailedToPayReward,
+			);
+		});
+	}
+
+	#[test]
+	fn reward_is_paid_to_configured_beneficiary() {
+		run_test(|| {
+			get_ready_for_events();
+
+			assert_ok!(Pallet::<TestRuntime>::set_beneficiary(
+				Origin::signed(REGULAR_RELAYER),
+				Some(REGISTER_RELAYER),
+			));
+			RelayerRewards::<TestRuntime>::insert(TEST_LANE_ID, REGULAR_RELAYER, 100);
+			assert_ok!(Pallet::<TestRuntime>::claim_rewards(
+				Origin::signed(REGULAR_RELAYER),
+				vec![TEST_LANE_ID]
+			));
+
+			assert_eq!(
+				System::<TestRuntime>::events(),
+				vec![
+					EventRecord {
+						phase: Phase::Initialization,
+						event: TestEvent::Relayers(BeneficiaryUpdated {
+							relayer: REGULAR_RELAYER,
+							beneficiary: Some(REGISTER_RELAYER),
+						}),
+						topics: vec![],
+					},
+					reward_paid_event(TEST_LANE_ID, REGISTER_RELAYER, 100),
+				],
+			);
+		});
+	}
+
+	#[test]
+	fn reward_is_paid_to_relayer_when_beneficiary_is_reset() {
+		run_test(|| {
+			assert_ok!(Pallet::<TestRuntime>::set_beneficiary(
+				Origin::signed(REGULAR_RELAYER),
+				Some(REGISTER_RELAYER),
+			));
+			assert_ok!(Pallet::<TestRuntime>::set_beneficiary(
+				Origin::signed(REGULAR_RELAYER),
+				None,
+			));
+			assert_eq!(RelayerBeneficiaries::<TestRuntime>::get(REGULAR_RELAYER), None);
+
+			get_ready_for_events();
+			RelayerRewards::<TestRuntime>::insert(TEST_LANE_ID, REGULAR_RELAYER, 100);
+			assert_ok!(Pallet::<TestRuntime>::claim_rewards(
+				Origin::signed(REGULAR_RELAYER),
+				vec![TEST_LANE_ID]
+			));
+
+			assert_eq!(
+				System::<TestRuntime>::events(),
+				vec![reward_paid_event(TEST_LANE_ID, REGULAR_RELAYER, 100)],
+			);
+		});
+	}
+
+	#[test]
+	fn rewards_are_registered_separately_for_every_lane() {
+		run_test(|| {
+			Pallet::<TestRuntime>::register_relayer_reward(TEST_LANE_ID, &REGULAR_RELAYER, 100);
+			Pallet::<TestRuntime>::register_relayer_reward(TEST_LANE_ID, &REGULAR_RELAYER, 50);
+			Pallet::<TestRuntime>::register_relayer_reward(OTHER_LANE_ID, &REGULAR_RELAYER, 10);
+			Pallet::<TestRuntime>::register_relayer_reward(OTHER_LANE_ID, &REGULAR_RELAYER, 0);
+
+			assert_eq!(
+				Pallet::<TestRuntime>::relayer_reward(TEST_LANE_ID, &REGULAR_RELAYER),
+				Some(150)
+			);
+			assert_eq!(
+				Pallet::<TestRuntime>::relayer_reward(OTHER_LANE_ID, &REGULAR_RELAYER),
+				Some(10)
 			);
 		});
 	}
@@ -192,4 +714,209 @@ mod tests {
 			assert_eq!(Balances::total_issuance(), 100);
 		});
 	}
+
+	fn fund_register_relayer() {
+		Balances::mint_into(&REGISTER_RELAYER, 3 * Stake::get()).unwrap();
+	}
+
+	fn register_relayer(valid_till: u64) {
+		assert_ok!(Pallet::<TestRuntime>::register(Origin::signed(REGISTER_RELAYER), valid_till));
+	}
+
+	#[test]
+	fn register_fails_if_valid_till_is_a_past_block() {
+		run_test(|| {
+			System::<TestRuntime>::set_block_number(100);
+			fund_register_relayer();
+
+			assert_noop!(
+				Pallet::<TestRuntime>::register(Origin::signed(REGISTER_RELAYER), 50),
+				Error::<TestRuntime>::InvalidRegistrationLease,
+			);
+		});
+	}
+
+	#[test]
+	fn register_fails_if_valid_till_lease_is_less_than_required() {
+		run_test(|| {
+			System::<TestRuntime>::set_block_number(100);
+			fund_register_relayer();
+
+			assert_noop!(
+				Pallet::<TestRuntime>::register(
+					Origin::signed(REGISTER_RELAYER),
+					99 + Lease::get()
+				),
+				Error::<TestRuntime>::InvalidRegistrationLease,
+			);
+		});
+	}
+
+	#[test]
+	fn register_works() {
+		run_test(|| {
+			get_ready_for_events();
+			fund_register_relayer();
+
+			register_relayer(150);
+			assert_eq!(Balances::reserved_balance(REGISTER_RELAYER), Stake::get());
+			assert_eq!(
+				Pallet::<TestRuntime>::registered_relayer(REGISTER_RELAYER),
+				Some(Registration { valid_till: 150, stake: Stake::get() }),
+			);
+			assert!(Pallet::<TestRuntime>::is_registration_active(&REGISTER_RELAYER));
+			assert_eq!(
+				Pallet::<TestRuntime>::priority_boost(&REGISTER_RELAYER),
+				PriorityBoost::get(),
+			);
+
+			assert_eq!(
+				System::<TestRuntime>::events().last(),
+				Some(&EventRecord {
+					phase: Phase::Initialization,
+					event: TestEvent::Relayers(RegistrationUpdated {
+						relayer: REGISTER_RELAYER,
+						registration: Registration { valid_till: 150, stake: Stake::get() },
+					}),
+					topics: vec![],
+				}),
+			);
+		});
+	}
+
+	#[test]
+	fn register_fails_if_new_valid_till_is_lesser_than_previous() {
+		run_test(|| {
+			fund_register_relayer();
+			register_relayer(150);
+
+			assert_noop!(
+				Pallet::<TestRuntime>::register(Origin::signed(REGISTER_RELAYER), 125),
+				Error::<TestRuntime>::CannotReduceRegistrationLease,
+			);
+		});
+	}
+
+	#[test]
+	fn register_extends_existing_registration() {
+		run_test(|| {
+			fund_register_relayer();
+			register_relayer(150);
+			register_relayer(200);
+
+			assert_eq!(Balances::reserved_balance(REGISTER_RELAYER), Stake::get());
+			assert_eq!(
+				Pallet::<TestRuntime>::registered_relayer(REGISTER_RELAYER),
+				Some(Registration { valid_till: 200, stake: Stake::get() }),
+			);
+		});
+	}
+
+	#[test]
+	fn register_fails_if_relayer_has_not_enough_funds() {
+		run_test(|| {
+			assert_noop!(
+				Pallet::<TestRuntime>::register(Origin::signed(REGISTER_RELAYER), 150),
+				Error::<TestRuntime>::FailedToReserve,
+			);
+		});
+	}
+
+	#[test]
+	fn deregister_fails_if_not_registered() {
+		run_test(|| {
+			assert_noop!(
+				Pallet::<TestRuntime>::deregister(Origin::signed(REGISTER_RELAYER)),
+				Error::<TestRuntime>::NotRegistered,
+			);
+		});
+	}
+
+	#[test]
+	fn deregister_fails_if_registration_is_still_active() {
+		run_test(|| {
+			fund_register_relayer();
+			register_relayer(150);
+
+			System::<TestRuntime>::set_block_number(150);
+			assert_noop!(
+				Pallet::<TestRuntime>::deregister(Origin::signed(REGISTER_RELAYER)),
+				Error::<TestRuntime>::RegistrationIsStillActive,
+			);
+		});
+	}
+
+	#[test]
+	fn deregister_works() {
+		run_test(|| {
+			get_ready_for_events();
+			fund_register_relayer();
+			register_relayer(150);
+
+			System::<TestRuntime>::set_block_number(151);
+			assert!(!Pallet::<TestRuntime>::is_registration_active(&REGISTER_RELAYER));
+			assert_eq!(Pallet::<TestRuntime>::priority_boost(&REGISTER_RELAYER), 0);
+
+			assert_ok!(Pallet::<TestRuntime>::deregister(Origin::signed(REGISTER_RELAYER)));
+			assert_eq!(Balances::reserved_balance(REGISTER_RELAYER), 0);
+			assert_eq!(Balances::free_balance(REGISTER_RELAYER), 3 * Stake::get());
+			assert_eq!(Pallet::<TestRuntime>::registered_relayer(REGISTER_RELAYER), None);
+
+			assert_eq!(
+				System::<TestRuntime>::events().last(),
+				Some(&EventRecord {
+					phase: Phase::Initialization,
+					event: TestEvent::Relayers(Deregistered { relayer: REGISTER_RELAYER }),
+					topics: vec![],
+				}),
+			);
+		});
+	}
+
+	#[test]
+	fn slash_and_deregister_works() {
+		run_test(|| {
+			get_ready_for_events();
+			fund_register_relayer();
+			register_relayer(150);
+
+			Pallet::<TestRuntime>::slash_and_deregister(&REGISTER_RELAYER);
+			assert_eq!(Balances::reserved_balance(REGISTER_RELAYER), 0);
+			assert_eq!(Balances::free_balance(REGISTER_RELAYER), 2 * Stake::get());
+			assert_eq!(Pallet::<TestRuntime>::registered_relayer(REGISTER_RELAYER), None);
+			assert!(!Pallet::<TestRuntime>::is_registration_active(&REGISTER_RELAYER));
+
+			assert_eq!(
+				System::<TestRuntime>::events().last(),
+				Some(&EventRecord {
+					phase: Phase::Initialization,
+					event: TestEvent::Relayers(SlashedAndDeregistered {
+						relayer: REGISTER_RELAYER,
+						registration: Registration { valid_till: 150, stake: Stake::get() },
+					}),
+					topics: vec![],
+				}),
+			);
+		});
+	}
+
+	#[test]
+	fn storage_keys_computed_properly() {
+		use bp_relayers::RelayerRewardsKeyProvider;
+		use bp_runtime::StorageDoubleMapKeyProvider;
+		use frame_support::storage::generator::StorageDoubleMap;
+
+		assert_eq!(
+			RelayerRewards::<TestRuntime>::storage_double_map_final_key(
+				TEST_LANE_ID,
+				REGULAR_RELAYER
+			),
+			RelayerRewardsKeyProvider::<AccountId, Balance>::final_key(
+				"Relayers",
+				&TEST_LANE_ID,
+				&REGULAR_RELAYER,
+			)
+			.0,
+		);
+	}
 }
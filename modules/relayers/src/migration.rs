@@ -0,0 +1,136 @@
+// Copyright 2019-2022 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Storage migrations of the relayers pallet.
+
+use crate::{Config, Pallet, LOG_TARGET};
+
+use bp_messages::LaneId;
+use frame_support::{
+	traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	weights::Weight,
+};
+use sp_std::{marker::PhantomData, vec::Vec};
+
+/// Storage items of the version 0 of the pallet.
+mod v0 {
+	use super::*;
+	use frame_support::Blake2_128Concat;
+
+	/// Map of the relayer => accumulated reward.
+	#[frame_support::storage_alias]
+	pub type RelayerRewards<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		<T as Config>::Reward,
+	>;
+}
+
+/// Migration from the storage version 0 to the version 1.
+pub mod v1 {
+	use super::*;
+
+	/// Moves rewards from the `relayer => reward` map to the `(lane, relayer) => reward` map.
+	///
+	/// Version 0 of the pallet hasn't been tracking lanes, so all rewards are moved to the
+	/// `Lane`.
+	pub struct MigrateToV1<T, Lane>(PhantomData<(T, Lane)>);
+
+	impl<T: Config, Lane: Get<LaneId>> OnRuntimeUpgrade for MigrateToV1<T, Lane> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version != 0 {
+				log::info!(
+					target: LOG_TARGET,
+					"Skipping relayer rewards migration: storage version is {:?}",
+					on_chain_version,
+				);
+				return T::DbWeight::get().reads(1)
+			}
+
+			let lane_id = Lane::get();
+			let old_rewards = v0::RelayerRewards::<T>::drain().collect::<Vec<_>>();
+			let migrated_rewards = old_rewards.len() as Weight;
+			for (relayer, reward) in old_rewards {
+				crate::RelayerRewards::<T>::insert(lane_id, relayer, reward);
+			}
+			StorageVersion::new(1).put::<Pallet<T>>();
+
+			log::info!(
+				target: LOG_TARGET,
+				"Moved {} relayer rewards to lane {:?}",
+				migrated_rewards,
+				lane_id,
+			);
+
+			T::DbWeight::get().reads_writes(migrated_rewards + 1, 2 * migrated_rewards + 1)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{mock::*, RelayerRewards};
+	use frame_support::parameter_types;
+
+	parameter_types! {
+		pub const MigrationLane: LaneId = [0, 0, 0, 1];
+	}
+
+	#[test]
+	fn rewards_are_moved_to_the_lane() {
+		run_test(|| {
+			StorageVersion::new(0).put::<Pallet<TestRuntime>>();
+			v0::RelayerRewards::<TestRuntime>::insert(REGULAR_RELAYER, 100);
+			v0::RelayerRewards::<TestRuntime>::insert(FAILING_RELAYER, 200);
+
+			v1::MigrateToV1::<TestRuntime, MigrationLane>::on_runtime_upgrade();
+
+			assert_eq!(Pallet::<TestRuntime>::on_chain_storage_version(), 1);
+			assert_eq!(
+				RelayerRewards::<TestRuntime>::get(MigrationLane::get(), REGULAR_RELAYER),
+				Some(100),
+			);
+			assert_eq!(
+				RelayerRewards::<TestRuntime>::get(MigrationLane::get(), FAILING_RELAYER),
+				Some(200),
+			);
+			assert_eq!(v0::RelayerRewards::<TestRuntime>::get(REGULAR_RELAYER), None);
+			assert_eq!(v0::RelayerRewards::<TestRuntime>::get(FAILING_RELAYER), None);
+		});
+	}
+
+	#[test]
+	fn migration_is_not_applied_twice() {
+		run_test(|| {
+			StorageVersion::new(1).put::<Pallet<TestRuntime>>();
+			RelayerRewards::<TestRuntime>::insert([0, 0, 0, 0], REGULAR_RELAYER, 100);
+
+			v1::MigrateToV1::<TestRuntime, MigrationLane>::on_runtime_upgrade();
+
+			assert_eq!(
+				RelayerRewards::<TestRuntime>::get([0, 0, 0, 0], REGULAR_RELAYER),
+				Some(100),
+			);
+			assert_eq!(
+				RelayerRewards::<TestRuntime>::get(MigrationLane::get(), REGULAR_RELAYER),
+				None,
+			);
+		});
+	}
+}
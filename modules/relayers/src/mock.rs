@@ -25,6 +25,7 @@ use sp_core::H256;
 use sp_runtime::{
 	testing::Header as SubstrateHeader,
 	traits::{BlakeTwo256, IdentityLookup},
+	transaction_validity::TransactionPriority,
 };
 
 pub type AccountId = u64;
@@ -101,6 +102,11 @@ impl pallet_bridge_messages::Config for TestRuntime {
 	type MaxMessagesToPruneAtOnce = frame_support::traits::ConstU64<0>;
 	type MaxUnrewardedRelayerEntriesAtInboundLane = frame_support::traits::ConstU64<8>;
 	type MaxUnconfirmedMessagesAtInboundLane = frame_support::traits::ConstU64<8>;
+	type MaxFailedDispatchesAtInboundLane = frame_support::traits::ConstU32<0>;
+	type MaxFailedDispatchPayloadSize = frame_support::traits::ConstU32<0>;
+	type FailedDispatchLifetime = frame_support::traits::ConstU64<0>;
+	type ConfirmedMessageStatusLifetime = frame_support::traits::ConstU64<0>;
+	type MaxMessageResponseSize = frame_support::traits::ConstU32<0>;
 
 	type MaximalOutboundPayloadSize = frame_support::traits::ConstU32<1024>;
 	type OutboundPayload = ();
@@ -115,16 +121,28 @@ impl pallet_bridge_messages::Config for TestRuntime {
 	type MessageDeliveryAndDispatchPayment = ();
 	type OnMessageAccepted = ();
 	type OnDeliveryConfirmed = ();
+	type OnMessageResponse = ();
 
 	type SourceHeaderChain = ForbidInboundMessages;
 	type MessageDispatch = ForbidInboundMessages;
 	type BridgedChainId = TestBridgedChainId;
 }
 
+parameter_types! {
+	pub const Stake: Balance = 1_000;
+	pub const Lease: u64 = 8;
+	pub const PriorityBoost: TransactionPriority = 1_000;
+}
+
 impl pallet_bridge_relayers::Config for TestRuntime {
 	type Event = Event;
 	type Reward = Balance;
 	type PaymentProcedure = TestPaymentProcedure;
+	type Currency = Balances;
+	type RequiredStake = Stake;
+	type RequiredRegistrationLease = Lease;
+	type RegisteredRelayerPriorityBoost = PriorityBoost;
+	type Slash = ();
 	type WeightInfo = ();
 }
 
@@ -134,6 +152,9 @@ pub const REGULAR_RELAYER: AccountId = 1;
 /// Relayer that can't receive rewards.
 pub const FAILING_RELAYER: AccountId = 2;
 
+/// Relayer that is used in registration tests.
+pub const REGISTER_RELAYER: AccountId = 3;
+
 /// Payment procedure that rejects payments to the `FAILING_RELAYER`.
 pub struct TestPaymentProcedure;
 
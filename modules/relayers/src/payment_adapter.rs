@@ -17,9 +17,12 @@
 //! Code that allows relayers pallet to be used as a delivery+dispatch payment mechanism
 //! for the messages pallet.
 
-use crate::{Config, RelayerRewards};
+use crate::{Config, Pallet};
 
-use bp_messages::source_chain::{MessageDeliveryAndDispatchPayment, RelayersRewards};
+use bp_messages::{
+	source_chain::{MessageDeliveryAndDispatchPayment, RelayersRewards},
+	LaneId,
+};
 use frame_support::{sp_runtime::SaturatedConversion, traits::Get};
 use sp_arithmetic::traits::{Saturating, Zero};
 use sp_std::{collections::vec_deque::VecDeque, marker::PhantomData, ops::RangeInclusive};
@@ -49,6 +52,15 @@ where
 		Ok(())
 	}
 
+	fn refund_delivery_and_dispatch_fee(
+		_sender: &T::AccountId,
+		_fee: &T::Reward,
+	) -> Result<(), Self::Error> {
+		// nothing has been withdrawn in `pay_delivery_and_dispatch_fee`, so there's nothing to
+		// refund
+		Ok(())
+	}
+
 	fn pay_relayers_rewards(
 		lane_id: bp_messages::LaneId,
 		messages_relayers: VecDeque<bp_messages::UnrewardedRelayer<T::AccountId>>,
@@ -62,6 +74,7 @@ where
 		);
 
 		register_relayers_rewards::<T>(
+			lane_id,
 			confirmation_relayer,
 			relayers_rewards,
 			GetConfirmationFee::get(),
@@ -71,6 +84,7 @@ where
 
 // Update rewards to given relayers, optionally rewarding confirmation relayer.
 fn register_relayers_rewards<T: Config>(
+	lane_id: LaneId,
 	confirmation_relayer: &T::AccountId,
 	relayers_rewards: RelayersRewards<T::AccountId, T::Reward>,
 	confirmation_fee: T::Reward,
@@ -93,7 +107,7 @@ fn register_relayers_rewards<T: Config>(
 			confirmation_relayer_reward =
 				confirmation_relayer_reward.saturating_add(confirmation_reward);
 
-			register_relayer_reward::<T>(&relayer, relayer_reward);
+			Pallet::<T>::register_relayer_reward(lane_id, &relayer, relayer_reward);
 		} else {
 			// If delivery confirmation is submitted by this relayer, let's add confirmation fee
 			// from other relayers to this relayer reward.
@@ -103,33 +117,19 @@ fn register_relayers_rewards<T: Config>(
 	}
 
 	// finally - pay reward to confirmation relayer
-	register_relayer_reward::<T>(confirmation_relayer, confirmation_relayer_reward);
-}
-
-/// Remember that the reward shall be paid to the relayer.
-fn register_relayer_reward<T: Config>(relayer: &T::AccountId, reward: T::Reward) {
-	if reward.is_zero() {
-		return
-	}
-
-	RelayerRewards::<T>::mutate(relayer, |old_reward: &mut Option<T::Reward>| {
-		let new_reward = old_reward.unwrap_or_else(Zero::zero).saturating_add(reward);
-		*old_reward = Some(new_reward);
-
-		log::trace!(
-			target: crate::LOG_TARGET,
-			"Relayer {:?} can now claim reward: {:?}",
-			relayer,
-			new_reward,
-		);
-	});
+	Pallet::<T>::register_relayer_reward(
+		lane_id,
+		confirmation_relayer,
+		confirmation_relayer_reward,
+	);
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::mock::*;
+	use crate::{mock::*, RelayerRewards};
 
+	const TEST_LANE_ID: LaneId = [0, 0, 0, 0];
 	const RELAYER_1: AccountId = 1;
 	const RELAYER_2: AccountId = 2;
 	const RELAYER_3: AccountId = 3;
@@ -146,32 +146,47 @@ mod tests {
 	#[test]
 	fn confirmation_relayer_is_rewarded_if_it_has_also_delivered_messages() {
 		run_test(|| {
-			register_relayers_rewards::<TestRuntime>(&RELAYER_2, relayers_rewards(), 10);
-
-			assert_eq!(RelayerRewards::<TestRuntime>::get(RELAYER_1), Some(80));
-			assert_eq!(RelayerRewards::<TestRuntime>::get(RELAYER_2), Some(120));
+			register_relayers_rewards::<TestRuntime>(
+				TEST_LANE_ID,
+				&RELAYER_2,
+				relayers_rewards(),
+				10,
+			);
+
+			assert_eq!(RelayerRewards::<TestRuntime>::get(TEST_LANE_ID, RELAYER_1), Some(80));
+			assert_eq!(RelayerRewards::<TestRuntime>::get(TEST_LANE_ID, RELAYER_2), Some(120));
 		});
 	}
 
 	#[test]
 	fn confirmation_relayer_is_rewarded_if_it_has_not_delivered_any_delivered_messages() {
 		run_test(|| {
-			register_relayers_rewards::<TestRuntime>(&RELAYER_3, relayers_rewards(), 10);
-
-			assert_eq!(RelayerRewards::<TestRuntime>::get(RELAYER_1), Some(80));
-			assert_eq!(RelayerRewards::<TestRuntime>::get(RELAYER_2), Some(70));
-			assert_eq!(RelayerRewards::<TestRuntime>::get(RELAYER_3), Some(50));
+			register_relayers_rewards::<TestRuntime>(
+				TEST_LANE_ID,
+				&RELAYER_3,
+				relayers_rewards(),
+				10,
+			);
+
+			assert_eq!(RelayerRewards::<TestRuntime>::get(TEST_LANE_ID, RELAYER_1), Some(80));
+			assert_eq!(RelayerRewards::<TestRuntime>::get(TEST_LANE_ID, RELAYER_2), Some(70));
+			assert_eq!(RelayerRewards::<TestRuntime>::get(TEST_LANE_ID, RELAYER_3), Some(50));
 		});
 	}
 
 	#[test]
 	fn only_confirmation_relayer_is_rewarded_if_confirmation_fee_has_significantly_increased() {
 		run_test(|| {
-			register_relayers_rewards::<TestRuntime>(&RELAYER_3, relayers_rewards(), 1000);
-
-			assert_eq!(RelayerRewards::<TestRuntime>::get(RELAYER_1), None);
-			assert_eq!(RelayerRewards::<TestRuntime>::get(RELAYER_2), None);
-			assert_eq!(RelayerRewards::<TestRuntime>::get(RELAYER_3), Some(200));
+			register_relayers_rewards::<TestRuntime>(
+				TEST_LANE_ID,
+				&RELAYER_3,
+				relayers_rewards(),
+				1000,
+			);
+
+			assert_eq!(RelayerRewards::<TestRuntime>::get(TEST_LANE_ID, RELAYER_1), None);
+			assert_eq!(RelayerRewards::<TestRuntime>::get(TEST_LANE_ID, RELAYER_2), None);
+			assert_eq!(RelayerRewards::<TestRuntime>::get(TEST_LANE_ID, RELAYER_3), Some(200));
 		});
 	}
 }
@@ -50,7 +50,11 @@ use sp_std::marker::PhantomData;
 
 /// Weight functions needed for `pallet_bridge_relayers`.
 pub trait WeightInfo {
-	fn claim_rewards() -> Weight;
+	fn claim_rewards(l: u32) -> Weight;
+	fn set_beneficiary() -> Weight;
+	fn register() -> Weight;
+	fn deregister() -> Weight;
+	fn slash_and_deregister() -> Weight;
 }
 
 /// Weights for `pallet_bridge_relayers` that are generated using one of the Bridge testnets.
@@ -58,8 +62,29 @@ pub trait WeightInfo {
 /// Those weights are test only and must never be used in production.
 pub struct BridgeWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for BridgeWeight<T> {
-	fn claim_rewards() -> Weight {
-		(55_856_000 as Weight)
+	fn claim_rewards(l: u32) -> Weight {
+		(18_316_000 as Weight)
+			// Standard Error: 24_000
+			.saturating_add((41_247_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(l as Weight)))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(l as Weight)))
+	}
+	fn set_beneficiary() -> Weight {
+		(17_592_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn register() -> Weight {
+		(39_542_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn deregister() -> Weight {
+		(41_263_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn slash_and_deregister() -> Weight {
+		(36_915_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
@@ -67,8 +92,29 @@ impl<T: frame_system::Config> WeightInfo for BridgeWeight<T> {
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
-	fn claim_rewards() -> Weight {
-		(55_856_000 as Weight)
+	fn claim_rewards(l: u32) -> Weight {
+		(18_316_000 as Weight)
+			// Standard Error: 24_000
+			.saturating_add((41_247_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(l as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(l as Weight)))
+	}
+	fn set_beneficiary() -> Weight {
+		(17_592_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn register() -> Weight {
+		(39_542_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn deregister() -> Weight {
+		(41_263_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn slash_and_deregister() -> Weight {
+		(36_915_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 	}
@@ -0,0 +1,138 @@
+// Copyright 2019-2022 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarks for the token transfer pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use crate::{
+	BalanceOf, Call, Config as TokenTransferConfig, InboundTransferMessageOf,
+	Pallet as TokenTransfer, PendingTransfer, PendingTransfers, RejectedTransfers,
+	TokenTransferMessage,
+};
+
+use bp_messages::{source_chain::OnDeliveryConfirmed, DeliveredMessages, MessageNonce};
+use codec::Decode;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::{Currency, Get};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Saturating, TrailingZeroInput, Zero};
+
+/// Nonce of the transfer message that is used in benchmarks.
+const TRANSFER_NONCE: MessageNonce = 1;
+
+/// Pallet we're benchmarking here.
+pub struct Pallet<T: Config>(TokenTransfer<T>);
+
+/// Trait that must be implemented by runtime.
+pub trait Config: TokenTransferConfig {
+	/// Delivery and dispatch fee of the transfer message, that is accepted by the messages
+	/// pallet.
+	fn message_fee() -> BalanceOf<Self>;
+}
+
+benchmarks! {
+	// Benchmark `transfer` call.
+	transfer {
+		let sender: T::AccountId = whitelisted_caller();
+		let amount = T::MinimalTransfer::get();
+		let fee = T::message_fee();
+		fund_account::<T>(&sender, amount.saturating_add(fee));
+	}: _(RawOrigin::Signed(sender.clone()), bridged_account_id::<T>(), amount, fee)
+	verify {
+		assert_eq!(PendingTransfers::<T>::iter().count(), 1);
+	}
+
+	// Benchmark `receive_transfer` method of the pallet in the worst case: tokens are unlocked
+	// from the bridge account and the inbound transfers volume is updated.
+	receive_transfer {
+		let amount = T::MinimalTransfer::get();
+		fund_account::<T>(&TokenTransfer::<T>::bridge_account(), amount);
+		let recipient: T::AccountId = account("recipient", 0, 0);
+		let message: InboundTransferMessageOf<T> = TokenTransferMessage {
+			sender: bridged_account_id::<T>(),
+			recipient: recipient.clone(),
+			amount,
+		};
+	}: {
+		TokenTransfer::<T>::receive_transfer(TRANSFER_NONCE, message)?
+	}
+	verify {
+		assert!(!T::Currency::free_balance(&recipient).is_zero());
+	}
+
+	// Benchmark `on_messages_delivered` callback for a single successfully dispatched transfer.
+	complete_transfer {
+		insert_pending_transfer::<T>();
+	}: {
+		TokenTransfer::<T>::on_messages_delivered(
+			&T::Lane::get(),
+			&DeliveredMessages::new(TRANSFER_NONCE, true),
+		)
+	}
+	verify {
+		assert!(!PendingTransfers::<T>::contains_key(TRANSFER_NONCE));
+	}
+
+	// Benchmark `on_messages_delivered` callback for a single rejected transfer.
+	reject_transfer {
+		insert_pending_transfer::<T>();
+	}: {
+		TokenTransfer::<T>::on_messages_delivered(
+			&T::Lane::get(),
+			&DeliveredMessages::new(TRANSFER_NONCE, false),
+		)
+	}
+	verify {
+		assert!(RejectedTransfers::<T>::contains_key(TRANSFER_NONCE));
+	}
+
+	// Benchmark refund of a single rejected transfer.
+	refund_transfer {
+		let sender = insert_pending_transfer::<T>();
+		RejectedTransfers::<T>::insert(TRANSFER_NONCE, ());
+	}: {
+		TokenTransfer::<T>::refund_rejected_transfers()
+	}
+	verify {
+		assert!(!PendingTransfers::<T>::contains_key(TRANSFER_NONCE));
+		assert!(!T::Currency::free_balance(&sender).is_zero());
+	}
+}
+
+/// Returns account at the bridged chain that is used in benchmarks.
+fn bridged_account_id<T: Config>() -> T::BridgedAccountId {
+	T::BridgedAccountId::decode(&mut TrailingZeroInput::zeroes())
+		.expect("infinite input is used to decode the account id")
+}
+
+/// Give account enough funds to pay given amount and stay alive.
+fn fund_account<T: Config>(account: &T::AccountId, amount: BalanceOf<T>) {
+	let balance = T::Currency::minimum_balance().saturating_add(amount);
+	T::Currency::make_free_balance_be(account, balance.saturating_add(balance));
+}
+
+/// Insert pending transfer and lock its amount at the bridge account. Returns transfer sender.
+fn insert_pending_transfer<T: Config>() -> T::AccountId {
+	let sender: T::AccountId = account("sender", 0, 0);
+	let amount = T::MinimalTransfer::get();
+	fund_account::<T>(&TokenTransfer::<T>::bridge_account(), amount);
+	PendingTransfers::<T>::insert(
+		TRANSFER_NONCE,
+		PendingTransfer { sender: sender.clone(), amount },
+	);
+	sender
+}
@@ -0,0 +1,921 @@
+// Copyright 2019-2022 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime module that is used to transfer native tokens between bridged chains.
+//!
+//! The same module is deployed at both bridged chains. When the transfer is initiated, the
+//! transferred amount is locked at the bridge account of the source chain and the typed
+//! transfer message is sent over the dedicated messages lane. When the message is dispatched at
+//! the target chain, tokens are unlocked from the bridge account of the target chain. If there
+//! are not enough locked tokens, tokens are minted instead.
+//!
+//! Inbound transfers are limited: every transfer must be at least `MinimalTransfer` and the
+//! total amount of inbound transfers within the `LimitPeriod` can't exceed the
+//! `InboundTransfersLimit`. If the transfer is rejected at the target chain, the locked amount
+//! is refunded to the sender after the delivery confirmation is received. Refunds are queued by
+//! the confirmation and paid from the `on_initialize` of next blocks, at most `MaxRefundsPerBlock`
+//! refunds per block.
+//!
+//! Only the bridge account must be allowed to send messages over the token transfers lane
+//! (see `bp_messages::LaneConfig::allowed_senders`). Otherwise, anyone would be able to send
+//! fake transfer messages.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(missing_docs)]
+
+use bp_messages::{
+	source_chain::{MessageInitiator, MessagesBridge, OnDeliveryConfirmed},
+	DeliveredMessages, LaneId, MessageNonce,
+};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	traits::{Currency, ExistenceRequirement, Imbalance},
+	weights::Weight,
+	PalletId, RuntimeDebug,
+};
+use scale_info::TypeInfo;
+use sp_runtime::traits::{AccountIdConversion, Saturating, Zero};
+use sp_std::{marker::PhantomData, vec::Vec};
+
+pub use message_dispatch::{RawMessagePayload, TokenTransferMessageDispatch};
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+mod message_dispatch;
+mod mock;
+
+pub mod benchmarking;
+pub mod weights;
+
+/// The target that will be used when publishing logs related to this pallet.
+pub const LOG_TARGET: &str = "runtime::bridge-token-transfer";
+
+/// Balance type of the token transfer pallet.
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+/// Transfer message that is sent by this chain.
+pub type OutboundTransferMessageOf<T> = TokenTransferMessage<
+	<T as frame_system::Config>::AccountId,
+	<T as Config>::BridgedAccountId,
+	BalanceOf<T>,
+>;
+/// Transfer message that is received by this chain.
+pub type InboundTransferMessageOf<T> = TokenTransferMessage<
+	<T as Config>::BridgedAccountId,
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T>,
+>;
+/// Pending transfer type of the token transfer pallet.
+pub type PendingTransferOf<T> =
+	PendingTransfer<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+/// Transfers volume type of the token transfer pallet.
+pub type TransfersVolumeOf<T> =
+	TransfersVolume<<T as frame_system::Config>::BlockNumber, BalanceOf<T>>;
+
+/// Token transfer message, that is sent over the bridge.
+///
+/// Both chains are expected to use the same encoding of balance type.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+pub struct TokenTransferMessage<SenderId, RecipientId, Balance> {
+	/// Account at the source chain that has initiated the transfer.
+	pub sender: SenderId,
+	/// Account at the target chain that receives tokens.
+	pub recipient: RecipientId,
+	/// Transferred amount.
+	pub amount: Balance,
+}
+
+/// Transfer that has been initiated at this chain and is waiting for delivery confirmation.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct PendingTransfer<AccountId, Balance> {
+	/// Account that has initiated the transfer. It receives refund if the transfer fails.
+	pub sender: AccountId,
+	/// Amount that has been locked at the bridge account.
+	pub amount: Balance,
+}
+
+/// Total amount of inbound transfers within the limit period.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct TransfersVolume<BlockNumber, Balance> {
+	/// First block of the limit period.
+	pub period_start: BlockNumber,
+	/// Total amount of transfers, received within the period.
+	pub volume: Balance,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// Currency that is transferred over the bridge.
+		type Currency: Currency<Self::AccountId>;
+		/// Identifier of the pallet. It is used to derive the bridge account.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+		/// Account identifier at the bridged chain.
+		type BridgedAccountId: Parameter;
+		/// Messages bridge that is used to send transfer messages.
+		type MessagesBridge: MessagesBridge<Self::Origin, Self::AccountId, BalanceOf<Self>, Vec<u8>>;
+		/// Lane that is used to send and receive transfer messages.
+		#[pallet::constant]
+		type Lane: Get<LaneId>;
+		/// Minimal amount that may be transferred.
+		#[pallet::constant]
+		type MinimalTransfer: Get<BalanceOf<Self>>;
+		/// Maximal total amount of inbound transfers within the `LimitPeriod`.
+		#[pallet::constant]
+		type InboundTransfersLimit: Get<BalanceOf<Self>>;
+		/// Duration (in blocks) of the inbound transfers limit period. Usually it is a day. If it
+		/// is zero, inbound transfers are not limited.
+		#[pallet::constant]
+		type LimitPeriod: Get<Self::BlockNumber>;
+		/// Maximal number of rejected transfers that are refunded in a single block.
+		#[pallet::constant]
+		type MaxRefundsPerBlock: Get<u32>;
+		/// Pallet call weights.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+			Self::refund_rejected_transfers()
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Transfer tokens to the account at the bridged chain.
+		///
+		/// The `amount` is locked at the bridge account until the transfer is confirmed. The
+		/// `delivery_and_dispatch_fee` is paid by the sender to relayers.
+		#[pallet::weight(T::WeightInfo::transfer())]
+		pub fn transfer(
+			origin: OriginFor<T>,
+			recipient: T::BridgedAccountId,
+			amount: BalanceOf<T>,
+			delivery_and_dispatch_fee: BalanceOf<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(amount >= T::MinimalTransfer::get(), Error::<T>::TransferAmountIsTooLow);
+
+			// the fee is paid by the bridge account (which is the message sender), so we move it
+			// there along with the transferred amount
+			let bridge_account = Self::bridge_account();
+			T::Currency::transfer(
+				&sender,
+				&bridge_account,
+				amount.saturating_add(delivery_and_dispatch_fee),
+				ExistenceRequirement::KeepAlive,
+			)
+			.map_err(|e| {
+				log::trace!(
+					target: LOG_TARGET,
+					"Failed to lock {:?} (fee: {:?}) on {:?} account: {:?}",
+					amount,
+					delivery_and_dispatch_fee,
+					sender,
+					e,
+				);
+				Error::<T>::FailedToLockTokens
+			})?;
+
+			let message = OutboundTransferMessageOf::<T> {
+				sender: sender.clone(),
+				recipient: recipient.clone(),
+				amount,
+			};
+			let artifacts = T::MessagesBridge::send_message(
+				frame_system::RawOrigin::Signed(bridge_account).into(),
+				T::Lane::get(),
+				message.encode(),
+				delivery_and_dispatch_fee,
+			)
+			.map_err(|e| {
+				log::trace!(
+					target: LOG_TARGET,
+					"Failed to send transfer message {:?}: {:?}",
+					message,
+					e,
+				);
+				Error::<T>::FailedToSendMessage
+			})?;
+
+			PendingTransfers::<T>::insert(
+				artifacts.nonce,
+				PendingTransfer { sender: sender.clone(), amount },
+			);
+
+			log::trace!(
+				target: LOG_TARGET,
+				"Initiated transfer {}: {:?}",
+				artifacts.nonce,
+				message,
+			);
+			Self::deposit_event(Event::<T>::TransferInitiated {
+				nonce: artifacts.nonce,
+				sender,
+				recipient,
+				amount,
+			});
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Returns the bridge account, that holds locked tokens and sends transfer messages.
+		pub fn bridge_account() -> T::AccountId {
+			T::PalletId::get().into_account_truncating()
+		}
+
+		/// Receive tokens, transferred from the bridged chain.
+		///
+		/// Tokens are unlocked from the bridge account if it has enough locked tokens. Otherwise,
+		/// tokens are minted.
+		pub fn receive_transfer(
+			nonce: MessageNonce,
+			message: InboundTransferMessageOf<T>,
+		) -> DispatchResult {
+			let TokenTransferMessage { sender, recipient, amount } = message;
+			ensure!(amount >= T::MinimalTransfer::get(), Error::<T>::TransferAmountIsTooLow);
+			let volume = Self::inbound_transfers_volume_after(amount)?;
+
+			let bridge_account = Self::bridge_account();
+			let unlockable_amount = T::Currency::free_balance(&bridge_account)
+				.saturating_sub(T::Currency::minimum_balance());
+			if unlockable_amount >= amount {
+				T::Currency::transfer(
+					&bridge_account,
+					&recipient,
+					amount,
+					ExistenceRequirement::KeepAlive,
+				)
+				.map_err(|e| {
+					log::trace!(
+						target: LOG_TARGET,
+						"Failed to unlock {:?} to {:?} account: {:?}",
+						amount,
+						recipient,
+						e,
+					);
+					Error::<T>::FailedToUnlockTokens
+				})?;
+
+				Self::deposit_event(Event::<T>::TokensUnlocked {
+					nonce,
+					sender,
+					recipient,
+					amount,
+				});
+			} else {
+				let minted = T::Currency::deposit_creating(&recipient, amount);
+				ensure!(!minted.peek().is_zero(), Error::<T>::FailedToMintTokens);
+
+				Self::deposit_event(Event::<T>::TokensMinted { nonce, sender, recipient, amount });
+			}
+
+			if let Some(volume) = volume {
+				InboundTransfersVolume::<T>::put(volume);
+			}
+
+			Ok(())
+		}
+
+		/// Dispatch inbound transfer message. Returns true if tokens have been received.
+		pub(crate) fn dispatch_transfer(
+			nonce: MessageNonce,
+			message: InboundTransferMessageOf<T>,
+		) -> bool {
+			match Self::receive_transfer(nonce, message.clone()) {
+				Ok(()) => {
+					log::trace!(target: LOG_TARGET, "Received transfer {}: {:?}", nonce, message);
+					true
+				},
+				Err(error) => {
+					log::trace!(
+						target: LOG_TARGET,
+						"Rejected transfer {}: {:?}. Error: {:?}",
+						nonce,
+						message,
+						error,
+					);
+					Self::deposit_event(Event::<T>::TransferRejected {
+						nonce,
+						sender: message.sender,
+						recipient: message.recipient,
+						amount: message.amount,
+						error,
+					});
+					false
+				},
+			}
+		}
+
+		/// Refund at most `MaxRefundsPerBlock` rejected transfers. Returns consumed weight.
+		pub(crate) fn refund_rejected_transfers() -> Weight {
+			let mut weight = T::DbWeight::get().reads(1);
+			let max_refunds = T::MaxRefundsPerBlock::get() as usize;
+			for (nonce, ()) in RejectedTransfers::<T>::drain().take(max_refunds) {
+				weight = weight.saturating_add(T::WeightInfo::refund_transfer());
+				let transfer = match PendingTransfers::<T>::take(nonce) {
+					Some(transfer) => transfer,
+					None => continue,
+				};
+
+				let refund_result = T::Currency::transfer(
+					&Self::bridge_account(),
+					&transfer.sender,
+					transfer.amount,
+					ExistenceRequirement::AllowDeath,
+				);
+				match refund_result {
+					Ok(()) => {
+						log::trace!(
+							target: LOG_TARGET,
+							"Refunded {:?} of rejected transfer {} to {:?}",
+							transfer.amount,
+							nonce,
+							transfer.sender,
+						);
+						Self::deposit_event(Event::<T>::TransferRefunded {
+							nonce,
+							sender: transfer.sender,
+							amount: transfer.amount,
+						});
+					},
+					Err(e) => {
+						log::error!(
+							target: LOG_TARGET,
+							"Failed to refund {:?} of rejected transfer {} to {:?}: {:?}",
+							transfer.amount,
+							nonce,
+							transfer.sender,
+							e,
+						);
+					},
+				}
+			}
+
+			weight
+		}
+
+		/// Returns volume of inbound transfers after receiving given amount or error if the
+		/// `InboundTransfersLimit` is exceeded.
+		///
+		/// Returns `None` if inbound transfers are not limited.
+		fn inbound_transfers_volume_after(
+			amount: BalanceOf<T>,
+		) -> Result<Option<TransfersVolumeOf<T>>, Error<T>> {
+			let period = T::LimitPeriod::get();
+			if period.is_zero() {
+				return Ok(None)
+			}
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let period_start = now - now % period;
+			let volume = InboundTransfersVolume::<T>::get()
+				.filter(|volume| volume.period_start == period_start)
+				.map(|volume| volume.volume)
+				.unwrap_or_else(Zero::zero)
+				.saturating_add(amount);
+			ensure!(volume <= T::InboundTransfersLimit::get(), Error::<T>::TransferLimitExceeded);
+
+			Ok(Some(TransfersVolume { period_start, volume }))
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Transfer to the bridged chain has been initiated.
+		TransferInitiated {
+			/// Nonce of the transfer message at the transfers lane.
+			nonce: MessageNonce,
+			/// Account that has initiated the transfer.
+			sender: T::AccountId,
+			/// Account at the bridged chain that receives tokens.
+			recipient: T::BridgedAccountId,
+			/// Amount that has been locked at the bridge account.
+			amount: BalanceOf<T>,
+		},
+		/// Transfer to the bridged chain has been completed.
+		TransferCompleted {
+			/// Nonce of the transfer message at the transfers lane.
+			nonce: MessageNonce,
+			/// Account that has initiated the transfer.
+			sender: T::AccountId,
+			/// Transferred amount.
+			amount: BalanceOf<T>,
+		},
+		/// Transfer to the bridged chain has been rejected by the bridged chain and the locked
+		/// amount has been refunded.
+		TransferRefunded {
+			/// Nonce of the transfer message at the transfers lane.
+			nonce: MessageNonce,
+			/// Account that has initiated the transfer and received refund.
+			sender: T::AccountId,
+			/// Refunded amount.
+			amount: BalanceOf<T>,
+		},
+		/// Transfer from the bridged chain has been received and tokens have been unlocked.
+		TokensUnlocked {
+			/// Nonce of the transfer message at the transfers lane.
+			nonce: MessageNonce,
+			/// Account at the bridged chain that has initiated the transfer.
+			sender: T::BridgedAccountId,
+			/// Account that has received tokens.
+			recipient: T::AccountId,
+			/// Unlocked amount.
+			amount: BalanceOf<T>,
+		},
+		/// Transfer from the bridged chain has been received and tokens have been minted.
+		TokensMinted {
+			/// Nonce of the transfer message at the transfers lane.
+			nonce: MessageNonce,
+			/// Account at the bridged chain that has initiated the transfer.
+			sender: T::BridgedAccountId,
+			/// Account that has received tokens.
+			recipient: T::AccountId,
+			/// Minted amount.
+			amount: BalanceOf<T>,
+		},
+		/// Transfer from the bridged chain has been rejected.
+		TransferRejected {
+			/// Nonce of the transfer message at the transfers lane.
+			nonce: MessageNonce,
+			/// Account at the bridged chain that has initiated the transfer.
+			sender: T::BridgedAccountId,
+			/// Account that should have received tokens.
+			recipient: T::AccountId,
+			/// Rejected amount.
+			amount: BalanceOf<T>,
+			/// Rejection reason.
+			error: DispatchError,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Transfer amount is less than the `MinimalTransfer`.
+		TransferAmountIsTooLow,
+		/// Failed to move transferred amount and fee to the bridge account.
+		FailedToLockTokens,
+		/// Failed to send transfer message over the bridge.
+		FailedToSendMessage,
+		/// Inbound transfers limit is exceeded.
+		TransferLimitExceeded,
+		/// Failed to unlock tokens from the bridge account.
+		FailedToUnlockTokens,
+		/// Failed to mint tokens.
+		FailedToMintTokens,
+	}
+
+	/// Map of the transfer message nonce => transfer, that is waiting for delivery confirmation.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_transfer)]
+	pub type PendingTransfers<T: Config> =
+		StorageMap<_, Twox64Concat, MessageNonce, PendingTransferOf<T>, OptionQuery>;
+
+	/// Set of nonces of transfers that have been rejected by the bridged chain and are waiting
+	/// for refund.
+	#[pallet::storage]
+	pub type RejectedTransfers<T: Config> =
+		StorageMap<_, Twox64Concat, MessageNonce, (), OptionQuery>;
+
+	/// Volume of inbound transfers within the current limit period.
+	#[pallet::storage]
+	#[pallet::getter(fn inbound_transfers_volume)]
+	pub type InboundTransfersVolume<T: Config> = StorageValue<_, TransfersVolumeOf<T>, OptionQuery>;
+}
+
+impl<T: Config> OnDeliveryConfirmed for Pallet<T> {
+	fn on_messages_delivered(lane: &LaneId, messages: &DeliveredMessages) -> Weight {
+		if *lane != T::Lane::get() {
+			return 0
+		}
+
+		// every rejected transfer is refunded later, because the refund is too heavy to fit
+		// into the per-message callback weight, reserved by the messages pallet
+		let mut weight: Weight = 0;
+		for nonce in messages.begin..=messages.end {
+			if messages.message_dispatch_result(nonce) {
+				let transfer = match PendingTransfers::<T>::take(nonce) {
+					Some(transfer) => transfer,
+					None => {
+						weight = weight.saturating_add(T::DbWeight::get().reads(1));
+						continue
+					},
+				};
+
+				weight = weight.saturating_add(T::WeightInfo::complete_transfer());
+				Self::deposit_event(Event::<T>::TransferCompleted {
+					nonce,
+					sender: transfer.sender,
+					amount: transfer.amount,
+				});
+				continue
+			}
+
+			if !PendingTransfers::<T>::contains_key(nonce) {
+				weight = weight.saturating_add(T::DbWeight::get().reads(1));
+				continue
+			}
+
+			weight = weight.saturating_add(T::WeightInfo::reject_transfer());
+			RejectedTransfers::<T>::insert(nonce, ());
+		}
+
+		weight
+	}
+}
+
+/// Message initiator of the transfer message, sent over the token transfers lane.
+///
+/// All transfer messages are sent by the bridge account, so the initiator is the account that
+/// has initiated the transfer. The payload of messages of the token transfers lane may be
+/// trusted, because only the bridge account is allowed to send messages over this lane.
+#[derive(RuntimeDebug)]
+pub struct TransferMessageInitiator<T>(PhantomData<T>);
+
+impl<T: Config, SenderOrigin> MessageInitiator<SenderOrigin, Vec<u8>, T::AccountId>
+	for TransferMessageInitiator<T>
+{
+	fn message_initiator(
+		_submitter: &SenderOrigin,
+		lane: &LaneId,
+		payload: &Vec<u8>,
+	) -> Option<T::AccountId> {
+		if *lane != T::Lane::get() {
+			return None
+		}
+
+		OutboundTransferMessageOf::<T>::decode(&mut &payload[..])
+			.ok()
+			.map(|message| message.sender)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use mock::{Event as TestEvent, *};
+
+	use frame_support::{assert_noop, assert_ok, traits::Hooks};
+	use frame_system::{EventRecord, Pallet as System, Phase};
+
+	fn get_ready_for_events() {
+		System::<TestRuntime>::set_block_number(1);
+		System::<TestRuntime>::reset_events();
+	}
+
+	fn last_event() -> TestEvent {
+		System::<TestRuntime>::events().pop().map(|record| record.event).unwrap()
+	}
+
+	fn inbound_message(amount: Balance) -> InboundTransferMessageOf<TestRuntime> {
+		TokenTransferMessage { sender: BRIDGED_SENDER, recipient: RECIPIENT, amount }
+	}
+
+	fn initiate_transfer(amount: Balance) -> MessageNonce {
+		assert_ok!(Pallet::<TestRuntime>::transfer(
+			Origin::signed(SENDER),
+			BRIDGED_RECIPIENT,
+			amount,
+			MESSAGE_FEE,
+		));
+		sent_messages().last().unwrap().nonce
+	}
+
+	#[test]
+	fn transfer_message_initiator_is_transfer_sender() {
+		run_test(|| {
+			initiate_transfer(100);
+
+			let message = sent_messages().pop().unwrap();
+			assert_eq!(message.sender, TokenTransfer::bridge_account());
+			assert_eq!(
+				TransferMessageInitiator::<TestRuntime>::message_initiator(
+					&(),
+					&message.lane,
+					&message.payload,
+				),
+				Some(SENDER),
+			);
+			assert_eq!(
+				TransferMessageInitiator::<TestRuntime>::message_initiator(
+					&(),
+					&OTHER_LANE_ID,
+					&message.payload,
+				),
+				None,
+			);
+		});
+	}
+
+	#[test]
+	fn transfer_locks_tokens_and_sends_message() {
+		run_test(|| {
+			get_ready_for_events();
+
+			let nonce = initiate_transfer(100);
+
+			assert_eq!(Balances::free_balance(SENDER), INITIAL_BALANCE - 100 - MESSAGE_FEE);
+			assert_eq!(Balances::free_balance(TokenTransfer::bridge_account()), 100 + MESSAGE_FEE);
+			assert_eq!(
+				sent_messages(),
+				vec![SentMessage {
+					sender: TokenTransfer::bridge_account(),
+					lane: TEST_LANE_ID,
+					nonce,
+					payload: TokenTransferMessage {
+						sender: SENDER,
+						recipient: BRIDGED_RECIPIENT,
+						amount: 100
+					}
+					.encode(),
+					fee: MESSAGE_FEE,
+				}],
+			);
+			assert_eq!(
+				TokenTransfer::pending_transfer(nonce),
+				Some(PendingTransfer { sender: SENDER, amount: 100 }),
+			);
+			assert_eq!(
+				System::<TestRuntime>::events(),
+				vec![EventRecord {
+					phase: Phase::Initialization,
+					event: TestEvent::TokenTransfer(Event::TransferInitiated {
+						nonce,
+						sender: SENDER,
+						recipient: BRIDGED_RECIPIENT,
+						amount: 100,
+					}),
+					topics: vec![],
+				}],
+			);
+		});
+	}
+
+	#[test]
+	fn transfer_fails_if_amount_is_too_low() {
+		run_test(|| {
+			assert_noop!(
+				Pallet::<TestRuntime>::transfer(
+					Origin::signed(SENDER),
+					BRIDGED_RECIPIENT,
+					MinimalTransfer::get() - 1,
+					MESSAGE_FEE,
+				),
+				Error::<TestRuntime>::TransferAmountIsTooLow,
+			);
+		});
+	}
+
+	#[test]
+	fn transfer_fails_if_sender_has_not_enough_tokens() {
+		run_test(|| {
+			assert_noop!(
+				Pallet::<TestRuntime>::transfer(
+					Origin::signed(SENDER),
+					BRIDGED_RECIPIENT,
+					INITIAL_BALANCE,
+					MESSAGE_FEE,
+				),
+				Error::<TestRuntime>::FailedToLockTokens,
+			);
+		});
+	}
+
+	#[test]
+	fn transfer_fails_if_message_is_not_sent() {
+		run_test(|| {
+			assert_noop!(
+				Pallet::<TestRuntime>::transfer(
+					Origin::signed(SENDER),
+					BRIDGED_RECIPIENT,
+					100,
+					FAILING_MESSAGE_FEE,
+				),
+				Error::<TestRuntime>::FailedToSendMessage,
+			);
+		});
+	}
+
+	#[test]
+	fn completed_transfer_is_removed_from_pending() {
+		run_test(|| {
+			let nonce = initiate_transfer(100);
+			get_ready_for_events();
+
+			TokenTransfer::on_messages_delivered(
+				&TEST_LANE_ID,
+				&DeliveredMessages::new(nonce, true),
+			);
+
+			assert_eq!(TokenTransfer::pending_transfer(nonce), None);
+			assert_eq!(Balances::free_balance(TokenTransfer::bridge_account()), 100 + MESSAGE_FEE);
+			assert_eq!(
+				last_event(),
+				TestEvent::TokenTransfer(Event::TransferCompleted {
+					nonce,
+					sender: SENDER,
+					amount: 100,
+				}),
+			);
+		});
+	}
+
+	#[test]
+	fn rejected_transfer_is_refunded() {
+		run_test(|| {
+			let nonce = initiate_transfer(100);
+			get_ready_for_events();
+
+			TokenTransfer::on_messages_delivered(
+				&TEST_LANE_ID,
+				&DeliveredMessages::new(nonce, false),
+			);
+			assert!(RejectedTransfers::<TestRuntime>::contains_key(nonce));
+			assert_eq!(Balances::free_balance(SENDER), INITIAL_BALANCE - 100 - MESSAGE_FEE);
+
+			TokenTransfer::on_initialize(2);
+
+			assert_eq!(TokenTransfer::pending_transfer(nonce), None);
+			assert!(!RejectedTransfers::<TestRuntime>::contains_key(nonce));
+			assert_eq!(Balances::free_balance(SENDER), INITIAL_BALANCE - MESSAGE_FEE);
+			assert_eq!(
+				last_event(),
+				TestEvent::TokenTransfer(Event::TransferRefunded {
+					nonce,
+					sender: SENDER,
+					amount: 100,
+				}),
+			);
+		});
+	}
+
+	#[test]
+	fn refunds_are_limited_per_block() {
+		run_test(|| {
+			let refunds = MaxRefundsPerBlock::get() as u64 + 1;
+			for _ in 0..refunds {
+				initiate_transfer(100);
+			}
+			let mut delivered_messages = DeliveredMessages::new(1, false);
+			for _ in 1..refunds {
+				delivered_messages.note_dispatched_message(false);
+			}
+			TokenTransfer::on_messages_delivered(&TEST_LANE_ID, &delivered_messages);
+
+			TokenTransfer::on_initialize(2);
+			assert_eq!(RejectedTransfers::<TestRuntime>::iter().count(), 1);
+			assert_eq!(
+				Balances::free_balance(SENDER),
+				INITIAL_BALANCE - 100 - refunds * MESSAGE_FEE,
+			);
+
+			TokenTransfer::on_initialize(3);
+			assert_eq!(RejectedTransfers::<TestRuntime>::iter().count(), 0);
+			assert_eq!(Balances::free_balance(SENDER), INITIAL_BALANCE - refunds * MESSAGE_FEE);
+		});
+	}
+
+	#[test]
+	fn confirmations_at_other_lanes_are_ignored() {
+		run_test(|| {
+			let nonce = initiate_transfer(100);
+
+			TokenTransfer::on_messages_delivered(
+				&OTHER_LANE_ID,
+				&DeliveredMessages::new(nonce, false),
+			);
+
+			assert!(TokenTransfer::pending_transfer(nonce).is_some());
+			assert_eq!(Balances::free_balance(SENDER), INITIAL_BALANCE - 100 - MESSAGE_FEE);
+		});
+	}
+
+	#[test]
+	fn tokens_are_minted_if_there_are_not_enough_locked_tokens() {
+		run_test(|| {
+			get_ready_for_events();
+			let total_issuance = Balances::total_issuance();
+
+			assert_ok!(TokenTransfer::receive_transfer(1, inbound_message(100)));
+
+			assert_eq!(Balances::free_balance(RECIPIENT), 100);
+			assert_eq!(Balances::total_issuance(), total_issuance + 100);
+			assert_eq!(
+				last_event(),
+				TestEvent::TokenTransfer(Event::TokensMinted {
+					nonce: 1,
+					sender: BRIDGED_SENDER,
+					recipient: RECIPIENT,
+					amount: 100,
+				}),
+			);
+		});
+	}
+
+	#[test]
+	fn locked_tokens_are_unlocked() {
+		run_test(|| {
+			initiate_transfer(200);
+			get_ready_for_events();
+			let total_issuance = Balances::total_issuance();
+
+			assert_ok!(TokenTransfer::receive_transfer(1, inbound_message(100)));
+
+			assert_eq!(Balances::free_balance(RECIPIENT), 100);
+			assert_eq!(Balances::free_balance(TokenTransfer::bridge_account()), 100 + MESSAGE_FEE);
+			assert_eq!(Balances::total_issuance(), total_issuance);
+			assert_eq!(
+				last_event(),
+				TestEvent::TokenTransfer(Event::TokensUnlocked {
+					nonce: 1,
+					sender: BRIDGED_SENDER,
+					recipient: RECIPIENT,
+					amount: 100,
+				}),
+			);
+		});
+	}
+
+	#[test]
+	fn too_small_inbound_transfer_is_rejected() {
+		run_test(|| {
+			assert_noop!(
+				TokenTransfer::receive_transfer(1, inbound_message(MinimalTransfer::get() - 1)),
+				Error::<TestRuntime>::TransferAmountIsTooLow,
+			);
+		});
+	}
+
+	#[test]
+	fn inbound_transfers_are_limited_within_period() {
+		run_test(|| {
+			System::<TestRuntime>::set_block_number(LimitPeriod::get() + 1);
+			assert_ok!(TokenTransfer::receive_transfer(1, inbound_message(600)));
+			assert_noop!(
+				TokenTransfer::receive_transfer(2, inbound_message(500)),
+				Error::<TestRuntime>::TransferLimitExceeded,
+			);
+			assert_ok!(TokenTransfer::receive_transfer(2, inbound_message(400)));
+			assert_eq!(
+				TokenTransfer::inbound_transfers_volume(),
+				Some(TransfersVolume { period_start: LimitPeriod::get(), volume: 1_000 }),
+			);
+
+			// limits are reset when the next period starts
+			System::<TestRuntime>::set_block_number(2 * LimitPeriod::get());
+			assert_ok!(TokenTransfer::receive_transfer(3, inbound_message(500)));
+			assert_eq!(
+				TokenTransfer::inbound_transfers_volume(),
+				Some(TransfersVolume { period_start: 2 * LimitPeriod::get(), volume: 500 }),
+			);
+		});
+	}
+
+	#[test]
+	fn rejected_inbound_transfer_is_reported() {
+		run_test(|| {
+			get_ready_for_events();
+
+			assert!(!TokenTransfer::dispatch_transfer(1, inbound_message(2_000)));
+			assert_eq!(
+				last_event(),
+				TestEvent::TokenTransfer(Event::TransferRejected {
+					nonce: 1,
+					sender: BRIDGED_SENDER,
+					recipient: RECIPIENT,
+					amount: 2_000,
+					error: Error::<TestRuntime>::TransferLimitExceeded.into(),
+				}),
+			);
+		});
+	}
+}
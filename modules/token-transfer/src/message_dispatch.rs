@@ -0,0 +1,329 @@
+// Copyright 2019-2022 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Dispatcher of inbound messages, that handles token transfer messages.
+
+use crate::{Config, InboundTransferMessageOf, Pallet, WeightInfo, LOG_TARGET};
+
+use bp_messages::target_chain::{DispatchMessage, DispatchMessageData, MessageDispatch};
+use bp_runtime::messages::MessageDispatchResult;
+use codec::{Decode, Input};
+use frame_support::{traits::Get, weights::Weight, RuntimeDebug};
+use sp_std::{marker::PhantomData, vec, vec::Vec};
+
+/// Message payload that is not decoded until the message is dispatched.
+///
+/// Token transfer messages and other messages are sharing the same messages pallet, so the
+/// payload can only be decoded when we know the message lane. Messages of other lanes are
+/// decoded (and decompressed, if required) once, by the `dispatch_weight` call. The decoded
+/// message is then reused by the `dispatch_retry_account` and `dispatch` calls.
+#[derive(RuntimeDebug)]
+pub struct RawMessagePayload<InnerPayload, Fee> {
+	/// Encoded message payload.
+	pub encoded: Vec<u8>,
+	/// Message of the `Inner` dispatcher, decoded from the `encoded` payload.
+	inner: Option<DispatchMessage<InnerPayload, Fee>>,
+}
+
+impl<InnerPayload, Fee> RawMessagePayload<InnerPayload, Fee> {
+	/// Create raw payload from encoded bytes.
+	pub fn new(encoded: Vec<u8>) -> Self {
+		RawMessagePayload { encoded, inner: None }
+	}
+}
+
+impl<InnerPayload, Fee> Decode for RawMessagePayload<InnerPayload, Fee> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, codec::Error> {
+		let payload_len = input
+			.remaining_len()?
+			.ok_or_else(|| codec::Error::from("Unknown raw message payload length"))?;
+		let mut payload = vec![0u8; payload_len];
+		input.read(&mut payload)?;
+		Ok(RawMessagePayload::new(payload))
+	}
+}
+
+/// Inbound messages dispatcher, that dispatches token transfer messages, received over the
+/// token transfers lane, and passes all other messages to the `Inner` dispatcher.
+#[derive(RuntimeDebug, Clone, Copy)]
+pub struct TokenTransferMessageDispatch<T, Inner>(PhantomData<(T, Inner)>);
+
+impl<T, Inner, Fee> MessageDispatch<T::AccountId, Fee> for TokenTransferMessageDispatch<T, Inner>
+where
+	T: Config,
+	Inner: MessageDispatch<T::AccountId, Fee>,
+	Fee: Clone,
+{
+	type DispatchPayload = RawMessagePayload<Inner::DispatchPayload, Fee>;
+
+	fn dispatch_weight(message: &mut DispatchMessage<Self::DispatchPayload, Fee>) -> Weight {
+		if message.key.lane_id == T::Lane::get() {
+			// cancelled transfers are rejected without touching the storage
+			if message.data.is_cancelled {
+				return 0
+			}
+			return T::WeightInfo::receive_transfer()
+		}
+
+		// the inner dispatcher may cache some data in the payload (e.g. XCM dispatcher caches
+		// message weight there), so the decoded message is kept until it is dispatched
+		let mut inner_message = Self::take_inner_message(message);
+		let weight = Inner::dispatch_weight(&mut inner_message);
+		if let Ok(ref mut payload) = message.data.payload {
+			payload.inner = Some(inner_message);
+		}
+		weight
+	}
+
+	fn dispatch_retry_account(
+		message: &DispatchMessage<Self::DispatchPayload, Fee>,
+	) -> Option<T::AccountId> {
+		// rejected transfers are refunded at the bridged chain, so there's nothing to retry
+		if message.key.lane_id == T::Lane::get() {
+			return None
+		}
+
+		match message.data.payload {
+			Ok(RawMessagePayload { inner: Some(ref inner_message), .. }) =>
+				Inner::dispatch_retry_account(inner_message),
+			_ => Inner::dispatch_retry_account(&Self::decode_inner_message(message)),
+		}
+	}
+
+	fn dispatch(
+		relayer_account: &T::AccountId,
+		mut message: DispatchMessage<Self::DispatchPayload, Fee>,
+	) -> MessageDispatchResult {
+		if message.key.lane_id != T::Lane::get() {
+			return Inner::dispatch(relayer_account, Self::take_inner_message(&mut message))
+		}
+
+		// tokens of cancelled transfers are refunded at the bridged chain, so they must not be
+		// received here
+		if message.data.is_cancelled {
+			log::trace!(target: LOG_TARGET, "Rejected cancelled transfer message {:?}", message.key);
+			return MessageDispatchResult {
+				dispatch_result: false,
+				unspent_weight: 0,
+				dispatch_fee_paid_during_dispatch: false,
+				response: None,
+			}
+		}
+
+		let transfer_message = message.data.payload.and_then(|payload| {
+			let encoded_message = Vec::<u8>::decode(&mut &payload.encoded[..])?;
+			InboundTransferMessageOf::<T>::decode(&mut &encoded_message[..])
+		});
+		let dispatch_result = match transfer_message {
+			Ok(transfer_message) =>
+				Pallet::<T>::dispatch_transfer(message.key.nonce, transfer_message),
+			Err(e) => {
+				log::trace!(
+					target: LOG_TARGET,
+					"Failed to decode transfer message {:?}: {:?}",
+					message.key,
+					e,
+				);
+				false
+			},
+		};
+
+		MessageDispatchResult {
+			dispatch_result,
+			unspent_weight: 0,
+			dispatch_fee_paid_during_dispatch: false,
+			response: None,
+		}
+	}
+}
+
+impl<T: Config, Inner> TokenTransferMessageDispatch<T, Inner> {
+	/// Take the message of the `Inner` dispatcher, decoded by the `dispatch_weight` call. If it
+	/// hasn't been called, the message is decoded from the raw message payload.
+	fn take_inner_message<Fee>(
+		message: &mut DispatchMessage<RawMessagePayload<Inner::DispatchPayload, Fee>, Fee>,
+	) -> DispatchMessage<Inner::DispatchPayload, Fee>
+	where
+		Inner: MessageDispatch<T::AccountId, Fee>,
+		Fee: Clone,
+	{
+		let inner_message = match message.data.payload {
+			Ok(ref mut payload) => payload.inner.take(),
+			Err(_) => None,
+		};
+		inner_message.unwrap_or_else(|| Self::decode_inner_message(message))
+	}
+
+	/// Decode raw message payload into the message of the `Inner` dispatcher.
+	fn decode_inner_message<Fee>(
+		message: &DispatchMessage<RawMessagePayload<Inner::DispatchPayload, Fee>, Fee>,
+	) -> DispatchMessage<Inner::DispatchPayload, Fee>
+	where
+		Inner: MessageDispatch<T::AccountId, Fee>,
+		Fee: Clone,
+	{
+		DispatchMessage {
+			key: message.key.clone(),
+			data: DispatchMessageData {
+				payload: match message.data.payload {
+					Ok(ref payload) => Decode::decode(&mut &payload.encoded[..]),
+					Err(ref e) => Err(e.clone()),
+				},
+				fee: message.data.fee.clone(),
+				is_cancelled: message.data.is_cancelled,
+			},
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{mock::*, TokenTransferMessage};
+
+	use bp_messages::{target_chain::ForbidInboundMessages, LaneId, MessageKey};
+	use codec::Encode;
+
+	type Dispatch = TokenTransferMessageDispatch<TestRuntime, ForbidInboundMessages>;
+	type CountingDispatch = TokenTransferMessageDispatch<TestRuntime, DecodeCountingDispatch>;
+
+	std::thread_local! {
+		static INNER_PAYLOAD_DECODES: std::cell::Cell<u32> = std::cell::Cell::new(0);
+	}
+
+	/// Payload of the `DecodeCountingDispatch`, that counts its decodes.
+	#[derive(Debug)]
+	struct DecodeCountingPayload;
+
+	impl Decode for DecodeCountingPayload {
+		fn decode<I: Input>(_input: &mut I) -> Result<Self, codec::Error> {
+			INNER_PAYLOAD_DECODES.with(|decodes| decodes.set(decodes.get() + 1));
+			Ok(DecodeCountingPayload)
+		}
+	}
+
+	/// Inner dispatcher that accepts all messages.
+	struct DecodeCountingDispatch;
+
+	impl MessageDispatch<AccountId, Balance> for DecodeCountingDispatch {
+		type DispatchPayload = DecodeCountingPayload;
+
+		fn dispatch_weight(
+			_message: &mut DispatchMessage<Self::DispatchPayload, Balance>,
+		) -> Weight {
+			1
+		}
+
+		fn dispatch_retry_account(
+			_message: &DispatchMessage<Self::DispatchPayload, Balance>,
+		) -> Option<AccountId> {
+			Some(SENDER)
+		}
+
+		fn dispatch(
+			_relayer_account: &AccountId,
+			message: DispatchMessage<Self::DispatchPayload, Balance>,
+		) -> MessageDispatchResult {
+			MessageDispatchResult {
+				dispatch_result: message.data.payload.is_ok(),
+				unspent_weight: 0,
+				dispatch_fee_paid_during_dispatch: false,
+				response: None,
+			}
+		}
+	}
+
+	fn message<InnerPayload>(
+		lane_id: LaneId,
+		payload: Vec<u8>,
+	) -> DispatchMessage<RawMessagePayload<InnerPayload, Balance>, Balance> {
+		DispatchMessage {
+			key: MessageKey { lane_id, nonce: 1 },
+			data: DispatchMessageData {
+				payload: RawMessagePayload::decode(&mut &payload.encode()[..]),
+				fee: 0,
+				is_cancelled: false,
+			},
+		}
+	}
+
+	fn transfer_message(amount: Balance) -> Vec<u8> {
+		TokenTransferMessage { sender: BRIDGED_SENDER, recipient: RECIPIENT, amount }.encode()
+	}
+
+	#[test]
+	fn transfer_message_is_dispatched() {
+		run_test(|| {
+			let mut message = message(TEST_LANE_ID, transfer_message(100));
+			assert_eq!(
+				Dispatch::dispatch_weight(&mut message),
+				<() as WeightInfo>::receive_transfer(),
+			);
+			assert_eq!(Dispatch::dispatch_retry_account(&message), None);
+			assert!(Dispatch::dispatch(&SENDER, message).dispatch_result);
+			assert_eq!(Balances::free_balance(RECIPIENT), 100);
+		});
+	}
+
+	#[test]
+	fn rejected_transfer_message_is_reported() {
+		run_test(|| {
+			let message = message(TEST_LANE_ID, transfer_message(MinimalTransfer::get() - 1));
+			assert!(!Dispatch::dispatch(&SENDER, message).dispatch_result);
+			assert_eq!(Balances::free_balance(RECIPIENT), 0);
+		});
+	}
+
+	#[test]
+	fn cancelled_transfer_message_is_rejected() {
+		run_test(|| {
+			let mut message = message(TEST_LANE_ID, transfer_message(100));
+			message.data.is_cancelled = true;
+			assert_eq!(Dispatch::dispatch_weight(&mut message), 0);
+			assert!(!Dispatch::dispatch(&SENDER, message).dispatch_result);
+			assert_eq!(Balances::free_balance(RECIPIENT), 0);
+		});
+	}
+
+	#[test]
+	fn undecodable_transfer_message_is_rejected() {
+		run_test(|| {
+			let message = message(TEST_LANE_ID, vec![42]);
+			assert!(!Dispatch::dispatch(&SENDER, message).dispatch_result);
+		});
+	}
+
+	#[test]
+	fn messages_from_other_lanes_are_decoded_once() {
+		run_test(|| {
+			let mut message = message(OTHER_LANE_ID, vec![42]);
+			assert_eq!(CountingDispatch::dispatch_weight(&mut message), 1);
+			assert_eq!(CountingDispatch::dispatch_retry_account(&message), Some(SENDER));
+			assert!(CountingDispatch::dispatch(&SENDER, message).dispatch_result);
+			assert_eq!(INNER_PAYLOAD_DECODES.with(|decodes| decodes.get()), 1);
+		});
+	}
+
+	#[test]
+	fn messages_from_other_lanes_are_passed_to_inner_dispatcher() {
+		run_test(|| {
+			let mut message = message::<()>(OTHER_LANE_ID, transfer_message(100));
+			assert_eq!(Dispatch::dispatch_weight(&mut message), Weight::MAX);
+			assert!(!Dispatch::dispatch(&SENDER, message).dispatch_result);
+			assert_eq!(Balances::free_balance(RECIPIENT), 0);
+		});
+	}
+}
@@ -0,0 +1,201 @@
+// Copyright 2019-2022 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use crate as pallet_bridge_token_transfer;
+
+use bp_messages::{
+	source_chain::{MessagesBridge, SendMessageArtifacts},
+	LaneId,
+};
+use codec::{Decode, Encode};
+use frame_support::{parameter_types, PalletId};
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header as SubstrateHeader,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+
+pub type AccountId = u64;
+pub type BridgedAccountId = u32;
+pub type Balance = u64;
+
+type Block = frame_system::mocking::MockBlock<TestRuntime>;
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<TestRuntime>;
+
+frame_support::construct_runtime! {
+	pub enum TestRuntime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Config<T>, Event<T>},
+		TokenTransfer: pallet_bridge_token_transfer::{Pallet, Call, Storage, Event<T>},
+	}
+}
+
+impl frame_system::Config for TestRuntime {
+	type Origin = Origin;
+	type Index = u64;
+	type Call = Call;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = SubstrateHeader;
+	type Event = Event;
+	type BlockHashCount = frame_support::traits::ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type BaseCallFilter = frame_support::traits::Everything;
+	type SystemWeightInfo = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+impl pallet_balances::Config for TestRuntime {
+	type MaxLocks = ();
+	type Balance = Balance;
+	type DustRemoval = ();
+	type Event = Event;
+	type ExistentialDeposit = frame_support::traits::ConstU64<1>;
+	type AccountStore = frame_system::Pallet<TestRuntime>;
+	type WeightInfo = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = ();
+}
+
+parameter_types! {
+	pub const TokenTransferPalletId: PalletId = PalletId(*b"py/brtkt");
+	pub const TestLane: LaneId = TEST_LANE_ID;
+	pub const MinimalTransfer: Balance = 10;
+	pub const InboundTransfersLimit: Balance = 1_000;
+	pub const LimitPeriod: u64 = 10;
+	pub const MaxRefundsPerBlock: u32 = 2;
+	pub storage SentMessages: Vec<SentMessage> = Vec::new();
+}
+
+impl pallet_bridge_token_transfer::Config for TestRuntime {
+	type Event = Event;
+	type Currency = Balances;
+	type PalletId = TokenTransferPalletId;
+	type BridgedAccountId = BridgedAccountId;
+	type MessagesBridge = TestMessagesBridge;
+	type Lane = TestLane;
+	type MinimalTransfer = MinimalTransfer;
+	type InboundTransfersLimit = InboundTransfersLimit;
+	type LimitPeriod = LimitPeriod;
+	type MaxRefundsPerBlock = MaxRefundsPerBlock;
+	type WeightInfo = ();
+}
+
+/// Lane that is used by the token transfer pallet.
+pub const TEST_LANE_ID: LaneId = [0, 0, 0, 2];
+
+/// Lane that is not used by the token transfer pallet.
+pub const OTHER_LANE_ID: LaneId = [0, 0, 0, 0];
+
+/// Account that initiates transfers at this chain.
+pub const SENDER: AccountId = 1;
+
+/// Account that receives transfers at this chain.
+pub const RECIPIENT: AccountId = 2;
+
+/// Account that initiates transfers at the bridged chain.
+pub const BRIDGED_SENDER: BridgedAccountId = 100;
+
+/// Account that receives transfers at the bridged chain.
+pub const BRIDGED_RECIPIENT: BridgedAccountId = 200;
+
+/// Initial balance of the `SENDER` account.
+pub const INITIAL_BALANCE: Balance = 1_000_000;
+
+/// Delivery and dispatch fee of regular transfer message.
+pub const MESSAGE_FEE: Balance = 5;
+
+/// Delivery and dispatch fee that is rejected by the `TestMessagesBridge`.
+pub const FAILING_MESSAGE_FEE: Balance = 13;
+
+/// Message, sent using `TestMessagesBridge`.
+#[derive(Encode, Decode, Clone, Debug, PartialEq, Eq)]
+pub struct SentMessage {
+	/// Message sender.
+	pub sender: AccountId,
+	/// Message lane.
+	pub lane: LaneId,
+	/// Message nonce.
+	pub nonce: u64,
+	/// Message payload.
+	pub payload: Vec<u8>,
+	/// Message delivery and dispatch fee.
+	pub fee: Balance,
+}
+
+/// Messages bridge that remembers all sent messages and rejects messages with
+/// `FAILING_MESSAGE_FEE`.
+pub struct TestMessagesBridge;
+
+impl MessagesBridge<Origin, AccountId, Balance, Vec<u8>> for TestMessagesBridge {
+	type Error = &'static str;
+
+	fn send_message(
+		sender: Origin,
+		lane: LaneId,
+		payload: Vec<u8>,
+		fee: Balance,
+	) -> Result<SendMessageArtifacts, Self::Error> {
+		if fee == FAILING_MESSAGE_FEE {
+			return Err("Message is rejected")
+		}
+
+		let sender = match sender.into() {
+			Ok(frame_system::RawOrigin::Signed(sender)) => sender,
+			_ => return Err("Message is sent by unsigned origin"),
+		};
+
+		let mut sent_messages = SentMessages::get();
+		let nonce = sent_messages.len() as u64 + 1;
+		sent_messages.push(SentMessage { sender, lane, nonce, payload, fee });
+		SentMessages::set(&sent_messages);
+
+		Ok(SendMessageArtifacts { nonce, weight: 0 })
+	}
+}
+
+/// Returns all messages, sent using `TestMessagesBridge`.
+pub fn sent_messages() -> Vec<SentMessage> {
+	SentMessages::get()
+}
+
+/// Run pallet test.
+pub fn run_test<T>(test: impl FnOnce() -> T) -> T {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<TestRuntime>().unwrap();
+	pallet_balances::GenesisConfig::<TestRuntime> { balances: vec![(SENDER, INITIAL_BALANCE)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(test)
+}
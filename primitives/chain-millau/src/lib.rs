@@ -21,7 +21,8 @@
 mod millau_hash;
 
 use bp_messages::{
-	InboundMessageDetails, LaneId, MessageNonce, MessagePayload, OutboundMessageDetails,
+	InboundMessageDetails, LaneId, MessageNonce, MessagePayload, MessageStatus,
+	MessagesOperatingMode, OutboundMessageDetails,
 };
 use bp_runtime::{decl_bridge_runtime_apis, Chain};
 use frame_support::{
@@ -91,7 +92,7 @@ pub const ADDITIONAL_MESSAGE_BYTE_DELIVERY_WEIGHT: Weight = 25_000;
 /// This value is a result of `pallet_bridge_messages::Pallet::receive_messages_delivery_proof`
 /// weight formula computation for the case when single message is confirmed. The result then must
 /// be rounded up to account possible future runtime upgrades.
-pub const MAX_SINGLE_MESSAGE_DELIVERY_CONFIRMATION_TX_WEIGHT: Weight = 2_000_000_000;
+pub const MAX_SINGLE_MESSAGE_DELIVERY_CONFIRMATION_TX_WEIGHT: Weight = 4_000_000_000;
 
 /// Weight of pay-dispatch-fee operation for inbound messages at Millau chain.
 ///
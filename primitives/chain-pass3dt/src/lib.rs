@@ -21,7 +21,8 @@
 mod pass3dt_hash;
 
 use bp_messages::{
-	InboundMessageDetails, LaneId, MessageNonce, MessagePayload, OutboundMessageDetails,
+	InboundMessageDetails, LaneId, MessageNonce, MessagePayload, MessageStatus,
+	MessagesOperatingMode, OutboundMessageDetails,
 };
 use bp_runtime::{decl_bridge_runtime_apis, Chain};
 use frame_support::{
@@ -77,21 +78,24 @@ pub const MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX: MessageNonce = 128;
 /// for the case when single message of `pallet_bridge_messages::EXPECTED_DEFAULT_MESSAGE_LENGTH`
 /// bytes is delivered. The message must have dispatch weight set to zero. The result then must be
 /// rounded up to account possible future runtime upgrades.
-pub const DEFAULT_MESSAGE_DELIVERY_TX_WEIGHT: Weight = 1_500_000_000;
+///
+/// All weight constants below are derived from the `pass3dt_runtime::weights` and are checked by
+/// the Pass3dt runtime integrity tests.
+pub const DEFAULT_MESSAGE_DELIVERY_TX_WEIGHT: Weight = 1_000_000_000;
 
 /// Increase of delivery transaction weight on Pass3dt chain with every additional message byte.
 ///
 /// This value is a result of
 /// `pallet_bridge_messages::WeightInfoExt::storage_proof_size_overhead(1)` call. The result then
 /// must be rounded up to account possible future runtime upgrades.
-pub const ADDITIONAL_MESSAGE_BYTE_DELIVERY_WEIGHT: Weight = 25_000;
+pub const ADDITIONAL_MESSAGE_BYTE_DELIVERY_WEIGHT: Weight = 5_000;
 
 /// Maximal weight of single message delivery confirmation transaction on Pass3dt chain.
 ///
 /// This value is a result of `pallet_bridge_messages::Pallet::receive_messages_delivery_proof`
 /// weight formula computation for the case when single message is confirmed. The result then must
 /// be rounded up to account possible future runtime upgrades.
-pub const MAX_SINGLE_MESSAGE_DELIVERY_CONFIRMATION_TX_WEIGHT: Weight = 2_000_000_000;
+pub const MAX_SINGLE_MESSAGE_DELIVERY_CONFIRMATION_TX_WEIGHT: Weight = 4_000_000_000;
 
 /// Weight of pay-dispatch-fee operation for inbound messages at Pass3dt chain.
 ///
@@ -100,7 +104,7 @@ pub const MAX_SINGLE_MESSAGE_DELIVERY_CONFIRMATION_TX_WEIGHT: Weight = 2_000_000
 /// chain. Don't put too much reserve there, because it is used to **decrease**
 /// `DEFAULT_MESSAGE_DELIVERY_TX_WEIGHT` cost. So putting large reserve would make delivery
 /// transactions cheaper.
-pub const PAY_INBOUND_DISPATCH_FEE_WEIGHT: Weight = 700_000_000;
+pub const PAY_INBOUND_DISPATCH_FEE_WEIGHT: Weight = 150_000;
 
 /// The target length of a session (how often authorities change) on Pass3dt measured in of number of
 /// blocks.
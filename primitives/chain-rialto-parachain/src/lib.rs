@@ -19,7 +19,8 @@
 #![allow(clippy::too_many_arguments)]
 
 use bp_messages::{
-	InboundMessageDetails, LaneId, MessageNonce, MessagePayload, OutboundMessageDetails,
+	InboundMessageDetails, LaneId, MessageNonce, MessagePayload, MessageStatus,
+	MessagesOperatingMode, OutboundMessageDetails,
 };
 use bp_runtime::{decl_bridge_runtime_apis, Chain};
 use frame_support::{
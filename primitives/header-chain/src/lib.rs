@@ -25,9 +25,9 @@ use core::{clone::Clone, cmp::Eq, default::Default, fmt::Debug};
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
-use sp_finality_grandpa::{AuthorityList, ConsensusLog, SetId, GRANDPA_ENGINE_ID};
+use sp_finality_grandpa::{AuthorityId, AuthorityList, ConsensusLog, SetId, GRANDPA_ENGINE_ID};
 use sp_runtime::{generic::OpaqueDigestItemId, traits::Header as HeaderT, RuntimeDebug};
-use sp_std::boxed::Box;
+use sp_std::{boxed::Box, vec::Vec};
 
 pub mod justification;
 pub mod storage_keys;
@@ -55,6 +55,30 @@ impl AuthoritySet {
 	}
 }
 
+/// GRANDPA authority set change that has been scheduled by some finalized header, but is
+/// not yet enacted.
+#[derive(Encode, Eq, Decode, RuntimeDebug, PartialEq, Clone, TypeInfo)]
+pub struct ScheduledAuthoritySetChange<N> {
+	/// Authorities that will become active once the change is enacted.
+	pub authorities: AuthorityList,
+	/// Number of the header that will enact the change.
+	pub enact_at: N,
+}
+
+/// Evidence of GRANDPA equivocation: two different headers with the same number that are
+/// finalized by the same authority set.
+#[derive(Encode, Eq, Decode, RuntimeDebug, PartialEq, Clone, TypeInfo)]
+pub struct EquivocationEvidence<N, H> {
+	/// Number of both conflicting headers.
+	pub header_number: N,
+	/// Hash of the first conflicting header.
+	pub first_header_hash: H,
+	/// Hash of the second conflicting header.
+	pub second_header_hash: H,
+	/// Authorities that have signed precommits for both conflicting headers.
+	pub equivocators: Vec<AuthorityId>,
+}
+
 /// Data required for initializing the bridge pallet.
 ///
 /// The bridge needs to know where to start its sync from, and this provides that initial context.
@@ -20,6 +20,8 @@
 pub const PALLET_OPERATING_MODE_VALUE_NAME: &str = "PalletOperatingMode";
 /// Name of the `BestFinalized` storage value.
 pub const BEST_FINALIZED_VALUE_NAME: &str = "BestFinalized";
+/// Name of the `CurrentAuthoritySet` storage value.
+pub const CURRENT_AUTHORITY_SET_VALUE_NAME: &str = "CurrentAuthoritySet";
 
 use sp_core::storage::StorageKey;
 
@@ -45,6 +47,17 @@ pub fn best_finalized_key(pallet_prefix: &str) -> StorageKey {
 	)
 }
 
+/// Storage key of the current GRANDPA authority set value in the runtime storage.
+pub fn current_authority_set_key(pallet_prefix: &str) -> StorageKey {
+	StorageKey(
+		bp_runtime::storage_value_final_key(
+			pallet_prefix.as_bytes(),
+			CURRENT_AUTHORITY_SET_VALUE_NAME.as_bytes(),
+		)
+		.to_vec(),
+	)
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
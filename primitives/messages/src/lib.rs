@@ -23,7 +23,7 @@
 use bitvec::prelude::*;
 use bp_runtime::{messages::DispatchFeePayment, BasicOperatingMode, OperatingMode};
 use codec::{Decode, Encode, MaxEncodedLen};
-use frame_support::RuntimeDebug;
+use frame_support::{traits::ConstU32, BoundedVec, RuntimeDebug};
 use scale_info::TypeInfo;
 use sp_std::{collections::vec_deque::VecDeque, prelude::*};
 
@@ -96,6 +96,15 @@ pub struct MessageKey {
 	pub nonce: MessageNonce,
 }
 
+/// Version of the messages wire format.
+///
+/// The wire format is the encoding of `MessageData`, which is proved by the source chain and
+/// decoded by the target chain, and the meaning of `DeliveredMessages`, which is proved by the
+/// target chain and interpreted by the source chain. It matches the storage version of the
+/// messages pallet where the format has been introduced, so relayers may read it from both
+/// chains. Both chains must be upgraded in lockstep when the format is changed.
+pub const MESSAGES_WIRE_FORMAT_VERSION: u16 = 2;
+
 /// Message data as it is stored in the storage.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 pub struct MessageData<Fee> {
@@ -103,6 +112,21 @@ pub struct MessageData<Fee> {
 	pub payload: MessagePayload,
 	/// Message delivery and dispatch fee, paid by the submitter.
 	pub fee: Fee,
+	/// Number of the source chain block, after which the message is considered expired and
+	/// may be cancelled by its sender. `None` if message has no time-to-live.
+	pub expires_at: Option<u64>,
+	/// True if the message has been cancelled at the source chain.
+	///
+	/// Cancelled messages have empty payload and zero fee. They still need to be delivered to
+	/// the target chain (to keep lane nonces consecutive), but they are never dispatched there.
+	pub is_cancelled: bool,
+}
+
+impl<Fee> MessageData<Fee> {
+	/// Create data of the message without time-to-live.
+	pub fn new(payload: MessagePayload, fee: Fee) -> Self {
+		MessageData { payload, fee, expires_at: None, is_cancelled: false }
+	}
 }
 
 /// Message as it is stored in the storage.
@@ -196,6 +220,53 @@ impl<RelayerId> InboundLaneData<RelayerId> {
 	}
 }
 
+/// Status of the outbound message, as it is seen by the source chain. Returned by runtime APIs.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub enum MessageStatus<BlockNumber> {
+	/// Message has been sent, but its delivery hasn't been confirmed yet.
+	Pending {
+		/// Number of the block where the message has been sent.
+		sent_at: BlockNumber,
+	},
+	/// Message has been cancelled by its sender, because its time-to-live has expired. Its
+	/// delivery hasn't been confirmed yet.
+	Cancelled {
+		/// Number of the block where the message has been sent.
+		sent_at: BlockNumber,
+		/// Number of the block where the message has been cancelled.
+		cancelled_at: BlockNumber,
+	},
+	/// Message has been delivered to the bridged chain and its delivery has been confirmed at
+	/// this chain.
+	Delivered {
+		/// Number of the block where the message has been sent.
+		sent_at: BlockNumber,
+		/// Number of the block where the message delivery has been confirmed.
+		delivered_at: BlockNumber,
+		/// Result of the message dispatch at the bridged chain.
+		///
+		/// It is also `true` if the dispatch has failed, but may be retried at the bridged chain.
+		dispatch_result: bool,
+	},
+	/// The bridged chain knows that the message delivery has been confirmed (and relayer has
+	/// been rewarded). This is the final status of the message.
+	///
+	/// The status is kept for some time after confirmation and then it is pruned.
+	Confirmed {
+		/// Number of the block where the message has been sent.
+		sent_at: BlockNumber,
+		/// Number of the block where the message delivery has been confirmed.
+		delivered_at: BlockNumber,
+		/// Number of the block where this chain has learned that the bridged chain knows about
+		/// the message delivery confirmation.
+		confirmed_at: BlockNumber,
+		/// Result of the message dispatch at the bridged chain.
+		///
+		/// It is also `true` if the dispatch has failed, but may be retried at the bridged chain.
+		dispatch_result: bool,
+	},
+}
+
 /// Outbound message details, returned by runtime APIs.
 #[derive(Clone, Encode, Decode, RuntimeDebug, PartialEq, Eq)]
 pub struct OutboundMessageDetails<OutboundMessageFee> {
@@ -251,6 +322,9 @@ pub struct DeliveredMessages {
 	/// Dispatch result (`false`/`true`), returned by the message dispatcher for every
 	/// message in the `[begin; end]` range. See `dispatch_result` field of the
 	/// `bp_runtime::messages::MessageDispatchResult` structure for more information.
+	///
+	/// Messages which dispatch has failed, but may be retried at the target chain, are
+	/// also marked as dispatched. Cancelled messages are never dispatched.
 	pub dispatch_results: DispatchResultsBitVec,
 }
 
@@ -340,6 +414,74 @@ impl Default for OutboundLaneData {
 	}
 }
 
+/// Maximal number of accounts that are allowed to send messages over the single lane.
+pub const MAX_LANE_ALLOWED_SENDERS: u32 = 16;
+
+/// Accounts that are allowed to send messages over the lane.
+pub type LaneAllowedSenders<AccountId> = BoundedVec<AccountId, ConstU32<MAX_LANE_ALLOWED_SENDERS>>;
+
+/// Configuration of the lane, opened in the messages pallet lane registry.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct LaneConfig<AccountId> {
+	/// Maximal number of pending (not yet confirmed) messages at the outbound lane.
+	pub max_pending_messages: MessageNonce,
+	/// Maximal size of the message payload that may be sent over the lane.
+	pub max_payload_size: u32,
+	/// Accounts that are allowed to send messages over the lane.
+	///
+	/// If `None`, messages from any origin are accepted. Otherwise, only messages that are
+	/// sent by signed origin of one of given accounts are accepted. There may be at most
+	/// `MAX_LANE_ALLOWED_SENDERS` allowed senders, so the lane configuration may be read when
+	/// every message is sent.
+	pub allowed_senders: Option<LaneAllowedSenders<AccountId>>,
+}
+
+/// State of the lane in the messages pallet lane registry.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub enum LaneState {
+	/// The lane is opened and accepts new messages.
+	Opened,
+	/// The lane is closing. It doesn't accept new messages, but already queued messages are
+	/// still delivered. Once all queued messages are confirmed, the lane is removed from the
+	/// registry.
+	Closing,
+}
+
+/// The lane, registered in the messages pallet lane registry.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+pub struct RegisteredLane<AccountId> {
+	/// Lane configuration.
+	pub config: LaneConfig<AccountId>,
+	/// Current lane state.
+	pub state: LaneState,
+}
+
+/// Error that happens when messages proof or messages delivery proof is verified.
+///
+/// Only some variants prove that the submitter has provided an invalid proof. Other errors
+/// may be caused by the state of this chain (e.g. the bridged header is not yet imported or
+/// the bridge is halted) and may be resolved later.
+#[derive(Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub enum VerificationError {
+	/// The storage proof doesn't have the state root of the bridged header.
+	StorageRootMismatch,
+	/// The storage proof can't be read or misses some values that it must prove.
+	InvalidStorageProof,
+	/// Messages, lane states or dispatch responses in the proof can't be decoded or don't
+	/// match the call arguments (e.g. messages are duplicated or their count differs from
+	/// the declared count).
+	InvalidProvedData,
+	/// The proof can't be verified at the moment.
+	Other(&'static str),
+}
+
+impl From<&'static str> for VerificationError {
+	fn from(error: &'static str) -> Self {
+		VerificationError::Other(error)
+	}
+}
+
 /// Returns total number of messages in the `InboundLaneData::relayers` vector.
 ///
 /// Returns `None` if there are more messages that `MessageNonce` may fit (i.e. `MessageNonce + 1`).
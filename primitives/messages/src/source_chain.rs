@@ -16,15 +16,21 @@
 
 //! Primitives of messages module, that are used on the source chain.
 
-use crate::{DeliveredMessages, InboundLaneData, LaneId, MessageNonce, OutboundLaneData};
+use crate::{
+	DeliveredMessages, InboundLaneData, LaneId, MessageNonce, OutboundLaneData, VerificationError,
+};
 
 use crate::UnrewardedRelayer;
 use bp_runtime::Size;
-use frame_support::{weights::Weight, Parameter, RuntimeDebug};
+use frame_support::{
+	weights::{RuntimeDbWeight, Weight},
+	Parameter, RuntimeDebug,
+};
 use sp_std::{
 	collections::{btree_map::BTreeMap, vec_deque::VecDeque},
 	fmt::Debug,
 	ops::RangeInclusive,
+	vec::Vec,
 };
 
 /// The sender of the message on the source chain.
@@ -44,9 +50,46 @@ pub trait SenderOrigin<AccountId> {
 	fn linked_account(&self) -> Option<AccountId>;
 }
 
+/// The account that has actually initiated the outbound message.
+///
+/// It may differ from the account, linked to the message origin (see `SenderOrigin`), when the
+/// message is sent on behalf of other accounts - e.g. by the XCM pallet or by the pallet that
+/// transfers tokens over the bridge.
+pub trait MessageInitiator<SenderOrigin, Payload, AccountId> {
+	/// Return id of the account that has initiated the message or `None` if it is unknown.
+	fn message_initiator(
+		submitter: &SenderOrigin,
+		lane: &LaneId,
+		payload: &Payload,
+	) -> Option<AccountId>;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(8)]
+impl<SenderOrigin, Payload, AccountId> MessageInitiator<SenderOrigin, Payload, AccountId>
+	for Tuple
+{
+	fn message_initiator(
+		submitter: &SenderOrigin,
+		lane: &LaneId,
+		payload: &Payload,
+	) -> Option<AccountId> {
+		for_tuples!(
+			#(
+				if let Some(initiator) = Tuple::message_initiator(submitter, lane, payload) {
+					return Some(initiator)
+				}
+			)*
+		);
+		None
+	}
+}
+
 /// Relayers rewards, grouped by relayer account id.
 pub type RelayersRewards<AccountId, Balance> = BTreeMap<AccountId, RelayerRewards<Balance>>;
 
+/// Responses of the message dispatcher at the target chain, grouped by message nonce.
+pub type MessagesResponses = BTreeMap<MessageNonce, Vec<u8>>;
+
 /// Single relayer rewards.
 #[derive(RuntimeDebug, Default)]
 pub struct RelayerRewards<Balance> {
@@ -67,7 +110,7 @@ pub struct RelayerRewards<Balance> {
 /// type used by the source chain.
 pub trait TargetHeaderChain<Payload, AccountId> {
 	/// Error type.
-	type Error: Debug + Into<&'static str>;
+	type Error: Debug + Into<VerificationError>;
 
 	/// Proof that messages have been received by target chain.
 	type MessagesDeliveryProof: Parameter + Size;
@@ -87,9 +130,12 @@ pub trait TargetHeaderChain<Payload, AccountId> {
 	fn verify_message(payload: &Payload) -> Result<(), Self::Error>;
 
 	/// Verify messages delivery proof and return lane && nonce of the latest received message.
+	///
+	/// Dispatch responses of all unrewarded messages (see `InboundLaneData::relayers`) are
+	/// also returned.
 	fn verify_messages_delivery_proof(
 		proof: Self::MessagesDeliveryProof,
-	) -> Result<(LaneId, InboundLaneData<AccountId>), Self::Error>;
+	) -> Result<(LaneId, InboundLaneData<AccountId>, MessagesResponses), Self::Error>;
 }
 
 /// Lane message verifier.
@@ -113,6 +159,14 @@ pub trait LaneMessageVerifier<SenderOrigin, Payload, Fee> {
 		outbound_data: &OutboundLaneData,
 		payload: &Payload,
 	) -> Result<(), Self::Error>;
+
+	/// Return maximal weight of additional storage accesses, performed by the `verify_message`
+	/// call.
+	///
+	/// This weight is added to the weight of the send-message transaction.
+	fn verify_message_weight(_db_weight: RuntimeDbWeight) -> Weight {
+		0
+	}
 }
 
 /// Message delivery payment. It is called as a part of submit-message transaction. Transaction
@@ -139,6 +193,13 @@ pub trait MessageDeliveryAndDispatchPayment<SenderOrigin, AccountId, Balance> {
 		fee: &Balance,
 	) -> Result<(), Self::Error>;
 
+	/// Refund delivery_and_dispatch_fee of the cancelled message from some relayers-fund account
+	/// back to the message sender.
+	fn refund_delivery_and_dispatch_fee(
+		sender: &AccountId,
+		fee: &Balance,
+	) -> Result<(), Self::Error>;
+
 	/// Pay rewards for delivering messages to the given relayers.
 	///
 	/// The implementation may also choose to pay reward to the `confirmation_relayer`, which is
@@ -163,6 +224,13 @@ impl<SenderOrigin, AccountId, Balance>
 		Ok(())
 	}
 
+	fn refund_delivery_and_dispatch_fee(
+		_sender: &AccountId,
+		_fee: &Balance,
+	) -> Result<(), Self::Error> {
+		Ok(())
+	}
+
 	fn pay_relayers_rewards(
 		_lane_id: LaneId,
 		_messages_relayers: VecDeque<UnrewardedRelayer<AccountId>>,
@@ -246,6 +314,26 @@ impl OnDeliveryConfirmed for Tuple {
 	}
 }
 
+/// Handler for responses of the message dispatcher at the target chain.
+pub trait OnMessageResponse {
+	/// Called when we receive confirmation that our message has been delivered to the target
+	/// chain and its dispatcher has produced the response. Guaranteed to be called at most once
+	/// per message.
+	///
+	/// Should return total weight consumed by the call.
+	///
+	/// NOTE: messages pallet reserves the single message callback overhead for every call of
+	/// this callback (in addition to the `OnDeliveryConfirmed` callback). So please keep it as
+	/// light as possible.
+	fn on_message_response(lane: &LaneId, nonce: MessageNonce, response: &[u8]) -> Weight;
+}
+
+impl OnMessageResponse for () {
+	fn on_message_response(_lane: &LaneId, _nonce: MessageNonce, _response: &[u8]) -> Weight {
+		0
+	}
+}
+
 /// Handler for messages have been accepted
 pub trait OnMessageAccepted {
 	/// Called when a message has been accepted by message pallet.
@@ -277,7 +365,7 @@ impl<Payload, AccountId> TargetHeaderChain<Payload, AccountId> for ForbidOutboun
 
 	fn verify_messages_delivery_proof(
 		_proof: Self::MessagesDeliveryProof,
-	) -> Result<(LaneId, InboundLaneData<AccountId>), Self::Error> {
+	) -> Result<(LaneId, InboundLaneData<AccountId>, MessagesResponses), Self::Error> {
 		Err(ALL_OUTBOUND_MESSAGES_REJECTED)
 	}
 }
@@ -310,6 +398,13 @@ impl<SenderOrigin, AccountId, Balance>
 		Err(ALL_OUTBOUND_MESSAGES_REJECTED)
 	}
 
+	fn refund_delivery_and_dispatch_fee(
+		_sender: &AccountId,
+		_fee: &Balance,
+	) -> Result<(), Self::Error> {
+		Err(ALL_OUTBOUND_MESSAGES_REJECTED)
+	}
+
 	fn pay_relayers_rewards(
 		_lane_id: LaneId,
 		_messages_relayers: VecDeque<UnrewardedRelayer<AccountId>>,
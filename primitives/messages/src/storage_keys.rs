@@ -24,13 +24,28 @@ pub const OUTBOUND_MESSAGES_MAP_NAME: &str = "OutboundMessages";
 pub const OUTBOUND_LANES_MAP_NAME: &str = "OutboundLanes";
 /// Name of the `InboundLanes` storage map.
 pub const INBOUND_LANES_MAP_NAME: &str = "InboundLanes";
+/// Name of the `LaneOperatingModes` storage map.
+pub const LANE_OPERATING_MODES_MAP_NAME: &str = "LaneOperatingModes";
+/// Name of the `InboundMessageResponses` storage map.
+pub const INBOUND_MESSAGE_RESPONSES_MAP_NAME: &str = "InboundMessageResponses";
 
 use crate::{LaneId, MessageKey, MessageNonce};
 
 use codec::Encode;
-use frame_support::Blake2_128Concat;
+use frame_support::{traits::STORAGE_VERSION_STORAGE_KEY_POSTFIX, Blake2_128Concat};
 use sp_core::storage::StorageKey;
 
+/// Storage key of the pallet storage version in the runtime storage.
+pub fn storage_version_key(pallet_prefix: &str) -> StorageKey {
+	StorageKey(
+		bp_runtime::storage_value_final_key(
+			pallet_prefix.as_bytes(),
+			STORAGE_VERSION_STORAGE_KEY_POSTFIX,
+		)
+		.to_vec(),
+	)
+}
+
 /// Storage key of the `PalletOperatingMode` value in the runtime storage.
 pub fn operating_mode_key(pallet_prefix: &str) -> StorageKey {
 	StorageKey(
@@ -69,6 +84,28 @@ pub fn inbound_lane_data_key(pallet_prefix: &str, lane: &LaneId) -> StorageKey {
 	)
 }
 
+/// Storage key of the inbound message response in the runtime storage.
+pub fn inbound_message_response_key(
+	pallet_prefix: &str,
+	lane: &LaneId,
+	nonce: MessageNonce,
+) -> StorageKey {
+	bp_runtime::storage_map_final_key::<Blake2_128Concat>(
+		pallet_prefix,
+		INBOUND_MESSAGE_RESPONSES_MAP_NAME,
+		&MessageKey { lane_id: *lane, nonce }.encode(),
+	)
+}
+
+/// Storage key of the lane operating mode in the runtime storage.
+pub fn lane_operating_mode_key(pallet_prefix: &str, lane: &LaneId) -> StorageKey {
+	bp_runtime::storage_map_final_key::<Blake2_128Concat>(
+		pallet_prefix,
+		LANE_OPERATING_MODES_MAP_NAME,
+		lane,
+	)
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -87,6 +124,19 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn storage_version_key_computed_properly() {
+		// If this test fails, then something has been changed in module storage that is possibly
+		// breaking all existing message relays.
+		let storage_key = storage_version_key("BridgeMessages").0;
+		assert_eq!(
+			storage_key,
+			hex!("dd16c784ebd3390a9bc0357c7511ed014e7b9012096b41c4eb3aaf947f6ea429").to_vec(),
+			"Unexpected storage key: {}",
+			hex::encode(&storage_key),
+		);
+	}
+
 	#[test]
 	fn storage_message_key_computed_properly() {
 		// If this test fails, then something has been changed in module storage that is breaking
@@ -100,6 +150,19 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn inbound_message_response_key_computed_properly() {
+		// If this test fails, then something has been changed in module storage that is breaking
+		// all previously crafted messages delivery proofs.
+		let storage_key = inbound_message_response_key("BridgeMessages", b"test", 42).0;
+		assert_eq!(
+			storage_key,
+			hex!("dd16c784ebd3390a9bc0357c7511ed014d3b5618f2b451098ffe64e8cad402d29446af0e09063bd4a7874aef8a997cec746573742a00000000000000").to_vec(),
+			"Unexpected storage key: {}",
+			hex::encode(&storage_key),
+		);
+	}
+
 	#[test]
 	fn outbound_lane_data_key_computed_properly() {
 		// If this test fails, then something has been changed in module storage that is breaking
@@ -125,4 +188,17 @@ mod tests {
 			hex::encode(&storage_key),
 		);
 	}
+
+	#[test]
+	fn lane_operating_mode_key_computed_properly() {
+		// If this test fails, then something has been changed in module storage that is possibly
+		// breaking all existing message relays.
+		let storage_key = lane_operating_mode_key("BridgeMessages", b"test").0;
+		assert_eq!(
+			storage_key,
+			hex!("dd16c784ebd3390a9bc0357c7511ed01421884be2ba562ac021181f17f1b00f044a8995dd50b6657a037a7839304535b74657374").to_vec(),
+			"Unexpected storage key: {}",
+			hex::encode(&storage_key),
+		);
+	}
 }
@@ -16,10 +16,10 @@
 
 //! Primitives of messages module, that are used on the target chain.
 
-use crate::{LaneId, Message, MessageData, MessageKey, OutboundLaneData};
+use crate::{LaneId, Message, MessageData, MessageKey, OutboundLaneData, VerificationError};
 
 use bp_runtime::{messages::MessageDispatchResult, Size};
-use codec::{Decode, Encode, Error as CodecError};
+use codec::{Decode, Encode, Error as CodecError, MaxEncodedLen};
 use frame_support::{weights::Weight, Parameter, RuntimeDebug};
 use scale_info::TypeInfo;
 use sp_std::{collections::btree_map::BTreeMap, fmt::Debug, prelude::*};
@@ -43,6 +43,9 @@ pub struct DispatchMessageData<DispatchPayload, Fee> {
 	pub payload: Result<DispatchPayload, CodecError>,
 	/// Message delivery and dispatch fee, paid by the submitter.
 	pub fee: Fee,
+	/// True if the message has been cancelled at the source chain. Cancelled messages are
+	/// never dispatched.
+	pub is_cancelled: bool,
 }
 
 /// Message with decoded dispatch payload.
@@ -54,6 +57,17 @@ pub struct DispatchMessage<DispatchPayload, Fee> {
 	pub data: DispatchMessageData<DispatchPayload, Fee>,
 }
 
+/// Inbound message which dispatch has failed and that may be retried later.
+#[derive(RuntimeDebug, Encode, Decode, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct FailedMessageDispatch<AccountId, BlockNumber, Payload> {
+	/// Account that is allowed to retry message dispatch.
+	pub retry_account: AccountId,
+	/// Number of the block where the dispatch has failed.
+	pub failed_at: BlockNumber,
+	/// Encoded message payload.
+	pub payload: Payload,
+}
+
 /// Source chain API. Used by target chain, to verify source chain proofs.
 ///
 /// All implementations of this trait should only work with finalized data that
@@ -61,7 +75,7 @@ pub struct DispatchMessage<DispatchPayload, Fee> {
 /// that's stuck) and/or processing messages without paying fees.
 pub trait SourceHeaderChain<Fee> {
 	/// Error type.
-	type Error: Debug + Into<&'static str>;
+	type Error: Debug + Into<VerificationError>;
 
 	/// Proof that messages are sent from source chain. This may also include proof
 	/// of corresponding outbound lane states.
@@ -98,6 +112,20 @@ pub trait MessageDispatch<AccountId, Fee> {
 	/// `From<Chain>InboundLaneApi::message_details().dispatch_weight` call.
 	fn dispatch_weight(message: &mut DispatchMessage<Self::DispatchPayload, Fee>) -> Weight;
 
+	/// Return account at this chain that is allowed to retry dispatch of the message, if its
+	/// dispatch fails.
+	///
+	/// Normally it is the account, derived from the message sender at the bridged chain. If
+	/// `None` is returned, the failed message won't be kept for retry.
+	///
+	/// The messages pallet calls this function after the `dispatch_weight` call, so it may
+	/// reuse data, cached in the payload by the latter.
+	fn dispatch_retry_account(
+		_message: &DispatchMessage<Self::DispatchPayload, Fee>,
+	) -> Option<AccountId> {
+		None
+	}
+
 	/// Called when inbound message is received.
 	///
 	/// It is up to the implementers of this trait to determine whether the message
@@ -105,6 +133,9 @@ pub trait MessageDispatch<AccountId, Fee> {
 	///
 	/// If your configuration allows paying dispatch fee at the target chain, then
 	/// it must be paid inside this method to the `relayer_account`.
+	///
+	/// The optional response, returned in the `MessageDispatchResult::response` field, is
+	/// relayed back to the source chain in the messages delivery proof.
 	fn dispatch(
 		relayer_account: &AccountId,
 		message: DispatchMessage<Self::DispatchPayload, Fee>,
@@ -130,6 +161,7 @@ impl<DispatchPayload: Decode, Fee> From<MessageData<Fee>>
 		DispatchMessageData {
 			payload: DispatchPayload::decode(&mut &data.payload[..]),
 			fee: data.fee,
+			is_cancelled: data.is_cancelled,
 		}
 	}
 }
@@ -169,6 +201,7 @@ impl<AccountId, Fee> MessageDispatch<AccountId, Fee> for ForbidInboundMessages {
 			dispatch_result: false,
 			unspent_weight: 0,
 			dispatch_fee_paid_during_dispatch: false,
+			response: None,
 		}
 	}
 }
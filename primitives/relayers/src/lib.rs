@@ -19,8 +19,45 @@
 #![warn(missing_docs)]
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use bp_messages::LaneId;
+use bp_runtime::StorageDoubleMapKeyProvider;
+use codec::{Decode, Encode, FullCodec, MaxEncodedLen};
+use frame_support::{Blake2_128Concat, RuntimeDebug};
+use scale_info::TypeInfo;
+use sp_runtime::traits::Zero;
 use sp_std::{fmt::Debug, marker::PhantomData};
 
+/// Name of the relayers pallet instance that is deployed at bridge runtimes.
+pub const RELAYERS_PALLET_NAME: &str = "BridgeRelayers";
+
+/// Pool of rewards for bridge transactions without messages calls (e.g. finality transactions).
+///
+/// Rewards are stored separately for every messages lane, so the pool is identified by the lane
+/// identifier that is never used by messages lanes. This way, refunds for finality transactions
+/// are never mixed with rewards for delivering and confirming messages.
+pub const FINALITY_REWARDS_POOL: LaneId = *b"fnlt";
+
+/// Relayer registration.
+#[derive(Copy, Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Registration<BlockNumber, Balance> {
+	/// The last block number, where this registration is considered active.
+	///
+	/// Relayer may renew the registration at any time (even before it has expired). Starting
+	/// from block `valid_till + 1`, relayer may `deregister` and get the stake back.
+	pub valid_till: BlockNumber,
+	/// Active relayer stake, which is mapped to the relayer reserved balance.
+	///
+	/// If `stake` is less than the required stake, the registration is considered inactive.
+	pub stake: Balance,
+}
+
+impl<BlockNumber: PartialOrd, Balance: PartialOrd + Zero> Registration<BlockNumber, Balance> {
+	/// Returns true if registration is active at given block and the stake is enough.
+	pub fn is_active(&self, at: BlockNumber, required_stake: Balance) -> bool {
+		!self.stake.is_zero() && self.stake >= required_stake && at <= self.valid_till
+	}
+}
+
 /// Reward payment procedure.
 pub trait PaymentProcedure<Relayer, Reward> {
 	/// Error that may be returned by the procedure.
@@ -43,3 +80,21 @@ where
 		T::mint_into(relayer, reward)
 	}
 }
+
+/// Can be use to access the runtime storage key within the `RelayerRewards` map of the relayers
+/// pallet.
+pub struct RelayerRewardsKeyProvider<AccountId, Reward>(PhantomData<(AccountId, Reward)>);
+
+impl<AccountId, Reward> StorageDoubleMapKeyProvider for RelayerRewardsKeyProvider<AccountId, Reward>
+where
+	AccountId: FullCodec,
+	Reward: FullCodec,
+{
+	const MAP_NAME: &'static str = "RelayerRewards";
+
+	type Hasher1 = Blake2_128Concat;
+	type Key1 = LaneId;
+	type Hasher2 = Blake2_128Concat;
+	type Key2 = AccountId;
+	type Value = Reward;
+}
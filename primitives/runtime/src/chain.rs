@@ -221,6 +221,8 @@ pub type TransactionEraOf<C> = crate::TransactionEra<BlockNumberOf<C>, HashOf<C>
 ///     - `<ThisChain>FinalityApi`
 /// - constants that are stringified names of runtime API methods:
 ///     - `BEST_FINALIZED_<THIS_CHAIN>_HEADER_METHOD`
+///     - `FINALIZED_<THIS_CHAIN>_HEADER_AT_METHOD`
+///     - `IMPORTED_<THIS_CHAIN>_HEADERS_RANGE_METHOD`
 /// The name of the chain has to be specified in snake case (e.g. `rialto_parachain`).
 #[macro_export]
 macro_rules! decl_bridge_finality_runtime_apis {
@@ -232,15 +234,39 @@ macro_rules! decl_bridge_finality_runtime_apis {
 				/// Name of the `<ThisChain>FinalityApi::best_finalized` runtime method.
 				pub const [<BEST_FINALIZED_ $chain:upper _HEADER_METHOD>]: &str =
 					stringify!([<$chain:camel FinalityApi_best_finalized>]);
+				/// Name of the `<ThisChain>FinalityApi::finalized_header_at` runtime method.
+				pub const [<FINALIZED_ $chain:upper _HEADER_AT_METHOD>]: &str =
+					stringify!([<$chain:camel FinalityApi_finalized_header_at>]);
+				/// Name of the `<ThisChain>FinalityApi::imported_headers_range` runtime method.
+				pub const [<IMPORTED_ $chain:upper _HEADERS_RANGE_METHOD>]: &str =
+					stringify!([<$chain:camel FinalityApi_imported_headers_range>]);
 
 				sp_api::decl_runtime_apis! {
 					/// API for querying information about the finalized chain headers.
 					///
 					/// This API is implemented by runtimes that are receiving messages from this chain, not by this
 					/// chain's runtime itself.
+					///
+					/// Version 2 has added the `finalized_header_at` and `imported_headers_range` methods. Runtimes
+					/// that are implementing version 2 must also run the
+					/// `pallet_bridge_grandpa::migration::v1::MigrateToV1` migration, so that headers imported
+					/// before the upgrade are returned by these methods.
+					#[api_version(2)]
 					pub trait [<$chain:camel FinalityApi>] {
 						/// Returns number and hash of the best finalized header known to the bridge module.
 						fn best_finalized() -> Option<bp_runtime::HeaderId<Hash, BlockNumber>>;
+						/// Returns number and hash of the header with given number, if it has been imported
+						/// by the bridge module and is not yet pruned.
+						///
+						/// Bridge module only imports some of finalized headers, so `None` doesn't mean
+						/// that the header is not finalized. Use `best_finalized` to check that.
+						fn finalized_header_at(number: BlockNumber) -> Option<bp_runtime::HeaderId<Hash, BlockNumber>>;
+						/// Returns numbers and hashes of all imported (and not yet pruned) headers in the given
+						/// inclusive range. The vector is ordered by the header number.
+						fn imported_headers_range(
+							from: BlockNumber,
+							to: BlockNumber,
+						) -> bp_runtime::sp_std::vec::Vec<bp_runtime::HeaderId<Hash, BlockNumber>>;
 					}
 				}
 			}
@@ -255,10 +281,12 @@ macro_rules! decl_bridge_finality_runtime_apis {
 /// - chain-specific bridge runtime APIs:
 ///     - `To<ThisChain>OutboundLaneApi`
 ///     - `From<ThisChain>InboundLaneApi`
+///     - `To<ThisChain>MessageStatusApi`
 /// - constants that are stringified names of runtime API methods:
 ///     - `TO_<THIS_CHAIN>_ESTIMATE_MESSAGE_FEE_METHOD`
 ///     - `TO_<THIS_CHAIN>_MESSAGE_DETAILS_METHOD`
 ///     - `FROM_<THIS_CHAIN>_MESSAGE_DETAILS_METHOD`,
+///     - `TO_<THIS_CHAIN>_MESSAGE_STATUS_METHOD`,
 /// The name of the chain has to be specified in snake case (e.g. `rialto_parachain`).
 #[macro_export]
 macro_rules! decl_bridge_messages_runtime_apis {
@@ -274,16 +302,27 @@ macro_rules! decl_bridge_messages_runtime_apis {
 				/// Name of the `To<ThisChain>OutboundLaneApi::message_details` runtime method.
 				pub const [<TO_ $chain:upper _MESSAGE_DETAILS_METHOD>]: &str =
 					stringify!([<To $chain:camel OutboundLaneApi_message_details>]);
+				/// Name of the `To<ThisChain>OutboundLaneApi::lane_operating_mode` runtime method.
+				pub const [<TO_ $chain:upper _LANE_OPERATING_MODE_METHOD>]: &str =
+					stringify!([<To $chain:camel OutboundLaneApi_lane_operating_mode>]);
 
 				/// Name of the `From<ThisChain>InboundLaneApi::message_details` runtime method.
 				pub const [<FROM_ $chain:upper _MESSAGE_DETAILS_METHOD>]: &str =
 					stringify!([<From $chain:camel InboundLaneApi_message_details>]);
 
+				/// Name of the `To<ThisChain>MessageStatusApi::message_status` runtime method.
+				pub const [<TO_ $chain:upper _MESSAGE_STATUS_METHOD>]: &str =
+					stringify!([<To $chain:camel MessageStatusApi_message_status>]);
+
 				sp_api::decl_runtime_apis! {
 					/// Outbound message lane API for messages that are sent to this chain.
 					///
 					/// This API is implemented by runtimes that are receiving messages from this chain, not by this
 					/// chain's runtime itself.
+					///
+					/// Version 2 has added the `lane_operating_mode` method. The whole API is versioned, so
+					/// runtimes that are implementing it must implement all its methods.
+					#[api_version(2)]
 					pub trait [<To $chain:camel OutboundLaneApi>]<OutboundMessageFee: Parameter, OutboundPayload: Parameter> {
 						/// Estimate message delivery and dispatch fee that needs to be paid by the sender on
 						/// this chain.
@@ -309,6 +348,12 @@ macro_rules! decl_bridge_messages_runtime_apis {
 							begin: MessageNonce,
 							end: MessageNonce,
 						) -> Vec<OutboundMessageDetails<OutboundMessageFee>>;
+						/// Returns operating mode of the given outbound lane.
+						///
+						/// Please keep in mind that the pallet operating mode is checked before the
+						/// lane operating mode, so the lane may be unusable even if it is operating
+						/// normally.
+						fn lane_operating_mode(lane: LaneId) -> MessagesOperatingMode;
 					}
 
 					/// Inbound message lane API for messages sent by this chain.
@@ -325,6 +370,18 @@ macro_rules! decl_bridge_messages_runtime_apis {
 							messages: Vec<(MessagePayload, OutboundMessageDetails<InboundMessageFee>)>,
 						) -> Vec<InboundMessageDetails>;
 					}
+
+					/// Status API for messages that are sent to this chain.
+					///
+					/// This API is implemented by runtimes that are sending messages to this chain, not by this
+					/// chain's runtime itself. The `BlockNumber` is the block number of the implementing runtime.
+					pub trait [<To $chain:camel MessageStatusApi>]<BlockNumber: Parameter> {
+						/// Returns status of the outbound message with given nonce.
+						///
+						/// Returns `None` if message hasn't been sent yet, or if its status is not tracked
+						/// by the messages pallet.
+						fn message_status(lane: LaneId, nonce: MessageNonce) -> Option<MessageStatus<BlockNumber>>;
+					}
 				}
 			}
 
@@ -26,7 +26,10 @@ use frame_system::RawOrigin;
 use scale_info::TypeInfo;
 use sp_core::{hash::H256, storage::StorageKey};
 use sp_io::hashing::blake2_256;
-use sp_runtime::traits::{BadOrigin, Header as HeaderT};
+use sp_runtime::{
+	traits::{BadOrigin, Header as HeaderT},
+	DispatchError,
+};
 use sp_std::{convert::TryFrom, fmt::Debug, vec, vec::Vec};
 
 pub use chain::{
@@ -52,6 +55,10 @@ mod storage_proof;
 // Re-export macro to aviod include paste dependency everywhere
 pub use sp_runtime::paste;
 
+// Re-export to use `Vec` in runtime APIs of chains that don't depend on `sp-std`
+#[doc(hidden)]
+pub use sp_std;
+
 /// Use this when something must be shared among all instances.
 pub const NO_INSTANCE_ID: ChainId = [0, 0, 0, 0];
 
@@ -336,24 +343,31 @@ pub trait StorageDoubleMapKeyProvider {
 	/// We're using it because to call `storage_double_map_final_key` directly, we need access
 	/// to the runtime and pallet instance, which (sometimes) is impossible.
 	fn final_key(pallet_prefix: &str, key1: &Self::Key1, key2: &Self::Key2) -> StorageKey {
-		let key1_hashed = Self::Hasher1::hash(&key1.encode());
 		let key2_hashed = Self::Hasher2::hash(&key2.encode());
+
+		let StorageKey(mut final_key) = Self::prefix_key(pallet_prefix, key1);
+		final_key.extend_from_slice(key2_hashed.as_ref());
+
+		StorageKey(final_key)
+	}
+
+	/// Returns prefix of runtime storage keys of all values with given first key.
+	///
+	/// This is the same as the prefix that `StorageDoubleMap::iter_prefix` is iterating.
+	fn prefix_key(pallet_prefix: &str, key1: &Self::Key1) -> StorageKey {
+		let key1_hashed = Self::Hasher1::hash(&key1.encode());
 		let pallet_prefix_hashed = frame_support::Twox128::hash(pallet_prefix.as_bytes());
 		let storage_prefix_hashed = frame_support::Twox128::hash(Self::MAP_NAME.as_bytes());
 
-		let mut final_key = Vec::with_capacity(
-			pallet_prefix_hashed.len() +
-				storage_prefix_hashed.len() +
-				key1_hashed.as_ref().len() +
-				key2_hashed.as_ref().len(),
+		let mut prefix_key = Vec::with_capacity(
+			pallet_prefix_hashed.len() + storage_prefix_hashed.len() + key1_hashed.as_ref().len(),
 		);
 
-		final_key.extend_from_slice(&pallet_prefix_hashed[..]);
-		final_key.extend_from_slice(&storage_prefix_hashed[..]);
-		final_key.extend_from_slice(key1_hashed.as_ref());
-		final_key.extend_from_slice(key2_hashed.as_ref());
+		prefix_key.extend_from_slice(&pallet_prefix_hashed[..]);
+		prefix_key.extend_from_slice(&storage_prefix_hashed[..]);
+		prefix_key.extend_from_slice(key1_hashed.as_ref());
 
-		StorageKey(final_key)
+		StorageKey(prefix_key)
 	}
 }
 
@@ -458,6 +472,29 @@ pub trait OwnedBridgeModule<T: frame_system::Config> {
 pub trait FilterCall<Call> {
 	/// Checks if a runtime call is valid.
 	fn validate(call: &Call) -> TransactionValidity;
+
+	/// Returns true if the runtime call is a bridge call, checked by this filter.
+	fn is_bridge_call(_call: &Call) -> bool {
+		false
+	}
+
+	/// Returns true if data, brought by the bridge call, is imported.
+	///
+	/// Must be called after the call is dispatched.
+	fn is_call_data_imported(_call: &Call) -> bool {
+		false
+	}
+
+	/// Returns true if the bridge call has failed with given error, because the submitter has
+	/// provided invalid proof.
+	fn is_invalid_proof_error(_error: &DispatchError) -> bool {
+		false
+	}
+
+	/// Returns true if both bridge calls are bringing (partially) the same data.
+	fn are_overlapping_calls(_call: &Call, _other_call: &Call) -> bool {
+		false
+	}
 }
 
 #[cfg(test)]
@@ -19,6 +19,7 @@
 use codec::{Decode, Encode};
 use frame_support::{weights::Weight, RuntimeDebug};
 use scale_info::TypeInfo;
+use sp_std::vec::Vec;
 
 /// Where message dispatch fee is paid?
 #[derive(Encode, Decode, RuntimeDebug, Clone, Copy, PartialEq, Eq, TypeInfo)]
@@ -54,4 +55,7 @@ pub struct MessageDispatchResult {
 	/// configuration supports pay-dispatch-fee-at-target-chain option and message sender has
 	/// enabled this option.
 	pub dispatch_fee_paid_during_dispatch: bool,
+	/// Optional response of the message dispatcher. If it is set, the response is relayed back
+	/// to the source chain, along with the dispatch result flag.
+	pub response: Option<Vec<u8>>,
 }
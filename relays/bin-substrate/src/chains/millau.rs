@@ -17,6 +17,10 @@
 //! Millau chain specification for CLI.
 
 use crate::cli::{
+	admin::{
+		decode_pallet_parameter, unsupported_pallet_error, BridgePallet, CliEncodeAdminCall,
+		OperatingMode,
+	},
 	bridge,
 	encode_message::{CliEncodeMessage, RawMessage},
 	CliChain,
@@ -25,7 +29,7 @@ use bp_messages::LaneId;
 use bp_rialto_parachain::RIALTO_PARACHAIN_ID;
 use bp_runtime::EncodedOrDecodedCall;
 use relay_millau_client::Millau;
-use relay_substrate_client::BalanceOf;
+use relay_substrate_client::{AccountIdOf, BalanceOf};
 use sp_version::RuntimeVersion;
 use xcm::latest::prelude::*;
 
@@ -89,6 +93,105 @@ impl CliEncodeMessage for Millau {
 	}
 }
 
+impl CliEncodeAdminCall for Millau {
+	fn encode_set_owner_call(
+		pallet: BridgePallet,
+		new_owner: Option<AccountIdOf<Self>>,
+		bridge_instance_index: u8,
+	) -> anyhow::Result<EncodedOrDecodedCall<Self::Call>> {
+		Ok(match (pallet, bridge_instance_index) {
+			(
+				BridgePallet::Grandpa,
+				bridge::MILLAU_TO_RIALTO_INDEX | bridge::MILLAU_TO_RIALTO_PARACHAIN_INDEX,
+			) => millau_runtime::Call::BridgeRialtoGrandpa(
+				millau_runtime::BridgeGrandpaCall::set_owner { new_owner },
+			),
+			(BridgePallet::Messages, bridge::MILLAU_TO_RIALTO_INDEX) =>
+				millau_runtime::Call::BridgeRialtoMessages(
+					millau_runtime::MessagesCall::set_owner { new_owner },
+				),
+			(BridgePallet::Messages, bridge::MILLAU_TO_RIALTO_PARACHAIN_INDEX) =>
+				millau_runtime::Call::BridgeRialtoParachainMessages(
+					millau_runtime::MessagesCall::set_owner { new_owner },
+				),
+			(BridgePallet::Parachains, bridge::MILLAU_TO_RIALTO_PARACHAIN_INDEX) =>
+				millau_runtime::Call::BridgeRialtoParachains(
+					millau_runtime::BridgeParachainsCall::set_owner { new_owner },
+				),
+			_ => return Err(unsupported_pallet_error(pallet, bridge_instance_index)),
+		}
+		.into())
+	}
+
+	fn encode_set_operating_mode_call(
+		pallet: BridgePallet,
+		operating_mode: OperatingMode,
+		bridge_instance_index: u8,
+	) -> anyhow::Result<EncodedOrDecodedCall<Self::Call>> {
+		Ok(match (pallet, bridge_instance_index) {
+			(
+				BridgePallet::Grandpa,
+				bridge::MILLAU_TO_RIALTO_INDEX | bridge::MILLAU_TO_RIALTO_PARACHAIN_INDEX,
+			) => millau_runtime::Call::BridgeRialtoGrandpa(
+				millau_runtime::BridgeGrandpaCall::set_operating_mode {
+					operating_mode: operating_mode.into_basic()?,
+				},
+			),
+			(BridgePallet::Messages, bridge::MILLAU_TO_RIALTO_INDEX) =>
+				millau_runtime::Call::BridgeRialtoMessages(
+					millau_runtime::MessagesCall::set_operating_mode {
+						operating_mode: operating_mode.into_messages(),
+					},
+				),
+			(BridgePallet::Messages, bridge::MILLAU_TO_RIALTO_PARACHAIN_INDEX) =>
+				millau_runtime::Call::BridgeRialtoParachainMessages(
+					millau_runtime::MessagesCall::set_operating_mode {
+						operating_mode: operating_mode.into_messages(),
+					},
+				),
+			(BridgePallet::Parachains, bridge::MILLAU_TO_RIALTO_PARACHAIN_INDEX) =>
+				millau_runtime::Call::BridgeRialtoParachains(
+					millau_runtime::BridgeParachainsCall::set_operating_mode {
+						operating_mode: operating_mode.into_basic()?,
+					},
+				),
+			_ => return Err(unsupported_pallet_error(pallet, bridge_instance_index)),
+		}
+		.into())
+	}
+
+	fn encode_update_pallet_parameter_call(
+		parameter: Vec<u8>,
+		bridge_instance_index: u8,
+	) -> anyhow::Result<EncodedOrDecodedCall<Self::Call>> {
+		Ok(match bridge_instance_index {
+			bridge::MILLAU_TO_RIALTO_INDEX => millau_runtime::Call::BridgeRialtoMessages(
+				millau_runtime::MessagesCall::update_pallet_parameter {
+					parameter: decode_pallet_parameter(&parameter)?,
+				},
+			),
+			bridge::MILLAU_TO_RIALTO_PARACHAIN_INDEX =>
+				millau_runtime::Call::BridgeRialtoParachainMessages(
+					millau_runtime::MessagesCall::update_pallet_parameter {
+						parameter: decode_pallet_parameter(&parameter)?,
+					},
+				),
+			_ =>
+				return Err(unsupported_pallet_error(BridgePallet::Messages, bridge_instance_index)),
+		}
+		.into())
+	}
+
+	fn encode_sudo_call(
+		call: EncodedOrDecodedCall<Self::Call>,
+	) -> anyhow::Result<EncodedOrDecodedCall<Self::Call>> {
+		Ok(millau_runtime::Call::Sudo(millau_runtime::SudoCall::sudo {
+			call: Box::new(call.into_decoded()?),
+		})
+		.into())
+	}
+}
+
 impl CliChain for Millau {
 	const RUNTIME_VERSION: Option<RuntimeVersion> = Some(millau_runtime::VERSION);
 
@@ -63,6 +63,8 @@ impl SubstrateMessageLane for MillauMessagesToRialto {
 		millau_runtime::WithRialtoMessagesInstance,
 	>;
 
+	type TargetBatchCallBuilder = ();
+
 	type TargetToSourceChainConversionRateUpdateBuilder =
 		MillauMessagesToRialtoUpdateConversionRateCallBuilder;
 
@@ -68,6 +68,8 @@ impl SubstrateMessageLane for MillauMessagesToRialtoParachain {
 		millau_runtime::WithRialtoParachainMessagesInstance,
 	>;
 
+	type TargetBatchCallBuilder = ();
+
 	type TargetToSourceChainConversionRateUpdateBuilder =
 		MillauMessagesToRialtoParachainUpdateConversionRateCallBuilder;
 
@@ -17,6 +17,10 @@
 //! Pass3d chain specification for CLI.
 
 use crate::cli::{
+	admin::{
+		decode_pallet_parameter, unsupported_pallet_error, BridgePallet, CliEncodeAdminCall,
+		OperatingMode,
+	},
 	bridge,
 	encode_message::{CliEncodeMessage, RawMessage},
 	CliChain,
@@ -24,7 +28,7 @@ use crate::cli::{
 use bp_messages::LaneId;
 use bp_runtime::EncodedOrDecodedCall;
 use relay_pass3d_client::Pass3d;
-use relay_substrate_client::BalanceOf;
+use relay_substrate_client::{AccountIdOf, BalanceOf};
 use sp_version::RuntimeVersion;
 use xcm::latest::prelude::*;
 
@@ -72,6 +76,75 @@ impl CliEncodeMessage for Pass3d {
 	}
 }
 
+impl CliEncodeAdminCall for Pass3d {
+	fn encode_set_owner_call(
+		pallet: BridgePallet,
+		new_owner: Option<AccountIdOf<Self>>,
+		bridge_instance_index: u8,
+	) -> anyhow::Result<EncodedOrDecodedCall<Self::Call>> {
+		Ok(match (pallet, bridge_instance_index) {
+			(BridgePallet::Grandpa, bridge::PASS3D_TO_PASS3DT_INDEX) =>
+				pass3d_runtime::Call::BridgePass3dtGrandpa(
+					pass3d_runtime::BridgeGrandpaCall::set_owner { new_owner },
+				),
+			(BridgePallet::Messages, bridge::PASS3D_TO_PASS3DT_INDEX) =>
+				pass3d_runtime::Call::BridgePass3dtMessages(
+					pass3d_runtime::MessagesCall::set_owner { new_owner },
+				),
+			_ => return Err(unsupported_pallet_error(pallet, bridge_instance_index)),
+		}
+		.into())
+	}
+
+	fn encode_set_operating_mode_call(
+		pallet: BridgePallet,
+		operating_mode: OperatingMode,
+		bridge_instance_index: u8,
+	) -> anyhow::Result<EncodedOrDecodedCall<Self::Call>> {
+		Ok(match (pallet, bridge_instance_index) {
+			(BridgePallet::Grandpa, bridge::PASS3D_TO_PASS3DT_INDEX) =>
+				pass3d_runtime::Call::BridgePass3dtGrandpa(
+					pass3d_runtime::BridgeGrandpaCall::set_operating_mode {
+						operating_mode: operating_mode.into_basic()?,
+					},
+				),
+			(BridgePallet::Messages, bridge::PASS3D_TO_PASS3DT_INDEX) =>
+				pass3d_runtime::Call::BridgePass3dtMessages(
+					pass3d_runtime::MessagesCall::set_operating_mode {
+						operating_mode: operating_mode.into_messages(),
+					},
+				),
+			_ => return Err(unsupported_pallet_error(pallet, bridge_instance_index)),
+		}
+		.into())
+	}
+
+	fn encode_update_pallet_parameter_call(
+		parameter: Vec<u8>,
+		bridge_instance_index: u8,
+	) -> anyhow::Result<EncodedOrDecodedCall<Self::Call>> {
+		Ok(match bridge_instance_index {
+			bridge::PASS3D_TO_PASS3DT_INDEX => pass3d_runtime::Call::BridgePass3dtMessages(
+				pass3d_runtime::MessagesCall::update_pallet_parameter {
+					parameter: decode_pallet_parameter(&parameter)?,
+				},
+			),
+			_ =>
+				return Err(unsupported_pallet_error(BridgePallet::Messages, bridge_instance_index)),
+		}
+		.into())
+	}
+
+	fn encode_sudo_call(
+		call: EncodedOrDecodedCall<Self::Call>,
+	) -> anyhow::Result<EncodedOrDecodedCall<Self::Call>> {
+		Ok(pass3d_runtime::Call::Sudo(pass3d_runtime::SudoCall::sudo {
+			call: Box::new(call.into_decoded()?),
+		})
+		.into())
+	}
+}
+
 impl CliChain for Pass3d {
 	const RUNTIME_VERSION: Option<RuntimeVersion> = Some(pass3d_runtime::VERSION);
 
@@ -19,9 +19,12 @@
 use messages_relay::relay_strategy::MixStrategy;
 use relay_pass3dt_client::Pass3dt;
 use relay_pass3d_client::Pass3d;
-use substrate_relay_helper::messages_lane::{
-	DirectReceiveMessagesDeliveryProofCallBuilder, DirectReceiveMessagesProofCallBuilder,
-	SubstrateMessageLane,
+use substrate_relay_helper::{
+	messages_lane::{
+		DirectReceiveMessagesDeliveryProofCallBuilder, DirectReceiveMessagesProofCallBuilder,
+		SubstrateMessageLane,
+	},
+	messages_scheduler::DirectBatchCallBuilder,
 };
 
 /// Description of Pass3d -> Pass3dt messages bridge.
@@ -63,6 +66,8 @@ impl SubstrateMessageLane for Pass3dMessagesToPass3dt {
 		pass3d_runtime::WithPass3dtMessagesInstance,
 	>;
 
+	type TargetBatchCallBuilder = DirectBatchCallBuilder<Pass3dt, pass3dt_runtime::Runtime>;
+
 	type TargetToSourceChainConversionRateUpdateBuilder =
 		Pass3dMessagesToPass3dtUpdateConversionRateCallBuilder;
 
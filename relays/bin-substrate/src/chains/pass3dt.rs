@@ -17,6 +17,10 @@
 //! Pass3dt chain specification for CLI.
 
 use crate::cli::{
+	admin::{
+		decode_pallet_parameter, unsupported_pallet_error, BridgePallet, CliEncodeAdminCall,
+		OperatingMode,
+	},
 	bridge,
 	encode_message::{CliEncodeMessage, RawMessage},
 	CliChain,
@@ -24,7 +28,7 @@ use crate::cli::{
 use bp_messages::LaneId;
 use bp_runtime::EncodedOrDecodedCall;
 use relay_pass3dt_client::Pass3dt;
-use relay_substrate_client::BalanceOf;
+use relay_substrate_client::{AccountIdOf, BalanceOf};
 use sp_version::RuntimeVersion;
 use xcm::latest::prelude::*;
 
@@ -72,6 +76,75 @@ impl CliEncodeMessage for Pass3dt {
 	}
 }
 
+impl CliEncodeAdminCall for Pass3dt {
+	fn encode_set_owner_call(
+		pallet: BridgePallet,
+		new_owner: Option<AccountIdOf<Self>>,
+		bridge_instance_index: u8,
+	) -> anyhow::Result<EncodedOrDecodedCall<Self::Call>> {
+		Ok(match (pallet, bridge_instance_index) {
+			(BridgePallet::Grandpa, bridge::PASS3DT_TO_PASS3D_INDEX) =>
+				pass3dt_runtime::Call::BridgePass3dGrandpa(
+					pass3dt_runtime::BridgeGrandpaCall::set_owner { new_owner },
+				),
+			(BridgePallet::Messages, bridge::PASS3DT_TO_PASS3D_INDEX) =>
+				pass3dt_runtime::Call::BridgePass3dMessages(
+					pass3dt_runtime::MessagesCall::set_owner { new_owner },
+				),
+			_ => return Err(unsupported_pallet_error(pallet, bridge_instance_index)),
+		}
+		.into())
+	}
+
+	fn encode_set_operating_mode_call(
+		pallet: BridgePallet,
+		operating_mode: OperatingMode,
+		bridge_instance_index: u8,
+	) -> anyhow::Result<EncodedOrDecodedCall<Self::Call>> {
+		Ok(match (pallet, bridge_instance_index) {
+			(BridgePallet::Grandpa, bridge::PASS3DT_TO_PASS3D_INDEX) =>
+				pass3dt_runtime::Call::BridgePass3dGrandpa(
+					pass3dt_runtime::BridgeGrandpaCall::set_operating_mode {
+						operating_mode: operating_mode.into_basic()?,
+					},
+				),
+			(BridgePallet::Messages, bridge::PASS3DT_TO_PASS3D_INDEX) =>
+				pass3dt_runtime::Call::BridgePass3dMessages(
+					pass3dt_runtime::MessagesCall::set_operating_mode {
+						operating_mode: operating_mode.into_messages(),
+					},
+				),
+			_ => return Err(unsupported_pallet_error(pallet, bridge_instance_index)),
+		}
+		.into())
+	}
+
+	fn encode_update_pallet_parameter_call(
+		parameter: Vec<u8>,
+		bridge_instance_index: u8,
+	) -> anyhow::Result<EncodedOrDecodedCall<Self::Call>> {
+		Ok(match bridge_instance_index {
+			bridge::PASS3DT_TO_PASS3D_INDEX => pass3dt_runtime::Call::BridgePass3dMessages(
+				pass3dt_runtime::MessagesCall::update_pallet_parameter {
+					parameter: decode_pallet_parameter(&parameter)?,
+				},
+			),
+			_ =>
+				return Err(unsupported_pallet_error(BridgePallet::Messages, bridge_instance_index)),
+		}
+		.into())
+	}
+
+	fn encode_sudo_call(
+		call: EncodedOrDecodedCall<Self::Call>,
+	) -> anyhow::Result<EncodedOrDecodedCall<Self::Call>> {
+		Ok(pass3dt_runtime::Call::Sudo(pass3dt_runtime::SudoCall::sudo {
+			call: Box::new(call.into_decoded()?),
+		})
+		.into())
+	}
+}
+
 impl CliChain for Pass3dt {
 	const RUNTIME_VERSION: Option<RuntimeVersion> = Some(pass3dt_runtime::VERSION);
 
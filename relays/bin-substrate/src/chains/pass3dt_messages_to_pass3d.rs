@@ -19,9 +19,12 @@
 use messages_relay::relay_strategy::MixStrategy;
 use relay_pass3dt_client::Pass3dt;
 use relay_pass3d_client::Pass3d;
-use substrate_relay_helper::messages_lane::{
-	DirectReceiveMessagesDeliveryProofCallBuilder, DirectReceiveMessagesProofCallBuilder,
-	SubstrateMessageLane,
+use substrate_relay_helper::{
+	messages_lane::{
+		DirectReceiveMessagesDeliveryProofCallBuilder, DirectReceiveMessagesProofCallBuilder,
+		SubstrateMessageLane,
+	},
+	messages_scheduler::DirectBatchCallBuilder,
 };
 
 /// Description of Pass3dt -> Pass3d messages bridge.
@@ -63,6 +66,8 @@ impl SubstrateMessageLane for Pass3dtMessagesToPass3d {
 		pass3dt_runtime::WithPass3dMessagesInstance,
 	>;
 
+	type TargetBatchCallBuilder = DirectBatchCallBuilder<Pass3d, pass3d_runtime::Runtime>;
+
 	type TargetToSourceChainConversionRateUpdateBuilder =
 		Pass3dtMessagesToPass3dUpdateConversionRateCallBuilder;
 
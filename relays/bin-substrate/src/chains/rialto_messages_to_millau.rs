@@ -63,6 +63,8 @@ impl SubstrateMessageLane for RialtoMessagesToMillau {
 		rialto_runtime::WithMillauMessagesInstance,
 	>;
 
+	type TargetBatchCallBuilder = ();
+
 	type TargetToSourceChainConversionRateUpdateBuilder =
 		RialtoMessagesToMillauUpdateConversionRateCallBuilder;
 
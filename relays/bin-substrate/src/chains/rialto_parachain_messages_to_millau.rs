@@ -68,6 +68,8 @@ impl SubstrateMessageLane for RialtoParachainMessagesToMillau {
 		rialto_parachain_runtime::WithMillauMessagesInstance,
 	>;
 
+	type TargetBatchCallBuilder = ();
+
 	type TargetToSourceChainConversionRateUpdateBuilder =
 		RialtoParachainMessagesToMillauUpdateConversionRateCallBuilder;
 
@@ -0,0 +1,400 @@
+// Copyright 2019-2022 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+	chains::{
+		millau_headers_to_rialto::MillauToRialtoCliBridge,
+		millau_headers_to_rialto_parachain::MillauToRialtoParachainCliBridge,
+		pass3d_headers_to_pass3dt::Pass3dToPass3dtCliBridge,
+		pass3dt_headers_to_pass3d::Pass3dtToPass3dCliBridge,
+		rialto_headers_to_millau::RialtoToMillauCliBridge,
+		rialto_parachains_to_millau::RialtoParachainToMillauCliBridge,
+	},
+	cli::{
+		bridge::{CliBridgeBase, FullBridge},
+		chain_schema::*,
+		CliChain, HexBytes,
+	},
+};
+use async_trait::async_trait;
+use bp_messages::MessagesOperatingMode;
+use bp_runtime::{BasicOperatingMode, EncodedOrDecodedCall};
+use codec::{Decode, DecodeAll, Encode};
+use relay_substrate_client::{
+	AccountIdOf, AccountKeyPairOf, Chain, SignParam, TransactionEra, TransactionSignScheme,
+	UnsignedTransaction,
+};
+use sp_core::{crypto::AccountId32, Pair};
+use structopt::StructOpt;
+use strum::{EnumString, EnumVariantNames, VariantNames};
+
+/// Administer bridge pallets.
+///
+/// Calls are sent to the bridge pallets, deployed at the source chain of the bridge. E.g.
+/// `admin millau-to-rialto` is used to administer Millau pallets that are bridging Millau with
+/// Rialto.
+#[derive(StructOpt)]
+pub struct Admin {
+	/// A bridge instance to administer.
+	#[structopt(possible_values = FullBridge::VARIANTS, case_insensitive = true)]
+	bridge: FullBridge,
+	/// Wrap the call into the `sudo` call.
+	///
+	/// Should be used when the signer is the sudo key of the chain and not the pallet owner.
+	#[structopt(long)]
+	sudo: bool,
+	/// Print the hex-encoded call instead of submitting it.
+	#[structopt(long)]
+	dry_run: bool,
+	#[structopt(flatten)]
+	source: SourceConnectionParams,
+	#[structopt(flatten)]
+	source_sign: SourceSigningParams,
+	/// Call to send.
+	#[structopt(subcommand)]
+	call: AdminCall,
+}
+
+/// All possible administrative calls of bridge pallets.
+#[derive(StructOpt, Debug, PartialEq, Eq)]
+pub enum AdminCall {
+	/// Change owner of the pallet.
+	SetOwner {
+		/// Pallet to change owner of.
+		#[structopt(possible_values = BridgePallet::VARIANTS, case_insensitive = true)]
+		pallet: BridgePallet,
+		/// SS58 address of the new owner. If not specified, the current owner is removed.
+		#[structopt(long)]
+		new_owner: Option<AccountId32>,
+	},
+	/// Halt or resume pallet operations.
+	SetOperatingMode {
+		/// Pallet to change operating mode of.
+		#[structopt(possible_values = BridgePallet::VARIANTS, case_insensitive = true)]
+		pallet: BridgePallet,
+		/// New operating mode of the pallet.
+		#[structopt(possible_values = OperatingMode::VARIANTS, case_insensitive = true)]
+		operating_mode: OperatingMode,
+	},
+	/// Update parameter of the messages pallet.
+	UpdatePalletParameter {
+		/// Hex-encoded SCALE-encoded value of the runtime-specific messages pallet parameter.
+		parameter: HexBytes,
+	},
+}
+
+/// Bridge pallet that may be administered.
+#[derive(Debug, EnumString, EnumVariantNames, Clone, Copy, PartialEq, Eq)]
+#[strum(serialize_all = "kebab_case")]
+pub enum BridgePallet {
+	/// GRANDPA finality pallet.
+	Grandpa,
+	/// Messages pallet.
+	Messages,
+	/// Parachains finality pallet.
+	Parachains,
+}
+
+/// Operating mode of the bridge pallet.
+#[derive(Debug, EnumString, EnumVariantNames, Clone, Copy, PartialEq, Eq)]
+#[strum(serialize_all = "kebab_case")]
+pub enum OperatingMode {
+	/// All pallet operations are allowed.
+	Normal,
+	/// All pallet operations (except operating mode change) are prohibited.
+	Halted,
+	/// Messages pallet is not accepting outbound messages. Only supported by messages pallet.
+	RejectingOutboundMessages,
+}
+
+impl OperatingMode {
+	/// Convert into operating mode of the finality pallet.
+	pub fn into_basic(self) -> anyhow::Result<BasicOperatingMode> {
+		match self {
+			OperatingMode::Normal => Ok(BasicOperatingMode::Normal),
+			OperatingMode::Halted => Ok(BasicOperatingMode::Halted),
+			OperatingMode::RejectingOutboundMessages => Err(anyhow::format_err!(
+				"{:?} operating mode is only supported by messages pallet",
+				self,
+			)),
+		}
+	}
+
+	/// Convert into operating mode of the messages pallet.
+	pub fn into_messages(self) -> MessagesOperatingMode {
+		match self {
+			OperatingMode::Normal => MessagesOperatingMode::Basic(BasicOperatingMode::Normal),
+			OperatingMode::Halted => MessagesOperatingMode::Basic(BasicOperatingMode::Halted),
+			OperatingMode::RejectingOutboundMessages =>
+				MessagesOperatingMode::RejectingOutboundMessages,
+		}
+	}
+}
+
+/// Encoding of administrative calls of bridge pallets, deployed at the chain.
+pub trait CliEncodeAdminCall: Chain {
+	/// Encode a `set_owner` call of the bridge pallet.
+	fn encode_set_owner_call(
+		pallet: BridgePallet,
+		new_owner: Option<AccountIdOf<Self>>,
+		bridge_instance_index: u8,
+	) -> anyhow::Result<EncodedOrDecodedCall<Self::Call>>;
+
+	/// Encode a `set_operating_mode` call of the bridge pallet.
+	fn encode_set_operating_mode_call(
+		pallet: BridgePallet,
+		operating_mode: OperatingMode,
+		bridge_instance_index: u8,
+	) -> anyhow::Result<EncodedOrDecodedCall<Self::Call>>;
+
+	/// Encode an `update_pallet_parameter` call of the bridge-messages pallet.
+	fn encode_update_pallet_parameter_call(
+		parameter: Vec<u8>,
+		bridge_instance_index: u8,
+	) -> anyhow::Result<EncodedOrDecodedCall<Self::Call>>;
+
+	/// Wrap the call into the `sudo` call.
+	fn encode_sudo_call(
+		call: EncodedOrDecodedCall<Self::Call>,
+	) -> anyhow::Result<EncodedOrDecodedCall<Self::Call>>;
+}
+
+/// Decode SCALE-encoded runtime-specific messages pallet parameter.
+pub(crate) fn decode_pallet_parameter<P: Decode>(parameter: &[u8]) -> anyhow::Result<P> {
+	P::decode_all(&mut &parameter[..])
+		.map_err(|e| anyhow::format_err!("Failed to decode messages pallet parameter: {:?}", e))
+}
+
+/// Error returned when the pallet is not deployed at the chain.
+pub(crate) fn unsupported_pallet_error(
+	pallet: BridgePallet,
+	bridge_instance_index: u8,
+) -> anyhow::Error {
+	anyhow::format_err!(
+		"Unsupported {:?} bridge pallet with instance index: {}",
+		pallet,
+		bridge_instance_index,
+	)
+}
+
+#[async_trait]
+trait AdminCallSender: CliBridgeBase
+where
+	Self::Source: TransactionSignScheme<Chain = Self::Source>
+		+ CliChain<KeyPair = AccountKeyPairOf<Self::Source>>
+		+ CliEncodeAdminCall,
+	<Self::Source as Chain>::Call: Sync,
+	AccountIdOf<Self::Source>:
+		From<<AccountKeyPairOf<Self::Source> as Pair>::Public> + From<AccountId32>,
+{
+	async fn send_admin_call(data: Admin) -> anyhow::Result<()> {
+		let bridge_instance_index = data.bridge.bridge_instance_index();
+		let call = match data.call {
+			AdminCall::SetOwner { pallet, new_owner } => Self::Source::encode_set_owner_call(
+				pallet,
+				new_owner.map(Into::into),
+				bridge_instance_index,
+			)?,
+			AdminCall::SetOperatingMode { pallet, operating_mode } =>
+				Self::Source::encode_set_operating_mode_call(
+					pallet,
+					operating_mode,
+					bridge_instance_index,
+				)?,
+			AdminCall::UpdatePalletParameter { parameter } =>
+				Self::Source::encode_update_pallet_parameter_call(
+					parameter.0,
+					bridge_instance_index,
+				)?,
+		};
+		let call = if data.sudo { Self::Source::encode_sudo_call(call)? } else { call };
+
+		if data.dry_run {
+			println!("{:?}", HexBytes(call.encode()));
+			return Ok(())
+		}
+
+		let source_client = data.source.into_client::<Self::Source>().await?;
+		let transaction_params = data.source_sign.transaction_params::<Self::Source>()?;
+		let signer_id = transaction_params.signer.public().into();
+		let (spec_version, transaction_version) = source_client.simple_runtime_version().await?;
+		let tx_hash = source_client
+			.submit_signed_extrinsic(
+				signer_id,
+				SignParam::<Self::Source> {
+					spec_version,
+					transaction_version,
+					genesis_hash: *source_client.genesis_hash(),
+					signer: transaction_params.signer,
+				},
+				move |best_block_id, transaction_nonce| {
+					Ok(UnsignedTransaction::new(call, transaction_nonce)
+						.era(TransactionEra::new(best_block_id, transaction_params.mortality)))
+				},
+			)
+			.await?;
+
+		log::info!(
+			target: "bridge",
+			"Submitted administrative transaction to {} node: {:?}",
+			Self::Source::NAME,
+			tx_hash,
+		);
+
+		Ok(())
+	}
+}
+
+impl AdminCallSender for MillauToRialtoCliBridge {}
+impl AdminCallSender for RialtoToMillauCliBridge {}
+impl AdminCallSender for MillauToRialtoParachainCliBridge {}
+impl AdminCallSender for RialtoParachainToMillauCliBridge {}
+impl AdminCallSender for Pass3dtToPass3dCliBridge {}
+impl AdminCallSender for Pass3dToPass3dtCliBridge {}
+
+impl Admin {
+	/// Run the command.
+	pub async fn run(self) -> anyhow::Result<()> {
+		match self.bridge {
+			FullBridge::MillauToRialto => MillauToRialtoCliBridge::send_admin_call(self),
+			FullBridge::RialtoToMillau => RialtoToMillauCliBridge::send_admin_call(self),
+			FullBridge::MillauToRialtoParachain =>
+				MillauToRialtoParachainCliBridge::send_admin_call(self),
+			FullBridge::RialtoParachainToMillau =>
+				RialtoParachainToMillauCliBridge::send_admin_call(self),
+			FullBridge::Pass3dtToPass3d => Pass3dtToPass3dCliBridge::send_admin_call(self),
+			FullBridge::Pass3dToPass3dt => Pass3dToPass3dtCliBridge::send_admin_call(self),
+		}
+		.await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cli::bridge::PASS3D_TO_PASS3DT_INDEX;
+	use pass3d_runtime::pass3dt_messages::Pass3dToPass3dtMessagesParameter;
+	use relay_pass3d_client::Pass3d;
+	use sp_keyring::AccountKeyring;
+
+	#[test]
+	fn set_operating_mode_is_parsed() {
+		let admin = Admin::from_iter(vec![
+			"admin",
+			"pass3d-to-pass3dt",
+			"--sudo",
+			"--dry-run",
+			"set-operating-mode",
+			"messages",
+			"rejecting-outbound-messages",
+		]);
+
+		assert_eq!(admin.bridge, FullBridge::Pass3dToPass3dt);
+		assert!(admin.sudo);
+		assert!(admin.dry_run);
+		assert_eq!(
+			admin.call,
+			AdminCall::SetOperatingMode {
+				pallet: BridgePallet::Messages,
+				operating_mode: OperatingMode::RejectingOutboundMessages,
+			}
+		);
+	}
+
+	#[test]
+	fn set_owner_is_parsed() {
+		let alice = AccountKeyring::Alice.to_account_id();
+		let admin = Admin::from_iter(vec![
+			"admin".to_string(),
+			"pass3dt-to-pass3d".to_string(),
+			"--source-signer".to_string(),
+			"//Alice".to_string(),
+			"set-owner".to_string(),
+			"grandpa".to_string(),
+			"--new-owner".to_string(),
+			alice.to_string(),
+		]);
+
+		assert_eq!(
+			admin.call,
+			AdminCall::SetOwner { pallet: BridgePallet::Grandpa, new_owner: Some(alice) }
+		);
+	}
+
+	#[test]
+	fn rejecting_outbound_messages_is_only_supported_by_messages_pallet() {
+		assert!(OperatingMode::RejectingOutboundMessages.into_basic().is_err());
+		assert_eq!(
+			OperatingMode::RejectingOutboundMessages.into_messages(),
+			MessagesOperatingMode::RejectingOutboundMessages,
+		);
+		assert_eq!(OperatingMode::Halted.into_basic().unwrap(), BasicOperatingMode::Halted);
+	}
+
+	#[test]
+	fn admin_calls_are_encoded() {
+		let call = Pass3d::encode_set_operating_mode_call(
+			BridgePallet::Grandpa,
+			OperatingMode::Halted,
+			PASS3D_TO_PASS3DT_INDEX,
+		)
+		.unwrap();
+		assert!(matches!(
+			call.to_decoded().unwrap(),
+			pass3d_runtime::Call::BridgePass3dtGrandpa(
+				pass3d_runtime::BridgeGrandpaCall::set_operating_mode {
+					operating_mode: BasicOperatingMode::Halted,
+				}
+			)
+		));
+
+		let call = Pass3d::encode_sudo_call(call).unwrap();
+		assert!(matches!(call.to_decoded().unwrap(), pass3d_runtime::Call::Sudo(_)));
+
+		assert!(Pass3d::encode_set_owner_call(
+			BridgePallet::Parachains,
+			None,
+			PASS3D_TO_PASS3DT_INDEX,
+		)
+		.is_err());
+	}
+
+	#[test]
+	fn pallet_parameter_is_decoded() {
+		let parameter = Pass3dToPass3dtMessagesParameter::Pass3dtToPass3dConversionRate(
+			sp_runtime::FixedU128::from_float(1.5),
+		);
+		let call = Pass3d::encode_update_pallet_parameter_call(
+			parameter.encode(),
+			PASS3D_TO_PASS3DT_INDEX,
+		)
+		.unwrap();
+		assert!(matches!(
+			call.to_decoded().unwrap(),
+			pass3d_runtime::Call::BridgePass3dtMessages(
+				pass3d_runtime::MessagesCall::update_pallet_parameter { .. }
+			)
+		));
+
+		let mut encoded_parameter = parameter.encode();
+		encoded_parameter.push(0);
+		assert!(Pass3d::encode_update_pallet_parameter_call(
+			encoded_parameter,
+			PASS3D_TO_PASS3DT_INDEX,
+		)
+		.is_err());
+	}
+}
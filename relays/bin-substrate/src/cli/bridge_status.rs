@@ -0,0 +1,532 @@
+// Copyright 2019-2022 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+	chains::{
+		millau_headers_to_rialto::MillauToRialtoCliBridge,
+		millau_headers_to_rialto_parachain::MillauToRialtoParachainCliBridge,
+		pass3d_headers_to_pass3dt::Pass3dToPass3dtCliBridge,
+		pass3dt_headers_to_pass3d::Pass3dtToPass3dCliBridge,
+		rialto_headers_to_millau::RialtoToMillauCliBridge,
+	},
+	cli::{
+		bridge::{CliBridgeBase, FullBridge},
+		chain_schema::*,
+		Balance, HexLaneId,
+	},
+};
+use async_trait::async_trait;
+use bp_header_chain::{
+	storage_keys::{best_finalized_key, current_authority_set_key, pallet_operating_mode_key},
+	AuthoritySet,
+};
+use bp_messages::{
+	storage_keys::{
+		inbound_lane_data_key, lane_operating_mode_key, operating_mode_key, outbound_lane_data_key,
+	},
+	InboundLaneData, LaneId, MessageNonce, MessagesOperatingMode, OutboundLaneData,
+};
+use bp_relayers::{RelayerRewardsKeyProvider, RELAYERS_PALLET_NAME};
+use bp_runtime::BasicOperatingMode;
+use relay_substrate_client::{
+	AccountIdOf, BalanceOf, BlockNumberOf, Chain, ChainWithGrandpa, ChainWithMessages, Client,
+	HashOf,
+};
+use serde::Serialize;
+use sp_runtime::traits::Header as HeaderT;
+use std::fmt::Display;
+use structopt::StructOpt;
+use strum::{EnumString, EnumVariantNames, VariantNames};
+
+/// Width of the label column in the table output.
+const LABEL_WIDTH: usize = 48;
+
+/// Print status of the bridge between two chains.
+#[derive(StructOpt, Debug, PartialEq)]
+pub struct BridgeStatus {
+	/// A bridge instance to inspect.
+	#[structopt(long, possible_values = FullBridge::VARIANTS, case_insensitive = true)]
+	bridge: FullBridge,
+	/// Hex-encoded identifiers of lanes to inspect.
+	#[structopt(long, default_value = "00000000")]
+	lane: Vec<HexLaneId>,
+	/// Output format.
+	#[structopt(long, possible_values = OutputFormat::VARIANTS, case_insensitive = true, default_value = "table")]
+	output: OutputFormat,
+	#[structopt(flatten)]
+	source: SourceConnectionParams,
+	#[structopt(flatten)]
+	target: TargetConnectionParams,
+}
+
+/// Format of the bridge status output.
+#[derive(Debug, EnumString, EnumVariantNames, Clone, Copy, PartialEq, Eq)]
+#[strum(serialize_all = "kebab_case")]
+pub enum OutputFormat {
+	/// Human-readable table.
+	Table,
+	/// JSON document.
+	Json,
+}
+
+/// Bridge status, read from both chains.
+#[derive(Debug, PartialEq, Serialize)]
+struct BridgeStatusReport {
+	/// Name of the source chain.
+	source_chain: String,
+	/// Name of the target chain.
+	target_chain: String,
+	/// Status of source headers at the target chain.
+	headers: HeadersStatus,
+	/// Operating mode of the GRANDPA pallet at the target chain.
+	grandpa_pallet_operating_mode: String,
+	/// Operating mode of the messages pallet at the source chain.
+	source_messages_pallet_operating_mode: String,
+	/// Operating mode of the messages pallet at the target chain.
+	target_messages_pallet_operating_mode: String,
+	/// Status of inspected lanes.
+	lanes: Vec<LaneStatus>,
+}
+
+/// Header number and hash.
+#[derive(Debug, PartialEq, Serialize)]
+struct HeaderStatus {
+	number: u64,
+	hash: String,
+}
+
+/// Status of source headers at the target chain.
+#[derive(Debug, PartialEq, Serialize)]
+struct HeadersStatus {
+	/// Best header of the source chain.
+	best_source_header: HeaderStatus,
+	/// Best finalized header of the source chain.
+	best_finalized_source_header: HeaderStatus,
+	/// Best finalized source header, imported by the GRANDPA pallet at the target chain.
+	///
+	/// It is `None` if the pallet is not yet initialized.
+	best_finalized_source_header_at_target: Option<HeaderStatus>,
+	/// Identifier of the source chain authority set, known to the target chain.
+	current_authority_set_id: Option<u64>,
+}
+
+/// Status of the single lane.
+#[derive(Debug, PartialEq, Serialize)]
+struct LaneStatus {
+	/// Hex-encoded lane identifier.
+	lane: String,
+	/// Operating mode of the outbound lane at the source chain.
+	source_lane_operating_mode: String,
+	/// Operating mode of the inbound lane at the target chain.
+	target_lane_operating_mode: String,
+	/// Nonce of the latest message, generated at the source chain.
+	latest_generated_nonce: MessageNonce,
+	/// Nonce of the latest message, which delivery has been confirmed at the source chain.
+	latest_received_nonce: MessageNonce,
+	/// Nonce of the oldest message that has not been pruned yet at the source chain.
+	oldest_unpruned_nonce: MessageNonce,
+	/// Nonce of the latest message, delivered to the target chain.
+	last_delivered_nonce: MessageNonce,
+	/// Nonce of the latest message, which delivery confirmation is known to the target chain.
+	last_confirmed_nonce: MessageNonce,
+	/// Unrewarded relayer entries of the inbound lane at the target chain.
+	unrewarded_relayers: Vec<UnrewardedRelayerStatus>,
+	/// Rewards that are registered at the source chain, but not yet claimed by relayers.
+	pending_rewards: Vec<RelayerRewardStatus>,
+}
+
+/// Unrewarded relayer entry.
+#[derive(Debug, PartialEq, Serialize)]
+struct UnrewardedRelayerStatus {
+	relayer: String,
+	begin: MessageNonce,
+	end: MessageNonce,
+}
+
+/// Pending reward of the relayer.
+#[derive(Debug, PartialEq, Serialize)]
+struct RelayerRewardStatus {
+	relayer: String,
+	reward: u128,
+}
+
+impl Display for BridgeStatusReport {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		fn row(
+			f: &mut std::fmt::Formatter,
+			label: String,
+			value: impl Display,
+		) -> std::fmt::Result {
+			writeln!(f, "  {:<width$} {}", label, value, width = LABEL_WIDTH)
+		}
+
+		let (source, target) = (&self.source_chain, &self.target_chain);
+		let headers = &self.headers;
+		writeln!(f, "{} -> {} bridge status", source, target)?;
+		writeln!(f, "Headers:")?;
+		row(f, format!("best {} header", source), &headers.best_source_header)?;
+		row(f, format!("best finalized {} header", source), &headers.best_finalized_source_header)?;
+		row(
+			f,
+			format!("best finalized {} header at {}", source, target),
+			headers
+				.best_finalized_source_header_at_target
+				.as_ref()
+				.map(|header| header.to_string())
+				.unwrap_or_else(|| "<not initialized>".into()),
+		)?;
+		row(
+			f,
+			format!("{} authority set id at {}", source, target),
+			headers
+				.current_authority_set_id
+				.map(|set_id| set_id.to_string())
+				.unwrap_or_else(|| "<not initialized>".into()),
+		)?;
+
+		writeln!(f, "Operating modes:")?;
+		row(f, format!("GRANDPA pallet at {}", target), &self.grandpa_pallet_operating_mode)?;
+		row(
+			f,
+			format!("messages pallet at {}", source),
+			&self.source_messages_pallet_operating_mode,
+		)?;
+		row(
+			f,
+			format!("messages pallet at {}", target),
+			&self.target_messages_pallet_operating_mode,
+		)?;
+
+		for lane in &self.lanes {
+			writeln!(f, "Lane {}:", lane.lane)?;
+			row(f, format!("operating mode at {}", source), &lane.source_lane_operating_mode)?;
+			row(f, format!("operating mode at {}", target), &lane.target_lane_operating_mode)?;
+			row(f, format!("latest generated nonce at {}", source), lane.latest_generated_nonce)?;
+			row(f, format!("latest received nonce at {}", source), lane.latest_received_nonce)?;
+			row(f, format!("oldest unpruned nonce at {}", source), lane.oldest_unpruned_nonce)?;
+			row(f, format!("last delivered nonce at {}", target), lane.last_delivered_nonce)?;
+			row(f, format!("last confirmed nonce at {}", target), lane.last_confirmed_nonce)?;
+			row(f, "unrewarded relayer entries".into(), lane.unrewarded_relayers.len())?;
+			for entry in &lane.unrewarded_relayers {
+				row(f, format!("  {}", entry.relayer), format!("{}..={}", entry.begin, entry.end))?;
+			}
+			row(f, format!("pending rewards at {}", source), lane.pending_rewards.len())?;
+			for reward in &lane.pending_rewards {
+				row(f, format!("  {}", reward.relayer), Balance(reward.reward))?;
+			}
+		}
+
+		Ok(())
+	}
+}
+
+impl Display for HeaderStatus {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(f, "#{} ({})", self.number, self.hash)
+	}
+}
+
+impl HeaderStatus {
+	fn new<C: Chain>(number: BlockNumberOf<C>, hash: HashOf<C>) -> Self {
+		HeaderStatus { number: number.into(), hash: format!("{:?}", hash) }
+	}
+}
+
+#[async_trait]
+trait BridgeStatusReader: CliBridgeBase
+where
+	Self::Source: ChainWithGrandpa + ChainWithMessages,
+	Self::Target: ChainWithMessages,
+	AccountIdOf<Self::Source>: From<AccountId32>,
+	BalanceOf<Self::Source>: Into<u128>,
+{
+	async fn bridge_status(data: BridgeStatus) -> anyhow::Result<()> {
+		let source_client = data.source.into_client::<Self::Source>().await?;
+		let target_client = data.target.into_client::<Self::Target>().await?;
+
+		let best_source_header = source_client.best_header().await?;
+		let best_finalized_source_header = source_client.best_finalized_header().await?;
+		let best_finalized_source_header_at_target: Option<(
+			BlockNumberOf<Self::Source>,
+			HashOf<Self::Source>,
+		)> = target_client
+			.storage_value(best_finalized_key(Self::Source::WITH_CHAIN_GRANDPA_PALLET_NAME), None)
+			.await?;
+		let current_authority_set: Option<AuthoritySet> = target_client
+			.storage_value(
+				current_authority_set_key(Self::Source::WITH_CHAIN_GRANDPA_PALLET_NAME),
+				None,
+			)
+			.await?;
+		let grandpa_pallet_operating_mode: Option<BasicOperatingMode> = target_client
+			.storage_value(
+				pallet_operating_mode_key(Self::Source::WITH_CHAIN_GRANDPA_PALLET_NAME),
+				None,
+			)
+			.await?;
+		let source_messages_pallet_operating_mode: Option<MessagesOperatingMode> = source_client
+			.storage_value(operating_mode_key(Self::Target::WITH_CHAIN_MESSAGES_PALLET_NAME), None)
+			.await?;
+		let target_messages_pallet_operating_mode: Option<MessagesOperatingMode> = target_client
+			.storage_value(operating_mode_key(Self::Source::WITH_CHAIN_MESSAGES_PALLET_NAME), None)
+			.await?;
+
+		let mut lanes = Vec::with_capacity(data.lane.len());
+		for lane in data.lane {
+			lanes.push(Self::lane_status(&source_client, &target_client, lane.into()).await?);
+		}
+
+		let report = BridgeStatusReport {
+			source_chain: Self::Source::NAME.into(),
+			target_chain: Self::Target::NAME.into(),
+			headers: HeadersStatus {
+				best_source_header: HeaderStatus::new::<Self::Source>(
+					*best_source_header.number(),
+					best_source_header.hash(),
+				),
+				best_finalized_source_header: HeaderStatus::new::<Self::Source>(
+					*best_finalized_source_header.number(),
+					best_finalized_source_header.hash(),
+				),
+				best_finalized_source_header_at_target: best_finalized_source_header_at_target
+					.map(|(number, hash)| HeaderStatus::new::<Self::Source>(number, hash)),
+				current_authority_set_id: current_authority_set
+					.map(|authority_set| authority_set.set_id),
+			},
+			grandpa_pallet_operating_mode: format!(
+				"{:?}",
+				grandpa_pallet_operating_mode.unwrap_or_default()
+			),
+			source_messages_pallet_operating_mode: format!(
+				"{:?}",
+				source_messages_pallet_operating_mode.unwrap_or_default()
+			),
+			target_messages_pallet_operating_mode: format!(
+				"{:?}",
+				target_messages_pallet_operating_mode.unwrap_or_default()
+			),
+			lanes,
+		};
+
+		match data.output {
+			OutputFormat::Table => print!("{}", report),
+			OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+		}
+
+		Ok(())
+	}
+
+	/// Read status of the single lane from both chains.
+	async fn lane_status(
+		source_client: &Client<Self::Source>,
+		target_client: &Client<Self::Target>,
+		lane: LaneId,
+	) -> anyhow::Result<LaneStatus> {
+		let source_lane_operating_mode: Option<MessagesOperatingMode> = source_client
+			.storage_value(
+				lane_operating_mode_key(Self::Target::WITH_CHAIN_MESSAGES_PALLET_NAME, &lane),
+				None,
+			)
+			.await?;
+		let target_lane_operating_mode: Option<MessagesOperatingMode> = target_client
+			.storage_value(
+				lane_operating_mode_key(Self::Source::WITH_CHAIN_MESSAGES_PALLET_NAME, &lane),
+				None,
+			)
+			.await?;
+		let outbound_lane_data: OutboundLaneData = source_client
+			.storage_value(
+				outbound_lane_data_key(Self::Target::WITH_CHAIN_MESSAGES_PALLET_NAME, &lane),
+				None,
+			)
+			.await?
+			.unwrap_or_default();
+		let inbound_lane_data: InboundLaneData<AccountIdOf<Self::Source>> = target_client
+			.storage_value(
+				inbound_lane_data_key(Self::Source::WITH_CHAIN_MESSAGES_PALLET_NAME, &lane),
+				None,
+			)
+			.await?
+			.unwrap_or_default();
+
+		let pending_rewards = source_client
+			.storage_double_map_values::<RelayerRewardsKeyProvider<
+				AccountIdOf<Self::Source>,
+				BalanceOf<Self::Source>,
+			>>(RELAYERS_PALLET_NAME, &lane, None)
+			.await?
+			.into_iter()
+			.map(|(relayer, reward)| RelayerRewardStatus {
+				relayer: relayer.to_string(),
+				reward: reward.into(),
+			})
+			.collect();
+
+		Ok(LaneStatus {
+			lane: hex::encode(lane),
+			source_lane_operating_mode: format!(
+				"{:?}",
+				source_lane_operating_mode.unwrap_or_default()
+			),
+			target_lane_operating_mode: format!(
+				"{:?}",
+				target_lane_operating_mode.unwrap_or_default()
+			),
+			latest_generated_nonce: outbound_lane_data.latest_generated_nonce,
+			latest_received_nonce: outbound_lane_data.latest_received_nonce,
+			oldest_unpruned_nonce: outbound_lane_data.oldest_unpruned_nonce,
+			last_delivered_nonce: inbound_lane_data.last_delivered_nonce(),
+			last_confirmed_nonce: inbound_lane_data.last_confirmed_nonce,
+			unrewarded_relayers: inbound_lane_data
+				.relayers
+				.iter()
+				.map(|entry| UnrewardedRelayerStatus {
+					relayer: entry.relayer.to_string(),
+					begin: entry.messages.begin,
+					end: entry.messages.end,
+				})
+				.collect(),
+			pending_rewards,
+		})
+	}
+}
+
+impl BridgeStatusReader for MillauToRialtoCliBridge {}
+impl BridgeStatusReader for RialtoToMillauCliBridge {}
+impl BridgeStatusReader for MillauToRialtoParachainCliBridge {}
+impl BridgeStatusReader for Pass3dtToPass3dCliBridge {}
+impl BridgeStatusReader for Pass3dToPass3dtCliBridge {}
+
+impl BridgeStatus {
+	/// Run the command.
+	pub async fn run(self) -> anyhow::Result<()> {
+		match self.bridge {
+			FullBridge::MillauToRialto => MillauToRialtoCliBridge::bridge_status(self),
+			FullBridge::RialtoToMillau => RialtoToMillauCliBridge::bridge_status(self),
+			FullBridge::MillauToRialtoParachain =>
+				MillauToRialtoParachainCliBridge::bridge_status(self),
+			FullBridge::RialtoParachainToMillau => {
+				anyhow::bail!(
+					"Bridge status is not supported for the {:?} bridge: headers of the source \
+					parachain are not finalized by the GRANDPA pallet",
+					self.bridge,
+				)
+			},
+			FullBridge::Pass3dtToPass3d => Pass3dtToPass3dCliBridge::bridge_status(self),
+			FullBridge::Pass3dToPass3dt => Pass3dToPass3dtCliBridge::bridge_status(self),
+		}
+		.await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn test_report() -> BridgeStatusReport {
+		BridgeStatusReport {
+			source_chain: "Pass3d".into(),
+			target_chain: "Pass3dt".into(),
+			headers: HeadersStatus {
+				best_source_header: HeaderStatus { number: 105, hash: "0x05".into() },
+				best_finalized_source_header: HeaderStatus { number: 100, hash: "0x00".into() },
+				best_finalized_source_header_at_target: Some(HeaderStatus {
+					number: 90,
+					hash: "0x90".into(),
+				}),
+				current_authority_set_id: Some(3),
+			},
+			grandpa_pallet_operating_mode: "Normal".into(),
+			source_messages_pallet_operating_mode: "Basic(Normal)".into(),
+			target_messages_pallet_operating_mode: "RejectingOutboundMessages".into(),
+			lanes: vec![LaneStatus {
+				lane: "00000000".into(),
+				source_lane_operating_mode: "Basic(Normal)".into(),
+				target_lane_operating_mode: "Basic(Halted)".into(),
+				latest_generated_nonce: 10,
+				latest_received_nonce: 5,
+				oldest_unpruned_nonce: 3,
+				last_delivered_nonce: 8,
+				last_confirmed_nonce: 5,
+				unrewarded_relayers: vec![UnrewardedRelayerStatus {
+					relayer: "relayer".into(),
+					begin: 6,
+					end: 8,
+				}],
+				pending_rewards: vec![RelayerRewardStatus {
+					relayer: "relayer".into(),
+					reward: 1_000_000,
+				}],
+			}],
+		}
+	}
+
+	#[test]
+	fn bridge_status_is_parsed() {
+		let status = BridgeStatus::from_iter(vec![
+			"bridge-status",
+			"--bridge",
+			"pass3d-to-pass3dt",
+			"--lane",
+			"00000000",
+			"--lane",
+			"73776170",
+			"--output",
+			"json",
+			"--source-host",
+			"pass3d-node",
+			"--target-host",
+			"pass3dt-node",
+		]);
+
+		assert_eq!(status.bridge, FullBridge::Pass3dToPass3dt);
+		assert_eq!(status.lane, vec![HexLaneId(*b"\0\0\0\0"), HexLaneId(*b"swap")]);
+		assert_eq!(status.output, OutputFormat::Json);
+	}
+
+	#[test]
+	fn report_is_formatted_as_table() {
+		let table = test_report().to_string();
+
+		assert!(table.starts_with("Pass3d -> Pass3dt bridge status\n"));
+		assert!(table.contains("best finalized Pass3d header at Pass3dt"));
+		assert!(table.contains("#90 (0x90)\n"));
+		assert!(table.contains("Pass3d authority set id at Pass3dt"));
+		assert!(table.contains("Lane 00000000:\n"));
+		assert!(table.contains("6..=8\n"));
+		assert!(table.contains("1,000,000\n"));
+	}
+
+	#[test]
+	fn report_is_serialized_to_json() {
+		let json = serde_json::to_value(test_report()).unwrap();
+
+		assert_eq!(json["headers"]["best_finalized_source_header_at_target"]["number"], 90);
+		assert_eq!(json["headers"]["current_authority_set_id"], 3);
+		assert_eq!(json["lanes"][0]["last_delivered_nonce"], 8);
+		assert_eq!(json["lanes"][0]["unrewarded_relayers"][0]["relayer"], "relayer");
+		assert_eq!(json["lanes"][0]["pending_rewards"][0]["reward"], 1_000_000);
+	}
+
+	#[test]
+	fn uninitialized_bridge_is_formatted_as_table() {
+		let mut report = test_report();
+		report.headers.best_finalized_source_header_at_target = None;
+		report.headers.current_authority_set_id = None;
+
+		assert!(report.to_string().contains("<not initialized>\n"));
+	}
+}
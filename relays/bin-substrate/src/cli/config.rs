@@ -0,0 +1,882 @@
+// Copyright 2019-2022 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Declarative relay configuration file support.
+//!
+//! The `--config <file>` option may be passed to the `relay-headers`, `relay-messages`,
+//! `relay-headers-and-messages` and `init-bridge` subcommands. The file (TOML or YAML,
+//! detected by the file extension) describes chains and bridges:
+//!
+//! ```toml
+//! [chains.pass3dt]
+//! host = "127.0.0.1"
+//! port = 9945
+//! signer = "//Alice"
+//! transactions_mortality = 64
+//!
+//! [chains.pass3d]
+//! host = "127.0.0.1"
+//! port = 9944
+//! signer_file = "/run/secrets/pass3d-signer"
+//!
+//! [bridges.pass3dt-pass3d]
+//! lanes = ["00000000", "00000002"]
+//! lane_priorities = { "00000002" = 10 }
+//! relayer_mode = "altruistic"
+//!
+//! [prometheus]
+//! host = "0.0.0.0"
+//! port = 9616
+//! ```
+//!
+//! Values from the file are converted into regular command line options of the subcommand,
+//! so every value may be overridden by the command line option. Flags that are set in the
+//! file (`secure` and `only_mandatory_headers`) may be disabled with the `--no-<flag>` option
+//! (e.g. `--no-pass3d-secure` or `--no-only-mandatory-headers`).
+
+use crate::cli::{
+	bridge::FullBridge, chain_schema::RuntimeVersionType, init_bridge::InitBridgeName,
+	relay_headers::RelayHeadersBridge, relay_messages::RelayerMode, HexLaneId,
+};
+
+use serde::Deserialize;
+use std::{
+	collections::{BTreeMap, HashSet},
+	path::{Path, PathBuf},
+	str::FromStr,
+};
+use strum::VariantNames;
+
+/// Name of the command line option with path to the configuration file.
+const CONFIG_OPTION: &str = "--config";
+
+/// Help message of subcommands that are supporting the configuration file.
+pub const CONFIG_HELP: &str = "This subcommand also accepts the `--config <file>` option with \
+	path to the TOML or YAML relay configuration file. Command line options are overriding \
+	values from the file. Flags that are set in the file may be disabled with the \
+	`--no-<flag>` option.";
+
+/// Complex relays, supported by the `relay-headers-and-messages` subcommand: bridge name, left
+/// chain, right chain and (optional) relay chain of the right parachain.
+const COMPLEX_RELAYS: &[(&str, &str, &str, Option<&str>)] = &[
+	("millau-rialto", "millau", "rialto", None),
+	("millau-rialto-parachain", "millau", "rialto-parachain", Some("rialto")),
+	("pass3dt-pass3d", "pass3dt", "pass3d", None),
+];
+
+/// Relay configuration file.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RelayConfig {
+	/// Chains, referenced by their command line names (e.g. `rialto-parachain`).
+	#[serde(default)]
+	pub chains: BTreeMap<String, ChainConfig>,
+	/// Bridges, referenced by the bridge name of the subcommand (e.g. `pass3dt-pass3d` or
+	/// `pass3dt-to-pass3d`).
+	#[serde(default)]
+	pub bridges: BTreeMap<String, BridgeConfig>,
+	/// Prometheus endpoint configuration.
+	#[serde(default)]
+	pub prometheus: Option<PrometheusConfig>,
+}
+
+/// Chain configuration.
+///
+/// Field names are matching names of chain-specific command line options.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ChainConfig {
+	/// Chain node host.
+	pub host: Option<String>,
+	/// Chain node websocket port.
+	pub port: Option<u16>,
+	/// Use secure websocket connection.
+	#[serde(default)]
+	pub secure: bool,
+	/// Runtime version mode: `Auto`, `Custom` or `Bundle`.
+	pub version_mode: Option<String>,
+	/// Custom `spec_version`.
+	pub spec_version: Option<u32>,
+	/// Custom `transaction_version`.
+	pub transaction_version: Option<u32>,
+	/// The SURI of transactions signer.
+	pub signer: Option<String>,
+	/// The password for the signer SURI.
+	pub signer_password: Option<String>,
+	/// Path to the file with the signer SURI.
+	pub signer_file: Option<PathBuf>,
+	/// Path to the file with the password for the signer SURI.
+	pub signer_password_file: Option<PathBuf>,
+	/// Transactions mortality period, in blocks.
+	pub transactions_mortality: Option<u32>,
+	/// The SURI of the messages pallet owner.
+	pub messages_pallet_owner: Option<String>,
+	/// The password for the messages pallet owner SURI.
+	pub messages_pallet_owner_password: Option<String>,
+}
+
+/// Bridge configuration.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct BridgeConfig {
+	/// Hex-encoded identifiers of lanes that are served by the relay.
+	#[serde(default)]
+	pub lanes: Vec<String>,
+	/// Priorities of lanes, keyed by hex-encoded lane identifiers.
+	#[serde(default)]
+	pub lane_priorities: BTreeMap<String, u32>,
+	/// Relayer mode: `altruistic` or `rational`.
+	pub relayer_mode: Option<String>,
+	/// Relay only mandatory headers.
+	#[serde(default)]
+	pub only_mandatory_headers: bool,
+}
+
+/// Prometheus endpoint configuration.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PrometheusConfig {
+	/// Expose Prometheus endpoint.
+	#[serde(default = "default_prometheus_enabled")]
+	pub enabled: bool,
+	/// Prometheus endpoint interface.
+	pub host: Option<String>,
+	/// Prometheus endpoint port.
+	pub port: Option<u16>,
+}
+
+fn default_prometheus_enabled() -> bool {
+	true
+}
+
+/// Group of chain-specific command line options.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ChainOptions {
+	/// Connection options.
+	Connection,
+	/// Transactions signing options.
+	Signing,
+	/// Messages pallet owner signing options.
+	MessagesPalletOwner,
+}
+
+/// Command line option, generated from the configuration file.
+type ConfigOption = (String, Option<String>);
+
+/// If command line arguments contain `--config <file>` option, replace it with options, read
+/// from the file. Options that are explicitly specified in the command line are not overridden.
+pub fn expand_config_file_args(args: Vec<String>) -> anyhow::Result<Vec<String>> {
+	match take_config_path(args)? {
+		(Some(config_path), args) => apply_config(&RelayConfig::read(&config_path)?, args),
+		(None, args) => Ok(args),
+	}
+}
+
+/// Insert options from the configuration into command line arguments.
+fn apply_config(config: &RelayConfig, mut args: Vec<String>) -> anyhow::Result<Vec<String>> {
+	// we expect `substrate-relay <subcommand> <bridge> [options]`
+	let (subcommand, bridge) = match (args.get(1), args.get(2)) {
+		(Some(subcommand), Some(bridge))
+			if !subcommand.starts_with('-') && !bridge.starts_with('-') =>
+			(subcommand.clone(), bridge.to_lowercase()),
+		_ =>
+			return Err(anyhow::format_err!(
+				"The {} option requires subcommand and bridge name to be specified",
+				CONFIG_OPTION,
+			)),
+	};
+
+	// `--no-<flag>` options are only disabling flags from the file, so they're removed here
+	let mut cli_args = args.split_off(3);
+	let mut cli_options = HashSet::new();
+	cli_args.retain(|arg| {
+		if !arg.starts_with("--") {
+			return true
+		}
+
+		let option = arg.split('=').next().unwrap_or(arg);
+		match option.strip_prefix("--no-").map(|flag| format!("--{}", flag)) {
+			Some(flag) if is_negatable_flag(&flag) => {
+				cli_options.insert(flag);
+				false
+			},
+			_ => {
+				cli_options.insert(option.to_owned());
+				true
+			},
+		}
+	});
+	let config_args = config
+		.subcommand_options(&subcommand, &bridge)?
+		.into_iter()
+		.filter(|(option, _)| !cli_options.contains(option))
+		.flat_map(|(option, value)| std::iter::once(option).chain(value));
+	args.extend(config_args);
+	args.extend(cli_args);
+
+	Ok(args)
+}
+
+/// Remove `--config <file>` (or `--config=<file>`) option from arguments.
+fn take_config_path(args: Vec<String>) -> anyhow::Result<(Option<PathBuf>, Vec<String>)> {
+	let mut config_path = None;
+	let mut other_args = Vec::with_capacity(args.len());
+	let mut args = args.into_iter();
+	while let Some(arg) = args.next() {
+		let path = if arg == CONFIG_OPTION {
+			args.next().ok_or_else(|| {
+				anyhow::format_err!("The {} option requires a value", CONFIG_OPTION)
+			})?
+		} else if let Some(path) = arg.strip_prefix(CONFIG_OPTION).and_then(|s| s.strip_prefix('='))
+		{
+			path.to_owned()
+		} else {
+			other_args.push(arg);
+			continue
+		};
+
+		if config_path.replace(PathBuf::from(path)).is_some() {
+			return Err(anyhow::format_err!("The {} option may only be used once", CONFIG_OPTION))
+		}
+	}
+
+	Ok((config_path, other_args))
+}
+
+impl RelayConfig {
+	/// Read and validate configuration from the file.
+	pub fn read(path: &Path) -> anyhow::Result<Self> {
+		let content = std::fs::read_to_string(path).map_err(|e| {
+			anyhow::format_err!("Failed to read relay configuration file {:?}: {}", path, e)
+		})?;
+		let extension = path.extension().and_then(|extension| extension.to_str());
+		let config = match extension {
+			Some("toml") => Self::from_toml(&content),
+			Some("yaml") | Some("yml") => Self::from_yaml(&content),
+			_ => Err(anyhow::format_err!(
+				"Unknown relay configuration file format. Expected .toml, .yaml or .yml file",
+			)),
+		};
+		config
+			.map_err(|e| anyhow::format_err!("Invalid relay configuration file {:?}: {}", path, e))
+	}
+
+	/// Parse and validate TOML configuration.
+	pub fn from_toml(content: &str) -> anyhow::Result<Self> {
+		let mut deserializer = toml::Deserializer::new(content);
+		let config: Self = serde_path_to_error::deserialize(&mut deserializer)
+			.map_err(|e| anyhow::format_err!("{}: {}", e.path(), e.inner()))?;
+		config.validate()?;
+		Ok(config)
+	}
+
+	/// Parse and validate YAML configuration.
+	pub fn from_yaml(content: &str) -> anyhow::Result<Self> {
+		let deserializer = serde_yaml::Deserializer::from_str(content);
+		let config: Self = serde_path_to_error::deserialize(deserializer)
+			.map_err(|e| anyhow::format_err!("{}: {}", e.path(), e.inner()))?;
+		config.validate()?;
+		Ok(config)
+	}
+
+	/// Validate values that can't be checked by the deserializer.
+	fn validate(&self) -> anyhow::Result<()> {
+		for (name, chain) in &self.chains {
+			if !is_known_chain(name) {
+				return Err(config_error(format!("chains.{}", name), "unknown chain"))
+			}
+			if let Some(ref version_mode) = chain.version_mode {
+				let version_mode = RuntimeVersionType::from_str(version_mode).map_err(|_| {
+					config_error(
+						format!("chains.{}.version_mode", name),
+						"expected one of `Auto`, `Custom` or `Bundle`",
+					)
+				})?;
+				if version_mode == RuntimeVersionType::Custom {
+					if chain.spec_version.is_none() {
+						return Err(config_error(
+							format!("chains.{}.spec_version", name),
+							"required when `version_mode` is `Custom`",
+						))
+					}
+					if chain.transaction_version.is_none() {
+						return Err(config_error(
+							format!("chains.{}.transaction_version", name),
+							"required when `version_mode` is `Custom`",
+						))
+					}
+				}
+			}
+			if let Some(transactions_mortality) = chain.transactions_mortality {
+				if !(4..=65536).contains(&transactions_mortality) ||
+					!transactions_mortality.is_power_of_two()
+				{
+					return Err(config_error(
+						format!("chains.{}.transactions_mortality", name),
+						"expected a power of two in a [4; 65536] range",
+					))
+				}
+			}
+		}
+
+		for (name, bridge) in &self.bridges {
+			if !is_known_bridge(name) {
+				return Err(config_error(format!("bridges.{}", name), "unknown bridge"))
+			}
+			for (index, lane) in bridge.lanes.iter().enumerate() {
+				HexLaneId::from_str(lane)
+					.map_err(|e| config_error(format!("bridges.{}.lanes[{}]", name, index), e))?;
+			}
+			for lane in bridge.lane_priorities.keys() {
+				HexLaneId::from_str(lane).map_err(|e| {
+					config_error(format!("bridges.{}.lane_priorities.{}", name, lane), e)
+				})?;
+			}
+			if let Some(ref relayer_mode) = bridge.relayer_mode {
+				RelayerMode::from_str(relayer_mode).map_err(|_| {
+					config_error(
+						format!("bridges.{}.relayer_mode", name),
+						"expected one of `altruistic` or `rational`",
+					)
+				})?;
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Returns command line options for given subcommand and bridge.
+	fn subcommand_options(
+		&self,
+		subcommand: &str,
+		bridge: &str,
+	) -> anyhow::Result<Vec<ConfigOption>> {
+		let mut options = Vec::new();
+		match subcommand {
+			"relay-headers" => {
+				let (source, target) = split_one_way_bridge_name(bridge)?;
+				self.chain_options(&mut options, source, "source", &[ChainOptions::Connection]);
+				self.chain_options(
+					&mut options,
+					target,
+					"target",
+					&[ChainOptions::Connection, ChainOptions::Signing],
+				);
+				if let Some(bridge_config) = self.bridges.get(bridge) {
+					push_flag(
+						&mut options,
+						"only-mandatory-headers",
+						bridge_config.only_mandatory_headers,
+					);
+				}
+				self.prometheus_options(&mut options);
+			},
+			"init-bridge" => {
+				let (source, target) = split_one_way_bridge_name(bridge)?;
+				self.chain_options(&mut options, source, "source", &[ChainOptions::Connection]);
+				self.chain_options(
+					&mut options,
+					target,
+					"target",
+					&[ChainOptions::Connection, ChainOptions::Signing],
+				);
+			},
+			"relay-messages" => {
+				let (source, target) = split_one_way_bridge_name(bridge)?;
+				let chain_options = [ChainOptions::Connection, ChainOptions::Signing];
+				self.chain_options(&mut options, source, "source", &chain_options);
+				self.chain_options(&mut options, target, "target", &chain_options);
+				if let Some(bridge_config) = self.bridges.get(bridge) {
+					match bridge_config.lanes.len() {
+						0 => (),
+						1 => push_option(&mut options, "lane", Some(&bridge_config.lanes[0])),
+						_ =>
+							return Err(config_error(
+								format!("bridges.{}.lanes", bridge),
+								"relay-messages subcommand serves a single lane",
+							)),
+					}
+					push_option(&mut options, "relayer-mode", bridge_config.relayer_mode.as_ref());
+				}
+				self.prometheus_options(&mut options);
+			},
+			"relay-headers-and-messages" => {
+				let (left, right, right_relay) = COMPLEX_RELAYS
+					.iter()
+					.find(|(name, _, _, _)| *name == bridge)
+					.map(|(_, left, right, right_relay)| (*left, *right, *right_relay))
+					.ok_or_else(|| {
+						anyhow::format_err!("Unknown relay-headers-and-messages bridge: {}", bridge)
+					})?;
+				let chain_options = [
+					ChainOptions::Connection,
+					ChainOptions::Signing,
+					ChainOptions::MessagesPalletOwner,
+				];
+				self.chain_options(&mut options, left, left, &chain_options);
+				self.chain_options(&mut options, right, right, &chain_options);
+				if let Some(right_relay) = right_relay {
+					self.chain_options(
+						&mut options,
+						right_relay,
+						right_relay,
+						&[ChainOptions::Connection],
+					);
+				}
+				if let Some(bridge_config) = self.bridges.get(bridge) {
+					for lane in &bridge_config.lanes {
+						push_option(&mut options, "lane", Some(lane));
+					}
+					for (lane, priority) in &bridge_config.lane_priorities {
+						push_option(
+							&mut options,
+							"lane-priority",
+							Some(format!("{}={}", lane, priority)),
+						);
+					}
+					push_option(&mut options, "relayer-mode", bridge_config.relayer_mode.as_ref());
+					push_flag(
+						&mut options,
+						"only-mandatory-headers",
+						bridge_config.only_mandatory_headers,
+					);
+				}
+				self.prometheus_options(&mut options);
+			},
+			_ =>
+				return Err(anyhow::format_err!(
+					"The {} option is not supported by the {} subcommand",
+					CONFIG_OPTION,
+					subcommand,
+				)),
+		}
+
+		Ok(options)
+	}
+
+	/// Push options of given chain, if it is present in the configuration.
+	fn chain_options(
+		&self,
+		options: &mut Vec<ConfigOption>,
+		chain: &str,
+		prefix: &str,
+		groups: &[ChainOptions],
+	) {
+		let chain = match self.chains.get(chain) {
+			Some(chain) => chain,
+			None => return,
+		};
+
+		let option = |name: &str| format!("{}-{}", prefix, name);
+		for group in groups {
+			match *group {
+				ChainOptions::Connection => {
+					push_option(options, &option("host"), chain.host.as_ref());
+					push_option(options, &option("port"), chain.port.as_ref());
+					push_flag(options, &option("secure"), chain.secure);
+					push_option(options, &option("version-mode"), chain.version_mode.as_ref());
+					push_option(options, &option("spec-version"), chain.spec_version.as_ref());
+					push_option(
+						options,
+						&option("transaction-version"),
+						chain.transaction_version.as_ref(),
+					);
+				},
+				ChainOptions::Signing => {
+					push_option(options, &option("signer"), chain.signer.as_ref());
+					push_option(
+						options,
+						&option("signer-password"),
+						chain.signer_password.as_ref(),
+					);
+					push_option(
+						options,
+						&option("signer-file"),
+						chain.signer_file.as_ref().map(|path| path.display()),
+					);
+					push_option(
+						options,
+						&option("signer-password-file"),
+						chain.signer_password_file.as_ref().map(|path| path.display()),
+					);
+					push_option(
+						options,
+						&option("transactions-mortality"),
+						chain.transactions_mortality.as_ref(),
+					);
+				},
+				ChainOptions::MessagesPalletOwner => {
+					push_option(
+						options,
+						&option("messages-pallet-owner"),
+						chain.messages_pallet_owner.as_ref(),
+					);
+					push_option(
+						options,
+						&option("messages-pallet-owner-password"),
+						chain.messages_pallet_owner_password.as_ref(),
+					);
+				},
+			}
+		}
+	}
+
+	/// Push Prometheus endpoint options.
+	fn prometheus_options(&self, options: &mut Vec<ConfigOption>) {
+		if let Some(ref prometheus) = self.prometheus {
+			push_flag(options, "no-prometheus", !prometheus.enabled);
+			push_option(options, "prometheus-host", prometheus.host.as_ref());
+			push_option(options, "prometheus-port", prometheus.port.as_ref());
+		}
+	}
+}
+
+/// Returns names of one-way bridges, supported by the `relay-headers`, `relay-messages` and
+/// `init-bridge` subcommands.
+fn one_way_bridges() -> impl Iterator<Item = &'static str> {
+	RelayHeadersBridge::VARIANTS
+		.iter()
+		.chain(FullBridge::VARIANTS)
+		.chain(InitBridgeName::VARIANTS)
+		.copied()
+}
+
+/// Returns true if bridge with given name may be configured in the `bridges` section.
+fn is_known_bridge(bridge: &str) -> bool {
+	COMPLEX_RELAYS.iter().any(|(name, _, _, _)| *name == bridge) ||
+		one_way_bridges().any(|name| name == bridge)
+}
+
+/// Returns true if chain with given name may be configured in the `chains` section.
+fn is_known_chain(chain: &str) -> bool {
+	COMPLEX_RELAYS.iter().any(|(_, left, right, right_relay)| {
+		*left == chain || *right == chain || *right_relay == Some(chain)
+	}) || one_way_bridges().any(|bridge| {
+		matches!(
+			split_one_way_bridge_name(bridge),
+			Ok((source, target)) if source == chain || target == chain
+		)
+	})
+}
+
+/// Returns true if given flag may be set in the configuration file and disabled with the
+/// `--no-<flag>` command line option.
+fn is_negatable_flag(flag: &str) -> bool {
+	flag == "--only-mandatory-headers" || flag.ends_with("-secure")
+}
+
+/// Split `<source>-to-<target>` bridge name into chain names.
+fn split_one_way_bridge_name(bridge: &str) -> anyhow::Result<(&str, &str)> {
+	bridge
+		.split_once("-to-")
+		.ok_or_else(|| anyhow::format_err!("Unexpected bridge name: {}", bridge))
+}
+
+/// Push option with value, if the value is defined.
+fn push_option(options: &mut Vec<ConfigOption>, name: &str, value: Option<impl ToString>) {
+	if let Some(value) = value {
+		options.push((format!("--{}", name), Some(value.to_string())));
+	}
+}
+
+/// Push flag option, if it is set.
+fn push_flag(options: &mut Vec<ConfigOption>, name: &str, is_set: bool) {
+	if is_set {
+		options.push((format!("--{}", name), None));
+	}
+}
+
+/// Returns error that is pointing to given configuration key.
+fn config_error(key: String, error: impl std::fmt::Display) -> anyhow::Error {
+	anyhow::format_err!("{}: {}", key, error)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const TOML_CONFIG: &str = r#"
+		[chains.pass3dt]
+		host = "pass3dt-node"
+		port = 9945
+		signer = "//Alice"
+		transactions_mortality = 64
+
+		[chains.pass3d]
+		host = "pass3d-node"
+		secure = true
+		signer_file = "/run/secrets/pass3d"
+
+		[bridges.pass3dt-pass3d]
+		lanes = ["00000000", "00000002"]
+		lane_priorities = { "00000002" = 10 }
+		relayer_mode = "altruistic"
+
+		[bridges.pass3dt-to-pass3d]
+		lanes = ["00000002"]
+
+		[prometheus]
+		host = "0.0.0.0"
+	"#;
+
+	fn args(args: &[&str]) -> Vec<String> {
+		args.iter().map(|arg| arg.to_string()).collect()
+	}
+
+	fn expand(config: &str, cli_args: &[&str]) -> anyhow::Result<Vec<String>> {
+		apply_config(&RelayConfig::from_toml(config)?, args(cli_args))
+	}
+
+	#[test]
+	fn complex_relay_options_are_read_from_config() {
+		assert_eq!(
+			expand(
+				TOML_CONFIG,
+				&["substrate-relay", "relay-headers-and-messages", "pass3dt-pass3d"]
+			)
+			.unwrap(),
+			args(&[
+				"substrate-relay",
+				"relay-headers-and-messages",
+				"pass3dt-pass3d",
+				"--pass3dt-host",
+				"pass3dt-node",
+				"--pass3dt-port",
+				"9945",
+				"--pass3dt-signer",
+				"//Alice",
+				"--pass3dt-transactions-mortality",
+				"64",
+				"--pass3d-host",
+				"pass3d-node",
+				"--pass3d-secure",
+				"--pass3d-signer-file",
+				"/run/secrets/pass3d",
+				"--lane",
+				"00000000",
+				"--lane",
+				"00000002",
+				"--lane-priority",
+				"00000002=10",
+				"--relayer-mode",
+				"altruistic",
+				"--prometheus-host",
+				"0.0.0.0",
+			]),
+		);
+	}
+
+	#[test]
+	fn one_way_relay_options_are_read_from_config() {
+		assert_eq!(
+			expand(TOML_CONFIG, &["substrate-relay", "relay-messages", "pass3dt-to-pass3d"])
+				.unwrap(),
+			args(&[
+				"substrate-relay",
+				"relay-messages",
+				"pass3dt-to-pass3d",
+				"--source-host",
+				"pass3dt-node",
+				"--source-port",
+				"9945",
+				"--source-signer",
+				"//Alice",
+				"--source-transactions-mortality",
+				"64",
+				"--target-host",
+				"pass3d-node",
+				"--target-secure",
+				"--target-signer-file",
+				"/run/secrets/pass3d",
+				"--lane",
+				"00000002",
+				"--prometheus-host",
+				"0.0.0.0",
+			]),
+		);
+	}
+
+	#[test]
+	fn cli_options_override_config_values() {
+		assert_eq!(
+			expand(
+				TOML_CONFIG,
+				&[
+					"substrate-relay",
+					"relay-messages",
+					"pass3dt-to-pass3d",
+					"--source-host",
+					"localhost",
+					"--lane",
+					"00000000",
+				]
+			)
+			.unwrap(),
+			args(&[
+				"substrate-relay",
+				"relay-messages",
+				"pass3dt-to-pass3d",
+				"--source-port",
+				"9945",
+				"--source-signer",
+				"//Alice",
+				"--source-transactions-mortality",
+				"64",
+				"--target-host",
+				"pass3d-node",
+				"--target-secure",
+				"--target-signer-file",
+				"/run/secrets/pass3d",
+				"--prometheus-host",
+				"0.0.0.0",
+				"--source-host",
+				"localhost",
+				"--lane",
+				"00000000",
+			]),
+		);
+	}
+
+	#[test]
+	fn config_path_is_taken_from_args() {
+		assert_eq!(
+			take_config_path(args(&[
+				"substrate-relay",
+				"--config",
+				"relay.toml",
+				"relay-messages"
+			]))
+			.unwrap(),
+			(Some(PathBuf::from("relay.toml")), args(&["substrate-relay", "relay-messages"])),
+		);
+		assert_eq!(
+			take_config_path(args(&["substrate-relay", "relay-messages", "--config=relay.yaml"]))
+				.unwrap(),
+			(Some(PathBuf::from("relay.yaml")), args(&["substrate-relay", "relay-messages"])),
+		);
+		assert_eq!(
+			take_config_path(args(&["substrate-relay", "relay-messages"])).unwrap(),
+			(None, args(&["substrate-relay", "relay-messages"])),
+		);
+		assert!(take_config_path(args(&["substrate-relay", "relay-messages", "--config"])).is_err());
+	}
+
+	#[test]
+	fn config_file_is_read() {
+		let temp_dir = tempfile::tempdir().unwrap();
+		let config_path = temp_dir.path().join("relay.toml");
+		std::fs::write(&config_path, TOML_CONFIG).unwrap();
+
+		assert_eq!(
+			expand_config_file_args(args(&[
+				"substrate-relay",
+				"relay-headers",
+				"pass3dt-to-pass3d",
+				"--config",
+				config_path.to_str().unwrap(),
+			]))
+			.unwrap(),
+			args(&[
+				"substrate-relay",
+				"relay-headers",
+				"pass3dt-to-pass3d",
+				"--source-host",
+				"pass3dt-node",
+				"--source-port",
+				"9945",
+				"--target-host",
+				"pass3d-node",
+				"--target-secure",
+				"--target-signer-file",
+				"/run/secrets/pass3d",
+				"--prometheus-host",
+				"0.0.0.0",
+			]),
+		);
+	}
+
+	#[test]
+	fn yaml_config_is_supported() {
+		let config = RelayConfig::from_yaml(
+			r#"
+chains:
+  pass3d:
+    host: pass3d-node
+    port: 9944
+bridges:
+  pass3dt-pass3d:
+    lanes: ["00000000"]
+"#,
+		)
+		.unwrap();
+		assert_eq!(config.chains["pass3d"].host, Some("pass3d-node".into()));
+		assert_eq!(config.chains["pass3d"].port, Some(9944));
+		assert_eq!(config.bridges["pass3dt-pass3d"].lanes, vec!["00000000".to_string()]);
+	}
+
+	#[test]
+	fn config_errors_point_at_config_key() {
+		fn error(config: &str) -> String {
+			RelayConfig::from_toml(config).unwrap_err().to_string()
+		}
+
+		assert!(error("[chains.pass3d]\nport = \"x\"").starts_with("chains.pass3d.port: "));
+		assert!(error("[chains.pass3d]\nhots = \"x\"").starts_with("chains.pass3d: "));
+		assert!(error("[chains.pass3d]\ntransactions_mortality = 3")
+			.starts_with("chains.pass3d.transactions_mortality: "));
+		assert!(error("[chains.pass3d]\nversion_mode = \"Custom\"")
+			.starts_with("chains.pass3d.spec_version: "));
+		assert!(error("[bridges.pass3dt-pass3d]\nlanes = [\"00000000\", \"0000\"]")
+			.starts_with("bridges.pass3dt-pass3d.lanes[1]: "));
+		assert!(error("[bridges.pass3dt-pass3d]\nrelayer_mode = \"greedy\"")
+			.starts_with("bridges.pass3dt-pass3d.relayer_mode: "));
+		assert!(error("[chains.pass3]\nport = 9944").starts_with("chains.pass3: "));
+		assert!(error("[bridges.pass3dt-pass3]\nlanes = []").starts_with("bridges.pass3dt-pass3: "));
+	}
+
+	#[test]
+	fn config_flags_are_disabled_from_cli() {
+		assert_eq!(
+			expand(
+				"[chains.pass3d]\nsecure = true\n\
+				[bridges.pass3dt-to-pass3d]\nonly_mandatory_headers = true",
+				&[
+					"substrate-relay",
+					"relay-headers",
+					"pass3dt-to-pass3d",
+					"--no-target-secure",
+					"--no-only-mandatory-headers",
+					"--no-prometheus",
+				]
+			)
+			.unwrap(),
+			args(&["substrate-relay", "relay-headers", "pass3dt-to-pass3d", "--no-prometheus"]),
+		);
+	}
+
+	#[test]
+	fn relay_messages_rejects_multiple_lanes() {
+		let config = RelayConfig::from_toml(
+			"[bridges.pass3dt-to-pass3d]\nlanes = [\"00000000\", \"00000002\"]",
+		)
+		.unwrap();
+		assert!(config
+			.subcommand_options("relay-messages", "pass3dt-to-pass3d")
+			.unwrap_err()
+			.to_string()
+			.starts_with("bridges.pass3dt-to-pass3d.lanes: "));
+	}
+}
@@ -58,10 +58,14 @@ pub trait CliEncodeMessage: Chain {
 }
 
 /// Encode message payload passed through CLI flags.
+///
+/// If `compress` is true, the payload is compressed (see
+/// `bridge_runtime_common::messages_compression`).
 pub(crate) fn encode_message<Source: Chain, Target: Chain>(
 	message: &Message,
+	compress: bool,
 ) -> anyhow::Result<RawMessage> {
-	Ok(match message {
+	let payload = match message {
 		Message::Raw { ref data } => data.0.clone(),
 		Message::Sized { ref size } => match *size {
 			ExplicitOrMaximal::Explicit(size) => vec![42; size as usize],
@@ -73,6 +77,15 @@ pub(crate) fn encode_message<Source: Chain, Target: Chain>(
 				vec![42; maximal_size as usize]
 			},
 		},
+	};
+
+	Ok(if compress {
+		bridge_runtime_common::messages_compression::compress_payload(
+			bridge_runtime_common::messages_compression::PayloadCompression::Lz4,
+			&payload,
+		)
+	} else {
+		payload
 	})
 }
 
@@ -57,6 +57,9 @@ pub struct EstimateFee {
 	/// your message won't be relayed.
 	#[structopt(long)]
 	conversion_rate_override: Option<ConversionRateOverride>,
+	/// Estimate fee of the compressed message payload.
+	#[structopt(long)]
+	compress: bool,
 	/// Payload to send over the bridge.
 	#[structopt(flatten)]
 	payload: crate::cli::encode_message::Message,
@@ -93,9 +96,11 @@ where
 	async fn estimate_fee(data: EstimateFee) -> anyhow::Result<()> {
 		let source_client = data.source.into_client::<Self::Source>().await?;
 		let lane = data.lane.into();
-		let payload =
-			crate::cli::encode_message::encode_message::<Self::Source, Self::Target>(&data.payload)
-				.map_err(|e| anyhow::format_err!("{:?}", e))?;
+		let payload = crate::cli::encode_message::encode_message::<Self::Source, Self::Target>(
+			&data.payload,
+			data.compress,
+		)
+		.map_err(|e| anyhow::format_err!("{:?}", e))?;
 
 		let fee = estimate_message_delivery_and_dispatch_fee::<Self::Source, Self::Target, _>(
 			&source_client,
@@ -276,6 +281,7 @@ mod tests {
 				bridge: FullBridge::RialtoToMillau,
 				lane: HexLaneId([0, 0, 0, 0]),
 				conversion_rate_override: Some(ConversionRateOverride::Explicit(42.5)),
+				compress: false,
 				source: SourceConnectionParams {
 					source_host: "127.0.0.1".into(),
 					source_port: 1234,
@@ -36,6 +36,7 @@ use substrate_relay_helper::finality::engine::{Engine, Grandpa as GrandpaFinalit
 
 /// Initialize bridge pallet.
 #[derive(StructOpt)]
+#[structopt(after_help = crate::cli::config::CONFIG_HELP)]
 pub struct InitBridge {
 	/// A bridge instance to initialize.
 	#[structopt(possible_values = InitBridgeName::VARIANTS, case_insensitive = true)]
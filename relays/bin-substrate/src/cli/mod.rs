@@ -24,12 +24,15 @@ use strum::{EnumString, EnumVariantNames};
 
 use bp_messages::LaneId;
 
+pub(crate) mod admin;
 pub(crate) mod bridge;
+pub(crate) mod bridge_status;
 pub(crate) mod encode_message;
 pub(crate) mod estimate_fee;
 pub(crate) mod send_message;
 
 mod chain_schema;
+mod config;
 mod init_bridge;
 mod register_parachain;
 mod relay_headers;
@@ -39,13 +42,25 @@ mod relay_parachains;
 mod resubmit_transactions;
 
 /// Parse relay CLI args.
-pub fn parse_args() -> Command {
-	Command::from_args()
+///
+/// If `--config <file>` option is specified, options from the file are inserted before other
+/// options of the subcommand.
+pub fn parse_args() -> anyhow::Result<Command> {
+	if !std::env::args_os().any(|arg| arg.to_string_lossy().starts_with("--config")) {
+		return Ok(Command::from_args())
+	}
+
+	config::expand_config_file_args(std::env::args().collect()).map(Command::from_iter)
 }
 
 /// Substrate-to-Substrate bridge utilities.
 #[derive(StructOpt)]
-#[structopt(about = "Substrate-to-Substrate relay")]
+#[structopt(
+	about = "Substrate-to-Substrate relay",
+	after_help = "The relay-headers, relay-messages, relay-headers-and-messages and init-bridge \
+	subcommands also accept the `--config <file>` option with path to the TOML or YAML relay \
+	configuration file. Command line options are overriding values from the file."
+)]
 pub enum Command {
 	/// Start headers relay between two chains.
 	///
@@ -82,6 +97,16 @@ pub enum Command {
 	RegisterParachain(register_parachain::RegisterParachain),
 	///
 	RelayParachains(relay_parachains::RelayParachains),
+	/// Print status of the bridge between two chains.
+	///
+	/// Reads state of bridge pallets at both chains: best finalized headers, operating modes,
+	/// lanes nonces, unrewarded relayers and pending relayer rewards.
+	BridgeStatus(bridge_status::BridgeStatus),
+	/// Administer bridge pallets.
+	///
+	/// Builds, signs and submits `set_owner`, `set_operating_mode` and `update_pallet_parameter`
+	/// calls of bridge pallets. The call may be wrapped into `sudo` call.
+	Admin(admin::Admin),
 }
 
 impl Command {
@@ -115,6 +140,8 @@ impl Command {
 			Self::ResubmitTransactions(arg) => arg.run().await?,
 			Self::RegisterParachain(arg) => arg.run().await?,
 			Self::RelayParachains(arg) => arg.run().await?,
+			Self::BridgeStatus(arg) => arg.run().await?,
+			Self::Admin(arg) => arg.run().await?,
 		}
 		Ok(())
 	}
@@ -35,6 +35,7 @@ use crate::cli::{bridge::*, chain_schema::*, PrometheusParams};
 
 /// Start headers relayer process.
 #[derive(StructOpt)]
+#[structopt(after_help = crate::cli::config::CONFIG_HELP)]
 pub struct RelayHeaders {
 	/// A bridge instance to relay headers for.
 	#[structopt(possible_values = RelayHeadersBridge::VARIANTS, case_insensitive = true)]
@@ -29,7 +29,7 @@ mod relay_to_relay;
 mod relay_to_parachain;
 
 use async_trait::async_trait;
-use std::{marker::PhantomData, sync::Arc};
+use std::{collections::HashMap, marker::PhantomData, sync::Arc, time::Duration};
 use structopt::StructOpt;
 use strum::VariantNames;
 
@@ -66,8 +66,13 @@ use relay_substrate_client::{
 use relay_utils::metrics::MetricsParams;
 use sp_core::Pair;
 use substrate_relay_helper::{
-	messages_lane::MessagesRelayParams, messages_metrics::StandaloneMessagesMetrics,
-	on_demand::OnDemandRelay, TaggedAccount, TransactionParams,
+	messages_lane::{MessagesRelayParams, SubstrateMessageLane},
+	messages_metrics::StandaloneMessagesMetrics,
+	messages_scheduler::{
+		MessagesConfirmationScheduler, MessagesDeliveryScheduler, DEFAULT_LANE_PRIORITY,
+	},
+	on_demand::OnDemandRelay,
+	TaggedAccount, TransactionParams,
 };
 
 /// Maximal allowed conversion rate error ratio (abs(real - stored) / stored) that we allow.
@@ -84,6 +89,19 @@ pub struct HeadersAndMessagesSharedParams {
 	/// Hex-encoded lane identifiers that should be served by the complex relay.
 	#[structopt(long, default_value = "00000000")]
 	pub lane: Vec<HexLaneId>,
+	/// Priority of the lane messages delivery, in `<hex-lane-id>=<priority>` format.
+	///
+	/// When messages of multiple lanes are delivered by the same transaction, messages of lanes
+	/// with larger priority are delivered first. Default priority is zero.
+	#[structopt(long)]
+	pub lane_priority: Vec<LanePriority>,
+	/// Time (in seconds) that the relay waits for messages transactions of other lanes, before
+	/// submitting them in a single transaction.
+	///
+	/// Only used when multiple lanes are served. The relay doesn't wait if transactions of all
+	/// lanes are ready.
+	#[structopt(long, default_value = "2")]
+	pub batch_window: u64,
 	#[structopt(long, possible_values = RelayerMode::VARIANTS, case_insensitive = true, default_value = "rational")]
 	pub relayer_mode: RelayerMode,
 	/// If passed, only mandatory headers (headers that are changing the GRANDPA authorities set)
@@ -94,6 +112,26 @@ pub struct HeadersAndMessagesSharedParams {
 	pub prometheus_params: PrometheusParams,
 }
 
+/// Priority of the lane messages delivery.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LanePriority {
+	/// Lane identifier.
+	pub lane: HexLaneId,
+	/// Lane priority.
+	pub priority: u32,
+}
+
+impl std::str::FromStr for LanePriority {
+	type Err = anyhow::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (lane, priority) = s.split_once('=').ok_or_else(|| {
+			anyhow::format_err!("Expected lane priority in <lane>=<priority> format, got: {}", s)
+		})?;
+		Ok(LanePriority { lane: lane.parse()?, priority: priority.parse()? })
+	}
+}
+
 pub struct Full2WayBridgeCommonParams<
 	Left: TransactionSignScheme + CliChain,
 	Right: TransactionSignScheme + CliChain,
@@ -220,10 +258,46 @@ where
 		Ok(())
 	}
 
+	/// Returns scheduler of messages transactions at the target chain.
+	///
+	/// The scheduler submits messages delivery transactions of this bridge direction and delivery
+	/// confirmation transactions of the opposite direction.
+	fn messages_target_scheduler(&self) -> MessagesDeliveryScheduler<Bridge::MessagesLane> {
+		let lane_priorities = self
+			.shared
+			.lane
+			.iter()
+			.map(|lane| {
+				let priority = self
+					.shared
+					.lane_priority
+					.iter()
+					.find(|lane_priority| lane_priority.lane == *lane)
+					.map(|lane_priority| lane_priority.priority)
+					.unwrap_or(DEFAULT_LANE_PRIORITY);
+				(lane.clone().into(), priority)
+			})
+			.collect::<HashMap<_, _>>();
+		MessagesDeliveryScheduler::<Bridge::MessagesLane>::new::<
+			Target,
+			<Bridge::MessagesLane as SubstrateMessageLane>::TargetBatchCallBuilder,
+		>(
+			self.target.client.clone(),
+			TransactionParams {
+				signer: self.target.sign.clone(),
+				mortality: self.target.transactions_mortality,
+			},
+			lane_priorities,
+			Duration::from_secs(self.shared.batch_window),
+		)
+	}
+
 	fn messages_relay_params(
 		&self,
 		source_to_target_headers_relay: Arc<dyn OnDemandRelay<BlockNumberOf<Source>>>,
 		target_to_source_headers_relay: Arc<dyn OnDemandRelay<BlockNumberOf<Target>>>,
+		delivery_scheduler: Option<MessagesDeliveryScheduler<Bridge::MessagesLane>>,
+		confirmation_scheduler: Option<MessagesConfirmationScheduler<Bridge::MessagesLane>>,
 		lane_id: LaneId,
 	) -> MessagesRelayParams<Bridge::MessagesLane> {
 		let relayer_mode = self.shared.relayer_mode.into();
@@ -246,6 +320,8 @@ where
 			metrics_params: self.metrics_params.clone().disable(),
 			standalone_metrics: Some(self.metrics.clone()),
 			relay_strategy,
+			delivery_scheduler,
+			confirmation_scheduler,
 		}
 	}
 }
@@ -386,6 +462,16 @@ where
 		}
 
 		let lanes = self.base().common().shared.lane.clone();
+		// delivery and confirmation transactions of all lanes are submitted to the same chain by
+		// the same scheduler, so they're not competing for the same signer nonce
+		let (right_scheduler, left_scheduler) = if lanes.len() > 1 {
+			(
+				Some(self.left_to_right().messages_target_scheduler()),
+				Some(self.right_to_left().messages_target_scheduler()),
+			)
+		} else {
+			(None, None)
+		};
 		// Need 2x capacity since we consider both directions for each lane
 		let mut message_relays = Vec::with_capacity(lanes.len() * 2);
 		for lane in lanes {
@@ -396,6 +482,8 @@ where
 			>(self.left_to_right().messages_relay_params(
 				left_to_right_on_demand_headers.clone(),
 				right_to_left_on_demand_headers.clone(),
+				right_scheduler.clone(),
+				left_scheduler.clone(),
 				lane,
 			))
 			.map_err(|e| anyhow::format_err!("{}", e))
@@ -407,6 +495,8 @@ where
 			>(self.right_to_left().messages_relay_params(
 				right_to_left_on_demand_headers.clone(),
 				left_to_right_on_demand_headers.clone(),
+				left_scheduler.clone(),
+				right_scheduler.clone(),
 				lane,
 			))
 			.map_err(|e| anyhow::format_err!("{}", e))
@@ -501,6 +591,7 @@ impl Full2WayBridge for MillauRialtoParachainFull2WayBridge {
 
 /// Start headers+messages relayer process.
 #[derive(Debug, PartialEq, StructOpt)]
+#[structopt(after_help = crate::cli::config::CONFIG_HELP)]
 pub enum RelayHeadersAndMessages {
 	MillauRialto(MillauRialtoHeadersAndMessages),
 	MillauRialtoParachain(MillauRialtoParachainHeadersAndMessages),
@@ -557,6 +648,10 @@ mod tests {
 			"00000000",
 			"--lane",
 			"73776170",
+			"--lane-priority",
+			"73776170=10",
+			"--batch-window",
+			"5",
 			"--prometheus-host",
 			"0.0.0.0",
 		]);
@@ -570,6 +665,11 @@ mod tests {
 						HexLaneId([0x00, 0x00, 0x00, 0x00]),
 						HexLaneId([0x73, 0x77, 0x61, 0x70])
 					],
+					lane_priority: vec![LanePriority {
+						lane: HexLaneId([0x73, 0x77, 0x61, 0x70]),
+						priority: 10,
+					}],
+					batch_window: 5,
 					relayer_mode: RelayerMode::Rational,
 					only_mandatory_headers: false,
 					prometheus_params: PrometheusParams {
@@ -683,6 +783,8 @@ mod tests {
 				MillauRialtoParachainHeadersAndMessages {
 					shared: HeadersAndMessagesSharedParams {
 						lane: vec![HexLaneId([0x00, 0x00, 0x00, 0x00])],
+						lane_priority: vec![],
+						batch_window: 2,
 						relayer_mode: RelayerMode::Rational,
 						only_mandatory_headers: false,
 						prometheus_params: PrometheusParams {
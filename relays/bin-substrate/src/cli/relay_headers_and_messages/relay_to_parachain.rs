@@ -63,6 +63,7 @@ macro_rules! declare_relay_to_parachain_bridge_schema {
 		bp_runtime::paste::item! {
 			#[doc = $left_chain ", " $right_parachain " and " $right_chain " headers+parachains+messages relay params."]
 			#[derive(Debug, PartialEq, StructOpt)]
+			#[structopt(after_help = crate::cli::config::CONFIG_HELP)]
 			pub struct [<$left_chain $right_parachain HeadersAndMessages>] {
 				#[structopt(flatten)]
 				shared: HeadersAndMessagesSharedParams,
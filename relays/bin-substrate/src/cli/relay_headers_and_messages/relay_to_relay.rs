@@ -50,6 +50,7 @@ macro_rules! declare_relay_to_relay_bridge_schema {
 		bp_runtime::paste::item! {
 			#[doc = $left_chain " and " $right_chain " headers+messages relay params."]
 			#[derive(Debug, PartialEq, StructOpt)]
+			#[structopt(after_help = crate::cli::config::CONFIG_HELP)]
 			pub struct [<$left_chain $right_chain HeadersAndMessages>] {
 				#[structopt(flatten)]
 				shared: HeadersAndMessagesSharedParams,
@@ -55,6 +55,7 @@ impl From<RelayerMode> for messages_relay::message_lane_loop::RelayerMode {
 
 /// Start messages relayer process.
 #[derive(StructOpt)]
+#[structopt(after_help = crate::cli::config::CONFIG_HELP)]
 pub struct RelayMessages {
 	/// A bridge instance to relay messages for.
 	#[structopt(possible_values = FullBridge::VARIANTS, case_insensitive = true)]
@@ -112,6 +113,8 @@ where
 			metrics_params: data.prometheus_params.into(),
 			standalone_metrics: None,
 			relay_strategy,
+			delivery_scheduler: None,
+			confirmation_scheduler: None,
 		})
 		.await
 		.map_err(|e| anyhow::format_err!("{}", e))
@@ -76,6 +76,12 @@ pub struct SendMessage {
 	/// bridge messages pallet.
 	#[structopt(long)]
 	use_xcm_pallet: bool,
+	/// Compress message payload before sending it. Compressed payloads are cheaper to deliver.
+	///
+	/// When the message is sent using XCM pallet, the raw XCM program is submitted and it is
+	/// compressed by the bridge router of the source chain runtime (if it is configured to).
+	#[structopt(long)]
+	compress: bool,
 	/// Hex-encoded lane id. Defaults to `00000000`.
 	#[structopt(long, default_value = "00000000")]
 	lane: HexLaneId,
@@ -108,7 +114,11 @@ where
 	AccountId32: From<<AccountKeyPairOf<Self::Source> as Pair>::Public>,
 {
 	async fn send_message(data: SendMessage) -> anyhow::Result<()> {
-		let payload = encode_message::encode_message::<Self::Source, Self::Target>(&data.message)?;
+		// XCM pallet accepts raw XCM programs only - the runtime compresses them itself
+		let payload = encode_message::encode_message::<Self::Source, Self::Target>(
+			&data.message,
+			data.compress && !data.use_xcm_pallet,
+		)?;
 
 		let source_client = data.source.into_client::<Self::Source>().await?;
 		let source_sign = data.source_sign.to_keypair::<Self::Source>()?;
@@ -263,6 +273,30 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn send_compressed_rialto_to_millau() {
+		// given
+		let send_message = SendMessage::from_iter(vec![
+			"send-message",
+			"rialto-to-millau",
+			"--source-port",
+			"1234",
+			"--source-signer",
+			"//Alice",
+			"--compress",
+			"raw",
+			"dead",
+		]);
+
+		// then
+		assert!(send_message.compress);
+		assert!(!send_message.use_xcm_pallet);
+		assert_eq!(
+			send_message.message,
+			crate::cli::encode_message::Message::Raw { data: HexBytes(vec![0xDE, 0xAD]) }
+		);
+	}
+
 	#[test]
 	fn send_sized_rialto_to_millau() {
 		// given
@@ -22,7 +22,13 @@ mod chains;
 mod cli;
 
 fn main() {
-	let command = cli::parse_args();
+	let command = match cli::parse_args() {
+		Ok(command) => command,
+		Err(error) => {
+			eprintln!("error: {}", error);
+			std::process::exit(1);
+		},
+	};
 	let run = command.run();
 	let result = async_std::task::block_on(run);
 	if let Err(error) = result {
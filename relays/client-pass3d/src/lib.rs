@@ -18,7 +18,7 @@
 
 use bp_messages::MessageNonce;
 use codec::{Compact, Decode, Encode};
-use frame_support::weights::Weight;
+use frame_support::weights::{RuntimeDbWeight, Weight};
 use relay_substrate_client::{
 	BalanceOf, Chain, ChainBase, ChainWithBalances, ChainWithGrandpa, ChainWithMessages,
 	Error as SubstrateError, IndexOf, SignParam, TransactionSignScheme,
@@ -91,6 +91,8 @@ impl ChainWithMessages for Pass3d {
 		bp_pass3d::MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX;
 	const MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX: MessageNonce =
 		bp_pass3d::MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX;
+	const MAX_MESSAGE_RESPONSE_SIZE: u32 = pass3d_runtime::MaxMessageResponseSize::get();
+	const DB_WEIGHT: RuntimeDbWeight = pass3d_runtime::DbWeight::get();
 	type WeightToFee = bp_pass3d::WeightToFee;
 	type WeightInfo = ();
 }
@@ -124,6 +126,8 @@ impl TransactionSignScheme for Pass3d {
 				frame_system::CheckNonce::<pass3d_runtime::Runtime>::from(unsigned.nonce),
 				frame_system::CheckWeight::<pass3d_runtime::Runtime>::new(),
 				pallet_transaction_payment::ChargeTransactionPayment::<pass3d_runtime::Runtime>::from(unsigned.tip),
+				pass3d_runtime::BridgeRejectObsoleteHeadersAndMessages,
+				pass3d_runtime::RefundBridgedPass3dtTransactions::default(),
 			),
 			(
 				(),
@@ -134,6 +138,8 @@ impl TransactionSignScheme for Pass3d {
 				(),
 				(),
 				(),
+				(),
+				(),
 			),
 		);
 		let signature = raw_payload.using_encoded(|payload| param.signer.sign(payload));
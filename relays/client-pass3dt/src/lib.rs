@@ -18,7 +18,7 @@
 
 use bp_messages::MessageNonce;
 use codec::{Compact, Decode, Encode};
-use frame_support::weights::Weight;
+use frame_support::weights::{RuntimeDbWeight, Weight};
 use relay_substrate_client::{
 	BalanceOf, Chain, ChainBase, ChainWithBalances, ChainWithGrandpa, ChainWithMessages,
 	Error as SubstrateError, IndexOf, SignParam, TransactionSignScheme, UnsignedTransaction,
@@ -71,6 +71,8 @@ impl ChainWithMessages for Pass3dt {
 		bp_pass3dt::MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX;
 	const MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX: MessageNonce =
 		bp_pass3dt::MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX;
+	const MAX_MESSAGE_RESPONSE_SIZE: u32 = pass3dt_runtime::MaxMessageResponseSize::get();
+	const DB_WEIGHT: RuntimeDbWeight = pass3dt_runtime::DbWeight::get();
 	type WeightToFee = bp_pass3dt::WeightToFee;
 	type WeightInfo = ();
 }
@@ -18,7 +18,7 @@
 
 use bp_messages::MessageNonce;
 use codec::Encode;
-use frame_support::weights::Weight;
+use frame_support::weights::{RuntimeDbWeight, Weight};
 use relay_substrate_client::{
 	Chain, ChainBase, ChainWithBalances, ChainWithMessages, Error as SubstrateError, SignParam,
 	TransactionSignScheme, UnsignedTransaction,
@@ -92,6 +92,9 @@ impl ChainWithMessages for RialtoParachain {
 		bp_rialto_parachain::MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX;
 	const MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX: MessageNonce =
 		bp_rialto_parachain::MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX;
+	const MAX_MESSAGE_RESPONSE_SIZE: u32 = rialto_parachain_runtime::MaxMessageResponseSize::get();
+	// the runtime is using `()` as its `DbWeight`
+	const DB_WEIGHT: RuntimeDbWeight = RuntimeDbWeight { read: 0, write: 0 };
 	type WeightToFee = bp_rialto_parachain::WeightToFee;
 	type WeightInfo = ();
 }
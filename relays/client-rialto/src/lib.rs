@@ -18,7 +18,7 @@
 
 use bp_messages::MessageNonce;
 use codec::{Compact, Decode, Encode};
-use frame_support::weights::Weight;
+use frame_support::weights::{RuntimeDbWeight, Weight};
 use relay_substrate_client::{
 	BalanceOf, Chain, ChainBase, ChainWithBalances, ChainWithGrandpa, ChainWithMessages,
 	Error as SubstrateError, IndexOf, RelayChain, SignParam, TransactionSignScheme,
@@ -91,6 +91,8 @@ impl ChainWithMessages for Rialto {
 		bp_rialto::MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX;
 	const MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX: MessageNonce =
 		bp_rialto::MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX;
+	const MAX_MESSAGE_RESPONSE_SIZE: u32 = rialto_runtime::MaxMessageResponseSize::get();
+	const DB_WEIGHT: RuntimeDbWeight = rialto_runtime::DbWeight::get();
 	type WeightToFee = bp_rialto::WeightToFee;
 	type WeightInfo = ();
 }
@@ -19,7 +19,7 @@ use bp_runtime::{
 	Chain as ChainBase, EncodedOrDecodedCall, HashOf, TransactionEra, TransactionEraOf,
 };
 use codec::{Codec, Encode};
-use frame_support::weights::{Weight, WeightToFee};
+use frame_support::weights::{RuntimeDbWeight, Weight, WeightToFee};
 use jsonrpsee::core::{DeserializeOwned, Serialize};
 use num_traits::Zero;
 use sc_transaction_pool_api::TransactionStatus;
@@ -116,6 +116,11 @@ pub trait ChainWithMessages: Chain {
 	/// Maximal number of unconfirmed messages in a single confirmation transaction at this
 	/// `ChainWithMessages`.
 	const MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX: MessageNonce;
+	/// Maximal size of the message dispatch response, that is handled by this
+	/// `ChainWithMessages`.
+	const MAX_MESSAGE_RESPONSE_SIZE: u32;
+	/// Weight of database operations at this `ChainWithMessages`.
+	const DB_WEIGHT: RuntimeDbWeight;
 
 	/// Type that is used by the chain, to convert from weight to fee.
 	type WeightToFee: WeightToFee<Balance = Self::Balance>;
@@ -127,6 +132,8 @@ pub trait ChainWithMessages: Chain {
 pub type CallOf<C> = <C as Chain>::Call;
 /// Weight-to-Fee type used by the chain.
 pub type WeightToFeeOf<C> = <C as ChainWithMessages>::WeightToFee;
+/// Weights of message pallet calls at the chain.
+pub type WeightInfoOf<C> = <C as ChainWithMessages>::WeightInfo;
 /// Transaction status of the chain.
 pub type TransactionStatusOf<C> = TransactionStatus<HashOf<C>, HashOf<C>>;
 
@@ -31,6 +31,7 @@ use async_std::sync::{Arc, Mutex};
 use async_trait::async_trait;
 use bp_runtime::{HeaderIdProvider, StorageDoubleMapKeyProvider, StorageMapKeyProvider};
 use codec::{Decode, Encode};
+use frame_support::ReversibleStorageHasher;
 use frame_system::AccountInfo;
 use futures::{SinkExt, StreamExt};
 use jsonrpsee::{
@@ -56,6 +57,8 @@ use std::{convert::TryFrom, future::Future};
 const SUB_API_GRANDPA_AUTHORITIES: &str = "GrandpaApi_grandpa_authorities";
 const SUB_API_TXPOOL_VALIDATE_TRANSACTION: &str = "TaggedTransactionQueue_validate_transaction";
 const MAX_SUBSCRIPTION_CAPACITY: usize = 4096;
+/// Maximal number of storage keys that we read in a single `state_getKeysPaged` call.
+const STORAGE_KEYS_PAGE_SIZE: u32 = 256;
 
 /// Opaque justifications subscription type.
 pub struct Subscription<T>(pub(crate) Mutex<futures::channel::mpsc::Receiver<Option<T>>>);
@@ -355,6 +358,70 @@ impl<C: Chain> Client<C> {
 			.transpose()
 	}
 
+	/// Read all `DoubleMapStorage` values with given first key from runtime storage.
+	pub async fn storage_double_map_values<T: StorageDoubleMapKeyProvider>(
+		&self,
+		pallet_prefix: &str,
+		key1: &T::Key1,
+		block_hash: Option<C::Hash>,
+	) -> Result<Vec<(T::Key2, T::Value)>>
+	where
+		T::Hasher2: ReversibleStorageHasher,
+	{
+		let prefix = T::prefix_key(pallet_prefix, key1);
+
+		let mut values = Vec::new();
+		let mut start_key = None;
+		loop {
+			let keys = self
+				.storage_keys_paged(
+					prefix.clone(),
+					STORAGE_KEYS_PAGE_SIZE,
+					start_key.take(),
+					block_hash,
+				)
+				.await?;
+			let is_last_page = keys.len() < STORAGE_KEYS_PAGE_SIZE as usize;
+			for storage_key in keys {
+				let key2 =
+					T::Key2::decode(&mut T::Hasher2::reverse(&storage_key.0[prefix.0.len()..]))
+						.map_err(Error::ResponseParseFailed)?;
+				let value = self.raw_storage_value(storage_key.clone(), block_hash).await?;
+				if let Some(encoded_value) = value {
+					let value = T::Value::decode(&mut &encoded_value.0[..])
+						.map_err(Error::ResponseParseFailed)?;
+					values.push((key2, value));
+				}
+				start_key = Some(storage_key);
+			}
+
+			if is_last_page {
+				return Ok(values)
+			}
+		}
+	}
+
+	/// Read page of runtime storage keys with given prefix.
+	pub async fn storage_keys_paged(
+		&self,
+		prefix: StorageKey,
+		count: u32,
+		start_key: Option<StorageKey>,
+		block_hash: Option<C::Hash>,
+	) -> Result<Vec<StorageKey>> {
+		self.jsonrpsee_execute(move |client| async move {
+			Ok(SubstrateStateClient::<C>::storage_keys_paged(
+				&*client,
+				Some(prefix),
+				count,
+				start_key,
+				block_hash,
+			)
+			.await?)
+		})
+		.await
+	}
+
 	/// Read raw value from runtime storage.
 	pub async fn raw_storage_value(
 		&self,
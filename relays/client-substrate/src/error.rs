@@ -54,9 +54,15 @@ pub enum Error {
 	/// The bridge pallet is halted and all transactions will be rejected.
 	#[error("Bridge pallet is halted.")]
 	BridgePalletIsHalted,
+	/// The bridge messages lane is halted and all lane transactions will be rejected.
+	#[error("Bridge messages lane {0:?} is halted.")]
+	BridgeLaneIsHalted(bp_messages::LaneId),
 	/// The bridge pallet is not yet initialized and all transactions will be rejected.
 	#[error("Bridge pallet is not initialized.")]
 	BridgePalletIsNotInitialized,
+	/// The bridge messages pallet uses older messages wire format than the relay.
+	#[error("Bridge messages pallet uses unsupported wire format version {0}.")]
+	UnsupportedMessagesWireFormat(u16),
 	/// An error has happened when we have tried to parse storage proof.
 	#[error("Error when parsing storage proof: {0:?}.")]
 	StorageProofError(bp_runtime::StorageProofError),
@@ -91,6 +91,15 @@ pub(crate) trait SubstrateState<C> {
 		key: StorageKey,
 		at_block: Option<C::Hash>,
 	) -> RpcResult<Option<StorageData>>;
+	/// Get keys of the runtime storage with given prefix, page by page.
+	#[method(name = "getKeysPaged")]
+	async fn storage_keys_paged(
+		&self,
+		prefix: Option<StorageKey>,
+		count: u32,
+		start_key: Option<StorageKey>,
+		at_block: Option<C::Hash>,
+	) -> RpcResult<Vec<StorageKey>>;
 	/// Get proof of the runtime storage value.
 	#[method(name = "getReadProof")]
 	async fn prove_storage(
@@ -24,6 +24,7 @@ pub mod finality;
 pub mod helpers;
 pub mod messages_lane;
 pub mod messages_metrics;
+pub mod messages_scheduler;
 pub mod messages_source;
 pub mod messages_target;
 pub mod on_demand;
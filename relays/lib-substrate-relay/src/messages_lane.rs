@@ -19,6 +19,9 @@
 use crate::{
 	conversion_rate_update::UpdateConversionRateCallBuilder,
 	messages_metrics::StandaloneMessagesMetrics,
+	messages_scheduler::{
+		BatchCallBuilder, MessagesConfirmationScheduler, MessagesDeliveryScheduler,
+	},
 	messages_source::{SubstrateMessagesProof, SubstrateMessagesSource},
 	messages_target::{SubstrateMessagesDeliveryProof, SubstrateMessagesTarget},
 	on_demand::OnDemandRelay,
@@ -90,6 +93,12 @@ pub trait SubstrateMessageLane: 'static + Clone + Debug + Send + Sync {
 	type ReceiveMessagesProofCallBuilder: ReceiveMessagesProofCallBuilder<Self>;
 	/// How receive messages delivery proof call is built?
 	type ReceiveMessagesDeliveryProofCallBuilder: ReceiveMessagesDeliveryProofCallBuilder<Self>;
+	/// How batch calls are built at the target chain?
+	///
+	/// If not applicable to this bridge, you may use `()` here. Then delivery transactions of
+	/// different lanes (and delivery confirmation transactions of the opposite bridge direction)
+	/// are never batched.
+	type TargetBatchCallBuilder: BatchCallBuilder<Self::TargetChain>;
 
 	/// `TargetChain` tokens to `SourceChain` tokens conversion rate update builder.
 	///
@@ -149,6 +158,14 @@ pub struct MessagesRelayParams<P: SubstrateMessageLane> {
 	pub standalone_metrics: Option<StandaloneMessagesMetrics<P::SourceChain, P::TargetChain>>,
 	/// Relay strategy.
 	pub relay_strategy: P::RelayStrategy,
+	/// Optional scheduler of delivery transactions, shared with other lanes.
+	///
+	/// If `None`, the lane submits its own delivery transactions.
+	pub delivery_scheduler: Option<MessagesDeliveryScheduler<P>>,
+	/// Optional scheduler of delivery confirmation transactions, shared with other lanes.
+	///
+	/// If `None`, the lane submits its own delivery confirmation transactions.
+	pub confirmation_scheduler: Option<MessagesConfirmationScheduler<P>>,
 }
 
 /// Run Substrate-to-Substrate messages sync loop.
@@ -240,6 +257,7 @@ where
 			params.lane_id,
 			params.source_transaction_params,
 			params.target_to_source_headers_relay,
+			params.confirmation_scheduler,
 		),
 		SubstrateMessagesTarget::<P>::new(
 			target_client,
@@ -249,6 +267,7 @@ where
 			params.target_transaction_params,
 			standalone_metrics.clone(),
 			params.source_to_target_headers_relay,
+			params.delivery_scheduler,
 		),
 		standalone_metrics.register_and_spawn(params.metrics_params)?,
 		futures::future::pending(),
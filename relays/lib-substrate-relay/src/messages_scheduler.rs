@@ -0,0 +1,662 @@
+// Copyright 2019-2022 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Scheduler of messages transactions, shared by multiple lanes of the same bridge.
+//!
+//! Every lane is still served by its own messages relay loop (with its own metrics and limits),
+//! but instead of submitting its own delivery (or delivery confirmation) transaction, the loop
+//! passes the call to the scheduler of the chain, where the transaction is submitted. The
+//! scheduler collects calls of all lanes for a short period, orders them by lane priority and
+//! packs calls with proofs, generated at the same bridged header, into a single `batch_all`
+//! transaction. So all lanes are sharing the same transactions signer without competing for its
+//! nonce.
+//!
+//! The target runtime rejects the whole batch if any of its calls is obsolete (e.g. because
+//! messages have been delivered by other relayer while the call has been waiting for other
+//! lanes). So right before submission, the scheduler drops calls that have become obsolete and
+//! reports their transactions as lost to their lanes.
+//!
+//! Deliveries of one direction and confirmations of the opposite direction are submitted to the
+//! same chain by the same signer, so they should be routed through the same scheduler.
+
+use crate::{messages_lane::SubstrateMessageLane, TransactionParams};
+
+use bp_messages::LaneId;
+use codec::Encode;
+use frame_support::weights::Weight;
+use futures::{
+	channel::{mpsc, oneshot},
+	future::{BoxFuture, Shared},
+	FutureExt, StreamExt,
+};
+use relay_substrate_client::{
+	AccountIdOf, AccountKeyPairOf, CallOf, Chain, Client, Error as SubstrateError, HeaderIdOf,
+	SignParam, TransactionEra, TransactionSignScheme, UnsignedTransaction,
+};
+use relay_utils::{TrackedTransactionStatus, TransactionTracker};
+use sp_core::Pair;
+use std::{
+	collections::{HashMap, HashSet},
+	marker::PhantomData,
+	time::Duration,
+};
+
+/// Priority of the lane that has no explicitly configured priority.
+pub const DEFAULT_LANE_PRIORITY: u32 = 0;
+
+/// Future that resolves to `true` if the scheduled call has become obsolete and must not be
+/// submitted. It is polled right before the call is submitted.
+pub type ObsoleteCallCheck = BoxFuture<'static, Result<bool, SubstrateError>>;
+
+/// Scheduler of messages delivery transactions of given messages pipeline.
+pub type MessagesDeliveryScheduler<P> = MessagesTransactionsScheduler<
+	<P as SubstrateMessageLane>::TargetChain,
+	<P as SubstrateMessageLane>::SourceChain,
+>;
+
+/// Scheduler of messages delivery confirmation transactions of given messages pipeline.
+pub type MessagesConfirmationScheduler<P> = MessagesTransactionsScheduler<
+	<P as SubstrateMessageLane>::SourceChain,
+	<P as SubstrateMessageLane>::TargetChain,
+>;
+
+/// Different ways of building batch calls.
+pub trait BatchCallBuilder<C: Chain> {
+	/// Given calls, build a single call that dispatches all of them atomically.
+	fn build_batch_call(calls: Vec<CallOf<C>>) -> anyhow::Result<CallOf<C>>;
+}
+
+impl<C: Chain> BatchCallBuilder<C> for () {
+	fn build_batch_call(_calls: Vec<CallOf<C>>) -> anyhow::Result<CallOf<C>> {
+		Err(anyhow::format_err!("Batch calls are not supported at {}", C::NAME))
+	}
+}
+
+/// Building `utility::batch_all` call when you have direct access to the chain runtime.
+pub struct DirectBatchCallBuilder<C, R> {
+	_phantom: PhantomData<(C, R)>,
+}
+
+impl<C, R> BatchCallBuilder<C> for DirectBatchCallBuilder<C, R>
+where
+	C: Chain,
+	R: pallet_utility::Config<Call = CallOf<C>>,
+	CallOf<C>: From<pallet_utility::Call<R>>,
+{
+	fn build_batch_call(calls: Vec<CallOf<C>>) -> anyhow::Result<CallOf<C>> {
+		Ok(pallet_utility::Call::<R>::batch_all { calls }.into())
+	}
+}
+
+/// Transaction tracker that may be shared by all lanes, served by the same transaction.
+pub struct SharedTransactionTracker<C: Chain> {
+	status: Shared<BoxFuture<'static, TrackedTransactionStatus<HeaderIdOf<C>>>>,
+}
+
+impl<C: Chain> SharedTransactionTracker<C> {
+	/// Create shared tracker from the tracker of submitted transaction.
+	pub fn new(
+		tracker: impl TransactionTracker<HeaderId = HeaderIdOf<C>> + 'static,
+	) -> SharedTransactionTracker<C> {
+		SharedTransactionTracker { status: tracker.wait().shared() }
+	}
+
+	/// Create tracker of the transaction that has never been submitted.
+	pub fn lost() -> SharedTransactionTracker<C> {
+		SharedTransactionTracker {
+			status: futures::future::ready(TrackedTransactionStatus::Lost).boxed().shared(),
+		}
+	}
+}
+
+impl<C: Chain> Clone for SharedTransactionTracker<C> {
+	fn clone(&self) -> Self {
+		SharedTransactionTracker { status: self.status.clone() }
+	}
+}
+
+#[async_trait::async_trait]
+impl<C: Chain> TransactionTracker for SharedTransactionTracker<C> {
+	type HeaderId = HeaderIdOf<C>;
+
+	async fn wait(self) -> TrackedTransactionStatus<HeaderIdOf<C>> {
+		self.status.await
+	}
+}
+
+/// Scheduler of messages transactions at chain `C`, that are bringing proofs of the bridged
+/// chain `B`.
+pub struct MessagesTransactionsScheduler<C: Chain, B: Chain> {
+	lane_priorities: HashMap<LaneId, u32>,
+	calls_sender: mpsc::UnboundedSender<ScheduledCallOf<C, B>>,
+}
+
+impl<C: Chain, B: Chain> Clone for MessagesTransactionsScheduler<C, B> {
+	fn clone(&self) -> Self {
+		Self {
+			lane_priorities: self.lane_priorities.clone(),
+			calls_sender: self.calls_sender.clone(),
+		}
+	}
+}
+
+impl<C: Chain, B: Chain> MessagesTransactionsScheduler<C, B> {
+	/// Create new scheduler and start its background task.
+	///
+	/// The `lane_priorities` must contain every lane, served by the scheduler. The scheduler
+	/// stops waiting for calls of other lanes after `batch_window` has passed, or when calls of
+	/// all served lanes are received.
+	pub fn new<S, BB>(
+		client: Client<C>,
+		transaction_params: TransactionParams<AccountKeyPairOf<S>>,
+		lane_priorities: HashMap<LaneId, u32>,
+		batch_window: Duration,
+	) -> Self
+	where
+		S: TransactionSignScheme<Chain = C>,
+		BB: BatchCallBuilder<C>,
+		AccountIdOf<C>: From<<AccountKeyPairOf<S> as Pair>::Public>,
+	{
+		let (calls_sender, calls_receiver) = mpsc::unbounded();
+		let lanes_count = lane_priorities.len();
+		async_std::task::spawn(async move {
+			background_task::<C, B, S, BB>(
+				client,
+				transaction_params,
+				lanes_count,
+				batch_window,
+				calls_receiver,
+			)
+			.await
+		});
+
+		MessagesTransactionsScheduler { lane_priorities, calls_sender }
+	}
+
+	/// Schedule submission of the call with proof, generated at given bridged header.
+	///
+	/// The `weight` is the full weight of the call, including weight of the proof verification
+	/// and dispatch weight of all bundled messages. The `obsolete_check` is polled right before
+	/// submission and if it reports that the call is obsolete, the call is not submitted and
+	/// the returned tracker reports that the transaction is lost.
+	///
+	/// Returns tracker of the transaction that has submitted the call. The same transaction may
+	/// also submit calls of other lanes.
+	pub async fn submit(
+		&self,
+		lane_id: LaneId,
+		generated_at_header: HeaderIdOf<B>,
+		weight: Weight,
+		call: CallOf<C>,
+		obsolete_check: ObsoleteCallCheck,
+	) -> Result<SharedTransactionTracker<C>, SubstrateError> {
+		let (tracker_sender, tracker_receiver) = oneshot::channel();
+		let scheduled_call = ScheduledCall {
+			lane_id,
+			priority: self.lane_priorities.get(&lane_id).copied().unwrap_or(DEFAULT_LANE_PRIORITY),
+			generated_at_header,
+			weight,
+			size: call.encoded_size() as u32,
+			call,
+			obsolete_check,
+			tracker_sender,
+		};
+		self.calls_sender
+			.unbounded_send(scheduled_call)
+			.map_err(|_| scheduler_stopped_error::<C>())?;
+		tracker_receiver.await.map_err(|_| scheduler_stopped_error::<C>())?
+	}
+}
+
+/// Call of a single lane, waiting for submission.
+struct ScheduledCall<H, Call, O, S> {
+	lane_id: LaneId,
+	priority: u32,
+	generated_at_header: H,
+	weight: Weight,
+	size: u32,
+	call: Call,
+	obsolete_check: O,
+	tracker_sender: S,
+}
+
+/// Scheduled call at chain `C` with proof of the bridged chain `B`.
+type ScheduledCallOf<C, B> = ScheduledCall<
+	HeaderIdOf<B>,
+	CallOf<C>,
+	ObsoleteCallCheck,
+	oneshot::Sender<Result<SharedTransactionTracker<C>, SubstrateError>>,
+>;
+
+/// Limits of the single transaction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct BatchLimits {
+	/// Maximal number of calls in the batch.
+	max_calls: usize,
+	/// Maximal cumulative size of all calls in the batch.
+	max_size: u32,
+	/// Maximal cumulative weight of all calls in the batch.
+	max_weight: Weight,
+}
+
+/// Background task of the scheduler.
+async fn background_task<C, B, S, BB>(
+	client: Client<C>,
+	transaction_params: TransactionParams<AccountKeyPairOf<S>>,
+	lanes_count: usize,
+	batch_window: Duration,
+	calls_receiver: mpsc::UnboundedReceiver<ScheduledCallOf<C, B>>,
+) where
+	C: Chain,
+	B: Chain,
+	S: TransactionSignScheme<Chain = C>,
+	BB: BatchCallBuilder<C>,
+	AccountIdOf<C>: From<<AccountKeyPairOf<S> as Pair>::Public>,
+{
+	// 1/3 of transaction size is reserved for its overhead and the rest is for the batched
+	// calls. Weights of calls are already including their proof verification weight, so we only
+	// reserve 1/10 of max weight for the `batch_all` and transaction overhead
+	let max_extrinsic_weight = C::max_extrinsic_weight();
+	let limits = BatchLimits {
+		max_calls: match BB::build_batch_call(Vec::new()) {
+			Ok(_) => usize::MAX,
+			Err(_) => 1,
+		},
+		max_size: C::max_extrinsic_size() / 3 * 2,
+		max_weight: max_extrinsic_weight - max_extrinsic_weight / 10,
+	};
+
+	let mut calls_receiver = calls_receiver.fuse();
+	while let Some(first_call) = calls_receiver.next().await {
+		// collect calls of other lanes, unless we already have calls of all lanes
+		let mut ready_lanes = HashSet::new();
+		ready_lanes.insert(first_call.lane_id);
+		let mut calls = vec![first_call];
+		let batch_window = async_std::task::sleep(batch_window).fuse();
+		futures::pin_mut!(batch_window);
+		while ready_lanes.len() < lanes_count {
+			futures::select! {
+				call = calls_receiver.next() => match call {
+					Some(call) => {
+						ready_lanes.insert(call.lane_id);
+						calls.push(call);
+					},
+					None => break,
+				},
+				_ = batch_window => break,
+			}
+		}
+
+		// submit them without blocking the loop, so that we may collect new calls in the
+		// meantime
+		for batch in select_batches(calls, limits) {
+			async_std::task::spawn(submit_batch::<C, B, S, BB>(
+				client.clone(),
+				transaction_params.clone(),
+				batch,
+			));
+		}
+	}
+}
+
+/// Split scheduled calls into batches.
+///
+/// Calls of every batch are having proofs that are generated at the same bridged header. Calls
+/// of lanes with larger priority are placed earlier in the batch and batches with such calls
+/// are spawned before others.
+fn select_batches<H: PartialEq, Call, O, S>(
+	mut calls: Vec<ScheduledCall<H, Call, O, S>>,
+	limits: BatchLimits,
+) -> Vec<Vec<ScheduledCall<H, Call, O, S>>> {
+	// stable sort => lanes with the same priority are ordered by the scheduling time
+	calls.sort_by(|a, b| b.priority.cmp(&a.priority));
+
+	let mut groups: Vec<Vec<ScheduledCall<H, Call, O, S>>> = Vec::new();
+	for call in calls {
+		match groups
+			.iter_mut()
+			.find(|group| group[0].generated_at_header == call.generated_at_header)
+		{
+			Some(group) => group.push(call),
+			None => groups.push(vec![call]),
+		}
+	}
+
+	let mut batches = Vec::new();
+	for group in groups {
+		let mut batch: Vec<ScheduledCall<H, Call, O, S>> = Vec::new();
+		let (mut batch_size, mut batch_weight) = (0u32, 0 as Weight);
+		for call in group {
+			let new_batch_size = batch_size.saturating_add(call.size);
+			let new_batch_weight = batch_weight.saturating_add(call.weight);
+			let fits_batch = batch.len() < limits.max_calls &&
+				new_batch_size <= limits.max_size &&
+				new_batch_weight <= limits.max_weight;
+			if !fits_batch && !batch.is_empty() {
+				batches.push(std::mem::take(&mut batch));
+				batch_size = call.size;
+				batch_weight = call.weight;
+			} else {
+				batch_size = new_batch_size;
+				batch_weight = new_batch_weight;
+			}
+			batch.push(call);
+		}
+		if !batch.is_empty() {
+			batches.push(batch);
+		}
+	}
+
+	batches
+}
+
+/// Submit single transaction and report its tracker to all batched lanes.
+async fn submit_batch<C, B, S, BB>(
+	client: Client<C>,
+	transaction_params: TransactionParams<AccountKeyPairOf<S>>,
+	batch: Vec<ScheduledCallOf<C, B>>,
+) where
+	C: Chain,
+	B: Chain,
+	S: TransactionSignScheme<Chain = C>,
+	BB: BatchCallBuilder<C>,
+	AccountIdOf<C>: From<<AccountKeyPairOf<S> as Pair>::Public>,
+{
+	// lanes of obsolete calls will re-read their state and generate new calls if required
+	let (batch, obsolete_calls) = split_obsolete_calls(batch).await;
+	for call in obsolete_calls {
+		log::trace!(
+			target: "bridge",
+			"Dropped obsolete {} -> {} messages call of lane {:?}",
+			B::NAME,
+			C::NAME,
+			call.lane_id,
+		);
+		let _ = call.tracker_sender.send(Ok(SharedTransactionTracker::lost()));
+	}
+	if batch.is_empty() {
+		return
+	}
+
+	let lanes = batch.iter().map(|call| call.lane_id).collect::<Vec<_>>();
+	let (calls, tracker_senders): (Vec<_>, Vec<_>) =
+		batch.into_iter().map(|call| (call.call, call.tracker_sender)).unzip();
+
+	let result = submit_calls::<C, S, BB>(&client, &transaction_params, calls).await;
+	match result {
+		Ok(tracker) => {
+			log::trace!(
+				target: "bridge",
+				"Submitted {} -> {} messages transaction for lanes: {:?}",
+				B::NAME,
+				C::NAME,
+				lanes,
+			);
+
+			for tracker_sender in tracker_senders {
+				let _ = tracker_sender.send(Ok(tracker.clone()));
+			}
+		},
+		Err(e) => {
+			log::error!(
+				target: "bridge",
+				"Failed to submit {} -> {} messages transaction for lanes {:?}: {:?}",
+				B::NAME,
+				C::NAME,
+				lanes,
+				e,
+			);
+
+			// the original error (that may be a connection error) goes to the first lane
+			let error_string = format!("{:?}", e);
+			let mut error = Some(e);
+			for tracker_sender in tracker_senders {
+				let _ = tracker_sender.send(Err(error
+					.take()
+					.unwrap_or_else(|| SubstrateError::Custom(error_string.clone()))));
+			}
+		},
+	}
+}
+
+/// Split calls into actual calls and calls that have become obsolete while they have been
+/// waiting for submission.
+///
+/// If the check itself has failed, the call is considered actual.
+async fn split_obsolete_calls<H, Call, S>(
+	mut batch: Vec<ScheduledCall<H, Call, ObsoleteCallCheck, S>>,
+) -> (
+	Vec<ScheduledCall<H, Call, ObsoleteCallCheck, S>>,
+	Vec<ScheduledCall<H, Call, ObsoleteCallCheck, S>>,
+) {
+	let obsolete_checks = batch
+		.iter_mut()
+		.map(|call| {
+			std::mem::replace(&mut call.obsolete_check, futures::future::ready(Ok(false)).boxed())
+		})
+		.collect::<Vec<_>>();
+	let obsolete_checks = futures::future::join_all(obsolete_checks).await;
+
+	let mut actual_calls = Vec::with_capacity(batch.len());
+	let mut obsolete_calls = Vec::new();
+	for (call, is_obsolete) in batch.into_iter().zip(obsolete_checks) {
+		match is_obsolete {
+			Ok(true) => obsolete_calls.push(call),
+			Ok(false) => actual_calls.push(call),
+			Err(e) => {
+				log::trace!(
+					target: "bridge",
+					"Failed to check if messages call of lane {:?} is obsolete: {:?}",
+					call.lane_id,
+					e,
+				);
+				actual_calls.push(call);
+			},
+		}
+	}
+
+	(actual_calls, obsolete_calls)
+}
+
+/// Submit transaction with given calls.
+async fn submit_calls<C, S, BB>(
+	client: &Client<C>,
+	transaction_params: &TransactionParams<AccountKeyPairOf<S>>,
+	mut calls: Vec<CallOf<C>>,
+) -> Result<SharedTransactionTracker<C>, SubstrateError>
+where
+	C: Chain,
+	S: TransactionSignScheme<Chain = C>,
+	BB: BatchCallBuilder<C>,
+	AccountIdOf<C>: From<<AccountKeyPairOf<S> as Pair>::Public>,
+{
+	let call = if calls.len() == 1 {
+		calls.remove(0)
+	} else {
+		BB::build_batch_call(calls).map_err(|e| SubstrateError::Custom(format!("{:?}", e)))?
+	};
+
+	let genesis_hash = *client.genesis_hash();
+	let mortality = transaction_params.mortality;
+	let (spec_version, transaction_version) = client.simple_runtime_version().await?;
+	let tx_tracker = client
+		.submit_and_watch_signed_extrinsic(
+			transaction_params.signer.public().into(),
+			SignParam::<S> {
+				spec_version,
+				transaction_version,
+				genesis_hash,
+				signer: transaction_params.signer.clone(),
+			},
+			move |best_block_id, transaction_nonce| {
+				Ok(UnsignedTransaction::new(call.into(), transaction_nonce)
+					.era(TransactionEra::new(best_block_id, mortality)))
+			},
+		)
+		.await?;
+	Ok(SharedTransactionTracker::new(tx_tracker))
+}
+
+/// Returns error that is reported when scheduler background task has stopped.
+fn scheduler_stopped_error<C: Chain>() -> SubstrateError {
+	SubstrateError::Custom(format!("{} messages transactions scheduler has stopped", C::NAME))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const LIMITS: BatchLimits = BatchLimits { max_calls: 3, max_size: 100, max_weight: 100 };
+
+	fn call(
+		lane: u8,
+		priority: u32,
+		generated_at_header: u32,
+		weight: Weight,
+		size: u32,
+	) -> ScheduledCall<u32, (), (), ()> {
+		ScheduledCall {
+			lane_id: [0, 0, 0, lane],
+			priority,
+			generated_at_header,
+			weight,
+			size,
+			call: (),
+			obsolete_check: (),
+			tracker_sender: (),
+		}
+	}
+
+	fn batched_lanes(batches: Vec<Vec<ScheduledCall<u32, (), (), ()>>>) -> Vec<Vec<u8>> {
+		batches
+			.into_iter()
+			.map(|batch| batch.into_iter().map(|call| call.lane_id[3]).collect())
+			.collect()
+	}
+
+	#[test]
+	fn calls_generated_at_the_same_header_are_batched() {
+		assert_eq!(
+			batched_lanes(select_batches(
+				vec![call(1, 0, 10, 10, 10), call(2, 0, 10, 10, 10)],
+				LIMITS,
+			)),
+			vec![vec![1, 2]],
+		);
+	}
+
+	#[test]
+	fn calls_generated_at_different_headers_are_not_batched() {
+		assert_eq!(
+			batched_lanes(select_batches(
+				vec![call(1, 0, 10, 10, 10), call(2, 0, 11, 10, 10)],
+				LIMITS,
+			)),
+			vec![vec![1], vec![2]],
+		);
+	}
+
+	#[test]
+	fn calls_are_ordered_by_lane_priority() {
+		assert_eq!(
+			batched_lanes(select_batches(
+				vec![
+					call(1, 0, 10, 10, 10),
+					call(2, 5, 11, 10, 10),
+					call(3, 10, 10, 10, 10),
+					call(4, 5, 10, 10, 10),
+				],
+				LIMITS,
+			)),
+			vec![vec![3, 4, 1], vec![2]],
+		);
+	}
+
+	#[test]
+	fn batches_are_respecting_limits() {
+		// max calls limit
+		assert_eq!(
+			batched_lanes(select_batches(
+				(1..=4).map(|lane| call(lane, 0, 10, 1, 1)).collect(),
+				LIMITS
+			)),
+			vec![vec![1, 2, 3], vec![4]],
+		);
+		// max size limit
+		assert_eq!(
+			batched_lanes(select_batches(
+				vec![call(1, 0, 10, 1, 60), call(2, 0, 10, 1, 60), call(3, 0, 10, 1, 40)],
+				LIMITS,
+			)),
+			vec![vec![1], vec![2, 3]],
+		);
+		// max weight limit
+		assert_eq!(
+			batched_lanes(select_batches(
+				vec![call(1, 0, 10, 60, 1), call(2, 0, 10, 60, 1), call(3, 0, 10, 40, 1)],
+				LIMITS,
+			)),
+			vec![vec![1], vec![2, 3]],
+		);
+	}
+
+	#[test]
+	fn oversized_call_is_submitted_alone() {
+		assert_eq!(
+			batched_lanes(select_batches(
+				vec![call(1, 0, 10, 1, 1), call(2, 0, 10, 1, 200), call(3, 0, 10, 1, 1)],
+				LIMITS,
+			)),
+			vec![vec![1], vec![2], vec![3]],
+		);
+	}
+
+	#[test]
+	fn obsolete_calls_are_dropped_from_batch() {
+		let scheduled_call = |lane, is_obsolete: Result<bool, SubstrateError>| ScheduledCall {
+			lane_id: [0, 0, 0, lane],
+			priority: 0,
+			generated_at_header: 10u32,
+			weight: 10,
+			size: 10,
+			call: (),
+			obsolete_check: futures::future::ready(is_obsolete).boxed(),
+			tracker_sender: (),
+		};
+		let lanes = |calls: Vec<ScheduledCall<u32, (), ObsoleteCallCheck, ()>>| {
+			calls.into_iter().map(|call| call.lane_id[3]).collect::<Vec<_>>()
+		};
+
+		let (actual_calls, obsolete_calls) = async_std::task::block_on(split_obsolete_calls(vec![
+			scheduled_call(1, Ok(false)),
+			scheduled_call(2, Ok(true)),
+			scheduled_call(3, Err(SubstrateError::Custom("test".into()))),
+		]));
+		assert_eq!(lanes(actual_calls), vec![1, 3]);
+		assert_eq!(lanes(obsolete_calls), vec![2]);
+	}
+
+	#[test]
+	fn calls_are_not_batched_if_batch_calls_are_unsupported() {
+		assert_eq!(
+			batched_lanes(select_batches(
+				vec![call(1, 0, 10, 10, 10), call(2, 0, 10, 10, 10)],
+				BatchLimits { max_calls: 1, ..LIMITS },
+			)),
+			vec![vec![1], vec![2]],
+		);
+	}
+}
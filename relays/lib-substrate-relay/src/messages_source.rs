@@ -22,6 +22,7 @@ use crate::{
 	messages_lane::{
 		MessageLaneAdapter, ReceiveMessagesDeliveryProofCallBuilder, SubstrateMessageLane,
 	},
+	messages_scheduler::{MessagesConfirmationScheduler, SharedTransactionTracker},
 	messages_target::SubstrateMessagesDeliveryProof,
 	on_demand::OnDemandRelay,
 	TransactionParams,
@@ -30,9 +31,12 @@ use crate::{
 use async_std::sync::Arc;
 use async_trait::async_trait;
 use bp_messages::{
-	storage_keys::{operating_mode_key, outbound_lane_data_key},
+	storage_keys::{
+		lane_operating_mode_key, operating_mode_key, outbound_lane_data_key, storage_version_key,
+	},
 	InboundMessageDetails, LaneId, MessageData, MessageNonce, MessagePayload,
 	MessagesOperatingMode, OutboundLaneData, OutboundMessageDetails, UnrewardedRelayersState,
+	MESSAGES_WIRE_FORMAT_VERSION,
 };
 use bp_runtime::{messages::DispatchFeePayment, BasicOperatingMode, HeaderIdProvider};
 use bridge_runtime_common::messages::{
@@ -40,6 +44,7 @@ use bridge_runtime_common::messages::{
 };
 use codec::{Decode, Encode};
 use frame_support::weights::Weight;
+use futures::FutureExt;
 use messages_relay::{
 	message_lane::{MessageLane, SourceHeaderIdOf, TargetHeaderIdOf},
 	message_lane_loop::{
@@ -48,10 +53,11 @@ use messages_relay::{
 	},
 };
 use num_traits::{Bounded, Zero};
+use pallet_bridge_messages::WeightInfoExt;
 use relay_substrate_client::{
-	AccountIdOf, AccountKeyPairOf, BalanceOf, BlockNumberOf, Chain, ChainWithMessages, Client,
-	Error as SubstrateError, HashOf, HeaderIdOf, IndexOf, SignParam, TransactionEra,
-	TransactionSignScheme, TransactionTracker, UnsignedTransaction,
+	AccountIdOf, AccountKeyPairOf, BalanceOf, BlockNumberOf, CallOf, Chain, ChainWithMessages,
+	Client, Error as SubstrateError, HashOf, HeaderIdOf, IndexOf, SignParam, TransactionEra,
+	TransactionSignScheme, UnsignedTransaction, WeightInfoOf,
 };
 use relay_utils::{relay_loop::Client as RelayClient, HeaderId};
 use sp_core::{Bytes, Pair};
@@ -71,6 +77,7 @@ pub struct SubstrateMessagesSource<P: SubstrateMessageLane> {
 	lane_id: LaneId,
 	transaction_params: TransactionParams<AccountKeyPairOf<P::SourceTransactionSignScheme>>,
 	target_to_source_headers_relay: Option<Arc<dyn OnDemandRelay<BlockNumberOf<P::TargetChain>>>>,
+	confirmation_scheduler: Option<MessagesConfirmationScheduler<P>>,
 }
 
 impl<P: SubstrateMessageLane> SubstrateMessagesSource<P> {
@@ -83,6 +90,7 @@ impl<P: SubstrateMessageLane> SubstrateMessagesSource<P> {
 		target_to_source_headers_relay: Option<
 			Arc<dyn OnDemandRelay<BlockNumberOf<P::TargetChain>>>,
 		>,
+		confirmation_scheduler: Option<MessagesConfirmationScheduler<P>>,
 	) -> Self {
 		SubstrateMessagesSource {
 			source_client,
@@ -90,6 +98,7 @@ impl<P: SubstrateMessageLane> SubstrateMessagesSource<P> {
 			lane_id,
 			transaction_params,
 			target_to_source_headers_relay,
+			confirmation_scheduler,
 		}
 	}
 
@@ -111,7 +120,11 @@ impl<P: SubstrateMessageLane> SubstrateMessagesSource<P> {
 
 	/// Ensure that the messages pallet at source chain is active.
 	async fn ensure_pallet_active(&self) -> Result<(), SubstrateError> {
-		ensure_messages_pallet_active::<P::SourceChain, P::TargetChain>(&self.source_client).await
+		ensure_messages_pallet_active::<P::SourceChain, P::TargetChain>(
+			&self.source_client,
+			self.lane_id,
+		)
+		.await
 	}
 }
 
@@ -123,6 +136,7 @@ impl<P: SubstrateMessageLane> Clone for SubstrateMessagesSource<P> {
 			lane_id: self.lane_id,
 			transaction_params: self.transaction_params.clone(),
 			target_to_source_headers_relay: self.target_to_source_headers_relay.clone(),
+			confirmation_scheduler: self.confirmation_scheduler.clone(),
 		}
 	}
 }
@@ -144,7 +158,7 @@ where
 		From<<AccountKeyPairOf<P::SourceTransactionSignScheme> as Pair>::Public>,
 	P::SourceTransactionSignScheme: TransactionSignScheme<Chain = P::SourceChain>,
 {
-	type TransactionTracker = TransactionTracker<P::SourceChain, Client<P::SourceChain>>;
+	type TransactionTracker = SharedTransactionTracker<P::SourceChain>;
 
 	async fn state(&self) -> Result<SourceClientState<MessageLaneAdapter<P>>, SubstrateError> {
 		// we can't continue to deliver confirmations if source node is out of sync, because
@@ -338,9 +352,36 @@ where
 
 	async fn submit_messages_receiving_proof(
 		&self,
-		_generated_at_block: TargetHeaderIdOf<MessageLaneAdapter<P>>,
+		generated_at_block: TargetHeaderIdOf<MessageLaneAdapter<P>>,
 		proof: <MessageLaneAdapter<P> as MessageLane>::MessagesReceivingProof,
 	) -> Result<Self::TransactionTracker, SubstrateError> {
+		if let Some(ref confirmation_scheduler) = self.confirmation_scheduler {
+			let weight = WeightInfoOf::<P::SourceChain>::receive_messages_delivery_proof_weight(
+				&proof.1,
+				&proof.0,
+				P::SourceChain::MAX_MESSAGE_RESPONSE_SIZE,
+				P::SourceChain::DB_WEIGHT,
+			);
+			// the call is obsolete if all messages have already been confirmed
+			let last_delivered_nonce = proof.0.last_delivered_nonce;
+			let source_client = self.source_client.clone();
+			let outbound_lane_data_key = outbound_lane_data_key(
+				P::TargetChain::WITH_CHAIN_MESSAGES_PALLET_NAME,
+				&self.lane_id,
+			);
+			let obsolete_check = async move {
+				let outbound_lane_data: Option<OutboundLaneData> =
+					source_client.storage_value(outbound_lane_data_key, None).await?;
+				Ok(outbound_lane_data.map(|data| data.latest_received_nonce).unwrap_or(0) >=
+					last_delivered_nonce)
+			}
+			.boxed();
+			let call = make_messages_delivery_proof_call::<P>(proof, true);
+			return confirmation_scheduler
+				.submit(self.lane_id, generated_at_block, weight, call, obsolete_check)
+				.await
+		}
+
 		let genesis_hash = *self.source_client.genesis_hash();
 		let transaction_params = self.transaction_params.clone();
 		let (spec_version, transaction_version) =
@@ -365,6 +406,7 @@ where
 				},
 			)
 			.await
+			.map(SharedTransactionTracker::new)
 	}
 
 	async fn require_target_header_on_source(&self, id: TargetHeaderIdOf<MessageLaneAdapter<P>>) {
@@ -407,24 +449,46 @@ where
 	}
 }
 
-/// Ensure that the messages pallet at source chain is active.
+/// Ensure that the messages pallet and the given lane at source chain are active.
+///
+/// The messages pallet must also support the messages wire format of this relay. Until both
+/// chains are upgraded to the same format, the relay refuses to serve the lane.
 pub(crate) async fn ensure_messages_pallet_active<AtChain, WithChain>(
 	client: &Client<AtChain>,
+	lane_id: LaneId,
 ) -> Result<(), SubstrateError>
 where
 	AtChain: ChainWithMessages,
 	WithChain: ChainWithMessages,
 {
+	let storage_version: u16 = client
+		.storage_value(storage_version_key(WithChain::WITH_CHAIN_MESSAGES_PALLET_NAME), None)
+		.await?
+		.unwrap_or(0);
+	if storage_version < MESSAGES_WIRE_FORMAT_VERSION {
+		return Err(SubstrateError::UnsupportedMessagesWireFormat(storage_version))
+	}
+
+	let halted_mode = Some(MessagesOperatingMode::Basic(BasicOperatingMode::Halted));
+
 	let operating_mode = client
 		.storage_value(operating_mode_key(WithChain::WITH_CHAIN_MESSAGES_PALLET_NAME), None)
 		.await?;
-	let is_halted =
-		operating_mode == Some(MessagesOperatingMode::Basic(BasicOperatingMode::Halted));
-	if is_halted {
-		Err(SubstrateError::BridgePalletIsHalted)
-	} else {
-		Ok(())
+	if operating_mode == halted_mode {
+		return Err(SubstrateError::BridgePalletIsHalted)
 	}
+
+	let lane_operating_mode = client
+		.storage_value(
+			lane_operating_mode_key(WithChain::WITH_CHAIN_MESSAGES_PALLET_NAME, &lane_id),
+			None,
+		)
+		.await?;
+	if lane_operating_mode == halted_mode {
+		return Err(SubstrateError::BridgeLaneIsHalted(lane_id))
+	}
+
+	Ok(())
 }
 
 /// Make messages delivery proof transaction from given proof.
@@ -438,14 +502,21 @@ fn make_messages_delivery_proof_transaction<P: SubstrateMessageLane>(
 where
 	P::SourceTransactionSignScheme: TransactionSignScheme<Chain = P::SourceChain>,
 {
-	let call =
-		P::ReceiveMessagesDeliveryProofCallBuilder::build_receive_messages_delivery_proof_call(
-			proof, trace_call,
-		);
+	let call = make_messages_delivery_proof_call::<P>(proof, trace_call);
 	Ok(UnsignedTransaction::new(call.into(), transaction_nonce)
 		.era(TransactionEra::new(source_best_block_id, source_transaction_params.mortality)))
 }
 
+/// Make messages delivery proof call from given proof.
+fn make_messages_delivery_proof_call<P: SubstrateMessageLane>(
+	proof: SubstrateMessagesDeliveryProof<P::TargetChain>,
+	trace_call: bool,
+) -> CallOf<P::SourceChain> {
+	P::ReceiveMessagesDeliveryProofCallBuilder::build_receive_messages_delivery_proof_call(
+		proof, trace_call,
+	)
+}
+
 /// Prepare 'dummy' messages delivery proof that will compose the delivery confirmation transaction.
 ///
 /// We don't care about proof actually being the valid proof, because its validity doesn't
@@ -21,6 +21,7 @@
 use crate::{
 	messages_lane::{MessageLaneAdapter, ReceiveMessagesProofCallBuilder, SubstrateMessageLane},
 	messages_metrics::StandaloneMessagesMetrics,
+	messages_scheduler::{MessagesDeliveryScheduler, SharedTransactionTracker},
 	messages_source::{ensure_messages_pallet_active, read_client_state, SubstrateMessagesProof},
 	on_demand::OnDemandRelay,
 	TransactionParams,
@@ -29,23 +30,25 @@ use crate::{
 use async_std::sync::Arc;
 use async_trait::async_trait;
 use bp_messages::{
-	storage_keys::inbound_lane_data_key, total_unrewarded_messages, InboundLaneData, LaneId,
-	MessageNonce, UnrewardedRelayersState,
+	storage_keys::{inbound_lane_data_key, inbound_message_response_key},
+	total_unrewarded_messages, InboundLaneData, LaneId, MessageNonce, UnrewardedRelayersState,
 };
 use bridge_runtime_common::messages::{
 	source::FromBridgedChainMessagesDeliveryProof, target::FromBridgedChainMessagesProof,
 };
 use codec::Encode;
 use frame_support::weights::{Weight, WeightToFee};
+use futures::FutureExt;
 use messages_relay::{
 	message_lane::{MessageLane, SourceHeaderIdOf, TargetHeaderIdOf},
 	message_lane_loop::{NoncesSubmitArtifacts, TargetClient, TargetClientState},
 };
 use num_traits::{Bounded, Zero};
+use pallet_bridge_messages::WeightInfoExt;
 use relay_substrate_client::{
-	AccountIdOf, AccountKeyPairOf, BalanceOf, BlockNumberOf, Chain, ChainWithMessages, Client,
-	Error as SubstrateError, HashOf, HeaderIdOf, IndexOf, SignParam, TransactionEra,
-	TransactionSignScheme, TransactionTracker, UnsignedTransaction, WeightToFeeOf,
+	AccountIdOf, AccountKeyPairOf, BalanceOf, BlockNumberOf, CallOf, Chain, ChainWithMessages,
+	Client, Error as SubstrateError, HashOf, HeaderIdOf, IndexOf, SignParam, TransactionEra,
+	TransactionSignScheme, UnsignedTransaction, WeightInfoOf, WeightToFeeOf,
 };
 use relay_utils::{relay_loop::Client as RelayClient, HeaderId};
 use sp_core::{Bytes, Pair};
@@ -65,6 +68,7 @@ pub struct SubstrateMessagesTarget<P: SubstrateMessageLane> {
 	transaction_params: TransactionParams<AccountKeyPairOf<P::TargetTransactionSignScheme>>,
 	metric_values: StandaloneMessagesMetrics<P::SourceChain, P::TargetChain>,
 	source_to_target_headers_relay: Option<Arc<dyn OnDemandRelay<BlockNumberOf<P::SourceChain>>>>,
+	delivery_scheduler: Option<MessagesDeliveryScheduler<P>>,
 }
 
 impl<P: SubstrateMessageLane> SubstrateMessagesTarget<P> {
@@ -79,6 +83,7 @@ impl<P: SubstrateMessageLane> SubstrateMessagesTarget<P> {
 		source_to_target_headers_relay: Option<
 			Arc<dyn OnDemandRelay<BlockNumberOf<P::SourceChain>>>,
 		>,
+		delivery_scheduler: Option<MessagesDeliveryScheduler<P>>,
 	) -> Self {
 		SubstrateMessagesTarget {
 			target_client,
@@ -88,6 +93,7 @@ impl<P: SubstrateMessageLane> SubstrateMessagesTarget<P> {
 			transaction_params,
 			metric_values,
 			source_to_target_headers_relay,
+			delivery_scheduler,
 		}
 	}
 
@@ -109,7 +115,11 @@ impl<P: SubstrateMessageLane> SubstrateMessagesTarget<P> {
 
 	/// Ensure that the messages pallet at target chain is active.
 	async fn ensure_pallet_active(&self) -> Result<(), SubstrateError> {
-		ensure_messages_pallet_active::<P::TargetChain, P::SourceChain>(&self.target_client).await
+		ensure_messages_pallet_active::<P::TargetChain, P::SourceChain>(
+			&self.target_client,
+			self.lane_id,
+		)
+		.await
 	}
 }
 
@@ -123,6 +133,7 @@ impl<P: SubstrateMessageLane> Clone for SubstrateMessagesTarget<P> {
 			transaction_params: self.transaction_params.clone(),
 			metric_values: self.metric_values.clone(),
 			source_to_target_headers_relay: self.source_to_target_headers_relay.clone(),
+			delivery_scheduler: self.delivery_scheduler.clone(),
 		}
 	}
 }
@@ -145,7 +156,7 @@ where
 	P::TargetTransactionSignScheme: TransactionSignScheme<Chain = P::TargetChain>,
 	BalanceOf<P::SourceChain>: TryFrom<BalanceOf<P::TargetChain>>,
 {
-	type TransactionTracker = TransactionTracker<P::TargetChain, Client<P::TargetChain>>;
+	type TransactionTracker = SharedTransactionTracker<P::TargetChain>;
 
 	async fn state(&self) -> Result<TargetClientState<MessageLaneAdapter<P>>, SubstrateError> {
 		// we can't continue to deliver confirmations if source node is out of sync, because
@@ -228,9 +239,26 @@ where
 			P::SourceChain::WITH_CHAIN_MESSAGES_PALLET_NAME,
 			&self.lane_id,
 		);
+		// dispatch responses of all unrewarded messages are proved along with the lane state
+		let unrewarded_nonces = self
+			.inbound_lane_data(id)
+			.await?
+			.and_then(|data| {
+				Some(data.relayers.front()?.messages.begin..=data.relayers.back()?.messages.end)
+			})
+			.unwrap_or(1..=0);
+		let storage_keys = std::iter::once(inbound_data_key)
+			.chain(unrewarded_nonces.map(|nonce| {
+				inbound_message_response_key(
+					P::SourceChain::WITH_CHAIN_MESSAGES_PALLET_NAME,
+					&self.lane_id,
+					nonce,
+				)
+			}))
+			.collect();
 		let proof = self
 			.target_client
-			.prove_storage(vec![inbound_data_key], id.1)
+			.prove_storage(storage_keys, id.1)
 			.await?
 			.iter_nodes()
 			.collect();
@@ -244,10 +272,44 @@ where
 
 	async fn submit_messages_proof(
 		&self,
-		_generated_at_header: SourceHeaderIdOf<MessageLaneAdapter<P>>,
+		generated_at_header: SourceHeaderIdOf<MessageLaneAdapter<P>>,
 		nonces: RangeInclusive<MessageNonce>,
 		proof: <MessageLaneAdapter<P> as MessageLane>::MessagesProof,
 	) -> Result<NoncesSubmitArtifacts<Self::TransactionTracker>, SubstrateError> {
+		if let Some(ref delivery_scheduler) = self.delivery_scheduler {
+			// the scheduler needs full weight of the call, not only the messages dispatch weight
+			let messages_count = nonces.end() - nonces.start() + 1;
+			let weight = WeightInfoOf::<P::TargetChain>::receive_messages_proof_weight(
+				&proof.1,
+				messages_count as _,
+				proof.0,
+			);
+			let call = make_messages_delivery_call::<P>(
+				self.relayer_id_at_source.clone(),
+				nonces.clone(),
+				proof,
+				true,
+			);
+			// the call is obsolete if (some of) messages have already been delivered
+			let first_nonce = *nonces.start();
+			let target_client = self.target_client.clone();
+			let inbound_lane_data_key = inbound_lane_data_key(
+				P::SourceChain::WITH_CHAIN_MESSAGES_PALLET_NAME,
+				&self.lane_id,
+			);
+			let obsolete_check = async move {
+				let inbound_lane_data: Option<InboundLaneData<AccountIdOf<P::SourceChain>>> =
+					target_client.storage_value(inbound_lane_data_key, None).await?;
+				Ok(inbound_lane_data.map(|data| data.last_delivered_nonce()).unwrap_or(0) >=
+					first_nonce)
+			}
+			.boxed();
+			let tx_tracker = delivery_scheduler
+				.submit(self.lane_id, generated_at_header, weight, call, obsolete_check)
+				.await?;
+			return Ok(NoncesSubmitArtifacts { nonces, tx_tracker })
+		}
+
 		let genesis_hash = *self.target_client.genesis_hash();
 		let transaction_params = self.transaction_params.clone();
 		let relayer_id_at_source = self.relayer_id_at_source.clone();
@@ -277,7 +339,7 @@ where
 				},
 			)
 			.await?;
-		Ok(NoncesSubmitArtifacts { nonces, tx_tracker })
+		Ok(NoncesSubmitArtifacts { nonces, tx_tracker: SharedTransactionTracker::new(tx_tracker) })
 	}
 
 	async fn require_source_header_on_target(&self, id: SourceHeaderIdOf<MessageLaneAdapter<P>>) {
@@ -436,17 +498,27 @@ fn make_messages_delivery_transaction<P: SubstrateMessageLane>(
 where
 	P::TargetTransactionSignScheme: TransactionSignScheme<Chain = P::TargetChain>,
 {
+	let call = make_messages_delivery_call::<P>(relayer_id_at_source, nonces, proof, trace_call);
+	Ok(UnsignedTransaction::new(call.into(), transaction_nonce)
+		.era(TransactionEra::new(target_best_block_id, target_transaction_params.mortality)))
+}
+
+/// Make messages delivery call from given proof.
+fn make_messages_delivery_call<P: SubstrateMessageLane>(
+	relayer_id_at_source: AccountIdOf<P::SourceChain>,
+	nonces: RangeInclusive<MessageNonce>,
+	proof: SubstrateMessagesProof<P::SourceChain>,
+	trace_call: bool,
+) -> CallOf<P::TargetChain> {
 	let messages_count = nonces.end() - nonces.start() + 1;
 	let dispatch_weight = proof.0;
-	let call = P::ReceiveMessagesProofCallBuilder::build_receive_messages_proof_call(
+	P::ReceiveMessagesProofCallBuilder::build_receive_messages_proof_call(
 		relayer_id_at_source,
 		proof,
 		messages_count as _,
 		dispatch_weight,
 		trace_call,
-	);
-	Ok(UnsignedTransaction::new(call.into(), transaction_nonce)
-		.era(TransactionEra::new(target_best_block_id, target_transaction_params.mortality)))
+	)
 }
 
 /// Prepare 'dummy' messages proof that will compose the delivery transaction.
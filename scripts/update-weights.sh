@@ -2,6 +2,9 @@
 #
 # Runtime benchmarks for the `pallet-bridge-messages` and `pallet-bridge-grandpa` pallets.
 #
+# Reference weights of bridge pallets are generated using the Millau runtime. Weights of
+# Pass3d and Pass3dt runtimes are generated using these runtimes themselves.
+#
 # Run this script from root of the repo.
 
 set -eux
@@ -53,3 +56,75 @@ time cargo run --release -p millau-bridge-node --features=runtime-benchmarks --
 	--heap-pages=4096 \
 	--output=./modules/relayers/src/weights.rs \
 	--template=./.maintain/millau-weight-template.hbs
+
+time cargo run --release -p pass3d-bridge-node --features=runtime-benchmarks -- benchmark pallet \
+	--chain=dev \
+	--steps=50 \
+	--repeat=20 \
+	--pallet=pallet_bridge_grandpa \
+	--extrinsic=* \
+	--execution=wasm \
+	--wasm-execution=Compiled \
+	--heap-pages=4096 \
+	--output=./bin/pass3d/runtime/src/weights/pallet_bridge_grandpa.rs \
+	--template=./.maintain/runtime-weight-template.hbs
+
+time cargo run --release -p pass3d-bridge-node --features=runtime-benchmarks -- benchmark pallet \
+	--chain=dev \
+	--steps=50 \
+	--repeat=20 \
+	--pallet=pallet_bridge_messages \
+	--extrinsic=* \
+	--execution=wasm \
+	--wasm-execution=Compiled \
+	--heap-pages=4096 \
+	--output=./bin/pass3d/runtime/src/weights/pallet_bridge_messages.rs \
+	--template=./.maintain/runtime-weight-template.hbs
+
+time cargo run --release -p pass3d-bridge-node --features=runtime-benchmarks -- benchmark pallet \
+	--chain=dev \
+	--steps=50 \
+	--repeat=20 \
+	--pallet=pallet_bridge_relayers \
+	--extrinsic=* \
+	--execution=wasm \
+	--wasm-execution=Compiled \
+	--heap-pages=4096 \
+	--output=./bin/pass3d/runtime/src/weights/pallet_bridge_relayers.rs \
+	--template=./.maintain/runtime-weight-template.hbs
+
+time cargo run --release -p pass3dt-bridge-node --features=runtime-benchmarks -- benchmark pallet \
+	--chain=dev \
+	--steps=50 \
+	--repeat=20 \
+	--pallet=pallet_bridge_grandpa \
+	--extrinsic=* \
+	--execution=wasm \
+	--wasm-execution=Compiled \
+	--heap-pages=4096 \
+	--output=./bin/pass3dt/runtime/src/weights/pallet_bridge_grandpa.rs \
+	--template=./.maintain/runtime-weight-template.hbs
+
+time cargo run --release -p pass3dt-bridge-node --features=runtime-benchmarks -- benchmark pallet \
+	--chain=dev \
+	--steps=50 \
+	--repeat=20 \
+	--pallet=pallet_bridge_messages \
+	--extrinsic=* \
+	--execution=wasm \
+	--wasm-execution=Compiled \
+	--heap-pages=4096 \
+	--output=./bin/pass3dt/runtime/src/weights/pallet_bridge_messages.rs \
+	--template=./.maintain/runtime-weight-template.hbs
+
+time cargo run --release -p pass3dt-bridge-node --features=runtime-benchmarks -- benchmark pallet \
+	--chain=dev \
+	--steps=50 \
+	--repeat=20 \
+	--pallet=pallet_bridge_relayers \
+	--extrinsic=* \
+	--execution=wasm \
+	--wasm-execution=Compiled \
+	--heap-pages=4096 \
+	--output=./bin/pass3dt/runtime/src/weights/pallet_bridge_relayers.rs \
+	--template=./.maintain/runtime-weight-template.hbs